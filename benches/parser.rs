@@ -0,0 +1,48 @@
+//! Time `TegraStats::parse` on one line per board family.
+//!
+//! cargo bench --bench parser                          # tokenizer
+//! cargo bench --bench parser --features regex-parser  # and `parse_regex`
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use anyhow::Result;
+use jetsonscope::parser::TegraStats;
+
+const LINES: [(&str, &str); 3] = [
+    (
+        "orin-nano",
+        "11-30-2025 13:26:01 RAM 2461/7620MB (lfb 3x2MB) SWAP 1243/3810MB (cached 5MB) CPU [19%@729,14%@729,22%@729,8%@729,15%@729,17%@729] EMC_FREQ 4%@2133 GR3D_FREQ 0%@[305] NVDEC off NVJPG off NVJPG1 off VIC off OFA off APE 200 cpu@46.531C soc2@47.312C soc0@46.593C gpu@48.218C tj@48.843C soc1@48.843C VDD_IN 5704mW/5704mW VDD_CPU_GPU_CV 831mW/831mW VDD_SOC 1624mW/1624mW",
+    ),
+    (
+        "xavier-nx",
+        "RAM 4181/7771MB (lfb 8x4MB) SWAP 0/3885MB (cached 0MB) CPU [10%@1190,0%@1190,1%@1190,0%@1190,5%@1190,1%@1190] EMC_FREQ 15%@1600 GR3D_FREQ 0% PLL@42.906C Tdiode@43.25C Tboard@36C GPU@41.75C BCPU@42.5C MCPU@47.5C thermal@42.425C VDD_SYS_GPU 47mW/0mW VDD_SYS_SOC 813mW/207mW VDD_4V0_WIFI 495mW/0mW VDD_IN 3539mW/1422mW VDD_SYS_CPU 125mW/104mW",
+    ),
+    (
+        "nano",
+        "RAM 2011/3956MB (lfb 4x2MB) SWAP 0/1978MB (cached 0MB) IRAM 0/252kB(lfb 252kB) CPU [15%@1479,8%@1479,12%@1479,6%@1479] EMC_FREQ 3%@1600 GR3D_FREQ 0%@921 APE 25 PLL@34.5C CPU@37C PMIC@50C GPU@35C AO@43C thermal@36.25C POM_5V_IN 2338/2338 POM_5V_GPU 40/40 POM_5V_CPU 443/443",
+    ),
+];
+
+const WARMUP: u32 = 1_000;
+const ROUNDS: u32 = 20_000;
+
+fn main() {
+    for (board, line) in LINES {
+        bench("tokenizer", board, line, TegraStats::parse);
+        #[cfg(feature = "regex-parser")]
+        bench("regex", board, line, TegraStats::parse_regex);
+    }
+}
+
+fn bench(parser: &str, board: &str, line: &str, parse: fn(&str) -> Result<TegraStats>) {
+    for _ in 0..WARMUP {
+        black_box(parse(black_box(line)).unwrap());
+    }
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        black_box(parse(black_box(line)).unwrap());
+    }
+    let per_line = start.elapsed() / ROUNDS;
+    println!("{parser:<9} {board:<9} {:>7} ns/line", per_line.as_nanos());
+}
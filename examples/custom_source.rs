@@ -0,0 +1,75 @@
+//! Feed a carrier-board MCU's readings through the JetsonScope collector.
+//!
+//! The MCU is expected to print one line per sample, e.g.
+//! `VDD_IN=5120 VDD_FAN=310 temp.board=41.5` (rails in mW, temperatures in C).
+//!
+//! cargo run --example custom_source -- /dev/ttyTHS1
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Lines};
+
+use jetsonscope::collector::{start_collector, CollectorMessage, CollectorMode, CustomSource, StatsSource};
+use jetsonscope::parser::{PowerRail, TegraStats};
+
+struct McuSource {
+    path: String,
+    lines: Lines<BufReader<File>>,
+}
+
+impl StatsSource for McuSource {
+    fn label(&self) -> String {
+        format!("mcu {}", self.path)
+    }
+
+    fn next(&mut self) -> anyhow::Result<TegraStats> {
+        let line = self
+            .lines
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("{} closed", self.path))??;
+        let mut stats = TegraStats {
+            raw: line.clone(),
+            ..Default::default()
+        };
+        for (key, value) in line.split_whitespace().filter_map(|kv| kv.split_once('=')) {
+            if let Some(sensor) = key.strip_prefix("temp.") {
+                stats.temps.insert(sensor.to_string(), value.parse()?);
+            } else {
+                let mw: u32 = value.parse()?;
+                stats.power.insert(
+                    key.to_string(),
+                    PowerRail {
+                        current_mw: mw,
+                        average_mw: mw,
+                    },
+                );
+            }
+        }
+        Ok(stats)
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let path = std::env::args().nth(1).unwrap_or_else(|| "/dev/ttyTHS1".to_string());
+    let lines = BufReader::new(File::open(&path)?).lines();
+    let source = McuSource { path, lines };
+    let collector = start_collector(CollectorMode::Custom(CustomSource::new(source)));
+    for msg in collector.rx.iter() {
+        match msg {
+            CollectorMessage::SourceLabel(label) => println!("Source: {label}"),
+            CollectorMessage::Stats(stats) => {
+                let mut rails: Vec<_> = stats.power.iter().collect();
+                rails.sort_by(|a, b| a.0.cmp(b.0));
+                for (rail, power) in rails {
+                    print!("{rail} {}mW  ", power.current_mw);
+                }
+                println!();
+            }
+            CollectorMessage::Error(err) => {
+                eprintln!("{err}");
+                break;
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,18 @@
+//! Generates jetsonscope.h from the `extern "C"` functions in src/lib.rs into
+//! OUT_DIR; a test checks that the checked-in include/jetsonscope.h matches it.
+
+use std::path::PathBuf;
+
+fn main() {
+    let dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    let config = cbindgen::Config::from_file(dir.join("cbindgen.toml")).expect("ffi/cbindgen.toml");
+    cbindgen::Builder::new()
+        .with_crate(&dir)
+        .with_config(config)
+        .generate()
+        .expect("generate jetsonscope.h")
+        .write_to_file(out.join("jetsonscope.h"));
+}
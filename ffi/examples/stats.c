@@ -0,0 +1,28 @@
+/* Print the daemon's latest sample and, with two arguments, set a control.
+ *
+ *   cargo build --release -p jetsonscope-ffi
+ *   cc ffi/examples/stats.c -Iffi/include -Ltarget/release -ljetsonscope_ffi -o jscope-ffi-stats
+ *   LD_LIBRARY_PATH=target/release ./jscope-ffi-stats [control value]
+ */
+#include <stdio.h>
+
+#include "jetsonscope.h"
+
+int main(int argc, char **argv) {
+    char *json = jetsonscope_get_stats_json();
+    if (json == NULL) {
+        fprintf(stderr, "stats: %s\n", jetsonscope_last_error());
+        return 1;
+    }
+    printf("%s\n", json);
+    jetsonscope_free_string(json);
+
+    if (argc == 3) {
+        int rc = jetsonscope_set_control(argv[1], argv[2]);
+        if (rc != JETSONSCOPE_OK) {
+            fprintf(stderr, "set %s=%s: %s (%d)\n", argv[1], argv[2], jetsonscope_last_error(), rc);
+            return 1;
+        }
+    }
+    return 0;
+}
@@ -0,0 +1,57 @@
+/* C API for jscoped: link with -ljetsonscope_ffi. */
+
+#ifndef JETSONSCOPE_H
+#define JETSONSCOPE_H
+
+/* Generated by cbindgen from ffi/src/lib.rs (copied from the build's OUT_DIR); do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The call succeeded.
+#define JETSONSCOPE_OK 0
+
+// A NULL or non-UTF-8 argument.
+#define JETSONSCOPE_ERR_ARGUMENT -1
+
+// The daemon could not be reached or its reply could not be read.
+#define JETSONSCOPE_ERR_CONNECTION -2
+
+// The daemon refused the request (unknown control, bad value, auth).
+#define JETSONSCOPE_ERR_DAEMON -3
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// The daemon's latest sample as JSON (the `data` of GetStats: `temps`,
+// `throttling`, `power`, `cpus`, ...). Returns NULL on error or while the
+// daemon has no sample yet; see `jetsonscope_last_error`. Free the string
+// with `jetsonscope_free_string`.
+char *jetsonscope_get_stats_json(void);
+
+// Set `control` (e.g. "fan", "nvpmodel", "gpu_max_freq") to `value`, with
+// the token from JETSONSCOPE_AUTH_TOKEN. Returns `JETSONSCOPE_OK` or one of
+// the `JETSONSCOPE_ERR_*` codes.
+//
+// # Safety
+// `control` and `value` must be NULL or NUL-terminated strings.
+int jetsonscope_set_control(const char *control, const char *value);
+
+// Message of the last failed call on this thread, or NULL. The string
+// stays valid until the next failing call on the same thread.
+const char *jetsonscope_last_error(void);
+
+// Free a string returned by this library; NULL is ignored.
+//
+// # Safety
+// `s` must be NULL or a pointer from `jetsonscope_get_stats_json`, freed once.
+void jetsonscope_free_string(char *s);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* JETSONSCOPE_H */
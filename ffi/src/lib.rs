@@ -0,0 +1,156 @@
+//! C API for jscoped, for programs on the same Jetson (e.g. a DeepStream
+//! pipeline reacting to throttling). Every call opens its own connection
+//! with `client::Client::connect`, so JETSONSCOPE_SOCKET_PATH,
+//! JETSONSCOPE_PROTO and JETSONSCOPE_AUTH_TOKEN apply as for jscopectl.
+//!
+//! The header, include/jetsonscope.h, is generated from this file by build.rs.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::ptr;
+
+use jetsonscope::client::Client;
+use jetsonscope::protocol::ErrorInfo;
+
+/// The call succeeded.
+pub const JETSONSCOPE_OK: c_int = 0;
+/// A NULL or non-UTF-8 argument.
+pub const JETSONSCOPE_ERR_ARGUMENT: c_int = -1;
+/// The daemon could not be reached or its reply could not be read.
+pub const JETSONSCOPE_ERR_CONNECTION: c_int = -2;
+/// The daemon refused the request (unknown control, bad value, auth).
+pub const JETSONSCOPE_ERR_DAEMON: c_int = -3;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(message));
+}
+
+/// Error code for a failed request, recording its message.
+fn fail(err: anyhow::Error) -> c_int {
+    set_last_error(format!("{err:#}"));
+    if err.downcast_ref::<ErrorInfo>().is_some() {
+        JETSONSCOPE_ERR_DAEMON
+    } else {
+        JETSONSCOPE_ERR_CONNECTION
+    }
+}
+
+/// `s` as UTF-8, or `None` (with the last error set) when NULL or invalid.
+///
+/// # Safety
+/// `s` must be NULL or a NUL-terminated string.
+unsafe fn arg<'a>(s: *const c_char, name: &str) -> Option<&'a str> {
+    if s.is_null() {
+        set_last_error(format!("{name} is NULL"));
+        return None;
+    }
+    match CStr::from_ptr(s).to_str() {
+        Ok(s) => Some(s),
+        Err(_) => {
+            set_last_error(format!("{name} is not UTF-8"));
+            None
+        }
+    }
+}
+
+/// The daemon's latest sample as JSON (the `data` of GetStats: `temps`,
+/// `throttling`, `power`, `cpus`, ...). Returns NULL on error or while the
+/// daemon has no sample yet; see `jetsonscope_last_error`. Free the string
+/// with `jetsonscope_free_string`.
+#[no_mangle]
+pub extern "C" fn jetsonscope_get_stats_json() -> *mut c_char {
+    let stats = match Client::connect().and_then(|mut client| client.get_stats()) {
+        Ok(Some(stats)) => stats,
+        Ok(None) => {
+            set_last_error("no sample yet".to_string());
+            return ptr::null_mut();
+        }
+        Err(err) => {
+            fail(err);
+            return ptr::null_mut();
+        }
+    };
+    match serde_json::to_string(&stats).map(CString::new) {
+        Ok(Ok(json)) => json.into_raw(),
+        _ => {
+            set_last_error("cannot encode stats".to_string());
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Set `control` (e.g. "fan", "nvpmodel", "gpu_max_freq") to `value`, with
+/// the token from JETSONSCOPE_AUTH_TOKEN. Returns `JETSONSCOPE_OK` or one of
+/// the `JETSONSCOPE_ERR_*` codes.
+///
+/// # Safety
+/// `control` and `value` must be NULL or NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn jetsonscope_set_control(control: *const c_char, value: *const c_char) -> c_int {
+    let (Some(control), Some(value)) = (arg(control, "control"), arg(value, "value")) else {
+        return JETSONSCOPE_ERR_ARGUMENT;
+    };
+    match Client::connect().and_then(|mut client| client.set_control(control, value)) {
+        Ok(_) => JETSONSCOPE_OK,
+        Err(err) => fail(err),
+    }
+}
+
+/// Message of the last failed call on this thread, or NULL. The string
+/// stays valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn jetsonscope_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |s| s.as_ptr()))
+}
+
+/// Free a string returned by this library; NULL is ignored.
+///
+/// # Safety
+/// `s` must be NULL or a pointer from `jetsonscope_get_stats_json`, freed once.
+#[no_mangle]
+pub unsafe extern "C" fn jetsonscope_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_bad_arguments_and_missing_daemon() {
+        let message = || unsafe { CStr::from_ptr(jetsonscope_last_error()) }.to_str().unwrap().to_string();
+        let value = CString::new("80").unwrap();
+        assert_eq!(
+            unsafe { jetsonscope_set_control(ptr::null(), value.as_ptr()) },
+            JETSONSCOPE_ERR_ARGUMENT
+        );
+        assert_eq!(message(), "control is NULL");
+
+        std::env::set_var("JETSONSCOPE_SOCKET_PATH", "/nonexistent/jetsonscope.sock");
+        assert!(jetsonscope_get_stats_json().is_null());
+        assert!(message().contains("/nonexistent/jetsonscope.sock"));
+        let control = CString::new("fan").unwrap();
+        assert_eq!(
+            unsafe { jetsonscope_set_control(control.as_ptr(), value.as_ptr()) },
+            JETSONSCOPE_ERR_CONNECTION
+        );
+        unsafe { jetsonscope_free_string(ptr::null_mut()) };
+    }
+
+    #[test]
+    fn checked_in_header_is_current() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/jetsonscope.h"));
+        assert!(
+            generated == include_str!("../include/jetsonscope.h"),
+            "ffi/include/jetsonscope.h is stale: cp {}/jetsonscope.h ffi/include/",
+            env!("OUT_DIR")
+        );
+    }
+}
@@ -38,7 +38,7 @@ echo ""
 # Install systemd service
 if [ -d /etc/systemd/system ]; then
     echo "📋 Installing systemd service..."
-    sudo cp install/jscoped.service /etc/systemd/system/
+    sudo cp install/jscoped.service install/jscoped.socket /etc/systemd/system/
     sudo systemctl daemon-reload
     sudo systemctl enable jscoped
     sudo systemctl start jscoped
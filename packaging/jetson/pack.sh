@@ -18,7 +18,7 @@ TARBALL="$ROOT/target/jetsonscope-${VERSION}-${ARCH}.tar.gz"
 
 echo "📦 Packaging JetsonScope version ${VERSION} for arch ${ARCH} (profile: ${PROFILE})"
 rm -rf "$STAGE"
-mkdir -p "$STAGE/usr/local/bin" "$STAGE/etc/systemd/system"
+mkdir -p "$STAGE/usr/local/bin" "$STAGE/etc/systemd/system" "$STAGE/etc/jetsonscope"
 
 echo "🔨 Building binaries..."
 cargo build ${PROFILE_FLAG} --features "${FEATURES}" --bin jscope --bin jscoped --bin jscopectl
@@ -28,6 +28,8 @@ cp "$ROOT/target/${PROFILE}/jscope" "$STAGE/usr/local/bin/jscope"
 cp "$ROOT/target/${PROFILE}/jscoped" "$STAGE/usr/local/bin/jscoped"
 cp "$ROOT/target/${PROFILE}/jscopectl" "$STAGE/usr/local/bin/jscopectl"
 install -m 0644 "$ROOT/install/jscoped.service" "$STAGE/etc/systemd/system/jscoped.service"
+install -m 0644 "$ROOT/install/jscoped.socket" "$STAGE/etc/systemd/system/jscoped.socket"
+install -m 0600 "$ROOT/install/daemon.toml" "$STAGE/etc/jetsonscope/daemon.toml"
 
 cat > "$STAGE/README-package.md" <<'EOF'
 JetsonScope - Jetson Package
@@ -38,6 +40,8 @@ Contents:
 - /usr/local/bin/jscoped (daemon)
 - /usr/local/bin/jscopectl (CLI)
 - /etc/systemd/system/jscoped.service
+- /etc/systemd/system/jscoped.socket (optional socket activation)
+- /etc/jetsonscope/daemon.toml (daemon settings)
 
 Install:
 1) As root (or sudo):
@@ -50,8 +54,8 @@ Install:
    jscope
 
 Notes:
-- Change JETSONSCOPE_SOCKET_PATH if you need a different socket.
-- If you use auth, export JETSONSCOPE_AUTH_TOKEN in the service.
+- Socket path, auth token, metrics endpoint and collector settings live in
+  /etc/jetsonscope/daemon.toml; JETSONSCOPE_* variables in the service override it.
 EOF
 
 echo "🗜️  Creating tarball..."
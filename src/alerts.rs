@@ -0,0 +1,415 @@
+//! Threshold alerts evaluated by the daemon on every sample.
+//!
+//! Rules come from JETSONSCOPE_ALERTS (`[alerts] rules = [...]` in the config
+//! file), separated by commas or semicolons, each
+//! `<metric> <op> <value>[unit] [for <duration>]`:
+//!
+//! - `tj > 85C for 30s`: a temperature sensor (the `C` unit picks sensors)
+//! - `ram > 90%`, `swap > 50%`, `cpu >= 95% for 1m` (mean load), `gpu > 90%`
+//! - `VDD_IN > 15W`, `VDD_GPU_SOC > 8000mW`: a power rail (current draw)
+//! - `temp.cpu > 70`, `power.VDD_IN > 15000`, `engine.NVENC > 80`: explicit kind
+//!
+//! A rule fires once its condition has held for the whole duration (at once
+//! without `for`) and resolves on the first sample where it does not hold.
+//! Metrics missing from a sample count as not matching.
+//!
+//! JETSONSCOPE_POWER_BUDGET_MW adds a `power budget` rule on the rolling
+//! average of the board input power, next to the cap that enforces it.
+
+use crate::parser::TegraStats;
+use crate::power_cap::{self, RollingAverage};
+use crate::t;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Metric {
+    RamPercent,
+    SwapPercent,
+    CpuPercent,
+    GpuPercent,
+    Temp(String),
+    /// Current draw in mW
+    Power(String),
+    Engine(String),
+    /// Board input power in mW (VDD_IN, else the sum of the rails)
+    InputPower,
+}
+
+impl Metric {
+    fn value(&self, stats: &TegraStats) -> Option<f64> {
+        match self {
+            Metric::RamPercent => stats
+                .ram
+                .as_ref()
+                .filter(|r| r.total_bytes > 0)
+                .map(|_| stats.ram_ratio() * 100.0),
+            Metric::SwapPercent => stats
+                .swap
+                .as_ref()
+                .filter(|s| s.total_bytes > 0)
+                .map(|_| stats.swap_ratio() * 100.0),
+            Metric::CpuPercent => {
+                let loads: Vec<u32> = stats.cpus.iter().filter_map(|c| c.load_percent).collect();
+                (!loads.is_empty()).then(|| loads.iter().sum::<u32>() as f64 / loads.len() as f64)
+            }
+            Metric::GpuPercent => stats.gpu_usage().map(f64::from),
+            Metric::Temp(name) => lookup(&stats.temps, name).map(|t| *t as f64),
+            Metric::Power(name) => lookup(&stats.power, name).map(|r| r.current_mw as f64),
+            Metric::Engine(name) => lookup(&stats.engines, name)
+                .and_then(|e| e.usage_percent)
+                .map(f64::from),
+            Metric::InputPower => power_cap::input_power_mw(stats).map(f64::from),
+        }
+    }
+
+    /// Label for metrics and listings: "ram", "temp.tj", "power.VDD_IN", ...
+    fn label(&self) -> String {
+        match self {
+            Metric::RamPercent => "ram".into(),
+            Metric::SwapPercent => "swap".into(),
+            Metric::CpuPercent => "cpu".into(),
+            Metric::GpuPercent => "gpu".into(),
+            Metric::Temp(n) => format!("temp.{n}"),
+            Metric::Power(n) => format!("power.{n}"),
+            Metric::Engine(n) => format!("engine.{n}"),
+            Metric::InputPower => "power.input".into(),
+        }
+    }
+}
+
+/// Sensor, rail and engine names are matched case-insensitively.
+fn lookup<'a, V>(map: &'a HashMap<String, V>, name: &str) -> Option<&'a V> {
+    map.iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Above,
+    AtLeast,
+    Below,
+    AtMost,
+}
+
+impl Op {
+    fn holds(self, value: f64, threshold: f64) -> bool {
+        match self {
+            Op::Above => value > threshold,
+            Op::AtLeast => value >= threshold,
+            Op::Below => value < threshold,
+            Op::AtMost => value <= threshold,
+        }
+    }
+}
+
+/// One parsed rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertRule {
+    /// The rule as written, which also names it
+    pub text: String,
+    metric: Metric,
+    op: Op,
+    /// In the metric's unit: %, °C, or mW for rails
+    threshold: f64,
+    hold: Duration,
+    /// Compare the metric's rolling average instead of each sample
+    average: Option<RollingAverage>,
+}
+
+impl AlertRule {
+    /// Fires while the input power averaged over `window` is above `budget_mw`.
+    pub fn power_budget(budget_mw: u32, window: Duration) -> Self {
+        AlertRule {
+            text: format!("power budget {}mW ({}s avg)", budget_mw, window.as_secs()),
+            metric: Metric::InputPower,
+            op: Op::Above,
+            threshold: budget_mw as f64,
+            hold: Duration::ZERO,
+            average: Some(RollingAverage::new(window)),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let (cond, hold) = match text.split_once(" for ") {
+            Some((cond, dur)) => (cond, crate::stress::parse_duration(dur)?),
+            None => (text.as_str(), Duration::ZERO),
+        };
+        let (pos, op, op_len) = [
+            (">=", Op::AtLeast),
+            ("<=", Op::AtMost),
+            (">", Op::Above),
+            ("<", Op::Below),
+        ]
+        .into_iter()
+        .find_map(|(sym, op)| cond.find(sym).map(|i| (i, op, sym.len())))
+        .ok_or_else(|| anyhow::anyhow!(t!("alerts.err.syntax", rule = text)))?;
+        let name = cond[..pos].trim();
+        let raw = cond[pos + op_len..].trim();
+        let split = raw
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+            .unwrap_or(raw.len());
+        let (number, unit) = raw.split_at(split);
+        let Ok(mut threshold) = number.parse::<f64>() else {
+            bail!(t!("alerts.err.syntax", rule = text));
+        };
+        if name.is_empty() {
+            bail!(t!("alerts.err.syntax", rule = text));
+        }
+
+        let metric = match (name.split_once('.'), unit.trim()) {
+            (Some(("temp", sensor)), _) => Metric::Temp(sensor.to_string()),
+            (Some(("power", rail)), _) => Metric::Power(rail.to_string()),
+            (Some(("engine", engine)), _) => Metric::Engine(engine.to_string()),
+            (_, "C" | "°C") => Metric::Temp(name.to_string()),
+            (_, "mW" | "W") => Metric::Power(name.to_string()),
+            _ => match name.to_ascii_lowercase().as_str() {
+                "ram" => Metric::RamPercent,
+                "swap" => Metric::SwapPercent,
+                "cpu" => Metric::CpuPercent,
+                "gpu" | "gr3d" => Metric::GpuPercent,
+                _ => bail!(t!("alerts.err.metric", rule = text, metric = name)),
+            },
+        };
+        match unit.trim() {
+            "" | "%" | "C" | "°C" | "mW" => {}
+            "W" => threshold *= 1000.0,
+            other => bail!(t!("alerts.err.unit", rule = text, unit = other)),
+        }
+        Ok(AlertRule {
+            text,
+            metric,
+            op,
+            threshold,
+            hold,
+            average: None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertState {
+    /// Condition false (or metric missing)
+    Ok,
+    /// Condition true for less than the rule's duration
+    Pending,
+    Firing,
+}
+
+/// A rule and where it stands, as served by GetAlerts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertStatus {
+    pub rule: String,
+    /// "ram", "temp.tj", "power.VDD_IN", ...
+    pub metric: String,
+    pub state: AlertState,
+    /// Latest value (mW for rails), if the metric was in the sample
+    pub value: Option<f64>,
+    pub threshold: f64,
+    /// When the condition started holding, ms since the Unix epoch
+    pub since_unix_ms: Option<u64>,
+}
+
+/// A state change worth logging.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlertEvent {
+    Fired(AlertStatus),
+    Resolved(AlertStatus),
+}
+
+#[derive(Debug, Default)]
+pub struct AlertEngine {
+    rules: Vec<AlertRule>,
+    status: Vec<AlertStatus>,
+}
+
+impl AlertEngine {
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        let status = rules
+            .iter()
+            .map(|r| AlertStatus {
+                rule: r.text.clone(),
+                metric: r.metric.label(),
+                state: AlertState::Ok,
+                value: None,
+                threshold: r.threshold,
+                since_unix_ms: None,
+            })
+            .collect();
+        AlertEngine { rules, status }
+    }
+
+    /// Rules from JETSONSCOPE_ALERTS, plus the power budget; bad ones are reported and skipped.
+    pub fn from_env() -> Self {
+        let spec = std::env::var("JETSONSCOPE_ALERTS").unwrap_or_default();
+        let mut rules: Vec<AlertRule> = spec
+            .split([',', ';'])
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+            .filter_map(|r| match AlertRule::parse(r) {
+                Ok(rule) => Some(rule),
+                Err(e) => {
+                    eprintln!("{e}");
+                    None
+                }
+            })
+            .collect();
+        if let Some(budget_mw) = power_cap::budget_from_env() {
+            rules.push(AlertRule::power_budget(budget_mw, power_cap::window_from_env()));
+        }
+        Self::new(rules)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Update every rule with a new sample taken at `now_ms`.
+    pub fn evaluate(&mut self, stats: &TegraStats, now_ms: u64) -> Vec<AlertEvent> {
+        let mut events = Vec::new();
+        for (rule, status) in self.rules.iter_mut().zip(self.status.iter_mut()) {
+            let value = rule.metric.value(stats);
+            let value = match rule.average.as_mut() {
+                Some(avg) => value.map(|v| avg.push(now_ms, v as u32) as f64),
+                None => value,
+            };
+            status.value = value;
+            let holds = value.is_some_and(|v| rule.op.holds(v, rule.threshold));
+            if !holds {
+                let was_firing = status.state == AlertState::Firing;
+                status.state = AlertState::Ok;
+                status.since_unix_ms = None;
+                if was_firing {
+                    events.push(AlertEvent::Resolved(status.clone()));
+                }
+                continue;
+            }
+            let since = *status.since_unix_ms.get_or_insert(now_ms);
+            if status.state != AlertState::Firing {
+                if now_ms.saturating_sub(since) >= rule.hold.as_millis() as u64 {
+                    status.state = AlertState::Firing;
+                    events.push(AlertEvent::Fired(status.clone()));
+                } else {
+                    status.state = AlertState::Pending;
+                }
+            }
+        }
+        events
+    }
+
+    pub fn status(&self) -> Vec<AlertStatus> {
+        self.status.clone()
+    }
+
+    /// `jetsonscope_alert_active{rule,metric}` (1 while firing) for every rule.
+    pub fn prometheus(&self) -> String {
+        if self.status.is_empty() {
+            return String::new();
+        }
+        let mut out = String::from(
+            "# HELP jetsonscope_alert_active Alert rule firing (1) or not (0)\n\
+             # TYPE jetsonscope_alert_active gauge\n",
+        );
+        for s in &self.status {
+            out.push_str(&format!(
+                "jetsonscope_alert_active{{rule=\"{}\",metric=\"{}\"}} {}\n",
+                s.rule.replace('\\', "\\\\").replace('"', "\\\""),
+                s.metric,
+                u8::from(s.state == AlertState::Firing)
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(tj: f32, ram_used: u32) -> TegraStats {
+        TegraStats::parse(&format!(
+            "RAM {ram_used}/1000MB (lfb 8x4MB) SWAP 0/999MB (cached 0MB) CPU [50%@1190,70%@1190] GR3D_FREQ 30%@918 tj@{tj}C VDD_IN 12000mW/11000mW"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_rules() {
+        let r = AlertRule::parse("tj  > 85C for 30s").unwrap();
+        assert_eq!(r.text, "tj > 85C for 30s");
+        assert_eq!(
+            (r.metric.label(), r.threshold),
+            ("temp.tj".to_string(), 85.0)
+        );
+        assert_eq!(r.hold, Duration::from_secs(30));
+        assert_eq!(AlertRule::parse("VDD_IN > 15W").unwrap().threshold, 15000.0);
+        assert_eq!(
+            AlertRule::parse("RAM >= 90%").unwrap().metric,
+            Metric::RamPercent
+        );
+        assert_eq!(
+            AlertRule::parse("engine.NVENC < 5").unwrap().metric,
+            Metric::Engine("NVENC".into())
+        );
+
+        assert!(AlertRule::parse("tj 85").is_err());
+        assert!(AlertRule::parse("fan > 50%").is_err());
+        assert!(AlertRule::parse("tj > 85K").is_err());
+        assert!(AlertRule::parse("ram > 90% for ever").is_err());
+    }
+
+    #[test]
+    fn fires_after_the_hold_and_resolves() {
+        let mut engine = AlertEngine::new(vec![
+            AlertRule::parse("TJ > 85C for 30s").unwrap(),
+            AlertRule::parse("ram > 90%").unwrap(),
+            AlertRule::parse("vdd_in > 11W").unwrap(),
+            AlertRule::parse("engine.NVENC > 10").unwrap(),
+        ]);
+
+        let events = engine.evaluate(&stats(90.0, 950), 0);
+        // RAM and power fire at once; tj waits; NVENC is missing from the sample
+        assert_eq!(events.len(), 2);
+        let status = engine.status();
+        assert_eq!(status[0].state, AlertState::Pending);
+        assert_eq!(status[1].state, AlertState::Firing);
+        assert_eq!(status[2].value, Some(12000.0));
+        assert_eq!(status[3].value, None);
+
+        assert!(engine.evaluate(&stats(90.0, 950), 20_000).is_empty());
+        let events = engine.evaluate(&stats(91.0, 100), 30_000);
+        assert!(matches!(&events[0], AlertEvent::Fired(s) if s.metric == "temp.TJ"));
+        assert!(matches!(&events[1], AlertEvent::Resolved(s) if s.metric == "ram"));
+        assert_eq!(engine.status()[0].since_unix_ms, Some(0));
+
+        // Dropping below restarts the hold
+        engine.evaluate(&stats(80.0, 100), 31_000);
+        engine.evaluate(&stats(90.0, 100), 32_000);
+        assert_eq!(engine.status()[0].state, AlertState::Pending);
+
+        let metrics = engine.prometheus();
+        assert!(metrics
+            .contains("jetsonscope_alert_active{rule=\"vdd_in > 11W\",metric=\"power.vdd_in\"} 1"));
+        assert!(metrics
+            .contains("jetsonscope_alert_active{rule=\"TJ > 85C for 30s\",metric=\"temp.TJ\"} 0"));
+    }
+
+    #[test]
+    fn power_budget_uses_the_rolling_average() {
+        let mut engine = AlertEngine::new(vec![AlertRule::power_budget(11_500, Duration::from_secs(10))]);
+        let power = |mw: u32| TegraStats::parse(&format!("VDD_IN {mw}mW/{mw}mW")).unwrap();
+        assert!(engine.evaluate(&power(10_000), 0).is_empty());
+        // 12 W on its own is over budget, but the average is not yet
+        assert!(engine.evaluate(&power(12_000), 1000).is_empty());
+        let events = engine.evaluate(&power(14_000), 2000);
+        assert!(matches!(&events[..], [AlertEvent::Fired(s)] if s.metric == "power.input" && s.value == Some(12_000.0)));
+        // Once the spike leaves the window the average drops back under
+        let events = engine.evaluate(&power(9_000), 12_000);
+        assert!(matches!(&events[..], [AlertEvent::Resolved(_)]));
+    }
+}
@@ -1,9 +1,39 @@
-use crate::collector::{CollectorMessage, start_collector, CollectorMode};
+use crate::burst::{BurstCapture, BurstConfig};
+use crate::collector::{
+    start_collector_with_requests, CollectorMessage, CollectorMode, CollectorReceiver, CollectorStop,
+};
 use crate::control::ControlManager;
-use crate::parser::TegraStats;
-use std::collections::VecDeque;
-use std::sync::mpsc::Receiver;
-use std::time::Instant;
+use crate::energy::{EnergyMeter, EnergyReport, EnergyTariff};
+use crate::extremes::SessionExtremes;
+use crate::stats::Aggregator;
+use crate::history::{now_unix_ms, HistoryPoint};
+use crate::i18n;
+use crate::link::LinkQuality;
+use crate::palette::{Palette, PaletteCommand, PaletteEntry};
+use crate::parser::{StatsDelta, TegraStats};
+use crate::processes::{self, ProcessDetail, ProcessInfo, ProcessMonitor, ProcessSort};
+use crate::profiles::ProfileList;
+use crate::protocol::{Request, Response};
+use crate::residency::ResidencyReport;
+use crate::scenario::Scenario;
+use crate::screen_dump::DumpFormat;
+use crate::t;
+use crate::theme::Theme;
+use crate::thermal_governor::valid_temp;
+use crate::thresholds::Thresholds;
+use crate::trip_points::TripPoints;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+use ratatui::widgets::TableState;
+use std::collections::{BTreeMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Data refresh steps for '+'/'-' (ms); the UI tick follows at a tenth of it.
+pub const REFRESH_STEPS_MS: [u64; 7] = [250, 500, 1000, 2000, 5000, 10000, 30000];
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ViewMode {
@@ -11,6 +41,78 @@ pub enum ViewMode {
     Processes,
     GpuEngines,
     Clocks,
+    /// Per-sensor temperature trends
+    Temperatures,
+    /// Per-rail power trends and energy
+    Power,
+}
+
+impl ViewMode {
+    /// Tab bar order, same as `v` cycles through.
+    pub const ALL: [ViewMode; 6] = [
+        ViewMode::Dashboard,
+        ViewMode::Processes,
+        ViewMode::GpuEngines,
+        ViewMode::Clocks,
+        ViewMode::Temperatures,
+        ViewMode::Power,
+    ];
+
+    /// Short name for the tab bar.
+    pub fn tab_label(&self) -> &'static str {
+        match self {
+            ViewMode::Dashboard => t!("ui.tab.dashboard"),
+            ViewMode::Processes => t!("ui.tab.processes"),
+            ViewMode::GpuEngines => t!("ui.tab.gpu"),
+            ViewMode::Clocks => t!("ui.tab.clocks"),
+            ViewMode::Temperatures => t!("ui.tab.temps"),
+            ViewMode::Power => t!("ui.tab.power"),
+        }
+    }
+}
+
+/// Startup settings from jscope's command line.
+#[derive(Debug, Clone)]
+pub struct AppOptions {
+    /// Daemon socket; unset looks at JETSONSCOPE_SOCKET_PATH and the default paths
+    pub socket: Option<PathBuf>,
+    /// Initial refresh interval; unset uses JETSONSCOPE_INTERVAL_MS
+    pub refresh_ms: Option<u64>,
+    pub view: ViewMode,
+    /// Synthetic data only, without looking for the daemon
+    pub demo: bool,
+    /// Demo data profile; unset uses JETSONSCOPE_DEMO_SCENARIO
+    pub scenario: Option<Scenario>,
+    pub animations: bool,
+    pub theme: Theme,
+    /// Ask before jetson_clocks, nvpmodel, governor, fan and fan curve
+    /// changes, profiles, and killing or renicing processes
+    pub confirm_controls: bool,
+}
+
+impl Default for AppOptions {
+    fn default() -> Self {
+        AppOptions {
+            socket: None,
+            refresh_ms: None,
+            view: ViewMode::Dashboard,
+            demo: false,
+            scenario: None,
+            animations: true,
+            theme: Theme::from_env(),
+            confirm_controls: std::env::var("JETSONSCOPE_CONFIRM").map_or(true, |v| v != "0"),
+        }
+    }
+}
+
+impl AppOptions {
+    fn collector_mode(&self) -> CollectorMode {
+        match (&self.socket, self.demo) {
+            (_, true) => CollectorMode::Demo(self.scenario.unwrap_or_else(Scenario::from_env)),
+            (Some(path), false) => CollectorMode::SocketAt(path.clone()),
+            (None, false) => CollectorMode::SocketOnly,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -64,28 +166,282 @@ impl HistoryWindow {
     }
 }
 
+/// How history series are drawn in the trend panels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphStyle {
+    /// Braille dots: 2x4 points per cell, line plot
+    Braille,
+    /// One vertical bar per sample, on the same axes
+    Bars,
+}
+
+impl GraphStyle {
+    /// JETSONSCOPE_GRAPH_STYLE=bars|braille (default braille).
+    pub fn from_env() -> Self {
+        match std::env::var("JETSONSCOPE_GRAPH_STYLE") {
+            Ok(v) if v.eq_ignore_ascii_case("bars") || v.eq_ignore_ascii_case("sparkline") => {
+                GraphStyle::Bars
+            }
+            _ => GraphStyle::Braille,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            GraphStyle::Braille => GraphStyle::Bars,
+            GraphStyle::Bars => GraphStyle::Braille,
+        }
+    }
+}
+
+/// Dashboard layout selection; `Auto` condenses below COMPACT_WIDTH x COMPACT_HEIGHT.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutMode {
+    Auto,
+    Compact,
+    Full,
+}
+
+/// Smallest terminal that still gets the full bordered layout.
+pub const COMPACT_WIDTH: u16 = 100;
+pub const COMPACT_HEIGHT: u16 = 30;
+
+impl LayoutMode {
+    /// JETSONSCOPE_LAYOUT=auto|compact|full (default auto).
+    pub fn from_env() -> Self {
+        match std::env::var("JETSONSCOPE_LAYOUT") {
+            Ok(v) if v.eq_ignore_ascii_case("compact") => LayoutMode::Compact,
+            Ok(v) if v.eq_ignore_ascii_case("full") => LayoutMode::Full,
+            _ => LayoutMode::Auto,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            LayoutMode::Auto => LayoutMode::Compact,
+            LayoutMode::Compact => LayoutMode::Full,
+            LayoutMode::Full => LayoutMode::Auto,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LayoutMode::Auto => t!("app.layout.auto"),
+            LayoutMode::Compact => t!("app.layout.compact"),
+            LayoutMode::Full => t!("app.layout.full"),
+        }
+    }
+
+    /// Whether a terminal of `width` x `height` gets the condensed layout.
+    pub fn is_compact(&self, width: u16, height: u16) -> bool {
+        match self {
+            LayoutMode::Auto => width < COMPACT_WIDTH || height < COMPACT_HEIGHT,
+            LayoutMode::Compact => true,
+            LayoutMode::Full => false,
+        }
+    }
+}
+
+/// State of the link to the stats source, shown in the header.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectionStatus {
+    Connecting,
+    Reconnecting,
+    Connected,
+    ConnectedSocket,
+    /// Synthetic data (no daemon, not a Jetson)
+    Demo,
+    Retrying(usize),
+    /// Retries exhausted
+    Offline,
+    Error(String),
+    /// Connected, but no sample for a while
+    NoData,
+}
+
+impl ConnectionStatus {
+    pub fn label(&self) -> String {
+        match self {
+            ConnectionStatus::Connecting => t!("status.connecting").to_string(),
+            ConnectionStatus::Reconnecting => t!("status.reconnecting").to_string(),
+            ConnectionStatus::Connected => t!("status.connected").to_string(),
+            ConnectionStatus::ConnectedSocket => t!("status.connected_socket").to_string(),
+            ConnectionStatus::Demo => t!("status.demo").to_string(),
+            ConnectionStatus::Retrying(n) => t!("status.retrying", n = n),
+            ConnectionStatus::Offline => t!("status.offline").to_string(),
+            ConnectionStatus::Error(err) => t!("status.error", error = err),
+            ConnectionStatus::NoData => t!("status.no_data").to_string(),
+        }
+    }
+
+    pub fn is_demo(&self) -> bool {
+        *self == ConnectionStatus::Demo
+    }
+
+    pub fn is_connected(&self) -> bool {
+        matches!(
+            self,
+            ConnectionStatus::Connected | ConnectionStatus::ConnectedSocket
+        )
+    }
+}
+
+/// Control change waiting for y/n, with the exact command it will run.
+#[derive(Debug, Clone)]
+pub struct PendingConfirm {
+    pub command: PaletteCommand,
+    /// e.g. "Apply nvpmodel MAXN?"
+    pub question: String,
+    /// The command that will run
+    pub detail: String,
+}
+
+/// Control change running on a worker thread, so the UI keeps drawing while
+/// jetson_clocks or nvpmodel take their few seconds.
+pub struct PendingControl {
+    pub label: String,
+    rx: Receiver<ControlManager>,
+}
+
+/// Burst capture started from the TUI, as far as it has got.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BurstState {
+    /// StartBurst queued, waiting for the daemon to accept it
+    Requested { since: Instant },
+    /// Daemon sampling until `until`
+    Running { id: u64, until: Instant },
+    /// Capture window over, GetBurst queued until it comes back finished
+    Fetching { id: u64, since: Instant },
+}
+
+/// Where the last frame drew clickable things, for mouse events.
+#[derive(Debug, Clone, Default)]
+pub struct ClickAreas {
+    /// One cell range per tab in the tab bar (empty in the compact layout)
+    pub tabs: Vec<(ViewMode, Rect)>,
+    /// Process table body, below its header row
+    pub process_rows: Option<Rect>,
+}
+
+/// Rows the mouse wheel moves the process selection.
+const WHEEL_ROWS: isize = 3;
+
+/// Nice value `n` gives the selected process (lower priority).
+pub const RENICE_NICE: i32 = 10;
+
+/// How often the Processes view rescans /proc.
+const PROCESS_REFRESH: Duration = Duration::from_secs(2);
+
+/// How long a burst request may go unanswered (e.g. no daemon) before giving up.
+const BURST_REPLY_TIMEOUT: Duration = Duration::from_secs(10);
+
 pub struct App {
     pub stats_history: Vec<TegraStats>,
     pub latest_stats: TegraStats,
-    pub rx: Receiver<CollectorMessage>,
+    pub rx: CollectorReceiver,
+    /// Shared with the collector thread
+    poll_interval_ms: Arc<AtomicU64>,
+    /// Daemon requests for the collector to send, shared with its thread
+    requests: Arc<Mutex<Vec<Request>>>,
     pub tick_count: u64,
     pub source_label: String,
-    pub connection_status: String,
+    pub connection_status: ConnectionStatus,
     pub last_update_tick: u64,
     pub retry_count: usize,
-    pub reconnect_requested: bool,
+    collector_stop: CollectorStop,
+    /// Where the collector looks for data, kept for reconnects
+    collector_mode: CollectorMode,
     pub history: History,
     pub history_window: HistoryWindow,
+    pub graph_style: GraphStyle,
+    pub layout_mode: LayoutMode,
     pub control: ControlManager,
     pub view_mode: ViewMode,
-    pub process_sort_by_mem: bool,
+    /// Sensor charted in the Temperatures view; `None` picks the first one
+    pub temp_sensor: Option<String>,
+    pub theme: Theme,
+    /// Rainbow/pulse effects follow the tick; off keeps colors still
+    pub animations: bool,
+    pub process_sort: ProcessSort,
+    /// Processes view shows one row per user instead of per process
+    pub process_by_user: bool,
+    /// Selected row and scroll offset of the process table
+    pub process_table: TableState,
+    /// Scanned only while the Processes view is open; created on first use
+    process_monitor: Option<ProcessMonitor>,
+    /// Last scan, sorted by `process_sort`
+    pub processes: Vec<ProcessInfo>,
+    processes_refreshed: Option<Instant>,
+    /// Pid and name of the selected row as last drawn
+    pub selected_process: Option<(u32, String)>,
+    /// Kill/renice sent to the daemon and not answered yet, for its error
+    process_action: Option<String>,
+    /// The daemon's profiles, refreshed on connect and after applying one
+    pub profiles: ProfileList,
+    /// Profile sent to the daemon and not applied yet, for its error
+    profile_action: Option<String>,
+    /// SetControl sent to the daemon and not answered yet, for its error
+    control_action: Option<String>,
+    /// Memory popup of the selected process (Enter in the Processes view)
+    pub process_detail: Option<ProcessDetail>,
+    /// Pid whose GetProcessDetail is in flight; the daemon can read nvmap
+    process_detail_requested: Option<u32>,
+    pub click_areas: ClickAreas,
     pub show_help: bool,
+    /// Energy integrated since the TUI started
+    pub energy: EnergyMeter,
+    /// The daemon's energy since it started (Power view)
+    pub daemon_energy: Option<EnergyReport>,
+    energy_requested: Option<Instant>,
+    pub tariff: Option<EnergyTariff>,
+    /// Ctrl-P command palette, when open
+    pub palette: Option<Palette>,
+    /// Control change shown in the confirmation dialog
+    pub confirm: Option<PendingConfirm>,
+    /// Control change still running, shown on the Controls panel
+    pub control_pending: Option<PendingControl>,
+    /// JETSONSCOPE_CONFIRM=0 or --no-confirm runs control changes without asking
+    confirm_controls: bool,
+    /// Short message for the footer (action results), with when it was set
+    pub notice: Option<(String, Instant)>,
+    /// Sub-second capture settings (JETSONSCOPE_BURST) and progress
+    pub burst_config: BurstConfig,
+    pub burst: Option<BurstState>,
+    /// Screen dump to write from the next rendered frame
+    pub dump_requested: Option<DumpFormat>,
+    /// Round trip, sample rate and late/dropped polls (header)
+    pub link: LinkQuality,
+    /// Warning/critical color cutoffs (JETSONSCOPE_THRESHOLDS)
+    pub thresholds: Thresholds,
+    /// Passive/critical trip points per sensor, read once from sysfs
+    pub trip_points: std::collections::BTreeMap<String, TripPoints>,
+    /// Estimated GPU% per pid from the daemon's runlist sampling (Processes view)
+    pub gpu_load_by_pid: std::collections::BTreeMap<u32, f32>,
+    gpu_procs_requested: Option<Instant>,
+    /// RAM/GPU/temperature min/max/avg since the TUI started
+    pub extremes: SessionExtremes,
+    /// Rolling mean/max/p95 for the trend panels, since the TUI started
+    pub summaries: Aggregator,
+    /// Frequency/rail-gate residency since the TUI started (Clocks view)
+    pub residency: ResidencyReport,
+    residency_baseline: ResidencyReport,
+    residency_refreshed: Option<Instant>,
 }
 
 pub struct History {
     pub ram: VecDeque<(Instant, f64)>,
     pub gpu: VecDeque<(Instant, f64)>,
     pub cpu: VecDeque<(Instant, f64)>,
+    /// Hottest valid sensor (°C)
+    pub temp: VecDeque<(Instant, f64)>,
+    /// Every valid sensor (°C), by name; starts with the TUI (the daemon
+    /// keeps only the hottest)
+    pub temps: BTreeMap<String, VecDeque<(Instant, f64)>>,
+    /// Instant power per rail (W), by name; starts with the TUI
+    pub rails: BTreeMap<String, VecDeque<(Instant, f64)>>,
+    /// Load per CPU core (%), indexed like `TegraStats::cpus`; offline cores
+    /// get no points
+    pub cores: Vec<VecDeque<(Instant, f64)>>,
     #[allow(dead_code)]
     start_time: Instant,
 }
@@ -96,47 +452,842 @@ impl Default for History {
             ram: VecDeque::new(),
             gpu: VecDeque::new(),
             cpu: VecDeque::new(),
+            temp: VecDeque::new(),
+            temps: BTreeMap::new(),
+            rails: BTreeMap::new(),
+            cores: Vec::new(),
             start_time: Instant::now(),
         }
     }
 }
 
+/// Finished burst capture as JSON in the working directory.
+fn save_burst(capture: &BurstCapture) -> anyhow::Result<String> {
+    let path = format!(
+        "jetsonscope-burst-{}-{}.json",
+        capture.id,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+    std::fs::write(&path, serde_json::to_string_pretty(capture)?)?;
+    Ok(path)
+}
+
+/// Admin token for daemon process actions, as jscopectl reads it.
+fn auth_token() -> Option<String> {
+    std::env::var("TEGRA_AUTH_TOKEN")
+        .ok()
+        .or_else(|| std::env::var("JETSONSCOPE_AUTH_TOKEN").ok())
+        .filter(|t| !t.is_empty())
+}
+
+fn history_request(window: HistoryWindow) -> Request {
+    Request::GetHistory {
+        since_secs: window.duration_secs(),
+        max_points: window.max_points(),
+        full: false,
+    }
+}
+
 impl Default for App {
     fn default() -> Self {
-        Self::new()
+        Self::new(AppOptions::default())
     }
 }
 
 impl App {
-    pub fn new() -> Self {
-        let collector = start_collector(CollectorMode::SocketOnly);
+    pub fn new(options: AppOptions) -> Self {
+        let history_window = HistoryWindow::OneMinute;
+        let collector_mode = options.collector_mode();
+        // Start the trend charts from the daemon's history instead of blank
+        let collector = start_collector_with_requests(
+            collector_mode.clone(),
+            vec![history_request(history_window), Request::ListProfiles],
+        );
+        if let Some(ms) = options.refresh_ms {
+            collector.interval_ms.store(ms, Ordering::Relaxed);
+        }
 
         Self {
             stats_history: Vec::new(),
             latest_stats: TegraStats::default(),
             rx: collector.rx,
+            poll_interval_ms: collector.interval_ms,
+            requests: collector.requests,
             tick_count: 0,
-            source_label: "Conectando...".to_string(),
-            connection_status: "conectando".to_string(),
+            source_label: t!("status.source_connecting").to_string(),
+            connection_status: ConnectionStatus::Connecting,
             last_update_tick: 0,
             retry_count: 0,
-            reconnect_requested: false,
+            collector_stop: collector.stop,
+            collector_mode,
             history: History::default(),
-            history_window: HistoryWindow::OneMinute,
+            history_window,
+            graph_style: GraphStyle::from_env(),
+            layout_mode: LayoutMode::from_env(),
             control: ControlManager::new(),
-            view_mode: ViewMode::Dashboard,
-            process_sort_by_mem: false,
+            view_mode: options.view,
+            temp_sensor: None,
+            theme: options.theme,
+            animations: options.animations,
+            process_sort: ProcessSort::default(),
+            process_by_user: false,
+            process_table: TableState::default(),
+            process_monitor: None,
+            processes: Vec::new(),
+            processes_refreshed: None,
+            selected_process: None,
+            process_action: None,
+            profiles: ProfileList::default(),
+            profile_action: None,
+            control_action: None,
+            process_detail: None,
+            process_detail_requested: None,
+            click_areas: ClickAreas::default(),
             show_help: false,
+            energy: EnergyMeter::new(),
+            daemon_energy: None,
+            energy_requested: None,
+            tariff: EnergyTariff::from_env(),
+            palette: None,
+            confirm: None,
+            control_pending: None,
+            confirm_controls: options.confirm_controls,
+            notice: None,
+            burst_config: BurstConfig::from_env(),
+            burst: None,
+            dump_requested: None,
+            link: LinkQuality::default(),
+            thresholds: Thresholds::from_env(),
+            trip_points: crate::trip_points::read_all(),
+            gpu_load_by_pid: Default::default(),
+            gpu_procs_requested: None,
+            extremes: SessionExtremes::default(),
+            summaries: Aggregator::new(),
+            residency: ResidencyReport::default(),
+            residency_baseline: ResidencyReport::read(),
+            residency_refreshed: None,
+        }
+    }
+
+    /// Change between the two most recent samples (drives the trend arrows).
+    pub fn last_delta(&self) -> Option<StatsDelta> {
+        let len = self.stats_history.len();
+        if len < 2 {
+            return None;
         }
+        Some(self.stats_history[len - 1].delta(&self.stats_history[len - 2]))
+    }
+
+    fn refresh_residency(&mut self) {
+        self.residency = ResidencyReport::read().since(&self.residency_baseline);
+        self.residency_refreshed = Some(Instant::now());
     }
 
     pub fn cycle_history_window(&mut self) {
         self.history_window = self.history_window.next();
+        self.request_backfill();
+    }
+
+    /// Ask the daemon for the current window's history (served after the next successful poll).
+    pub fn request_backfill(&mut self) {
+        if let Ok(mut queue) = self.requests.lock() {
+            queue.retain(|r| !matches!(r, Request::GetHistory { .. }));
+            queue.push(history_request(self.history_window));
+        }
+    }
+
+    /// Queue a request for the daemon; the answer arrives as `CollectorMessage::Reply`.
+    fn send_request(&self, request: Request) {
+        if let Ok(mut queue) = self.requests.lock() {
+            queue.push(request);
+        }
+    }
+
+    /// Prepend daemon history older than what the charts already hold.
+    fn backfill_history(&mut self, points: Vec<HistoryPoint>) {
+        let now = Instant::now();
+        let now_ms = now_unix_ms();
+        let oldest = self.history.ram.front().map(|(at, _)| *at);
+        let older: Vec<(Instant, HistoryPoint)> = points
+            .into_iter()
+            .filter_map(|p| {
+                let at = now.checked_sub(Duration::from_millis(now_ms.saturating_sub(p.unix_ms)))?;
+                Some((at, p))
+            })
+            .filter(|(at, _)| oldest.map_or(true,|o| *at < o))
+            .collect();
+        for (at, point) in older.into_iter().rev() {
+            self.history.ram.push_front((at, point.ram));
+            self.history.gpu.push_front((at, point.gpu));
+            self.history.cpu.push_front((at, point.cpu));
+            self.history.temp.push_front((at, point.temp));
+        }
+        self.trim_history();
+    }
+
+    /// Keep at most the current window's max points per series.
+    fn trim_history(&mut self) {
+        let max_points = self.history_window.max_points();
+        for series in [
+            &mut self.history.ram,
+            &mut self.history.gpu,
+            &mut self.history.cpu,
+            &mut self.history.temp,
+        ] {
+            while series.len() > max_points {
+                series.pop_front();
+            }
+        }
+        for series in self
+            .history
+            .temps
+            .values_mut()
+            .chain(self.history.rails.values_mut())
+            .chain(self.history.cores.iter_mut())
+        {
+            while series.len() > max_points {
+                series.pop_front();
+            }
+        }
+    }
+
+    /// The Temperatures view's sensor: the chosen one while it still reports, else the first.
+    pub fn selected_temp_sensor(&self) -> Option<&str> {
+        self.temp_sensor
+            .as_deref()
+            .filter(|name| self.history.temps.contains_key(*name))
+            .or_else(|| self.history.temps.keys().next().map(String::as_str))
+    }
+
+    /// Move the Temperatures view to the next (`step` 1) or previous (-1) sensor.
+    pub fn select_temp_sensor(&mut self, step: isize) {
+        let names: Vec<&String> = self.history.temps.keys().collect();
+        if names.is_empty() {
+            return;
+        }
+        let current = self
+            .selected_temp_sensor()
+            .and_then(|s| names.iter().position(|n| n.as_str() == s))
+            .unwrap_or(0);
+        let next = (current as isize + step).rem_euclid(names.len() as isize) as usize;
+        self.temp_sensor = Some(names[next].clone());
+    }
+
+    /// Data polling period (also passed to a stats command's `--interval`).
+    pub fn refresh_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms.load(Ordering::Relaxed))
+    }
+
+    /// UI tick (key polling and redraw), 50-500 ms depending on the data rate.
+    pub fn tick_interval(&self) -> Duration {
+        self.refresh_interval().div_f64(10.0).clamp(
+            Duration::from_millis(50),
+            Duration::from_millis(500),
+        )
+    }
+
+    /// Step the refresh rate; `faster` moves to a shorter interval.
+    pub fn adjust_refresh(&mut self, faster: bool) {
+        let current = self.poll_interval_ms.load(Ordering::Relaxed);
+        let idx = REFRESH_STEPS_MS
+            .iter()
+            .position(|s| *s >= current)
+            .unwrap_or(REFRESH_STEPS_MS.len() - 1);
+        let idx = if faster {
+            idx.saturating_sub(1)
+        } else {
+            (idx + 1).min(REFRESH_STEPS_MS.len() - 1)
+        };
+        self.poll_interval_ms
+            .store(REFRESH_STEPS_MS[idx], Ordering::Relaxed);
+    }
+
+    /// Ticks that make up roughly `period`, at the current tick rate.
+    fn ticks_for(&self, period: Duration) -> u64 {
+        (period.as_millis() / self.tick_interval().as_millis().max(1)).max(1) as u64
+    }
+
+    pub fn open_palette(&mut self) {
+        self.palette = Some(Palette::default());
+    }
+
+    pub fn close_palette(&mut self) {
+        self.palette = None;
+    }
+
+    /// Every action the palette can run, in display order.
+    pub fn palette_entries(&self) -> Vec<PaletteEntry> {
+        use PaletteCommand as C;
+        let mut entries = vec![
+            PaletteEntry::new(t!("app.palette.view_dashboard"), C::View(ViewMode::Dashboard)),
+            PaletteEntry::new(t!("app.palette.view_processes"), C::View(ViewMode::Processes)),
+            PaletteEntry::new(t!("app.palette.view_gpu"), C::View(ViewMode::GpuEngines)),
+            PaletteEntry::new(t!("app.palette.view_clocks"), C::View(ViewMode::Clocks)),
+            PaletteEntry::new(t!("app.palette.view_temps"), C::View(ViewMode::Temperatures)),
+            PaletteEntry::new(t!("app.palette.view_power"), C::View(ViewMode::Power)),
+            PaletteEntry::new(t!("app.palette.history_window"), C::CycleHistoryWindow),
+            PaletteEntry::new(t!("app.palette.graph_style"), C::CycleGraphStyle),
+            PaletteEntry::new(t!("app.palette.layout"), C::CycleLayout),
+            PaletteEntry::new(t!("app.palette.theme"), C::CycleTheme),
+            PaletteEntry::new(t!("app.palette.refresh_faster"), C::RefreshFaster),
+            PaletteEntry::new(t!("app.palette.refresh_slower"), C::RefreshSlower),
+            PaletteEntry::new(t!("app.palette.process_sort"), C::ToggleProcessSort),
+            PaletteEntry::new("jetson_clocks: toggle", C::ToggleJetsonClocks),
+        ];
+        let status = self.control.status();
+        for mode in &status.nvpmodel_modes {
+            entries.push(PaletteEntry::new(
+                format!("nvpmodel: {}", mode),
+                C::Nvpmodel(mode.clone()),
+            ));
+        }
+        for governor in &status.cpu_governor_modes {
+            entries.push(PaletteEntry::new(
+                format!("cpu_governor: {}", governor),
+                C::CpuGovernor(governor.clone()),
+            ));
+        }
+        for pct in [0, 30, 50, 80, 100] {
+            entries.push(PaletteEntry::new(format!("Fan {}%", pct), C::Fan(pct)));
+        }
+        if status.supports_fan {
+            for (name, _) in crate::fans::PRESETS.iter().chain(&[("off", "")]) {
+                entries.push(PaletteEntry::new(
+                    format!("fan_curve: {}", name),
+                    C::FanCurve(name.to_string()),
+                ));
+            }
+        }
+        for profile in &self.profiles.profiles {
+            entries.push(PaletteEntry::new(
+                t!("app.palette.profile", name = profile.name),
+                C::ApplyProfile(profile.name.clone()),
+            ));
+        }
+        entries.push(PaletteEntry::new(t!("app.palette.snapshot"), C::ExportSnapshot));
+        entries.push(PaletteEntry::new(
+            t!("app.palette.dump", format = "ANSI"),
+            C::DumpScreen(DumpFormat::Ansi),
+        ));
+        entries.push(PaletteEntry::new(
+            t!("app.palette.dump", format = "HTML"),
+            C::DumpScreen(DumpFormat::Html),
+        ));
+        entries.push(PaletteEntry::new(
+            t!(
+                "app.palette.burst",
+                ms = self.burst_config.interval_ms,
+                secs = self.burst_config.duration_secs
+            ),
+            C::StartBurst,
+        ));
+        entries.push(PaletteEntry::new(
+            t!("app.palette.language", name = t!("lang.name")),
+            C::CycleLanguage,
+        ));
+        entries.push(PaletteEntry::new(t!("app.palette.reconnect"), C::Reconnect));
+        entries.push(PaletteEntry::new(t!("app.palette.help"), C::Help));
+        entries
+    }
+
+    /// Run the highlighted palette entry and close the palette.
+    pub fn run_palette_selection(&mut self) {
+        let Some(palette) = self.palette.take() else {
+            return;
+        };
+        let entries = self.palette_entries();
+        let command = palette
+            .filter(&entries)
+            .get(palette.selected)
+            .map(|e| e.command.clone());
+        if let Some(command) = command {
+            self.request(command);
+        }
+    }
+
+    /// Run `command`, asking first when it changes a control, applies a profile
+    /// or signals a process (`confirm_detail`).
+    pub fn request(&mut self, command: PaletteCommand) {
+        match self.confirm_detail(&command) {
+            Some((question, detail)) if self.confirm_controls => {
+                self.confirm = Some(PendingConfirm {
+                    command,
+                    question,
+                    detail,
+                })
+            }
+            _ => self.run_command(command),
+        }
+    }
+
+    /// Answer the confirmation dialog.
+    pub fn resolve_confirm(&mut self, accept: bool) {
+        let Some(pending) = self.confirm.take() else {
+            return;
+        };
+        if accept {
+            self.run_command(pending.command);
+        } else {
+            self.set_notice(t!("app.cancelled", detail = pending.detail));
+        }
+    }
+
+    /// The dialog's question and the command a control change will run;
+    /// `None` for actions that need no confirmation.
+    fn confirm_detail(&self, command: &PaletteCommand) -> Option<(String, String)> {
+        let status = self.control.status();
+        match command {
+            PaletteCommand::ToggleJetsonClocks => Some(match status.jetson_clocks {
+                Some(true) => (
+                    t!("app.confirm.q.clocks_off").to_string(),
+                    t!("app.confirm.clocks_off").to_string(),
+                ),
+                Some(false) => (
+                    t!("app.confirm.q.clocks_on").to_string(),
+                    t!("app.confirm.clocks_on").to_string(),
+                ),
+                None => (
+                    t!("app.confirm.q.clocks_unknown").to_string(),
+                    t!("app.confirm.clocks_unknown").to_string(),
+                ),
+            }),
+            PaletteCommand::Nvpmodel(mode) => Some((
+                t!("app.confirm.q.nvpmodel", mode = mode),
+                t!(
+                    "app.confirm.nvpmodel",
+                    mode = mode,
+                    current = status.nvpmodel.as_deref().unwrap_or("?")
+                ),
+            )),
+            PaletteCommand::SignalProcess { pid, name } => Some((
+                t!("app.confirm.q.kill", name = name, pid = pid),
+                t!("app.confirm.kill", pid = pid),
+            )),
+            PaletteCommand::ReniceProcess { pid, name, nice } => Some((
+                t!("app.confirm.q.renice", name = name, pid = pid, nice = nice),
+                t!("app.confirm.renice", pid = pid, nice = nice),
+            )),
+            PaletteCommand::ApplyProfile(name) => Some((
+                t!("app.confirm.q.profile", name = name),
+                self.profiles
+                    .profiles
+                    .iter()
+                    .find(|p| &p.name == name)
+                    .map(|p| p.describe())
+                    .unwrap_or_default(),
+            )),
+            PaletteCommand::Fan(pct) => Some((
+                t!("app.confirm.q.fan", pct = pct),
+                t!(
+                    "app.confirm.fan",
+                    pct = pct,
+                    current = status.fan.as_deref().unwrap_or("?")
+                ),
+            )),
+            PaletteCommand::FanCurve(spec) => Some((
+                t!("app.confirm.q.fan_curve", spec = spec),
+                t!("app.confirm.fan_curve", spec = spec),
+            )),
+            PaletteCommand::CpuGovernor(governor) => Some((
+                t!("app.confirm.q.governor", governor = governor),
+                t!(
+                    "app.confirm.governor",
+                    governor = governor,
+                    current = status.cpu_governor.as_deref().unwrap_or("?")
+                ),
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn run_command(&mut self, command: PaletteCommand) {
+        match command {
+            PaletteCommand::View(mode) => self.view_mode = mode,
+            PaletteCommand::CycleHistoryWindow => self.cycle_history_window(),
+            PaletteCommand::CycleGraphStyle => self.cycle_graph_style(),
+            PaletteCommand::CycleLayout => self.cycle_layout(),
+            PaletteCommand::CycleTheme => self.cycle_theme(),
+            PaletteCommand::RefreshFaster => self.adjust_refresh(true),
+            PaletteCommand::RefreshSlower => self.adjust_refresh(false),
+            PaletteCommand::ToggleProcessSort => self.toggle_process_sort(),
+            PaletteCommand::ToggleJetsonClocks => {
+                self.run_control("jetson_clocks".to_string(), |c| c.toggle_jetson_clocks())
+            }
+            PaletteCommand::Nvpmodel(mode) => {
+                self.run_control(format!("nvpmodel {mode}"), move |c| {
+                    c.set_nvpmodel_mode(Some(mode))
+                })
+            }
+            PaletteCommand::CpuGovernor(governor) => {
+                if let Err(e) = self.control.set_cpu_governor(&governor) {
+                    self.set_notice(e.to_string());
+                }
+            }
+            PaletteCommand::Fan(pct) => self.run_control(format!("fan {pct}%"), move |c| c.set_fan(pct)),
+            // The daemon runs the curve, so it keeps going after the TUI exits
+            PaletteCommand::FanCurve(spec) => {
+                if self.connection_status != ConnectionStatus::ConnectedSocket {
+                    self.set_notice(t!("app.fan_curve_needs_daemon").to_string());
+                    return;
+                }
+                self.send_request(Request::SetControl {
+                    control: "fan_curve".to_string(),
+                    value: spec.clone(),
+                    token: auth_token(),
+                    dry_run: false,
+                });
+                self.control_action = Some(format!("fan_curve {spec}"));
+            }
+            PaletteCommand::ApplyProfile(name) => {
+                if self.connection_status != ConnectionStatus::ConnectedSocket {
+                    self.set_notice(t!("app.profile_needs_daemon").to_string());
+                    return;
+                }
+                self.send_request(Request::ApplyProfile {
+                    name: name.clone(),
+                    token: auth_token(),
+                    dry_run: false,
+                });
+                self.profile_action = Some(name);
+            }
+            PaletteCommand::SignalProcess { pid, name } => self.send_process_action(
+                format!("kill {name} ({pid})"),
+                Request::SignalProcess {
+                    pid,
+                    signal: "TERM".to_string(),
+                    token: auth_token(),
+                },
+            ),
+            PaletteCommand::ReniceProcess { pid, name, nice } => self.send_process_action(
+                format!("renice {name} ({pid})"),
+                Request::ReniceProcess {
+                    pid,
+                    nice,
+                    token: auth_token(),
+                },
+            ),
+            PaletteCommand::ExportSnapshot => {
+                let notice = match self.export_snapshot() {
+                    Ok(path) => t!("app.snapshot_saved", path = path),
+                    Err(e) => t!("app.snapshot_failed", error = e),
+                };
+                self.set_notice(notice);
+            }
+            PaletteCommand::DumpScreen(format) => self.dump_requested = Some(format),
+            PaletteCommand::StartBurst => self.start_burst(),
+            PaletteCommand::CycleLanguage => self.cycle_language(),
+            PaletteCommand::Reconnect => self.request_reconnect(),
+            PaletteCommand::Help => self.toggle_help(),
+        }
+    }
+
+    /// Process actions go through the daemon, which runs as root.
+    fn send_process_action(&mut self, label: String, request: Request) {
+        if self.connection_status != ConnectionStatus::ConnectedSocket {
+            self.set_notice(t!("app.process_needs_daemon").to_string());
+            return;
+        }
+        self.send_request(request);
+        self.process_action = Some(label);
+    }
+
+    /// `p`: apply the profile after the active one, after confirmation.
+    pub fn next_profile(&mut self) {
+        let profiles = &self.profiles.profiles;
+        let next = match self
+            .profiles
+            .active
+            .as_ref()
+            .and_then(|active| profiles.iter().position(|p| &p.name == active))
+        {
+            Some(i) => profiles.get((i + 1) % profiles.len()),
+            None => profiles.first(),
+        };
+        match next.map(|p| p.name.clone()) {
+            Some(name) => self.request(PaletteCommand::ApplyProfile(name)),
+            None if self.connection_status != ConnectionStatus::ConnectedSocket => {
+                self.set_notice(t!("app.profile_needs_daemon").to_string())
+            }
+            None => self.set_notice(t!("app.no_profiles").to_string()),
+        }
+    }
+
+    /// `k`: SIGTERM the selected process, after confirmation.
+    pub fn kill_selected_process(&mut self) {
+        if let Some((pid, name)) = self.selected_process.clone() {
+            self.request(PaletteCommand::SignalProcess { pid, name });
+        }
+    }
+
+    /// Enter: memory breakdown of the selected process. Read locally right
+    /// away, then replaced by the daemon's answer, which includes nvmap when
+    /// it runs as root.
+    pub fn open_process_detail(&mut self) {
+        let Some((pid, _)) = self.selected_process.clone() else {
+            return;
+        };
+        let connected = self.connection_status == ConnectionStatus::ConnectedSocket;
+        match processes::process_detail(pid) {
+            Ok(detail) => self.process_detail = Some(detail),
+            Err(e) if !connected => self.set_notice(e.to_string()),
+            Err(_) => {}
+        }
+        if connected {
+            self.send_request(Request::GetProcessDetail { pid });
+            self.process_detail_requested = Some(pid);
+        }
+    }
+
+    pub fn close_process_detail(&mut self) {
+        self.process_detail = None;
+        self.process_detail_requested = None;
+    }
+
+    /// `n`: lower the selected process's priority to `RENICE_NICE`, after confirmation.
+    pub fn renice_selected_process(&mut self) {
+        if let Some((pid, name)) = self.selected_process.clone() {
+            self.request(PaletteCommand::ReniceProcess {
+                pid,
+                name,
+                nice: RENICE_NICE,
+            });
+        }
+    }
+
+    /// Run a blocking control action on a copy of the control state in a
+    /// worker thread; `on_tick` takes over the result. One at a time.
+    fn run_control<F>(&mut self, label: String, action: F)
+    where
+        F: FnOnce(&mut ControlManager) + Send + 'static,
+    {
+        if let Some(pending) = &self.control_pending {
+            self.set_notice(t!("app.control_busy", action = pending.label));
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        let mut control = self.control.clone();
+        std::thread::spawn(move || {
+            action(&mut control);
+            let _ = tx.send(control);
+        });
+        self.control_pending = Some(PendingControl { label, rx });
+    }
+
+    fn poll_control(&mut self) {
+        let Some(pending) = &self.control_pending else {
+            return;
+        };
+        match pending.rx.try_recv() {
+            Ok(done) => {
+                self.control.adopt_action_result(done);
+                self.control_pending = None;
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => self.control_pending = None,
+        }
+    }
+
+    /// Latest stats (and their time in UTC), control state and data source.
+    pub fn snapshot(&self) -> serde_json::Value {
+        serde_json::json!({
+            "time": self.latest_stats.time_rfc3339(),
+            "stats": self.latest_stats,
+            "control": self.control.status(),
+            "source": self.source_label,
+        })
+    }
+
+    /// `snapshot` as JSON in the working directory.
+    fn export_snapshot(&self) -> anyhow::Result<String> {
+        let path = format!(
+            "jetsonscope-snapshot-{}.json",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        );
+        std::fs::write(&path, serde_json::to_string_pretty(&self.snapshot())?)?;
+        Ok(path)
+    }
+
+    /// Write a rendered frame as ANSI/HTML in the working directory and report it in the footer.
+    pub fn save_dump(&mut self, format: DumpFormat, buffer: &ratatui::buffer::Buffer) {
+        let path = format!(
+            "jetsonscope-screen-{}.{}",
+            chrono::Local::now().format("%Y%m%d-%H%M%S"),
+            format.extension()
+        );
+        let notice = match std::fs::write(&path, format.render(buffer)) {
+            Ok(()) => t!("app.screen_saved", path = path),
+            Err(e) => t!("app.screen_failed", error = e),
+        };
+        self.set_notice(notice);
+    }
+
+    /// Ask the daemon for a sub-second capture; it is saved as JSON once finished.
+    pub fn start_burst(&mut self) {
+        if self.burst.is_some() {
+            self.set_notice(t!("app.burst_busy").to_string());
+            return;
+        }
+        self.send_request(Request::StartBurst {
+            interval_ms: self.burst_config.interval_ms,
+            duration_secs: self.burst_config.duration_secs,
+        });
+        self.burst = Some(BurstState::Requested {
+            since: Instant::now(),
+        });
+    }
+
+    /// Advance the burst: fetch the capture once its window is over, give up on silence.
+    fn poll_burst(&mut self) {
+        let now = Instant::now();
+        match self.burst {
+            Some(BurstState::Running { id, until }) if now >= until => {
+                self.send_request(Request::GetBurst { id });
+                self.burst = Some(BurstState::Fetching { id, since: now });
+            }
+            Some(BurstState::Requested { since } | BurstState::Fetching { since, .. })
+                if now.duration_since(since) > BURST_REPLY_TIMEOUT =>
+            {
+                // Non-socket sources never drain the queue; don't fire it later
+                if let Ok(mut queue) = self.requests.lock() {
+                    queue.retain(|r| {
+                        !matches!(r, Request::StartBurst { .. } | Request::GetBurst { .. })
+                    });
+                }
+                self.burst = None;
+                self.set_notice(t!("app.burst_no_reply").to_string());
+            }
+            _ => {}
+        }
     }
 
+    fn on_reply(&mut self, reply: Response) {
+        match reply {
+            Response::History(points) => self.backfill_history(points),
+            Response::Energy(report) => self.daemon_energy = Some(report),
+            Response::GpuProcesses(procs) => {
+                self.gpu_load_by_pid = procs
+                    .iter()
+                    .filter_map(|p| Some((p.pid, p.gpu_percent?)))
+                    .collect();
+            }
+            Response::BurstStarted { id } => {
+                if matches!(self.burst, Some(BurstState::Requested { .. })) {
+                    let secs = self.burst_config.duration_secs;
+                    self.burst = Some(BurstState::Running {
+                        id,
+                        until: Instant::now() + Duration::from_secs(secs),
+                    });
+                }
+            }
+            Response::Burst(capture) if capture.running => {
+                // Still draining the last samples; ask again on the next poll
+                self.send_request(Request::GetBurst { id: capture.id });
+            }
+            Response::Burst(capture) => {
+                self.burst = None;
+                let notice = match save_burst(&capture) {
+                    Ok(path) => t!("app.burst_saved", path = path, samples = capture.samples.len()),
+                    Err(e) => t!("app.burst_failed", error = e),
+                };
+                self.set_notice(notice);
+            }
+            Response::ProcessDetail(detail) if self.process_detail_requested == Some(detail.pid) => {
+                self.process_detail_requested = None;
+                self.process_detail = Some(detail);
+            }
+            Response::ProcessUpdated { pid, action } => {
+                self.process_action = None;
+                self.set_notice(t!("app.process_updated", pid = pid, action = action));
+            }
+            Response::ControlState(info) if self.control_action.is_some() => {
+                self.control_action = None;
+                self.set_notice(t!("app.control_set", control = info.name, value = info.value));
+            }
+            Response::Profiles(list) => self.profiles = list,
+            Response::ProfileApplied { name, .. } => {
+                self.profile_action = None;
+                self.set_notice(t!("app.profile_applied", name = name));
+                self.send_request(Request::ListProfiles);
+            }
+            // History errors just leave the charts empty; burst ones end the capture
+            Response::Error(err)
+                if matches!(
+                    self.burst,
+                    Some(BurstState::Requested { .. } | BurstState::Fetching { .. })
+                ) =>
+            {
+                self.burst = None;
+                self.set_notice(format!("Burst: {}", err.message));
+            }
+            // The local read already failed or succeeded; only say why if nothing is shown
+            Response::Error(err) if self.process_detail_requested.is_some() => {
+                self.process_detail_requested = None;
+                if self.process_detail.is_none() {
+                    self.set_notice(err.message);
+                }
+            }
+            Response::Error(err) if self.process_action.is_some() => {
+                let label = self.process_action.take().unwrap_or_default();
+                self.set_notice(format!("{label}: {}", err.message));
+            }
+            Response::Error(err) if self.control_action.is_some() => {
+                let label = self.control_action.take().unwrap_or_default();
+                self.set_notice(format!("{label}: {}", err.message));
+            }
+            Response::Error(err) if self.profile_action.is_some() => {
+                let name = self.profile_action.take().unwrap_or_default();
+                self.set_notice(format!("{name}: {}", err.message));
+            }
+            _ => {}
+        }
+    }
+
+    pub fn set_notice(&mut self, message: String) {
+        self.notice = Some((message, Instant::now()));
+    }
+
+    pub fn cycle_graph_style(&mut self) {
+        self.graph_style = self.graph_style.next();
+    }
+
+    pub fn cycle_layout(&mut self) {
+        self.layout_mode = self.layout_mode.next();
+        self.set_notice(format!("Layout: {}", self.layout_mode.label()));
+    }
+
+    pub fn cycle_theme(&mut self) {
+        self.theme = self.theme.next();
+        self.set_notice(t!("app.theme", name = self.theme.name()));
+    }
+
+    /// Switch every label to the next catalog language.
+    pub fn cycle_language(&mut self) {
+        i18n::set_lang(i18n::lang().next());
+        self.set_notice(t!("app.language", name = t!("lang.name")));
+    }
+
+    /// Replace the collector with a fresh one, which looks for the socket
+    /// again (e.g. after the daemon restarted or moved it). Queued daemon
+    /// requests and the refresh rate carry over.
     pub fn request_reconnect(&mut self) {
-        self.reconnect_requested = true;
-        self.connection_status = "reconectando...".to_string();
+        self.collector_stop.stop();
+        let queued: Vec<Request> = self
+            .requests
+            .lock()
+            .map(|mut q| q.drain(..).collect())
+            .unwrap_or_default();
+        let collector = start_collector_with_requests(self.collector_mode.clone(), queued);
+        collector
+            .interval_ms
+            .store(self.poll_interval_ms.load(Ordering::Relaxed), Ordering::Relaxed);
+        self.rx = collector.rx;
+        self.poll_interval_ms = collector.interval_ms;
+        self.requests = collector.requests;
+        self.collector_stop = collector.stop;
+
+        self.request_backfill();
+        self.source_label = t!("status.source_connecting").to_string();
+        self.connection_status = ConnectionStatus::Reconnecting;
         self.retry_count = 0;
     }
 
@@ -144,8 +1295,83 @@ impl App {
         self.show_help = !self.show_help;
     }
 
+    /// CPU -> memory -> I/O.
     pub fn toggle_process_sort(&mut self) {
-        self.process_sort_by_mem = !self.process_sort_by_mem;
+        self.process_sort = self.process_sort.next();
+        processes::sort_processes(&mut self.processes, self.process_sort);
+    }
+
+    pub fn toggle_process_users(&mut self) {
+        self.process_by_user = !self.process_by_user;
+    }
+
+    /// Rescan /proc. CPU% is the usage since the previous scan, so the first
+    /// scan reads 0% everywhere.
+    fn refresh_processes(&mut self) {
+        let monitor = self.process_monitor.get_or_insert_with(ProcessMonitor::new);
+        monitor.refresh();
+        self.processes = monitor.processes();
+        processes::sort_processes(&mut self.processes, self.process_sort);
+        self.processes_refreshed = Some(Instant::now());
+        // Keep the selection on the same process as rows move
+        if let Some((pid, _)) = &self.selected_process {
+            if let Some(index) = self.processes.iter().position(|p| p.pid == *pid) {
+                self.process_table.select(Some(index));
+            }
+        }
+    }
+
+    /// Move the process selection by `step` rows, within the drawn list.
+    pub fn select_process(&mut self, step: isize) {
+        if self.process_by_user || self.processes.is_empty() {
+            return;
+        }
+        let next = match self.process_table.selected() {
+            Some(current) => current as isize + step,
+            None => 0,
+        };
+        self.process_table
+            .select(Some(next.clamp(0, self.processes.len() as isize - 1) as usize));
+    }
+
+    /// Rows PageUp/PageDown move: one page of the table as last drawn.
+    pub fn process_page(&self) -> isize {
+        self.click_areas
+            .process_rows
+            .map_or(10, |rows| rows.height.max(1) as isize)
+    }
+
+    /// Tab clicks switch views; in the Processes view a click selects a row
+    /// and the wheel moves the selection.
+    pub fn on_mouse(&mut self, mouse: MouseEvent) {
+        if self.confirm.is_some() || self.palette.is_some() || self.process_detail.is_some() {
+            return;
+        }
+        let hit = |area: &Rect| area.contains(Position::new(mouse.column, mouse.row));
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some((mode, _)) = self.click_areas.tabs.iter().find(|(_, area)| hit(area)) {
+                    self.view_mode = *mode;
+                    return;
+                }
+                if self.view_mode != ViewMode::Processes {
+                    return;
+                }
+                if let Some(rows) = self.click_areas.process_rows.filter(hit) {
+                    let index = self.process_table.offset() + (mouse.row - rows.y) as usize;
+                    if index < self.processes.len() {
+                        self.process_table.select(Some(index));
+                    }
+                }
+            }
+            MouseEventKind::ScrollDown if self.view_mode == ViewMode::Processes => {
+                self.select_process(WHEEL_ROWS)
+            }
+            MouseEventKind::ScrollUp if self.view_mode == ViewMode::Processes => {
+                self.select_process(-WHEEL_ROWS)
+            }
+            _ => {}
+        }
     }
 
     pub fn cycle_view(&mut self) {
@@ -153,54 +1379,77 @@ impl App {
             ViewMode::Dashboard => ViewMode::Processes,
             ViewMode::Processes => ViewMode::GpuEngines,
             ViewMode::GpuEngines => ViewMode::Clocks,
-            ViewMode::Clocks => ViewMode::Dashboard,
+            ViewMode::Clocks => ViewMode::Temperatures,
+            ViewMode::Temperatures => ViewMode::Power,
+            ViewMode::Power => ViewMode::Dashboard,
         };
     }
 
     pub fn on_tick(&mut self) {
         self.tick_count += 1;
 
+        // Residency counters move slowly; refresh about once per second
+        if self
+            .residency_refreshed
+            .map_or(true,|t| t.elapsed() >= Duration::from_secs(1))
+        {
+            self.refresh_residency();
+        }
+
+        // A full process scan is expensive on small boards; only the
+        // Processes view shows it
+        if self.view_mode == ViewMode::Processes
+            && self
+                .processes_refreshed
+                .map_or(true,|t| t.elapsed() >= PROCESS_REFRESH)
+        {
+            self.refresh_processes();
+        }
+
         // Check for new stats
         while let Ok(event) = self.rx.try_recv() {
             match event {
                 CollectorMessage::Stats(stats) => {
+                    self.energy.record(&stats, Instant::now());
+                    self.extremes.record(&stats, chrono::Local::now());
+                    self.summaries.push(&stats, now_unix_ms());
+                    self.link.record_sample(Instant::now());
                     self.latest_stats = stats.clone();
                     self.stats_history.push(stats.clone());
                     self.last_update_tick = self.tick_count;
                     self.retry_count = 0;
-                    self.connection_status = "conectado".to_string();
+                    self.connection_status = ConnectionStatus::Connected;
                     
                     // Update history with timestamps
                     let now = Instant::now();
-                    let ram_pct = stats.ram.as_ref().map_or(0.0, |r| {
-                        if r.total_bytes == 0 { 0.0 } else { r.used_bytes as f64 / r.total_bytes as f64 * 100.0 }
-                    });
-                    let gpu_pct = stats.gpu_usage().map_or(0.0, |g| g as f64);
-                    let cpu_pct = if stats.cpus.is_empty() {
-                        0.0
-                    } else {
-                        let sum: f32 = stats.cpus.iter()
-                            .filter_map(|c| c.load_percent)
-                            .map(|v| v as f32)
-                            .sum();
-                        sum as f64 / stats.cpus.len() as f64
-                    };
-                    
-                    self.history.ram.push_back((now, ram_pct));
-                    self.history.gpu.push_back((now, gpu_pct));
-                    self.history.cpu.push_back((now, cpu_pct));
-                    
-                    // Trim to max points for current window
-                    let max_points = self.history_window.max_points();
-                    while self.history.ram.len() > max_points {
-                        self.history.ram.pop_front();
+                    let point = HistoryPoint::from_stats(&stats, now_unix_ms());
+                    self.history.ram.push_back((now, point.ram));
+                    self.history.gpu.push_back((now, point.gpu));
+                    self.history.cpu.push_back((now, point.cpu));
+                    self.history.temp.push_back((now, point.temp));
+                    for (name, temp) in stats.temps.iter().filter(|(_, t)| valid_temp(**t)) {
+                        self.history
+                            .temps
+                            .entry(name.clone())
+                            .or_default()
+                            .push_back((now, *temp as f64));
                     }
-                    while self.history.gpu.len() > max_points {
-                        self.history.gpu.pop_front();
+                    for (name, rail) in &stats.power {
+                        self.history
+                            .rails
+                            .entry(name.clone())
+                            .or_default()
+                            .push_back((now, rail.current_mw as f64 / 1000.0));
                     }
-                    while self.history.cpu.len() > max_points {
-                        self.history.cpu.pop_front();
+                    if self.history.cores.len() < stats.cpus.len() {
+                        self.history.cores.resize_with(stats.cpus.len(), VecDeque::new);
+                    }
+                    for (series, core) in self.history.cores.iter_mut().zip(&stats.cpus) {
+                        if let Some(load) = core.load_percent {
+                            series.push_back((now, load as f64));
+                        }
                     }
+                    self.trim_history();
                     
                     if self.stats_history.len() > 100 {
                         self.stats_history.remove(0);
@@ -209,14 +1458,20 @@ impl App {
                 CollectorMessage::SourceLabel(label) => {
                     self.source_label = label.clone();
                     if label.contains("synthetic") {
-                        self.connection_status = "modo demo (sintético)".to_string();
+                        self.connection_status = ConnectionStatus::Demo;
                     } else if label.contains("socket") {
-                        self.connection_status = "conectado (socket)".to_string();
+                        self.connection_status = ConnectionStatus::ConnectedSocket;
                     } else {
-                        self.connection_status = "conectado".to_string();
+                        self.connection_status = ConnectionStatus::Connected;
                     }
                 }
+                CollectorMessage::Poll { rtt, fresh } => self.link.record_poll(rtt, fresh),
+                CollectorMessage::ParseFailed(_) | CollectorMessage::FormatDrift(_) => {}
+                CollectorMessage::Reply(reply) => self.on_reply(reply),
                 CollectorMessage::Error(err) => {
+                    self.link.record_failure();
+                    // Charts missed samples while the daemon was away; refill on reconnect
+                    self.request_backfill();
                     // Parse retry info from error message
                     if err.contains("retry") || err.contains("Retrying") {
                         // Extract retry count if present
@@ -225,21 +1480,43 @@ impl App {
                                 self.retry_count = num.parse().unwrap_or(0);
                             }
                         }
-                        self.connection_status = format!("reintentando ({}/5)", self.retry_count);
+                        self.connection_status = ConnectionStatus::Retrying(self.retry_count);
                     } else if err.contains("Max retries") || err.contains("fallback") {
-                        self.connection_status = "offline (max reintentos)".to_string();
+                        self.connection_status = ConnectionStatus::Offline;
                     } else {
-                        self.connection_status = format!("error: {}", err);
+                        self.connection_status = ConnectionStatus::Error(err);
                     }
                 }
             }
         }
 
+        self.poll_burst();
+        self.poll_control();
+
+        // GPU attribution needs root on the daemon side; refresh it while the Processes view is open
+        if self.view_mode == ViewMode::Processes
+            && self
+                .gpu_procs_requested
+                .map_or(true,|t| t.elapsed() >= Duration::from_secs(2))
+        {
+            self.send_request(Request::GetGpuProcesses);
+            self.gpu_procs_requested = Some(Instant::now());
+        }
+        if self.view_mode == ViewMode::Power
+            && self
+                .energy_requested
+                .map_or(true,|t| t.elapsed() >= Duration::from_secs(2))
+        {
+            self.send_request(Request::GetEnergy);
+            self.energy_requested = Some(Instant::now());
+        }
+
         // Timeout detection
-        if self.tick_count.saturating_sub(self.last_update_tick) > 50 {
-            // ~5s sin datos
-            if self.connection_status.starts_with("conectado") {
-                self.connection_status = "sin datos (timeout)".to_string();
+        let timeout = self.refresh_interval().mul_f64(3.0).max(Duration::from_secs(5));
+        if self.tick_count.saturating_sub(self.last_update_tick) > self.ticks_for(timeout) {
+            // ~5s sin datos (o tres lecturas perdidas con refresco lento)
+            if self.connection_status.is_connected() {
+                self.connection_status = ConnectionStatus::NoData;
             }
         }
     }
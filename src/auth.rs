@@ -0,0 +1,214 @@
+//! Named daemon tokens with a scope: `read` tokens see stats, health and the
+//! rest of the read-only API; `admin` tokens may also change controls.
+//!
+//! JETSONSCOPE_AUTH_TOKENS lists them as `name:scope:secret`, separated by
+//! commas (e.g. `grafana:read:s3cret,ops:admin:0th3r`). The single
+//! JETSONSCOPE_AUTH_TOKEN (or TEGRA_AUTH_TOKEN) still works as an admin token
+//! named "default". Names show up in the control audit log instead of secrets.
+
+use crate::t;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+/// What a token may do; `Admin` includes everything `Read` allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    Read,
+    Admin,
+}
+
+impl Scope {
+    fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "read" | "ro" => Some(Scope::Read),
+            "admin" | "control" => Some(Scope::Admin),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedToken {
+    pub name: String,
+    pub scope: Scope,
+    secret: String,
+}
+
+/// Outcome of checking a request's token against the configured ones.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Access {
+    /// No token is configured for this scope
+    Open,
+    /// The token of this name allows it
+    Granted(String),
+    Denied,
+}
+
+impl Access {
+    pub fn allowed(&self) -> bool {
+        !matches!(self, Access::Denied)
+    }
+
+    /// Token name for the audit log ("anonymous" when access is open).
+    pub fn who(&self) -> &str {
+        match self {
+            Access::Granted(name) => name,
+            _ => "anonymous",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TokenSet {
+    tokens: Vec<NamedToken>,
+}
+
+impl TokenSet {
+    pub fn new(tokens: Vec<NamedToken>) -> Self {
+        TokenSet { tokens }
+    }
+
+    /// JETSONSCOPE_AUTH_TOKENS plus the legacy single token; bad entries are
+    /// reported and skipped.
+    pub fn from_env() -> Self {
+        let mut tokens = Vec::new();
+        if let Ok(spec) = std::env::var("JETSONSCOPE_AUTH_TOKENS") {
+            for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+                match parse_entry(entry) {
+                    Ok(token) => tokens.push(token),
+                    Err(e) => eprintln!("JETSONSCOPE_AUTH_TOKENS: {e}"),
+                }
+            }
+        }
+        let legacy = std::env::var("JETSONSCOPE_AUTH_TOKEN")
+            .or_else(|_| std::env::var("TEGRA_AUTH_TOKEN"))
+            .ok()
+            .filter(|t| !t.is_empty());
+        if let Some(secret) = legacy {
+            tokens.push(NamedToken {
+                name: "default".to_string(),
+                scope: Scope::Admin,
+                secret,
+            });
+        }
+        TokenSet { tokens }
+    }
+
+    /// No tokens at all: every request is allowed, as before tokens existed.
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Reads need a token once any `read` token is configured.
+    pub fn restricts_reads(&self) -> bool {
+        self.tokens.iter().any(|t| t.scope == Scope::Read)
+    }
+
+    /// The configured token `token` is, if it allows `scope`.
+    pub fn find(&self, token: Option<&str>, scope: Scope) -> Option<&NamedToken> {
+        let token = token?;
+        self.tokens
+            .iter()
+            .find(|t| t.secret == token && t.scope >= scope)
+    }
+
+    /// Controls are open only while no token is configured; reads only while
+    /// no `read` token is.
+    pub fn check(&self, token: Option<&str>, scope: Scope) -> Access {
+        let open = match scope {
+            Scope::Read => !self.restricts_reads(),
+            Scope::Admin => self.is_empty(),
+        };
+        match self.find(token, scope) {
+            Some(t) => Access::Granted(t.name.clone()),
+            None if open => Access::Open,
+            None => Access::Denied,
+        }
+    }
+
+    /// Admin access for a client; with no token configured, only local
+    /// (Unix socket) clients are let in, never `remote` (TCP) ones.
+    pub fn check_admin(&self, token: Option<&str>, remote: bool) -> Access {
+        if remote && self.is_empty() {
+            return Access::Denied;
+        }
+        self.check(token, Scope::Admin)
+    }
+
+    /// Signalling or renicing a process always takes a configured admin token:
+    /// a root daemon would otherwise kill any pid for any client.
+    pub fn check_process(&self, token: Option<&str>) -> Access {
+        match self.check(token, Scope::Admin) {
+            Access::Open => Access::Denied,
+            access => access,
+        }
+    }
+}
+
+/// One `name:scope:secret` entry. Errors name the entry, never the secret.
+pub fn parse_entry(entry: &str) -> Result<NamedToken> {
+    let mut parts = entry.splitn(3, ':');
+    let name = parts.next().unwrap_or_default();
+    let (Some(scope), Some(secret)) = (parts.next(), parts.next()) else {
+        bail!(t!("auth.err.entry", name = name));
+    };
+    let Some(scope) = Scope::parse(scope) else {
+        bail!(t!("auth.err.scope", scope = scope));
+    };
+    if name.trim().is_empty() || secret.is_empty() {
+        bail!(t!("auth.err.entry", name = name));
+    }
+    Ok(NamedToken {
+        name: name.trim().to_string(),
+        scope,
+        secret: secret.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scopes_gate_reads_and_controls() {
+        let tokens = TokenSet::new(vec![
+            parse_entry("grafana:read:r1").unwrap(),
+            parse_entry("ops:admin:a:1").unwrap(),
+        ]);
+        assert_eq!(
+            tokens.check(Some("r1"), Scope::Read),
+            Access::Granted("grafana".into())
+        );
+        assert_eq!(
+            tokens.check(Some("a:1"), Scope::Read),
+            Access::Granted("ops".into())
+        );
+        assert_eq!(tokens.check(Some("r1"), Scope::Admin), Access::Denied);
+        assert_eq!(tokens.check(Some("a:1"), Scope::Admin).who(), "ops");
+        assert_eq!(tokens.check(None, Scope::Read), Access::Denied);
+
+        // Admin tokens alone leave reads open but close controls
+        let admin_only = TokenSet::new(vec![parse_entry("ops:admin:x").unwrap()]);
+        assert_eq!(admin_only.check(None, Scope::Read), Access::Open);
+        assert!(!admin_only.check(Some("y"), Scope::Admin).allowed());
+        assert_eq!(
+            TokenSet::default().check(None, Scope::Admin).who(),
+            "anonymous"
+        );
+
+        // Without tokens, the network never gets controls
+        assert_eq!(TokenSet::default().check_admin(None, false), Access::Open);
+        assert_eq!(TokenSet::default().check_admin(None, true), Access::Denied);
+        assert_eq!(admin_only.check_admin(Some("x"), true).who(), "ops");
+        // Process actions are never open
+        assert_eq!(TokenSet::default().check_process(None), Access::Denied);
+        assert_eq!(TokenSet::default().check_process(Some("x")), Access::Denied);
+        assert_eq!(admin_only.check_process(Some("x")).who(), "ops");
+        assert_eq!(tokens.check_process(Some("r1")), Access::Denied);
+
+        assert!(parse_entry("nosecret:read").is_err());
+        assert!(parse_entry("x:root:s").is_err());
+        assert!(parse_entry(":read:s").is_err());
+    }
+}
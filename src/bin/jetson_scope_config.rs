@@ -1,32 +1,41 @@
-use jetsonscope::protocol::{ControlInfo, Request, Response};
-use std::io::{Read, Write};
-use std::os::unix::net::UnixStream;
-use std::path::PathBuf;
+use jetsonscope::client::Client;
+use jetsonscope::idle::parse_profile;
 
 fn main() -> anyhow::Result<()> {
-    let args: Vec<String> = std::env::args().collect();
+    let mut args: Vec<String> = std::env::args().collect();
+    let dry_run = args.iter().any(|a| a == "--dry-run");
+    args.retain(|a| a != "--dry-run");
     if args.len() <= 1 {
         print_usage();
         return Ok(());
     }
 
-    let path = socket_path();
+    let mut client = Client::connect()?;
     let command = args[1].as_str();
     match command {
-        "list" => list_controls(&path)?,
-        "preset" => {
+        "list" => list_controls(&mut client)?,
+        "profiles" => list_profiles(&mut client)?,
+        "preset" | "profile" => {
             if args.len() < 3 {
-                eprintln!("Usage: jetson_scope_config preset <performance|balanced>");
+                eprintln!("Usage: jetson_scope_config profile <name>");
                 return Ok(());
             }
-            apply_preset(&path, &args[2])?;
+            apply_profile(&mut client, &args[2], dry_run)?;
+        }
+        "save" => {
+            if args.len() < 4 {
+                eprintln!("Usage: jetson_scope_config save <name> <control=value,...>");
+                return Ok(());
+            }
+            client.save_profile(&args[2], &parse_profile(&args[3]))?;
+            println!("saved profile {}", args[2]);
         }
         "set" => {
             if args.len() < 4 {
                 eprintln!("Usage: jetson_scope_config set <control> <value>");
                 return Ok(());
             }
-            set_control(&path, &args[2], &args[3])?;
+            set_control(&mut client, &args[2], &args[3], dry_run);
         }
         _ => print_usage(),
     }
@@ -37,105 +46,62 @@ fn main() -> anyhow::Result<()> {
 fn print_usage() {
     println!("jetson_scope_config commands:");
     println!("  list                            # list controls");
-    println!("  preset performance|balanced     # apply preset");
+    println!("  profiles                        # list the daemon's profiles");
+    println!("  profile <name>                  # apply a profile (e.g. performance, balanced)");
+    println!("  save <name> <control=value,...> # store a profile in the daemon");
     println!("  set <control> <value>           # set specific control");
+    println!("  --dry-run                       # with profile/set: show what would run, change nothing");
     println!("Controls include: jetson_clocks, nvpmodel, fan, cpu_governor, gpu_governor, gpu_railgate");
 }
 
-fn socket_path() -> PathBuf {
-    std::env::var("JETSONSCOPE_SOCKET_PATH")
-        .or_else(|_| std::env::var("TEGRA_SOCKET_PATH"))
-        .map(PathBuf::from)
-        .unwrap_or_else(|_| PathBuf::from("/tmp/jetsonscope.sock"))
-}
-
-fn list_controls(path: &PathBuf) -> anyhow::Result<()> {
-    let mut stream = UnixStream::connect(path)?;
-    let req = Request::ListControls;
-    let json = serde_json::to_string(&req)?;
-    stream.write_all(json.as_bytes())?;
-    let mut buf = Vec::new();
-    stream.read_to_end(&mut buf)?;
-    let resp: Response = serde_json::from_slice(&buf)?;
-    match resp {
-        Response::Controls(list) => {
-            for c in list {
-                println!(
-                    "- {} (value: {}, options: {:?}, sudo: {})",
-                    c.name, c.value, c.options, c.requires_sudo
-                );
-            }
-        }
-        other => println!("Unexpected response: {:?}", other),
+fn list_controls(client: &mut Client) -> anyhow::Result<()> {
+    for c in client.list_controls()? {
+        println!(
+            "- {} (value: {}, options: {:?}, sudo: {})",
+            c.name, c.value, c.options, c.requires_sudo
+        );
     }
     Ok(())
 }
 
-fn apply_preset(path: &PathBuf, preset: &str) -> anyhow::Result<()> {
-    let mut stream = UnixStream::connect(path)?;
-    // list controls first
-    let req = Request::ListControls;
-    let json = serde_json::to_string(&req)?;
-    stream.write_all(json.as_bytes())?;
-    let mut buf = Vec::new();
-    stream.read_to_end(&mut buf)?;
-    let resp: Response = serde_json::from_slice(&buf)?;
-    let controls = match resp {
-        Response::Controls(list) => list,
-        other => {
-            eprintln!("Unexpected response: {:?}", other);
-            return Ok(());
-        }
-    };
-    match preset {
-        "performance" => {
-            if has_control(&controls, "jetson_clocks") {
-                set_control(path, "jetson_clocks", "on")?;
-            }
-            if has_control(&controls, "cpu_governor") {
-                set_control(path, "cpu_governor", "performance")?;
-            }
-            if has_control(&controls, "gpu_governor") {
-                set_control(path, "gpu_governor", "performance")?;
-            }
-        }
-        "balanced" => {
-            if has_control(&controls, "jetson_clocks") {
-                set_control(path, "jetson_clocks", "off")?;
-            }
-            if has_control(&controls, "cpu_governor") {
-                set_control(path, "cpu_governor", "ondemand")?;
-            }
-            if has_control(&controls, "gpu_governor") {
-                set_control(path, "gpu_governor", "nvhost_podgov")?;
-            }
+/// Profiles live in the daemon (`performance` and `balanced` are built in).
+fn apply_profile(client: &mut Client, name: &str, dry_run: bool) -> anyhow::Result<()> {
+    if dry_run {
+        let actions = client.plan_profile(name)?;
+        if actions.is_empty() {
+            println!("would apply {} -> no commands", name);
         }
-        other => {
-            eprintln!("Unknown preset: {}", other);
+        for action in actions {
+            println!("would apply {} -> {}", name, action);
         }
+        return Ok(());
+    }
+    for c in client.apply_profile(name)? {
+        println!("set {}={} ({})", c.name, c.value, name);
     }
     Ok(())
 }
 
-fn set_control(path: &PathBuf, name: &str, value: &str) -> anyhow::Result<()> {
-    let token = std::env::var("JETSONSCOPE_AUTH_TOKEN")
-        .or_else(|_| std::env::var("TEGRA_AUTH_TOKEN"))
-        .ok();
-    let mut stream = UnixStream::connect(path)?;
-    let req = Request::SetControl {
-        control: name.to_string(),
-        value: value.to_string(),
-        token,
-    };
-    let json = serde_json::to_string(&req)?;
-    stream.write_all(json.as_bytes())?;
-    let mut buf = Vec::new();
-    stream.read_to_end(&mut buf)?;
-    let resp: Response = serde_json::from_slice(&buf)?;
-    println!("set {}={} -> {:?}", name, value, resp);
+fn list_profiles(client: &mut Client) -> anyhow::Result<()> {
+    let list = client.list_profiles()?;
+    for p in &list.profiles {
+        let active = if list.active.as_deref() == Some(p.name.as_str()) { " (active)" } else { "" };
+        println!("- {}{}: {}", p.name, active, p.describe());
+    }
     Ok(())
 }
 
-fn has_control(list: &[ControlInfo], name: &str) -> bool {
-    list.iter().any(|c| c.name == name)
+fn set_control(client: &mut Client, name: &str, value: &str, dry_run: bool) {
+    if dry_run {
+        match client.plan_control(name, value) {
+            Ok(actions) if actions.is_empty() => println!("would set {}={} -> no commands", name, value),
+            Ok(actions) => println!("would set {}={} -> {}", name, value, actions.join("; ")),
+            Err(e) => println!("would set {}={} -> {}", name, value, e),
+        }
+        return;
+    }
+    match client.set_control(name, value) {
+        Ok(c) => println!("set {}={} -> {}", name, value, c.value),
+        Err(e) => println!("set {}={} -> {}", name, value, e),
+    }
 }
@@ -0,0 +1,53 @@
+use jetsonscope::emulator::{BoardModel, Emulator};
+use std::io::Write;
+use std::time::Duration;
+
+/// Prints tegrastats-format lines for an emulated board, like
+/// `tegrastats --interval MS`, so it can stand in via JETSONSCOPE_STATS_CMD.
+fn main() -> anyhow::Result<()> {
+    let mut model = BoardModel::from_env();
+    let mut interval_ms: u64 = 1000;
+    let mut count: Option<u64> = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| {
+            args.next()
+                .ok_or_else(|| anyhow::anyhow!("{flag} needs a value"))
+        };
+        match arg.as_str() {
+            "--model" => {
+                let name = value("--model")?;
+                model = BoardModel::parse(&name)
+                    .ok_or_else(|| anyhow::anyhow!("unknown model {name}; try --help"))?;
+            }
+            "--interval" => interval_ms = value("--interval")?.parse()?,
+            "--count" => count = Some(value("--count")?.parse()?),
+            "-h" | "--help" => {
+                print_usage();
+                return Ok(());
+            }
+            other => anyhow::bail!("unknown argument {other}; try --help"),
+        }
+    }
+
+    let mut emulator = Emulator::new(model);
+    let mut stdout = std::io::stdout().lock();
+    let mut printed = 0;
+    while count.map_or(true,|c| printed < c) {
+        // A closed pipe (consumer exited) ends the emulator
+        if writeln!(stdout, "{}", emulator.next_line()).is_err() || stdout.flush().is_err() {
+            break;
+        }
+        printed += 1;
+        std::thread::sleep(Duration::from_millis(interval_ms));
+    }
+    Ok(())
+}
+
+fn print_usage() {
+    let models: Vec<&str> = BoardModel::ALL.iter().map(|m| m.name()).collect();
+    println!("jscope-emulator [--model MODEL] [--interval MS] [--count N]");
+    println!("  --model MODEL    {} (default: JETSONSCOPE_EMULATOR_MODEL or orin-nx)", models.join(", "));
+    println!("  --interval MS    time between lines (default 1000)");
+    println!("  --count N        exit after N lines");
+}
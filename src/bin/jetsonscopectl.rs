@@ -1,126 +1,671 @@
 use std::env;
-use std::io::{Read, Write};
-use std::os::unix::net::UnixStream;
-use std::path::PathBuf;
 
-use jetsonscope::protocol::{Request, Response};
+use jetsonscope::processes::ProcessSort;
+use jetsonscope::client::{self, Client};
+use jetsonscope::protocol::{Request, Response, VersionInfo};
+use jetsonscope::t;
+use jetsonscope::thermal_governor::valid_temp;
 
-fn resolve_socket_path() -> PathBuf {
-    // Prefer new env var, fall back to legacy, then defaults with legacy compatibility.
-    let sock = env::var("JETSONSCOPE_SOCKET_PATH")
-        .or_else(|_| env::var("TEGRA_SOCKET_PATH"))
-        .unwrap_or_else(|_| "/tmp/jetsonscope.sock".to_string());
-    let candidate = PathBuf::from(sock.clone());
-    if candidate.exists() {
-        return candidate;
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        t!("common.yes")
+    } else {
+        t!("common.no")
+    }
+}
+
+/// "now", "24h" (that long ago), "2026-10-15T08:00" (local time) or ms since the Unix epoch.
+fn parse_time(arg: &str, now_ms: u64) -> anyhow::Result<u64> {
+    if arg == "now" {
+        return Ok(now_ms);
+    }
+    if let Ok(ago) = jetsonscope::stress::parse_duration(arg) {
+        return Ok(now_ms.saturating_sub(ago.as_millis() as u64));
+    }
+    if let Ok(ms) = arg.parse::<u64>() {
+        return Ok(ms);
+    }
+    chrono::NaiveDateTime::parse_from_str(arg, "%Y-%m-%dT%H:%M")
+        .ok()
+        .and_then(|t| t.and_local_timezone(chrono::Local).earliest())
+        .map(|t| t.timestamp_millis().max(0) as u64)
+        .ok_or_else(|| anyhow::anyhow!(t!("cli.bad_time", value = arg)))
+}
+
+/// Removes `--host HOST[:PORT]` (or `--host=...`) from `args` and records it
+/// in JETSONSCOPE_HOST, which `connect` reads.
+fn take_host_flag(args: &mut Vec<String>) -> anyhow::Result<()> {
+    let Some(i) = args.iter().position(|a| a == "--host" || a.starts_with("--host=")) else {
+        return Ok(());
+    };
+    let host = match args.remove(i).strip_prefix("--host=") {
+        Some(host) => host.to_string(),
+        None if i < args.len() => args.remove(i),
+        None => anyhow::bail!(t!("cli.usage.host")),
+    };
+    env::set_var("JETSONSCOPE_HOST", host);
+    Ok(())
+}
+
+/// Framed connection to the daemon, reused for every request of a command:
+/// over TCP to JETSONSCOPE_HOST (`--host`) when set, else the local socket.
+/// TCP connections authenticate first when a token is set, so daemons with
+/// read-scoped tokens answer the reads too.
+fn connect() -> anyhow::Result<Client> {
+    if let Ok(host) = env::var("JETSONSCOPE_HOST").map(|h| h.trim().to_string()) {
+        if !host.is_empty() {
+            let mut client = Client::connect_tcp(&host, client::cbor_from_env())
+                .map_err(|e| anyhow::anyhow!(t!("cli.host_unreachable", host = host, error = e.root_cause())))?
+                .with_token(client::auth_token_from_env());
+            client.authenticate()?;
+            return Ok(client);
+        }
+    }
+    let path = client::socket_path();
+    if !path.exists() {
+        anyhow::bail!(t!("cli.socket_missing", path = path.display()));
+    }
+    Ok(Client::connect_unix(&path, client::cbor_from_env())?.with_token(client::auth_token_from_env()))
+}
+
+/// `smi`: nvidia-smi style table from stats, meta, controls and GPU processes.
+fn smi(conn: &mut Client) -> anyhow::Result<()> {
+    // Pipelined: all four requests go out before the first reply is read
+    for req in [
+        Request::GetStats,
+        Request::GetMeta,
+        Request::ListControls,
+        Request::GetGpuProcesses,
+    ] {
+        conn.send(&req)?;
+    }
+    let stats = match conn.recv()? {
+        Response::Stats { data, .. } => data,
+        _ => None,
+    };
+    let hw = match conn.recv()? {
+        Response::Meta(hw) => hw,
+        _ => Default::default(),
+    };
+    let controls = match conn.recv()? {
+        Response::Controls(c) => c,
+        _ => Vec::new(),
+    };
+    let procs = match conn.recv()? {
+        Response::GpuProcesses(p) => p,
+        _ => Vec::new(),
+    };
+    let now = chrono::Local::now().format("%a %b %e %H:%M:%S %Y").to_string();
+    println!(
+        "{}",
+        jetsonscope::smi::render(&now, stats.as_ref(), &hw, &controls, &procs)
+    );
+    Ok(())
+}
+
+/// `burst [ms] [secs]`: run a sub-second capture and print it as JSON once done.
+fn burst(conn: &mut Client, interval_ms: u64, duration_secs: u64) -> anyhow::Result<()> {
+    let id = match conn.request(&Request::StartBurst {
+        interval_ms,
+        duration_secs,
+    })? {
+        Response::BurstStarted { id } => id,
+        Response::Error(err) => anyhow::bail!("Error [{}]: {}", err.code, err.message),
+        _ => anyhow::bail!(t!("cli.unexpected", request = "StartBurst")),
+    };
+    eprintln!(
+        "{}",
+        t!(
+            "cli.burst_running",
+            id = id,
+            ms = interval_ms,
+            secs = duration_secs
+        )
+    );
+    std::thread::sleep(std::time::Duration::from_secs(duration_secs));
+    loop {
+        match conn.request(&Request::GetBurst { id })? {
+            Response::Burst(capture) if !capture.running => {
+                println!("{}", serde_json::to_string_pretty(&capture)?);
+                return Ok(());
+            }
+            Response::Burst(_) => std::thread::sleep(std::time::Duration::from_millis(200)),
+            Response::Error(err) => anyhow::bail!("Error [{}]: {}", err.code, err.message),
+            _ => anyhow::bail!(t!("cli.unexpected", request = "GetBurst")),
+        }
     }
-    let legacy = PathBuf::from("/tmp/tegrastats.sock");
-    if legacy.exists() {
-        return legacy;
+}
+
+/// `stress --cpu 100% --gpu --duration 10m`: load the board while the daemon records a session.
+fn stress(conn: &mut Client, args: &[String]) -> anyhow::Result<()> {
+    use jetsonscope::stress::{self, StressConfig, Workload};
+    use std::time::{Duration, Instant};
+
+    let cfg = StressConfig::parse_args(args)?;
+    let id = match conn.request(&Request::StartSession {
+        label: Some(cfg.label()),
+    })? {
+        Response::SessionStarted { id } => id,
+        Response::Error(err) => anyhow::bail!("Error [{}]: {}", err.code, err.message),
+        _ => anyhow::bail!(t!("cli.unexpected", request = "StartSession")),
+    };
+    eprintln!(
+        "{}",
+        t!(
+            "cli.stress_started",
+            id = id,
+            label = cfg.label(),
+            secs = cfg.duration.as_secs()
+        )
+    );
+    let mut workload = Workload::start(&cfg)?;
+    let started = Instant::now();
+    let mut missed_polls = 0;
+    let mut gpu_reported = false;
+    while let Some(left) = cfg.duration.checked_sub(started.elapsed()) {
+        std::thread::sleep(left.min(Duration::from_secs(5)));
+        match conn.request(&Request::GetStats) {
+            Ok(Response::Stats {
+                data: Some(stats), ..
+            }) => {
+                let hottest = stats
+                    .temps
+                    .iter()
+                    .filter(|(_, t)| valid_temp(**t))
+                    .max_by(|a, b| a.1.total_cmp(b.1))
+                    .map(|(name, t)| format!("{} {:.1}C", name, t))
+                    .unwrap_or_default();
+                eprintln!(
+                    "[{:>4}s] GPU {:>3}%  {}",
+                    started.elapsed().as_secs(),
+                    stats.gpu_usage().unwrap_or(0),
+                    hottest
+                );
+            }
+            Ok(_) => missed_polls += 1,
+            Err(_) => {
+                missed_polls += 1;
+                // The stream may be out of step after a failed exchange
+                if let Ok(fresh) = connect() {
+                    *conn = fresh;
+                }
+            }
+        }
+        if let Some(reason) = workload.gpu_exit().filter(|_| !gpu_reported) {
+            eprintln!("{}", reason);
+            gpu_reported = true;
+        }
+    }
+    let gpu_exit = workload.stop();
+
+    let summary = match conn.request(&Request::StopSession { id })? {
+        Response::SessionSummary(summary) => summary,
+        Response::Error(err) => anyhow::bail!("Error [{}]: {}", err.code, err.message),
+        _ => anyhow::bail!(t!("cli.unexpected", request = "StopSession")),
+    };
+    let report = stress::evaluate(&cfg, &summary, gpu_exit, missed_polls);
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    if !report.stable {
+        std::process::exit(1);
     }
-    candidate
+    Ok(())
 }
 
-fn use_cbor() -> bool {
-    env::var("JETSONSCOPE_PROTO")
-        .or_else(|_| env::var("TEGRA_PROTO"))
-        .map(|v| v.to_ascii_lowercase() == "cbor")
-        .unwrap_or(false)
+/// `version`: this client's and the daemon's versions. Daemons that predate
+/// GetVersion answer it like GetStats, without a protocol version.
+fn version(conn: &mut Client) -> anyhow::Result<()> {
+    let info = VersionInfo::current();
+    println!("jscopectl {} ({} {})", info.crate_version, t!("cli.version.protocol"), info.protocol_version);
+    match conn.request(&Request::GetVersion)? {
+        Response::Version(daemon) => {
+            println!(
+                "jscoped {} ({} {})",
+                daemon.crate_version,
+                t!("cli.version.protocol"),
+                daemon.protocol_version
+            );
+            println!("{}: {}", t!("cli.version.features"), daemon.features.join(", "));
+        }
+        Response::Error(err) => anyhow::bail!("Error [{}]: {}", err.code, err.message),
+        _ => println!(
+            "jscoped: {}",
+            t!("cli.version.unversioned", protocol = conn.protocol_version().unwrap_or_default())
+        ),
+    }
+    Ok(())
 }
 
 fn main() -> anyhow::Result<()> {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    take_host_flag(&mut args)?;
     let cmd = args.get(1).map(|s| s.as_str()).unwrap_or("stats");
 
     let req = match cmd {
         "meta" => Request::GetMeta,
+        "health" => Request::GetHealth,
         "list" => Request::ListControls,
+        "residency" => Request::GetResidency,
+        "gpu-procs" => Request::GetGpuProcesses,
+        "proc" => Request::GetProcessDetail {
+            pid: args
+                .get(2)
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| anyhow::anyhow!(t!("cli.usage.proc")))?,
+        },
+        "top" => Request::GetProcesses {
+            sort: match args.get(2) {
+                Some(s) => ProcessSort::parse(s).ok_or_else(|| anyhow::anyhow!(t!("cli.usage.top")))?,
+                None => ProcessSort::Cpu,
+            },
+            limit: args.get(3).and_then(|s| s.parse().ok()),
+        },
+        "alerts" => Request::GetAlerts,
+        "energy" => Request::GetEnergy,
+        "history" => Request::GetHistory {
+            since_secs: args.get(2).and_then(|s| s.parse().ok()).unwrap_or(300),
+            max_points: args.get(3).and_then(|s| s.parse().ok()).unwrap_or(60),
+            full: false,
+        },
+        "summary" => Request::GetSummary {
+            window_secs: args.get(2).and_then(|s| s.parse().ok()).unwrap_or(300),
+        },
+        "samples" => Request::GetHistory {
+            since_secs: args.get(2).and_then(|s| s.parse().ok()).unwrap_or(600),
+            max_points: args.get(3).and_then(|s| s.parse().ok()).unwrap_or(0),
+            full: true,
+        },
+        "query" => {
+            let now = jetsonscope::history::now_unix_ms();
+            let from = args.get(2).ok_or_else(|| anyhow::anyhow!(t!("cli.usage.query")))?;
+            Request::QueryHistory {
+                from_unix_ms: parse_time(from, now)?,
+                to_unix_ms: match args.get(3) {
+                    Some(to) => parse_time(to, now)?,
+                    None => now,
+                },
+                max_points: args.get(4).and_then(|s| s.parse().ok()).unwrap_or(0),
+            }
+        }
+        "version" => return version(&mut connect()?),
+        "smi" => return smi(&mut connect()?),
+        "stress" => return stress(&mut connect()?, &args[2..]),
+        "burst" => match args.get(2).map(|s| s.as_str()) {
+            Some("get") => {
+                let id = args
+                    .get(3)
+                    .and_then(|s| s.parse().ok())
+                    .ok_or_else(|| anyhow::anyhow!(t!("cli.usage.burst_get")))?;
+                Request::GetBurst { id }
+            }
+            _ => {
+                return burst(
+                    &mut connect()?,
+                    args.get(2).and_then(|s| s.parse().ok()).unwrap_or(100),
+                    args.get(3).and_then(|s| s.parse().ok()).unwrap_or(10),
+                )
+            }
+        },
         "set" => {
-            if args.len() < 4 {
-                anyhow::bail!("Usage: jetsonscopectl set <control> <value>");
+            let dry_run = args.iter().any(|a| a == "--dry-run");
+            let rest: Vec<&String> = args[2..].iter().filter(|a| *a != "--dry-run").collect();
+            if rest.len() < 2 {
+                anyhow::bail!(t!("cli.usage.set"));
             }
             Request::SetControl {
-                control: args[2].clone(),
-                value: args[3].clone(),
-                token: env::var("TEGRA_AUTH_TOKEN")
-                    .ok()
-                    .or_else(|| env::var("JETSONSCOPE_AUTH_TOKEN").ok()),
+                control: rest[0].clone(),
+                value: rest[1].clone(),
+                token: client::auth_token_from_env(),
+                dry_run,
             }
         }
+        "profiles" => Request::ListProfiles,
+        "profile" => {
+            let dry_run = args.iter().any(|a| a == "--dry-run");
+            let rest: Vec<&str> = args[2..]
+                .iter()
+                .filter(|a| *a != "--dry-run")
+                .map(|s| s.as_str())
+                .collect();
+            match rest.as_slice() {
+                ["apply", name] => Request::ApplyProfile {
+                    name: name.to_string(),
+                    token: client::auth_token_from_env(),
+                    dry_run,
+                },
+                ["save", name, spec] => Request::SaveProfile {
+                    name: name.to_string(),
+                    controls: jetsonscope::idle::parse_profile(spec),
+                    token: client::auth_token_from_env(),
+                },
+                _ => anyhow::bail!(t!("cli.usage.profile")),
+            }
+        }
+        "kill" => Request::SignalProcess {
+            pid: args
+                .get(2)
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| anyhow::anyhow!(t!("cli.usage.kill")))?,
+            signal: args.get(3).cloned().unwrap_or_else(|| "TERM".to_string()),
+            token: client::auth_token_from_env(),
+        },
+        "renice" => match (
+            args.get(2).and_then(|s| s.parse().ok()),
+            args.get(3).and_then(|s| s.parse().ok()),
+        ) {
+            (Some(pid), Some(nice)) => Request::ReniceProcess {
+                pid,
+                nice,
+                token: client::auth_token_from_env(),
+            },
+            _ => anyhow::bail!(t!("cli.usage.renice")),
+        },
+        "session" => match (args.get(2).map(|s| s.as_str()), args.get(3)) {
+            (Some("start"), label) => Request::StartSession {
+                label: label.cloned(),
+            },
+            (Some("stop"), Some(id)) => Request::StopSession {
+                id: id
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!(t!("cli.bad_session_id", id = id)))?,
+            },
+            _ => anyhow::bail!(t!("cli.usage.session")),
+        },
         _ => Request::GetStats,
     };
 
-    let path = resolve_socket_path();
-    if !path.exists() {
-        anyhow::bail!(format!("Socket not found: {}", path.display()));
-    }
-
-    let mut stream = UnixStream::connect(&path)?;
-    let use_cbor = use_cbor();
-
-    if use_cbor {
-        let bytes = serde_cbor::to_vec(&req)?;
-        stream.write_all(&bytes)?;
-    } else {
-        let json_req = serde_json::to_string(&req)?;
-        stream.write_all(json_req.as_bytes())?;
-    }
-
-    let mut buf = Vec::new();
-    stream.read_to_end(&mut buf)?;
-
-    let resp: Response = if use_cbor {
-        serde_cbor::from_slice(&buf)?
-    } else {
-        serde_json::from_slice(&buf)?
-    };
-
-    match resp {
+    match connect()?.request(&req)? {
         Response::Stats { source, data } => {
-            println!("Source: {}", source);
+            println!("{}: {}", t!("cli.stats.source"), source);
             if let Some(stats) = data {
-                println!("Timestamp: {:?}", stats.timestamp);
+                let time = stats.time().map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+                println!("{}: {}", t!("cli.stats.timestamp"), time.as_deref().unwrap_or("-"));
                 println!("RAM: {:?}", stats.ram);
                 println!("SWAP: {:?}", stats.swap);
-                println!("CPU cores: {}", stats.cpus.len());
+                println!("{}: {}", t!("cli.stats.cores"), stats.cpus.len());
                 if let Some(gpu) = stats.gpu_usage() {
                     println!("GPU: {}%", gpu);
                 }
             } else {
-                println!("No stats available");
+                println!("{}", t!("cli.stats.none"));
             }
         }
         Response::Meta(hw) => {
-            println!("Hardware Info:");
-            println!("  Model: {}", hw.model);
+            println!("{}:", t!("cli.meta.title"));
+            println!("  {}: {}", t!("cli.meta.model"), hw.model);
             println!("  SoC: {}", hw.soc);
             println!("  L4T: {}", hw.l4t_version);
             println!("  JetPack: {}", hw.jetpack_version);
-            println!("  Is Jetson: {}", hw.is_jetson);
+            println!("  {}: {}", t!("cli.meta.is_jetson"), yes_no(hw.is_jetson));
+            if !hw.nvpmodel_caps.is_empty() {
+                println!("  {}:", t!("cli.meta.nvpmodel"));
+                for mode in &hw.nvpmodel_caps {
+                    println!("    {} {}: {}", mode.id, mode.name, mode.describe());
+                }
+            }
+            if !hw.frequencies.is_empty() {
+                println!("  {}:", t!("cli.meta.frequencies"));
+                for caps in &hw.frequencies {
+                    let steps: Vec<String> = caps
+                        .available_hz
+                        .iter()
+                        .map(|hz| (hz / 1_000_000).to_string())
+                        .collect();
+                    println!("    {} ({}): {}", caps.domain, caps.device, steps.join(" "));
+                }
+            }
         }
         Response::Controls(controls) => {
-            println!("Available Controls:");
+            println!("{}:", t!("cli.controls.title"));
             for ctrl in controls {
                 println!("  {} = {} ({})", ctrl.name, ctrl.value, ctrl.description);
                 if !ctrl.supported {
-                    println!("    [NOT SUPPORTED]");
+                    println!("    [{}]", t!("cli.controls.unsupported"));
+                }
+                for (option, detail) in &ctrl.option_details {
+                    println!("    {}: {}", option, detail);
+                }
+                if !ctrl.frequencies_hz.is_empty() {
+                    println!("    {} {}", ctrl.options.join(" "), ctrl.unit.as_deref().unwrap_or(""));
                 }
             }
         }
         Response::ControlState(ctrl) => {
-            println!("Control Updated:");
+            println!("{}:", t!("cli.controls.updated"));
             println!("  {} = {}", ctrl.name, ctrl.value);
         }
+        Response::ControlPlan { control, value, actions } => {
+            println!("{}:", t!("cli.controls.plan", control = control, value = value));
+            if actions.is_empty() {
+                println!("  {}", t!("cli.controls.plan_empty"));
+            }
+            for action in actions {
+                println!("  {action}");
+            }
+        }
+        Response::Processes(procs) => {
+            for p in procs {
+                println!(
+                    "{:>7}  {:<20} {:>6.1}%  {:>8} MiB  R {:>9}/s  W {:>9}/s  {}",
+                    p.pid,
+                    p.name,
+                    p.cpu_usage,
+                    p.memory_kb / 1024,
+                    p.read_bytes_per_sec,
+                    p.write_bytes_per_sec,
+                    p.user.as_deref().unwrap_or("-")
+                );
+            }
+        }
+        Response::ProcessDetail(detail) => {
+            println!("{} ({})", detail.name, detail.pid);
+            for (label, kb) in detail.memory_rows() {
+                match kb {
+                    Some(kb) => println!("  {:<12} {:>10} KiB", label, kb),
+                    None => println!("  {:<12} {:>10}", label, t!("proc.mem.needs_root")),
+                }
+            }
+        }
+        Response::Profiles(list) => {
+            println!("{}:", t!("cli.profiles.title"));
+            for profile in list.profiles {
+                let active = if list.active.as_deref() == Some(profile.name.as_str()) {
+                    format!(" ({})", t!("cli.profiles.active"))
+                } else {
+                    String::new()
+                };
+                println!("  {}{}: {}", profile.name, active, profile.describe());
+            }
+        }
+        Response::ProfileApplied { name, controls, skipped } => {
+            println!("{}:", t!("cli.profile_applied", name = name));
+            for ctrl in controls {
+                println!("  {} = {}", ctrl.name, ctrl.value);
+            }
+            if !skipped.is_empty() {
+                println!("{}", t!("cli.profile_skipped", controls = skipped.join(", ")));
+            }
+        }
+        Response::ProfilePlan { name, actions, skipped } => {
+            println!("{}:", t!("cli.profile_plan", name = name));
+            if actions.is_empty() {
+                println!("  {}", t!("cli.controls.plan_empty"));
+            }
+            for action in actions {
+                println!("  {action}");
+            }
+            if !skipped.is_empty() {
+                println!("{}", t!("cli.profile_skipped", controls = skipped.join(", ")));
+            }
+        }
+        Response::ProfileSaved { name } => println!("{}", t!("cli.profile_saved", name = name)),
+        Response::ProcessUpdated { pid, action } => {
+            println!("{}", t!("cli.process_updated", pid = pid, action = action));
+        }
         Response::Health(health) => {
-            println!("Daemon Health:");
-            println!("  Uptime (s): {}", health.uptime_secs);
-            println!("  Total requests: {}", health.total_requests);
-            println!("  Errors: {}", health.errors);
-            println!("  Connected clients: {}", health.connected_clients);
-            println!("  Stats collected: {}", health.stats_collected);
+            println!("{}:", t!("cli.health.title"));
+            println!("  {}: {}", t!("cli.health.uptime"), health.uptime_secs);
+            println!("  {}: {}", t!("cli.health.requests"), health.total_requests);
+            println!("  {}: {}", t!("cli.health.errors"), health.errors);
+            println!("  {}: {}", t!("cli.health.clients"), health.connected_clients);
+            println!("  {}: {}", t!("cli.health.collected"), health.stats_collected);
+            println!("  {}: {}", t!("cli.health.dropped"), health.dropped_samples);
+            if let Some(ms) = health.sample_interval_ms {
+                println!(
+                    "  {}: {} ms ({} {} ms)",
+                    t!("cli.health.interval"),
+                    ms,
+                    t!("cli.health.max"),
+                    health.max_sample_interval_ms
+                );
+            }
+            println!("  {}: {}", t!("cli.health.parse_failures"), health.parse_failures);
+            if let Some(line) = &health.last_parse_failure {
+                println!("    {}", line);
+            }
             if let Some(err) = health.last_error {
-                println!("  Last error: {}", err);
+                println!("  {}: {}", t!("cli.health.last_error"), err);
+            }
+            if !health.requests_by_type.is_empty() {
+                println!("  {}:", t!("cli.health.by_type"));
+                for (request, n) in &health.requests_by_type {
+                    println!("    {:<18} {:>8}", request, n);
+                }
             }
+            if !health.errors_by_code.is_empty() {
+                println!("  {}:", t!("cli.health.by_code"));
+                for (code, n) in &health.errors_by_code {
+                    println!("    {:<18} {:>8}", code, n);
+                }
+            }
+            if !health.recent_errors.is_empty() {
+                println!("  {}:", t!("cli.health.recent_errors"));
+                for err in &health.recent_errors {
+                    let time = chrono::DateTime::from_timestamp_millis(err.unix_ms as i64)
+                        .map(|t| t.with_timezone(&chrono::Local).format("%H:%M:%S").to_string())
+                        .unwrap_or_default();
+                    println!("    {} [{}] {}", time, err.code, err.message);
+                }
+            }
+        }
+        Response::SessionStarted { id } => {
+            // Bare id on stdout so scripts can capture it: ID=$(jscopectl session start)
+            println!("{}", id);
+        }
+        Response::SessionSummary(summary) => {
+            println!("{}", serde_json::to_string_pretty(&summary)?);
+        }
+        Response::History(points) => {
+            println!("time,ram_pct,gpu_pct,cpu_pct,temp_max_c");
+            for p in points {
+                let time = chrono::DateTime::from_timestamp_millis(p.unix_ms as i64)
+                    .map(|t| t.with_timezone(&chrono::Local).format("%H:%M:%S").to_string())
+                    .unwrap_or_default();
+                println!("{},{:.1},{:.1},{:.1},{:.1}", time, p.ram, p.gpu, p.cpu, p.temp);
+            }
+        }
+        Response::Summary(summary) => {
+            if summary.samples == 0 {
+                println!("{}", t!("cli.summary.none", secs = summary.window_secs));
+            } else {
+                println!("{:<24} {:>8} {:>8} {:>8} {:>8}", "series", "min", "mean", "p95", "max");
+            }
+            let fixed = [
+                ("cpu_pct", summary.cpu),
+                ("gpu_pct", summary.gpu),
+                ("ram_pct", summary.ram),
+                ("temp_max_c", summary.hottest),
+            ];
+            let rows = fixed
+                .into_iter()
+                .filter_map(|(name, stat)| Some((name.to_string(), stat?)))
+                .chain(summary.temps.iter().map(|(name, s)| (format!("temp_c:{name}"), *s)))
+                .chain(summary.power.iter().map(|(name, s)| (format!("power_mw:{name}"), *s)));
+            for (name, s) in rows {
+                println!("{:<24} {:>8.1} {:>8.1} {:>8.1} {:>8.1}", name, s.min, s.mean, s.p95, s.max);
+            }
+        }
+        Response::StatsHistory(samples) => {
+            println!("{}", serde_json::to_string_pretty(&samples)?);
+        }
+        Response::BurstStarted { id } => {
+            println!("{}", id);
+        }
+        Response::Burst(capture) => {
+            println!("{}", serde_json::to_string_pretty(&capture)?);
+        }
+        Response::Authenticated { name, .. } => println!("{name}"),
+        Response::Alerts(alerts) => {
+            if alerts.is_empty() {
+                println!("{}", t!("cli.alerts.none"));
+            }
+            for a in alerts {
+                let value = a.value.map(|v| format!("{v:.1}")).unwrap_or_else(|| "-".into());
+                let state = serde_json::to_value(a.state)?;
+                println!(
+                    "{:<8} {}  ({} {})",
+                    state.as_str().unwrap_or_default(),
+                    a.rule,
+                    t!("cli.alerts.now"),
+                    value
+                );
+            }
+        }
+        Response::Energy(report) => {
+            if report.total_wh.is_empty() {
+                println!("{}", t!("cli.energy.none"));
+            } else {
+                println!(
+                    "{:<14} {:>14} {:>12}",
+                    t!("cli.energy.rail"),
+                    t!("cli.energy.since_start"),
+                    t!("cli.energy.total")
+                );
+            }
+            for (rail, total) in &report.total_wh {
+                let since = report.since_start_wh.get(rail).copied().unwrap_or_default();
+                println!("{:<14} {:>11.1} mWh {:>8.3} Wh", rail, since * 1000.0, total);
+            }
+        }
+        Response::Residency(report) => {
+            if report.domains.is_empty() {
+                println!("{}", t!("cli.residency.none"));
+            }
+            for domain in report.domains {
+                println!("{} ({:.1}s):", domain.domain, domain.total_ms() as f64 / 1000.0);
+                for level in domain.levels.iter().rev() {
+                    let mark = if domain.current_mhz == Some(level.freq_mhz) { "*" } else { " " };
+                    println!("  {}{:>5} MHz  {:>5.1}%", mark, level.freq_mhz, level.percent);
+                }
+            }
+            if let Some(pm) = report.gpu_runtime {
+                println!(
+                    "{}",
+                    t!(
+                        "cli.residency.railgate",
+                        percent = format!("{:.1}", pm.suspended_percent),
+                        active = format!("{:.1}", pm.active_ms as f64 / 1000.0),
+                        suspended = format!("{:.1}", pm.suspended_ms as f64 / 1000.0)
+                    )
+                );
+            }
+        }
+        Response::GpuProcesses(procs) => {
+            for p in procs {
+                let gpu = p
+                    .gpu_percent
+                    .map(|g| format!("{:>5.1}%", g))
+                    .unwrap_or_else(|| "     -".to_string());
+                println!(
+                    "{:>7}  {:<20} {:>8} MiB  GPU {}",
+                    p.pid,
+                    p.name,
+                    p.gpu_memory_kb / 1024,
+                    gpu
+                );
+            }
+        }
+        Response::Version(info) => {
+            println!("{}", serde_json::to_string_pretty(&info)?);
         }
         Response::Error(err) => {
             eprintln!("Error [{}]: {}", err.code, err.message);
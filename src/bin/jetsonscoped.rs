@@ -1,20 +1,58 @@
+use std::cell::RefCell;
 use std::fs;
 use std::io::{Cursor, Read, Write};
-use std::os::unix::net::{UnixListener, UnixStream};
+use std::net::TcpListener;
+use std::os::unix::net::UnixListener;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use jetsonscope::collector::{start_collector, CollectorMessage, CollectorMode};
-use jetsonscope::control::ControlManager;
-use jetsonscope::health::HealthTracker;
+use jetsonscope::alerts::{AlertEngine, AlertEvent};
+use jetsonscope::auth::{Access, Scope, TokenSet};
+use jetsonscope::burst::{self, BurstManager};
+use jetsonscope::collector::{start_collector, CollectorMessage, CollectorMode, CollectorStop};
+use jetsonscope::config::DaemonConfig;
+use jetsonscope::control::{ControlError, ControlManager};
+use jetsonscope::dcgm::DcgmConfig;
+use jetsonscope::energy::{EnergyMeter, EnergyTariff};
+use jetsonscope::export;
+use jetsonscope::graphite::GraphiteConfig;
 use jetsonscope::hardware::JetsonHardware;
+use jetsonscope::health::{DaemonHealth, HealthTracker, LATENCY_BUCKETS};
+use jetsonscope::history::{self, HistoryRing};
+#[cfg(feature = "sqlite")]
+use jetsonscope::history_db::{HistoryDb, HistoryDbConfig};
+use jetsonscope::idle::IdleConfig;
+use jetsonscope::jsonrpc;
+use jetsonscope::k8s::MetricLabels;
 use jetsonscope::metrics_auth;
+#[cfg(feature = "mqtt")]
+use jetsonscope::mqtt::{self, MqttClient, MqttConfig};
+use jetsonscope::netdata::NetdataWriter;
 use jetsonscope::parser::TegraStats;
-use jetsonscope::protocol::{ErrorInfo, Request, Response};
-use jetsonscope::processes::ProcessMonitor;
-use tiny_http::{Header, Response as HttpResponse, Server};
+use jetsonscope::power_cap;
+use jetsonscope::power_supply;
+use jetsonscope::processes::{self, GpuAttribution, ProcessMonitor, ProcessSort};
+use jetsonscope::profiles::{Profile, ProfileStore};
+use jetsonscope::protocol::{self, ErrorInfo, Reply, Request, Response, VersionInfo};
+use jetsonscope::recorder::{Recorder, RecorderConfig};
+use jetsonscope::residency::ResidencyReport;
+use jetsonscope::session::SessionManager;
+use jetsonscope::statsd::StatsdConfig;
+use jetsonscope::systemd;
+use jetsonscope::t;
+use jetsonscope::thermal_guard::{GuardConfig, GuardEvent};
+use jetsonscope::websocket;
+use once_cell::sync::Lazy;
+use signal_hook::consts::{SIGINT, SIGTERM};
+use signal_hook::iterator::Signals;
+use tiny_http::{Header, Method, Response as HttpResponse, Server};
+
+/// Control and read tokens, parsed once after the config file is applied.
+static TOKENS: Lazy<TokenSet> = Lazy::new(TokenSet::from_env);
+/// A TCP peer that sends nothing for this long is disconnected.
+const TCP_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
 
 fn socket_path() -> String {
     std::env::var("JETSONSCOPE_SOCKET_PATH")
@@ -23,26 +61,179 @@ fn socket_path() -> String {
 }
 
 fn main() -> anyhow::Result<()> {
-    let socket_path = socket_path();
-    if Path::new(&socket_path).exists() {
-        fs::remove_file(&socket_path)?;
+    let args: Vec<String> = std::env::args().collect();
+    // Before anything reads the environment or starts a thread
+    if let Some(cfg) = DaemonConfig::from_args(&args[1..])? {
+        let count = cfg.apply();
+        eprintln!(
+            "{}",
+            t!("config.loaded", path = cfg.path.display(), count = count)
+        );
+    }
+    // Report bad JETSONSCOPE_AUTH_TOKENS entries at startup
+    Lazy::force(&TOKENS);
+    if let Some(i) = args.iter().position(|a| a == "--netdata") {
+        let update_every = args.get(i + 1).and_then(|s| s.parse().ok()).unwrap_or(1);
+        return run_netdata_plugin(update_every);
+    }
+    if let Some(i) = args.iter().position(|a| a == "--install-service") {
+        let dir = args
+            .get(i + 1)
+            .filter(|a| !a.starts_with("--"))
+            .map_or(Path::new(systemd::DEFAULT_UNIT_DIR), |a| Path::new(a));
+        return install_service(dir);
     }
-    let listener = UnixListener::bind(&socket_path)?;
+
+    let socket_path = socket_path();
+    // Under socket activation systemd owns the socket file
+    let (listener, owned_socket) = match systemd::take_listener() {
+        Some(listener) => (listener, None),
+        None => {
+            if Path::new(&socket_path).exists() {
+                fs::remove_file(&socket_path)?;
+            }
+            (UnixListener::bind(&socket_path)?, Some(socket_path.clone()))
+        }
+    };
 
     let collector = start_collector(CollectorMode::AutoCommand);
     let latest_stats: Arc<Mutex<Option<TegraStats>>> = Arc::new(Mutex::new(None));
     let source_label: Arc<Mutex<String>> = Arc::new(Mutex::new(String::from("initializing")));
     let control = Arc::new(Mutex::new(ControlManager::new()));
+    if let Ok(mut ctrl) = control.lock() {
+        if let Some(cfg) = IdleConfig::from_env() {
+            ctrl.configure_idle(cfg);
+        }
+        if let Some(cfg) = GuardConfig::from_env() {
+            ctrl.configure_thermal_guard(cfg);
+        }
+        // The budget alert comes from AlertEngine::from_env
+        if let Some(budget_mw) = power_cap::budget_from_env() {
+            if let Err(e) = ctrl.set_power_budget(budget_mw) {
+                eprintln!("Power budget not enforced: {e}");
+            }
+        }
+        if let Ok(target) = std::env::var("JETSONSCOPE_THERMAL_TARGET") {
+            if let Err(e) = ctrl.set_thermal_target(&target) {
+                eprintln!("Thermal governor not enabled: {e}");
+            }
+        }
+    }
     let hardware = Arc::new(JetsonHardware::detect());
     let health = Arc::new(Mutex::new(HealthTracker::new()));
+    let energy_state = energy_state_path();
+    let energy = Arc::new(Mutex::new(match &energy_state {
+        Some(path) => EnergyMeter::load(path).unwrap_or_else(|e| {
+            eprintln!("Energy state not loaded ({e}), starting from zero");
+            EnergyMeter::new()
+        }),
+        None => EnergyMeter::new(),
+    }));
+    let sessions = Arc::new(Mutex::new(SessionManager::new()));
+    // Recent full samples and trend history, served to TUIs on connect and to tools (GetHistory)
+    let history = Arc::new(Mutex::new(HistoryRing::from_env()));
+    // Sub-second captures (StartBurst/GetBurst)
+    let bursts = Arc::new(Mutex::new(BurstManager::new()));
+    // Per-process share of GR3D load from the nvgpu runlist or fdinfo (GetGpuProcesses, /debug/processes)
+    let gpu_attribution = Arc::new(Mutex::new(GpuAttribution::new()));
+    // One process table for GetProcesses, /debug/processes and the I/O metrics,
+    // so CPU% and I/O rates cover the time since the previous scan
+    let process_monitor = Arc::new(Mutex::new(ProcessMonitor::new()));
+
+    // Queryable long-term history (QueryHistory)
+    let long_term = open_long_term_history();
+    // Threshold rules checked on every sample (GetAlerts, jetsonscope_alert_active)
+    let alerts = Arc::new(Mutex::new(AlertEngine::from_env()));
+    // Named control profiles (ListProfiles/ApplyProfile/SaveProfile)
+    let profiles = Arc::new(Mutex::new(ProfileStore::from_env()));
+
+    // Crash-safe long-term sample recording
+    let recorder = Arc::new(Mutex::new(RecorderConfig::from_env().and_then(|cfg| {
+        let dir = cfg.dir.clone();
+        match Recorder::open(cfg, history::now_unix_ms()) {
+            Ok(r) => Some(r),
+            Err(e) => {
+                eprintln!("Recorder disabled ({}): {e:#}", dir.display());
+                None
+            }
+        }
+    })));
 
     // Telemetry: file logging
-    if let Some(cfg) = TelemetryConfig::from_env() {
+    let telemetry = TelemetryConfig::from_env();
+    if let Some(cfg) = telemetry.clone() {
         spawn_telemetry_logger(cfg, health.clone());
     }
+
+    #[cfg(feature = "mqtt")]
+    let mqtt_client = Arc::new(Mutex::new(None));
+
+    // SIGTERM/SIGINT: stop the collector, flush state and remove the socket
+    spawn_signal_handler(Shutdown {
+        socket_path: owned_socket,
+        collector: collector.stop.clone(),
+        recorder: recorder.clone(),
+        energy: energy.clone(),
+        energy_state: energy_state.clone(),
+        telemetry,
+        health: health.clone(),
+        control: control.clone(),
+        #[cfg(feature = "mqtt")]
+        mqtt: mqtt_client.clone(),
+    })?;
     // Metrics/Debug HTTP
     if let Ok(addr) = std::env::var("JETSONSCOPE_HTTP_ADDR") {
-        spawn_http_metrics(addr, health.clone(), latest_stats.clone(), control.clone());
+        spawn_http_metrics(
+            addr,
+            health.clone(),
+            latest_stats.clone(),
+            control.clone(),
+            energy.clone(),
+            gpu_attribution.clone(),
+            process_monitor.clone(),
+            HttpOptions {
+                dcgm: DcgmConfig::from_env(&hardware),
+                labels: MetricLabels::from_env(),
+                ready_max_age: Duration::from_secs(
+                    std::env::var("JETSONSCOPE_READY_MAX_AGE")
+                        .ok()
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or(10),
+                ),
+                hardware: hardware.clone(),
+                alerts: alerts.clone(),
+                process_io_top: std::env::var("JETSONSCOPE_METRICS_PROCESS_IO")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(0),
+            },
+        );
+    }
+
+    // MQTT push (+ Home Assistant discovery)
+    #[cfg(feature = "mqtt")]
+    if let Some(cfg) = MqttConfig::from_env() {
+        spawn_mqtt_exporter(
+            cfg,
+            mqtt_client,
+            latest_stats.clone(),
+            control.clone(),
+            hardware.clone(),
+        );
+    }
+    #[cfg(not(feature = "mqtt"))]
+    if std::env::var_os("JETSONSCOPE_MQTT_ADDR").is_some() {
+        eprintln!("JETSONSCOPE_MQTT_ADDR ignored: jscoped was built without the mqtt feature");
+    }
+
+    // Graphite plaintext push
+    if let Some(cfg) = GraphiteConfig::from_env() {
+        spawn_graphite_exporter(cfg, latest_stats.clone(), control.clone());
+    }
+
+    // StatsD gauges over UDP
+    if let Some(cfg) = StatsdConfig::from_env() {
+        spawn_statsd_exporter(cfg, latest_stats.clone(), control.clone());
     }
 
     // Thread to receive stats from collector
@@ -50,15 +241,117 @@ fn main() -> anyhow::Result<()> {
         let latest_stats = Arc::clone(&latest_stats);
         let source_label = Arc::clone(&source_label);
         let health = Arc::clone(&health);
+        let energy = Arc::clone(&energy);
+        let control = Arc::clone(&control);
+        let sessions = Arc::clone(&sessions);
+        let history = Arc::clone(&history);
+        let long_term = long_term.clone();
+        let gpu_attribution = Arc::clone(&gpu_attribution);
+        let alerts = Arc::clone(&alerts);
+        let recorder = Arc::clone(&recorder);
         thread::spawn(move || {
+            let mut samples: u64 = 0;
+            let low_battery = power_supply::low_battery_threshold();
+            let mut low_alerted: Vec<String> = Vec::new();
+            let mut recorder_failing = false;
+            let mut long_term_failing = false;
             for msg in collector.rx.iter() {
                 match msg {
                     CollectorMessage::Stats(s) => {
+                        samples += 1;
+                        for supply in s.supplies.iter() {
+                            let alerted = low_alerted.contains(&supply.name);
+                            if supply.is_low(low_battery) && !alerted {
+                                eprintln!(
+                                    "Low battery: {} at {}% (threshold {}%)",
+                                    supply.name,
+                                    supply.capacity_percent.unwrap_or(0),
+                                    low_battery
+                                );
+                                low_alerted.push(supply.name.clone());
+                            } else if !supply.is_low(low_battery) && alerted {
+                                low_alerted.retain(|n| n != &supply.name);
+                            }
+                        }
+                        if let Ok(mut engine) = alerts.lock() {
+                            for event in engine.evaluate(&s, history::now_unix_ms()) {
+                                match event {
+                                    AlertEvent::Fired(a) => eprintln!(
+                                        "Alert firing: {} (now {:.1})",
+                                        a.rule,
+                                        a.value.unwrap_or_default()
+                                    ),
+                                    AlertEvent::Resolved(a) => {
+                                        eprintln!("Alert resolved: {}", a.rule)
+                                    }
+                                }
+                            }
+                        }
+                        if let Ok(mut meter) = energy.lock() {
+                            meter.record(&s, Instant::now());
+                            // Persist roughly once a minute at 1 Hz sampling
+                            match &energy_state {
+                                Some(path) if samples % 60 == 0 => {
+                                    if let Err(e) = meter.save(path) {
+                                        eprintln!("Failed to save energy state: {e}");
+                                    }
+                                }
+                                _ => {}
+                            }
+                        }
+                        let cap_level = match control.lock() {
+                            Ok(mut ctrl) => {
+                                ctrl.on_stats(&s);
+                                for event in ctrl.take_guard_events() {
+                                    log_guard_event(&event);
+                                }
+                                ctrl.status().clock_cap.level
+                            }
+                            Err(_) => 0,
+                        };
+                        if let Ok(mut active) = sessions.lock() {
+                            active.record(&s, cap_level);
+                        }
+                        if let Ok(mut ring) = history.lock() {
+                            ring.record(&s, history::now_unix_ms());
+                        }
+                        if let Err(e) =
+                            store_long_term_history(&long_term, history::now_unix_ms(), &s)
+                        {
+                            if !long_term_failing {
+                                eprintln!("History database: {e:#}");
+                                record_error(&health, "history_db", &format!("history db: {e:#}"));
+                                long_term_failing = true;
+                            }
+                        } else {
+                            long_term_failing = false;
+                        }
+                        if let Some(rec) = recorder.lock().ok().as_mut().and_then(|r| r.as_mut()) {
+                            // Keep retrying (e.g. disk full); report only the transitions
+                            match rec.append(history::now_unix_ms(), &s) {
+                                Ok(()) if recorder_failing => {
+                                    eprintln!("Recorder: writing again");
+                                    recorder_failing = false;
+                                }
+                                Err(e) if !recorder_failing => {
+                                    eprintln!("Recorder: {e:#}");
+                                    record_error(&health, "recorder", &format!("recorder: {e:#}"));
+                                    recorder_failing = true;
+                                }
+                                _ => {}
+                            }
+                        }
+                        if let (Ok(mut attribution), Some(gr3d)) =
+                            (gpu_attribution.lock(), s.gpu_usage())
+                        {
+                            attribution.sample(gr3d as f32);
+                        }
                         if let Ok(mut guard) = latest_stats.lock() {
                             *guard = Some(s);
                         }
                         if let Ok(mut h) = health.lock() {
                             h.record_stats_collection();
+                            h.set_dropped_samples(collector.rx.dropped());
                         }
                     }
                     CollectorMessage::SourceLabel(label) => {
@@ -66,23 +359,68 @@ fn main() -> anyhow::Result<()> {
                             *guard = label;
                         }
                     }
-                    CollectorMessage::Error(_) => {}
+                    CollectorMessage::Error(err) => {
+                        eprintln!("Stats source: {err}");
+                        record_error(&health, "collector", &err);
+                    }
+                    CollectorMessage::FormatDrift(err) => {
+                        let message = format!("stats format changed? {err}");
+                        eprintln!("Stats source: {message}");
+                        record_error(&health, "format_drift", &message);
+                    }
+                    CollectorMessage::ParseFailed(line) => {
+                        if let Ok(mut h) = health.lock() {
+                            h.record_parse_failure(line);
+                        }
+                    }
+                    CollectorMessage::Poll { .. } | CollectorMessage::Reply(_) => {}
                 }
             }
         });
     }
 
+    let ctx = ClientContext {
+        stats: latest_stats,
+        label: source_label,
+        control,
+        hardware,
+        health,
+        energy,
+        sessions,
+        history,
+        long_term,
+        bursts,
+        gpu_attribution,
+        process_monitor,
+        alerts,
+        profiles,
+    };
+    let tcp_listener = std::env::var("JETSONSCOPE_TCP_ADDR")
+        .ok()
+        .and_then(|addr| bind_tcp_listener(&addr));
+    // Type=notify units wait for this; a no-op outside systemd
+    let _ = systemd::notify(&format!("READY=1\nSTATUS=Listening on {socket_path}"));
+    if let Some(interval) = systemd::watchdog_interval() {
+        spawn_watchdog(interval, ctx.health.clone());
+    }
+    serve_clients(listener, tcp_listener, ctx)
+}
+
+/// A thread per Unix and TCP connection.
+#[cfg(not(feature = "async"))]
+fn serve_clients(
+    listener: UnixListener,
+    tcp_listener: Option<TcpListener>,
+    ctx: ClientContext,
+) -> anyhow::Result<()> {
+    if let Some(tcp_listener) = tcp_listener {
+        spawn_tcp_listener(tcp_listener, ctx.clone());
+    }
     for stream in listener.incoming() {
         match stream {
             Ok(stream) => {
-                let stats = latest_stats.clone();
-                let label = source_label.clone();
-                let control = control.clone();
-                let hw = hardware.clone();
-                let health = health.clone();
-                thread::spawn(move || {
-                    handle_client(stream, stats, label, control, hw, health);
-                });
+                let ctx = ctx.clone();
+                thread::spawn(move || handle_client(stream, ctx, false));
             }
             Err(err) => eprintln!("Error accepting client: {err}"),
         }
@@ -91,6 +429,14 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Energy totals get their own state file instead of riding on the recorder journal:
+/// recording is optional and rotates, so the totals could not be restored from it cheaply.
+fn energy_state_path() -> Option<PathBuf> {
+    std::env::var("JETSONSCOPE_ENERGY_STATE")
+        .ok()
+        .map(PathBuf::from)
+}
+
 #[derive(Clone)]
 struct TelemetryConfig {
     path: PathBuf,
@@ -114,190 +460,1408 @@ impl TelemetryConfig {
 fn spawn_telemetry_logger(cfg: TelemetryConfig, health: Arc<Mutex<HealthTracker>>) {
     thread::spawn(move || loop {
         thread::sleep(cfg.interval);
-        if let Ok(h) = health.lock() {
-            let snapshot = h.get_health(0);
-            if let Ok(json) = serde_json::to_string(&snapshot) {
-                let _ = std::fs::OpenOptions::new()
-                    .create(true)
-                    .append(true)
-                    .open(&cfg.path)
-                    .and_then(|mut f| {
-                        use std::io::Write;
-                        writeln!(f, "{}", json)
-                    });
+        write_telemetry_line(&cfg.path, &health);
+    });
+}
+
+/// A telemetry log line: the health snapshot and when it was taken.
+#[derive(serde::Serialize)]
+struct TelemetryLine {
+    /// UTC, RFC 3339
+    time: String,
+    #[serde(flatten)]
+    health: DaemonHealth,
+}
+
+/// Append one health snapshot as a JSON line.
+fn write_telemetry_line(path: &Path, health: &Arc<Mutex<HealthTracker>>) {
+    if let Ok(h) = health.lock() {
+        let line = TelemetryLine {
+            time: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            health: h.get_health(),
+        };
+        if let Ok(json) = serde_json::to_string(&line) {
+            let _ = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|mut f| writeln!(f, "{}", json));
+        }
+    }
+}
+
+/// What has to be flushed or cleaned up when the daemon is told to stop.
+struct Shutdown {
+    /// None when the socket came from systemd socket activation
+    socket_path: Option<String>,
+    collector: CollectorStop,
+    recorder: Arc<Mutex<Option<Recorder>>>,
+    energy: Arc<Mutex<EnergyMeter>>,
+    energy_state: Option<PathBuf>,
+    telemetry: Option<TelemetryConfig>,
+    health: Arc<Mutex<HealthTracker>>,
+    control: Arc<Mutex<ControlManager>>,
+    /// The MQTT exporter's connection, dropped (retained "offline", DISCONNECT) on exit
+    #[cfg(feature = "mqtt")]
+    mqtt: Arc<Mutex<Option<MqttClient>>>,
+}
+
+impl Shutdown {
+    fn run(&self) {
+        let _ = systemd::notify("STOPPING=1");
+        // Kills tegrastats (or the configured command) so it doesn't outlive us
+        self.collector.stop();
+        // Idle profiles, guard trips and clock caps must not outlive the daemon that applied them
+        if let Ok(mut ctrl) = self.control.lock() {
+            ctrl.release_automation();
+            for event in ctrl.take_guard_events() {
+                log_guard_event(&event);
+            }
+        }
+        if let Some(rec) = self.recorder.lock().ok().as_mut().and_then(|r| r.as_mut()) {
+            if let Err(e) = rec.sync() {
+                eprintln!("Recorder: {e:#}");
+            }
+        }
+        if let (Some(path), Ok(meter)) = (&self.energy_state, self.energy.lock()) {
+            if let Err(e) = meter.save(path) {
+                eprintln!("Failed to save energy state: {e}");
+            }
+        }
+        if let Some(cfg) = &self.telemetry {
+            write_telemetry_line(&cfg.path, &self.health);
+        }
+        #[cfg(feature = "mqtt")]
+        drop(self.mqtt.lock().ok().and_then(|mut c| c.take()));
+        if let Some(path) = &self.socket_path {
+            if let Err(e) = fs::remove_file(path) {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    eprintln!("Failed to remove {path}: {e}");
+                }
+            }
+        }
+    }
+}
+
+/// Ping the systemd watchdog while the daemon's shared state stays reachable;
+/// a deadlock stops the pings and systemd restarts the service.
+fn spawn_watchdog(interval: Duration, health: Arc<Mutex<HealthTracker>>) {
+    thread::spawn(move || loop {
+        if health.lock().is_ok() {
+            let _ = systemd::notify("WATCHDOG=1");
+        }
+        thread::sleep(interval);
+    });
+}
+
+/// Write the systemd units for this binary (`--install-service [dir]`).
+fn install_service(dir: &Path) -> anyhow::Result<()> {
+    let exe = std::env::current_exe()?;
+    for path in systemd::install_units(dir, &exe, &socket_path())? {
+        println!("{}", t!("service.installed", path = path.display()));
+    }
+    println!("{}", t!("service.next"));
+    Ok(())
+}
+
+/// Wait for SIGTERM or SIGINT, clean up and exit. Client connections (Unix,
+/// TCP, HTTP, WebSocket) are closed by the process exit.
+fn spawn_signal_handler(shutdown: Shutdown) -> anyhow::Result<()> {
+    let mut signals = Signals::new([SIGTERM, SIGINT])?;
+    thread::spawn(move || {
+        if let Some(signal) = signals.forever().next() {
+            let name = if signal == SIGINT {
+                "SIGINT"
+            } else {
+                "SIGTERM"
+            };
+            eprintln!("Received {name}, shutting down");
+            shutdown.run();
+            std::process::exit(0);
+        }
+    });
+    Ok(())
+}
+
+/// Key metrics of the latest sample, with the fan duty from the control status.
+fn current_key_metrics(
+    stats: &Arc<Mutex<Option<TegraStats>>>,
+    control: &Arc<Mutex<ControlManager>>,
+) -> Option<Vec<export::KeyMetric>> {
+    let latest = stats.lock().ok()?.clone()?;
+    let fan = control.lock().ok().and_then(|c| {
+        c.status()
+            .fan
+            .as_deref()
+            .and_then(|f| f.trim_end_matches('%').trim().parse::<f64>().ok())
+    });
+    Some(export::key_metrics(&latest, fan))
+}
+
+/// Publishes through `client`; the thread ends once the shutdown handler takes it.
+#[cfg(feature = "mqtt")]
+fn spawn_mqtt_exporter(
+    cfg: MqttConfig,
+    client: Arc<Mutex<Option<MqttClient>>>,
+    stats: Arc<Mutex<Option<TegraStats>>>,
+    control: Arc<Mutex<ControlManager>>,
+    hardware: Arc<JetsonHardware>,
+) {
+    thread::spawn(move || loop {
+        match MqttClient::connect(&cfg) {
+            Ok(c) => {
+                if let Ok(mut slot) = client.lock() {
+                    *slot = Some(c);
+                }
+            }
+            Err(e) => {
+                eprintln!("MQTT: {e:#}");
+                thread::sleep(cfg.interval.max(Duration::from_secs(5)));
+                continue;
+            }
+        };
+        // Discovery configs are re-sent on every connection and whenever new sensors show up
+        let mut announced: Vec<String> = Vec::new();
+        let result = (|| -> anyhow::Result<()> {
+            let mut online = false;
+            loop {
+                {
+                    let mut slot = client
+                        .lock()
+                        .map_err(|_| anyhow::anyhow!("client lock poisoned"))?;
+                    // Taken by the shutdown handler
+                    let Some(client) = slot.as_mut() else {
+                        return Ok(());
+                    };
+                    if !online {
+                        client.publish(&cfg.availability_topic(), b"online", true)?;
+                        online = true;
+                    }
+                    if let Some(metrics) = current_key_metrics(&stats, &control) {
+                        let fresh: Vec<_> = metrics
+                            .iter()
+                            .filter(|m| !announced.contains(&m.key))
+                            .cloned()
+                            .collect();
+                        for (topic, payload) in mqtt::discovery_messages(&cfg, &fresh, &hardware) {
+                            client.publish(&topic, payload.as_bytes(), true)?;
+                        }
+                        announced.extend(fresh.into_iter().map(|m| m.key));
+                        client.publish(
+                            &cfg.state_topic(),
+                            mqtt::state_payload(&metrics).as_bytes(),
+                            false,
+                        )?;
+                    }
+                    if cfg.full_payloads {
+                        if let Some(latest) = stats.lock().ok().and_then(|s| s.clone()) {
+                            client.publish(
+                                &cfg.stats_topic(),
+                                &serde_json::to_vec(&latest)?,
+                                false,
+                            )?;
+                        }
+                        if let Some(status) = control.lock().ok().map(|c| c.status_cloned()) {
+                            client.publish(
+                                &cfg.control_topic(),
+                                &serde_json::to_vec(&status)?,
+                                true,
+                            )?;
+                        }
+                    }
+                    client.keep_alive()?;
+                }
+                thread::sleep(cfg.interval);
+            }
+        })();
+        match result {
+            Ok(()) => return,
+            Err(e) => eprintln!("MQTT: {e:#}, reconnecting"),
+        }
+    });
+}
+
+fn spawn_graphite_exporter(
+    cfg: GraphiteConfig,
+    stats: Arc<Mutex<Option<TegraStats>>>,
+    control: Arc<Mutex<ControlManager>>,
+) {
+    thread::spawn(move || {
+        let mut conn: Option<std::net::TcpStream> = None;
+        // Logged on the first failure and on recovery only, not on every interval
+        let mut failing = false;
+        loop {
+            thread::sleep(cfg.interval);
+            let Some(metrics) = current_key_metrics(&stats, &control) else {
+                continue;
+            };
+            let sampled = stats.lock().ok().and_then(|s| s.as_ref()?.timestamp_unix);
+            let lines = cfg.format_lines(
+                &metrics,
+                sampled.unwrap_or_else(|| chrono::Utc::now().timestamp()),
+            );
+            if conn.is_none() {
+                // A carbon host that stops answering must not hold the thread past one interval
+                match graphite_connect(&cfg.addr, cfg.interval) {
+                    Ok(c) => conn = Some(c),
+                    Err(e) => {
+                        if !failing {
+                            eprintln!("Graphite: connecting to {}: {e}", cfg.addr);
+                            failing = true;
+                        }
+                        continue;
+                    }
+                }
+            }
+            if let Some(c) = conn.as_mut() {
+                match c.write_all(lines.as_bytes()) {
+                    Ok(()) if failing => {
+                        eprintln!("Graphite: sending to {} again", cfg.addr);
+                        failing = false;
+                    }
+                    Ok(()) => {}
+                    Err(e) => {
+                        if !failing {
+                            eprintln!("Graphite: {e}, reconnecting");
+                            failing = true;
+                        }
+                        conn = None;
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Connect to the first resolved address that answers within `timeout`; writes time out too.
+fn graphite_connect(addr: &str, timeout: Duration) -> std::io::Result<std::net::TcpStream> {
+    use std::net::ToSocketAddrs;
+    let mut last_err = None;
+    for sock_addr in addr.to_socket_addrs()? {
+        match std::net::TcpStream::connect_timeout(&sock_addr, timeout) {
+            Ok(stream) => {
+                stream.set_write_timeout(Some(timeout))?;
+                return Ok(stream);
+            }
+            Err(e) => last_err = Some(e),
+        }
+    }
+    Err(last_err.unwrap_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no address")))
+}
+
+fn spawn_statsd_exporter(
+    cfg: StatsdConfig,
+    stats: Arc<Mutex<Option<TegraStats>>>,
+    control: Arc<Mutex<ControlManager>>,
+) {
+    let socket = match std::net::UdpSocket::bind("0.0.0.0:0") {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("StatsD: cannot open UDP socket: {e}");
+            return;
+        }
+    };
+    thread::spawn(move || loop {
+        thread::sleep(cfg.interval);
+        let Some(metrics) = current_key_metrics(&stats, &control) else {
+            continue;
+        };
+        for datagram in cfg.datagrams(&metrics) {
+            // Fire-and-forget, like any StatsD client; log only so a missing agent is visible
+            if let Err(e) = socket.send_to(datagram.as_bytes(), &cfg.addr) {
+                eprintln!("StatsD: sending to {}: {e}", cfg.addr);
+                break;
             }
         }
     });
 }
 
-fn handle_client(
-    mut stream: UnixStream,
+/// State every client connection answers from, whatever the transport.
+#[derive(Clone)]
+struct ClientContext {
     stats: Arc<Mutex<Option<TegraStats>>>,
     label: Arc<Mutex<String>>,
     control: Arc<Mutex<ControlManager>>,
     hardware: Arc<JetsonHardware>,
     health: Arc<Mutex<HealthTracker>>,
-) {
-    let mut buf = Vec::new();
-    let _ = stream.read_to_end(&mut buf);
+    energy: Arc<Mutex<EnergyMeter>>,
+    sessions: Arc<Mutex<SessionManager>>,
+    history: Arc<Mutex<HistoryRing>>,
+    long_term: LongTermHistory,
+    bursts: Arc<Mutex<BurstManager>>,
+    gpu_attribution: Arc<Mutex<GpuAttribution>>,
+    process_monitor: Arc<Mutex<ProcessMonitor>>,
+    alerts: Arc<Mutex<AlertEngine>>,
+    profiles: Arc<Mutex<ProfileStore>>,
+}
 
-    if let Ok(mut h) = health.lock() {
-        h.record_request();
+/// Same protocol as the Unix socket on JETSONSCOPE_TCP_ADDR, for remote
+/// `jscopectl --host`. Reads are open to anyone who can reach the port unless
+/// read-scoped tokens are configured (then clients send Authenticate first).
+/// Control, profile and process changes always need an admin token over TCP.
+fn bind_tcp_listener(addr: &str) -> Option<TcpListener> {
+    let listener = match TcpListener::bind(addr) {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("TCP listener on {addr} not started: {e}");
+            return None;
+        }
+    };
+    if TOKENS.is_empty() {
+        eprintln!(
+            "Controls are refused on {addr} until JETSONSCOPE_AUTH_TOKEN or JETSONSCOPE_AUTH_TOKENS is set"
+        );
+    }
+    Some(listener)
+}
+
+#[cfg(not(feature = "async"))]
+fn spawn_tcp_listener(listener: TcpListener, ctx: ClientContext) {
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let _ = stream.set_nodelay(true);
+                    let _ = stream.set_read_timeout(Some(TCP_IDLE_TIMEOUT));
+                    let ctx = ctx.clone();
+                    thread::spawn(move || handle_client(stream, ctx, true));
+                }
+                Err(err) => eprintln!("Error accepting TCP client: {err}"),
+            }
+        }
+    });
+}
+
+/// With the `async` feature, every Unix and TCP connection is a task on a
+/// tokio runtime instead of a thread. Requests are still answered by the
+/// blocking code, inside `block_in_place`.
+#[cfg(feature = "async")]
+fn serve_clients(
+    listener: UnixListener,
+    tcp_listener: Option<TcpListener>,
+    ctx: ClientContext,
+) -> anyhow::Result<()> {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?;
+    runtime.block_on(async move {
+        listener.set_nonblocking(true)?;
+        let listener = tokio::net::UnixListener::from_std(listener)?;
+        if let Some(tcp_listener) = tcp_listener {
+            tcp_listener.set_nonblocking(true)?;
+            let tcp_listener = tokio::net::TcpListener::from_std(tcp_listener)?;
+            let ctx = ctx.clone();
+            tokio::spawn(async move {
+                loop {
+                    match tcp_listener.accept().await {
+                        Ok((stream, _)) => {
+                            let _ = stream.set_nodelay(true);
+                            tokio::spawn(handle_client_async(stream, ctx.clone(), true));
+                        }
+                        Err(err) => eprintln!("Error accepting TCP client: {err}"),
+                    }
+                }
+            });
+        }
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tokio::spawn(handle_client_async(stream, ctx.clone(), false));
+                }
+                Err(err) => eprintln!("Error accepting client: {err}"),
+            }
+        }
+    })
+}
+
+/// `handle_client` on a tokio stream.
+#[cfg(feature = "async")]
+async fn handle_client_async<S>(stream: S, ctx: ClientContext, remote: bool)
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite,
+{
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let _client = HealthTracker::client_connected(&ctx.health);
+    let session = ClientSession::new(ctx, remote);
+    let (mut reader, mut writer) = tokio::io::split(stream);
+
+    let mut first = [0u8; 1];
+    let n = idle_timeout(remote, reader.read(&mut first))
+        .await
+        .unwrap_or(0);
+    if n == 1 && first[0] == 0 {
+        let mut reader = AsyncReadExt::chain(&first[..], reader);
+        loop {
+            let buf = match idle_timeout(remote, protocol::read_frame_async(&mut reader)).await {
+                Ok(Some(buf)) => buf,
+                Ok(None) => break,
+                Err(err) => {
+                    record_error(
+                        &session.ctx.health,
+                        "bad_frame",
+                        &format!("bad frame: {err}"),
+                    );
+                    break;
+                }
+            };
+            let reply = tokio::task::block_in_place(|| session.serve(&buf, true));
+            if let Some(reply) = reply {
+                if protocol::write_frame_async(&mut writer, &reply)
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        }
+    } else {
+        let mut buf = first[..n].to_vec();
+        let _ = idle_timeout(remote, reader.read_to_end(&mut buf)).await;
+        let reply = tokio::task::block_in_place(|| session.serve(&buf, false));
+        if let Some(reply) = reply {
+            let _ = writer.write_all(&reply).await;
+        }
+    }
+}
+
+/// A read from a `remote` peer fails with TimedOut after TCP_IDLE_TIMEOUT.
+#[cfg(feature = "async")]
+async fn idle_timeout<T>(
+    remote: bool,
+    read: impl std::future::Future<Output = std::io::Result<T>>,
+) -> std::io::Result<T> {
+    if !remote {
+        return read.await;
+    }
+    tokio::time::timeout(TCP_IDLE_TIMEOUT, read)
+        .await
+        .unwrap_or_else(|_| Err(std::io::ErrorKind::TimedOut.into()))
+}
+
+/// One connection's state: what it answers from, and its Authenticate token.
+struct ClientSession {
+    ctx: ClientContext,
+    /// TCP: reads need a read-scoped token once read tokens are configured
+    remote: bool,
+    /// Token from Authenticate, for the rest of the connection
+    session_token: RefCell<Option<String>>,
+}
+
+impl ClientSession {
+    fn new(ctx: ClientContext, remote: bool) -> Self {
+        ClientSession {
+            ctx,
+            remote,
+            session_token: RefCell::new(None),
+        }
+    }
+
+    /// Reply to one request payload; `None` for JSON-RPC notifications.
+    /// `framed` replies carry the protocol version (`Reply`).
+    fn serve(&self, buf: &[u8], framed: bool) -> Option<Vec<u8>> {
+        let health = &self.ctx.health;
+        if let Ok(mut h) = health.lock() {
+            h.record_request();
+        }
+        let decoded = decode_request(buf);
+        if let (Ok((req, _)), Ok(mut h)) = (&decoded, health.lock()) {
+            h.record_request_type(req.name());
+        }
+        match decoded {
+            Ok((req, framing)) if self.gated(&req) => {
+                let err = ErrorInfo {
+                    code: "auth_required".to_string(),
+                    message: t!("daemon.err.auth_required").to_string(),
+                };
+                record_error(health, &err.code, &err.message);
+                encode_reply(Response::Error(err), framing, framed)
+            }
+            Ok((req, framing)) => {
+                let name = req.name();
+                let started = Instant::now();
+                let resp = self.answer(req);
+                if let Ok(mut h) = health.lock() {
+                    h.record_latency(name, started.elapsed());
+                }
+                encode_reply(resp, framing, framed)
+            }
+            Err(reply) => {
+                record_error(
+                    health,
+                    "jsonrpc",
+                    reply["error"]["message"].as_str().unwrap_or_default(),
+                );
+                Some(reply.to_string().into_bytes())
+            }
+        }
+    }
+
+    // Control and profile changes, process actions and Authenticate carry their own token and GetVersion is
+    // public; everything else over TCP needs a read-scoped session once read tokens exist
+    fn gated(&self, req: &Request) -> bool {
+        self.remote
+            && !matches!(
+                req,
+                Request::SetControl { .. }
+                    | Request::ApplyProfile { .. }
+                    | Request::SaveProfile { .. }
+                    | Request::SignalProcess { .. }
+                    | Request::ReniceProcess { .. }
+                    | Request::Authenticate { .. }
+                    | Request::GetVersion
+            )
+            && !auth_ok(self.session_token.borrow().as_deref(), Scope::Read).allowed()
+    }
+
+    /// Admin access from the request's token, else the session's; TCP clients
+    /// always need one.
+    fn admin_access(&self, token: Option<String>) -> Access {
+        let token = token.or_else(|| self.session_token.borrow().clone());
+        TOKENS.check_admin(token.as_deref(), self.remote)
+    }
+
+    /// Process actions need a configured admin token, on any transport.
+    fn process_access(&self, token: Option<String>) -> Access {
+        let token = token.or_else(|| self.session_token.borrow().clone());
+        TOKENS.check_process(token.as_deref())
+    }
+
+    fn answer(&self, req: Request) -> Response {
+        let ClientContext {
+            stats,
+            label,
+            control,
+            hardware,
+            health,
+            energy,
+            sessions,
+            history,
+            long_term,
+            bursts,
+            gpu_attribution,
+            process_monitor,
+            alerts,
+            profiles,
+        } = &self.ctx;
+        let session_token = &self.session_token;
+        match req {
+            Request::GetStats => {
+                let s = stats.lock().ok().and_then(|g| g.clone());
+                let l = label.lock().ok().map(|g| g.clone()).unwrap_or_default();
+                Response::Stats { source: l, data: s }
+            }
+            Request::GetHealth => {
+                let h = health
+                    .lock()
+                    .map(|hh| hh.get_health())
+                    .unwrap_or_else(|_| HealthTracker::new().get_health());
+                Response::Health(h)
+            }
+            Request::GetMeta => Response::Meta((**hardware).clone()),
+            Request::GetVersion => Response::Version(VersionInfo::current()),
+            Request::GetResidency => Response::Residency(ResidencyReport::read()),
+            Request::GetGpuProcesses => {
+                let mut procs = processes::gpu_processes();
+                if let Ok(attribution) = gpu_attribution.lock() {
+                    attribution.annotate(&mut procs);
+                }
+                Response::GpuProcesses(procs)
+            }
+            Request::GetProcessDetail { pid } => match processes::process_detail(pid) {
+                Ok(detail) => Response::ProcessDetail(detail),
+                Err(e) => Response::Error(ErrorInfo {
+                    code: "process_error".to_string(),
+                    message: e.to_string(),
+                }),
+            },
+            Request::GetProcesses { sort, limit } => Response::Processes(scan_processes(
+                process_monitor,
+                gpu_attribution,
+                sort,
+                limit.unwrap_or(20),
+            )),
+            Request::GetHistory {
+                since_secs,
+                max_points,
+                full: false,
+            } => Response::History(
+                history
+                    .lock()
+                    .map(|ring| ring.query(since_secs, max_points, history::now_unix_ms()))
+                    .unwrap_or_default(),
+            ),
+            Request::GetHistory {
+                since_secs,
+                max_points,
+                full: true,
+            } => Response::StatsHistory(
+                history
+                    .lock()
+                    .map(|ring| ring.query_stats(since_secs, max_points, history::now_unix_ms()))
+                    .unwrap_or_default(),
+            ),
+            Request::GetSummary { window_secs } => Response::Summary(
+                history
+                    .lock()
+                    .map(|ring| ring.summary(window_secs, history::now_unix_ms()))
+                    .unwrap_or_default(),
+            ),
+            Request::QueryHistory {
+                from_unix_ms,
+                to_unix_ms,
+                max_points,
+            } => match query_long_term_history(long_term, from_unix_ms, to_unix_ms, max_points) {
+                Ok(samples) => Response::StatsHistory(samples),
+                Err(err) => {
+                    record_error(health, &err.code, &err.message);
+                    Response::Error(err)
+                }
+            },
+            Request::StartBurst {
+                interval_ms,
+                duration_secs,
+            } => match burst::start(bursts, interval_ms, duration_secs) {
+                Ok(id) => Response::BurstStarted { id },
+                Err(e) => {
+                    let err = ErrorInfo {
+                        code: "burst_rejected".to_string(),
+                        message: e.to_string(),
+                    };
+                    record_error(health, &err.code, &err.message);
+                    Response::Error(err)
+                }
+            },
+            Request::GetEnergy => Response::Energy(
+                energy
+                    .lock()
+                    .map(|meter| meter.report())
+                    .unwrap_or_default(),
+            ),
+            Request::GetAlerts => Response::Alerts(
+                alerts
+                    .lock()
+                    .map(|engine| engine.status())
+                    .unwrap_or_default(),
+            ),
+            Request::GetBurst { id } => match bursts.lock().ok().and_then(|b| b.get(id)) {
+                Some(capture) => Response::Burst(capture),
+                None => Response::Error(ErrorInfo {
+                    code: "unknown_burst".to_string(),
+                    message: t!("daemon.err.no_burst", id = id),
+                }),
+            },
+            Request::StartSession { label } => {
+                let energy_wh = energy
+                    .lock()
+                    .map(|m| m.rails_wh.clone())
+                    .unwrap_or_default();
+                let cap_level = control
+                    .lock()
+                    .map(|c| c.status().clock_cap.level)
+                    .unwrap_or(0);
+                match sessions.lock() {
+                    Ok(mut active) => Response::SessionStarted {
+                        id: active.start(label, energy_wh, cap_level),
+                    },
+                    Err(_) => Response::Error(ErrorInfo {
+                        code: "lock_error".to_string(),
+                        message: t!("daemon.err.lock").to_string(),
+                    }),
+                }
+            }
+            Request::StopSession { id } => {
+                let energy_wh = energy
+                    .lock()
+                    .map(|m| m.rails_wh.clone())
+                    .unwrap_or_default();
+                match sessions
+                    .lock()
+                    .ok()
+                    .and_then(|mut a| a.stop(id, &energy_wh))
+                {
+                    Some(summary) => Response::SessionSummary(summary),
+                    None => {
+                        let err = ErrorInfo {
+                            code: "unknown_session".to_string(),
+                            message: t!("daemon.err.no_session", id = id),
+                        };
+                        record_error(health, &err.code, &err.message);
+                        Response::Error(err)
+                    }
+                }
+            }
+            Request::ListControls => match control.lock() {
+                Ok(ctrl) => Response::Controls(ctrl.list_controls()),
+                Err(_) => Response::Error(ErrorInfo {
+                    code: "lock_error".to_string(),
+                    message: t!("daemon.err.lock").to_string(),
+                }),
+            },
+            Request::SetControl {
+                control: name,
+                value,
+                token,
+                dry_run,
+            } => {
+                let access = self.admin_access(token);
+                if !access.allowed() {
+                    let err = ErrorInfo {
+                        code: "auth_failed".to_string(),
+                        message: t!("daemon.err.auth").to_string(),
+                    };
+                    record_error(health, &err.code, &err.message);
+                    Response::Error(err)
+                } else {
+                    set_control(control, health, &name, value, dry_run, access.who())
+                }
+            }
+            Request::ListProfiles => match profiles.lock() {
+                Ok(store) => Response::Profiles(store.list().clone()),
+                Err(_) => Response::Error(ErrorInfo {
+                    code: "lock_error".to_string(),
+                    message: t!("daemon.err.lock").to_string(),
+                }),
+            },
+            Request::ApplyProfile {
+                name,
+                token,
+                dry_run,
+            } => {
+                let access = self.admin_access(token);
+                if !access.allowed() {
+                    let err = ErrorInfo {
+                        code: "auth_failed".to_string(),
+                        message: t!("daemon.err.auth").to_string(),
+                    };
+                    record_error(health, &err.code, &err.message);
+                    Response::Error(err)
+                } else {
+                    apply_profile(control, profiles, health, &name, dry_run, access.who())
+                }
+            }
+            Request::SaveProfile {
+                name,
+                controls,
+                token,
+            } => {
+                let access = self.admin_access(token);
+                let result = if !access.allowed() {
+                    Err(ErrorInfo {
+                        code: "auth_failed".to_string(),
+                        message: t!("daemon.err.auth").to_string(),
+                    })
+                } else {
+                    check_profile(control, &controls)
+                        .and_then(|()| {
+                            profiles.lock().map_err(|_| ErrorInfo {
+                                code: "lock_error".to_string(),
+                                message: t!("daemon.err.lock").to_string(),
+                            })
+                        })
+                        .and_then(|mut store| {
+                            store
+                                .save(Profile {
+                                    name: name.clone(),
+                                    controls,
+                                })
+                                .map_err(|e| ErrorInfo {
+                                    code: "profile_error".to_string(),
+                                    message: format!("{e:#}"),
+                                })
+                        })
+                };
+                match result {
+                    Ok(()) => {
+                        eprintln!("Profile {name} saved by {}", access.who());
+                        Response::ProfileSaved { name }
+                    }
+                    Err(err) => {
+                        record_error(health, &err.code, &err.message);
+                        Response::Error(err)
+                    }
+                }
+            }
+            Request::SignalProcess { pid, signal, token } => {
+                control_process(health, self.process_access(token), pid, || {
+                    processes::signal_process(pid, &signal).map(|name| format!("SIG{name}"))
+                })
+            }
+            Request::ReniceProcess { pid, nice, token } => {
+                control_process(health, self.process_access(token), pid, || {
+                    processes::renice_process(pid, nice).map(|()| format!("nice {nice}"))
+                })
+            }
+            Request::Authenticate { token } => match TOKENS.find(Some(&token), Scope::Read) {
+                Some(named) => {
+                    let resp = Response::Authenticated {
+                        name: named.name.clone(),
+                        scope: named.scope,
+                    };
+                    *session_token.borrow_mut() = Some(token);
+                    resp
+                }
+                None => {
+                    let err = ErrorInfo {
+                        code: "auth_failed".to_string(),
+                        message: t!("daemon.err.auth").to_string(),
+                    };
+                    record_error(health, &err.code, &err.message);
+                    Response::Error(err)
+                }
+            },
+        }
+    }
+}
+
+/// Serves one connection; `remote` (TCP) connections must authenticate for
+/// reads when read-scoped tokens are configured.
+#[cfg(not(feature = "async"))]
+fn handle_client<S>(mut stream: S, ctx: ClientContext, remote: bool)
+where
+    S: Read + Write,
+    for<'a> &'a S: Read + Write,
+{
+    let _client = HealthTracker::client_connected(&ctx.health);
+    let session = ClientSession::new(ctx, remote);
+
+    // A zero first byte starts a length-prefixed frame; anything else is a
+    // single unframed request read to EOF.
+    let mut first = [0u8; 1];
+    let n = stream.read(&mut first).unwrap_or(0);
+    if n == 1 && first[0] == 0 {
+        let mut reader = Cursor::new(first).chain(&stream);
+        loop {
+            let buf = match protocol::read_frame(&mut reader) {
+                Ok(Some(buf)) => buf,
+                Ok(None) => break,
+                Err(err) => {
+                    record_error(
+                        &session.ctx.health,
+                        "bad_frame",
+                        &format!("bad frame: {err}"),
+                    );
+                    break;
+                }
+            };
+            if let Some(reply) = session.serve(&buf, true) {
+                if protocol::write_frame(&mut &stream, &reply).is_err() {
+                    break;
+                }
+            }
+        }
+    } else {
+        let mut buf = first[..n].to_vec();
+        let _ = stream.read_to_end(&mut buf);
+        if let Some(reply) = session.serve(&buf, false) {
+            let _ = stream.write_all(&reply);
+        }
+    }
+}
+
+/// Applies one control change (SetControl, `PUT /api/v1/controls/{name}`);
+/// successful changes are logged with the name of the token that made them.
+/// A dry run only validates it and lists what it would run.
+fn set_control(
+    control: &Arc<Mutex<ControlManager>>,
+    health: &Arc<Mutex<HealthTracker>>,
+    name: &str,
+    value: String,
+    dry_run: bool,
+    who: &str,
+) -> Response {
+    let Ok(mut ctrl) = control.lock() else {
+        let err = ErrorInfo {
+            code: "lock_error".to_string(),
+            message: t!("daemon.err.lock").to_string(),
+        };
+        record_error(health, &err.code, &err.message);
+        return Response::Error(err);
+    };
+    let result = if dry_run {
+        ctrl.plan_control(name, &value)
+            .map(|actions| Response::ControlPlan {
+                control: name.to_string(),
+                value: value.clone(),
+                actions,
+            })
+    } else {
+        ctrl.apply_control(name, &value).map(|info| {
+            eprintln!("Control {name} set to {value:?} by {who}");
+            Response::ControlState(info)
+        })
+    };
+    match result {
+        Ok(response) => response,
+        Err(e) => {
+            // An unknown name or a malformed value is the caller's mistake
+            let code = match e {
+                ControlError::Unknown(_) | ControlError::Invalid(_) => "invalid_control",
+                ControlError::Failed(_) => "control_error",
+            };
+            let error_info = ErrorInfo {
+                code: code.to_string(),
+                message: e.to_string(),
+            };
+            record_error(health, &error_info.code, &error_info.message);
+            Response::Error(error_info)
+        }
+    }
+}
+
+/// Rejects a profile entry naming a control this daemon does not have, or
+/// with a malformed value; controls the board cannot apply right now are kept.
+fn check_profile(
+    control: &Arc<Mutex<ControlManager>>,
+    controls: &[(String, String)],
+) -> Result<(), ErrorInfo> {
+    let ctrl = control.lock().map_err(|_| ErrorInfo {
+        code: "lock_error".to_string(),
+        message: t!("daemon.err.lock").to_string(),
+    })?;
+    for (name, value) in controls {
+        if let Err(e) = ctrl.check_control(name, value) {
+            return Err(ErrorInfo {
+                code: "profile_error".to_string(),
+                message: match e {
+                    ControlError::Unknown(_) => {
+                        t!("profile.err.control", control = name).to_string()
+                    }
+                    _ => format!("{name}={value}: {e}"),
+                },
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Applies a profile's controls in order, after checking every value with a
+/// dry run so a bad entry changes nothing; unsupported controls are skipped.
+fn apply_profile(
+    control: &Arc<Mutex<ControlManager>>,
+    profiles: &Arc<Mutex<ProfileStore>>,
+    health: &Arc<Mutex<HealthTracker>>,
+    name: &str,
+    dry_run: bool,
+    who: &str,
+) -> Response {
+    let fail = |code: &str, message: String| {
+        record_error(health, code, &message);
+        Response::Error(ErrorInfo {
+            code: code.to_string(),
+            message,
+        })
+    };
+    let (Ok(mut store), Ok(mut ctrl)) = (profiles.lock(), control.lock()) else {
+        return fail("lock_error", t!("daemon.err.lock").to_string());
+    };
+    let Some(profile) = store.get(name).cloned() else {
+        return fail(
+            "unknown_profile",
+            t!("profile.err.unknown", name = name).to_string(),
+        );
+    };
+    let (supported, skipped): (Vec<_>, Vec<_>) = profile
+        .controls
+        .into_iter()
+        .partition(|(control, _)| ctrl.control_info(control).supported);
+    let skipped: Vec<String> = skipped.into_iter().map(|(control, _)| control).collect();
+
+    let mut actions = Vec::new();
+    for (control, value) in &supported {
+        match ctrl.plan_control(control, value) {
+            Ok(plan) => actions.extend(plan),
+            Err(e) => return fail("control_error", format!("{name}: {control}={value}: {e}")),
+        }
+    }
+    if dry_run {
+        return Response::ProfilePlan {
+            name: name.to_string(),
+            actions,
+            skipped,
+        };
+    }
+    let mut controls = Vec::new();
+    for (control, value) in &supported {
+        match ctrl.apply_control(control, value) {
+            Ok(info) => controls.push(info),
+            Err(e) => return fail("control_error", format!("{name}: {control}={value}: {e}")),
+        }
+    }
+    store.set_active(name);
+    eprintln!("Profile {name} applied by {who}");
+    Response::ProfileApplied {
+        name: name.to_string(),
+        controls,
+        skipped,
+    }
+}
+
+/// Signals or renices a process once `access` allows it; logged like control changes.
+fn control_process(
+    health: &Arc<Mutex<HealthTracker>>,
+    access: Access,
+    pid: u32,
+    action: impl FnOnce() -> anyhow::Result<String>,
+) -> Response {
+    let result = if access.allowed() {
+        action().map_err(|e| ErrorInfo {
+            code: "process_error".to_string(),
+            message: e.to_string(),
+        })
+    } else {
+        Err(ErrorInfo {
+            code: "auth_failed".to_string(),
+            message: t!("daemon.err.auth").to_string(),
+        })
+    };
+    match result {
+        Ok(action) => {
+            eprintln!("Process {pid}: {action} by {}", access.who());
+            Response::ProcessUpdated { pid, action }
+        }
+        Err(err) => {
+            record_error(health, &err.code, &err.message);
+            Response::Error(err)
+        }
+    }
+}
+
+fn decode_request(buf: &[u8]) -> Result<(Request, Framing), serde_json::Value> {
+    Ok(match jsonrpc::decode(buf) {
+        Some(Ok(call)) => (call.request, Framing::JsonRpc(call.id)),
+        Some(Err(reply)) => return Err(reply),
+        None => match serde_json::from_slice::<Request>(buf) {
+            Ok(r) => (r, Framing::Json),
+            Err(_) => match serde_cbor::from_slice::<Request>(buf) {
+                Ok(r) => (r, Framing::Cbor),
+                Err(_) => (Request::GetStats, Framing::Json),
+            },
+        },
+    })
+}
+
+/// Wire format of a request, mirrored in the reply.
+enum Framing {
+    Json,
+    Cbor,
+    JsonRpc(Option<serde_json::Value>),
+}
+
+/// Reply payload in the request's format; `None` for JSON-RPC notifications.
+/// Only `framed` JSON and CBOR replies carry the protocol version: unframed
+/// one-shot clients decode a bare `Response`, as before versioning.
+fn encode_reply(resp: Response, framing: Framing, framed: bool) -> Option<Vec<u8>> {
+    match framing {
+        Framing::JsonRpc(Some(id)) => Some(jsonrpc::reply(id, resp).to_string().into_bytes()),
+        Framing::JsonRpc(None) => None,
+        Framing::Cbor if framed => {
+            let reply = Reply::new(resp);
+            Some(
+                serde_cbor::to_vec(&reply)
+                    .unwrap_or_else(|_| serde_json::to_vec(&reply).unwrap_or_default()),
+            )
+        }
+        Framing::Cbor => Some(
+            serde_cbor::to_vec(&resp)
+                .unwrap_or_else(|_| serde_json::to_vec(&resp).unwrap_or_default()),
+        ),
+        Framing::Json if framed => {
+            Some(serde_json::to_vec(&Reply::new(resp)).unwrap_or_else(|_| b"{}".to_vec()))
+        }
+        Framing::Json => Some(serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec())),
+    }
+}
+
+/// Whether `token` may do what `scope` covers, and whose token it is.
+fn auth_ok(token: Option<&str>, scope: Scope) -> Access {
+    TOKENS.check(token, scope)
+}
+
+/// HTTP reads (`/metrics`, GET `/api/v1/*`): JETSONSCOPE_METRICS_TOKEN or any
+/// named token as the bearer, required once either kind is configured.
+fn http_read_ok(request: &tiny_http::Request) -> bool {
+    let metrics_token = std::env::var("JETSONSCOPE_METRICS_TOKEN")
+        .ok()
+        .filter(|t| !t.is_empty());
+    if metrics_token.is_none() && !TOKENS.restricts_reads() {
+        return true;
+    }
+    let bearer = metrics_auth::bearer_token(request);
+    metrics_token.is_some_and(|t| bearer.as_deref() == Some(t.as_str()))
+        || TOKENS.find(bearer.as_deref(), Scope::Read).is_some()
+}
+
+/// SQLite history (`sqlite` feature, JETSONSCOPE_HISTORY_DB), shared by the stats thread and clients.
+#[cfg(feature = "sqlite")]
+type LongTermHistory = Option<Arc<HistoryDb>>;
+#[cfg(not(feature = "sqlite"))]
+type LongTermHistory = Option<Arc<()>>;
+
+#[cfg(feature = "sqlite")]
+fn open_long_term_history() -> LongTermHistory {
+    let cfg = HistoryDbConfig::from_env()?;
+    match HistoryDb::open(&cfg) {
+        Ok(db) => Some(Arc::new(db)),
+        Err(e) => {
+            eprintln!("History database disabled ({}): {e:#}", cfg.path.display());
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn open_long_term_history() -> LongTermHistory {
+    if std::env::var_os("JETSONSCOPE_HISTORY_DB").is_some() {
+        eprintln!("JETSONSCOPE_HISTORY_DB ignored: jscoped was built without the sqlite feature");
+    }
+    None
+}
+
+#[cfg(feature = "sqlite")]
+fn store_long_term_history(
+    db: &LongTermHistory,
+    unix_ms: u64,
+    stats: &TegraStats,
+) -> anyhow::Result<()> {
+    match db {
+        Some(db) => db.insert(unix_ms, stats),
+        None => Ok(()),
     }
+}
 
-    let (req, respond_cbor) = match serde_json::from_slice::<Request>(&buf) {
-        Ok(r) => (r, false),
-        Err(_) => match serde_cbor::from_slice::<Request>(&buf) {
-            Ok(r) => (r, true),
-            Err(_) => (Request::GetStats, false),
-        },
-    };
+#[cfg(not(feature = "sqlite"))]
+fn store_long_term_history(_: &LongTermHistory, _: u64, _: &TegraStats) -> anyhow::Result<()> {
+    Ok(())
+}
 
-    let response = match req {
-        Request::GetStats => {
-            let s = stats.lock().ok().and_then(|g| g.clone());
-            let l = label.lock().ok().map(|g| g.clone()).unwrap_or_default();
-            Response::Stats { source: l, data: s }
-        }
-        Request::GetHealth => {
-            let h = health
-                .lock()
-                .map(|hh| hh.get_health(0))
-                .unwrap_or_else(|_| HealthTracker::new().get_health(0));
-            Response::Health(h)
-        }
-        Request::GetMeta => Response::Meta((*hardware).clone()),
-        Request::ListControls => match control.lock() {
-            Ok(ctrl) => Response::Controls(ctrl.list_controls()),
-            Err(_) => Response::Error(ErrorInfo {
-                code: "lock_error".to_string(),
-                message: "Lock error".to_string(),
-            }),
-        },
-        Request::SetControl {
-            control: name,
-            value,
-            token,
-        } => {
-            if !auth_ok(token) {
-                let err = ErrorInfo {
-                    code: "auth_failed".to_string(),
-                    message: "Auth failed (set JETSONSCOPE_AUTH_TOKEN)".to_string(),
-                };
-                record_error(&health, &err.message);
-                Response::Error(err)
-            } else if let Ok(mut ctrl) = control.lock() {
-                let mut err = None;
-                match name.as_str() {
-                    "jetson_clocks" => ctrl.toggle_jetson_clocks(),
-                    "nvpmodel" => ctrl.set_nvpmodel_mode(Some(value)),
-                    "fan" => {
-                        if let Ok(p) = value.parse::<u8>() {
-                            ctrl.set_fan(p);
-                        } else {
-                            err = Some("Invalid fan value (0-100)".to_string());
-                        }
-                    }
-                    "cpu_governor" => {
-                        if let Err(e) = ctrl.set_cpu_governor(&value) {
-                            err = Some(e.to_string());
-                        }
-                    }
-                    _ => err = Some("Unknown control".to_string()),
-                }
+#[cfg(feature = "sqlite")]
+fn query_long_term_history(
+    db: &LongTermHistory,
+    from_ms: u64,
+    to_ms: u64,
+    max_points: usize,
+) -> Result<Vec<history::StatsSample>, ErrorInfo> {
+    let db = db.as_ref().ok_or_else(|| ErrorInfo {
+        code: "history_db_off".to_string(),
+        message: t!("daemon.err.history_db_off").to_string(),
+    })?;
+    db.query(from_ms, to_ms, max_points).map_err(|e| ErrorInfo {
+        code: "history_db_error".to_string(),
+        message: format!("{e:#}"),
+    })
+}
 
-                if let Some(e) = err {
-                    let error_info = ErrorInfo {
-                        code: "invalid_control".to_string(),
-                        message: e,
-                    };
-                    record_error(&health, &error_info.message);
-                    Response::Error(error_info)
-                } else if let Some(last_err) = &ctrl.status().last_error {
-                    let error_info = ErrorInfo {
-                        code: "control_error".to_string(),
-                        message: last_err.clone(),
-                    };
-                    record_error(&health, &error_info.message);
-                    Response::Error(error_info)
-                } else {
-                    Response::ControlState(ctrl.control_info(&name))
-                }
-            } else {
-                let err = ErrorInfo {
-                    code: "lock_error".to_string(),
-                    message: "Lock error".to_string(),
-                };
-                record_error(&health, &err.message);
-                Response::Error(err)
+#[cfg(not(feature = "sqlite"))]
+fn query_long_term_history(
+    _: &LongTermHistory,
+    _: u64,
+    _: u64,
+    _: usize,
+) -> Result<Vec<history::StatsSample>, ErrorInfo> {
+    Err(ErrorInfo {
+        code: "history_db_off".to_string(),
+        message: t!("daemon.err.history_db_off").to_string(),
+    })
+}
+
+/// Audit-log the thermal guard's changes like client ones, attributed to "thermal_guard".
+fn log_guard_event(event: &GuardEvent) {
+    match event {
+        GuardEvent::Tripped { temp_c, actions } => {
+            eprintln!("Thermal guard tripped at {temp_c:.1}C");
+            for (name, value) in actions {
+                eprintln!("Control {name} set to {value:?} by thermal_guard");
             }
         }
-    };
-
-    write_response(&mut stream, response, respond_cbor);
+        GuardEvent::Recovered { temp_c, restored } => {
+            eprintln!("Thermal guard recovered at {temp_c:.1}C");
+            for (name, value) in restored {
+                eprintln!("Control {name} set to {value:?} by thermal_guard");
+            }
+        }
+        GuardEvent::Failed { control, error } => {
+            eprintln!("Thermal guard could not set {control}: {error}");
+        }
+    }
 }
 
-fn write_response(stream: &mut UnixStream, resp: Response, as_cbor: bool) {
-    if as_cbor {
-        if let Ok(bytes) = serde_cbor::to_vec(&resp) {
-            let _ = stream.write_all(&bytes);
-            return;
-        }
+fn record_error(health: &Arc<Mutex<HealthTracker>>, code: &str, message: &str) {
+    if let Ok(mut h) = health.lock() {
+        h.record_error(code, message.to_string());
     }
-    let json = serde_json::to_string(&resp).unwrap_or_else(|_| "{}".to_string());
-    let _ = stream.write_all(json.as_bytes());
 }
 
-fn auth_ok(token: Option<String>) -> bool {
-    if let Ok(expected) = std::env::var("JETSONSCOPE_AUTH_TOKEN")
-        .or_else(|_| std::env::var("TEGRA_AUTH_TOKEN"))
-    {
-        if expected.is_empty() {
-            return true;
+/// Netdata external plugin mode: charts on stdout, no socket or HTTP server.
+///
+/// Runs its own stats source (same selection as the daemon), so it works whether
+/// or not a daemon is up. Exits when Netdata closes the pipe.
+fn run_netdata_plugin(update_every: u64) -> anyhow::Result<()> {
+    let collector = start_collector(CollectorMode::AutoCommand);
+    let mut writer = NetdataWriter::new(update_every);
+    let interval = Duration::from_secs(update_every.max(1));
+    let mut latest: Option<TegraStats> = None;
+    let mut last_sent: Option<Instant> = None;
+    let mut next = Instant::now() + interval;
+    let mut stdout = std::io::stdout().lock();
+    loop {
+        let wait = next.saturating_duration_since(Instant::now());
+        match collector.rx.recv_timeout(wait) {
+            Ok(CollectorMessage::Stats(stats)) => {
+                latest = Some(stats);
+                continue;
+            }
+            Ok(CollectorMessage::Error(e)) => {
+                eprintln!("jetsonscope.plugin: {}", e);
+                continue;
+            }
+            Ok(CollectorMessage::SourceLabel(_))
+            | Ok(CollectorMessage::ParseFailed(_))
+            | Ok(CollectorMessage::FormatDrift(_))
+            | Ok(CollectorMessage::Poll { .. })
+            | Ok(CollectorMessage::Reply(_)) => continue,
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+                writeln!(stdout, "DISABLE")?;
+                return Ok(());
+            }
+        }
+        next += interval;
+        let Some(stats) = &latest else { continue };
+        let since_last = last_sent
+            .map(|t| t.elapsed().as_micros() as u64)
+            .unwrap_or(0);
+        last_sent = Some(Instant::now());
+        // A write error means Netdata went away
+        if stdout
+            .write_all(writer.sample(stats, since_last).as_bytes())
+            .and_then(|_| stdout.flush())
+            .is_err()
+        {
+            return Ok(());
         }
-        token.map(|t| t == expected).unwrap_or(false)
-    } else {
-        true
     }
 }
 
-fn record_error(health: &Arc<Mutex<HealthTracker>>, message: &str) {
-    if let Ok(mut h) = health.lock() {
-        h.record_error(message.to_string());
-    }
+/// Settings for the metrics/probe HTTP endpoint beyond the shared state.
+struct HttpOptions {
+    dcgm: Option<DcgmConfig>,
+    /// Node/pod labels added to every series
+    labels: MetricLabels,
+    /// `/readyz` fails once the newest sample is older than this
+    ready_max_age: Duration,
+    /// Served by `/api/v1/meta`
+    hardware: Arc<JetsonHardware>,
+    /// `jetsonscope_alert_active` series
+    alerts: Arc<Mutex<AlertEngine>>,
+    /// Top I/O processes exported as metrics (JETSONSCOPE_METRICS_PROCESS_IO; 0: off)
+    process_io_top: usize,
 }
 
 // HTTP metrics/debug
+#[allow(clippy::too_many_arguments)]
 fn spawn_http_metrics(
     addr: String,
     health: Arc<Mutex<HealthTracker>>,
     stats: Arc<Mutex<Option<TegraStats>>>,
     control: Arc<Mutex<ControlManager>>,
+    energy: Arc<Mutex<EnergyMeter>>,
+    gpu_attribution: Arc<Mutex<GpuAttribution>>,
+    process_monitor: Arc<Mutex<ProcessMonitor>>,
+    options: HttpOptions,
 ) {
     thread::spawn(move || {
         if let Ok(server) = Server::http(&addr) {
-            for request in server.incoming_requests() {
+            for mut request in server.incoming_requests() {
                 let path = request.url().to_string();
-                let resp = handle_http_request(&request, &path, &health, &stats, &control)
-                    .unwrap_or_else(|| HttpResponse::from_string("not found").with_status_code(404));
+                if path.starts_with("/ws/stats") {
+                    serve_stats_websocket(request, &path, &stats, &health);
+                    continue;
+                }
+                let _client = HealthTracker::client_connected(&health);
+                let resp = handle_http_request(
+                    &mut request,
+                    &path,
+                    &health,
+                    &stats,
+                    &control,
+                    &energy,
+                    &gpu_attribution,
+                    &process_monitor,
+                    &options,
+                )
+                .unwrap_or_else(|| HttpResponse::from_string("not found").with_status_code(404));
                 let _ = request.respond(resp);
             }
         }
     });
 }
 
+#[allow(clippy::too_many_arguments)]
 fn handle_http_request(
-    request: &tiny_http::Request,
+    request: &mut tiny_http::Request,
     path: &str,
     health: &Arc<Mutex<HealthTracker>>,
     stats: &Arc<Mutex<Option<TegraStats>>>,
     control: &Arc<Mutex<ControlManager>>,
+    energy: &Arc<Mutex<EnergyMeter>>,
+    gpu_attribution: &Arc<Mutex<GpuAttribution>>,
+    process_monitor: &Arc<Mutex<ProcessMonitor>>,
+    options: &HttpOptions,
 ) -> Option<HttpResponse<Cursor<Vec<u8>>>> {
+    // Kubernetes probes: unauthenticated, plain text
+    if path == "/healthz" {
+        return Some(HttpResponse::from_string("ok").with_status_code(200));
+    }
+    if path == "/readyz" {
+        let age = health.lock().ok().and_then(|h| h.last_stats_age());
+        return Some(match age {
+            Some(age) if age <= options.ready_max_age => {
+                HttpResponse::from_string("ok").with_status_code(200)
+            }
+            Some(age) => {
+                HttpResponse::from_string(format!("stale: last sample {}s ago", age.as_secs()))
+                    .with_status_code(503)
+            }
+            None => HttpResponse::from_string("waiting for first sample").with_status_code(503),
+        });
+    }
+    if path.starts_with("/api/v1/") {
+        return Some(handle_api_request(
+            request,
+            path,
+            health,
+            stats,
+            control,
+            &options.hardware,
+        ));
+    }
     if path.starts_with("/metrics") {
-        if !metrics_auth::authorize_request(request, "JETSONSCOPE_METRICS_TOKEN") {
+        if !http_read_ok(request) {
             return Some(HttpResponse::from_string("unauthorized").with_status_code(401));
         }
-        let metrics = build_metrics(health, stats, control);
+        let mut metrics = match &options.dcgm {
+            Some(cfg) if cfg.only => String::new(),
+            _ => build_metrics(health, stats, control, energy),
+        };
+        if let Some(cfg) = &options.dcgm {
+            if let Some(s) = stats.lock().ok().and_then(|s| s.clone()) {
+                metrics.push_str(&cfg.render(&s));
+            }
+        }
+        if let Ok(engine) = options.alerts.lock() {
+            metrics.push_str(&engine.prometheus());
+        }
+        if options.process_io_top > 0 {
+            let top = scan_processes(
+                process_monitor,
+                gpu_attribution,
+                ProcessSort::Io,
+                options.process_io_top,
+            );
+            metrics.push_str(&process_io_metrics(&top));
+        }
+        let metrics = options.labels.apply(&metrics);
         let resp = HttpResponse::from_string(metrics)
             .with_status_code(200)
             .with_header(
@@ -311,7 +1875,7 @@ fn handle_http_request(
             return Some(HttpResponse::from_string("unauthorized").with_status_code(401));
         }
         if path.starts_with("/debug/processes") {
-            let body = debug_processes();
+            let body = debug_processes(process_monitor, gpu_attribution);
             let resp = HttpResponse::from_string(body)
                 .with_status_code(200)
                 .with_header(Header::from_bytes(b"Content-Type", b"application/json").unwrap());
@@ -328,10 +1892,268 @@ fn handle_http_request(
     None
 }
 
-fn debug_processes() -> String {
-    let mut mon = ProcessMonitor::new();
-    let top = mon.top_processes(15, false);
-    serde_json::to_string(&top).unwrap_or_else(|_| "[]".to_string())
+/// REST view of the socket API for clients on other hosts.
+///
+/// GETs are guarded like `/metrics` (JETSONSCOPE_METRICS_TOKEN or a read
+/// token). `PUT /api/v1/controls/{name}` needs an admin token as the bearer
+/// token and is refused while no token is set, so controls are never open to
+/// the network by default. The body is the value, as plain text, a JSON
+/// string or number, or `{"value": ...}`.
+fn handle_api_request(
+    request: &mut tiny_http::Request,
+    path: &str,
+    health: &Arc<Mutex<HealthTracker>>,
+    stats: &Arc<Mutex<Option<TegraStats>>>,
+    control: &Arc<Mutex<ControlManager>>,
+    hardware: &JetsonHardware,
+) -> HttpResponse<Cursor<Vec<u8>>> {
+    let route = path.split('?').next().unwrap_or(path).trim_end_matches('/');
+    let control_name = route.strip_prefix("/api/v1/controls/");
+
+    if *request.method() == Method::Put {
+        let Some(name) = control_name else {
+            return api_error(
+                405,
+                "method_not_allowed",
+                "PUT only applies to /api/v1/controls/{name}",
+            );
+        };
+        if TOKENS.is_empty() {
+            return api_error(
+                403,
+                "auth_required",
+                "set JETSONSCOPE_AUTH_TOKEN or JETSONSCOPE_AUTH_TOKENS on the daemon to allow control changes over HTTP",
+            );
+        }
+        let access = auth_ok(metrics_auth::bearer_token(request).as_deref(), Scope::Admin);
+        if !access.allowed() {
+            return api_error(401, "auth_failed", t!("daemon.err.auth"));
+        }
+        let mut body = String::new();
+        if request
+            .as_reader()
+            .take(64 * 1024)
+            .read_to_string(&mut body)
+            .is_err()
+        {
+            return api_error(400, "bad_request", "body is not UTF-8 text");
+        }
+        return match set_control(
+            control,
+            health,
+            name,
+            control_value(&body),
+            false,
+            access.who(),
+        ) {
+            Response::ControlState(info) => api_json(200, &info),
+            Response::Error(err) => {
+                let status = match err.code.as_str() {
+                    "invalid_control" => 400,
+                    _ => 500,
+                };
+                api_error(status, &err.code, &err.message)
+            }
+            _ => api_error(500, "internal", "unexpected reply"),
+        };
+    }
+    if *request.method() != Method::Get {
+        return api_error(405, "method_not_allowed", "use GET, or PUT on a control");
+    }
+    if !http_read_ok(request) {
+        return api_error(401, "auth_failed", "unauthorized");
+    }
+    match (route, control_name) {
+        ("/api/v1/stats", _) => match stats.lock().ok().and_then(|s| s.clone()) {
+            Some(latest) => api_json(200, &latest),
+            None => api_error(503, "no_stats", "waiting for first sample"),
+        },
+        ("/api/v1/meta", _) => api_json(200, hardware),
+        ("/api/v1/health", _) => match health.lock() {
+            Ok(h) => api_json(200, &h.get_health()),
+            Err(_) => api_error(500, "lock_error", t!("daemon.err.lock")),
+        },
+        ("/api/v1/controls", _) => match control.lock() {
+            Ok(ctrl) => api_json(200, &ctrl.list_controls()),
+            Err(_) => api_error(500, "lock_error", t!("daemon.err.lock")),
+        },
+        (_, Some(name)) => match control
+            .lock()
+            .ok()
+            .and_then(|ctrl| ctrl.list_controls().into_iter().find(|c| c.name == name))
+        {
+            Some(info) => api_json(200, &info),
+            None => api_error(404, "unknown_control", &format!("no control named {name}")),
+        },
+        _ => api_error(404, "not_found", "not found"),
+    }
+}
+
+/// The value of a control PUT body: `{"value": ...}`, a JSON string, or the trimmed text.
+fn control_value(body: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(serde_json::Value::Object(obj)) => match obj.get("value") {
+            Some(serde_json::Value::String(s)) => s.clone(),
+            Some(other) => other.to_string(),
+            None => String::new(),
+        },
+        Ok(serde_json::Value::String(s)) => s,
+        _ => body.trim().to_string(),
+    }
+}
+
+fn api_json<T: serde::Serialize>(status: u16, body: &T) -> HttpResponse<Cursor<Vec<u8>>> {
+    HttpResponse::from_string(serde_json::to_string(body).unwrap_or_else(|_| "{}".to_string()))
+        .with_status_code(status)
+        .with_header(Header::from_bytes(b"Content-Type", b"application/json").unwrap())
+}
+
+fn api_error(status: u16, code: &str, message: &str) -> HttpResponse<Cursor<Vec<u8>>> {
+    api_json(
+        status,
+        &serde_json::json!({ "error": { "code": code, "message": message } }),
+    )
+}
+
+/// Upgrades `/ws/stats` and pushes the latest `TegraStats` as a JSON text frame
+/// every `interval_ms` (query string, default JETSONSCOPE_WS_INTERVAL_MS or 1 s)
+/// until the client goes away. Guarded by JETSONSCOPE_DEBUG_TOKEN, which may
+/// also be passed as `?token=` since browsers cannot set headers on WebSockets.
+fn serve_stats_websocket(
+    request: tiny_http::Request,
+    path: &str,
+    stats: &Arc<Mutex<Option<TegraStats>>>,
+    health: &Arc<Mutex<HealthTracker>>,
+) {
+    let query = path.split_once('?').map(|(_, q)| q).unwrap_or("");
+    if !metrics_auth::authorize_request_or_query(&request, query, "JETSONSCOPE_DEBUG_TOKEN") {
+        let _ = request.respond(HttpResponse::from_string("unauthorized").with_status_code(401));
+        return;
+    }
+    let header = |name: &'static str| {
+        request
+            .headers()
+            .iter()
+            .find(|h| h.field.equiv(name))
+            .map(|h| h.value.to_string())
+    };
+    let upgrade = header("Upgrade").is_some_and(|v| v.eq_ignore_ascii_case("websocket"));
+    let Some(key) = header("Sec-WebSocket-Key").filter(|_| upgrade) else {
+        let _ = request.respond(
+            HttpResponse::from_string("expected a WebSocket upgrade").with_status_code(400),
+        );
+        return;
+    };
+    let interval = websocket::stream_interval(query);
+    // tiny_http adds the Upgrade and Connection headers itself
+    let response = HttpResponse::empty(101).with_header(
+        Header::from_bytes(
+            b"Sec-WebSocket-Accept",
+            websocket::accept_key(&key).as_bytes(),
+        )
+        .unwrap(),
+    );
+    let mut stream = request.upgrade("websocket", response);
+    let stats = stats.clone();
+    let client = HealthTracker::client_connected(health);
+    thread::spawn(move || {
+        let _client = client;
+        loop {
+            let latest = stats.lock().ok().and_then(|s| s.clone());
+            if let Some(latest) = latest {
+                let frame = serde_json::to_string(&latest).unwrap_or_else(|_| "{}".to_string());
+                if websocket::write_text(&mut stream, &frame).is_err() {
+                    break;
+                }
+            }
+            thread::sleep(interval);
+        }
+    });
+}
+
+/// Scans closer together than this reuse the last one (CPU% and I/O rates
+/// over a few milliseconds are noise).
+const PROCESS_SCAN_MIN_AGE: Duration = Duration::from_secs(1);
+
+/// Every process from the shared monitor, rescanned if stale, in `sort` order.
+fn all_processes(
+    process_monitor: &Arc<Mutex<ProcessMonitor>>,
+    sort: ProcessSort,
+) -> Vec<processes::ProcessInfo> {
+    let mut all = match process_monitor.lock() {
+        Ok(mut monitor) => {
+            monitor.refresh_if_older(PROCESS_SCAN_MIN_AGE);
+            monitor.processes()
+        }
+        Err(_) => Vec::new(),
+    };
+    processes::sort_processes(&mut all, sort);
+    all
+}
+
+/// The first `limit` processes in `sort` order, with their GPU share.
+fn scan_processes(
+    process_monitor: &Arc<Mutex<ProcessMonitor>>,
+    gpu_attribution: &Arc<Mutex<GpuAttribution>>,
+    sort: ProcessSort,
+    limit: usize,
+) -> Vec<processes::ProcessInfo> {
+    let mut top = all_processes(process_monitor, sort);
+    top.truncate(limit);
+    if let Ok(attribution) = gpu_attribution.lock() {
+        attribution.annotate_processes(&mut top);
+    }
+    top
+}
+
+fn debug_processes(
+    process_monitor: &Arc<Mutex<ProcessMonitor>>,
+    gpu_attribution: &Arc<Mutex<GpuAttribution>>,
+) -> String {
+    #[derive(serde::Serialize)]
+    struct DebugProcesses {
+        processes: Vec<processes::ProcessInfo>,
+        users: Vec<processes::UserUsage>,
+    }
+
+    let mut all = all_processes(process_monitor, ProcessSort::Cpu);
+    // Per-user totals cover every process, not just the top 15
+    let users = processes::usage_by_user(&all, ProcessSort::Cpu);
+    all.truncate(15);
+    if let Ok(attribution) = gpu_attribution.lock() {
+        attribution.annotate_processes(&mut all);
+    }
+    serde_json::to_string(&DebugProcesses {
+        processes: all,
+        users,
+    })
+    .unwrap_or_else(|_| "{}".to_string())
+}
+
+/// Read/write rates of the top I/O processes, labelled by pid and name.
+fn process_io_metrics(top: &[processes::ProcessInfo]) -> String {
+    let mut out = String::new();
+    out.push_str("# HELP jetsonscope_process_io_read_bytes_per_second Storage reads of the top I/O processes\n");
+    out.push_str("# TYPE jetsonscope_process_io_read_bytes_per_second gauge\n");
+    for p in top {
+        out.push_str(&format!(
+            "jetsonscope_process_io_read_bytes_per_second{{pid=\"{}\",name=\"{}\"}} {}\n",
+            p.pid,
+            sanitize_label(&p.name),
+            p.read_bytes_per_sec
+        ));
+    }
+    out.push_str("# HELP jetsonscope_process_io_write_bytes_per_second Storage writes of the top I/O processes\n");
+    out.push_str("# TYPE jetsonscope_process_io_write_bytes_per_second gauge\n");
+    for p in top {
+        out.push_str(&format!(
+            "jetsonscope_process_io_write_bytes_per_second{{pid=\"{}\",name=\"{}\"}} {}\n",
+            p.pid,
+            sanitize_label(&p.name),
+            p.write_bytes_per_sec
+        ));
+    }
+    out
 }
 
 fn debug_snapshot(
@@ -346,7 +2168,7 @@ fn debug_snapshot(
         control: jetsonscope::control::ControlStatus,
     }
 
-    let h = health.lock().ok().map(|hh| hh.get_health(0));
+    let h = health.lock().ok().map(|hh| hh.get_health());
     let s = stats.lock().ok().and_then(|ss| ss.clone());
     let ctrl = control
         .lock()
@@ -371,20 +2193,33 @@ fn debug_snapshot(
             supports_gpu_railgate: false,
             note: "unavailable".into(),
             last_error: None,
+            clock_cap: Default::default(),
+            idle: Default::default(),
+            guard: Default::default(),
+            max_freqs: Default::default(),
+            cpu_min_freq_khz: None,
+            cpu_max_freq_khz: None,
+            fans: Vec::new(),
+            fan_curve: None,
         });
 
-    serde_json::to_string(&Snapshot { health: h, stats: s, control: ctrl })
-        .unwrap_or_else(|_| "{}".to_string())
+    serde_json::to_string(&Snapshot {
+        health: h,
+        stats: s,
+        control: ctrl,
+    })
+    .unwrap_or_else(|_| "{}".to_string())
 }
 
 fn build_metrics(
     health: &Arc<Mutex<HealthTracker>>,
     stats: &Arc<Mutex<Option<TegraStats>>>,
     control: &Arc<Mutex<ControlManager>>,
+    energy: &Arc<Mutex<EnergyMeter>>,
 ) -> String {
     let mut out = String::new();
     if let Ok(h) = health.lock() {
-        let snap = h.get_health(0);
+        let snap = h.get_health();
         out.push_str(&format!(
             concat!(
                 "# HELP jetsonscope_uptime_seconds Daemon uptime in seconds\n",
@@ -399,6 +2234,9 @@ fn build_metrics(
                 "# HELP jetsonscope_stats_collected_total Total stats collected\n",
                 "# TYPE jetsonscope_stats_collected_total counter\n",
                 "jetsonscope_stats_collected_total {}\n",
+                "# HELP jetsonscope_stats_dropped_total Samples dropped while the daemon fell behind the collector\n",
+                "# TYPE jetsonscope_stats_dropped_total counter\n",
+                "jetsonscope_stats_dropped_total {}\n",
                 "# HELP jetsonscope_connected_clients Connected clients (observed)\n",
                 "# TYPE jetsonscope_connected_clients gauge\n",
                 "jetsonscope_connected_clients {}\n"
@@ -407,34 +2245,127 @@ fn build_metrics(
             snap.total_requests,
             snap.errors,
             snap.stats_collected,
+            snap.dropped_samples,
             snap.connected_clients
         ));
+        if !snap.requests_by_type.is_empty() {
+            out.push_str(
+                "# HELP jetsonscope_requests_by_type_total Requests handled per request type\n",
+            );
+            out.push_str("# TYPE jetsonscope_requests_by_type_total counter\n");
+        }
+        for (request, n) in &snap.requests_by_type {
+            out.push_str(&format!(
+                "jetsonscope_requests_by_type_total{{request=\"{}\"}} {}\n",
+                request, n
+            ));
+        }
+        if !snap.errors_by_code.is_empty() {
+            out.push_str("# HELP jetsonscope_errors_by_code_total Errors per error code\n");
+            out.push_str("# TYPE jetsonscope_errors_by_code_total counter\n");
+        }
+        for (code, n) in &snap.errors_by_code {
+            out.push_str(&format!(
+                "jetsonscope_errors_by_code_total{{code=\"{}\"}} {}\n",
+                code, n
+            ));
+        }
+        out.push_str("# HELP jetsonscope_stats_parse_failures_total Stats command lines that yielded no data\n");
+        out.push_str("# TYPE jetsonscope_stats_parse_failures_total counter\n");
+        out.push_str(&format!(
+            "jetsonscope_stats_parse_failures_total {}\n",
+            snap.parse_failures
+        ));
+        if let Some(gap) = h.sample_interval() {
+            out.push_str("# HELP jetsonscope_stats_interval_seconds Time between the last two stats samples\n");
+            out.push_str("# TYPE jetsonscope_stats_interval_seconds gauge\n");
+            out.push_str(&format!(
+                "jetsonscope_stats_interval_seconds {:.3}\n",
+                gap.as_secs_f64()
+            ));
+            out.push_str("# HELP jetsonscope_stats_interval_max_seconds Longest time between two stats samples\n");
+            out.push_str("# TYPE jetsonscope_stats_interval_max_seconds gauge\n");
+            out.push_str(&format!(
+                "jetsonscope_stats_interval_max_seconds {:.3}\n",
+                h.max_sample_interval().as_secs_f64()
+            ));
+        }
+        if let Some(age) = h.last_stats_age() {
+            out.push_str("# HELP jetsonscope_stats_age_seconds Time since the last stats sample\n");
+            out.push_str("# TYPE jetsonscope_stats_age_seconds gauge\n");
+            out.push_str(&format!(
+                "jetsonscope_stats_age_seconds {:.3}\n",
+                age.as_secs_f64()
+            ));
+        }
+        if !h.latencies().is_empty() {
+            out.push_str(
+                "# HELP jetsonscope_request_duration_seconds Time to answer socket requests\n",
+            );
+            out.push_str("# TYPE jetsonscope_request_duration_seconds histogram\n");
+        }
+        for (request, hist) in h.latencies() {
+            for (le, count) in LATENCY_BUCKETS.iter().zip(hist.buckets) {
+                out.push_str(&format!(
+                    "jetsonscope_request_duration_seconds_bucket{{request=\"{}\",le=\"{}\"}} {}\n",
+                    request, le, count
+                ));
+            }
+            out.push_str(&format!(
+                "jetsonscope_request_duration_seconds_bucket{{request=\"{}\",le=\"+Inf\"}} {}\n",
+                request, hist.count
+            ));
+            out.push_str(&format!(
+                "jetsonscope_request_duration_seconds_sum{{request=\"{}\"}} {:.6}\n",
+                request, hist.sum_secs
+            ));
+            out.push_str(&format!(
+                "jetsonscope_request_duration_seconds_count{{request=\"{}\"}} {}\n",
+                request, hist.count
+            ));
+        }
     }
 
     if let Ok(snap) = stats.lock() {
         if let Some(s) = snap.as_ref() {
+            if let Some(secs) = s.timestamp_unix {
+                out.push_str("# HELP jetsonscope_stats_timestamp_seconds Sample time from the tegrastats line (Unix seconds)\n");
+                out.push_str("# TYPE jetsonscope_stats_timestamp_seconds gauge\n");
+                out.push_str(&format!("jetsonscope_stats_timestamp_seconds {}\n", secs));
+            }
             // RAM/SWAP
             if let Some(ram) = &s.ram {
                 out.push_str("# HELP jetsonscope_ram_bytes_total RAM total bytes\n");
                 out.push_str("# TYPE jetsonscope_ram_bytes_total gauge\n");
-                out.push_str(&format!("jetsonscope_ram_bytes_total {}\n", ram.total_bytes));
+                out.push_str(&format!(
+                    "jetsonscope_ram_bytes_total {}\n",
+                    ram.total_bytes
+                ));
                 out.push_str("# HELP jetsonscope_ram_bytes_used RAM used bytes\n");
                 out.push_str("# TYPE jetsonscope_ram_bytes_used gauge\n");
                 out.push_str(&format!("jetsonscope_ram_bytes_used {}\n", ram.used_bytes));
                 if let Some(lfb) = &ram.largest_free_block {
                     match lfb {
                         jetsonscope::parser::LargestFreeBlock::Blocks { count, size_bytes } => {
-                            out.push_str("# HELP jetsonscope_ram_lfb_blocks Largest free blocks count\n");
+                            out.push_str(
+                                "# HELP jetsonscope_ram_lfb_blocks Largest free blocks count\n",
+                            );
                             out.push_str("# TYPE jetsonscope_ram_lfb_blocks gauge\n");
                             out.push_str(&format!("jetsonscope_ram_lfb_blocks {}\n", count));
                             out.push_str("# HELP jetsonscope_ram_lfb_block_size_bytes LFB block size bytes\n");
                             out.push_str("# TYPE jetsonscope_ram_lfb_block_size_bytes gauge\n");
-                            out.push_str(&format!("jetsonscope_ram_lfb_block_size_bytes {}\n", size_bytes));
+                            out.push_str(&format!(
+                                "jetsonscope_ram_lfb_block_size_bytes {}\n",
+                                size_bytes
+                            ));
                         }
                         jetsonscope::parser::LargestFreeBlock::Size { size_bytes } => {
                             out.push_str("# HELP jetsonscope_ram_lfb_size_bytes Largest free block size bytes\n");
                             out.push_str("# TYPE jetsonscope_ram_lfb_size_bytes gauge\n");
-                            out.push_str(&format!("jetsonscope_ram_lfb_size_bytes {}\n", size_bytes));
+                            out.push_str(&format!(
+                                "jetsonscope_ram_lfb_size_bytes {}\n",
+                                size_bytes
+                            ));
                         }
                     }
                 }
@@ -442,7 +2373,10 @@ fn build_metrics(
             if let Some(sw) = &s.swap {
                 out.push_str("# HELP jetsonscope_swap_bytes_total SWAP total bytes\n");
                 out.push_str("# TYPE jetsonscope_swap_bytes_total gauge\n");
-                out.push_str(&format!("jetsonscope_swap_bytes_total {}\n", sw.total_bytes));
+                out.push_str(&format!(
+                    "jetsonscope_swap_bytes_total {}\n",
+                    sw.total_bytes
+                ));
                 out.push_str("# HELP jetsonscope_swap_bytes_used SWAP used bytes\n");
                 out.push_str("# TYPE jetsonscope_swap_bytes_used gauge\n");
                 out.push_str(&format!("jetsonscope_swap_bytes_used {}\n", sw.used_bytes));
@@ -509,6 +2443,23 @@ fn build_metrics(
                     ));
                 }
             }
+            if let Some(bw) = s.emc_bandwidth {
+                out.push_str("# HELP jetsonscope_emc_bandwidth_gbps Memory bandwidth estimated from EMC load, clock and bus width (GB/s)\n");
+                out.push_str("# TYPE jetsonscope_emc_bandwidth_gbps gauge\n");
+                out.push_str(&format!("jetsonscope_emc_bandwidth_gbps {:.3}\n", bw.gbps));
+                out.push_str("# HELP jetsonscope_emc_bandwidth_peak_gbps Memory bandwidth at the current EMC clock when fully busy (GB/s)\n");
+                out.push_str("# TYPE jetsonscope_emc_bandwidth_peak_gbps gauge\n");
+                out.push_str(&format!(
+                    "jetsonscope_emc_bandwidth_peak_gbps {:.3}\n",
+                    bw.peak_gbps
+                ));
+            }
+            out.push_str("# HELP jetsonscope_throttling Clocks held back by heat or a lowered ceiling (1=yes)\n");
+            out.push_str("# TYPE jetsonscope_throttling gauge\n");
+            out.push_str(&format!(
+                "jetsonscope_throttling {}\n",
+                s.throttling.is_some() as u8
+            ));
 
             // Power rails
             if !s.power.is_empty() {
@@ -528,16 +2479,176 @@ fn build_metrics(
                 }
             }
 
+            // Power supplies (batteries, USB-C PD inputs)
+            if !s.supplies.is_empty() {
+                let threshold = power_supply::low_battery_threshold();
+                out.push_str("# HELP jetsonscope_supply_online Power supply online (1/0)\n");
+                out.push_str("# TYPE jetsonscope_supply_online gauge\n");
+                out.push_str("# HELP jetsonscope_supply_voltage_mv Power supply voltage mV\n");
+                out.push_str("# TYPE jetsonscope_supply_voltage_mv gauge\n");
+                out.push_str(
+                    "# HELP jetsonscope_battery_capacity_percent Battery state of charge\n",
+                );
+                out.push_str("# TYPE jetsonscope_battery_capacity_percent gauge\n");
+                out.push_str("# HELP jetsonscope_battery_discharge_mw Battery discharge rate mW\n");
+                out.push_str("# TYPE jetsonscope_battery_discharge_mw gauge\n");
+                out.push_str("# HELP jetsonscope_battery_low Battery below low threshold (1/0)\n");
+                out.push_str("# TYPE jetsonscope_battery_low gauge\n");
+                for supply in s.supplies.iter() {
+                    let labels = format!("supply=\"{}\",type=\"{}\"", supply.name, supply.kind);
+                    if let Some(online) = supply.online {
+                        out.push_str(&format!(
+                            "jetsonscope_supply_online{{{}}} {}\n",
+                            labels, online as u8
+                        ));
+                    }
+                    if let Some(mv) = supply.voltage_mv {
+                        out.push_str(&format!(
+                            "jetsonscope_supply_voltage_mv{{{}}} {}\n",
+                            labels, mv
+                        ));
+                    }
+                    if !supply.is_battery() {
+                        continue;
+                    }
+                    if let Some(pct) = supply.capacity_percent {
+                        out.push_str(&format!(
+                            "jetsonscope_battery_capacity_percent{{{}}} {}\n",
+                            labels, pct
+                        ));
+                    }
+                    out.push_str(&format!(
+                        "jetsonscope_battery_discharge_mw{{{}}} {}\n",
+                        labels,
+                        supply.discharge_mw().unwrap_or(0)
+                    ));
+                    out.push_str(&format!(
+                        "jetsonscope_battery_low{{{}}} {}\n",
+                        labels,
+                        supply.is_low(threshold) as u8
+                    ));
+                }
+            }
+
+            // Network interfaces (/proc/net/dev)
+            if !s.network.is_empty() {
+                out.push_str(
+                    "# HELP jetsonscope_network_receive_bytes_total Bytes received per interface\n",
+                );
+                out.push_str("# TYPE jetsonscope_network_receive_bytes_total counter\n");
+                out.push_str(
+                    "# HELP jetsonscope_network_transmit_bytes_total Bytes sent per interface\n",
+                );
+                out.push_str("# TYPE jetsonscope_network_transmit_bytes_total counter\n");
+                out.push_str("# HELP jetsonscope_network_receive_bytes_per_second Receive rate per interface\n");
+                out.push_str("# TYPE jetsonscope_network_receive_bytes_per_second gauge\n");
+                out.push_str("# HELP jetsonscope_network_transmit_bytes_per_second Transmit rate per interface\n");
+                out.push_str("# TYPE jetsonscope_network_transmit_bytes_per_second gauge\n");
+                for iface in s.network.iter() {
+                    let labels = format!("interface=\"{}\"", iface.name);
+                    out.push_str(&format!(
+                        "jetsonscope_network_receive_bytes_total{{{}}} {}\n",
+                        labels, iface.rx_bytes
+                    ));
+                    out.push_str(&format!(
+                        "jetsonscope_network_transmit_bytes_total{{{}}} {}\n",
+                        labels, iface.tx_bytes
+                    ));
+                    out.push_str(&format!(
+                        "jetsonscope_network_receive_bytes_per_second{{{}}} {:.0}\n",
+                        labels, iface.rx_bytes_per_sec
+                    ));
+                    out.push_str(&format!(
+                        "jetsonscope_network_transmit_bytes_per_second{{{}}} {:.0}\n",
+                        labels, iface.tx_bytes_per_sec
+                    ));
+                }
+            }
+
+            // Disks (mount usage, block-device throughput)
+            if let Some(disk) = &s.disk {
+                if !disk.mounts.is_empty() {
+                    out.push_str(
+                        "# HELP jetsonscope_disk_total_bytes Filesystem size per mount point\n",
+                    );
+                    out.push_str("# TYPE jetsonscope_disk_total_bytes gauge\n");
+                    out.push_str("# HELP jetsonscope_disk_available_bytes Filesystem space left per mount point\n");
+                    out.push_str("# TYPE jetsonscope_disk_available_bytes gauge\n");
+                    out.push_str(
+                        "# HELP jetsonscope_disk_used_percent Filesystem usage per mount point\n",
+                    );
+                    out.push_str("# TYPE jetsonscope_disk_used_percent gauge\n");
+                    for mount in disk.mounts.iter() {
+                        let labels = format!(
+                            "mount=\"{}\",device=\"{}\",fstype=\"{}\"",
+                            mount.mount_point, mount.device, mount.file_system
+                        );
+                        out.push_str(&format!(
+                            "jetsonscope_disk_total_bytes{{{}}} {}\n",
+                            labels, mount.total_bytes
+                        ));
+                        out.push_str(&format!(
+                            "jetsonscope_disk_available_bytes{{{}}} {}\n",
+                            labels, mount.available_bytes
+                        ));
+                        out.push_str(&format!(
+                            "jetsonscope_disk_used_percent{{{}}} {:.1}\n",
+                            labels,
+                            mount.used_percent()
+                        ));
+                    }
+                }
+                if !disk.devices.is_empty() {
+                    out.push_str(
+                        "# HELP jetsonscope_disk_read_bytes_total Bytes read per block device\n",
+                    );
+                    out.push_str("# TYPE jetsonscope_disk_read_bytes_total counter\n");
+                    out.push_str("# HELP jetsonscope_disk_written_bytes_total Bytes written per block device\n");
+                    out.push_str("# TYPE jetsonscope_disk_written_bytes_total counter\n");
+                    out.push_str("# HELP jetsonscope_disk_read_bytes_per_second Read rate per block device\n");
+                    out.push_str("# TYPE jetsonscope_disk_read_bytes_per_second gauge\n");
+                    out.push_str("# HELP jetsonscope_disk_write_bytes_per_second Write rate per block device\n");
+                    out.push_str("# TYPE jetsonscope_disk_write_bytes_per_second gauge\n");
+                    for dev in disk.devices.iter() {
+                        let labels = format!("device=\"{}\"", dev.name);
+                        out.push_str(&format!(
+                            "jetsonscope_disk_read_bytes_total{{{}}} {}\n",
+                            labels, dev.read_bytes
+                        ));
+                        out.push_str(&format!(
+                            "jetsonscope_disk_written_bytes_total{{{}}} {}\n",
+                            labels, dev.write_bytes
+                        ));
+                        out.push_str(&format!(
+                            "jetsonscope_disk_read_bytes_per_second{{{}}} {:.0}\n",
+                            labels, dev.read_bytes_per_sec
+                        ));
+                        out.push_str(&format!(
+                            "jetsonscope_disk_write_bytes_per_second{{{}}} {:.0}\n",
+                            labels, dev.write_bytes_per_sec
+                        ));
+                    }
+                }
+            }
+
             // IRAM
             if let Some(iram) = &s.iram {
                 out.push_str("# HELP jetsonscope_iram_bytes_total IRAM total bytes\n");
                 out.push_str("# TYPE jetsonscope_iram_bytes_total gauge\n");
-                out.push_str(&format!("jetsonscope_iram_bytes_total {}\n", iram.total_bytes));
+                out.push_str(&format!(
+                    "jetsonscope_iram_bytes_total {}\n",
+                    iram.total_bytes
+                ));
                 out.push_str("# HELP jetsonscope_iram_bytes_used IRAM used bytes\n");
                 out.push_str("# TYPE jetsonscope_iram_bytes_used gauge\n");
-                out.push_str(&format!("jetsonscope_iram_bytes_used {}\n", iram.used_bytes));
+                out.push_str(&format!(
+                    "jetsonscope_iram_bytes_used {}\n",
+                    iram.used_bytes
+                ));
                 if let Some(lfb) = iram.lfb_bytes {
-                    out.push_str("# HELP jetsonscope_iram_lfb_bytes IRAM largest free block bytes\n");
+                    out.push_str(
+                        "# HELP jetsonscope_iram_lfb_bytes IRAM largest free block bytes\n",
+                    );
                     out.push_str("# TYPE jetsonscope_iram_lfb_bytes gauge\n");
                     out.push_str(&format!("jetsonscope_iram_lfb_bytes {}\n", lfb));
                 }
@@ -547,10 +2658,61 @@ fn build_metrics(
             if let Some(mts) = &s.mts {
                 out.push_str("# HELP jetsonscope_mts_usage_fg_percent MTS FG usage percent\n");
                 out.push_str("# TYPE jetsonscope_mts_usage_fg_percent gauge\n");
-                out.push_str(&format!("jetsonscope_mts_usage_fg_percent {}\n", mts.fg_percent));
+                out.push_str(&format!(
+                    "jetsonscope_mts_usage_fg_percent {}\n",
+                    mts.fg_percent
+                ));
                 out.push_str("# HELP jetsonscope_mts_usage_bg_percent MTS BG usage percent\n");
                 out.push_str("# TYPE jetsonscope_mts_usage_bg_percent gauge\n");
-                out.push_str(&format!("jetsonscope_mts_usage_bg_percent {}\n", mts.bg_percent));
+                out.push_str(&format!(
+                    "jetsonscope_mts_usage_bg_percent {}\n",
+                    mts.bg_percent
+                ));
+            }
+        }
+    }
+
+    // Energy (integrated rail power, persisted across restarts when configured)
+    if let Ok(meter) = energy.lock() {
+        if !meter.rails_wh.is_empty() {
+            out.push_str(
+                "# HELP jetsonscope_energy_wh_total Cumulative rail energy in watt-hours\n",
+            );
+            out.push_str("# TYPE jetsonscope_energy_wh_total counter\n");
+            for (rail, wh) in meter.rails_wh.iter() {
+                out.push_str(&format!(
+                    "jetsonscope_energy_wh_total{{rail=\"{}\"}} {:.6}\n",
+                    rail, wh
+                ));
+            }
+            out.push_str("# HELP jetsonscope_energy_mwh_total Rail energy since the daemon started in milliwatt-hours\n");
+            out.push_str("# TYPE jetsonscope_energy_mwh_total counter\n");
+            for (rail, wh) in meter.since_start_wh() {
+                out.push_str(&format!(
+                    "jetsonscope_energy_mwh_total{{rail=\"{}\"}} {:.3}\n",
+                    rail,
+                    wh * 1000.0
+                ));
+            }
+            if let Some(tariff) = EnergyTariff::from_env() {
+                let input_wh = meter.input_wh();
+                if let Some(cost) = tariff.cost(input_wh) {
+                    out.push_str("# HELP jetsonscope_energy_cost_total Estimated cost of board input energy\n");
+                    out.push_str("# TYPE jetsonscope_energy_cost_total counter\n");
+                    out.push_str(&format!(
+                        "jetsonscope_energy_cost_total{{currency=\"{}\"}} {:.6}\n",
+                        sanitize_label(&tariff.currency),
+                        cost
+                    ));
+                }
+                if let Some(grams) = tariff.co2_grams(input_wh) {
+                    out.push_str("# HELP jetsonscope_energy_co2_grams_total Estimated CO2 emissions of board input energy\n");
+                    out.push_str("# TYPE jetsonscope_energy_co2_grams_total counter\n");
+                    out.push_str(&format!(
+                        "jetsonscope_energy_co2_grams_total {:.3}\n",
+                        grams
+                    ));
+                }
             }
         }
     }
@@ -635,13 +2797,96 @@ fn build_metrics(
             ));
         }
         if let Some(auto) = status.gpu_railgate {
-            out.push_str("# HELP jetsonscope_control_gpu_railgate GPU rail-gating state (auto=1/on=0)\n");
+            out.push_str(
+                "# HELP jetsonscope_control_gpu_railgate GPU rail-gating state (auto=1/on=0)\n",
+            );
             out.push_str("# TYPE jetsonscope_control_gpu_railgate gauge\n");
             out.push_str(&format!(
                 "jetsonscope_control_gpu_railgate {}\n",
                 if auto { 1 } else { 0 }
             ));
         }
+        if let Some(pm) = ResidencyReport::read().gpu_runtime {
+            out.push_str("# HELP jetsonscope_gpu_runtime_suspended_seconds_total Time the GPU spent rail-gated/runtime-suspended since boot\n");
+            out.push_str("# TYPE jetsonscope_gpu_runtime_suspended_seconds_total counter\n");
+            out.push_str(&format!(
+                "jetsonscope_gpu_runtime_suspended_seconds_total {:.3}\n",
+                pm.suspended_ms as f64 / 1000.0
+            ));
+            out.push_str("# HELP jetsonscope_gpu_runtime_active_seconds_total Time the GPU spent powered since boot\n");
+            out.push_str("# TYPE jetsonscope_gpu_runtime_active_seconds_total counter\n");
+            out.push_str(&format!(
+                "jetsonscope_gpu_runtime_active_seconds_total {:.3}\n",
+                pm.active_ms as f64 / 1000.0
+            ));
+            out.push_str("# HELP jetsonscope_gpu_runtime_suspended_percent Share of time the GPU was rail-gated since boot\n");
+            out.push_str("# TYPE jetsonscope_gpu_runtime_suspended_percent gauge\n");
+            out.push_str(&format!(
+                "jetsonscope_gpu_runtime_suspended_percent {:.2}\n",
+                pm.suspended_percent
+            ));
+        }
+        if let Some(cap) = status.clock_cap.power_cap_watts {
+            out.push_str("# HELP jetsonscope_control_power_cap_watts Input power cap in watts\n");
+            out.push_str("# TYPE jetsonscope_control_power_cap_watts gauge\n");
+            out.push_str(&format!("jetsonscope_control_power_cap_watts {}\n", cap));
+        }
+        if let Some(budget) = status.clock_cap.power_budget_mw {
+            out.push_str("# HELP jetsonscope_control_power_budget_watts Enforced board power budget in watts\n");
+            out.push_str("# TYPE jetsonscope_control_power_budget_watts gauge\n");
+            out.push_str(&format!(
+                "jetsonscope_control_power_budget_watts {}\n",
+                budget as f32 / 1000.0
+            ));
+        }
+        if let Some(avg) = status.clock_cap.power_avg_mw {
+            out.push_str("# HELP jetsonscope_control_power_avg_watts Rolling average input power the cap compares against\n");
+            out.push_str("# TYPE jetsonscope_control_power_avg_watts gauge\n");
+            out.push_str(&format!(
+                "jetsonscope_control_power_avg_watts {}\n",
+                avg as f32 / 1000.0
+            ));
+        }
+        if let Some(target) = status.clock_cap.thermal_target_c {
+            out.push_str(
+                "# HELP jetsonscope_control_thermal_target_celsius Thermal governor target\n",
+            );
+            out.push_str("# TYPE jetsonscope_control_thermal_target_celsius gauge\n");
+            out.push_str(&format!(
+                "jetsonscope_control_thermal_target_celsius {}\n",
+                target
+            ));
+        }
+        if status.guard.enabled {
+            out.push_str("# HELP jetsonscope_control_thermal_guard_tripped Thermal guard actions in effect (1/0)\n");
+            out.push_str("# TYPE jetsonscope_control_thermal_guard_tripped gauge\n");
+            out.push_str(&format!(
+                "jetsonscope_control_thermal_guard_tripped {}\n",
+                if status.guard.tripped { 1 } else { 0 }
+            ));
+            out.push_str("# HELP jetsonscope_control_thermal_guard_trips_total Thermal guard trips since start\n");
+            out.push_str("# TYPE jetsonscope_control_thermal_guard_trips_total counter\n");
+            out.push_str(&format!(
+                "jetsonscope_control_thermal_guard_trips_total {}\n",
+                status.guard.trips
+            ));
+        }
+        if status.idle.enabled {
+            out.push_str("# HELP jetsonscope_control_idle Idle power-save profile active (1/0)\n");
+            out.push_str("# TYPE jetsonscope_control_idle gauge\n");
+            out.push_str(&format!(
+                "jetsonscope_control_idle {}\n",
+                if status.idle.idle { 1 } else { 0 }
+            ));
+        }
+        out.push_str(
+            "# HELP jetsonscope_control_clock_cap_level Applied clock cap level (0=uncapped)\n",
+        );
+        out.push_str("# TYPE jetsonscope_control_clock_cap_level gauge\n");
+        out.push_str(&format!(
+            "jetsonscope_control_clock_cap_level {}\n",
+            status.clock_cap.level
+        ));
         if let Some(err) = status.last_error {
             out.push_str("# HELP jetsonscope_control_last_error Last control error (info)\n");
             out.push_str("# TYPE jetsonscope_control_last_error gauge\n");
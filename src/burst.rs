@@ -0,0 +1,339 @@
+//! Sub-second burst captures for profiling short transients.
+//!
+//! A burst runs its own copy of the stats source at 100–250 ms for a bounded
+//! time and buffers every sample in the daemon; clients download the capture
+//! with `GetBurst` once it has finished. The regular 1 s stream is untouched.
+
+use crate::collector;
+use crate::emulator::{BoardModel, Emulator};
+use crate::history::now_unix_ms;
+use crate::parser::TegraStats;
+use crate::sysfs_stats::SysfsSampler;
+use crate::t;
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader};
+use std::process::Stdio;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const MIN_INTERVAL_MS: u64 = 100;
+pub const MAX_INTERVAL_MS: u64 = 250;
+pub const MAX_DURATION_SECS: u64 = 60;
+/// Finished captures kept for download, oldest dropped first.
+const KEEP_CAPTURES: usize = 4;
+/// Ceiling on samples per capture, in case the source outpaces the interval.
+const MAX_SAMPLES: usize = (MAX_DURATION_SECS * 1000 / MIN_INTERVAL_MS) as usize * 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BurstSample {
+    /// Sample time, ms since the Unix epoch
+    pub unix_ms: u64,
+    pub stats: TegraStats,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BurstCapture {
+    pub id: u64,
+    pub interval_ms: u64,
+    pub duration_secs: u64,
+    pub started_unix_ms: u64,
+    /// Stats command used, or "synthetic" when none could be started
+    pub source: String,
+    /// Still sampling; `samples` is partial until this is false
+    pub running: bool,
+    pub samples: Vec<BurstSample>,
+}
+
+/// Burst the TUI starts with `b`: `JETSONSCOPE_BURST=ms:secs`, default 100 ms for 10 s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BurstConfig {
+    pub interval_ms: u64,
+    pub duration_secs: u64,
+}
+
+impl Default for BurstConfig {
+    fn default() -> Self {
+        BurstConfig {
+            interval_ms: MIN_INTERVAL_MS,
+            duration_secs: 10,
+        }
+    }
+}
+
+impl BurstConfig {
+    pub fn from_env() -> Self {
+        match std::env::var("JETSONSCOPE_BURST") {
+            Ok(spec) => Self::parse(&spec).unwrap_or_else(|| {
+                eprintln!("Ignoring JETSONSCOPE_BURST '{}': expected ms:secs", spec);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// "200:30" -> 200 ms for 30 s, clamped to the daemon's limits.
+    fn parse(spec: &str) -> Option<Self> {
+        let (ms, secs) = spec.split_once(':')?;
+        Some(BurstConfig {
+            interval_ms: ms
+                .trim()
+                .parse::<u64>()
+                .ok()?
+                .clamp(MIN_INTERVAL_MS, MAX_INTERVAL_MS),
+            duration_secs: secs.trim().parse::<u64>().ok()?.clamp(1, MAX_DURATION_SECS),
+        })
+    }
+}
+
+/// Captures known to the daemon; at most one runs at a time.
+#[derive(Debug, Default)]
+pub struct BurstManager {
+    next_id: u64,
+    captures: VecDeque<BurstCapture>,
+}
+
+impl BurstManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a capture; fails on out-of-range parameters or while another one runs.
+    pub fn begin(
+        &mut self,
+        interval_ms: u64,
+        duration_secs: u64,
+        now_ms: u64,
+    ) -> anyhow::Result<u64> {
+        if !(MIN_INTERVAL_MS..=MAX_INTERVAL_MS).contains(&interval_ms) {
+            bail!(t!(
+                "burst.err.interval",
+                min = MIN_INTERVAL_MS,
+                max = MAX_INTERVAL_MS,
+                value = interval_ms
+            ));
+        }
+        if !(1..=MAX_DURATION_SECS).contains(&duration_secs) {
+            bail!(t!(
+                "burst.err.duration",
+                max = MAX_DURATION_SECS,
+                value = duration_secs
+            ));
+        }
+        if let Some(active) = self.captures.iter().find(|c| c.running) {
+            bail!(t!("burst.err.running", id = active.id));
+        }
+        self.next_id += 1;
+        self.captures.push_back(BurstCapture {
+            id: self.next_id,
+            interval_ms,
+            duration_secs,
+            started_unix_ms: now_ms,
+            running: true,
+            ..Default::default()
+        });
+        while self.captures.len() > KEEP_CAPTURES {
+            self.captures.pop_front();
+        }
+        Ok(self.next_id)
+    }
+
+    pub fn set_source(&mut self, id: u64, source: &str) {
+        if let Some(capture) = self.capture_mut(id) {
+            capture.source = source.to_string();
+        }
+    }
+
+    pub fn push(&mut self, id: u64, sample: BurstSample) {
+        if let Some(capture) = self.capture_mut(id) {
+            if capture.running && capture.samples.len() < MAX_SAMPLES {
+                capture.samples.push(sample);
+            }
+        }
+    }
+
+    pub fn finish(&mut self, id: u64) {
+        if let Some(capture) = self.capture_mut(id) {
+            capture.running = false;
+        }
+    }
+
+    pub fn get(&self, id: u64) -> Option<BurstCapture> {
+        self.captures.iter().find(|c| c.id == id).cloned()
+    }
+
+    fn capture_mut(&mut self, id: u64) -> Option<&mut BurstCapture> {
+        self.captures.iter_mut().find(|c| c.id == id)
+    }
+}
+
+/// Start a capture and the thread that fills it; returns the capture id.
+pub fn start(
+    manager: &Arc<Mutex<BurstManager>>,
+    interval_ms: u64,
+    duration_secs: u64,
+) -> anyhow::Result<u64> {
+    let id = manager
+        .lock()
+        .map_err(|_| anyhow::anyhow!("lock error"))?
+        .begin(interval_ms, duration_secs, now_unix_ms())?;
+    let manager = Arc::clone(manager);
+    thread::spawn(move || {
+        let deadline = Instant::now() + Duration::from_secs(duration_secs);
+        run_capture(&manager, id, interval_ms, deadline);
+        if let Ok(mut m) = manager.lock() {
+            m.finish(id);
+        }
+    });
+    Ok(id)
+}
+
+fn run_capture(manager: &Mutex<BurstManager>, id: u64, interval_ms: u64, deadline: Instant) {
+    let record = |stats: TegraStats| {
+        if let Ok(mut m) = manager.lock() {
+            m.push(
+                id,
+                BurstSample {
+                    unix_ms: now_unix_ms(),
+                    stats,
+                },
+            );
+        }
+    };
+    let set_source = |source: &str| {
+        if let Ok(mut m) = manager.lock() {
+            m.set_source(id, source);
+        }
+    };
+
+    if collector::use_sysfs() {
+        set_source("sysfs");
+        let mut sampler = SysfsSampler::new();
+        while Instant::now() < deadline {
+            if let Ok(stats) = TegraStats::parse(&sampler.sample_line()) {
+                record(stats);
+            }
+            thread::sleep(Duration::from_millis(interval_ms));
+        }
+        return;
+    }
+
+    let Some(mut cmd) = collector::burst_command(interval_ms) else {
+        let mut emulator = Emulator::new(BoardModel::from_env());
+        set_source(&format!("emulator ({})", emulator.model().name()));
+        while Instant::now() < deadline {
+            if let Ok(stats) = TegraStats::parse(&emulator.next_line()) {
+                record(stats);
+            }
+            thread::sleep(Duration::from_millis(interval_ms));
+        }
+        return;
+    };
+    cmd.stdout(Stdio::piped()).stderr(Stdio::null());
+    if let Ok(mut child) = cmd.spawn() {
+        set_source(&format!("{:?}", cmd));
+        let (tx, rx) = mpsc::channel();
+        if let Some(stdout) = child.stdout.take() {
+            thread::spawn(move || {
+                for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                    if tx.send(line).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        let mut ended = false;
+        while !ended {
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                break;
+            }
+            match rx.recv_timeout(left) {
+                Ok(line) => {
+                    if let Ok(stats) = TegraStats::parse(&line) {
+                        record(stats);
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => ended = true,
+            }
+        }
+        let _ = child.kill();
+        let _ = child.wait();
+        if !ended {
+            return;
+        }
+    }
+
+    // No usable command (or it exited early): synthetic samples for the rest of the window
+    set_source("synthetic");
+    while Instant::now() < deadline {
+        record(collector::synthesize_stats());
+        thread::sleep(Duration::from_millis(interval_ms));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_and_tracks_captures() {
+        let mut m = BurstManager::new();
+        assert!(m.begin(50, 10, 0).is_err());
+        assert!(m.begin(100, 0, 0).is_err());
+        assert!(m.begin(250, 61, 0).is_err());
+
+        let id = m.begin(100, 5, 1000).unwrap();
+        assert!(m.begin(100, 5, 1000).is_err(), "only one burst at a time");
+        let stats = TegraStats::parse("RAM 2000/8000MB CPU [10%@1190] GR3D_FREQ 40%@918").unwrap();
+        m.push(
+            id,
+            BurstSample {
+                unix_ms: 1100,
+                stats: stats.clone(),
+            },
+        );
+        m.finish(id);
+        // Late samples are dropped once finished
+        m.push(
+            id,
+            BurstSample {
+                unix_ms: 1200,
+                stats,
+            },
+        );
+        let capture = m.get(id).unwrap();
+        assert!(!capture.running);
+        assert_eq!(capture.samples.len(), 1);
+
+        for _ in 0..KEEP_CAPTURES {
+            let next = m.begin(200, 1, 2000).unwrap();
+            m.finish(next);
+        }
+        assert!(m.get(id).is_none(), "oldest capture evicted");
+        assert!(m.get(99).is_none());
+    }
+
+    #[test]
+    fn parses_tui_config() {
+        assert_eq!(
+            BurstConfig::parse("250:30"),
+            Some(BurstConfig {
+                interval_ms: 250,
+                duration_secs: 30
+            })
+        );
+        assert_eq!(
+            BurstConfig::parse("10:600"),
+            Some(BurstConfig {
+                interval_ms: MIN_INTERVAL_MS,
+                duration_secs: MAX_DURATION_SECS
+            })
+        );
+        assert!(BurstConfig::parse("fast").is_none());
+    }
+}
@@ -0,0 +1,575 @@
+//! Blocking client for jscoped: finds the socket, picks JSON or CBOR,
+//! frames requests and bounds every exchange with a timeout.
+//!
+//! ```no_run
+//! use jetsonscope::client::Client;
+//!
+//! let mut client = Client::connect()?;
+//! if let Some(stats) = client.get_stats()? {
+//!     println!("GPU {:?}%", stats.gpu_usage());
+//! }
+//! # anyhow::Ok(())
+//! ```
+
+use crate::control::Control;
+use crate::hardware::JetsonHardware;
+use crate::health::DaemonHealth;
+use crate::parser::TegraStats;
+use crate::profiles::ProfileList;
+use crate::protocol::{ControlInfo, Connection, Request, Response};
+use anyhow::{anyhow, Context, Result};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Socket the daemon listens on unless JETSONSCOPE_SOCKET_PATH says otherwise.
+pub const DEFAULT_SOCKET_PATH: &str = "/tmp/jetsonscope.sock";
+/// Socket of daemons from before the rename.
+pub const LEGACY_SOCKET_PATH: &str = "/tmp/tegrastats.sock";
+/// Longest a request may take before it fails.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// JETSONSCOPE_SOCKET_PATH (or TEGRA_SOCKET_PATH), else the default socket,
+/// falling back to the legacy one when only that exists.
+pub fn socket_path() -> PathBuf {
+    let candidate = env::var("JETSONSCOPE_SOCKET_PATH")
+        .or_else(|_| env::var("TEGRA_SOCKET_PATH"))
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(DEFAULT_SOCKET_PATH));
+    if candidate.exists() {
+        return candidate;
+    }
+    let legacy = PathBuf::from(LEGACY_SOCKET_PATH);
+    if legacy.exists() {
+        return legacy;
+    }
+    candidate
+}
+
+/// CBOR payloads when JETSONSCOPE_PROTO (or TEGRA_PROTO) is "cbor", else JSON.
+pub fn cbor_from_env() -> bool {
+    env::var("JETSONSCOPE_PROTO")
+        .or_else(|_| env::var("TEGRA_PROTO"))
+        .map(|v| v.eq_ignore_ascii_case("cbor"))
+        .unwrap_or(false)
+}
+
+/// TEGRA_AUTH_TOKEN or JETSONSCOPE_AUTH_TOKEN, when set and not empty.
+pub fn auth_token_from_env() -> Option<String> {
+    env::var("TEGRA_AUTH_TOKEN")
+        .ok()
+        .or_else(|| env::var("JETSONSCOPE_AUTH_TOKEN").ok())
+        .filter(|t| !t.is_empty())
+}
+
+/// Connection to the daemon with typed requests.
+///
+/// Daemon errors come back as `Err` wrapping the `ErrorInfo`, so callers can
+/// `downcast_ref::<ErrorInfo>()` to check its code.
+pub struct Client {
+    conn: Connection,
+    token: Option<String>,
+}
+
+impl Client {
+    /// Connect the way jscopectl does: to JETSONSCOPE_HOST over TCP when set
+    /// (authenticating with the env token, if any), else to `socket_path()`.
+    /// The encoding follows `cbor_from_env()`.
+    pub fn connect() -> Result<Self> {
+        let cbor = cbor_from_env();
+        let token = auth_token_from_env();
+        if let Some(host) = env::var("JETSONSCOPE_HOST")
+            .ok()
+            .map(|h| h.trim().to_string())
+            .filter(|h| !h.is_empty())
+        {
+            let mut client = Self::connect_tcp(&host, cbor)?.with_token(token);
+            client.authenticate()?;
+            return Ok(client);
+        }
+        Ok(Self::connect_unix(&socket_path(), cbor)?.with_token(token))
+    }
+
+    /// Connect to the daemon socket at `path`.
+    pub fn connect_unix(path: &Path, cbor: bool) -> Result<Self> {
+        let conn = Connection::connect(path, cbor)
+            .with_context(|| format!("cannot connect to {}", path.display()))?;
+        Self::with_connection(conn)
+    }
+
+    /// Connect to a daemon's TCP listener: "host:port", or "host" for the default port.
+    pub fn connect_tcp(addr: &str, cbor: bool) -> Result<Self> {
+        let conn = Connection::connect_tcp(addr, cbor).with_context(|| format!("cannot connect to {addr}"))?;
+        Self::with_connection(conn)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self> {
+        conn.set_timeout(Some(DEFAULT_TIMEOUT))?;
+        Ok(Client { conn, token: None })
+    }
+
+    /// Token sent with SetControl and by `authenticate`.
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+
+    /// Fail requests that take longer than `timeout`; `None` waits forever.
+    pub fn set_timeout(&self, timeout: Option<Duration>) -> Result<()> {
+        Ok(self.conn.set_timeout(timeout)?)
+    }
+
+    /// Authenticate the connection with the token, so TCP reads are allowed
+    /// by daemons with read-scoped tokens. Does nothing without a token.
+    pub fn authenticate(&mut self) -> Result<()> {
+        let Some(token) = self.token.clone() else {
+            return Ok(());
+        };
+        match self.call(&Request::Authenticate { token })? {
+            Response::Authenticated { .. } => Ok(()),
+            other => Err(unexpected("Authenticate", other)),
+        }
+    }
+
+    /// Latest sample; `None` while the daemon has none yet.
+    pub fn get_stats(&mut self) -> Result<Option<TegraStats>> {
+        match self.call(&Request::GetStats)? {
+            Response::Stats { data, .. } => Ok(data),
+            other => Err(unexpected("GetStats", other)),
+        }
+    }
+
+    pub fn get_meta(&mut self) -> Result<JetsonHardware> {
+        match self.call(&Request::GetMeta)? {
+            Response::Meta(hw) => Ok(hw),
+            other => Err(unexpected("GetMeta", other)),
+        }
+    }
+
+    pub fn list_controls(&mut self) -> Result<Vec<ControlInfo>> {
+        match self.call(&Request::ListControls)? {
+            Response::Controls(list) => Ok(list),
+            other => Err(unexpected("ListControls", other)),
+        }
+    }
+
+    /// Set `control` to `value`; returns the control's state afterwards.
+    pub fn set_control(&mut self, control: &str, value: &str) -> Result<ControlInfo> {
+        let req = Request::SetControl {
+            control: control.to_string(),
+            value: value.to_string(),
+            token: self.token.clone(),
+            dry_run: false,
+        };
+        match self.call(&req)? {
+            Response::ControlState(info) => Ok(info),
+            other => Err(unexpected("SetControl", other)),
+        }
+    }
+
+    /// `set_control` with a typed control.
+    pub fn apply(&mut self, control: &Control) -> Result<ControlInfo> {
+        self.set_control(control.name(), &control.value())
+    }
+
+    /// Validate `value` and list the commands and sysfs writes setting it
+    /// would run; nothing is changed.
+    pub fn plan_control(&mut self, control: &str, value: &str) -> Result<Vec<String>> {
+        let req = Request::SetControl {
+            control: control.to_string(),
+            value: value.to_string(),
+            token: self.token.clone(),
+            dry_run: true,
+        };
+        match self.call(&req)? {
+            Response::ControlPlan { actions, .. } => Ok(actions),
+            other => Err(unexpected("SetControl", other)),
+        }
+    }
+
+    pub fn list_profiles(&mut self) -> Result<ProfileList> {
+        match self.call(&Request::ListProfiles)? {
+            Response::Profiles(list) => Ok(list),
+            other => Err(unexpected("ListProfiles", other)),
+        }
+    }
+
+    /// Apply a daemon profile; returns the state of every control it set.
+    pub fn apply_profile(&mut self, name: &str) -> Result<Vec<ControlInfo>> {
+        match self.call(&self.profile_request(name, false))? {
+            Response::ProfileApplied { controls, .. } => Ok(controls),
+            other => Err(unexpected("ApplyProfile", other)),
+        }
+    }
+
+    /// Commands and sysfs writes `apply_profile` would run; nothing is changed.
+    pub fn plan_profile(&mut self, name: &str) -> Result<Vec<String>> {
+        match self.call(&self.profile_request(name, true))? {
+            Response::ProfilePlan { actions, .. } => Ok(actions),
+            other => Err(unexpected("ApplyProfile", other)),
+        }
+    }
+
+    /// Add or replace a daemon profile.
+    pub fn save_profile(&mut self, name: &str, controls: &[(String, String)]) -> Result<()> {
+        let req = Request::SaveProfile {
+            name: name.to_string(),
+            controls: controls.to_vec(),
+            token: self.token.clone(),
+        };
+        match self.call(&req)? {
+            Response::ProfileSaved { .. } => Ok(()),
+            other => Err(unexpected("SaveProfile", other)),
+        }
+    }
+
+    fn profile_request(&self, name: &str, dry_run: bool) -> Request {
+        Request::ApplyProfile {
+            name: name.to_string(),
+            token: self.token.clone(),
+            dry_run,
+        }
+    }
+
+    pub fn get_health(&mut self) -> Result<DaemonHealth> {
+        match self.call(&Request::GetHealth)? {
+            Response::Health(health) => Ok(health),
+            other => Err(unexpected("GetHealth", other)),
+        }
+    }
+
+    /// Any request; the reply is returned as is, `Response::Error` included.
+    pub fn request(&mut self, req: &Request) -> Result<Response> {
+        self.conn.request(req)
+    }
+
+    /// Queue a request without waiting; `recv` the replies in order.
+    pub fn send(&mut self, req: &Request) -> Result<()> {
+        self.conn.send(req)
+    }
+
+    pub fn recv(&mut self) -> Result<Response> {
+        self.conn.recv()
+    }
+
+    /// Protocol version of the last reply; `None` before the first one.
+    pub fn protocol_version(&self) -> Option<u32> {
+        self.conn.protocol_version()
+    }
+
+    /// `request`, with `Response::Error` turned into `Err`.
+    fn call(&mut self, req: &Request) -> Result<Response> {
+        match self.conn.request(req)? {
+            Response::Error(err) => Err(err.into()),
+            other => Ok(other),
+        }
+    }
+}
+
+fn unexpected(request: &str, reply: Response) -> anyhow::Error {
+    anyhow!("unexpected reply to {request}: {reply:?}")
+}
+
+/// A tokio Unix or TCP stream.
+#[cfg(feature = "async")]
+trait AsyncStream: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send {}
+
+#[cfg(feature = "async")]
+impl<T: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send> AsyncStream for T {}
+
+/// `Client` for tokio applications (`async` feature): the same requests and
+/// errors, without a thread per connection. Requests on one client run one
+/// at a time; open several clients for concurrent requests.
+#[cfg(feature = "async")]
+pub struct AsyncClient {
+    stream: Box<dyn AsyncStream>,
+    cbor: bool,
+    timeout: Option<Duration>,
+    token: Option<String>,
+    protocol_version: Option<u32>,
+}
+
+#[cfg(feature = "async")]
+impl AsyncClient {
+    /// Like `Client::connect`: JETSONSCOPE_HOST over TCP when set, else `socket_path()`.
+    pub async fn connect() -> Result<Self> {
+        let cbor = cbor_from_env();
+        let token = auth_token_from_env();
+        if let Some(host) = env::var("JETSONSCOPE_HOST")
+            .ok()
+            .map(|h| h.trim().to_string())
+            .filter(|h| !h.is_empty())
+        {
+            let mut client = Self::connect_tcp(&host, cbor).await?.with_token(token);
+            client.authenticate().await?;
+            return Ok(client);
+        }
+        Ok(Self::connect_unix(&socket_path(), cbor).await?.with_token(token))
+    }
+
+    pub async fn connect_unix(path: &Path, cbor: bool) -> Result<Self> {
+        let stream = tokio::net::UnixStream::connect(path)
+            .await
+            .with_context(|| format!("cannot connect to {}", path.display()))?;
+        Ok(Self::with_stream(Box::new(stream), cbor))
+    }
+
+    /// "host:port", or "host" for the default port.
+    pub async fn connect_tcp(addr: &str, cbor: bool) -> Result<Self> {
+        let stream = tokio::net::TcpStream::connect(crate::protocol::split_tcp_addr(addr))
+            .await
+            .with_context(|| format!("cannot connect to {addr}"))?;
+        stream.set_nodelay(true)?;
+        Ok(Self::with_stream(Box::new(stream), cbor))
+    }
+
+    fn with_stream(stream: Box<dyn AsyncStream>, cbor: bool) -> Self {
+        AsyncClient {
+            stream,
+            cbor,
+            timeout: Some(DEFAULT_TIMEOUT),
+            token: None,
+            protocol_version: None,
+        }
+    }
+
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+
+    /// Fail requests that take longer than `timeout`; `None` waits forever.
+    /// A request that timed out may leave its reply unread, so reconnect after one.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    pub async fn authenticate(&mut self) -> Result<()> {
+        let Some(token) = self.token.clone() else {
+            return Ok(());
+        };
+        match self.call(&Request::Authenticate { token }).await? {
+            Response::Authenticated { .. } => Ok(()),
+            other => Err(unexpected("Authenticate", other)),
+        }
+    }
+
+    pub async fn get_stats(&mut self) -> Result<Option<TegraStats>> {
+        match self.call(&Request::GetStats).await? {
+            Response::Stats { data, .. } => Ok(data),
+            other => Err(unexpected("GetStats", other)),
+        }
+    }
+
+    pub async fn get_meta(&mut self) -> Result<JetsonHardware> {
+        match self.call(&Request::GetMeta).await? {
+            Response::Meta(hw) => Ok(hw),
+            other => Err(unexpected("GetMeta", other)),
+        }
+    }
+
+    pub async fn list_controls(&mut self) -> Result<Vec<ControlInfo>> {
+        match self.call(&Request::ListControls).await? {
+            Response::Controls(list) => Ok(list),
+            other => Err(unexpected("ListControls", other)),
+        }
+    }
+
+    pub async fn set_control(&mut self, control: &str, value: &str) -> Result<ControlInfo> {
+        let req = Request::SetControl {
+            control: control.to_string(),
+            value: value.to_string(),
+            token: self.token.clone(),
+            dry_run: false,
+        };
+        match self.call(&req).await? {
+            Response::ControlState(info) => Ok(info),
+            other => Err(unexpected("SetControl", other)),
+        }
+    }
+
+    /// `set_control` with a typed control.
+    pub async fn apply(&mut self, control: &Control) -> Result<ControlInfo> {
+        self.set_control(control.name(), &control.value()).await
+    }
+
+    /// See `Client::plan_control`.
+    pub async fn plan_control(&mut self, control: &str, value: &str) -> Result<Vec<String>> {
+        let req = Request::SetControl {
+            control: control.to_string(),
+            value: value.to_string(),
+            token: self.token.clone(),
+            dry_run: true,
+        };
+        match self.call(&req).await? {
+            Response::ControlPlan { actions, .. } => Ok(actions),
+            other => Err(unexpected("SetControl", other)),
+        }
+    }
+
+    pub async fn list_profiles(&mut self) -> Result<ProfileList> {
+        match self.call(&Request::ListProfiles).await? {
+            Response::Profiles(list) => Ok(list),
+            other => Err(unexpected("ListProfiles", other)),
+        }
+    }
+
+    /// Apply a daemon profile; returns the state of every control it set.
+    pub async fn apply_profile(&mut self, name: &str) -> Result<Vec<ControlInfo>> {
+        match self.call(&self.profile_request(name, false)).await? {
+            Response::ProfileApplied { controls, .. } => Ok(controls),
+            other => Err(unexpected("ApplyProfile", other)),
+        }
+    }
+
+    /// Commands and sysfs writes `apply_profile` would run; nothing is changed.
+    pub async fn plan_profile(&mut self, name: &str) -> Result<Vec<String>> {
+        match self.call(&self.profile_request(name, true)).await? {
+            Response::ProfilePlan { actions, .. } => Ok(actions),
+            other => Err(unexpected("ApplyProfile", other)),
+        }
+    }
+
+    /// Add or replace a daemon profile.
+    pub async fn save_profile(&mut self, name: &str, controls: &[(String, String)]) -> Result<()> {
+        let req = Request::SaveProfile {
+            name: name.to_string(),
+            controls: controls.to_vec(),
+            token: self.token.clone(),
+        };
+        match self.call(&req).await? {
+            Response::ProfileSaved { .. } => Ok(()),
+            other => Err(unexpected("SaveProfile", other)),
+        }
+    }
+
+    fn profile_request(&self, name: &str, dry_run: bool) -> Request {
+        Request::ApplyProfile {
+            name: name.to_string(),
+            token: self.token.clone(),
+            dry_run,
+        }
+    }
+
+    pub async fn get_health(&mut self) -> Result<DaemonHealth> {
+        match self.call(&Request::GetHealth).await? {
+            Response::Health(health) => Ok(health),
+            other => Err(unexpected("GetHealth", other)),
+        }
+    }
+
+    /// Any request; the reply is returned as is, `Response::Error` included.
+    pub async fn request(&mut self, req: &Request) -> Result<Response> {
+        match self.timeout {
+            Some(limit) => tokio::time::timeout(limit, self.exchange(req))
+                .await
+                .map_err(|_| anyhow!("no reply to {} within {:?}", req.name(), limit))?,
+            None => self.exchange(req).await,
+        }
+    }
+
+    pub fn protocol_version(&self) -> Option<u32> {
+        self.protocol_version
+    }
+
+    async fn exchange(&mut self, req: &Request) -> Result<Response> {
+        let payload = if self.cbor {
+            serde_cbor::to_vec(req)?
+        } else {
+            serde_json::to_vec(req)?
+        };
+        crate::protocol::write_frame_async(&mut self.stream, &payload).await?;
+        let payload = crate::protocol::read_frame_async(&mut self.stream)
+            .await?
+            .ok_or_else(|| anyhow!("daemon closed the connection"))?;
+        let reply: crate::protocol::Reply = if self.cbor {
+            serde_cbor::from_slice(&payload)?
+        } else {
+            serde_json::from_slice(&payload)?
+        };
+        self.protocol_version = Some(reply.protocol_version);
+        Ok(reply.response)
+    }
+
+    async fn call(&mut self, req: &Request) -> Result<Response> {
+        match self.request(req).await? {
+            Response::Error(err) => Err(err.into()),
+            other => Ok(other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{read_frame, write_frame, ErrorInfo, Reply};
+    use std::os::unix::net::UnixListener;
+
+    #[test]
+    fn typed_requests_and_daemon_errors() {
+        let path = env::temp_dir().join(format!("jscope-client-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut s, _) = listener.accept().unwrap();
+            while let Some(payload) = read_frame(&mut s).unwrap() {
+                let reply = match serde_cbor::from_slice(&payload).unwrap() {
+                    Request::GetStats => Response::Stats {
+                        source: "test".to_string(),
+                        data: Some(TegraStats::default()),
+                    },
+                    Request::ListControls => Response::Controls(Vec::new()),
+                    Request::SetControl { token, .. } => Response::Error(ErrorInfo {
+                        code: "auth_failed".to_string(),
+                        message: format!("token {token:?}"),
+                    }),
+                    _ => Response::SessionStarted { id: 1 },
+                };
+                write_frame(&mut s, &serde_cbor::to_vec(&Reply::new(reply)).unwrap()).unwrap();
+            }
+        });
+
+        let mut client = Client::connect_unix(&path, true)
+            .unwrap()
+            .with_token(Some("secret".to_string()));
+        assert!(client.get_stats().unwrap().is_some());
+        assert!(client.list_controls().unwrap().is_empty());
+        let err = client.set_control("fan", "80").unwrap_err();
+        let info = err.downcast_ref::<ErrorInfo>().unwrap();
+        assert_eq!((info.code.as_str(), info.message.as_str()), ("auth_failed", "token Some(\"secret\")"));
+        assert!(client.get_health().unwrap_err().to_string().contains("unexpected reply to GetHealth"));
+        drop(client);
+        server.join().unwrap();
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn async_client_over_tcp() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let server = std::thread::spawn(move || {
+            let (mut s, _) = listener.accept().unwrap();
+            let payload = read_frame(&mut s).unwrap().unwrap();
+            assert!(matches!(serde_json::from_slice(&payload).unwrap(), Request::GetMeta));
+            let reply = Reply::new(Response::Meta(JetsonHardware::default()));
+            write_frame(&mut s, &serde_json::to_vec(&reply).unwrap()).unwrap();
+            // Never answers the second request
+            while let Ok(Some(_)) = read_frame(&mut s) {}
+        });
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let mut client = AsyncClient::connect_tcp(&addr, false).await.unwrap();
+            assert!(client.get_meta().await.is_ok());
+            assert!(client.protocol_version().is_some());
+            client.set_timeout(Some(Duration::from_millis(100)));
+            let err = client.get_health().await.unwrap_err();
+            assert!(err.to_string().contains("no reply to GetHealth"));
+        });
+        drop(runtime);
+        server.join().unwrap();
+    }
+}
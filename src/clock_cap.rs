@@ -0,0 +1,207 @@
+use anyhow::{anyhow, Context, Result};
+use crate::t;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// State of the software clock caps, shared by the automatic controllers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClockCapStatus {
+    /// Active wattage cap (power_cap_watts control)
+    pub power_cap_watts: Option<f32>,
+    /// Rolling average of the input power the cap compares against
+    #[serde(default)]
+    pub power_avg_mw: Option<u32>,
+    /// Board power budget enforced by the daemon (JETSONSCOPE_POWER_BUDGET_MW)
+    #[serde(default)]
+    pub power_budget_mw: Option<u32>,
+    /// Active temperature target (thermal_target_c control)
+    pub thermal_target_c: Option<f32>,
+    /// Last temperature seen by the thermal governor
+    pub thermal_temp_c: Option<f32>,
+    /// Effective throttle level (0 = uncapped)
+    pub level: usize,
+    pub max_level: usize,
+    /// Human-readable applied caps, e.g. "CPU<=1190MHz GPU<=624MHz"
+    pub applied: Option<String>,
+}
+
+/// Available CPU/GPU frequency steps (ascending), used to derive max-frequency caps.
+///
+/// Level 0 leaves both at their highest step; each level lowers the cap by one step
+/// on every table until its lowest step is reached. CPU caps stay within the
+/// user's `cpu_min_freq`/`cpu_max_freq` bounds.
+#[derive(Debug, Clone, Default)]
+pub struct ClockTable {
+    pub cpu_khz: Vec<u64>,
+    pub gpu_hz: Vec<u64>,
+    /// User CPU floor (cpu_min_freq); a cap never writes below it
+    pub cpu_floor_khz: Option<u64>,
+    /// User CPU ceiling (cpu_max_freq); a cap never writes above it
+    pub cpu_ceiling_khz: Option<u64>,
+    cpu_dirs: Vec<PathBuf>,
+    gpu_dir: Option<PathBuf>,
+}
+
+impl ClockTable {
+    pub fn detect() -> Self {
+        let cpu_dirs: Vec<PathBuf> = crate::control::cpu_paths()
+            .into_iter()
+            .map(|p| p.join("cpufreq"))
+            .filter(|p| p.join("scaling_max_freq").exists())
+            .collect();
+        let cpu_khz = cpu_dirs
+            .first()
+            .map(|d| read_freqs(&d.join("scaling_available_frequencies")))
+            .unwrap_or_default();
+        let gpu_dir = crate::control::gpu_devfreq_path();
+        let gpu_hz = gpu_dir
+            .as_ref()
+            .map(|d| read_freqs(&d.join("available_frequencies")))
+            .unwrap_or_default();
+        ClockTable {
+            cpu_khz,
+            gpu_hz,
+            cpu_dirs,
+            gpu_dir,
+            ..Default::default()
+        }
+    }
+
+    /// Fixed Orin-like table with no sysfs backing (mock mode).
+    pub fn mock() -> Self {
+        ClockTable {
+            cpu_khz: vec![729_600, 1_190_400, 1_497_600, 1_728_000],
+            gpu_hz: vec![306_000_000, 624_750_000, 918_000_000, 1_300_500_000],
+            ..Default::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cpu_khz.is_empty() && self.gpu_hz.is_empty()
+    }
+
+    pub fn max_level(&self) -> usize {
+        self.cpu_khz.len().max(self.gpu_hz.len()).saturating_sub(1)
+    }
+
+    /// (cpu kHz, gpu Hz) caps for `level`.
+    pub fn caps(&self, level: usize) -> (Option<u64>, Option<u64>) {
+        let cpu = step_down(&self.cpu_khz, level).map(|khz| {
+            let khz = self.cpu_ceiling_khz.map_or(khz, |ceiling| khz.min(ceiling));
+            self.cpu_floor_khz.map_or(khz, |floor| khz.max(floor))
+        });
+        (cpu, step_down(&self.gpu_hz, level))
+    }
+
+    pub fn describe(&self, level: usize) -> String {
+        let (cpu, gpu) = self.caps(level);
+        let mut parts = Vec::new();
+        if let Some(khz) = cpu {
+            parts.push(format!("CPU<={}MHz", khz / 1000));
+        }
+        if let Some(hz) = gpu {
+            parts.push(format!("GPU<={}MHz", hz / 1_000_000));
+        }
+        parts.join(" ")
+    }
+
+    /// Write max-frequency caps for `level` to sysfs.
+    pub fn apply(&self, level: usize) -> Result<()> {
+        let (cpu, gpu) = self.caps(level);
+        if let Some(khz) = cpu {
+            for dir in &self.cpu_dirs {
+                let path = dir.join("scaling_max_freq");
+                std::fs::write(&path, khz.to_string())
+                    .with_context(|| t!("control.err.writing", path = format!("{:?}", path)))?;
+            }
+        }
+        if let (Some(hz), Some(dir)) = (gpu, &self.gpu_dir) {
+            let path = dir.join("max_freq");
+            std::fs::write(&path, hz.to_string())
+                .with_context(|| t!("control.err.writing", path = format!("{:?}", path)))?;
+        }
+        if self.cpu_dirs.is_empty() && self.gpu_dir.is_none() {
+            return Err(anyhow!(t!("control.err.no_clock_paths")));
+        }
+        Ok(())
+    }
+}
+
+/// Per-source throttle requests; the most restrictive one wins.
+#[derive(Debug, Clone, Default)]
+pub struct CapArbiter {
+    requests: BTreeMap<String, usize>,
+}
+
+impl CapArbiter {
+    /// Record `level` for `source` (0 withdraws it) and return the effective level.
+    pub fn request(&mut self, source: &str, level: usize) -> usize {
+        if level == 0 {
+            self.requests.remove(source);
+        } else {
+            self.requests.insert(source.to_string(), level);
+        }
+        self.effective()
+    }
+
+    /// Sources with a throttle request in force.
+    pub fn sources(&self) -> Vec<String> {
+        self.requests.keys().cloned().collect()
+    }
+
+    pub fn effective(&self) -> usize {
+        self.requests.values().copied().max().unwrap_or(0)
+    }
+}
+
+fn step_down(steps: &[u64], level: usize) -> Option<u64> {
+    let top = steps.len().checked_sub(1)?;
+    Some(steps[top.saturating_sub(level)])
+}
+
+fn read_freqs(path: &std::path::Path) -> Vec<u64> {
+    let mut freqs: Vec<u64> = std::fs::read_to_string(path)
+        .unwrap_or_default()
+        .split_whitespace()
+        .filter_map(|s| s.parse().ok())
+        .collect();
+    freqs.sort_unstable();
+    freqs.dedup();
+    freqs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_step_down_and_clamp() {
+        let table = ClockTable::mock();
+        assert_eq!(table.max_level(), 3);
+        assert_eq!(table.caps(0), (Some(1_728_000), Some(1_300_500_000)));
+        assert_eq!(table.caps(1), (Some(1_497_600), Some(918_000_000)));
+        assert_eq!(table.caps(9), (Some(729_600), Some(306_000_000)));
+        assert_eq!(table.describe(1), "CPU<=1497MHz GPU<=918MHz");
+    }
+
+    #[test]
+    fn cpu_caps_stay_within_user_bounds() {
+        let mut table = ClockTable::mock();
+        table.cpu_ceiling_khz = Some(1_497_600);
+        table.cpu_floor_khz = Some(1_190_400);
+        assert_eq!(table.caps(0).0, Some(1_497_600));
+        assert_eq!(table.caps(2).0, Some(1_190_400));
+        assert_eq!(table.caps(3), (Some(1_190_400), Some(306_000_000)));
+    }
+
+    #[test]
+    fn arbiter_takes_most_restrictive() {
+        let mut arb = CapArbiter::default();
+        assert_eq!(arb.request("power_cap", 2), 2);
+        assert_eq!(arb.request("thermal", 1), 2);
+        assert_eq!(arb.sources(), ["power_cap", "thermal"]);
+        assert_eq!(arb.request("power_cap", 0), 1);
+        assert_eq!(arb.request("thermal", 0), 0);
+    }
+}
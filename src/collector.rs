@@ -1,81 +1,447 @@
-use crate::parser::{CpuCore, EngineStat, MemoryStat, PowerRail, SizeUnit, SwapStat, TegraStats};
+use crate::disk::{BlockDeviceIo, DiskSampler, DiskStats, MountUsage};
+use crate::emulator::{BoardModel, Emulator};
+use crate::hardware::{self, MemoryConfig, CPU_SYSFS};
+use crate::network::{InterfaceStats, NetworkSampler};
+use crate::parser::{CpuCore, EngineStat, MemoryStat, ParseError, PowerRail, SizeUnit, SwapStat, TegraStats};
+use crate::power_supply;
+use crate::protocol::{Connection, ErrorInfo, Request, Response};
+use crate::scenario::{Scenario, ScenarioGenerator};
+use crate::sysfs_stats::SysfsSampler;
+use crate::throttle::ThrottleMonitor;
 use chrono::Local;
 use rand::Rng;
-use serde::{Deserialize, Serialize};
 use std::env;
 use std::fs;
-use std::io::Read;
 use std::io::{BufRead, BufReader};
-use std::os::unix::net::UnixStream;
-use std::path::PathBuf;
-use std::process::{Command, Stdio};
-use std::sync::mpsc::{self, Receiver, Sender};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::collections::VecDeque;
+use std::sync::mpsc::{RecvError, RecvTimeoutError, TryRecvError};
+use std::sync::{Arc, Condvar, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum CollectorMessage {
     Stats(TegraStats),
     SourceLabel(String),
     Error(String),
+    /// A stats command line that yielded no data (format drift, a warning on stdout)
+    #[allow(dead_code)] // daemon-side; the TUI skips the line
+    ParseFailed(String),
+    /// The first line of a stats command run has unknown tokens or lacks a section
+    #[allow(dead_code)] // daemon-side; the TUI skips it
+    FormatDrift(ParseError),
+    /// One socket round trip; `fresh` is false when the daemon had no new sample
+    Poll { rtt: Duration, fresh: bool },
+    /// Daemon answer to a request from `StatsCollector::requests`
+    Reply(Response),
+}
+
+/// Samples kept for a consumer that falls behind; older ones are dropped.
+pub const STATS_QUEUE_CAPACITY: usize = 4;
+
+struct Queue {
+    messages: VecDeque<CollectorMessage>,
+    /// `Stats` entries in `messages`
+    stats: usize,
+    senders: usize,
+    receiver_alive: bool,
+}
+
+struct Channel {
+    queue: Mutex<Queue>,
+    ready: Condvar,
+    capacity: usize,
+    dropped: AtomicU64,
+}
+
+/// Sending half of the collector channel.
+pub struct CollectorSender(Arc<Channel>);
+
+/// Receiving half of the collector channel.
+///
+/// At most `capacity` samples wait here: a stalled consumer loses the oldest
+/// ones (counted by `dropped`) and catches up on fresh data, instead of
+/// replaying a backlog. Labels, errors, polls and replies are never dropped.
+pub struct CollectorReceiver(Arc<Channel>);
+
+/// Bounded, drop-oldest channel between a collector thread and its consumer.
+pub fn channel(capacity: usize) -> (CollectorSender, CollectorReceiver) {
+    let shared = Arc::new(Channel {
+        queue: Mutex::new(Queue {
+            messages: VecDeque::new(),
+            stats: 0,
+            senders: 1,
+            receiver_alive: true,
+        }),
+        ready: Condvar::new(),
+        capacity: capacity.max(1),
+        dropped: AtomicU64::new(0),
+    });
+    (CollectorSender(shared.clone()), CollectorReceiver(shared))
+}
+
+impl CollectorSender {
+    /// Queue a message; false once the receiver is gone.
+    pub fn send(&self, msg: CollectorMessage) -> bool {
+        let Ok(mut queue) = self.0.queue.lock() else {
+            return false;
+        };
+        if !queue.receiver_alive {
+            return false;
+        }
+        if matches!(msg, CollectorMessage::Stats(_)) {
+            if queue.stats >= self.0.capacity {
+                if let Some(oldest) = queue
+                    .messages
+                    .iter()
+                    .position(|m| matches!(m, CollectorMessage::Stats(_)))
+                {
+                    queue.messages.remove(oldest);
+                    queue.stats -= 1;
+                    self.0.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            queue.stats += 1;
+        }
+        queue.messages.push_back(msg);
+        self.0.ready.notify_one();
+        true
+    }
+}
+
+impl Clone for CollectorSender {
+    fn clone(&self) -> Self {
+        if let Ok(mut queue) = self.0.queue.lock() {
+            queue.senders += 1;
+        }
+        Self(self.0.clone())
+    }
+}
+
+impl Drop for CollectorSender {
+    fn drop(&mut self) {
+        if let Ok(mut queue) = self.0.queue.lock() {
+            queue.senders -= 1;
+        }
+        self.0.ready.notify_all();
+    }
+}
+
+impl CollectorReceiver {
+    /// Next message without blocking.
+    pub fn try_recv(&self) -> Result<CollectorMessage, TryRecvError> {
+        let mut queue = self.0.queue.lock().map_err(|_| TryRecvError::Disconnected)?;
+        match Self::pop(&mut queue) {
+            Some(msg) => Ok(msg),
+            None if queue.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Next message, waiting at most `timeout`.
+    #[allow(dead_code)] // daemon-side
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<CollectorMessage, RecvTimeoutError> {
+        let deadline = Instant::now() + timeout;
+        let mut queue = self
+            .0
+            .queue
+            .lock()
+            .map_err(|_| RecvTimeoutError::Disconnected)?;
+        loop {
+            if let Some(msg) = Self::pop(&mut queue) {
+                return Ok(msg);
+            }
+            if queue.senders == 0 {
+                return Err(RecvTimeoutError::Disconnected);
+            }
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                return Err(RecvTimeoutError::Timeout);
+            }
+            queue = self
+                .0
+                .ready
+                .wait_timeout(queue, left)
+                .map_err(|_| RecvTimeoutError::Disconnected)?
+                .0;
+        }
+    }
+
+    /// Next message, blocking until one arrives or every sender is gone.
+    #[allow(dead_code)] // daemon-side
+    pub fn recv(&self) -> Result<CollectorMessage, RecvError> {
+        let mut queue = self.0.queue.lock().map_err(|_| RecvError)?;
+        loop {
+            if let Some(msg) = Self::pop(&mut queue) {
+                return Ok(msg);
+            }
+            if queue.senders == 0 {
+                return Err(RecvError);
+            }
+            queue = self.0.ready.wait(queue).map_err(|_| RecvError)?;
+        }
+    }
+
+    /// Blocking iterator that ends when the collector thread exits.
+    #[allow(dead_code)] // daemon-side
+    pub fn iter(&self) -> impl Iterator<Item = CollectorMessage> + '_ {
+        std::iter::from_fn(|| self.recv().ok())
+    }
+
+    /// Samples dropped because the consumer fell behind.
+    #[allow(dead_code)] // daemon-side
+    pub fn dropped(&self) -> u64 {
+        self.0.dropped.load(Ordering::Relaxed)
+    }
+
+    fn pop(queue: &mut Queue) -> Option<CollectorMessage> {
+        let msg = queue.messages.pop_front()?;
+        if matches!(msg, CollectorMessage::Stats(_)) {
+            queue.stats -= 1;
+        }
+        Some(msg)
+    }
+}
+
+impl Drop for CollectorReceiver {
+    fn drop(&mut self) {
+        if let Ok(mut queue) = self.0.queue.lock() {
+            queue.receiver_alive = false;
+            queue.messages.clear();
+        }
+    }
 }
 
 pub struct StatsCollector {
-    pub rx: Receiver<CollectorMessage>,
+    pub rx: CollectorReceiver,
+    /// Polling period in ms; may be changed while running. A stats command
+    /// with an `--interval` argument is restarted with the new value; other
+    /// commands keep their own rate.
+    pub interval_ms: Arc<AtomicU64>,
+    /// Requests sent to the daemon after the next successful poll, answered
+    /// with `CollectorMessage::Reply`. Ignored by non-socket sources.
+    pub requests: Arc<Mutex<Vec<Request>>>,
+    /// Ends the collector thread (daemon shutdown, TUI reconnect)
+    pub stop: CollectorStop,
+}
+
+/// Ends a collector thread and its stats command (e.g. tegrastats).
+#[derive(Clone, Default)]
+pub struct CollectorStop {
+    stopped: Arc<AtomicBool>,
+    child: Arc<Mutex<Option<Child>>>,
+}
+
+impl CollectorStop {
+    /// Kill the running command, if any, and reap it. Polling loops end at
+    /// their next iteration instead of falling back to synthetic data.
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        if let Some(mut child) = self.child.lock().ok().and_then(|mut c| c.take()) {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::SeqCst)
+    }
+}
+
+/// Wait before restarting a stats command that exited (OOM killer, crash),
+/// doubled after each run that produced no samples.
+const RESTART_BACKOFF_MIN: Duration = Duration::from_secs(1);
+const RESTART_BACKOFF_MAX: Duration = Duration::from_secs(30);
+/// Restarts in a row without a sample before serving synthetic data between retries
+const MAX_RESTART_FAILURES: u32 = 5;
+
+/// Default polling period for socket and synthetic sources.
+pub const DEFAULT_INTERVAL_MS: u64 = 1000;
+
+/// Sampling period from JETSONSCOPE_INTERVAL_MS (100-60000), else `DEFAULT_INTERVAL_MS`.
+///
+/// Also the `--interval` of the tegrastats the daemon launches.
+pub fn interval_from_env() -> u64 {
+    env::var("JETSONSCOPE_INTERVAL_MS")
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .filter(|ms| (100..=60_000).contains(ms))
+        .unwrap_or(DEFAULT_INTERVAL_MS)
 }
 
-#[derive(Debug, Clone, Copy)]
+/// A stats source outside JetsonScope (carrier-board MCU over serial, a
+/// remote board, a test fixture), fed through the same pipeline as tegrastats.
+///
+/// Register one with `start_collector(CollectorMode::Custom(CustomSource::new(src)))`.
+pub trait StatsSource: Send {
+    /// Shown as the collector's source label
+    fn label(&self) -> String {
+        "custom source".to_string()
+    }
+
+    /// Block until the next sample is ready. Errors are reported as
+    /// `CollectorMessage::Error` and the call is retried after the polling interval.
+    fn next(&mut self) -> anyhow::Result<TegraStats>;
+}
+
+/// Shareable handle to a `StatsSource`, so a `CollectorMode` can be cloned
+/// (the TUI restarts its collector on reconnect).
+#[derive(Clone)]
+pub struct CustomSource(Arc<Mutex<Box<dyn StatsSource>>>);
+
+impl CustomSource {
+    #[allow(dead_code)] // for downstream crates
+    pub fn new(source: impl StatsSource + 'static) -> Self {
+        Self(Arc::new(Mutex::new(Box::new(source))))
+    }
+
+    fn label(&self) -> String {
+        self.0
+            .lock()
+            .map(|s| s.label())
+            .unwrap_or_else(|_| "custom source".to_string())
+    }
+}
+
+impl std::fmt::Debug for CustomSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CustomSource").field(&self.label()).finish()
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum CollectorMode {
     #[allow(dead_code)]
-    AutoCommand,   // daemon: socket if present else command/emulator/synthetic
+    AutoCommand,   // daemon: command/emulator/synthetic (never its own socket)
     #[allow(dead_code)]
     PreferSocket,  // prefer socket, otherwise command/emulator/synthetic
     SocketOnly,    // socket else synthetic (no command)
+    SocketAt(PathBuf), // this socket only, never the default paths
+    Demo(Scenario), // synthetic only (jscope --demo)
+    #[allow(dead_code)]
+    Custom(CustomSource), // a registered StatsSource only
 }
 
+#[allow(dead_code)] // the TUI always queues a history request
 pub fn start_collector(mode: CollectorMode) -> StatsCollector {
-    let (tx, rx) = mpsc::channel();
+    start_collector_with_requests(mode, Vec::new())
+}
+
+/// Like `start_collector`, with daemon requests queued before the first poll.
+pub fn start_collector_with_requests(mode: CollectorMode, initial: Vec<Request>) -> StatsCollector {
+    let (tx, rx) = channel(STATS_QUEUE_CAPACITY);
+    let interval_ms = Arc::new(AtomicU64::new(interval_from_env()));
+    let interval = interval_ms.clone();
+    let requests = Arc::new(Mutex::new(initial));
+    let pending = requests.clone();
+    let stop = CollectorStop::default();
+    let stop_loop = stop.clone();
     thread::spawn(move || {
-        spawn_collection_loop(tx, mode);
+        spawn_collection_loop(tx, mode, interval, pending, stop_loop);
     });
-    StatsCollector { rx }
+    StatsCollector {
+        rx,
+        interval_ms,
+        requests,
+        stop,
+    }
 }
 
-fn spawn_collection_loop(tx: Sender<CollectorMessage>, mode: CollectorMode) {
+fn spawn_collection_loop(
+    tx: CollectorSender,
+    mode: CollectorMode,
+    interval: Arc<AtomicU64>,
+    requests: Arc<Mutex<Vec<Request>>>,
+    stop: CollectorStop,
+) {
     let choice = select_source(&mode);
     let _ = tx.send(CollectorMessage::SourceLabel(choice.label.clone()));
     match choice.kind {
-        SourceKind::Command(mut cmd) => {
-            cmd.stdout(Stdio::piped());
-            match cmd.spawn() {
-                Ok(mut child) => {
-                    if let Some(stdout) = child.stdout.take() {
-                        let reader = BufReader::new(stdout);
-                        for line in reader.lines().flatten() {
-                            if let Ok(stats) = TegraStats::parse(&line) {
-                                let _ = tx.send(CollectorMessage::Stats(stats));
-                            }
-                        }
-                    }
+        SourceKind::Command(cmd) => run_command(cmd, &choice.label, &tx, &interval, &stop),
+        SourceKind::Emulator(model) => {
+            let mut emulator = Emulator::new(model);
+            let clusters = emulator.cpu_clusters();
+            let memory = emulator.memory_config();
+            let mut network = NetworkSampler::new();
+            let mut disk = DiskSampler::new();
+            while !stop.is_stopped() {
+                if let Ok(mut stats) = TegraStats::parse(&emulator.next_line()) {
+                    hardware::assign_clusters(&mut stats.cpus, &clusters);
+                    stats.emc_bandwidth = memory.as_ref().and_then(|m| m.emc_bandwidth(&stats));
+                    stats.network = network.sample();
+                    stats.disk = disk.sample();
+                    let _ = tx.send(CollectorMessage::Stats(stats));
                 }
-                Err(err) => {
-                    eprintln!("Failed to start stats source ({:?}): {err}", cmd);
+                thread::sleep(Duration::from_millis(interval.load(Ordering::Relaxed)));
+            }
+        }
+        SourceKind::Sysfs => {
+            let mut sampler = SysfsSampler::new();
+            let clusters = hardware::read_cpu_clusters(Path::new(CPU_SYSFS));
+            let mut throttle = ThrottleMonitor::new();
+            let memory = MemoryConfig::detect();
+            let mut network = NetworkSampler::new();
+            let mut disk = DiskSampler::new();
+            while !stop.is_stopped() {
+                if let Ok(mut stats) = TegraStats::parse(&sampler.sample_line()) {
+                    hardware::assign_clusters(&mut stats.cpus, &clusters);
+                    stats.emc_bandwidth = memory.as_ref().and_then(|m| m.emc_bandwidth(&stats));
+                    stats.supplies = power_supply::read_all();
+                    stats.network = network.sample();
+                    stats.disk = disk.sample();
+                    stats.throttling = throttle.sample(&stats);
+                    let _ = tx.send(CollectorMessage::Stats(stats));
                 }
+                thread::sleep(Duration::from_millis(interval.load(Ordering::Relaxed)));
             }
-            run_synthetic(&tx);
         }
         SourceKind::Socket(path) => {
             let mut retry_count = 0;
-            let max_retries = if matches!(mode, CollectorMode::SocketOnly) { usize::MAX } else { 5 };
+            let max_retries = if matches!(mode, CollectorMode::SocketOnly | CollectorMode::SocketAt(_)) {
+                usize::MAX
+            } else {
+                5
+            };
             let mut backoff_ms = 1000;
 
-            loop {
-                match read_once_from_socket(&path) {
+            let mut last_raw: Option<String> = None;
+            // Kept open across polls; dropped on any error and reopened on the next one
+            let mut conn: Option<Connection> = None;
+
+            while !stop.is_stopped() {
+                let started = Instant::now();
+                match read_once_from_socket(&mut conn, &path) {
                     Ok(resp) => {
-                        if let Some(stats) = resp.stats {
+                        let rtt = started.elapsed();
+                        let fresh = resp
+                            .stats
+                            .as_ref()
+                            .is_some_and(|s| last_raw.as_deref() != Some(s.raw.as_str()));
+                        let _ = tx.send(CollectorMessage::Poll { rtt, fresh });
+                        // Repeats of the previous sample would skew history and energy
+                        if let Some(stats) = resp.stats.filter(|_| fresh) {
+                            last_raw = Some(stats.raw.clone());
                             let _ = tx.send(CollectorMessage::Stats(stats));
                         }
                         let _ = tx.send(CollectorMessage::SourceLabel(resp.source));
+                        let queued: Vec<Request> = requests
+                            .lock()
+                            .map(|mut q| q.drain(..).collect())
+                            .unwrap_or_default();
+                        for request in queued {
+                            let reply =
+                                socket_request(&mut conn, &path, &request).unwrap_or_else(|e| {
+                                    Response::Error(ErrorInfo {
+                                        code: "request_failed".to_string(),
+                                        message: e.to_string(),
+                                    })
+                                });
+                            let _ = tx.send(CollectorMessage::Reply(reply));
+                        }
                         retry_count = 0; // Reset on success
                         backoff_ms = 1000;
                     }
@@ -85,7 +451,7 @@ fn spawn_collection_loop(tx: Sender<CollectorMessage>, mode: CollectorMode) {
                         retry_count += 1;
 
                         if retry_count >= max_retries {
-                            run_synthetic(&tx);
+                            run_synthetic(&tx, &interval, &stop, Scenario::from_env());
                             return;
                         }
 
@@ -93,10 +459,27 @@ fn spawn_collection_loop(tx: Sender<CollectorMessage>, mode: CollectorMode) {
                         backoff_ms = (backoff_ms * 2).min(10000); // Exponential backoff, max 10s
                     }
                 }
-                thread::sleep(Duration::from_millis(1000));
+                thread::sleep(Duration::from_millis(interval.load(Ordering::Relaxed)));
+            }
+        }
+        SourceKind::Synthetic(scenario) => run_synthetic(&tx, &interval, &stop, scenario),
+        SourceKind::Custom(source) => {
+            while !stop.is_stopped() {
+                let sample = match source.0.lock() {
+                    Ok(mut src) => src.next(),
+                    Err(_) => return,
+                };
+                match sample {
+                    Ok(stats) => {
+                        let _ = tx.send(CollectorMessage::Stats(stats));
+                    }
+                    Err(err) => {
+                        let _ = tx.send(CollectorMessage::Error(format!("{}: {err}", choice.label)));
+                        thread::sleep(Duration::from_millis(interval.load(Ordering::Relaxed)));
+                    }
+                }
             }
         }
-        SourceKind::Synthetic => run_synthetic(&tx),
     }
 }
 
@@ -107,11 +490,42 @@ struct SourceChoice {
 
 enum SourceKind {
     Command(Command),
+    /// Direct sysfs/procfs reads, polled at `interval_ms`
+    Sysfs,
+    /// Built-in tegrastats emulator, polled at `interval_ms`
+    Emulator(BoardModel),
     Socket(PathBuf),
-    Synthetic,
+    Synthetic(Scenario),
+    Custom(CustomSource),
 }
 
 fn select_source(mode: &CollectorMode) -> SourceChoice {
+    // The daemon binds the socket itself; reading it back would loop on its own output
+    match mode {
+        CollectorMode::AutoCommand => return select_source_auto(false),
+        CollectorMode::SocketAt(path) => {
+            return SourceChoice {
+                kind: SourceKind::Socket(path.clone()),
+                label: format!("socket {}", path.display()),
+            }
+        }
+        CollectorMode::Demo(scenario) => {
+            return SourceChoice {
+                kind: SourceKind::Synthetic(*scenario),
+                label: match scenario {
+                    Scenario::Random => "synthetic (demo)".to_string(),
+                    other => format!("synthetic (demo: {})", other.name()),
+                },
+            }
+        }
+        CollectorMode::Custom(source) => {
+            return SourceChoice {
+                kind: SourceKind::Custom(source.clone()),
+                label: source.label(),
+            }
+        }
+        _ => {}
+    }
     if let Ok(sock_path) = env::var("JETSONSCOPE_SOCKET_PATH")
         .or_else(|_| env::var("TEGRA_SOCKET_PATH"))
     {
@@ -137,12 +551,12 @@ fn select_source(mode: &CollectorMode) -> SourceChoice {
     }
 
     match mode {
-        CollectorMode::SocketOnly => SourceChoice {
-            kind: SourceKind::Synthetic,
-            label: "synthetic (socket missing)".to_string(),
-        },
         CollectorMode::PreferSocket => select_source_auto(true),
         CollectorMode::AutoCommand => select_source_auto(false),
+        _ => SourceChoice {
+            kind: SourceKind::Synthetic(Scenario::from_env()),
+            label: "synthetic (socket missing)".to_string(),
+        },
     }
 }
 
@@ -166,31 +580,31 @@ fn select_source_auto(prefer_socket: bool) -> SourceChoice {
 
     if prefer_socket {
         return SourceChoice {
-            kind: SourceKind::Synthetic,
+            kind: SourceKind::Synthetic(Scenario::from_env()),
             label: "synthetic (socket preferred, none found)".to_string(),
         };
     }
 
     if should_force_emulator() {
+        return emulator_source();
+    }
+
+    if use_sysfs() {
         return SourceChoice {
-            kind: SourceKind::Command(emulator_command()),
-            label: "python emulator".to_string(),
+            kind: SourceKind::Sysfs,
+            label: "sysfs".to_string(),
         };
     }
 
     if is_jetson() {
         let mut cmd = Command::new("tegrastats");
-        cmd.arg("--interval").arg("1000");
+        cmd.arg("--interval").arg(interval_from_env().to_string());
         SourceChoice {
             kind: SourceKind::Command(cmd),
             label: "tegrastats real".to_string(),
         }
     } else {
-        let cmd = emulator_command();
-        SourceChoice {
-            kind: SourceKind::Command(cmd),
-            label: "python emulator".to_string(),
-        }
+        emulator_source()
     }
 }
 
@@ -205,12 +619,86 @@ fn should_force_emulator() -> bool {
     )
 }
 
-fn emulator_command() -> Command {
-    let mut cmd = Command::new("python3");
-    cmd.arg("../tegrastats_emulator.py")
-        .arg("--interval")
-        .arg("1000");
-    cmd
+/// Whether stats are read from sysfs instead of a stats command.
+///
+/// JETSONSCOPE_SOURCE=sysfs forces it and =tegrastats rules it out; by
+/// default a Jetson without a tegrastats binary uses it. A custom
+/// JETSONSCOPE_STATS_CMD or the emulator mode take precedence.
+pub fn use_sysfs() -> bool {
+    if env::var("JETSONSCOPE_STATS_CMD")
+        .or_else(|_| env::var("TEGRASTATS_CMD"))
+        .is_ok()
+        || should_force_emulator()
+    {
+        return false;
+    }
+    let source = env::var("JETSONSCOPE_SOURCE").unwrap_or_default().to_ascii_lowercase();
+    match source.as_str() {
+        "sysfs" => true,
+        "tegrastats" => false,
+        _ => {
+            fs::metadata("/etc/nv_tegra_release").is_ok()
+                && which::which("tegrastats").is_err()
+                && SysfsSampler::new().available()
+        }
+    }
+}
+
+/// Stats command as the daemon would pick it, sampling every `interval_ms`;
+/// `None` off a Jetson, where the built-in `Emulator` stands in.
+///
+/// A custom JETSONSCOPE_STATS_CMD only gets its `--interval` value replaced,
+/// since arbitrary scripts may not accept the flag.
+pub fn burst_command(interval_ms: u64) -> Option<Command> {
+    let interval = interval_ms.to_string();
+    if let Ok(raw_cmd) = env::var("JETSONSCOPE_STATS_CMD")
+        .or_else(|_| env::var("TEGRASTATS_CMD"))
+    {
+        let mut parts: Vec<&str> = raw_cmd.split_whitespace().collect();
+        if let Some(pos) = parts.iter().position(|a| *a == "--interval") {
+            if pos + 1 < parts.len() {
+                parts[pos + 1] = &interval;
+            }
+        }
+        if let Some((program, args)) = parts.split_first() {
+            let mut cmd = Command::new(program);
+            cmd.args(args);
+            return Some(cmd);
+        }
+    }
+    if should_force_emulator() || !is_jetson() {
+        return None;
+    }
+    let mut cmd = Command::new("tegrastats");
+    cmd.arg("--interval").arg(interval);
+    Some(cmd)
+}
+
+/// `cmd` with the value after its `--interval` argument replaced by
+/// `interval_ms`; `None` when it has no such argument.
+fn with_interval(cmd: &Command, interval_ms: u64) -> Option<Command> {
+    let args: Vec<&std::ffi::OsStr> = cmd.get_args().collect();
+    let pos = args
+        .iter()
+        .position(|a| *a == "--interval")
+        .filter(|pos| pos + 1 < args.len())?;
+    let mut updated = Command::new(cmd.get_program());
+    for (i, arg) in args.iter().enumerate() {
+        if i == pos + 1 {
+            updated.arg(interval_ms.to_string());
+        } else {
+            updated.arg(arg);
+        }
+    }
+    Some(updated)
+}
+
+fn emulator_source() -> SourceChoice {
+    let model = BoardModel::from_env();
+    SourceChoice {
+        kind: SourceKind::Emulator(model),
+        label: format!("emulator ({})", model.name()),
+    }
 }
 
 fn is_jetson() -> bool {
@@ -221,27 +709,207 @@ fn is_jetson() -> bool {
     which::which("tegrastats").is_ok()
 }
 
-fn run_synthetic(tx: &Sender<CollectorMessage>) {
-    let _ = tx.send(CollectorMessage::SourceLabel(
-        "synthetic generator".to_string(),
-    ));
+/// Run a stats command, restarting it with backoff when it exits. After
+/// `MAX_RESTART_FAILURES` runs in a row without a sample, synthetic data fills
+/// the waits between retries until the command produces samples again.
+fn run_command(
+    mut cmd: Command,
+    label: &str,
+    tx: &CollectorSender,
+    interval: &AtomicU64,
+    stop: &CollectorStop,
+) {
+    cmd.stdout(Stdio::piped());
+    let clusters = hardware::read_cpu_clusters(Path::new(CPU_SYSFS));
+    let mut throttle = ThrottleMonitor::new();
+    let memory = MemoryConfig::detect();
+    let mut network = NetworkSampler::new();
+    let mut disk = DiskSampler::new();
+    let mut backoff = RESTART_BACKOFF_MIN;
+    let mut failures = 0;
+    // Synthetic data while the command keeps failing
+    let mut fallback: Option<ScenarioGenerator> = None;
+    let follows_interval = with_interval(&cmd, DEFAULT_INTERVAL_MS).is_some();
+    loop {
+        let mut samples = 0u64;
+        let started_ms = interval.load(Ordering::Relaxed);
+        if let Some(updated) = with_interval(&cmd, started_ms) {
+            cmd = updated;
+            cmd.stdout(Stdio::piped());
+        }
+        let mut interval_changed = false;
+        match cmd.spawn() {
+            Ok(mut child) => {
+                let stdout = child.stdout.take();
+                if let Ok(mut slot) = stop.child.lock() {
+                    *slot = Some(child);
+                }
+                if let Some(stdout) = stdout {
+                    let reader = BufReader::new(stdout);
+                    let mut format_checked = false;
+                    for line in reader.lines().map_while(Result::ok) {
+                        // The refresh rate changed (TUI +/-): restart at the new one
+                        if follows_interval && interval.load(Ordering::Relaxed) != started_ms {
+                            interval_changed = true;
+                            break;
+                        }
+                        if line.trim().is_empty() {
+                            continue;
+                        }
+                        match TegraStats::parse(&line) {
+                            Ok(mut stats) if !stats.is_empty() => {
+                                if !format_checked {
+                                    format_checked = true;
+                                    if let Err(err) = TegraStats::parse_strict(&line) {
+                                        let _ = tx.send(CollectorMessage::FormatDrift(err));
+                                    }
+                                }
+                                if fallback.take().is_some() {
+                                    let _ = tx.send(CollectorMessage::SourceLabel(label.to_string()));
+                                }
+                                hardware::assign_clusters(&mut stats.cpus, &clusters);
+                                stats.emc_bandwidth = memory.as_ref().and_then(|m| m.emc_bandwidth(&stats));
+                                stats.supplies = power_supply::read_all();
+                                stats.network = network.sample();
+                                stats.disk = disk.sample();
+                                stats.throttling = throttle.sample(&stats);
+                                let _ = tx.send(CollectorMessage::Stats(stats));
+                                samples += 1;
+                            }
+                            _ => {
+                                let _ = tx.send(CollectorMessage::ParseFailed(line));
+                            }
+                        }
+                    }
+                }
+                if stop.is_stopped() {
+                    return;
+                }
+                if interval_changed {
+                    if let Some(mut child) = stop.child.lock().ok().and_then(|mut c| c.take()) {
+                        let _ = child.kill();
+                        let _ = child.wait();
+                    }
+                    continue;
+                }
+                // The command exited on its own; reap it
+                let status = stop
+                    .child
+                    .lock()
+                    .ok()
+                    .and_then(|mut c| c.take())
+                    .and_then(|mut child| child.wait().ok());
+                let _ = tx.send(CollectorMessage::Error(format!(
+                    "{} exited ({})",
+                    label,
+                    status.map(|s| s.to_string()).unwrap_or_else(|| "unknown status".into())
+                )));
+            }
+            Err(err) => {
+                eprintln!("Failed to start stats source ({:?}): {err}", cmd);
+                let _ = tx.send(CollectorMessage::Error(format!(
+                    "failed to start {}: {err}",
+                    label
+                )));
+            }
+        }
+        // A run that produced data was real: start the backoff over
+        if samples > 0 {
+            failures = 0;
+            backoff = RESTART_BACKOFF_MIN;
+        }
+        failures += 1;
+        if failures > MAX_RESTART_FAILURES {
+            let generator =
+                fallback.get_or_insert_with(|| ScenarioGenerator::new(Scenario::from_env()));
+            let _ = tx.send(CollectorMessage::SourceLabel(format!(
+                "synthetic generator ({} failing, retrying in {}s)",
+                label,
+                backoff.as_secs()
+            )));
+            if !synthetic_for(backoff, tx, interval, stop, generator) {
+                return;
+            }
+            backoff = (backoff * 2).min(RESTART_BACKOFF_MAX);
+            continue;
+        }
+        let _ = tx.send(CollectorMessage::SourceLabel(format!(
+            "{} (restarting in {}s)",
+            label,
+            backoff.as_secs()
+        )));
+        if !sleep_unless_stopped(backoff, stop) {
+            return;
+        }
+        backoff = (backoff * 2).min(RESTART_BACKOFF_MAX);
+        let _ = tx.send(CollectorMessage::SourceLabel(label.to_string()));
+    }
+}
+
+/// Sleep in short steps; false if the collector was stopped meanwhile.
+fn sleep_unless_stopped(duration: Duration, stop: &CollectorStop) -> bool {
+    let until = Instant::now() + duration;
+    while !stop.is_stopped() {
+        let left = until.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return true;
+        }
+        thread::sleep(left.min(Duration::from_millis(100)));
+    }
+    false
+}
+
+/// Send synthetic samples for `duration`; false if the collector was stopped meanwhile.
+fn synthetic_for(
+    duration: Duration,
+    tx: &CollectorSender,
+    interval: &AtomicU64,
+    stop: &CollectorStop,
+    generator: &mut ScenarioGenerator,
+) -> bool {
+    let until = Instant::now() + duration;
     loop {
-        let stats = synthesize_stats();
-        let _ = tx.send(CollectorMessage::Stats(stats));
-        thread::sleep(Duration::from_millis(1000));
+        let left = until.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return !stop.is_stopped();
+        }
+        let _ = tx.send(CollectorMessage::Stats(generator.next_stats()));
+        let step = Duration::from_millis(interval.load(Ordering::Relaxed));
+        if !sleep_unless_stopped(step.min(left), stop) {
+            return false;
+        }
+    }
+}
+
+fn run_synthetic(
+    tx: &CollectorSender,
+    interval: &AtomicU64,
+    stop: &CollectorStop,
+    scenario: Scenario,
+) {
+    let label = match scenario {
+        Scenario::Random => "synthetic generator".to_string(),
+        other => format!("synthetic generator ({})", other.name()),
+    };
+    let _ = tx.send(CollectorMessage::SourceLabel(label));
+    let mut generator = ScenarioGenerator::new(scenario);
+    while !stop.is_stopped() {
+        let _ = tx.send(CollectorMessage::Stats(generator.next_stats()));
+        thread::sleep(Duration::from_millis(interval.load(Ordering::Relaxed)));
     }
 }
 
-fn synthesize_stats() -> TegraStats {
+pub fn synthesize_stats() -> TegraStats {
     let mut rng = rand::thread_rng();
     let cpu_count = 8;
     let mut cpus = Vec::with_capacity(cpu_count);
-    for _ in 0..cpu_count {
+    for i in 0..cpu_count {
         let load = rng.gen_range(0..100) as u32;
         let freq = [729, 1036, 1190, 1497][rng.gen_range(0..4)];
         cpus.push(CpuCore {
             load_percent: Some(load),
             freq_mhz: Some(freq),
+            cluster: Some(i as u32 / 4),
         });
     }
 
@@ -272,6 +940,31 @@ fn synthesize_stats() -> TegraStats {
                 raw_value: Some(716),
             },
         );
+        // Orin-style DLAs: one busy, one powered off
+        map.insert(
+            "DLA0_CORE".into(),
+            EngineStat {
+                usage_percent: Some(rng.gen_range(0..80)),
+                freq_mhz: Some(1600),
+                raw_value: None,
+            },
+        );
+        map.insert(
+            "DLA0_FALCON".into(),
+            EngineStat {
+                usage_percent: None,
+                freq_mhz: Some(768),
+                raw_value: None,
+            },
+        );
+        map.insert(
+            "DLA1_CORE".into(),
+            EngineStat {
+                usage_percent: Some(0),
+                freq_mhz: None,
+                raw_value: None,
+            },
+        );
         map
     };
 
@@ -304,8 +997,10 @@ fn synthesize_stats() -> TegraStats {
     let swap_total = SizeUnit::MB.to_bytes(8_000);
     let swap_used = swap_total / 4 + rng.gen_range(0..(swap_total / 4));
 
+    let now = Local::now();
     TegraStats {
-        timestamp: Some(Local::now().format("%m-%d-%Y %H:%M:%S").to_string()),
+        timestamp: Some(now.format("%m-%d-%Y %H:%M:%S").to_string()),
+        timestamp_unix: Some(now.timestamp()),
         ram: Some(MemoryStat {
             used_bytes: ram_used,
             total_bytes: ram_total,
@@ -324,20 +1019,228 @@ fn synthesize_stats() -> TegraStats {
         engines,
         temps,
         power,
+        supplies: Vec::new(),
+        network: vec![InterfaceStats {
+            name: "eth0".into(),
+            rx_bytes: rng.gen_range(1_000_000_000..2_000_000_000),
+            tx_bytes: rng.gen_range(5_000_000_000..6_000_000_000),
+            rx_bytes_per_sec: rng.gen_range(10_000.0..200_000.0),
+            tx_bytes_per_sec: rng.gen_range(1_000_000.0..12_000_000.0),
+        }],
+        disk: Some(DiskStats {
+            mounts: vec![MountUsage {
+                mount_point: "/".into(),
+                device: "/dev/mmcblk0p1".into(),
+                file_system: "ext4".into(),
+                total_bytes: 59_000_000_000,
+                available_bytes: rng.gen_range(8_000_000_000..12_000_000_000),
+            }],
+            devices: vec![BlockDeviceIo {
+                name: "mmcblk0".into(),
+                read_bytes: rng.gen_range(4_000_000_000..5_000_000_000),
+                write_bytes: rng.gen_range(9_000_000_000..10_000_000_000),
+                read_bytes_per_sec: rng.gen_range(0.0..2_000_000.0),
+                write_bytes_per_sec: rng.gen_range(0.0..20_000_000.0),
+            }],
+        }),
+        throttling: None,
+        emc_bandwidth: None,
         raw: String::from("synthetic"),
     }
 }
 
-#[derive(Serialize, Deserialize)]
 struct SocketResponse {
     source: String,
     stats: Option<TegraStats>,
 }
 
-fn read_once_from_socket(path: &PathBuf) -> anyhow::Result<SocketResponse> {
-    let mut stream = UnixStream::connect(path)?;
-    let mut buf = String::new();
-    stream.read_to_string(&mut buf)?;
-    let resp: SocketResponse = serde_json::from_str(&buf)?;
-    Ok(resp)
+/// Give up on a poll after this long so a wedged daemon shows up as dropped samples.
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// One request/response exchange over the persistent connection, opened on demand.
+fn socket_request(
+    conn: &mut Option<Connection>,
+    path: &Path,
+    request: &Request,
+) -> anyhow::Result<Response> {
+    let mut c = match conn.take() {
+        Some(c) => c,
+        None => {
+            let c = Connection::connect(path, false)?;
+            c.set_timeout(Some(SOCKET_TIMEOUT))?;
+            c
+        }
+    };
+    // On error the connection is dropped: a late reply would answer the next request
+    let reply = c.request(request)?;
+    *conn = Some(c);
+    Ok(reply)
+}
+
+fn read_once_from_socket(
+    conn: &mut Option<Connection>,
+    path: &Path,
+) -> anyhow::Result<SocketResponse> {
+    match socket_request(conn, path, &Request::GetStats)? {
+        Response::Stats { source, data } => Ok(SocketResponse {
+            source,
+            stats: data,
+        }),
+        Response::Error(err) => anyhow::bail!("{}: {}", err.code, err.message),
+        _ => anyhow::bail!("unexpected response to GetStats"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Section;
+
+    /// Two samples, then a failing read.
+    struct Fixture(u32);
+
+    impl StatsSource for Fixture {
+        fn label(&self) -> String {
+            "fixture".to_string()
+        }
+
+        fn next(&mut self) -> anyhow::Result<TegraStats> {
+            self.0 += 1;
+            if self.0 > 2 {
+                anyhow::bail!("link down");
+            }
+            Ok(TegraStats {
+                raw: format!("sample {}", self.0),
+                ..Default::default()
+            })
+        }
+    }
+
+    fn stats(raw: &str) -> CollectorMessage {
+        CollectorMessage::Stats(TegraStats {
+            raw: raw.to_string(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn full_channel_drops_the_oldest_sample_only() {
+        let (tx, rx) = channel(2);
+        assert!(tx.send(stats("1")));
+        assert!(tx.send(CollectorMessage::SourceLabel("src".into())));
+        assert!(tx.send(stats("2")));
+        assert!(tx.send(stats("3")));
+        assert_eq!(rx.dropped(), 1);
+        assert!(matches!(rx.try_recv(), Ok(CollectorMessage::SourceLabel(_))));
+        assert!(matches!(rx.try_recv(), Ok(CollectorMessage::Stats(s)) if s.raw == "2"));
+        assert!(matches!(rx.try_recv(), Ok(CollectorMessage::Stats(s)) if s.raw == "3"));
+        assert!(matches!(rx.try_recv(), Err(TryRecvError::Empty)));
+        drop(tx);
+        assert!(matches!(rx.try_recv(), Err(TryRecvError::Disconnected)));
+        assert!(rx.iter().next().is_none());
+    }
+
+    #[test]
+    fn send_fails_once_the_receiver_is_gone() {
+        let (tx, rx) = channel(2);
+        drop(rx);
+        assert!(!tx.send(stats("1")));
+    }
+
+    #[test]
+    fn exited_command_is_restarted() {
+        let (tx, rx) = channel(4);
+        let stop = CollectorStop::default();
+        let stop_thread = stop.clone();
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "echo 'RAM 2257/30536MB (lfb 5392x4MB) GR3D_FREQ 75%'"]);
+        let handle = thread::spawn(move || {
+            run_command(cmd, "fake tegrastats", &tx, &AtomicU64::new(100), &stop_thread)
+        });
+        let next = || rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        // The fake line has no CPU, temperatures or rails: each run flags it once
+        assert!(matches!(next(), CollectorMessage::FormatDrift(e) if e.missing.contains(&Section::Cpu)));
+        assert!(matches!(next(), CollectorMessage::Stats(_)));
+        assert!(matches!(next(), CollectorMessage::Error(e) if e.starts_with("fake tegrastats exited")));
+        assert!(matches!(next(), CollectorMessage::SourceLabel(l) if l == "fake tegrastats (restarting in 1s)"));
+        assert!(matches!(next(), CollectorMessage::SourceLabel(l) if l == "fake tegrastats"));
+        assert!(matches!(next(), CollectorMessage::FormatDrift(_)));
+        assert!(matches!(next(), CollectorMessage::Stats(_)));
+        stop.stop();
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn interval_change_restarts_the_command() {
+        let (tx, rx) = channel(4);
+        let stop = CollectorStop::default();
+        let stop_thread = stop.clone();
+        let interval = Arc::new(AtomicU64::new(100));
+        let interval_thread = interval.clone();
+        let mut cmd = Command::new("sh");
+        cmd.args([
+            "-c",
+            "while true; do echo \"RAM 2257/30536MB GR3D_FREQ $1%\"; sleep 0.05; done",
+            "--interval",
+            "100",
+        ]);
+        let handle = thread::spawn(move || {
+            run_command(cmd, "fake tegrastats", &tx, &interval_thread, &stop_thread)
+        });
+        let gpu = || loop {
+            match rx.recv_timeout(Duration::from_secs(5)).unwrap() {
+                CollectorMessage::Stats(s) => return s.gpu_usage(),
+                CollectorMessage::Error(e) => panic!("unexpected error: {e}"),
+                _ => {}
+            }
+        };
+        assert_eq!(gpu(), Some(100));
+        interval.store(42, Ordering::Relaxed);
+        // Lines already buffered from the first run may still arrive
+        while gpu() != Some(42) {}
+        stop.stop();
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn interval_argument_follows_the_refresh_rate() {
+        let mut cmd = Command::new("tegrastats");
+        cmd.args(["--interval", "1000", "--verbose"]);
+        let updated = with_interval(&cmd, 250).unwrap();
+        let args: Vec<_> = updated.get_args().collect();
+        assert_eq!(args, ["--interval", "250", "--verbose"]);
+        assert!(with_interval(&Command::new("tegrastats"), 250).is_none());
+    }
+
+    #[test]
+    fn lines_without_stats_are_reported() {
+        let (tx, rx) = channel(4);
+        let stop = CollectorStop::default();
+        let stop_thread = stop.clone();
+        let mut cmd = Command::new("sh");
+        cmd.args([
+            "-c",
+            "echo 'NvRmPrivGetChipIdLimited: Could not read Tegra chip id/rev'; echo; echo 'RAM 2257/30536MB (lfb 5392x4MB)'",
+        ]);
+        let handle = thread::spawn(move || {
+            run_command(cmd, "fake tegrastats", &tx, &AtomicU64::new(100), &stop_thread)
+        });
+        let next = || rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(matches!(next(), CollectorMessage::ParseFailed(l) if l.starts_with("NvRmPriv")));
+        assert!(matches!(next(), CollectorMessage::FormatDrift(_)));
+        assert!(matches!(next(), CollectorMessage::Stats(_)));
+        stop.stop();
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn custom_source_feeds_the_collector() {
+        let collector = start_collector(CollectorMode::Custom(CustomSource::new(Fixture(0))));
+        let next = || collector.rx.recv_timeout(Duration::from_secs(2)).unwrap();
+        assert!(matches!(next(), CollectorMessage::SourceLabel(l) if l == "fixture"));
+        assert!(matches!(next(), CollectorMessage::Stats(s) if s.raw == "sample 1"));
+        assert!(matches!(next(), CollectorMessage::Stats(s) if s.raw == "sample 2"));
+        assert!(matches!(next(), CollectorMessage::Error(e) if e == "fixture: link down"));
+        collector.stop.stop();
+    }
 }
@@ -0,0 +1,400 @@
+//! Daemon settings from a TOML file (`jscoped --config /etc/jetsonscope/daemon.toml`).
+//!
+//! Every setting is one of the JETSONSCOPE_* environment variables: a key in
+//! a `[section]` names `JETSONSCOPE_<SECTION>_<KEY>`, a key before any section
+//! `JETSONSCOPE_<KEY>`. So `[mqtt] addr = "broker:1883"` is
+//! JETSONSCOPE_MQTT_ADDR, and `http_addr = "0.0.0.0:9090"` is
+//! JETSONSCOPE_HTTP_ADDR. A few variables also have a name in a section
+//! (`[collector] source`, `[metrics] addr`, ...; see `ALIASES`).
+//!
+//! Loading the file sets the variables the environment does not already
+//! have, which keeps env vars as overrides and lets every `from_env()` read
+//! the file's values unchanged.
+//!
+//! Only the flat subset of TOML the settings need is understood: tables,
+//! strings, integers, floats, booleans and single-line arrays (joined with
+//! commas, e.g. an idle profile or metric labels).
+
+use crate::t;
+use anyhow::{anyhow, bail, Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Loaded when no `--config` is given and the file exists.
+pub const DEFAULT_PATH: &str = "/etc/jetsonscope/daemon.toml";
+
+/// Variables a config file may set; anything else is a typo.
+const KNOWN: &[&str] = &[
+    "JETSONSCOPE_SOCKET_PATH",
+    "JETSONSCOPE_PROTO",
+    "JETSONSCOPE_TCP_ADDR",
+    "JETSONSCOPE_AUTH_TOKEN",
+    "JETSONSCOPE_AUTH_TOKENS",
+    "JETSONSCOPE_METRICS_TOKEN",
+    "JETSONSCOPE_DEBUG_TOKEN",
+    "JETSONSCOPE_HTTP_ADDR",
+    "JETSONSCOPE_METRICS_DCGM",
+    "JETSONSCOPE_METRIC_LABELS",
+    "JETSONSCOPE_READY_MAX_AGE",
+    "JETSONSCOPE_WS_INTERVAL_MS",
+    "JETSONSCOPE_SOURCE",
+    "JETSONSCOPE_STATS_CMD",
+    "JETSONSCOPE_TUI_MODE",
+    "JETSONSCOPE_INTERVAL_MS",
+    "JETSONSCOPE_TELEMETRY_LOG",
+    "JETSONSCOPE_TELEMETRY_INTERVAL",
+    "JETSONSCOPE_MQTT_ADDR",
+    "JETSONSCOPE_MQTT_TOPIC",
+    "JETSONSCOPE_MQTT_USER",
+    "JETSONSCOPE_MQTT_PASSWORD",
+    "JETSONSCOPE_MQTT_INTERVAL",
+    "JETSONSCOPE_MQTT_FULL",
+    "JETSONSCOPE_HA_DISCOVERY",
+    "JETSONSCOPE_GRAPHITE_ADDR",
+    "JETSONSCOPE_GRAPHITE_PREFIX",
+    "JETSONSCOPE_GRAPHITE_INTERVAL",
+    "JETSONSCOPE_STATSD_ADDR",
+    "JETSONSCOPE_STATSD_PREFIX",
+    "JETSONSCOPE_STATSD_INTERVAL",
+    "JETSONSCOPE_HISTORY_MINUTES",
+    "JETSONSCOPE_HISTORY_DB",
+    "JETSONSCOPE_HISTORY_RETENTION_HOURS",
+    "JETSONSCOPE_RECORD_DIR",
+    "JETSONSCOPE_RECORD_FSYNC",
+    "JETSONSCOPE_RECORD_SEGMENT_MB",
+    "JETSONSCOPE_ENERGY_STATE",
+    "JETSONSCOPE_ENERGY_PRICE",
+    "JETSONSCOPE_ENERGY_CURRENCY",
+    "JETSONSCOPE_CO2_G_PER_KWH",
+    "JETSONSCOPE_LOW_BATTERY_PCT",
+    "JETSONSCOPE_THERMAL_TARGET",
+    "JETSONSCOPE_THERMAL_SENSOR",
+    "JETSONSCOPE_POWER_BUDGET_MW",
+    "JETSONSCOPE_POWER_BUDGET_WINDOW",
+    "JETSONSCOPE_GUARD_CRITICAL_C",
+    "JETSONSCOPE_GUARD_RECOVER_C",
+    "JETSONSCOPE_GUARD_AFTER",
+    "JETSONSCOPE_GUARD_SENSOR",
+    "JETSONSCOPE_IDLE_PROFILE",
+    "JETSONSCOPE_IDLE_AFTER",
+    "JETSONSCOPE_IDLE_CPU_PCT",
+    "JETSONSCOPE_IDLE_ENGINE_PCT",
+    "JETSONSCOPE_PROFILES",
+    "JETSONSCOPE_DEVICE_NAME",
+    "JETSONSCOPE_NODE_NAME",
+    "JETSONSCOPE_POD_NAME",
+    "JETSONSCOPE_POD_NAMESPACE",
+    "JETSONSCOPE_PODINFO_LABELS",
+    "JETSONSCOPE_LANG",
+    "JETSONSCOPE_ALERTS",
+];
+
+/// `section.key` names for variables that do not follow the section rule.
+const ALIASES: &[(&str, &str)] = &[
+    ("collector.source", "JETSONSCOPE_SOURCE"),
+    ("collector.stats_cmd", "JETSONSCOPE_STATS_CMD"),
+    ("collector.mode", "JETSONSCOPE_TUI_MODE"),
+    ("collector.interval_ms", "JETSONSCOPE_INTERVAL_MS"),
+    ("socket.proto", "JETSONSCOPE_PROTO"),
+    ("socket.tcp_addr", "JETSONSCOPE_TCP_ADDR"),
+    ("metrics.addr", "JETSONSCOPE_HTTP_ADDR"),
+    ("metrics.ws_interval_ms", "JETSONSCOPE_WS_INTERVAL_MS"),
+    ("metrics.labels", "JETSONSCOPE_METRIC_LABELS"),
+    ("metrics.ready_max_age", "JETSONSCOPE_READY_MAX_AGE"),
+    ("mqtt.ha_discovery", "JETSONSCOPE_HA_DISCOVERY"),
+    ("auth.metrics_token", "JETSONSCOPE_METRICS_TOKEN"),
+    ("auth.debug_token", "JETSONSCOPE_DEBUG_TOKEN"),
+    ("alerts.rules", "JETSONSCOPE_ALERTS"),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DaemonConfig {
+    pub path: PathBuf,
+    /// (variable, value) in file order
+    pub vars: Vec<(String, String)>,
+}
+
+impl DaemonConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| t!("config.err.read", path = path.display()))?;
+        let vars = parse(&text).with_context(|| path.display().to_string())?;
+        Ok(DaemonConfig {
+            path: path.to_path_buf(),
+            vars,
+        })
+    }
+
+    /// `--config PATH` (or `--config=PATH`), else `DEFAULT_PATH` if it exists.
+    pub fn from_args(args: &[String]) -> Result<Option<Self>> {
+        let mut it = args.iter();
+        while let Some(arg) = it.next() {
+            let path = match arg.strip_prefix("--config") {
+                Some("") => it.next().ok_or_else(|| anyhow!(t!("config.err.no_path")))?,
+                Some(rest) if rest.starts_with('=') => &rest[1..],
+                _ => continue,
+            };
+            return Self::load(Path::new(path)).map(Some);
+        }
+        let default = Path::new(DEFAULT_PATH);
+        if default.exists() {
+            return Self::load(default).map(Some);
+        }
+        Ok(None)
+    }
+
+    /// Settings whose variable `is_set` does not report as already set.
+    pub fn missing_from(&self, is_set: impl Fn(&str) -> bool) -> Vec<(&str, &str)> {
+        self.vars
+            .iter()
+            .filter(|(name, _)| !is_set(name))
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect()
+    }
+
+    /// Export the settings the environment lacks; returns how many were set.
+    ///
+    /// Call before any thread starts, since it changes the process environment.
+    pub fn apply(&self) -> usize {
+        let missing = self.missing_from(|name| std::env::var_os(name).is_some());
+        for (name, value) in &missing {
+            std::env::set_var(name, value);
+        }
+        missing.len()
+    }
+}
+
+/// (variable, value) for every key in `text`.
+pub fn parse(text: &str) -> Result<Vec<(String, String)>> {
+    let mut section = String::new();
+    let mut vars: Vec<(String, String)> = Vec::new();
+    for (i, raw) in text.lines().enumerate() {
+        let line_no = i + 1;
+        let line = strip_comment(raw).trim();
+        if line.is_empty() {
+            continue;
+        }
+        let at_line = |e: anyhow::Error| anyhow!(t!("config.err.line", line = line_no, error = e));
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name
+                .strip_suffix(']')
+                .map(str::trim)
+                .filter(|n| is_bare_key(n))
+                .ok_or_else(|| at_line(anyhow!(t!("config.err.section", section = line))))?;
+            section = name.to_string();
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .map(|(k, v)| (k.trim(), v.trim()))
+            .filter(|(k, _)| is_bare_key(k))
+            .ok_or_else(|| at_line(anyhow!(t!("config.err.syntax"))))?;
+        let name = var_name(&section, key);
+        if !KNOWN.contains(&name.as_str()) {
+            return Err(at_line(anyhow!(t!(
+                "config.err.unknown",
+                key = key,
+                var = name
+            ))));
+        }
+        let value = parse_value(value).map_err(at_line)?;
+        match vars.iter_mut().find(|(n, _)| *n == name) {
+            Some(existing) => existing.1 = value,
+            None => vars.push((name, value)),
+        }
+    }
+    Ok(vars)
+}
+
+fn var_name(section: &str, key: &str) -> String {
+    let dotted = format!("{section}.{key}");
+    if let Some((_, var)) = ALIASES.iter().find(|(alias, _)| *alias == dotted) {
+        return var.to_string();
+    }
+    let name = match section {
+        "" => key.to_string(),
+        _ => format!("{section}_{key}"),
+    };
+    format!(
+        "JETSONSCOPE_{}",
+        name.to_ascii_uppercase().replace('-', "_")
+    )
+}
+
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+}
+
+/// The line up to a `#` that is not inside a string.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(q), c) if c == q && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return &line[..i],
+            _ => {}
+        }
+        escaped = false;
+    }
+    line
+}
+
+/// A scalar as the variable's text; arrays are joined with commas.
+fn parse_value(value: &str) -> Result<String> {
+    if let Some(inner) = value.strip_prefix('[') {
+        let inner = inner
+            .strip_suffix(']')
+            .ok_or_else(|| anyhow!(t!("config.err.value", value = value)))?;
+        let items = split_array(inner)
+            .into_iter()
+            .map(|item| parse_scalar(item.trim()))
+            .collect::<Result<Vec<_>>>()?;
+        return Ok(items.join(","));
+    }
+    parse_scalar(value)
+}
+
+/// Array items, splitting on commas outside strings; a trailing comma is allowed.
+fn split_array(inner: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let (mut start, mut quote) = (0, None);
+    for (i, c) in inner.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, ',') => {
+                items.push(&inner[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(&inner[start..]);
+    items.retain(|item| !item.trim().is_empty());
+    items
+}
+
+fn parse_scalar(value: &str) -> Result<String> {
+    if let Some(s) = value.strip_prefix('"') {
+        let s = s
+            .strip_suffix('"')
+            .ok_or_else(|| anyhow!(t!("config.err.string", value = value)))?;
+        return unescape(s).ok_or_else(|| anyhow!(t!("config.err.string", value = value)));
+    }
+    if let Some(s) = value.strip_prefix('\'') {
+        return s
+            .strip_suffix('\'')
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!(t!("config.err.string", value = value)));
+    }
+    let plain = value.replace('_', "");
+    if matches!(value, "true" | "false")
+        || plain.parse::<i64>().is_ok()
+        || plain.parse::<f64>().is_ok()
+    {
+        return Ok(plain);
+    }
+    bail!(t!("config.err.value", value = value))
+}
+
+fn unescape(s: &str) -> Option<String> {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '"' {
+            return None;
+        }
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        out.push(match chars.next()? {
+            'n' => '\n',
+            't' => '\t',
+            '"' => '"',
+            '\\' => '\\',
+            _ => return None,
+        });
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_keys_to_environment_variables() {
+        let vars = parse(
+            r#"
+# Daemon settings
+http_addr = "0.0.0.0:9090"   # Prometheus endpoint
+
+[socket]
+path = '/run/jetsonscope.sock'
+
+[auth]
+token = "s3cr\"et#1"
+
+[collector]
+source = "sysfs"
+interval_ms = 500
+
+[idle]
+profile = ["nvpmodel=MODE_1", "fan=20",]
+
+[mqtt]
+ha_discovery = false
+interval = 1_000
+"#,
+        )
+        .unwrap();
+        let get = |name: &str| {
+            vars.iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v.as_str())
+        };
+        assert_eq!(get("JETSONSCOPE_HTTP_ADDR"), Some("0.0.0.0:9090"));
+        assert_eq!(
+            get("JETSONSCOPE_SOCKET_PATH"),
+            Some("/run/jetsonscope.sock")
+        );
+        assert_eq!(get("JETSONSCOPE_AUTH_TOKEN"), Some("s3cr\"et#1"));
+        assert_eq!(get("JETSONSCOPE_INTERVAL_MS"), Some("500"));
+        assert_eq!(get("JETSONSCOPE_SOURCE"), Some("sysfs"));
+        assert_eq!(
+            get("JETSONSCOPE_IDLE_PROFILE"),
+            Some("nvpmodel=MODE_1,fan=20")
+        );
+        assert_eq!(get("JETSONSCOPE_HA_DISCOVERY"), Some("false"));
+        assert_eq!(get("JETSONSCOPE_MQTT_INTERVAL"), Some("1000"));
+
+        let shipped = parse(include_str!("../install/daemon.toml")).unwrap();
+        assert!(shipped.contains(&("JETSONSCOPE_INTERVAL_MS".to_string(), "1000".to_string())));
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_bad_values() {
+        let err = parse("[mqtt]\naddr = \"broker:1883\"\nadress = \"x\"\n").unwrap_err();
+        assert!(err.to_string().contains('3'), "{err}");
+        assert!(parse("http_addr = 0.0.0.0:9090").is_err());
+        assert!(parse("[socket\npath = \"/tmp/x\"").is_err());
+        assert!(parse("[socket]\npath = \"/tmp/x").is_err());
+        assert!(parse("[socket]\npath").is_err());
+
+        let cfg = DaemonConfig {
+            path: PathBuf::from(DEFAULT_PATH),
+            vars: parse("http_addr = \":9090\"\n[auth]\ntoken = \"file\"\n").unwrap(),
+        };
+        let missing = cfg.missing_from(|name| name == "JETSONSCOPE_AUTH_TOKEN");
+        assert_eq!(missing, vec![("JETSONSCOPE_HTTP_ADDR", ":9090")]);
+    }
+}
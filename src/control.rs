@@ -1,10 +1,170 @@
-use crate::hardware::JetsonHardware;
+use crate::clock_cap::{CapArbiter, ClockCapStatus, ClockTable};
+use crate::fans::{self, FanCurve, FanState};
+use crate::hardware::{FrequencyCaps, JetsonHardware};
+use crate::idle::{IdleConfig, IdleDetector, IdleStatus, IdleTransition};
+use crate::parser::TegraStats;
+use crate::power_cap::{self, PowerCapController};
 use crate::protocol::ControlInfo;
+use crate::t;
+use crate::thermal_governor::{self, valid_temp, ThermalGovernor};
+use crate::thermal_guard::{GuardConfig, GuardEvent, GuardStatus, GuardTransition, ThermalGuard};
 use anyhow::{anyhow, Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// A change to one of the fixed controls, parsed from its wire name and value
+/// by `Control::parse` and applied with `ControlManager::apply`. Per-fan
+/// speeds and curves, frequency caps and the power and thermal targets keep
+/// their string form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Control {
+    JetsonClocks(bool),
+    Nvpmodel(String),
+    FanPercent(u8),
+    CpuGovernor(String),
+    GpuGovernor(String),
+    GpuRailgate(RailgateMode),
+}
+
+/// GPU runtime power management: rail-gated when idle, or always powered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RailgateMode {
+    Auto,
+    On,
+}
+
+impl RailgateMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "auto" => Some(RailgateMode::Auto),
+            "on" => Some(RailgateMode::On),
+            _ => None,
+        }
+    }
+
+    /// Value of `power/control` and of the wire
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RailgateMode::Auto => "auto",
+            RailgateMode::On => "on",
+        }
+    }
+}
+
+impl Control {
+    pub const JETSON_CLOCKS: &'static str = "jetson_clocks";
+    pub const NVPMODEL: &'static str = "nvpmodel";
+    pub const FAN: &'static str = "fan";
+    pub const CPU_GOVERNOR: &'static str = "cpu_governor";
+    pub const GPU_GOVERNOR: &'static str = "gpu_governor";
+    pub const GPU_RAILGATE: &'static str = "gpu_railgate";
+
+    /// The typed form of a SetControl; `None` when `name` is not one of the
+    /// fixed controls above, `Some(Err)` when the value is malformed for it.
+    /// Whether a mode or governor exists is checked when it is applied.
+    pub fn parse(name: &str, value: &str) -> Option<Result<Self>> {
+        let control = match name {
+            Self::JETSON_CLOCKS => match value {
+                "on" => Ok(Control::JetsonClocks(true)),
+                "off" => Ok(Control::JetsonClocks(false)),
+                _ => Err(anyhow!(t!("control.err.jetson_clocks_value", value = value))),
+            },
+            Self::NVPMODEL => Ok(Control::Nvpmodel(value.to_string())),
+            Self::FAN => match value.parse::<u8>() {
+                Ok(percent) if percent <= 100 => Ok(Control::FanPercent(percent)),
+                Ok(percent) => Err(anyhow!(t!("control.err.fan_range", value = percent))),
+                Err(_) => Err(anyhow!(t!("control.err.fan_value"))),
+            },
+            Self::CPU_GOVERNOR => Ok(Control::CpuGovernor(value.to_string())),
+            Self::GPU_GOVERNOR => Ok(Control::GpuGovernor(value.to_string())),
+            Self::GPU_RAILGATE => RailgateMode::parse(value)
+                .map(Control::GpuRailgate)
+                .ok_or_else(|| anyhow!(t!("control.err.railgate_mode", mode = value))),
+            _ => return None,
+        };
+        Some(control)
+    }
+
+    /// Wire name, as in `ControlInfo::name`
+    #[allow(dead_code)] // Client::apply
+    pub fn name(&self) -> &'static str {
+        match self {
+            Control::JetsonClocks(_) => Self::JETSON_CLOCKS,
+            Control::Nvpmodel(_) => Self::NVPMODEL,
+            Control::FanPercent(_) => Self::FAN,
+            Control::CpuGovernor(_) => Self::CPU_GOVERNOR,
+            Control::GpuGovernor(_) => Self::GPU_GOVERNOR,
+            Control::GpuRailgate(_) => Self::GPU_RAILGATE,
+        }
+    }
+
+    /// Wire value, as SetControl takes it
+    #[allow(dead_code)] // Client::apply
+    pub fn value(&self) -> String {
+        match self {
+            Control::JetsonClocks(on) => if *on { "on" } else { "off" }.to_string(),
+            Control::Nvpmodel(mode) => mode.clone(),
+            Control::FanPercent(percent) => percent.to_string(),
+            Control::CpuGovernor(governor) | Control::GpuGovernor(governor) => governor.clone(),
+            Control::GpuRailgate(mode) => mode.as_str().to_string(),
+        }
+    }
+}
+
+/// Why `apply_control` or `plan_control` turned a change down. The daemon
+/// answers `Unknown` and `Invalid` with `invalid_control`, `Failed` with
+/// `control_error`.
+#[derive(Debug)]
+pub enum ControlError {
+    /// No control by that name on this board
+    Unknown(String),
+    /// The value does not parse, or is out of range for the control
+    Invalid(anyhow::Error),
+    /// A well-formed change that could not be made (unsupported, no such
+    /// mode or step, a command or sysfs write failed)
+    Failed(anyhow::Error),
+}
+
+impl std::fmt::Display for ControlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ControlError::Unknown(name) => write!(f, "{}: {name}", t!("control.err.unknown_control")),
+            ControlError::Invalid(e) | ControlError::Failed(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for ControlError {}
+
+impl From<anyhow::Error> for ControlError {
+    /// Setters mark malformed values with `invalid`; any other error is a failure.
+    fn from(e: anyhow::Error) -> Self {
+        if e.downcast_ref::<InvalidValue>().is_some() {
+            ControlError::Invalid(e)
+        } else {
+            ControlError::Failed(e)
+        }
+    }
+}
+
+/// A setter's error for a value that does not parse or is out of range.
+#[derive(Debug)]
+struct InvalidValue(String);
+
+impl std::fmt::Display for InvalidValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for InvalidValue {}
+
+fn invalid(message: impl std::fmt::Display) -> anyhow::Error {
+    InvalidValue(message.to_string()).into()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ControlStatus {
     pub available: bool,
@@ -25,6 +185,26 @@ pub struct ControlStatus {
     pub supports_gpu_railgate: bool,
     pub note: String,
     pub last_error: Option<String>,
+    #[serde(default)]
+    pub clock_cap: ClockCapStatus,
+    #[serde(default)]
+    pub idle: IdleStatus,
+    #[serde(default)]
+    pub guard: GuardStatus,
+    /// Current devfreq max_freq (Hz) per frequency domain, e.g. "GPU"
+    #[serde(default)]
+    pub max_freqs: BTreeMap<String, u64>,
+    /// cpufreq scaling_min_freq / scaling_max_freq (kHz), written to every core
+    #[serde(default)]
+    pub cpu_min_freq_khz: Option<u64>,
+    #[serde(default)]
+    pub cpu_max_freq_khz: Option<u64>,
+    /// Speed and curve of each hwmon fan (fan0, fan1, ...)
+    #[serde(default)]
+    pub fans: Vec<FanState>,
+    /// Curve driving the "fan" setpoint, as given to `fan_curve`
+    #[serde(default)]
+    pub fan_curve: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -33,6 +213,26 @@ pub struct ControlManager {
     mock: bool,
     #[allow(dead_code)]
     hardware: JetsonHardware,
+    clocks: ClockTable,
+    caps: CapArbiter,
+    power_cap: Option<PowerCapController>,
+    /// JETSONSCOPE_POWER_BUDGET_MW; a cap source of its own that power_cap_watts cannot lift
+    power_budget: Option<PowerCapController>,
+    thermal: Option<ThermalGovernor>,
+    idle: Option<IdleDetector>,
+    /// Control values to restore when leaving idle
+    idle_restore: Vec<(String, String)>,
+    guard: Option<ThermalGuard>,
+    /// Control values to restore once the thermal guard recovers
+    guard_restore: Vec<(String, String)>,
+    /// Guard actions not yet written to the audit log (`take_guard_events`)
+    guard_events: Vec<GuardEvent>,
+    /// Temperature curves per fan index
+    fan_curves: BTreeMap<usize, FanCurve>,
+    /// Temperature curve for the "fan" setpoint
+    fan_curve: Option<FanCurve>,
+    /// Commands and sysfs writes recorded instead of run (`plan_control`)
+    plan: Option<Vec<String>>,
 }
 
 impl Default for ControlManager {
@@ -55,14 +255,33 @@ impl ControlManager {
             } else {
                 hardware.nvpmodel_modes.clone()
             };
+            let clocks = ClockTable::mock();
+            let max_level = clocks.max_level();
+            let max_freqs = frequency_maxima(&hardware.frequencies, true);
+            let (cpu_min_freq_khz, cpu_max_freq_khz) =
+                (clocks.cpu_khz.first().copied(), clocks.cpu_khz.last().copied());
+            let fans = fan_states(&hardware, true);
             return ControlManager {
                 hardware,
                 mock: true,
+                clocks,
+                caps: CapArbiter::default(),
+                power_cap: None,
+                power_budget: None,
+                thermal: None,
+                idle: None,
+                idle_restore: Vec::new(),
+                guard: None,
+                guard_restore: Vec::new(),
+                guard_events: Vec::new(),
+                fan_curves: BTreeMap::new(),
+                fan_curve: None,
+                plan: None,
                 status: ControlStatus {
                     available: true,
                     jetson_clocks: Some(false),
                     fan: Some("0%".into()),
-                    nvpmodel: nvpmodel_modes.get(0).cloned().or_else(|| Some("unknown".into())),
+                    nvpmodel: nvpmodel_modes.first().cloned().or_else(|| Some("unknown".into())),
                     nvpmodel_modes,
                     cpu_governor: Some("ondemand".into()),
                     cpu_governor_modes: vec!["ondemand".into(), "performance".into()],
@@ -75,8 +294,19 @@ impl ControlManager {
                     supports_cpu_governor: true,
                     supports_gpu_governor: true,
                     supports_gpu_railgate: true,
-                    note: "Mock mode (no real commands)".to_string(),
+                    note: t!("control.note.mock").to_string(),
                     last_error: None,
+                    clock_cap: ClockCapStatus {
+                        max_level,
+                        ..Default::default()
+                    },
+                    idle: IdleStatus::default(),
+                    guard: GuardStatus::default(),
+                    max_freqs,
+                    cpu_min_freq_khz,
+                    cpu_max_freq_khz,
+                    fans,
+                    fan_curve: None,
                 },
             };
         }
@@ -105,10 +335,28 @@ impl ControlManager {
             let supports_cpu_governor = !cpu_governor_modes.is_empty();
             let supports_gpu_governor = !gpu_governor_modes.is_empty();
             let supports_gpu_railgate = gpu_railgate.is_some();
+            let clocks = ClockTable::detect();
+            let max_level = clocks.max_level();
+            let max_freqs = frequency_maxima(&hardware.frequencies, mock);
+            let (cpu_min_freq_khz, cpu_max_freq_khz) = detect_cpu_freq_limits();
+            let fans = fan_states(&hardware, mock);
 
             ControlManager {
                 hardware,
                 mock,
+                clocks,
+                caps: CapArbiter::default(),
+                power_cap: None,
+                power_budget: None,
+                thermal: None,
+                idle: None,
+                idle_restore: Vec::new(),
+                guard: None,
+                guard_restore: Vec::new(),
+                guard_events: Vec::new(),
+                fan_curves: BTreeMap::new(),
+                fan_curve: None,
+                plan: None,
                 status: ControlStatus {
                     available: true,
                     jetson_clocks: if mock {
@@ -130,14 +378,38 @@ impl ControlManager {
                     supports_cpu_governor,
                     supports_gpu_governor,
                     supports_gpu_railgate,
-                    note: "Controles listos".to_string(),
+                    note: t!("control.note.ready").to_string(),
                     last_error: None,
+                    clock_cap: ClockCapStatus {
+                        max_level,
+                        ..Default::default()
+                    },
+                    idle: IdleStatus::default(),
+                    guard: GuardStatus::default(),
+                    max_freqs,
+                    cpu_min_freq_khz,
+                    cpu_max_freq_khz,
+                    fans,
+                    fan_curve: None,
                 },
             }
         } else {
             ControlManager {
                 hardware,
                 mock,
+                clocks: ClockTable::default(),
+                caps: CapArbiter::default(),
+                power_cap: None,
+                power_budget: None,
+                thermal: None,
+                idle: None,
+                idle_restore: Vec::new(),
+                guard: None,
+                guard_restore: Vec::new(),
+                guard_events: Vec::new(),
+                fan_curves: BTreeMap::new(),
+                fan_curve: None,
+                plan: None,
                 status: ControlStatus {
                     available: false,
                     jetson_clocks: None,
@@ -155,8 +427,16 @@ impl ControlManager {
                     supports_cpu_governor: false,
                     supports_gpu_governor: false,
                     supports_gpu_railgate: false,
-                    note: "Host no Jetson: modo demo".to_string(),
+                    note: t!("control.note.not_jetson").to_string(),
                     last_error: None,
+                    clock_cap: ClockCapStatus::default(),
+                    idle: IdleStatus::default(),
+                    guard: GuardStatus::default(),
+                    max_freqs: BTreeMap::new(),
+                    cpu_min_freq_khz: None,
+                    cpu_max_freq_khz: None,
+                    fans: Vec::new(),
+                    fan_curve: None,
                 },
             }
         }
@@ -189,8 +469,8 @@ impl ControlManager {
 
         if self.status.supports_jetson_clocks {
             controls.push(ControlInfo {
-                name: "jetson_clocks".to_string(),
-                description: "Max performance mode".to_string(),
+                name: Control::JETSON_CLOCKS.to_string(),
+                description: t!("control.desc.jetson_clocks").to_string(),
                 value: self
                     .status
                     .jetson_clocks
@@ -205,13 +485,15 @@ impl ControlManager {
                 requires_sudo: true,
                 supported: self.status.supports_jetson_clocks,
                 unit: None,
+                option_details: BTreeMap::new(),
+                frequencies_hz: Vec::new(),
             });
         }
 
         if self.status.supports_nvpmodel {
             controls.push(ControlInfo {
-                name: "nvpmodel".to_string(),
-                description: "Power mode".to_string(),
+                name: Control::NVPMODEL.to_string(),
+                description: t!("control.desc.nvpmodel").to_string(),
                 value: self
                     .status
                     .nvpmodel
@@ -225,13 +507,20 @@ impl ControlManager {
                 requires_sudo: true,
                 supported: self.status.supports_nvpmodel,
                 unit: None,
+                option_details: self
+                    .hardware
+                    .nvpmodel_caps
+                    .iter()
+                    .map(|m| (m.name.clone(), m.describe()))
+                    .collect(),
+                frequencies_hz: Vec::new(),
             });
         }
 
         if self.status.supports_fan {
             controls.push(ControlInfo {
-                name: "fan".to_string(),
-                description: "Fan speed".to_string(),
+                name: Control::FAN.to_string(),
+                description: t!("control.desc.fan").to_string(),
                 value: self.status.fan.clone().unwrap_or("0%".to_string()),
                 options: vec!["0-100".to_string()], // Special handling for range
                 readonly: false,
@@ -241,13 +530,33 @@ impl ControlManager {
                 requires_sudo: true,
                 supported: self.status.supports_fan,
                 unit: Some("%".to_string()),
+                option_details: BTreeMap::new(),
+                frequencies_hz: Vec::new(),
+            });
+            controls.push(ControlInfo {
+                name: "fan_curve".to_string(),
+                description: t!("control.desc.fan_curve_legacy").to_string(),
+                value: self.status.fan_curve.clone().unwrap_or_else(|| "off".to_string()),
+                options: curve_options(),
+                readonly: false,
+                min: None,
+                max: None,
+                step: None,
+                requires_sudo: true,
+                supported: self.status.supports_fan,
+                unit: None,
+                option_details: fans::PRESETS
+                    .iter()
+                    .map(|(name, spec)| (name.to_string(), spec.to_string()))
+                    .collect(),
+                frequencies_hz: Vec::new(),
             });
         }
 
         if self.status.supports_cpu_governor {
             controls.push(ControlInfo {
-                name: "cpu_governor".to_string(),
-                description: "CPU governor".to_string(),
+                name: Control::CPU_GOVERNOR.to_string(),
+                description: t!("control.desc.cpu_governor").to_string(),
                 value: self
                     .status
                     .cpu_governor
@@ -261,13 +570,15 @@ impl ControlManager {
                 requires_sudo: true,
                 supported: self.status.supports_cpu_governor,
                 unit: None,
+                option_details: BTreeMap::new(),
+                frequencies_hz: Vec::new(),
             });
         }
 
         if self.status.supports_gpu_governor {
             controls.push(ControlInfo {
-                name: "gpu_governor".to_string(),
-                description: "GPU governor".to_string(),
+                name: Control::GPU_GOVERNOR.to_string(),
+                description: t!("control.desc.gpu_governor").to_string(),
                 value: self
                     .status
                     .gpu_governor
@@ -281,20 +592,24 @@ impl ControlManager {
                 requires_sudo: true,
                 supported: self.status.supports_gpu_governor,
                 unit: None,
+                option_details: BTreeMap::new(),
+                frequencies_hz: Vec::new(),
             });
         }
 
         if self.status.supports_gpu_railgate {
             controls.push(ControlInfo {
-                name: "gpu_railgate".to_string(),
-                description: "GPU rail-gating (power control)".to_string(),
+                name: Control::GPU_RAILGATE.to_string(),
+                description: t!("control.desc.gpu_railgate").to_string(),
                 value: self
                     .status
                     .gpu_railgate
-                    .map(|v| if v { "auto" } else { "on" })
+                    .map(|auto| if auto { RailgateMode::Auto } else { RailgateMode::On }.as_str())
                     .unwrap_or("unknown")
                     .to_string(),
-                options: vec!["auto".to_string(), "on".to_string()],
+                options: [RailgateMode::Auto, RailgateMode::On]
+                    .map(|m| m.as_str().to_string())
+                    .to_vec(),
                 readonly: false,
                 min: None,
                 max: None,
@@ -302,6 +617,194 @@ impl ControlManager {
                 requires_sudo: true,
                 supported: self.status.supports_gpu_railgate,
                 unit: None,
+                option_details: BTreeMap::new(),
+                frequencies_hz: Vec::new(),
+            });
+        }
+
+        if !self.clocks.is_empty() {
+            controls.push(ControlInfo {
+                name: "power_cap_watts".to_string(),
+                description: t!("control.desc.power_cap").to_string(),
+                value: self
+                    .power_cap
+                    .as_ref()
+                    .map(|c| format!("{:.1}", c.cap_watts()))
+                    .unwrap_or_else(|| "off".to_string()),
+                options: vec!["off".to_string(), "1-100".to_string()],
+                readonly: false,
+                min: Some(1),
+                max: Some(100),
+                step: Some(1),
+                requires_sudo: true,
+                supported: true,
+                unit: Some("W".to_string()),
+                option_details: BTreeMap::new(),
+                frequencies_hz: Vec::new(),
+            });
+        }
+
+        if !self.clocks.is_empty() {
+            controls.push(ControlInfo {
+                name: "thermal_target_c".to_string(),
+                description: t!("control.desc.thermal_target").to_string(),
+                value: self
+                    .thermal
+                    .as_ref()
+                    .map(|t| format!("{:.1}", t.target_c()))
+                    .unwrap_or_else(|| "off".to_string()),
+                options: vec!["off".to_string(), "40-105".to_string()],
+                readonly: false,
+                min: Some(40),
+                max: Some(105),
+                step: Some(1),
+                requires_sudo: true,
+                supported: true,
+                unit: Some("C".to_string()),
+                option_details: BTreeMap::new(),
+                frequencies_hz: Vec::new(),
+            });
+        }
+
+        for (fan, state) in self.hardware.fans.iter().zip(&self.status.fans) {
+            let rpm = state.rpm.map(|r| format!(", {} RPM", r)).unwrap_or_default();
+            controls.push(ControlInfo {
+                name: fan.control_name(),
+                description: t!("control.desc.fan_n", index = fan.index, name = fan.name, rpm = rpm),
+                value: state
+                    .percent()
+                    .map(|p| format!("{}%", p))
+                    .unwrap_or_else(|| "unknown".to_string()),
+                options: vec!["0-100".to_string()],
+                readonly: false,
+                min: Some(0),
+                max: Some(100),
+                step: Some(1),
+                requires_sudo: true,
+                supported: true,
+                unit: Some("%".to_string()),
+                option_details: BTreeMap::new(),
+                frequencies_hz: Vec::new(),
+            });
+            controls.push(ControlInfo {
+                name: format!("{}_curve", fan.control_name()),
+                description: t!("control.desc.fan_curve", index = fan.index),
+                value: state.curve.clone().unwrap_or_else(|| "off".to_string()),
+                options: curve_options(),
+                readonly: false,
+                min: None,
+                max: None,
+                step: None,
+                requires_sudo: true,
+                supported: true,
+                unit: None,
+                option_details: BTreeMap::new(),
+                frequencies_hz: Vec::new(),
+            });
+        }
+
+        if let (Some(lowest), Some(highest)) = (self.clocks.cpu_khz.first(), self.clocks.cpu_khz.last()) {
+            let mhz = |khz: u64| (khz / 1000) as u32;
+            for (name, description, current) in [
+                (
+                    "cpu_min_freq",
+                    t!("control.desc.cpu_min_freq"),
+                    self.status.cpu_min_freq_khz,
+                ),
+                (
+                    "cpu_max_freq",
+                    t!("control.desc.cpu_max_freq"),
+                    self.status.cpu_max_freq_khz,
+                ),
+            ] {
+                controls.push(ControlInfo {
+                    name: name.to_string(),
+                    description: description.to_string(),
+                    value: current
+                        .map(|khz| mhz(khz).to_string())
+                        .unwrap_or_else(|| "unknown".to_string()),
+                    options: self.clocks.cpu_khz.iter().map(|khz| mhz(*khz).to_string()).collect(),
+                    readonly: false,
+                    min: Some(mhz(*lowest)),
+                    max: Some(mhz(*highest)),
+                    step: None,
+                    requires_sudo: true,
+                    supported: true,
+                    unit: Some("MHz".to_string()),
+                    option_details: BTreeMap::new(),
+                    frequencies_hz: self.clocks.cpu_khz.iter().map(|khz| khz * 1000).collect(),
+                });
+            }
+        }
+
+        for caps in &self.hardware.frequencies {
+            let mhz = |hz: u64| (hz / 1_000_000) as u32;
+            controls.push(ControlInfo {
+                name: caps.control_name(),
+                description: t!("control.desc.max_freq", domain = caps.domain, device = caps.device),
+                value: self
+                    .status
+                    .max_freqs
+                    .get(&caps.domain)
+                    .map(|hz| mhz(*hz).to_string())
+                    .unwrap_or_else(|| "unknown".to_string()),
+                options: caps
+                    .available_hz
+                    .iter()
+                    .map(|hz| mhz(*hz).to_string())
+                    .collect(),
+                readonly: false,
+                min: Some(mhz(caps.min_hz)),
+                max: Some(mhz(caps.max_hz)),
+                step: caps.step_hz.map(mhz),
+                requires_sudo: true,
+                supported: true,
+                unit: Some("MHz".to_string()),
+                option_details: BTreeMap::new(),
+                frequencies_hz: caps.available_hz.clone(),
+            });
+        }
+
+        if self.status.idle.enabled {
+            controls.push(ControlInfo {
+                name: "idle_profile".to_string(),
+                description: t!(
+                    "control.desc.idle_profile",
+                    profile = self.status.idle.profile.clone().unwrap_or_default()
+                ),
+                value: if self.status.idle.idle { "idle" } else { "active" }.to_string(),
+                options: vec!["idle".to_string(), "active".to_string()],
+                readonly: true,
+                min: None,
+                max: None,
+                step: None,
+                requires_sudo: false,
+                supported: true,
+                unit: None,
+                option_details: BTreeMap::new(),
+                frequencies_hz: Vec::new(),
+            });
+        }
+
+        if self.status.guard.enabled {
+            controls.push(ControlInfo {
+                name: "thermal_guard".to_string(),
+                description: t!(
+                    "control.desc.thermal_guard",
+                    critical = self.status.guard.critical_c.unwrap_or_default(),
+                    recover = self.status.guard.recover_c.unwrap_or_default()
+                ),
+                value: if self.status.guard.tripped { "tripped" } else { "armed" }.to_string(),
+                options: vec!["tripped".to_string(), "armed".to_string()],
+                readonly: true,
+                min: None,
+                max: None,
+                step: None,
+                requires_sudo: false,
+                supported: true,
+                unit: None,
+                option_details: BTreeMap::new(),
+                frequencies_hz: Vec::new(),
             });
         }
 
@@ -309,42 +812,108 @@ impl ControlManager {
     }
 
     #[allow(dead_code)]
-    pub fn apply_control(&mut self, name: &str, value: &str) -> Result<ControlInfo> {
-        match name {
-            "jetson_clocks" => {
-                self.set_jetson_clocks(value)?;
-                Ok(self.control_info(name))
-            }
-            "nvpmodel" => {
-                self.set_nvpmodel_mode(Some(value.to_string()));
-                self.status
-                    .last_error
-                    .as_ref()
-                    .map(|e| Err(anyhow!(e.clone())))
-                    .unwrap_or_else(|| Ok(self.control_info(name)))
-            }
-            "fan" => {
-                let p: u8 = value.parse().context("fan value debe ser 0-100")?;
-                self.set_fan(p);
-                self.status
-                    .last_error
-                    .as_ref()
-                    .map(|e| Err(anyhow!(e.clone())))
-                    .unwrap_or_else(|| Ok(self.control_info(name)))
+    pub fn apply_control(&mut self, name: &str, value: &str) -> Result<ControlInfo, ControlError> {
+        if let Some((index, curve)) = fan_control(name) {
+            if curve {
+                self.set_fan_curve(index, value)?;
+            } else {
+                let p: u8 = value
+                    .parse()
+                    .map_err(|_| ControlError::Invalid(anyhow!(t!("control.err.fan_value"))))?;
+                self.set_fan_speed(index, p)?;
             }
-            "cpu_governor" => {
-                self.set_cpu_governor(value)?;
-                Ok(self.control_info(name))
+            return Ok(self.control_info(name));
+        }
+        // "toggle" flips jetson_clocks from whatever state it is in (the TUI's `c`)
+        if name == Control::JETSON_CLOCKS && matches!(value, "toggle" | "") {
+            self.toggle_jetson_clocks();
+            return Ok(self.last_error_or(self.control_info(name))?);
+        }
+        if let Some(control) = Control::parse(name, value) {
+            self.apply(control.map_err(ControlError::Invalid)?)?;
+            return Ok(self.control_info(name));
+        }
+        match name {
+            "fan_curve" => self.set_legacy_fan_curve(value)?,
+            "power_cap_watts" => self.set_power_cap(value)?,
+            "thermal_target_c" => self.set_thermal_target(value)?,
+            "cpu_min_freq" | "cpu_max_freq" => self.set_cpu_freq_limit(name, value)?,
+            _ if self.devfreq_caps(name).is_some() => self.set_max_freq(name, value)?,
+            _ => return Err(ControlError::Unknown(name.to_string())),
+        }
+        Ok(self.control_info(name))
+    }
+
+    /// Validate a control change like `apply_control` and list the commands
+    /// and sysfs writes it would run, without running them.
+    #[allow(dead_code)] // jscoped
+    pub fn plan_control(&self, name: &str, value: &str) -> Result<Vec<String>, ControlError> {
+        let mut dry = self.clone();
+        dry.plan = Some(Vec::new());
+        dry.apply_control(name, value)?;
+        Ok(dry.plan.unwrap_or_default())
+    }
+
+    /// Check a stored profile entry: the control must exist and the value be
+    /// well-formed, but the board need not support it right now.
+    #[allow(dead_code)] // jscoped
+    pub fn check_control(&self, name: &str, value: &str) -> Result<(), ControlError> {
+        match self.plan_control(name, value) {
+            Ok(_) | Err(ControlError::Failed(_)) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The devfreq domain behind a "<domain>_max_freq" control.
+    fn devfreq_caps(&self, control: &str) -> Option<&FrequencyCaps> {
+        self.hardware
+            .frequencies
+            .iter()
+            .find(|c| c.control_name() == control)
+    }
+
+    /// Run a command or sysfs write; a dry run only records `action`, mock mode skips it.
+    fn effect(&mut self, action: String, run: impl FnOnce() -> Result<()>) -> Result<()> {
+        match self.plan.as_mut() {
+            Some(plan) => {
+                plan.push(action);
+                Ok(())
             }
-            "gpu_governor" => {
-                self.set_gpu_governor(value)?;
-                Ok(self.control_info(name))
+            None if self.mock => Ok(()),
+            None => run(),
+        }
+    }
+
+    fn write_sysfs(&mut self, path: &Path, value: &str) -> Result<()> {
+        self.effect(format!("echo {value} > {}", path.display()), || {
+            std::fs::write(path, value)
+                .with_context(|| t!("control.err.writing", path = format!("{:?}", path)))
+        })
+    }
+
+    /// Apply a typed control change.
+    pub fn apply(&mut self, control: Control) -> Result<()> {
+        match control {
+            Control::JetsonClocks(on) => self.set_jetson_clocks(on),
+            Control::Nvpmodel(mode) => {
+                self.set_nvpmodel_mode(Some(mode));
+                self.last_error_or(())
             }
-            "gpu_railgate" => {
-                self.set_gpu_railgate(value)?;
-                Ok(self.control_info(name))
+            Control::FanPercent(percent) => {
+                self.set_fan(percent);
+                self.last_error_or(())
             }
-            _ => Err(anyhow!("control desconocido")),
+            Control::CpuGovernor(governor) => self.set_cpu_governor(&governor),
+            Control::GpuGovernor(governor) => self.set_gpu_governor(&governor),
+            Control::GpuRailgate(mode) => self.set_gpu_railgate(mode),
+        }
+    }
+
+    /// `ok`, or the error the last status-reporting action left behind.
+    fn last_error_or<T>(&self, ok: T) -> Result<T> {
+        match &self.status.last_error {
+            Some(e) => Err(anyhow!(e.clone())),
+            None => Ok(ok),
         }
     }
 
@@ -365,25 +934,25 @@ impl ControlManager {
                 requires_sudo: false,
                 supported: false,
                 unit: None,
+                option_details: BTreeMap::new(),
+                frequencies_hz: Vec::new(),
             })
     }
 
     pub fn toggle_jetson_clocks(&mut self) {
-        if self.mock {
-            let current = self.status.jetson_clocks.unwrap_or(false);
-            self.status.jetson_clocks = Some(!current);
-            self.status.last_error = None;
+        if self.mock || self.plan.is_some() {
+            let on = !self.status.jetson_clocks.unwrap_or(false);
+            self.status.last_error = self.set_jetson_clocks(on).err().map(|e| e.to_string());
             return;
         }
 
         if !self.status.available {
-            self.status.last_error = Some("No es Jetson (demo)".to_string());
+            self.status.last_error = Some(t!("control.err.not_jetson").to_string());
             return;
         }
 
         if !self.status.supports_jetson_clocks {
-            self.status.last_error =
-                Some("jetson_clocks no disponible en este sistema".to_string());
+            self.status.last_error = Some(t!("control.err.no_jetson_clocks").to_string());
             return;
         }
 
@@ -398,38 +967,42 @@ impl ControlManager {
         }
     }
 
-    #[allow(dead_code)]
-    pub fn set_jetson_clocks(&mut self, value: &str) -> Result<()> {
+    pub fn set_jetson_clocks(&mut self, on: bool) -> Result<()> {
         if !self.status.available {
-            return Err(anyhow!("No es Jetson (demo)"));
+            return Err(anyhow!(t!("control.err.not_jetson")));
         }
         if !self.status.supports_jetson_clocks {
-            return Err(anyhow!("jetson_clocks no disponible en este sistema"));
+            return Err(anyhow!(t!("control.err.no_jetson_clocks")));
         }
-        match value {
-            "on" => run_jetson_clocks_set(true),
-            "off" => run_jetson_clocks_set(false),
-            "toggle" | "" => {
-                self.toggle_jetson_clocks();
-                return Ok(());
-            }
-            _ => Err(anyhow!("Valor inválido para jetson_clocks: {}", value)),
+        let arg = if on { "--on" } else { "--off" };
+        self.effect(format!("jetson_clocks {arg}"), || run_jetson_clocks_set(on))?;
+        self.status.jetson_clocks = Some(on);
+        self.status.last_error = None;
+        Ok(())
+    }
+
+    /// Mode `cycle_nvpmodel` would switch to; `None` without known modes.
+    pub fn next_nvpmodel(&self) -> Option<String> {
+        if self.status.nvpmodel_modes.is_empty() {
+            return None;
         }
+        let current = self.status.nvpmodel.clone().unwrap_or_default();
+        Some(next_mode(&self.status.nvpmodel_modes, &current))
     }
 
     pub fn cycle_nvpmodel(&mut self) {
         if !self.status.available {
-            self.status.last_error = Some("No es Jetson (demo)".to_string());
+            self.status.last_error = Some(t!("control.err.not_jetson").to_string());
             return;
         }
 
         if !self.status.supports_nvpmodel {
-            self.status.last_error = Some("nvpmodel no disponible en este sistema".to_string());
+            self.status.last_error = Some(t!("control.err.no_nvpmodel").to_string());
             return;
         }
 
         if self.status.nvpmodel_modes.is_empty() {
-            self.status.last_error = Some("No se pudieron leer modos nvpmodel".to_string());
+            self.status.last_error = Some(t!("control.err.nvpmodel_modes").to_string());
             return;
         }
         let current = self.status.nvpmodel.clone().unwrap_or_default();
@@ -448,26 +1021,7 @@ impl ControlManager {
     #[allow(dead_code)]
     pub fn set_nvpmodel_mode(&mut self, mode: Option<String>) {
         if !self.status.available {
-            self.status.last_error = Some("No es Jetson (demo)".to_string());
-            return;
-        }
-
-        if self.mock {
-            let target = if let Some(m) = mode {
-                if !self.status.nvpmodel_modes.contains(&m) {
-                    self.status.last_error = Some(format!(
-                        "Modo inválido: {}. Modos disponibles: {:?}",
-                        m, self.status.nvpmodel_modes
-                    ));
-                    return;
-                }
-                m
-            } else {
-                let current = self.status.nvpmodel.clone().unwrap_or_default();
-                next_mode(&self.status.nvpmodel_modes, &current)
-            };
-            self.status.nvpmodel = Some(target);
-            self.status.last_error = None;
+            self.status.last_error = Some(t!("control.err.not_jetson").to_string());
             return;
         }
 
@@ -477,9 +1031,10 @@ impl ControlManager {
             // Our detect_nvpmodel_modes returns names like "MAXN", "15W", etc.
             // We should check if 'm' exists in that list.
             if !self.status.nvpmodel_modes.contains(&m) {
-                self.status.last_error = Some(format!(
-                    "Modo inválido: {}. Modos disponibles: {:?}",
-                    m, self.status.nvpmodel_modes
+                self.status.last_error = Some(t!(
+                    "control.err.nvpmodel_mode",
+                    mode = m,
+                    modes = format!("{:?}", self.status.nvpmodel_modes)
                 ));
                 return;
             }
@@ -489,7 +1044,7 @@ impl ControlManager {
             next_mode(&self.status.nvpmodel_modes, &current)
         };
 
-        match set_nvpmodel(&target) {
+        match self.effect(format!("nvpmodel -m {target}"), || set_nvpmodel(&target)) {
             Ok(_) => {
                 self.status.nvpmodel = Some(target);
                 self.status.last_error = None;
@@ -500,33 +1055,60 @@ impl ControlManager {
         }
     }
 
+    /// Take over what a control action run on a clone of this manager changed;
+    /// the TUI runs the blocking ones (jetson_clocks, nvpmodel, jetson_fan)
+    /// on a worker thread.
+    pub fn adopt_action_result(&mut self, done: ControlManager) {
+        self.status.jetson_clocks = done.status.jetson_clocks;
+        self.status.nvpmodel = done.status.nvpmodel;
+        self.status.fan = done.status.fan;
+        self.status.fan_curve = done.status.fan_curve;
+        self.status.cpu_governor = done.status.cpu_governor;
+        self.status.last_error = done.status.last_error;
+        self.fan_curve = done.fan_curve;
+    }
+
+    /// Fixed "fan" setpoint; this drops its curve.
     pub fn set_fan(&mut self, percent: u8) {
-        if percent > 100 {
-            self.status.last_error = Some(format!(
-                "Valor de fan inválido: {}. Rango válido: 0-100",
-                percent
-            ));
-            return;
+        if percent <= 100 {
+            self.fan_curve = None;
+            self.status.fan_curve = None;
         }
+        self.write_legacy_fan(percent);
+    }
 
-        if self.mock {
-            self.status.fan = Some(format!("{}%", percent));
-            self.status.last_error = None;
+    /// Drive the "fan" setpoint from the hottest sensor with a curve or preset; "off" leaves it where it is.
+    pub fn set_legacy_fan_curve(&mut self, spec: &str) -> Result<()> {
+        let curve = match spec {
+            "off" | "" => None,
+            _ => Some(FanCurve::parse(spec).map_err(invalid)?),
+        };
+        if !self.status.supports_fan {
+            return Err(anyhow!(t!("control.err.no_fan")));
+        }
+        self.status.fan_curve = curve.as_ref().map(|_| spec.to_string());
+        self.fan_curve = curve;
+        self.status.last_error = None;
+        Ok(())
+    }
+
+    fn write_legacy_fan(&mut self, percent: u8) {
+        if percent > 100 {
+            self.status.last_error = Some(t!("control.err.fan_range", value = percent));
             return;
         }
 
         if !self.status.available {
-            self.status.last_error = Some("No es Jetson (demo)".to_string());
+            self.status.last_error = Some(t!("control.err.not_jetson").to_string());
             return;
         }
 
         if !self.status.supports_fan {
-            self.status.last_error =
-                Some("Control de fan no soportado en este hardware".to_string());
+            self.status.last_error = Some(t!("control.err.no_fan").to_string());
             return;
         }
 
-        match set_fan_percent(percent) {
+        match self.effect(format!("jetson_fan --set {percent}"), || set_fan_percent(percent)) {
             Ok(_) => {
                 self.status.fan = Some(format!("{}%", percent));
                 self.status.last_error = None;
@@ -539,19 +1121,21 @@ impl ControlManager {
 
     pub fn set_cpu_governor(&mut self, governor: &str) -> Result<()> {
         if !self.status.available {
-            return Err(anyhow!("No es Jetson (demo)"));
+            return Err(anyhow!(t!("control.err.not_jetson")));
         }
         if !self.status.supports_cpu_governor {
-            return Err(anyhow!("Control de governor no soportado"));
+            return Err(anyhow!(t!("control.err.no_cpu_governor")));
         }
         if !self.status.cpu_governor_modes.contains(&governor.to_string()) {
-            return Err(anyhow!(
-                "Governor inválido: {}. Disponibles: {:?}",
-                governor,
-                self.status.cpu_governor_modes
-            ));
+            return Err(anyhow!(t!(
+                "control.err.cpu_governor",
+                governor = governor,
+                available = format!("{:?}", self.status.cpu_governor_modes)
+            )));
         }
         if self.mock {
+            let path = Path::new("/sys/devices/system/cpu/cpu*/cpufreq/scaling_governor");
+            self.write_sysfs(path, governor)?;
             self.status.cpu_governor = Some(governor.to_string());
             self.status.last_error = None;
             return Ok(());
@@ -561,13 +1145,12 @@ impl ControlManager {
         for path in cpu_paths() {
             let gov_path = path.join("cpufreq/scaling_governor");
             if gov_path.exists() {
-                std::fs::write(&gov_path, governor)
-                    .with_context(|| format!("escribiendo {:?}", gov_path))?;
+                self.write_sysfs(&gov_path, governor)?;
                 wrote_any = true;
             }
         }
         if !wrote_any {
-            return Err(anyhow!("No se pudieron escribir governors (sin rutas)"));
+            return Err(anyhow!(t!("control.err.cpu_governor_paths")));
         }
         self.status.cpu_governor = Some(governor.to_string());
         self.status.last_error = None;
@@ -576,59 +1159,641 @@ impl ControlManager {
 
     pub fn set_gpu_governor(&mut self, governor: &str) -> Result<()> {
         if !self.status.available {
-            return Err(anyhow!("No es Jetson (demo)"));
+            return Err(anyhow!(t!("control.err.not_jetson")));
         }
         if !self.status.supports_gpu_governor {
-            return Err(anyhow!("Control de GPU governor no soportado"));
+            return Err(anyhow!(t!("control.err.no_gpu_governor")));
         }
         if !self.status.gpu_governor_modes.contains(&governor.to_string()) {
-            return Err(anyhow!(
-                "GPU governor inválido: {}. Disponibles: {:?}",
-                governor,
-                self.status.gpu_governor_modes
-            ));
+            return Err(anyhow!(t!(
+                "control.err.gpu_governor",
+                governor = governor,
+                available = format!("{:?}", self.status.gpu_governor_modes)
+            )));
         }
         if self.mock {
+            self.write_sysfs(Path::new("/sys/class/devfreq/<gpu>/governor"), governor)?;
             self.status.gpu_governor = Some(governor.to_string());
             self.status.last_error = None;
             return Ok(());
         }
 
         if let Some(path) = gpu_devfreq_path() {
-            let gov_path = path.join("governor");
-            std::fs::write(&gov_path, governor)
-                .with_context(|| format!("escribiendo {:?}", gov_path))?;
+            self.write_sysfs(&path.join("governor"), governor)?;
             self.status.gpu_governor = Some(governor.to_string());
             self.status.last_error = None;
             return Ok(());
         }
-        Err(anyhow!("No se pudo escribir GPU governor (sin rutas)"))
+        Err(anyhow!(t!("control.err.gpu_governor_path")))
     }
 
-    pub fn set_gpu_railgate(&mut self, mode: &str) -> Result<()> {
+    pub fn set_gpu_railgate(&mut self, mode: RailgateMode) -> Result<()> {
         if !self.status.available {
-            return Err(anyhow!("No es Jetson (demo)"));
+            return Err(anyhow!(t!("control.err.not_jetson")));
         }
         if !self.status.supports_gpu_railgate {
-            return Err(anyhow!("Control de GPU railgate no soportado"));
+            return Err(anyhow!(t!("control.err.no_railgate")));
         }
-        let target = match mode {
-            "auto" => "auto",
-            "on" => "on",
-            _ => return Err(anyhow!("Modo inválido: {} (auto|on)", mode)),
-        };
         if self.mock {
-            self.status.gpu_railgate = Some(target == "auto");
+            self.write_sysfs(Path::new("/sys/devices/<gpu>/power/control"), mode.as_str())?;
+            self.status.gpu_railgate = Some(mode == RailgateMode::Auto);
             self.status.last_error = None;
             return Ok(());
         }
         if let Some(path) = gpu_power_control_path() {
-            std::fs::write(&path, target).with_context(|| format!("escribiendo {:?}", path))?;
-            self.status.gpu_railgate = Some(target == "auto");
+            self.write_sysfs(&path, mode.as_str())?;
+            self.status.gpu_railgate = Some(mode == RailgateMode::Auto);
             self.status.last_error = None;
             return Ok(());
         }
-        Err(anyhow!("No se pudo ajustar railgate (sin ruta power/control)"))
+        Err(anyhow!(t!("control.err.railgate_path")))
+    }
+
+    /// Set one fan (`fan<index>`) to a fixed speed; this drops its curve.
+    pub fn set_fan_speed(&mut self, index: usize, percent: u8) -> Result<()> {
+        if percent > 100 {
+            return Err(invalid(t!("control.err.fan_range", value = percent)));
+        }
+        self.fan_curves.remove(&index);
+        self.write_fan(index, percent)?;
+        if let Some(state) = self.status.fans.get_mut(index) {
+            state.curve = None;
+        }
+        self.status.last_error = None;
+        Ok(())
+    }
+
+    /// Drive a fan from the hottest sensor with a "temp:pct,..." curve; "off" leaves it where it is.
+    pub fn set_fan_curve(&mut self, index: usize, spec: &str) -> Result<()> {
+        let curve = match spec {
+            "off" | "" => None,
+            _ => Some(FanCurve::parse(spec).map_err(invalid)?),
+        };
+        if index >= self.hardware.fans.len() {
+            return Err(anyhow!(t!("control.err.no_such_fan", index = index)));
+        }
+        let curve = match curve {
+            None => {
+                self.fan_curves.remove(&index);
+                None
+            }
+            Some(curve) => {
+                self.fan_curves.insert(index, curve);
+                Some(spec.to_string())
+            }
+        };
+        if let Some(state) = self.status.fans.get_mut(index) {
+            state.curve = curve;
+        }
+        self.status.last_error = None;
+        Ok(())
+    }
+
+    fn write_fan(&mut self, index: usize, percent: u8) -> Result<()> {
+        let fan = self
+            .hardware
+            .fans
+            .get(index)
+            .cloned()
+            .ok_or_else(|| anyhow!(t!("control.err.no_such_fan", index = index)))?;
+        let pwm = fans::percent_to_pwm(percent);
+        self.effect(format!("echo {pwm} > {}", fan.pwm_path.display()), || fan.set_percent(percent))?;
+        if let Some(state) = self.status.fans.get_mut(index) {
+            state.pwm = Some(pwm);
+        }
+        Ok(())
+    }
+
+    /// Re-read fan speeds and move curve-driven fans to the speed for the hottest sensor.
+    fn update_fans(&mut self, stats: &TegraStats) {
+        if !self.mock {
+            for (fan, state) in self.hardware.fans.iter().zip(self.status.fans.iter_mut()) {
+                let fresh = fan.read();
+                state.rpm = fresh.rpm;
+                state.pwm = fresh.pwm;
+            }
+        }
+        // -256C marks an offline sensor
+        let Some(hottest) = stats
+            .temps
+            .values()
+            .copied()
+            .filter(|t| valid_temp(*t))
+            .reduce(f32::max)
+        else {
+            return;
+        };
+        if let Some(percent) = self.fan_curve.as_ref().map(|c| c.percent_at(hottest)) {
+            if self.status.fan.as_deref().map(|f| f.trim_end_matches('%')) != Some(&percent.to_string()) {
+                self.write_legacy_fan(percent);
+            }
+        }
+        let targets: Vec<(usize, u8)> = self
+            .fan_curves
+            .iter()
+            .map(|(index, curve)| (*index, curve.percent_at(hottest)))
+            .collect();
+        for (index, percent) in targets {
+            let current = self.status.fans.get(index).and_then(|s| s.percent());
+            if current == Some(percent) {
+                continue;
+            }
+            if let Err(e) = self.write_fan(index, percent) {
+                self.status.last_error = Some(t!("control.err.fan_curve", index = index, error = e));
+            }
+        }
+    }
+
+    /// Cap a devfreq domain ("gpu_max_freq") at one of its steps, given in MHz or Hz; "max" lifts it.
+    ///
+    /// The GPU step is also written by the power/thermal caps while those are active.
+    pub fn set_max_freq(&mut self, control: &str, value: &str) -> Result<()> {
+        let caps = self
+            .devfreq_caps(control)
+            .ok_or_else(|| anyhow!(t!("control.err.unknown_control")))?;
+        let hz = if value == "max" {
+            caps.max_hz
+        } else {
+            let requested: u64 = value
+                .parse()
+                .map_err(|_| invalid(t!("control.err.freq_value", control = control)))?;
+            *caps
+                .available_hz
+                .iter()
+                .find(|hz| **hz == requested || **hz / 1_000_000 == requested)
+                .ok_or_else(|| {
+                    let mhz: Vec<u64> = caps.available_hz.iter().map(|hz| hz / 1_000_000).collect();
+                    anyhow!(t!(
+                        "control.err.freq",
+                        value = value,
+                        available = format!("{:?}", mhz)
+                    ))
+                })?
+        };
+        let path = PathBuf::from("/sys/class/devfreq")
+            .join(&caps.device)
+            .join("max_freq");
+        let domain = caps.domain.clone();
+        self.write_sysfs(&path, &hz.to_string())?;
+        self.status.max_freqs.insert(domain, hz);
+        self.status.last_error = None;
+        Ok(())
+    }
+
+    /// Pin the CPU floor ("cpu_min_freq") or ceiling ("cpu_max_freq") on every core at
+    /// one of the cpufreq steps, given in MHz or kHz; "min"/"max" pick the lowest/highest.
+    ///
+    /// Both bounds are kept in the clock table, so the power/thermal caps only ever
+    /// lower the ceiling further and never below the floor.
+    pub fn set_cpu_freq_limit(&mut self, control: &str, value: &str) -> Result<()> {
+        let requested: Option<u64> = match value {
+            "min" | "max" => None,
+            _ => Some(
+                value
+                    .parse()
+                    .map_err(|_| invalid(t!("control.err.cpu_freq_value", control = control)))?,
+            ),
+        };
+        if !self.status.available {
+            return Err(anyhow!(t!("control.err.not_jetson")));
+        }
+        let (Some(&lowest), Some(&highest)) = (self.clocks.cpu_khz.first(), self.clocks.cpu_khz.last()) else {
+            return Err(anyhow!(t!("control.err.no_cpu_freq")));
+        };
+        let khz = match requested {
+            None if value == "min" => lowest,
+            None => highest,
+            Some(requested) => {
+                *self
+                    .clocks
+                    .cpu_khz
+                    .iter()
+                    .find(|khz| **khz == requested || **khz / 1000 == requested)
+                    .ok_or_else(|| {
+                        let mhz: Vec<u64> = self.clocks.cpu_khz.iter().map(|khz| khz / 1000).collect();
+                        anyhow!(t!(
+                            "control.err.freq",
+                            value = value,
+                            available = format!("{:?}", mhz)
+                        ))
+                    })?
+            }
+        };
+        let ceiling = control == "cpu_max_freq";
+        // Compare against the user's bounds: the live ceiling may be lowered by a cap
+        let (min, max) = if ceiling {
+            (self.clocks.cpu_floor_khz.or(self.status.cpu_min_freq_khz), khz)
+        } else {
+            (Some(khz), self.clocks.cpu_ceiling_khz.unwrap_or(highest))
+        };
+        if let Some(min) = min {
+            if min > max {
+                return Err(anyhow!(t!(
+                    "control.err.cpu_freq_order",
+                    min = min / 1000,
+                    max = max / 1000
+                )));
+            }
+        }
+        if ceiling {
+            self.clocks.cpu_ceiling_khz = Some(khz);
+        } else {
+            self.clocks.cpu_floor_khz = Some(khz);
+        }
+        // The ceiling actually written: the user's, lowered by any active cap but
+        // never under the floor
+        let max = self.clocks.caps(self.status.clock_cap.level).0.unwrap_or(khz);
+        if ceiling {
+            self.write_cpu_freq("scaling_max_freq", max)?;
+        } else {
+            let rewrite_max = self.status.cpu_max_freq_khz != Some(max);
+            // The kernel refuses a floor above the ceiling, so raise the ceiling first
+            if rewrite_max && self.status.cpu_max_freq_khz.is_some_and(|cur| khz > cur) {
+                self.write_cpu_freq("scaling_max_freq", max)?;
+                self.write_cpu_freq("scaling_min_freq", khz)?;
+            } else {
+                self.write_cpu_freq("scaling_min_freq", khz)?;
+                if rewrite_max {
+                    self.write_cpu_freq("scaling_max_freq", max)?;
+                }
+            }
+            self.status.cpu_min_freq_khz = Some(khz);
+        }
+        self.status.cpu_max_freq_khz = Some(max);
+        self.status.last_error = None;
+        Ok(())
+    }
+
+    /// Write a cpufreq limit (`scaling_min_freq`/`scaling_max_freq`) on every core.
+    fn write_cpu_freq(&mut self, file: &str, khz: u64) -> Result<()> {
+        if self.mock {
+            let path = PathBuf::from("/sys/devices/system/cpu/cpu*/cpufreq").join(file);
+            return self.write_sysfs(&path, &khz.to_string());
+        }
+        let mut wrote_any = false;
+        for path in cpu_paths() {
+            let path = path.join("cpufreq").join(file);
+            if path.exists() {
+                self.write_sysfs(&path, &khz.to_string())?;
+                wrote_any = true;
+            }
+        }
+        if !wrote_any {
+            return Err(anyhow!(t!("control.err.no_cpu_freq")));
+        }
+        Ok(())
+    }
+
+    /// Enable ("<watts>") or disable ("off") the closed-loop input power cap.
+    pub fn set_power_cap(&mut self, value: &str) -> Result<()> {
+        let off = matches!(value, "off" | "0" | "");
+        // A malformed value is reported as such, even where there is nothing to cap
+        let watts: f32 = if off {
+            0.0
+        } else {
+            value
+                .parse()
+                .map_err(|_| invalid(t!("control.err.power_cap_value")))?
+        };
+        if !off && !(1.0..=100.0).contains(&watts) {
+            return Err(invalid(t!("control.err.power_cap_range", value = watts)));
+        }
+        if self.clocks.is_empty() {
+            return Err(anyhow!(t!("control.err.no_power_cap")));
+        }
+        if off {
+            self.power_cap = None;
+            self.status.clock_cap.power_cap_watts = None;
+            self.status.clock_cap.power_avg_mw = None;
+            return self.request_clock_cap("power_cap", 0);
+        }
+        // Keep the current throttle level when only the cap value changes
+        match self.power_cap.as_mut() {
+            Some(ctl) => ctl.set_cap_watts(watts),
+            None => {
+                self.power_cap = Some(PowerCapController::new(
+                    watts,
+                    self.clocks.max_level(),
+                    power_cap::window_from_env(),
+                ))
+            }
+        }
+        self.status.clock_cap.power_cap_watts = Some(watts);
+        self.status.last_error = None;
+        Ok(())
+    }
+
+    /// Enforce a board power budget (mW) for the life of the manager. It throttles like
+    /// `power_cap_watts` but is a separate request, so changing or clearing that
+    /// control leaves the budget in force.
+    #[allow(dead_code)] // jscoped
+    pub fn set_power_budget(&mut self, budget_mw: u32) -> Result<()> {
+        if self.clocks.is_empty() {
+            return Err(anyhow!(t!("control.err.no_power_cap")));
+        }
+        self.power_budget = Some(PowerCapController::new(
+            budget_mw as f32 / 1000.0,
+            self.clocks.max_level(),
+            power_cap::window_from_env(),
+        ));
+        self.status.clock_cap.power_budget_mw = Some(budget_mw);
+        Ok(())
+    }
+
+    /// Enable ("<celsius>") or disable ("off") the thermal governor.
+    pub fn set_thermal_target(&mut self, value: &str) -> Result<()> {
+        let off = matches!(value, "off" | "0" | "");
+        let target: f32 = if off {
+            0.0
+        } else {
+            value
+                .parse()
+                .map_err(|_| invalid(t!("control.err.thermal_value")))?
+        };
+        if !off && !(40.0..=105.0).contains(&target) {
+            return Err(invalid(t!("control.err.thermal_range", value = target)));
+        }
+        if self.clocks.is_empty() {
+            return Err(anyhow!(t!("control.err.no_thermal")));
+        }
+        if off {
+            self.thermal = None;
+            self.status.clock_cap.thermal_target_c = None;
+            self.status.clock_cap.thermal_temp_c = None;
+            return self.request_clock_cap("thermal", 0);
+        }
+        match self.thermal.as_mut() {
+            Some(gov) => gov.set_target_c(target),
+            None => {
+                self.thermal = Some(ThermalGovernor::new(
+                    target,
+                    thermal_governor::sensor_from_env(),
+                    self.clocks.max_level(),
+                ))
+            }
+        }
+        self.status.clock_cap.thermal_target_c = Some(target);
+        self.status.last_error = None;
+        Ok(())
+    }
+
+    /// Enable idle detection: switch to `config.profile` after sustained low activity.
+    #[allow(dead_code)] // jscoped
+    pub fn configure_idle(&mut self, config: IdleConfig) {
+        self.status.idle = IdleStatus {
+            enabled: true,
+            profile: Some(config.describe()),
+            ..Default::default()
+        };
+        self.idle = Some(IdleDetector::new(config));
+    }
+
+    /// Enable the thermal guard: step nvpmodel down, cap the clocks and max the fans
+    /// while a sensor stays above `config.critical_c`, then put them back.
+    #[allow(dead_code)] // jscoped
+    pub fn configure_thermal_guard(&mut self, config: GuardConfig) {
+        self.status.guard = GuardStatus {
+            enabled: true,
+            critical_c: Some(config.critical_c),
+            recover_c: Some(config.recover_c),
+            ..Default::default()
+        };
+        self.guard = Some(ThermalGuard::new(config));
+    }
+
+    /// Guard trips, recoveries and failures since the last call.
+    #[allow(dead_code)] // jscoped
+    pub fn take_guard_events(&mut self) -> Vec<GuardEvent> {
+        std::mem::take(&mut self.guard_events)
+    }
+
+    /// Drive the automatic controllers from a new stats sample.
+    #[allow(dead_code)] // jscoped
+    pub fn on_stats(&mut self, stats: &TegraStats) {
+        if let Some(input_mw) = power_cap::input_power_mw(stats) {
+            let now_ms = crate::history::now_unix_ms();
+            if let Some(ctl) = self.power_cap.as_mut() {
+                let level = ctl.update(input_mw, now_ms);
+                self.status.clock_cap.power_avg_mw = ctl.average_mw();
+                if let Err(e) = self.request_clock_cap("power_cap", level) {
+                    self.status.last_error = Some(e.to_string());
+                }
+            }
+            if let Some(ctl) = self.power_budget.as_mut() {
+                let level = ctl.update(input_mw, now_ms);
+                if let Err(e) = self.request_clock_cap("power_budget", level) {
+                    self.status.last_error = Some(e.to_string());
+                }
+            }
+        }
+
+        if let Some(gov) = self.thermal.as_mut() {
+            let temp = gov.read_temp(stats);
+            self.status.clock_cap.thermal_temp_c = temp;
+            if let Some(temp) = temp {
+                let level = gov.update(temp);
+                if let Err(e) = self.request_clock_cap("thermal", level) {
+                    self.status.last_error = Some(e.to_string());
+                }
+            }
+        }
+
+        let now = std::time::Instant::now();
+        self.update_guard(stats, now);
+        self.update_fans(stats);
+
+        let Some(det) = self.idle.as_mut() else {
+            return;
+        };
+        let transition = det.update(stats, now);
+        self.status.idle.quiet_secs = det.quiet_for(now).as_secs();
+        self.status.idle.idle = det.is_idle();
+        match transition {
+            Some(IdleTransition::EnterIdle) => {
+                let profile = det.config().profile.clone();
+                for (name, value) in profile {
+                    let previous = self.restore_value(&name);
+                    match self.apply_control(&name, &value) {
+                        Ok(_) => self.idle_restore.push(previous),
+                        Err(e) => self.status.last_error = Some(t!("control.err.idle", control = name, error = e)),
+                    }
+                }
+            }
+            Some(IdleTransition::ExitIdle) => self.restore_idle(),
+            None => {}
+        }
+    }
+
+    /// Put back what the idle profile changed.
+    fn restore_idle(&mut self) {
+        let restore = std::mem::take(&mut self.idle_restore);
+        for (name, value) in restore.into_iter().rev() {
+            if let Err(e) = self.apply_control(&name, &value) {
+                self.status.last_error = Some(t!("control.err.idle", control = name, error = e));
+            }
+        }
+    }
+
+    /// Undo an idle profile, a guard trip and every clock cap still in effect
+    /// (power budget, power cap, thermal target); jscoped calls this before it exits.
+    #[allow(dead_code)] // jscoped
+    pub fn release_automation(&mut self) {
+        self.restore_idle();
+        self.status.idle.idle = false;
+        if let Some(guard) = self.guard.as_mut().filter(|g| g.is_tripped()) {
+            guard.reset();
+            self.status.guard.tripped = false;
+            let restored = self.restore_guard();
+            let temp_c = self.status.guard.temp_c.unwrap_or_default();
+            self.guard_events.push(GuardEvent::Recovered { temp_c, restored });
+        }
+        for source in self.caps.sources() {
+            if let Err(e) = self.request_clock_cap(&source, 0) {
+                self.status.last_error = Some(e.to_string());
+            }
+        }
+    }
+
+    fn update_guard(&mut self, stats: &TegraStats, now: std::time::Instant) {
+        let Some(guard) = self.guard.as_mut() else {
+            return;
+        };
+        let temp = guard.read_temp(stats);
+        let transition = guard.update(temp, now);
+        self.status.guard.temp_c = temp;
+        self.status.guard.tripped = guard.is_tripped();
+        let temp_c = temp.unwrap_or_default();
+        match transition {
+            Some(GuardTransition::Trip) => {
+                let mut actions = Vec::new();
+                for (name, value) in self.guard_actions() {
+                    let previous = self.restore_value(&name);
+                    match self.apply_control(&name, &value) {
+                        Ok(_) => {
+                            self.guard_restore.push(previous);
+                            actions.push((name, value));
+                        }
+                        Err(e) => self.guard_failed(name, e),
+                    }
+                }
+                // The lowest clock steps, through the arbiter like the power and thermal caps
+                if !self.clocks.is_empty() {
+                    let level = self.clocks.max_level();
+                    match self.request_clock_cap("guard", level) {
+                        Ok(()) => actions.push(("clock_cap".to_string(), self.clocks.describe(level))),
+                        Err(e) => self.guard_failed("clock_cap".to_string(), ControlError::Failed(e)),
+                    }
+                }
+                self.status.guard.trips += 1;
+                self.status.guard.actions = actions.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+                self.guard_events.push(GuardEvent::Tripped { temp_c, actions });
+            }
+            Some(GuardTransition::Recover) => {
+                let restored = self.restore_guard();
+                self.guard_events.push(GuardEvent::Recovered { temp_c, restored });
+            }
+            None => {}
+        }
+    }
+
+    /// Lift the guard's clock cap and put back what its trip changed.
+    fn restore_guard(&mut self) -> Vec<(String, String)> {
+        let mut restored = Vec::new();
+        if let Err(e) = self.request_clock_cap("guard", 0) {
+            self.guard_failed("clock_cap".to_string(), ControlError::Failed(e));
+        }
+        for (name, value) in std::mem::take(&mut self.guard_restore).into_iter().rev() {
+            match self.apply_control(&name, &value) {
+                Ok(_) => restored.push((name, value)),
+                Err(e) => self.guard_failed(name, e),
+            }
+        }
+        self.status.guard.actions.clear();
+        restored
+    }
+
+    fn guard_failed(&mut self, control: String, error: ControlError) {
+        self.status.last_error = Some(t!("control.err.guard", control = control, error = error));
+        self.guard_events.push(GuardEvent::Failed {
+            control,
+            error: error.to_string(),
+        });
+    }
+
+    /// What a guard trip changes besides the clock cap: one nvpmodel mode down
+    /// (modes are listed from the most powerful) and every fan at 100%.
+    fn guard_actions(&self) -> Vec<(String, String)> {
+        let mut actions = Vec::new();
+        if self.status.supports_nvpmodel {
+            let modes = &self.status.nvpmodel_modes;
+            let lower = self
+                .status
+                .nvpmodel
+                .as_ref()
+                .and_then(|current| modes.iter().position(|m| m == current))
+                .and_then(|i| modes.get(i + 1));
+            if let Some(mode) = lower {
+                actions.push((Control::NVPMODEL.to_string(), mode.clone()));
+            }
+        }
+        if self.status.supports_fan {
+            actions.push((Control::FAN.to_string(), "100".to_string()));
+        }
+        // A fixed speed drops the fan's curve until the trip is restored
+        for fan in &self.hardware.fans {
+            actions.push((fan.control_name(), "100".to_string()));
+        }
+        actions
+    }
+
+    /// (control, value) that puts `name` back the way it is now.
+    fn restore_value(&self, name: &str) -> (String, String) {
+        let info = self.control_info(name);
+        // Values are reported with their unit ("60%"); setters take the bare number
+        let previous = match &info.unit {
+            Some(unit) => info.value.trim_end_matches(unit.as_str()).to_string(),
+            None => info.value,
+        };
+        let fan_curve = match fan_control(name) {
+            Some((index, false)) => self.status.fans.get(index).and_then(|f| f.curve.as_ref()),
+            _ => None,
+        };
+        match (name, &self.status.fan_curve) {
+            // A curve-driven fan goes back to its curve, not to the speed it had
+            ("fan", Some(curve)) => ("fan_curve".to_string(), curve.clone()),
+            (_, _) if fan_curve.is_some() => (format!("{name}_curve"), fan_curve.cloned().unwrap_or_default()),
+            // An unread devfreq cap was never lowered
+            (_, _) if self.devfreq_caps(name).is_some() && previous == "unknown" => {
+                (name.to_string(), "max".to_string())
+            }
+            _ => (name.to_string(), previous),
+        }
+    }
+
+    /// Record a throttle request from `source` and apply the most restrictive one.
+    fn request_clock_cap(&mut self, source: &str, level: usize) -> Result<()> {
+        let effective = self.caps.request(source, level);
+        if effective == self.status.clock_cap.level && self.status.clock_cap.applied.is_some() {
+            return Ok(());
+        }
+        match self.plan.as_mut() {
+            Some(plan) => plan.push(format!("cap clocks: {}", self.clocks.describe(effective))),
+            None if self.mock => {
+                self.status.cpu_max_freq_khz = self.clocks.caps(effective).0;
+            }
+            None => {
+                self.clocks.apply(effective)?;
+                // Report the limits the kernel actually took
+                let (min, max) = detect_cpu_freq_limits();
+                if max.is_some() {
+                    self.status.cpu_min_freq_khz = min;
+                    self.status.cpu_max_freq_khz = max;
+                }
+            }
+        }
+        self.status.clock_cap.level = effective;
+        self.status.clock_cap.applied = Some(self.clocks.describe(effective));
+        Ok(())
     }
 }
 
@@ -694,10 +1859,10 @@ fn run_jetson_clocks_toggle() -> Result<bool> {
         Command::new("jetson_clocks")
             .arg(target)
             .output()
-            .context("ejecutando jetson_clocks toggle")?;
+            .with_context(|| t!("control.err.running", command = "jetson_clocks"))?;
         return Ok(!state);
     }
-    Err(anyhow!("No se pudo leer estado jetson_clocks"))
+    Err(anyhow!(t!("control.err.jetson_clocks_state")))
 }
 
 #[allow(dead_code)]
@@ -706,11 +1871,11 @@ fn run_jetson_clocks_set(on: bool) -> Result<()> {
     let output = Command::new("jetson_clocks")
         .arg(arg)
         .output()
-        .context("ejecutando jetson_clocks")?;
+        .with_context(|| t!("control.err.running", command = "jetson_clocks"))?;
     if output.status.success() {
         Ok(())
     } else {
-        Err(anyhow!("jetson_clocks {} falló", arg))
+        Err(anyhow!(t!("control.err.failed", command = format!("jetson_clocks {}", arg))))
     }
 }
 
@@ -719,31 +1884,92 @@ fn set_nvpmodel(mode: &str) -> Result<()> {
         .arg("-m")
         .arg(mode)
         .output()
-        .context("ejecutando nvpmodel -m")?;
+        .with_context(|| t!("control.err.running", command = "nvpmodel -m"))?;
     if output.status.success() {
         Ok(())
     } else {
-        Err(anyhow!("nvpmodel -m {} falló", mode))
+        Err(anyhow!(t!("control.err.failed", command = format!("nvpmodel -m {}", mode))))
     }
 }
 
 fn set_fan_percent(percent: u8) -> Result<()> {
         if percent > 100 {
-        return Err(anyhow!("valor de fan inválido (0-100)"));
+        return Err(anyhow!(t!("control.err.fan_range", value = percent)));
     }
     if which::which("jetson_fan").is_ok() {
         let output = Command::new("jetson_fan")
             .arg("--set")
             .arg(percent.to_string())
             .output()
-            .context("ejecutando jetson_fan --set")?;
+            .with_context(|| t!("control.err.running", command = "jetson_fan --set"))?;
         if output.status.success() {
             return Ok(());
         }
     }
-    Err(anyhow!(
-        "No se pudo ajustar fan (requiere utilidades en Jetson)"
-    ))
+    Err(anyhow!(t!("control.err.fan_tool")))
+}
+
+/// Initial fan readings; mock mode reports every fan stopped.
+fn fan_states(hardware: &JetsonHardware, mock: bool) -> Vec<FanState> {
+    hardware
+        .fans
+        .iter()
+        .map(|fan| {
+            if mock {
+                FanState {
+                    index: fan.index,
+                    name: fan.name.clone(),
+                    pwm: Some(0),
+                    ..Default::default()
+                }
+            } else {
+                fan.read()
+            }
+        })
+        .collect()
+}
+
+/// "off", the presets and an example spec.
+fn curve_options() -> Vec<String> {
+    std::iter::once("off")
+        .chain(fans::PRESETS.iter().map(|(name, _)| *name))
+        .chain(std::iter::once("40:20,60:50,80:100"))
+        .map(String::from)
+        .collect()
+}
+
+/// "fan1" -> (1, false), "fan1_curve" -> (1, true); the legacy "fan" control is not one of these.
+fn fan_control(name: &str) -> Option<(usize, bool)> {
+    let rest = name.strip_prefix("fan")?;
+    let (index, curve) = match rest.strip_suffix("_curve") {
+        Some(index) => (index, true),
+        None => (rest, false),
+    };
+    if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((index.parse().ok()?, curve))
+}
+
+/// devfreq max_freq per domain; mock mode starts every domain at its top step.
+fn frequency_maxima(frequencies: &[FrequencyCaps], mock: bool) -> BTreeMap<String, u64> {
+    frequencies
+        .iter()
+        .filter_map(|caps| {
+            let hz = if mock {
+                Some(caps.max_hz)
+            } else {
+                std::fs::read_to_string(
+                    PathBuf::from("/sys/class/devfreq")
+                        .join(&caps.device)
+                        .join("max_freq"),
+                )
+                .ok()
+                .and_then(|s| s.trim().parse().ok())
+            };
+            Some((caps.domain.clone(), hz?))
+        })
+        .collect()
 }
 
 fn detect_gpu_governors() -> (Vec<String>, Option<String>) {
@@ -772,7 +1998,7 @@ fn detect_gpu_railgate() -> Option<bool> {
     None
 }
 
-fn gpu_devfreq_path() -> Option<PathBuf> {
+pub(crate) fn gpu_devfreq_path() -> Option<PathBuf> {
     let candidates = vec![
         "/sys/devices/17000000.gv11b/devfreq/17000000.gv11b",
         "/sys/devices/17000000.gp10b/devfreq/17000000.gp10b",
@@ -786,7 +2012,7 @@ fn gpu_devfreq_path() -> Option<PathBuf> {
     None
 }
 
-fn gpu_power_control_path() -> Option<PathBuf> {
+pub(crate) fn gpu_power_control_path() -> Option<PathBuf> {
     let candidates = vec![
         "/sys/devices/17000000.gv11b/power/control",
         "/sys/devices/17000000.gp10b/power/control",
@@ -815,6 +2041,21 @@ fn detect_cpu_governors() -> Vec<String> {
     govs
 }
 
+/// (scaling_min_freq, scaling_max_freq) in kHz of the first core that has them.
+fn detect_cpu_freq_limits() -> (Option<u64>, Option<u64>) {
+    let read = |path: PathBuf| {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+    };
+    cpu_paths()
+        .into_iter()
+        .map(|p| p.join("cpufreq"))
+        .find(|p| p.join("scaling_max_freq").exists())
+        .map(|p| (read(p.join("scaling_min_freq")), read(p.join("scaling_max_freq"))))
+        .unwrap_or((None, None))
+}
+
 fn detect_current_cpu_governor() -> Option<String> {
     for path in cpu_paths() {
         let gov = path.join("cpufreq/scaling_governor");
@@ -828,7 +2069,7 @@ fn detect_current_cpu_governor() -> Option<String> {
     None
 }
 
-fn cpu_paths() -> Vec<std::path::PathBuf> {
+pub(crate) fn cpu_paths() -> Vec<std::path::PathBuf> {
     let mut paths = Vec::new();
     if let Ok(entries) = std::fs::read_dir("/sys/devices/system/cpu") {
         for entry in entries.flatten() {
@@ -927,13 +2168,123 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_control_errors_are_classified() {
+        let mut mgr = ControlManager::mock(JetsonHardware::default());
+        let kind = |r: Result<ControlInfo, ControlError>| match r {
+            Err(ControlError::Unknown(_)) => "unknown",
+            Err(ControlError::Invalid(_)) => "invalid",
+            Err(ControlError::Failed(_)) => "failed",
+            Ok(_) => "ok",
+        };
+        assert_eq!(kind(mgr.apply_control("warp_drive", "on")), "unknown");
+        assert_eq!(kind(mgr.apply_control("emc_max_freq", "max")), "unknown");
+        assert_eq!(kind(mgr.apply_control("power_cap_watts", "abc")), "invalid");
+        assert_eq!(kind(mgr.apply_control("fan1", "x")), "invalid");
+        assert_eq!(kind(mgr.apply_control("fan", "101")), "invalid");
+        assert_eq!(kind(mgr.apply_control("cpu_max_freq", "fast")), "invalid");
+        assert_eq!(kind(mgr.apply_control("cpu_max_freq", "1000")), "failed");
+        assert_eq!(kind(mgr.apply_control("nvpmodel", "MODE_9")), "failed");
+        assert!(matches!(mgr.plan_control("thermal_target_c", "hot"), Err(ControlError::Invalid(_))));
+        // Stored profiles only need a known control and a well-formed value
+        assert!(mgr.check_control("fan1", "60").is_ok());
+        assert!(mgr.check_control("fan1", "x").is_err());
+        assert!(mgr.check_control("warp_drive", "on").is_err());
+    }
+
+    #[test]
+    fn test_control_parse_and_apply() {
+        for (name, value, control) in [
+            ("jetson_clocks", "on", Control::JetsonClocks(true)),
+            ("nvpmodel", "MODE_1", Control::Nvpmodel("MODE_1".into())),
+            ("fan", "80", Control::FanPercent(80)),
+            ("cpu_governor", "performance", Control::CpuGovernor("performance".into())),
+            ("gpu_governor", "performance", Control::GpuGovernor("performance".into())),
+            ("gpu_railgate", "on", Control::GpuRailgate(RailgateMode::On)),
+        ] {
+            assert_eq!(Control::parse(name, value).unwrap().unwrap(), control);
+            assert_eq!((control.name(), control.value().as_str()), (name, value));
+        }
+        assert!(Control::parse("fan", "101").unwrap().is_err());
+        assert!(Control::parse("gpu_railgate", "off").unwrap().is_err());
+        assert!(Control::parse("fan0", "50").is_none());
+
+        let mut mgr = ControlManager::mock(JetsonHardware::default());
+        mgr.apply(Control::JetsonClocks(true)).unwrap();
+        mgr.apply(Control::GpuRailgate(RailgateMode::On)).unwrap();
+        assert_eq!(mgr.status().jetson_clocks, Some(true));
+        assert_eq!(mgr.status().gpu_railgate, Some(false));
+        assert_eq!(mgr.apply_control("jetson_clocks", "toggle").unwrap().value, "off");
+    }
+
+    #[test]
+    fn test_plan_control_changes_nothing() {
+        let mgr = ControlManager::mock(JetsonHardware::default());
+        assert_eq!(mgr.plan_control("jetson_clocks", "on").unwrap(), ["jetson_clocks --on"]);
+        assert_eq!(mgr.plan_control("nvpmodel", "MODE_1").unwrap(), ["nvpmodel -m MODE_1"]);
+        assert_eq!(mgr.plan_control("fan", "80").unwrap(), ["jetson_fan --set 80"]);
+        assert!(mgr.plan_control("fan", "abc").is_err());
+        assert!(mgr.plan_control("nvpmodel", "MODE_9").is_err());
+        assert_eq!(mgr.status().jetson_clocks, Some(false));
+        assert_eq!(mgr.status().nvpmodel.as_deref(), Some("MODE_0"));
+        assert_eq!(mgr.status().fan.as_deref(), Some("0%"));
+    }
+
+    #[test]
+    fn test_cpu_freq_limits() {
+        let mut mgr = ControlManager::mock(JetsonHardware::default());
+        let ceiling = mgr.control_info("cpu_max_freq");
+        assert_eq!(ceiling.value, "1728");
+        assert_eq!(ceiling.options, ["729", "1190", "1497", "1728"]);
+        assert_eq!(
+            mgr.plan_control("cpu_max_freq", "1190").unwrap(),
+            ["echo 1190400 > /sys/devices/system/cpu/cpu*/cpufreq/scaling_max_freq"]
+        );
+        assert_eq!(mgr.apply_control("cpu_max_freq", "1497600").unwrap().value, "1497");
+        assert_eq!(mgr.apply_control("cpu_min_freq", "1190").unwrap().value, "1190");
+        // Floor above the ceiling, off-table and malformed values are refused
+        assert!(mgr.apply_control("cpu_min_freq", "max").is_err());
+        assert!(mgr.apply_control("cpu_max_freq", "729").is_err());
+        assert!(mgr.apply_control("cpu_max_freq", "1000").is_err());
+        assert!(mgr.apply_control("cpu_max_freq", "fast").is_err());
+        assert_eq!(mgr.status().cpu_max_freq_khz, Some(1_497_600));
+        assert_eq!(mgr.apply_control("cpu_min_freq", "min").unwrap().value, "729");
+    }
+
+    #[test]
+    fn test_thermal_guard_acts_and_restores() {
+        let mut mgr = ControlManager::mock(JetsonHardware::default());
+        mgr.configure_thermal_guard(GuardConfig {
+            critical_c: 95.0,
+            recover_c: 85.0,
+            hold: std::time::Duration::ZERO,
+            sensor: None,
+        });
+        let mut stats = TegraStats::default();
+        stats.temps.insert("tj".into(), 97.0);
+        mgr.on_stats(&stats);
+        assert!(mgr.status().guard.tripped);
+        assert_eq!(mgr.status().nvpmodel.as_deref(), Some("MODE_1"));
+        assert_eq!(mgr.status().fan.as_deref(), Some("100%"));
+        assert_eq!(mgr.status().clock_cap.level, 3);
+        assert!(matches!(&mgr.take_guard_events()[..], [GuardEvent::Tripped { actions, .. }] if actions.len() == 3));
+
+        stats.temps.insert("tj".into(), 80.0);
+        mgr.on_stats(&stats);
+        assert!(!mgr.status().guard.tripped);
+        assert_eq!(mgr.status().nvpmodel.as_deref(), Some("MODE_0"));
+        assert_eq!(mgr.status().fan.as_deref(), Some("0%"));
+        assert_eq!(mgr.status().clock_cap.level, 0);
+        assert_eq!(mgr.status().guard.trips, 1);
+    }
+
     #[test]
     fn test_no_op_on_non_jetson() {
         let mgr = ControlManager::new();
         
         if !mgr.status().available {
             // On non-Jetson, all controls should be unavailable
-            assert_eq!(mgr.status().note, "Host no Jetson: modo demo");
+            assert_eq!(mgr.status().note, t!("control.note.not_jetson"));
             assert!(!mgr.status().supports_jetson_clocks);
             assert!(!mgr.status().supports_nvpmodel);
             assert!(!mgr.status().supports_fan);
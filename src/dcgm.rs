@@ -0,0 +1,203 @@
+//! DCGM exporter compatible metric names for the `/metrics` endpoint.
+//!
+//! dcgm-exporter publishes `DCGM_FI_*` gauges (one per DCGM field id) labelled with
+//! `gpu`, `UUID`, `device`, `modelName` and `Hostname`; Kubernetes GPU dashboards are
+//! built on those names. Jetson has a single integrated GPU, so it is always `gpu="0"`,
+//! and the framebuffer fields report the shared system RAM.
+
+use crate::export;
+use crate::hardware::JetsonHardware;
+use crate::parser::TegraStats;
+
+/// DCGM-style output settings (JETSONSCOPE_METRICS_DCGM).
+#[derive(Debug, Clone)]
+pub struct DcgmConfig {
+    /// Emit only the DCGM families, without the native `jetsonscope_*` ones
+    pub only: bool,
+    pub uuid: String,
+    pub model_name: String,
+    pub hostname: String,
+}
+
+impl DcgmConfig {
+    /// `JETSONSCOPE_METRICS_DCGM=1` adds DCGM families next to the native ones,
+    /// `JETSONSCOPE_METRICS_DCGM=only` replaces them.
+    pub fn from_env(hw: &JetsonHardware) -> Option<Self> {
+        let mode = std::env::var("JETSONSCOPE_METRICS_DCGM").ok()?;
+        let only = match mode.trim().to_ascii_lowercase().as_str() {
+            "only" => true,
+            "1" | "true" | "yes" | "on" => false,
+            _ => return None,
+        };
+        Some(Self::new(hw, &export::device_name(), only))
+    }
+
+    pub fn new(hw: &JetsonHardware, hostname: &str, only: bool) -> Self {
+        let model_name = if hw.model.is_empty() {
+            "Jetson GPU".to_string()
+        } else {
+            hw.model.clone()
+        };
+        // Stable across restarts: derived from the module serial, else the hostname
+        let seed = if hw.serial_number.is_empty() {
+            hostname
+        } else {
+            hw.serial_number.as_str()
+        };
+        DcgmConfig {
+            only,
+            uuid: gpu_uuid(seed),
+            model_name,
+            hostname: hostname.to_string(),
+        }
+    }
+
+    fn labels(&self) -> String {
+        format!(
+            "gpu=\"0\",UUID=\"{}\",device=\"nvidia0\",modelName=\"{}\",Hostname=\"{}\"",
+            self.uuid,
+            escape(&self.model_name),
+            escape(&self.hostname)
+        )
+    }
+
+    /// Prometheus text for the fields this sample can fill.
+    pub fn render(&self, stats: &TegraStats) -> String {
+        let gr3d = stats.engines.get("GR3D");
+        let emc = stats.engines.get("EMC");
+        let engine_usage = |name: &str| {
+            stats
+                .engines
+                .get(name)
+                .and_then(|e| e.usage_percent)
+                .map(f64::from)
+        };
+        let mib = |bytes: u64| (bytes / (1024 * 1024)) as f64;
+        let fields: [(&str, &str, Option<f64>); 10] = [
+            (
+                "DCGM_FI_DEV_SM_CLOCK",
+                "SM clock frequency (in MHz).",
+                gr3d.and_then(|e| e.freq_mhz).map(f64::from),
+            ),
+            (
+                "DCGM_FI_DEV_MEM_CLOCK",
+                "Memory clock frequency (in MHz).",
+                emc.and_then(|e| e.freq_mhz).map(f64::from),
+            ),
+            (
+                "DCGM_FI_DEV_GPU_TEMP",
+                "GPU temperature (in C).",
+                export::gpu_temp_c(stats).map(f64::from),
+            ),
+            (
+                "DCGM_FI_DEV_POWER_USAGE",
+                "Power draw (in W).",
+                export::gpu_power_mw(stats).map(|mw| mw as f64 / 1000.0),
+            ),
+            (
+                "DCGM_FI_DEV_GPU_UTIL",
+                "GPU utilization (in %).",
+                engine_usage("GR3D"),
+            ),
+            (
+                "DCGM_FI_DEV_MEM_COPY_UTIL",
+                "Memory utilization (in %).",
+                engine_usage("EMC"),
+            ),
+            (
+                "DCGM_FI_DEV_ENC_UTIL",
+                "Encoder utilization (in %).",
+                engine_usage("NVENC"),
+            ),
+            (
+                "DCGM_FI_DEV_DEC_UTIL",
+                "Decoder utilization (in %).",
+                engine_usage("NVDEC"),
+            ),
+            (
+                "DCGM_FI_DEV_FB_FREE",
+                "Framebuffer memory free (in MiB).",
+                stats
+                    .ram
+                    .as_ref()
+                    .map(|r| mib(r.total_bytes.saturating_sub(r.used_bytes))),
+            ),
+            (
+                "DCGM_FI_DEV_FB_USED",
+                "Framebuffer memory used (in MiB).",
+                stats.ram.as_ref().map(|r| mib(r.used_bytes)),
+            ),
+        ];
+        let labels = self.labels();
+        let mut out = String::new();
+        for (name, help, value) in fields {
+            let Some(value) = value else { continue };
+            out.push_str(&format!(
+                "# HELP {name} {help}\n# TYPE {name} gauge\n{name}{{{labels}}} {value}\n"
+            ));
+        }
+        out
+    }
+}
+
+/// "GPU-xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx" from two FNV-1a hashes of `seed`.
+fn gpu_uuid(seed: &str) -> String {
+    let fnv = |basis: u64| {
+        seed.bytes().fold(basis, |h, b| {
+            (h ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+    };
+    let hex = format!(
+        "{:016x}{:016x}",
+        fnv(0xcbf2_9ce4_8422_2325),
+        fnv(0x6c62_272e_07bb_0142)
+    );
+    format!(
+        "GPU-{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_dcgm_fields_with_labels() {
+        let stats = TegraStats::parse(
+            "RAM 2048/7620MB (lfb 10x4MB) CPU [10%@1497] EMC_FREQ 12%@2133 GR3D_FREQ 95%@918 gpu@51.2C VDD_GPU_SOC 3200mW/3000mW",
+        )
+        .unwrap();
+        let hw = JetsonHardware {
+            model: "NVIDIA Jetson AGX Orin Developer Kit".into(),
+            serial_number: "1421022012345".into(),
+            ..Default::default()
+        };
+        let cfg = DcgmConfig::new(&hw, "orin-1", false);
+        let out = cfg.render(&stats);
+        let labels = format!(
+            "{{gpu=\"0\",UUID=\"{}\",device=\"nvidia0\",modelName=\"NVIDIA Jetson AGX Orin Developer Kit\",Hostname=\"orin-1\"}}",
+            cfg.uuid
+        );
+        assert!(out.contains(&format!("DCGM_FI_DEV_GPU_UTIL{labels} 95\n")));
+        assert!(out.contains(&format!("DCGM_FI_DEV_SM_CLOCK{labels} 918\n")));
+        assert!(out.contains(&format!("DCGM_FI_DEV_MEM_CLOCK{labels} 2133\n")));
+        assert!(out.contains(&format!("DCGM_FI_DEV_POWER_USAGE{labels} 3.2\n")));
+        assert!(out.contains(&format!("DCGM_FI_DEV_FB_USED{labels} 2048\n")));
+        assert!(out.contains("# TYPE DCGM_FI_DEV_GPU_TEMP gauge\n"));
+        // No NVENC in this sample
+        assert!(!out.contains("DCGM_FI_DEV_ENC_UTIL"));
+
+        assert_eq!(cfg.uuid.len(), 40);
+        assert!(cfg.uuid.starts_with("GPU-"));
+        assert_eq!(cfg.uuid, DcgmConfig::new(&hw, "other", true).uuid);
+    }
+}
@@ -0,0 +1,227 @@
+//! Filesystem usage per mount point and block-device throughput.
+//!
+//! Usage comes from sysinfo (statvfs on each mounted disk), throughput from
+//! the sector counters in `/proc/diskstats`. Only whole devices listed in
+//! `/sys/block` are kept, so partitions are not counted twice, and
+//! loop/ram/zram devices are skipped.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use sysinfo::Disks;
+
+/// `/proc/diskstats` counts 512-byte sectors whatever the device's block size.
+const SECTOR_BYTES: u64 = 512;
+
+/// Disk section of a snapshot (not part of the tegrastats line).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DiskStats {
+    pub mounts: Vec<MountUsage>,
+    pub devices: Vec<BlockDeviceIo>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MountUsage {
+    pub mount_point: String,
+    /// Backing device, e.g. /dev/mmcblk0p1
+    pub device: String,
+    pub file_system: String,
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+}
+
+impl MountUsage {
+    pub fn used_bytes(&self) -> u64 {
+        self.total_bytes.saturating_sub(self.available_bytes)
+    }
+
+    pub fn used_percent(&self) -> f64 {
+        if self.total_bytes == 0 {
+            return 0.0;
+        }
+        self.used_bytes() as f64 * 100.0 / self.total_bytes as f64
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BlockDeviceIo {
+    pub name: String,
+    /// Counters since boot
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+    pub read_bytes_per_sec: f64,
+    pub write_bytes_per_sec: f64,
+}
+
+/// Device name and read/written bytes per line of `/proc/diskstats`.
+pub fn parse_diskstats(content: &str) -> Vec<(String, u64, u64)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            // major minor name reads merged sectors_read ms writes merged sectors_written ...
+            let name = fields.get(2)?;
+            let read: u64 = fields.get(5)?.parse().ok()?;
+            let written: u64 = fields.get(9)?.parse().ok()?;
+            Some((name.to_string(), read * SECTOR_BYTES, written * SECTOR_BYTES))
+        })
+        .collect()
+}
+
+/// Keeps the previous device counters so each sample carries per-second rates.
+pub struct DiskSampler {
+    proc_root: PathBuf,
+    sys_block: PathBuf,
+    disks: Disks,
+    prev: HashMap<String, (u64, u64)>,
+    prev_at: Option<Instant>,
+}
+
+impl Default for DiskSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DiskSampler {
+    pub fn new() -> Self {
+        Self::with_roots(Path::new("/proc"), Path::new("/sys/block"))
+    }
+
+    pub fn with_roots(proc_root: &Path, sys_block: &Path) -> Self {
+        Self {
+            proc_root: proc_root.to_path_buf(),
+            sys_block: sys_block.to_path_buf(),
+            disks: Disks::new(),
+            prev: HashMap::new(),
+            prev_at: None,
+        }
+    }
+
+    /// `None` when neither mounts nor block devices could be read.
+    pub fn sample(&mut self) -> Option<DiskStats> {
+        let stats = DiskStats {
+            mounts: self.mounts(),
+            devices: self.devices(Instant::now()),
+        };
+        (!stats.mounts.is_empty() || !stats.devices.is_empty()).then_some(stats)
+    }
+
+    fn mounts(&mut self) -> Vec<MountUsage> {
+        // Re-list every time: SD cards and USB drives come and go
+        self.disks.refresh_list();
+        let mut mounts: Vec<MountUsage> = Vec::new();
+        for disk in self.disks.list() {
+            let mount_point = disk.mount_point().to_string_lossy().to_string();
+            if disk.total_space() == 0 || mounts.iter().any(|m| m.mount_point == mount_point) {
+                continue;
+            }
+            mounts.push(MountUsage {
+                mount_point,
+                device: disk.name().to_string_lossy().to_string(),
+                file_system: disk.file_system().to_string_lossy().to_string(),
+                total_bytes: disk.total_space(),
+                available_bytes: disk.available_space(),
+            });
+        }
+        mounts.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+        mounts
+    }
+
+    fn devices(&mut self, now: Instant) -> Vec<BlockDeviceIo> {
+        let Ok(content) = fs::read_to_string(self.proc_root.join("diskstats")) else {
+            return Vec::new();
+        };
+        let counters: Vec<(String, u64, u64)> = parse_diskstats(&content)
+            .into_iter()
+            .filter(|(name, _, _)| self.is_whole_device(name))
+            .collect();
+        self.update(counters, now)
+    }
+
+    fn is_whole_device(&self, name: &str) -> bool {
+        !["loop", "ram", "zram"].iter().any(|p| name.starts_with(p))
+            && self.sys_block.join(name).exists()
+    }
+
+    fn update(&mut self, counters: Vec<(String, u64, u64)>, now: Instant) -> Vec<BlockDeviceIo> {
+        let secs = self
+            .prev_at
+            .map(|at| now.duration_since(at).as_secs_f64())
+            .filter(|s| *s > 0.0);
+        let devices = counters
+            .iter()
+            .map(|(name, read, written)| {
+                let rate = |prev: Option<u64>, cur: u64| match (prev, secs) {
+                    (Some(p), Some(s)) if cur >= p => (cur - p) as f64 / s,
+                    _ => 0.0,
+                };
+                let prev = self.prev.get(name);
+                BlockDeviceIo {
+                    name: name.clone(),
+                    read_bytes: *read,
+                    write_bytes: *written,
+                    read_bytes_per_sec: rate(prev.map(|p| p.0), *read),
+                    write_bytes_per_sec: rate(prev.map(|p| p.1), *written),
+                }
+            })
+            .collect();
+        self.prev = counters
+            .into_iter()
+            .map(|(name, read, written)| (name, (read, written)))
+            .collect();
+        self.prev_at = Some(now);
+        devices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn diskstats(sectors_read: u64, sectors_written: u64) -> String {
+        format!(
+            "   7       0 loop0 120 0 2000 10 0 0 0 0 0 20 10 0 0 0 0 0 0\n \
+179       0 mmcblk0 5000 100 {sectors_read} 900 300 50 {sectors_written} 400 0 1200 1300 0 0 0 0 0 0\n \
+179       1 mmcblk0p1 4900 100 90000 880 300 50 20000 400 0 1100 1280 0 0 0 0 0 0\n"
+        )
+    }
+
+    #[test]
+    fn keeps_whole_devices_and_computes_rates() {
+        let root = std::env::temp_dir().join(format!("jscope-disk-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("block/loop0")).unwrap();
+        fs::create_dir_all(root.join("block/mmcblk0")).unwrap();
+        fs::write(root.join("diskstats"), diskstats(100_000, 20_000)).unwrap();
+
+        let mut sampler = DiskSampler::with_roots(&root, &root.join("block"));
+        let start = Instant::now();
+        let first = sampler.devices(start);
+        fs::write(root.join("diskstats"), diskstats(104_000, 22_000)).unwrap();
+        let next = sampler.devices(start + Duration::from_secs(2));
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].name, "mmcblk0");
+        assert_eq!(first[0].read_bytes, 100_000 * 512);
+        assert_eq!(first[0].read_bytes_per_sec, 0.0);
+        assert_eq!(next[0].read_bytes_per_sec, 4_000.0 * 512.0 / 2.0);
+        assert_eq!(next[0].write_bytes_per_sec, 2_000.0 * 512.0 / 2.0);
+    }
+
+    #[test]
+    fn mount_usage_percent() {
+        let mount = MountUsage {
+            total_bytes: 32_000,
+            available_bytes: 8_000,
+            ..Default::default()
+        };
+        assert_eq!(mount.used_bytes(), 24_000);
+        assert_eq!(mount.used_percent(), 75.0);
+        assert_eq!(MountUsage::default().used_percent(), 0.0);
+    }
+}
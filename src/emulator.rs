@@ -0,0 +1,354 @@
+//! Built-in tegrastats emulator for development off a Jetson.
+//!
+//! Emits lines in the format of each board's tegrastats: Orin prints rails in
+//! mW with `GR3D_FREQ n%@[mhz]`, Xavier and Nano print bare numbers and
+//! `GR3D_FREQ n%@mhz`, Nano adds IRAM. Loads drift as a random walk and
+//! temperatures follow rail power with a lag, so the lines look like a board
+//! under a changing workload. The collector uses it in emulator mode
+//! (JETSONSCOPE_TUI_MODE=emulator, JETSONSCOPE_EMULATOR_MODEL) and the
+//! `jscope-emulator` binary prints it like tegrastats.
+
+use chrono::Local;
+use rand::Rng;
+
+use crate::hardware::{CpuCluster, MemoryConfig};
+
+/// Emulated board (`jscope-emulator --model`, JETSONSCOPE_EMULATOR_MODEL).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoardModel {
+    OrinAgx,
+    #[default]
+    OrinNx,
+    OrinNano,
+    XavierNx,
+    Nano,
+}
+
+impl BoardModel {
+    pub const ALL: [BoardModel; 5] = [
+        BoardModel::OrinAgx,
+        BoardModel::OrinNx,
+        BoardModel::OrinNano,
+        BoardModel::XavierNx,
+        BoardModel::Nano,
+    ];
+
+    /// JETSONSCOPE_EMULATOR_MODEL, else orin-nx.
+    pub fn from_env() -> Self {
+        std::env::var("JETSONSCOPE_EMULATOR_MODEL")
+            .ok()
+            .and_then(|v| Self::parse(&v))
+            .unwrap_or_default()
+    }
+
+    /// "orin-nx", "xavier-nx", ... (case-insensitive; `_` works for `-`).
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().to_ascii_lowercase().replace('_', "-");
+        Self::ALL.into_iter().find(|m| m.name() == s)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BoardModel::OrinAgx => "orin-agx",
+            BoardModel::OrinNx => "orin-nx",
+            BoardModel::OrinNano => "orin-nano",
+            BoardModel::XavierNx => "xavier-nx",
+            BoardModel::Nano => "nano",
+        }
+    }
+
+    fn spec(&self) -> BoardSpec {
+        match self {
+            BoardModel::OrinAgx => BoardSpec {
+                board_id: "p3701-0004",
+                cpus: 12,
+                clusters: &[4, 4, 4],
+                cpu_mhz: 2201,
+                gpu_mhz: 1301,
+                emc_mhz: 3199,
+                ram_mb: 30536,
+                swap_mb: 15268,
+                dla: 2,
+                nvenc: true,
+                idle_mw: 7_500.0,
+                full_mw: 55_000.0,
+                rails: &["VIN_SYS_5V0", "VDD_CPU_CV", "VDD_GPU_SOC"],
+                temps: &["cpu", "gpu", "soc0", "soc1", "soc2", "tj"],
+            },
+            BoardModel::OrinNx => BoardSpec {
+                board_id: "p3767-0000",
+                cpus: 8,
+                clusters: &[4, 4],
+                cpu_mhz: 1984,
+                gpu_mhz: 918,
+                emc_mhz: 3199,
+                ram_mb: 15656,
+                swap_mb: 7828,
+                dla: 2,
+                nvenc: true,
+                idle_mw: 4_500.0,
+                full_mw: 25_000.0,
+                rails: &["VDD_IN", "VDD_CPU_GPU_CV", "VDD_SOC"],
+                temps: &["cpu", "gpu", "soc0", "soc1", "soc2", "tj"],
+            },
+            BoardModel::OrinNano => BoardSpec {
+                board_id: "p3767-0003",
+                cpus: 6,
+                clusters: &[4, 2],
+                cpu_mhz: 1510,
+                gpu_mhz: 625,
+                emc_mhz: 2133,
+                ram_mb: 7620,
+                swap_mb: 3810,
+                dla: 0,
+                nvenc: false,
+                idle_mw: 3_800.0,
+                full_mw: 15_000.0,
+                rails: &["VDD_IN", "VDD_CPU_GPU_CV", "VDD_SOC"],
+                temps: &["cpu", "gpu", "soc0", "soc1", "soc2", "tj"],
+            },
+            BoardModel::XavierNx => BoardSpec {
+                board_id: "p3668-0001",
+                cpus: 6,
+                clusters: &[2, 2, 2],
+                cpu_mhz: 1907,
+                gpu_mhz: 1109,
+                emc_mhz: 1866,
+                ram_mb: 7765,
+                swap_mb: 3882,
+                dla: 2,
+                nvenc: true,
+                idle_mw: 3_500.0,
+                full_mw: 20_000.0,
+                rails: &["VDD_IN", "VDD_CPU_GPU_CV", "VDD_SOC"],
+                temps: &["AO", "GPU", "AUX", "CPU", "thermal"],
+            },
+            BoardModel::Nano => BoardSpec {
+                board_id: "p3448-0000",
+                cpus: 4,
+                clusters: &[4],
+                cpu_mhz: 1479,
+                gpu_mhz: 921,
+                emc_mhz: 1600,
+                ram_mb: 3956,
+                swap_mb: 1978,
+                dla: 0,
+                nvenc: true,
+                idle_mw: 1_200.0,
+                full_mw: 10_000.0,
+                rails: &["POM_5V_IN", "POM_5V_GPU", "POM_5V_CPU"],
+                temps: &["PLL", "CPU", "GPU", "AO", "thermal"],
+            },
+        }
+    }
+
+    fn is_orin(&self) -> bool {
+        matches!(self, BoardModel::OrinAgx | BoardModel::OrinNx | BoardModel::OrinNano)
+    }
+}
+
+struct BoardSpec {
+    /// Module the board stands in for (see `MemoryConfig::for_board`)
+    board_id: &'static str,
+    cpus: usize,
+    /// Cores per CPU cluster, in CPU order
+    clusters: &'static [u32],
+    cpu_mhz: u32,
+    gpu_mhz: u32,
+    emc_mhz: u32,
+    ram_mb: u64,
+    swap_mb: u64,
+    dla: usize,
+    nvenc: bool,
+    idle_mw: f64,
+    full_mw: f64,
+    /// Input rail first, then the CPU/GPU rail and the SoC rail
+    rails: &'static [&'static str],
+    temps: &'static [&'static str],
+}
+
+const AMBIENT_C: f64 = 30.0;
+/// Share of the gap to the steady-state temperature closed per line
+const THERMAL_LAG: f64 = 0.1;
+
+/// Produces tegrastats lines for one board model.
+pub struct Emulator {
+    model: BoardModel,
+    spec: BoardSpec,
+    cpu: Vec<f64>,
+    gpu: f64,
+    dla: f64,
+    ram_mb: f64,
+    temp_c: f64,
+    rail_avg: Vec<f64>,
+    samples: f64,
+}
+
+impl Emulator {
+    pub fn new(model: BoardModel) -> Self {
+        let spec = model.spec();
+        Self {
+            model,
+            cpu: vec![10.0; spec.cpus],
+            gpu: 5.0,
+            dla: 0.0,
+            ram_mb: spec.ram_mb as f64 * 0.3,
+            temp_c: AMBIENT_C + 10.0,
+            rail_avg: vec![0.0; spec.rails.len()],
+            samples: 0.0,
+            spec,
+        }
+    }
+
+    pub fn model(&self) -> BoardModel {
+        self.model
+    }
+
+    /// The board's CPU clusters, as sysfs would report them.
+    pub fn cpu_clusters(&self) -> Vec<CpuCluster> {
+        let mut first = 0;
+        self.spec
+            .clusters
+            .iter()
+            .map(|&cores| {
+                let cpus = (first..first + cores).collect();
+                first += cores;
+                CpuCluster {
+                    cpus,
+                    max_khz: Some(self.spec.cpu_mhz as u64 * 1000),
+                }
+            })
+            .collect()
+    }
+
+    /// The emulated module's DRAM, for EMC bandwidth.
+    pub fn memory_config(&self) -> Option<MemoryConfig> {
+        MemoryConfig::for_board(self.spec.board_id)
+    }
+
+    /// Advance one sample and render it as a tegrastats line.
+    pub fn next_line(&mut self) -> String {
+        let mut rng = rand::thread_rng();
+        let mut walk = |value: f64, step: f64| (value + rng.gen_range(-step..=step)).clamp(0.0, 100.0);
+        for load in self.cpu.iter_mut() {
+            *load = walk(*load, 8.0);
+        }
+        self.gpu = walk(self.gpu, 12.0);
+        self.dla = if self.spec.dla > 0 { walk(self.dla, 10.0) } else { 0.0 };
+        let ram_total = self.spec.ram_mb as f64;
+        self.ram_mb = (self.ram_mb + rng.gen_range(-0.01..=0.01) * ram_total)
+            .clamp(ram_total * 0.15, ram_total * 0.9);
+
+        let cpu_avg = self.cpu.iter().sum::<f64>() / self.cpu.len() as f64;
+        let busy = (cpu_avg * 0.4 + self.gpu * 0.5 + self.dla * 0.1) / 100.0;
+        let input_mw = self.spec.idle_mw + (self.spec.full_mw - self.spec.idle_mw) * busy;
+        let target = AMBIENT_C + busy * 45.0;
+        self.temp_c += (target - self.temp_c) * THERMAL_LAG;
+        // Input rail, then CPU/GPU and SoC shares of it
+        let rails = [input_mw, input_mw * (0.15 + 0.45 * busy), input_mw * 0.2];
+        self.samples += 1.0;
+        for (avg, mw) in self.rail_avg.iter_mut().zip(rails) {
+            *avg += (mw - *avg) / self.samples;
+        }
+
+        let spec = &self.spec;
+        let mut line = Local::now().format("%m-%d-%Y %H:%M:%S ").to_string();
+        line.push_str(&format!(
+            "RAM {}/{}MB (lfb {}x4MB) SWAP 0/{}MB (cached 0MB) ",
+            self.ram_mb as u64,
+            spec.ram_mb,
+            rng.gen_range(50..600),
+            spec.swap_mb
+        ));
+        if self.model == BoardModel::Nano {
+            line.push_str("IRAM 0/252kB(lfb 252kB) ");
+        }
+        let cores: Vec<String> = self
+            .cpu
+            .iter()
+            .map(|load| {
+                let mhz = (spec.cpu_mhz as f64 * (0.35 + 0.65 * load / 100.0)) as u32;
+                format!("{}%@{}", load.round() as u32, mhz)
+            })
+            .collect();
+        line.push_str(&format!("CPU [{}] ", cores.join(",")));
+        let emc = (busy * 60.0) as u32;
+        let gpu_mhz = if self.gpu < 1.0 { 306 } else { spec.gpu_mhz };
+        if self.model.is_orin() {
+            line.push_str(&format!(
+                "EMC_FREQ {}%@{} GR3D_FREQ {}%@[{}] ",
+                emc,
+                spec.emc_mhz,
+                self.gpu.round() as u32,
+                gpu_mhz
+            ));
+        } else {
+            line.push_str(&format!(
+                "EMC_FREQ {}%@{} GR3D_FREQ {}%@{} ",
+                emc,
+                spec.emc_mhz,
+                self.gpu.round() as u32,
+                gpu_mhz
+            ));
+        }
+        if spec.nvenc {
+            line.push_str("NVENC off NVDEC off ");
+        }
+        for unit in 0..spec.dla {
+            if self.dla < 1.0 || unit > 0 {
+                line.push_str(&format!("NVDLA{} off ", unit));
+            } else {
+                line.push_str(&format!("NVDLA{} 1600 ", unit));
+            }
+        }
+        line.push_str("APE 150 ");
+        for (i, sensor) in spec.temps.iter().enumerate() {
+            let temp = self.temp_c + i as f64 * 0.4 + rng.gen_range(-0.2..=0.2);
+            line.push_str(&format!("{}@{:.3}C ", sensor, temp));
+        }
+        for ((rail, mw), avg) in spec.rails.iter().zip(rails).zip(self.rail_avg.iter()) {
+            if self.model.is_orin() {
+                line.push_str(&format!("{} {}mW/{}mW ", rail, mw as u32, *avg as u32));
+            } else {
+                line.push_str(&format!("{} {}/{} ", rail, mw as u32, *avg as u32));
+            }
+        }
+        line.trim_end().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::TegraStats;
+
+    #[test]
+    fn parses_model_names() {
+        assert_eq!(BoardModel::parse("Orin_NX"), Some(BoardModel::OrinNx));
+        assert_eq!(BoardModel::parse("tx2"), None);
+        for model in BoardModel::ALL {
+            assert_eq!(BoardModel::parse(model.name()), Some(model));
+        }
+    }
+
+    #[test]
+    fn every_model_emits_lines_the_parser_reads() {
+        for model in BoardModel::ALL {
+            let spec = model.spec();
+            let mut emulator = Emulator::new(model);
+            for _ in 0..5 {
+                let line = emulator.next_line();
+                let stats = TegraStats::parse(&line).unwrap();
+                assert_eq!(stats.cpus.len(), spec.cpus, "{line}");
+                assert_eq!(stats.ram.as_ref().unwrap().total_bytes, spec.ram_mb * 1024 * 1024);
+                assert!(stats.gpu_usage().is_some(), "{line}");
+                assert!(stats.power.contains_key(spec.rails[0]), "{line}");
+                assert_eq!(stats.temps.len(), spec.temps.len(), "{line}");
+                assert_eq!(stats.iram.is_some(), model == BoardModel::Nano);
+                assert!(TegraStats::parse_strict(&line).is_ok(), "{line}");
+            }
+            let clustered: usize = emulator.cpu_clusters().iter().map(|c| c.cpus.len()).sum();
+            assert_eq!(clustered, spec.cpus, "{}", model.name());
+        }
+    }
+}
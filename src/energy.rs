@@ -0,0 +1,219 @@
+use crate::parser::TegraStats;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Gaps longer than this (collector stall, suspend) are not integrated.
+const MAX_INTEGRATION_GAP: Duration = Duration::from_secs(30);
+
+/// Per-rail energy accumulator (trapezoidal integration of rail power over time).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EnergyMeter {
+    /// Cumulative energy per rail in watt-hours
+    pub rails_wh: BTreeMap<String, f64>,
+    #[serde(skip)]
+    last: Option<(Instant, HashMap<String, u32>)>,
+    /// `rails_wh` when the meter was loaded, so a restored total can still
+    /// tell what this process added
+    #[serde(skip)]
+    baseline_wh: BTreeMap<String, f64>,
+}
+
+/// Rail energy since the daemon started and in total (for GetEnergy).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EnergyReport {
+    /// Wh per rail integrated by this daemon process
+    pub since_start_wh: BTreeMap<String, f64>,
+    /// Wh per rail including totals restored from JETSONSCOPE_ENERGY_STATE
+    pub total_wh: BTreeMap<String, f64>,
+}
+
+impl EnergyMeter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Integrate the rails of `stats` since the previous call.
+    pub fn record(&mut self, stats: &TegraStats, now: Instant) {
+        let current: HashMap<String, u32> = stats
+            .power
+            .iter()
+            .map(|(name, rail)| (name.clone(), rail.current_mw))
+            .collect();
+
+        if let Some((prev_at, prev)) = &self.last {
+            let elapsed = now.saturating_duration_since(*prev_at);
+            if elapsed <= MAX_INTEGRATION_GAP {
+                let hours = elapsed.as_secs_f64() / 3600.0;
+                for (name, mw) in &current {
+                    let prev_mw = prev.get(name).copied().unwrap_or(*mw);
+                    let avg_w = (*mw as f64 + prev_mw as f64) / 2.0 / 1000.0;
+                    *self.rails_wh.entry(name.clone()).or_insert(0.0) += avg_w * hours;
+                }
+            }
+        }
+        self.last = Some((now, current));
+    }
+
+    pub fn rail_wh(&self, rail: &str) -> f64 {
+        self.rails_wh.get(rail).copied().unwrap_or_default()
+    }
+
+    /// Board input energy: VDD_IN when reported, otherwise the sum of all rails.
+    pub fn input_wh(&self) -> f64 {
+        self.rails_wh
+            .get("VDD_IN")
+            .copied()
+            .unwrap_or_else(|| self.rails_wh.values().sum())
+    }
+
+    /// Energy per rail since this meter was created or loaded.
+    pub fn since_start_wh(&self) -> BTreeMap<String, f64> {
+        self.rails_wh
+            .iter()
+            .map(|(rail, wh)| {
+                let base = self.baseline_wh.get(rail).copied().unwrap_or_default();
+                (rail.clone(), (wh - base).max(0.0))
+            })
+            .collect()
+    }
+
+    pub fn report(&self) -> EnergyReport {
+        EnergyReport {
+            since_start_wh: self.since_start_wh(),
+            total_wh: self.rails_wh.clone(),
+        }
+    }
+
+    /// Load a previously saved meter; a missing file starts from zero.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+        let data = fs::read_to_string(path).with_context(|| format!("reading {:?}", path))?;
+        let mut meter: Self =
+            serde_json::from_str(&data).with_context(|| format!("parsing {:?}", path))?;
+        meter.baseline_wh = meter.rails_wh.clone();
+        Ok(meter)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).ok();
+        }
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_string(self)?)
+            .with_context(|| format!("writing {:?}", tmp))?;
+        fs::rename(&tmp, path).with_context(|| format!("renaming {:?}", tmp))?;
+        Ok(())
+    }
+}
+
+/// Electricity price and grid carbon intensity used to turn Wh into cost/CO2 estimates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnergyTariff {
+    pub price_per_kwh: Option<f64>,
+    pub currency: String,
+    pub co2_g_per_kwh: Option<f64>,
+}
+
+impl EnergyTariff {
+    /// JETSONSCOPE_ENERGY_PRICE (per kWh), JETSONSCOPE_ENERGY_CURRENCY (default "USD"),
+    /// JETSONSCOPE_CO2_G_PER_KWH; `None` when neither estimate is configured.
+    pub fn from_env() -> Option<Self> {
+        let num = |key: &str| {
+            std::env::var(key)
+                .ok()
+                .and_then(|s| s.trim().parse::<f64>().ok())
+                .filter(|v| *v >= 0.0)
+        };
+        let tariff = EnergyTariff {
+            price_per_kwh: num("JETSONSCOPE_ENERGY_PRICE"),
+            currency: std::env::var("JETSONSCOPE_ENERGY_CURRENCY")
+                .unwrap_or_else(|_| "USD".to_string()),
+            co2_g_per_kwh: num("JETSONSCOPE_CO2_G_PER_KWH"),
+        };
+        (tariff.price_per_kwh.is_some() || tariff.co2_g_per_kwh.is_some()).then_some(tariff)
+    }
+
+    pub fn cost(&self, wh: f64) -> Option<f64> {
+        self.price_per_kwh.map(|p| wh / 1000.0 * p)
+    }
+
+    pub fn co2_grams(&self, wh: f64) -> Option<f64> {
+        self.co2_g_per_kwh.map(|g| wh / 1000.0 * g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::PowerRail;
+
+    fn sample(vdd_in_mw: u32) -> TegraStats {
+        let mut stats = TegraStats::default();
+        stats.power.insert(
+            "VDD_IN".into(),
+            PowerRail {
+                current_mw: vdd_in_mw,
+                average_mw: vdd_in_mw,
+            },
+        );
+        stats
+    }
+
+    #[test]
+    fn integrates_trapezoid_per_rail() {
+        let mut meter = EnergyMeter::new();
+        let t0 = Instant::now();
+        meter.record(&sample(6000), t0);
+        meter.record(&sample(6000), t0 + Duration::from_secs(10));
+        meter.record(&sample(12000), t0 + Duration::from_secs(20));
+        // 10s @ 6W + 10s @ 9W average = 150 J
+        let expected = 150.0 / 3600.0;
+        assert!((meter.rail_wh("VDD_IN") - expected).abs() < 1e-9);
+        assert!((meter.input_wh() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn skips_long_gaps() {
+        let mut meter = EnergyMeter::new();
+        let t0 = Instant::now();
+        meter.record(&sample(6000), t0);
+        meter.record(&sample(6000), t0 + Duration::from_secs(600));
+        assert_eq!(meter.rail_wh("VDD_IN"), 0.0);
+    }
+
+    #[test]
+    fn save_and_load_roundtrip() {
+        let path = std::env::temp_dir().join(format!("jscope-energy-{}.json", std::process::id()));
+        let mut meter = EnergyMeter::new();
+        meter.rails_wh.insert("VDD_IN".into(), 1.25);
+        meter.save(&path).unwrap();
+        let mut loaded = EnergyMeter::load(&path).unwrap();
+        assert_eq!(loaded.rail_wh("VDD_IN"), 1.25);
+        let _ = fs::remove_file(&path);
+
+        // Only what this process integrates counts as since-start
+        let t0 = Instant::now();
+        loaded.record(&sample(3600), t0);
+        loaded.record(&sample(3600), t0 + Duration::from_secs(10));
+        let report = loaded.report();
+        assert!((report.since_start_wh["VDD_IN"] - 0.01).abs() < 1e-9);
+        assert!((report.total_wh["VDD_IN"] - 1.26).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tariff_estimates() {
+        let tariff = EnergyTariff {
+            price_per_kwh: Some(0.30),
+            currency: "EUR".into(),
+            co2_g_per_kwh: None,
+        };
+        assert!((tariff.cost(2500.0).unwrap() - 0.75).abs() < 1e-9);
+        assert_eq!(tariff.co2_grams(2500.0), None);
+    }
+}
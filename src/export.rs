@@ -0,0 +1,176 @@
+use crate::parser::TegraStats;
+use crate::thermal_governor::valid_temp;
+
+/// What a key metric measures; push exporters use it for units and sensor classes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    Temperature,
+    Power,
+    Percent,
+}
+
+impl MetricKind {
+    pub fn unit(&self) -> &'static str {
+        match self {
+            MetricKind::Temperature => "°C",
+            MetricKind::Power => "W",
+            MetricKind::Percent => "%",
+        }
+    }
+}
+
+/// One flattened value of a sample, e.g. `temp_tj` = 54.5.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyMetric {
+    /// Stable identifier: lowercase, `[a-z0-9_]`
+    pub key: String,
+    /// Human-readable name, e.g. "tj temperature"
+    pub name: String,
+    pub kind: MetricKind,
+    pub value: f64,
+}
+
+impl KeyMetric {
+    fn new(key: String, name: String, kind: MetricKind, value: f64) -> Self {
+        KeyMetric {
+            key,
+            name,
+            kind,
+            value,
+        }
+    }
+}
+
+/// Key metrics of a sample (loads, temperatures, rail power, fan) for push exporters.
+pub fn key_metrics(stats: &TegraStats, fan_percent: Option<f64>) -> Vec<KeyMetric> {
+    let mut out = Vec::new();
+    let loads: Vec<u32> = stats.cpus.iter().filter_map(|c| c.load_percent).collect();
+    if !loads.is_empty() {
+        let avg = loads.iter().sum::<u32>() as f64 / loads.len() as f64;
+        out.push(KeyMetric::new(
+            "cpu_load".into(),
+            "CPU load".into(),
+            MetricKind::Percent,
+            avg,
+        ));
+    }
+    if let Some(gpu) = stats.gpu_usage() {
+        out.push(KeyMetric::new(
+            "gpu_load".into(),
+            "GPU load".into(),
+            MetricKind::Percent,
+            gpu as f64,
+        ));
+    }
+    if let Some(ram) = stats.ram.as_ref().filter(|r| r.total_bytes > 0) {
+        out.push(KeyMetric::new(
+            "ram_used".into(),
+            "RAM used".into(),
+            MetricKind::Percent,
+            ram.used_bytes as f64 * 100.0 / ram.total_bytes as f64,
+        ));
+    }
+    let mut temps: Vec<_> = stats.temps.iter().filter(|(_, t)| valid_temp(**t)).collect();
+    temps.sort_by(|a, b| a.0.cmp(b.0));
+    for (name, temp) in temps {
+        out.push(KeyMetric::new(
+            format!("temp_{}", sanitize_key(name)),
+            format!("{} temperature", name),
+            MetricKind::Temperature,
+            *temp as f64,
+        ));
+    }
+    let mut rails: Vec<_> = stats.power.iter().collect();
+    rails.sort_by(|a, b| a.0.cmp(b.0));
+    for (name, rail) in rails {
+        out.push(KeyMetric::new(
+            format!("power_{}", sanitize_key(name)),
+            format!("{} power", name),
+            MetricKind::Power,
+            rail.current_mw as f64 / 1000.0,
+        ));
+    }
+    if let Some(fan) = fan_percent {
+        out.push(KeyMetric::new(
+            "fan".into(),
+            "Fan speed".into(),
+            MetricKind::Percent,
+            fan,
+        ));
+    }
+    out
+}
+
+/// GPU rail names across Jetson generations, most specific first.
+const GPU_RAILS: &[&str] = &["VDD_GPU_SOC", "VDD_GPU", "POM_5V_GPU", "VDD_SYS_GPU"];
+const GPU_TEMPS: &[&str] = &["GPU", "gpu", "GPU-therm", "gpu-thermal"];
+
+/// GPU temperature, whichever name this board's tegrastats uses.
+pub fn gpu_temp_c(stats: &TegraStats) -> Option<f32> {
+    GPU_TEMPS
+        .iter()
+        .find_map(|s| stats.temps.get(*s).copied())
+        .filter(|t| valid_temp(*t))
+}
+
+/// Instant GPU rail power (mW); on Orin the rail also feeds the CV engines.
+pub fn gpu_power_mw(stats: &TegraStats) -> Option<u32> {
+    GPU_RAILS
+        .iter()
+        .find_map(|r| stats.power.get(*r))
+        .map(|r| r.current_mw)
+}
+
+/// Lowercase and replace anything outside `[a-z0-9]` with `_`.
+pub fn sanitize_key(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Device name used in exporter topics/paths: JETSONSCOPE_DEVICE_NAME, else the hostname.
+pub fn device_name() -> String {
+    std::env::var("JETSONSCOPE_DEVICE_NAME")
+        .ok()
+        .or_else(|| std::fs::read_to_string("/proc/sys/kernel/hostname").ok())
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "jetson".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flattens_sample() {
+        let stats = TegraStats::parse(
+            "RAM 2000/4000MB (lfb 10x4MB) CPU [10%@1497,30%@1497] GR3D_FREQ 45%@918 CPU@50.5C GPU@-256C VDD_IN 5123mW/5000mW",
+        )
+        .unwrap();
+        let metrics = key_metrics(&stats, Some(60.0));
+        let keys: Vec<&str> = metrics.iter().map(|m| m.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "cpu_load",
+                "gpu_load",
+                "ram_used",
+                "temp_cpu",
+                "power_vdd_in",
+                "fan"
+            ]
+        );
+        assert_eq!(metrics[0].value, 20.0);
+        assert_eq!(metrics[2].value, 50.0);
+        assert_eq!(metrics[4].value, 5.123);
+        assert_eq!(metrics[4].kind.unit(), "W");
+    }
+}
@@ -0,0 +1,116 @@
+//! Min/max/avg since the TUI started, with the wall-clock time of each extreme.
+
+use crate::parser::TegraStats;
+use crate::thermal_governor::valid_temp;
+use chrono::{DateTime, Local};
+use std::collections::BTreeMap;
+
+/// Running min/max/avg of one series.
+#[derive(Debug, Clone, Default)]
+pub struct RunningStat {
+    pub min: f64,
+    pub min_at: Option<DateTime<Local>>,
+    pub max: f64,
+    pub max_at: Option<DateTime<Local>>,
+    sum: f64,
+    pub count: u64,
+}
+
+impl RunningStat {
+    pub fn push(&mut self, value: f64, at: DateTime<Local>) {
+        if self.count == 0 || value < self.min {
+            self.min = value;
+            self.min_at = Some(at);
+        }
+        if self.count == 0 || value > self.max {
+            self.max = value;
+            self.max_at = Some(at);
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    pub fn avg(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.sum / self.count as f64)
+    }
+
+    /// "▼12% 10:01:05 ▲98% 10:03:10 ⌀41%", empty before the first sample.
+    pub fn annotation(&self, unit: &str) -> String {
+        let Some(avg) = self.avg() else {
+            return String::new();
+        };
+        let time = |at: &Option<DateTime<Local>>| {
+            at.map(|t| t.format("%H:%M:%S").to_string())
+                .unwrap_or_default()
+        };
+        format!(
+            "▼{:.0}{unit} {} ▲{:.0}{unit} {} ⌀{:.0}{unit}",
+            self.min,
+            time(&self.min_at),
+            self.max,
+            time(&self.max_at),
+            avg
+        )
+    }
+}
+
+/// Extremes for the dashboard's RAM, GPU and temperature widgets.
+#[derive(Debug, Clone, Default)]
+pub struct SessionExtremes {
+    /// RAM used (MB)
+    pub ram: RunningStat,
+    /// GR3D load (%)
+    pub gpu: RunningStat,
+    pub temps: BTreeMap<String, RunningStat>,
+}
+
+impl SessionExtremes {
+    pub fn record(&mut self, stats: &TegraStats, at: DateTime<Local>) {
+        if let Some(ram) = &stats.ram {
+            self.ram.push((ram.used_bytes / 1024 / 1024) as f64, at);
+        }
+        if let Some(gpu) = stats.gpu_usage() {
+            self.gpu.push(gpu as f64, at);
+        }
+        for (name, temp) in &stats.temps {
+            // -256C marks an offline sensor
+            if valid_temp(*temp) {
+                self.temps
+                    .entry(name.clone())
+                    .or_default()
+                    .push(*temp as f64, at);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn tracks_extremes_and_when_they_happened() {
+        let at = |s: u32| Local.with_ymd_and_hms(2024, 5, 1, 10, 0, s).unwrap();
+        let mut ext = SessionExtremes::default();
+        for (i, line) in [
+            "RAM 1000/7620MB (lfb 10x4MB) GR3D_FREQ 10%@918 tj@50C CPU@-256C",
+            "RAM 3000/7620MB (lfb 10x4MB) GR3D_FREQ 90%@918 tj@71.5C",
+            "RAM 2000/7620MB (lfb 10x4MB) GR3D_FREQ 50%@918 tj@60C",
+        ]
+        .iter()
+        .enumerate()
+        {
+            ext.record(&TegraStats::parse(line).unwrap(), at(i as u32));
+        }
+        assert_eq!(ext.gpu.min, 10.0);
+        assert_eq!(ext.gpu.max, 90.0);
+        assert_eq!(ext.gpu.avg(), Some(50.0));
+        assert_eq!(ext.gpu.max_at, Some(at(1)));
+        assert_eq!(ext.ram.max, 3000.0);
+        assert_eq!(ext.temps["tj"].max, 71.5);
+        assert!(!ext.temps.contains_key("CPU"));
+        assert_eq!(ext.gpu.annotation("%"), "▼10% 10:00:00 ▲90% 10:00:01 ⌀50%");
+        assert_eq!(RunningStat::default().annotation("%"), "");
+    }
+}
@@ -0,0 +1,248 @@
+//! Fans from hwmon, one entry per PWM output, and temperature -> PWM curves.
+//!
+//! Carriers may have several pwm-fan devices (or one device driving several
+//! outputs); each `pwmN` file is a fan, numbered in hwmon order as fan0, fan1, ...
+
+use crate::t;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SYSFS_HWMON: &str = "/sys/class/hwmon";
+
+/// One PWM output and where to read its speed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FanInfo {
+    /// Position among all fans; the control is `fan<index>`
+    pub index: usize,
+    /// hwmon `name` (e.g. "pwm-fan"), plus the `fanN_label` when the driver has one
+    pub name: String,
+    /// The `pwmN` file (0-255)
+    pub pwm_path: PathBuf,
+    /// Tachometer file (`fanN_input`, or the Tegra `rpm` attribute), if any
+    pub rpm_path: Option<PathBuf>,
+}
+
+/// Live reading of one fan.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FanState {
+    pub index: usize,
+    pub name: String,
+    pub rpm: Option<u32>,
+    /// Raw PWM duty (0-255)
+    pub pwm: Option<u8>,
+    /// Curve driving this fan, as given to `fan<index>_curve`
+    pub curve: Option<String>,
+}
+
+impl FanState {
+    pub fn percent(&self) -> Option<u8> {
+        self.pwm.map(pwm_to_percent)
+    }
+}
+
+impl FanInfo {
+    pub fn control_name(&self) -> String {
+        format!("fan{}", self.index)
+    }
+
+    pub fn read(&self) -> FanState {
+        FanState {
+            index: self.index,
+            name: self.name.clone(),
+            rpm: self.rpm_path.as_deref().and_then(read_number),
+            pwm: read_number(&self.pwm_path).map(|v| v.min(255) as u8),
+            curve: None,
+        }
+    }
+
+    pub fn set_percent(&self, percent: u8) -> anyhow::Result<()> {
+        use anyhow::Context;
+        fs::write(&self.pwm_path, percent_to_pwm(percent).to_string())
+            .with_context(|| t!("control.err.writing", path = format!("{:?}", self.pwm_path)))
+    }
+}
+
+pub fn percent_to_pwm(percent: u8) -> u8 {
+    (percent.min(100) as u32 * 255 / 100) as u8
+}
+
+pub fn pwm_to_percent(pwm: u8) -> u8 {
+    ((pwm as u32 * 100 + 127) / 255) as u8
+}
+
+/// Fans on this system.
+pub fn detect() -> Vec<FanInfo> {
+    read_from(Path::new(SYSFS_HWMON))
+}
+
+/// Every `pwmN` under the `hwmon*` directories of `base`, in hwmon order.
+pub fn read_from(base: &Path) -> Vec<FanInfo> {
+    let Ok(entries) = fs::read_dir(base) else {
+        return Vec::new();
+    };
+    let mut devices: Vec<(u32, PathBuf)> = entries
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            Some((name.strip_prefix("hwmon")?.parse().ok()?, e.path()))
+        })
+        .collect();
+    devices.sort();
+
+    let mut fans = Vec::new();
+    for (_, dir) in devices {
+        let device = read_attr(&dir, "name").unwrap_or_else(|| "fan".to_string());
+        for n in 1..=8 {
+            let pwm_path = dir.join(format!("pwm{n}"));
+            if !pwm_path.exists() {
+                continue;
+            }
+            let rpm_path = [format!("fan{n}_input"), "rpm".to_string()]
+                .into_iter()
+                .map(|f| dir.join(f))
+                .find(|p| p.exists());
+            let name = match read_attr(&dir, &format!("fan{n}_label")) {
+                Some(label) => format!("{device} {label}"),
+                None => device.clone(),
+            };
+            fans.push(FanInfo {
+                index: fans.len(),
+                name,
+                pwm_path,
+                rpm_path,
+            });
+        }
+    }
+    fans
+}
+
+fn read_attr(dir: &Path, attr: &str) -> Option<String> {
+    fs::read_to_string(dir.join(attr))
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn read_number(path: &Path) -> Option<u32> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Named curves, accepted wherever a "temp:pct,..." spec is.
+pub const PRESETS: [(&str, &str); 3] = [
+    ("quiet", "50:0,65:30,80:60,90:100"),
+    ("balanced", "40:20,60:50,80:100"),
+    ("aggressive", "35:40,50:70,65:100"),
+];
+
+/// Piecewise-linear fan curve: "40:20,60:50,80:100" is 20% at 40°C up to 100% at 80°C.
+///
+/// Below the first point the first speed holds, above the last point the last one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FanCurve {
+    points: Vec<(f32, u8)>,
+}
+
+impl FanCurve {
+    /// A spec or the name of one of the `PRESETS`.
+    pub fn parse(spec: &str) -> anyhow::Result<Self> {
+        let spec = PRESETS
+            .iter()
+            .find(|(name, _)| *name == spec.trim())
+            .map_or(spec, |(_, preset)| *preset);
+        let mut points = Vec::new();
+        for pair in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let parsed = pair.split_once(':').and_then(|(t, p)| {
+                Some((t.trim().parse::<f32>().ok()?, p.trim().parse::<u8>().ok()?))
+            });
+            match parsed {
+                Some((temp, pct)) if pct <= 100 => points.push((temp, pct)),
+                _ => anyhow::bail!(t!("fans.err.point", point = pair)),
+            }
+        }
+        if points.is_empty() {
+            anyhow::bail!(t!("fans.err.empty"));
+        }
+        if points.windows(2).any(|w| w[1].0 <= w[0].0) {
+            anyhow::bail!(t!("fans.err.order"));
+        }
+        Ok(FanCurve { points })
+    }
+
+    pub fn percent_at(&self, temp_c: f32) -> u8 {
+        let (first, last) = (self.points[0], self.points[self.points.len() - 1]);
+        if temp_c <= first.0 {
+            return first.1;
+        }
+        if temp_c >= last.0 {
+            return last.1;
+        }
+        let w = self
+            .points
+            .windows(2)
+            .find(|w| temp_c <= w[1].0)
+            .expect("temp is inside the curve");
+        let ((t0, p0), (t1, p1)) = (w[0], w[1]);
+        let frac = (temp_c - t0) / (t1 - t0);
+        (p0 as f32 + frac * (p1 as f32 - p0 as f32)).round() as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enumerates_pwm_outputs_across_devices() {
+        let base = std::env::temp_dir().join(format!("jscope-hwmon-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        let dev = |n: u32, attrs: &[(&str, &str)]| {
+            let dir = base.join(format!("hwmon{n}"));
+            fs::create_dir_all(&dir).unwrap();
+            for (attr, value) in attrs {
+                fs::write(dir.join(attr), format!("{value}\n")).unwrap();
+            }
+        };
+        dev(10, &[("name", "pwm-fan"), ("pwm1", "128"), ("rpm", "2400")]);
+        dev(2, &[("name", "cpu_thermal"), ("temp1_input", "45000")]);
+        dev(
+            3,
+            &[
+                ("name", "emc2305"),
+                ("pwm1", "255"),
+                ("fan1_input", "5100"),
+                ("fan1_label", "front"),
+                ("pwm2", "0"),
+            ],
+        );
+
+        let fans = read_from(&base);
+        let states: Vec<FanState> = fans.iter().map(FanInfo::read).collect();
+        let _ = fs::remove_dir_all(&base);
+
+        assert_eq!(fans.len(), 3);
+        assert_eq!(fans[0].name, "emc2305 front");
+        assert_eq!(fans[1].control_name(), "fan1");
+        assert!(fans[1].rpm_path.is_none());
+        assert_eq!(fans[2].name, "pwm-fan");
+        assert_eq!((states[0].rpm, states[0].percent()), (Some(5100), Some(100)));
+        assert_eq!((states[2].rpm, states[2].percent()), (Some(2400), Some(50)));
+        assert_eq!(percent_to_pwm(50), 127);
+    }
+
+    #[test]
+    fn interpolates_curves() {
+        let curve = FanCurve::parse("40:20, 60:50,80:100").unwrap();
+        assert_eq!(curve.percent_at(20.0), 20);
+        assert_eq!(curve.percent_at(50.0), 35);
+        assert_eq!(curve.percent_at(70.0), 75);
+        assert_eq!(curve.percent_at(95.0), 100);
+        assert_eq!(FanCurve::parse("balanced").unwrap(), curve);
+        assert_eq!(FanCurve::parse("quiet").unwrap().percent_at(45.0), 0);
+
+        assert!(FanCurve::parse("").is_err());
+        assert!(FanCurve::parse("60:50,40:20").is_err());
+        assert!(FanCurve::parse("40:120").is_err());
+        assert!(FanCurve::parse("hot").is_err());
+    }
+}
@@ -0,0 +1,76 @@
+use crate::export::{self, KeyMetric};
+use std::time::Duration;
+
+/// Graphite plaintext exporter settings (JETSONSCOPE_GRAPHITE_* environment variables).
+#[derive(Debug, Clone)]
+pub struct GraphiteConfig {
+    /// Carbon "host:port" (plaintext protocol, usually 2003)
+    pub addr: String,
+    /// Metric path root; paths are "<prefix>.<device>.<metric>"
+    pub prefix: String,
+    pub device: String,
+    pub interval: Duration,
+}
+
+impl GraphiteConfig {
+    /// `JETSONSCOPE_GRAPHITE_ADDR=host:2003` enables the exporter; `JETSONSCOPE_GRAPHITE_PREFIX`
+    /// (default "jetsonscope") and `JETSONSCOPE_GRAPHITE_INTERVAL` (secs, default 10) tune it.
+    pub fn from_env() -> Option<Self> {
+        let addr = std::env::var("JETSONSCOPE_GRAPHITE_ADDR").ok()?;
+        let prefix = std::env::var("JETSONSCOPE_GRAPHITE_PREFIX")
+            .ok()
+            .map(|p| p.trim_matches('.').to_string())
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| "jetsonscope".to_string());
+        let interval = std::env::var("JETSONSCOPE_GRAPHITE_INTERVAL")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(10)
+            .max(1);
+        Some(GraphiteConfig {
+            addr,
+            prefix,
+            device: export::sanitize_key(&export::device_name()),
+            interval: Duration::from_secs(interval),
+        })
+    }
+
+    /// Plaintext lines "<path> <value> <unix ts>\n" for one sample.
+    pub fn format_lines(&self, metrics: &[KeyMetric], unix_ts: i64) -> String {
+        metrics
+            .iter()
+            .map(|m| {
+                format!(
+                    "{}.{}.{} {} {}\n",
+                    self.prefix, self.device, m.key, m.value, unix_ts
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::MetricKind;
+
+    #[test]
+    fn formats_plaintext_lines() {
+        let cfg = GraphiteConfig {
+            addr: "localhost:2003".into(),
+            prefix: "plant.line3".into(),
+            device: "orin_1".into(),
+            interval: Duration::from_secs(10),
+        };
+        let metrics = vec![KeyMetric {
+            key: "temp_tj".into(),
+            name: "tj temperature".into(),
+            kind: MetricKind::Temperature,
+            value: 51.5,
+        }];
+        assert_eq!(
+            cfg.format_lines(&metrics, 1_700_000_000),
+            "plant.line3.orin_1.temp_tj 51.5 1700000000\n"
+        );
+    }
+}
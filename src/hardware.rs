@@ -1,6 +1,8 @@
+use crate::fans::FanInfo;
+use crate::parser::CpuCore;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::Path;
 
@@ -21,6 +23,351 @@ pub struct JetsonHardware {
     pub power_rails: Vec<String>,
     pub engines: Vec<String>,
     pub nvpmodel_modes: Vec<String>,
+    /// Per-mode clock caps and budgets from /etc/nvpmodel.conf
+    #[serde(default)]
+    pub nvpmodel_caps: Vec<NvpModelCaps>,
+    /// Frequency steps of every devfreq device (GPU, EMC and engines where exposed)
+    #[serde(default)]
+    pub frequencies: Vec<FrequencyCaps>,
+    /// PWM fan outputs from hwmon (controls fan0, fan1, ...)
+    #[serde(default)]
+    pub fans: Vec<FanInfo>,
+    /// CPUs grouped by shared cpufreq policy, e.g. 3x4 on AGX Orin
+    #[serde(default)]
+    pub cpu_clusters: Vec<CpuCluster>,
+    /// DRAM type and bus width of the module, for EMC bandwidth
+    #[serde(default)]
+    pub memory: Option<MemoryConfig>,
+}
+
+/// Frequencies one devfreq device accepts, from its `available_frequencies`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct FrequencyCaps {
+    /// Domain label, e.g. "GPU", "EMC", "NVENC"
+    pub domain: String,
+    /// Device name under /sys/class/devfreq
+    pub device: String,
+    /// Available steps, ascending
+    pub available_hz: Vec<u64>,
+    pub min_hz: u64,
+    pub max_hz: u64,
+    /// Spacing between steps, when they are evenly spaced
+    pub step_hz: Option<u64>,
+}
+
+impl FrequencyCaps {
+    pub fn from_steps(domain: &str, device: &str, mut steps: Vec<u64>) -> Option<Self> {
+        steps.sort_unstable();
+        steps.dedup();
+        let (min_hz, max_hz) = (*steps.first()?, *steps.last()?);
+        let gaps: Vec<u64> = steps.windows(2).map(|w| w[1] - w[0]).collect();
+        let step_hz = gaps.first().copied().filter(|g| gaps.iter().all(|x| x == g));
+        Some(FrequencyCaps {
+            domain: domain.to_string(),
+            device: device.to_string(),
+            available_hz: steps,
+            min_hz,
+            max_hz,
+            step_hz,
+        })
+    }
+
+    /// Name of the control that caps this domain, e.g. "gpu_max_freq".
+    pub fn control_name(&self) -> String {
+        format!("{}_max_freq", self.domain.to_lowercase())
+    }
+}
+
+/// DRAM behind the EMC, from the module's board id.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct MemoryConfig {
+    /// "LPDDR5", "LPDDR4x", "LPDDR4"
+    pub dram: String,
+    pub bus_width_bits: u32,
+}
+
+/// Memory traffic estimated from `EMC_FREQ n%@mhz` (snapshot field `emc_bandwidth`).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+pub struct EmcBandwidth {
+    pub gbps: f64,
+    /// What the bus moves at the current EMC clock when 100% busy
+    pub peak_gbps: f64,
+}
+
+impl MemoryConfig {
+    /// Bus width per module (Orin Nano 4GB has half the channels of the 8GB).
+    pub fn for_board(board_id: &str) -> Option<Self> {
+        let (dram, bus_width_bits) = match board_id {
+            id if id.starts_with("p3701") => ("LPDDR5", 256),
+            "p3767-0000" | "p3767-0001" | "p3767-0003" | "p3767-0005" => ("LPDDR5", 128),
+            "p3767-0004" => ("LPDDR5", 64),
+            id if id.starts_with("p2888") => ("LPDDR4x", 256),
+            id if id.starts_with("p3668") => ("LPDDR4x", 128),
+            id if id.starts_with("p3310") => ("LPDDR4", 128),
+            id if id.starts_with("p3448") || id.starts_with("p2180") => ("LPDDR4", 64),
+            _ => return None,
+        };
+        Some(MemoryConfig {
+            dram: dram.to_string(),
+            bus_width_bits,
+        })
+    }
+
+    /// The running module's configuration; `None` off a Jetson or on an unknown module.
+    pub fn detect() -> Option<Self> {
+        JetsonHardware::read_board_id().and_then(|(id, _)| Self::for_board(&id))
+    }
+
+    /// GB/s at `freq_mhz` EMC clock with the bus `usage_percent` busy. LPDDR
+    /// moves data on both clock edges, so the rate is twice the EMC clock.
+    pub fn bandwidth(&self, usage_percent: u32, freq_mhz: u32) -> EmcBandwidth {
+        let peak_gbps = freq_mhz as f64 * 1e6 * 2.0 * (self.bus_width_bits / 8) as f64 / 1e9;
+        EmcBandwidth {
+            gbps: peak_gbps * usage_percent.min(100) as f64 / 100.0,
+            peak_gbps,
+        }
+    }
+
+    /// Bandwidth from the sample's EMC engine, when it has both load and clock.
+    pub fn emc_bandwidth(&self, stats: &crate::parser::TegraStats) -> Option<EmcBandwidth> {
+        let emc = stats.engines.get("EMC")?;
+        Some(self.bandwidth(emc.usage_percent?, emc.freq_mhz?))
+    }
+}
+
+/// Where `read_cpu_clusters` looks on a running system.
+pub const CPU_SYSFS: &str = "/sys/devices/system/cpu";
+
+/// CPUs that share a cpufreq policy (and so a clock).
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct CpuCluster {
+    /// CPU numbers, ascending
+    pub cpus: Vec<u32>,
+    /// `cpuinfo_max_freq` in kHz
+    pub max_khz: Option<u64>,
+}
+
+/// Clusters from each `cpu<n>/cpufreq/related_cpus` under `base` (normally
+/// /sys/devices/system/cpu), ordered by their first CPU. CPUs whose whole
+/// cluster is offline have no cpufreq directory and belong to none.
+pub fn read_cpu_clusters(base: &Path) -> Vec<CpuCluster> {
+    let mut clusters: Vec<CpuCluster> = Vec::new();
+    let Ok(entries) = fs::read_dir(base) else {
+        return clusters;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.strip_prefix("cpu").and_then(|n| n.parse::<u32>().ok()).is_none() {
+            continue;
+        }
+        let cpufreq = entry.path().join("cpufreq");
+        let cpus = fs::read_to_string(cpufreq.join("related_cpus"))
+            .map(|s| parse_cpu_list(&s))
+            .unwrap_or_default();
+        if cpus.is_empty() || clusters.iter().any(|c| c.cpus == cpus) {
+            continue;
+        }
+        let max_khz = fs::read_to_string(cpufreq.join("cpuinfo_max_freq"))
+            .ok()
+            .and_then(|s| s.trim().parse().ok());
+        clusters.push(CpuCluster { cpus, max_khz });
+    }
+    clusters.sort_by_key(|c| c.cpus[0]);
+    clusters
+}
+
+/// A sysfs CPU list, "0-3,8" or "0 1 2 3", as ascending CPU numbers.
+pub fn parse_cpu_list(list: &str) -> Vec<u32> {
+    let mut cpus = Vec::new();
+    for part in list.split([',', ' ', '\n']).filter(|p| !p.is_empty()) {
+        match part.split_once('-') {
+            Some((from, to)) => {
+                if let (Ok(from), Ok(to)) = (from.parse::<u32>(), to.parse::<u32>()) {
+                    cpus.extend(from..=to);
+                }
+            }
+            None => cpus.extend(part.parse::<u32>().ok()),
+        }
+    }
+    cpus.sort_unstable();
+    cpus.dedup();
+    cpus
+}
+
+/// Set each core's `cluster` to the index of the cluster holding its CPU
+/// (tegrastats lists cores in CPU number order).
+pub fn assign_clusters(cores: &mut [CpuCore], clusters: &[CpuCluster]) {
+    for (cpu, core) in cores.iter_mut().enumerate() {
+        core.cluster = clusters
+            .iter()
+            .position(|c| c.cpus.contains(&(cpu as u32)))
+            .map(|i| i as u32);
+    }
+}
+
+/// DLA units with a `<addr>.nvdla<n>` platform device under `base` (normally
+/// /sys/devices/platform), looking a few levels down for the host1x and bus@0
+/// parents of different L4T releases.
+pub fn find_dla_units(base: &Path) -> Vec<u32> {
+    fn walk(dir: &Path, depth: usize, units: &mut Vec<u32>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let unit = name
+                .rsplit_once(".nvdla")
+                .and_then(|(_, n)| n.parse::<u32>().ok());
+            match unit {
+                Some(unit) if !units.contains(&unit) => units.push(unit),
+                Some(_) => {}
+                None if depth > 0 && entry.file_type().is_ok_and(|t| t.is_dir()) => {
+                    walk(&entry.path(), depth - 1, units)
+                }
+                None => {}
+            }
+        }
+    }
+    let mut units = Vec::new();
+    walk(base, 2, &mut units);
+    units.sort_unstable();
+    units
+}
+
+/// Read `available_frequencies` of every device under `base` (normally /sys/class/devfreq).
+pub fn read_devfreq(base: &Path) -> Vec<FrequencyCaps> {
+    let mut out: Vec<FrequencyCaps> = Vec::new();
+    let Ok(entries) = fs::read_dir(base) else {
+        return out;
+    };
+    for entry in entries.flatten() {
+        let device = entry.file_name().to_string_lossy().to_string();
+        let steps = fs::read_to_string(entry.path().join("available_frequencies"))
+            .map(|s| s.split_whitespace().filter_map(|f| f.parse().ok()).collect())
+            .unwrap_or_default();
+        if let Some(caps) = FrequencyCaps::from_steps(&devfreq_domain(&device), &device, steps) {
+            out.push(caps);
+        }
+    }
+    out.sort_by(|a, b| a.domain.cmp(&b.domain));
+    out
+}
+
+/// "17000000.gpu" -> "GPU", "154c0000.nvenc" -> "NVENC".
+pub fn devfreq_domain(device: &str) -> String {
+    let name = device.rsplit('.').next().unwrap_or(device).to_lowercase();
+    match name.as_str() {
+        "gpu" | "gv11b" | "gp10b" | "ga10b" | "gm20b" => "GPU".to_string(),
+        _ => name.to_uppercase(),
+    }
+}
+
+/// Limits one nvpmodel mode applies, as declared in /etc/nvpmodel.conf.
+///
+/// `None` frequencies mean the mode leaves that clock uncapped (-1/0 in the conf).
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct NvpModelCaps {
+    pub id: u32,
+    pub name: String,
+    /// Cores the mode keeps online (CPU_ONLINE entries set to 1)
+    pub cpu_online: Option<u32>,
+    /// Max CPU frequency per cluster, e.g. {"CPU_A78_0": 1113600} (kHz)
+    pub cpu_max_khz: BTreeMap<String, u64>,
+    pub gpu_max_hz: Option<u64>,
+    pub emc_max_hz: Option<u64>,
+    /// Power budget in watts, taken from the mode name (e.g. "MODE_15W", "10W")
+    pub power_budget_w: Option<u32>,
+}
+
+impl NvpModelCaps {
+    /// One-line summary, e.g. "15W, 4 cores, CPU<=1113MHz, GPU<=420MHz, EMC<=2133MHz"
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(w) = self.power_budget_w {
+            parts.push(format!("{}W", w));
+        }
+        if let Some(n) = self.cpu_online {
+            parts.push(format!("{} core{}", n, if n == 1 { "" } else { "s" }));
+        }
+        if let Some(khz) = self.cpu_max_khz.values().max() {
+            parts.push(format!("CPU<={}MHz", khz / 1000));
+        }
+        if let Some(hz) = self.gpu_max_hz {
+            parts.push(format!("GPU<={}MHz", hz / 1_000_000));
+        }
+        if let Some(hz) = self.emc_max_hz {
+            parts.push(format!("EMC<={}MHz", hz / 1_000_000));
+        }
+        if parts.is_empty() {
+            "sin limites".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+/// Parse the `< POWER_MODEL ID=.. NAME=.. >` sections of an nvpmodel.conf.
+pub fn parse_nvpmodel_conf(content: &str) -> Vec<NvpModelCaps> {
+    let mut modes: Vec<NvpModelCaps> = Vec::new();
+    let mut in_mode = false;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('#') || line.is_empty() {
+            continue;
+        }
+        if line.starts_with('<') {
+            // < POWER_MODEL ID=0 NAME=MAXN > (older files use "< MODEL")
+            in_mode = line.starts_with("< POWER_MODEL") || line.starts_with("< MODEL");
+            if in_mode {
+                let mut caps = NvpModelCaps::default();
+                for part in line.trim_matches(|c| c == '<' || c == '>').split_whitespace() {
+                    if let Some(id) = part.strip_prefix("ID=") {
+                        caps.id = id.parse().unwrap_or(modes.len() as u32);
+                    } else if let Some(name) = part.strip_prefix("NAME=") {
+                        caps.name = name.to_string();
+                    }
+                }
+                caps.power_budget_w = budget_from_name(&caps.name);
+                modes.push(caps);
+            }
+            continue;
+        }
+        let Some(caps) = modes.last_mut().filter(|_| in_mode) else {
+            continue;
+        };
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match tokens.as_slice() {
+            ["CPU_ONLINE", _, state] => {
+                let online = caps.cpu_online.get_or_insert(0);
+                if *state == "1" {
+                    *online += 1;
+                }
+            }
+            [clock, "MAX_FREQ", value] => {
+                // -1 (and 0 for EMC) leave the clock at its maximum
+                let Some(freq) = value.parse::<i64>().ok().filter(|v| *v > 0) else {
+                    continue;
+                };
+                let freq = freq as u64;
+                match *clock {
+                    "GPU" => caps.gpu_max_hz = Some(freq),
+                    "EMC" => caps.emc_max_hz = Some(freq),
+                    c if c.starts_with("CPU") => {
+                        caps.cpu_max_khz.insert(c.to_string(), freq);
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    modes
+}
+
+fn budget_from_name(name: &str) -> Option<u32> {
+    name.split(['_', '-'])
+        .find_map(|part| part.strip_suffix('W').or_else(|| part.strip_suffix('w')))
+        .and_then(|n| n.parse().ok())
 }
 
 static MODULE_NAME_TABLE: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
@@ -67,6 +414,10 @@ impl JetsonHardware {
             hw.power_rails = Self::detect_power_rails();
             hw.engines = Self::detect_engines();
             hw.nvpmodel_modes = Self::detect_nvpmodel_modes();
+            hw.nvpmodel_caps = Self::detect_nvpmodel_caps();
+            hw.frequencies = Self::detect_frequencies();
+            hw.fans = crate::fans::detect();
+            hw.cpu_clusters = read_cpu_clusters(Path::new(CPU_SYSFS));
         } else {
             // Fallback for dev/emulator
             hw.is_jetson = false;
@@ -82,10 +433,7 @@ impl JetsonHardware {
         // 3. Read SoC (compatible)
         if let Ok(compatible) = fs::read_to_string("/proc/device-tree/compatible") {
             let parts: Vec<&str> = compatible.split('\0').collect();
-            let maybe_last = parts.iter().rev().find(|item| match item {
-                s if !s.is_empty() => true,
-                _ => false,
-            });
+            let maybe_last = parts.iter().rev().find(|item| !item.is_empty());
             if let Some(last) = maybe_last {
                 // usually something like "nvidia,tegra234"
                 if let Some(soc) = last.split(',').nth(1) {
@@ -103,26 +451,28 @@ impl JetsonHardware {
         }
 
         // 5. Try to identify specific module via dtsfilename or boardids
-        // This is a simplified version of jtop's logic
-        if let Ok(dts) = fs::read_to_string("/proc/device-tree/nvidia,dtsfilename") {
-            // Example: /dvs/git/dirty/git-master_linux/kernel/kernel-5.10/arch/arm64/boot/dts/../../../../../../hardware/nvidia/platform/t23x/p3768/kernel-dts/tegra234-p3701-0000-p3737-0000.dts
-            // We look for pXXXX-XXXX patterns
-            let parts: Vec<&str> = dts.split('/').collect();
-            if let Some(filename) = parts.last() {
-                // Try to match pXXXX-XXXX
-                for (id, name) in MODULE_NAME_TABLE.iter() {
-                    if filename.contains(id) {
-                        hw.module = name.to_string();
-                        hw.board_id = id.to_string();
-                        break;
-                    }
-                }
-            }
+        if let Some((id, name)) = Self::read_board_id() {
+            hw.memory = MemoryConfig::for_board(&id);
+            hw.board_id = id;
+            hw.module = name;
         }
 
         hw
     }
 
+    /// Module board id and name from the device tree's dts file name.
+    /// This is a simplified version of jtop's logic.
+    pub fn read_board_id() -> Option<(String, String)> {
+        let dts = fs::read_to_string("/proc/device-tree/nvidia,dtsfilename").ok()?;
+        // Example: /dvs/git/dirty/git-master_linux/kernel/kernel-5.10/arch/arm64/boot/dts/../../../../../../hardware/nvidia/platform/t23x/p3768/kernel-dts/tegra234-p3701-0000-p3737-0000.dts
+        // We look for pXXXX-XXXX patterns
+        let filename = dts.split('/').next_back()?;
+        MODULE_NAME_TABLE
+            .iter()
+            .find(|(id, _)| filename.contains(*id))
+            .map(|(id, name)| (id.to_string(), name.to_string()))
+    }
+
     pub fn detect_governors() -> Vec<String> {
         let mut govs = Vec::new();
         if let Ok(entries) = fs::read_dir("/sys/devices/system/cpu") {
@@ -183,22 +533,21 @@ impl JetsonHardware {
     }
 
     pub fn detect_nvpmodel_modes() -> Vec<String> {
-        let mut modes = Vec::new();
-        if let Ok(content) = fs::read_to_string("/etc/nvpmodel.conf") {
-            for line in content.lines() {
-                if line.trim().starts_with("< MODEL") {
-                    // < MODEL ID=0 NAME=MAXN >
-                    let parts: Vec<&str> = line.split_whitespace().collect();
-                    for part in parts {
-                        if part.starts_with("NAME=") {
-                            let name = part.replace("NAME=", "").replace(">", "");
-                            modes.push(name);
-                        }
-                    }
-                }
-            }
-        }
-        modes
+        Self::detect_nvpmodel_caps()
+            .into_iter()
+            .map(|m| m.name)
+            .filter(|n| !n.is_empty())
+            .collect()
+    }
+
+    pub fn detect_nvpmodel_caps() -> Vec<NvpModelCaps> {
+        fs::read_to_string("/etc/nvpmodel.conf")
+            .map(|content| parse_nvpmodel_conf(&content))
+            .unwrap_or_default()
+    }
+
+    pub fn detect_frequencies() -> Vec<FrequencyCaps> {
+        read_devfreq(Path::new("/sys/class/devfreq"))
     }
 
     pub fn detect_fan() -> bool {
@@ -261,13 +610,157 @@ impl JetsonHardware {
     }
 
     pub fn detect_engines() -> Vec<String> {
-        vec![
-            "GR3D".to_string(),
-            "EMC".to_string(),
-            "NVENC".to_string(),
-            "NVDEC".to_string(),
-            "VIC".to_string(),
-            "NVJPG".to_string(),
-        ]
+        let mut engines: Vec<String> = ["GR3D", "EMC", "NVENC", "NVDEC", "VIC", "NVJPG"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        // Xavier and AGX/NX Orin have two DLAs; Orin Nano and older boards none
+        for unit in find_dla_units(Path::new("/sys/devices/platform")) {
+            engines.push(format!("DLA{unit}_CORE"));
+            engines.push(format!("DLA{unit}_FALCON"));
+        }
+        engines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+< PARAM TYPE=CLOCK NAME=GPU >
+MAX_FREQ /sys/devices/platform/17000000.gpu/devfreq_dev/max_freq
+< POWER_MODEL ID=0 NAME=MAXN >
+CPU_ONLINE CORE_0 1
+CPU_ONLINE CORE_1 1
+CPU_A78_0 MAX_FREQ -1
+GPU MAX_FREQ -1
+EMC MAX_FREQ 0
+< POWER_MODEL ID=1 NAME=MODE_15W >
+CPU_ONLINE CORE_0 1
+CPU_ONLINE CORE_1 0
+CPU_A78_0 MAX_FREQ 1113600
+GPU MAX_FREQ 420750000
+EMC MAX_FREQ 2133000000
+< PM_CONFIG DEFAULT=1 >
+";
+
+    #[test]
+    fn parses_mode_caps() {
+        let modes = parse_nvpmodel_conf(SAMPLE);
+        assert_eq!(modes.len(), 2);
+        let maxn = &modes[0];
+        assert_eq!((maxn.id, maxn.name.as_str()), (0, "MAXN"));
+        assert_eq!(maxn.cpu_online, Some(2));
+        assert!(maxn.cpu_max_khz.is_empty());
+        assert_eq!((maxn.gpu_max_hz, maxn.emc_max_hz, maxn.power_budget_w), (None, None, None));
+
+        let m15 = &modes[1];
+        assert_eq!(m15.power_budget_w, Some(15));
+        assert_eq!(m15.cpu_online, Some(1));
+        assert_eq!(m15.cpu_max_khz["CPU_A78_0"], 1_113_600);
+        assert_eq!(
+            m15.describe(),
+            "15W, 1 core, CPU<=1113MHz, GPU<=420MHz, EMC<=2133MHz"
+        );
+    }
+
+    #[test]
+    fn reads_devfreq_steps() {
+        let base = std::env::temp_dir().join(format!("jscope-devfreq-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        for (device, freqs) in [
+            ("17000000.gpu", "918000000 306000000 612000000 0x"),
+            ("15480000.nvdec", "115200000 268800000 998400000"),
+            ("broken.vic", ""),
+        ] {
+            fs::create_dir_all(base.join(device)).unwrap();
+            fs::write(base.join(device).join("available_frequencies"), freqs).unwrap();
+        }
+        let caps = read_devfreq(&base);
+        let _ = fs::remove_dir_all(&base);
+
+        assert_eq!(caps.len(), 2);
+        let gpu = &caps[0];
+        assert_eq!((gpu.domain.as_str(), gpu.device.as_str()), ("GPU", "17000000.gpu"));
+        assert_eq!(gpu.available_hz, vec![306_000_000, 612_000_000, 918_000_000]);
+        assert_eq!((gpu.min_hz, gpu.max_hz), (306_000_000, 918_000_000));
+        assert_eq!(gpu.step_hz, Some(306_000_000));
+        assert_eq!(gpu.control_name(), "gpu_max_freq");
+        assert_eq!(caps[1].domain, "NVDEC");
+        assert_eq!(caps[1].step_hz, None);
+    }
+
+    #[test]
+    fn reads_cpu_clusters_from_related_cpus() {
+        let base = std::env::temp_dir().join(format!("jscope-clusters-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        for cpu in 0..8 {
+            let cpufreq = base.join(format!("cpu{cpu}/cpufreq"));
+            // CPU 7 is offline with its whole cluster gone: no cpufreq directory
+            if cpu == 7 {
+                fs::create_dir_all(base.join("cpu7")).unwrap();
+                continue;
+            }
+            fs::create_dir_all(&cpufreq).unwrap();
+            let (related, max) = if cpu < 4 { ("0-3", "1984000") } else { ("4 5 6", "1510400") };
+            fs::write(cpufreq.join("related_cpus"), format!("{related}\n")).unwrap();
+            fs::write(cpufreq.join("cpuinfo_max_freq"), max).unwrap();
+        }
+        fs::create_dir_all(base.join("cpufreq")).unwrap();
+        let clusters = read_cpu_clusters(&base);
+        let _ = fs::remove_dir_all(&base);
+
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].cpus, vec![0, 1, 2, 3]);
+        assert_eq!(clusters[0].max_khz, Some(1_984_000));
+        assert_eq!(clusters[1].cpus, vec![4, 5, 6]);
+        assert_eq!(parse_cpu_list("0-1,4,6-7"), vec![0, 1, 4, 6, 7]);
+
+        let mut cores = vec![CpuCore::default(); 8];
+        assign_clusters(&mut cores, &clusters);
+        let assigned: Vec<Option<u32>> = cores.iter().map(|c| c.cluster).collect();
+        assert_eq!(assigned, [Some(0), Some(0), Some(0), Some(0), Some(1), Some(1), Some(1), None]);
+    }
+
+    #[test]
+    fn estimates_emc_bandwidth_from_bus_width() {
+        let agx = MemoryConfig::for_board("p3701-0005").unwrap();
+        assert_eq!((agx.dram.as_str(), agx.bus_width_bits), ("LPDDR5", 256));
+        // 256-bit LPDDR5 at 3199 MHz: 6398 MT/s x 32 bytes
+        let full = agx.bandwidth(100, 3199);
+        assert!((full.peak_gbps - 204.736).abs() < 1e-9);
+        assert!((agx.bandwidth(45, 3199).gbps - 92.1312).abs() < 1e-9);
+
+        let nano = MemoryConfig::for_board("p3448-0002").unwrap();
+        assert!((nano.bandwidth(50, 1600).gbps - 12.8).abs() < 1e-9);
+        let nano_4gb = MemoryConfig::for_board("p3767-0004").unwrap();
+        assert_eq!(nano_4gb.bus_width_bits, 64);
+        assert!(MemoryConfig::for_board("p9999-0000").is_none());
+
+        let stats = crate::parser::TegraStats::parse("EMC_FREQ 4%@2133 GR3D_FREQ 0%@[305]").unwrap();
+        let orin_nx = MemoryConfig::for_board("p3767-0000").unwrap();
+        let emc = orin_nx.emc_bandwidth(&stats).unwrap();
+        assert!((emc.gbps - 2.730_24).abs() < 1e-9);
+        assert!(orin_nx.emc_bandwidth(&crate::parser::TegraStats::default()).is_none());
+    }
+
+    #[test]
+    fn finds_dla_units_below_host1x() {
+        let base = std::env::temp_dir().join(format!("jscope-dla-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        for dir in [
+            "bus@0/13e00000.host1x/158c0000.nvdla1",
+            "bus@0/13e00000.host1x/15880000.nvdla0",
+            "bus@0/13e00000.host1x/15340000.vic",
+        ] {
+            fs::create_dir_all(base.join(dir)).unwrap();
+        }
+        let units = find_dla_units(&base);
+        let none = find_dla_units(&base.join("bus@0/13e00000.host1x/15340000.vic"));
+        let _ = fs::remove_dir_all(&base);
+
+        assert_eq!(units, vec![0, 1]);
+        assert!(none.is_empty());
     }
 }
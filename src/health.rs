@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
-use std::time::Instant;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 /// Daemon health and telemetry information
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,10 +14,72 @@ pub struct DaemonHealth {
     pub errors: u64,
     /// Last error message (if any)
     pub last_error: Option<String>,
-    /// Number of currently connected clients
+    /// Open socket connections, WebSocket streams and HTTP requests being answered
     pub connected_clients: usize,
     /// Total stats collected
     pub stats_collected: u64,
+    /// Samples the collector dropped because the daemon fell behind
+    #[serde(default)]
+    pub dropped_samples: u64,
+    /// Stats command lines that yielded no data
+    #[serde(default)]
+    pub parse_failures: u64,
+    /// The most recent of them
+    #[serde(default)]
+    pub last_parse_failure: Option<String>,
+    /// Time between the last two samples
+    #[serde(default)]
+    pub sample_interval_ms: Option<u64>,
+    /// Longest time between two samples since the daemon started
+    #[serde(default)]
+    pub max_sample_interval_ms: u64,
+    /// Requests handled per type ("GetStats", "SetControl", ...)
+    #[serde(default)]
+    pub requests_by_type: BTreeMap<String, u64>,
+    /// Errors per code ("auth_failed", "collector", ...)
+    #[serde(default)]
+    pub errors_by_code: BTreeMap<String, u64>,
+    /// The last `RECENT_ERRORS` errors, oldest first
+    #[serde(default)]
+    pub recent_errors: Vec<ErrorRecord>,
+}
+
+/// How many errors `DaemonHealth::recent_errors` keeps
+pub const RECENT_ERRORS: usize = 32;
+
+/// One recorded error
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorRecord {
+    pub unix_ms: u64,
+    pub code: String,
+    pub message: String,
+}
+
+/// Upper bounds (seconds) of the request latency histogram buckets
+pub const LATENCY_BUCKETS: [f64; 12] = [
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5,
+];
+
+/// Request durations in Prometheus histogram form.
+#[derive(Debug, Clone, Default)]
+pub struct LatencyHistogram {
+    /// Observations at or below each of `LATENCY_BUCKETS`, cumulative
+    pub buckets: [u64; LATENCY_BUCKETS.len()],
+    pub count: u64,
+    pub sum_secs: f64,
+}
+
+impl LatencyHistogram {
+    pub fn observe(&mut self, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        for (bucket, le) in self.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if secs <= le {
+                *bucket += 1;
+            }
+        }
+        self.count += 1;
+        self.sum_secs += secs;
+    }
 }
 
 /// Health tracker for daemon
@@ -26,6 +90,18 @@ pub struct HealthTracker {
     errors: u64,
     last_error: Option<String>,
     stats_collected: u64,
+    dropped_samples: u64,
+    last_stats: Option<Instant>,
+    connected_clients: usize,
+    parse_failures: u64,
+    last_parse_failure: Option<String>,
+    sample_interval: Option<Duration>,
+    max_sample_interval: Duration,
+    requests_by_type: BTreeMap<&'static str, u64>,
+    errors_by_code: BTreeMap<String, u64>,
+    recent_errors: VecDeque<ErrorRecord>,
+    /// Keyed by request name ("GetStats", ...)
+    latencies: BTreeMap<&'static str, LatencyHistogram>,
 }
 
 impl Default for HealthTracker {
@@ -43,6 +119,17 @@ impl HealthTracker {
             errors: 0,
             last_error: None,
             stats_collected: 0,
+            dropped_samples: 0,
+            last_stats: None,
+            connected_clients: 0,
+            parse_failures: 0,
+            last_parse_failure: None,
+            sample_interval: None,
+            max_sample_interval: Duration::ZERO,
+            requests_by_type: BTreeMap::new(),
+            errors_by_code: BTreeMap::new(),
+            recent_errors: VecDeque::with_capacity(RECENT_ERRORS),
+            latencies: BTreeMap::new(),
         }
     }
 
@@ -50,23 +137,189 @@ impl HealthTracker {
         self.total_requests += 1;
     }
 
-    pub fn record_error(&mut self, error: String) {
+    /// Count a decoded request by type (`Request::name`).
+    pub fn record_request_type(&mut self, request: &'static str) {
+        *self.requests_by_type.entry(request).or_default() += 1;
+    }
+
+    /// Time the daemon took to answer a `request` (`Request::name`).
+    pub fn record_latency(&mut self, request: &'static str, elapsed: Duration) {
+        self.latencies.entry(request).or_default().observe(elapsed);
+    }
+
+    pub fn latencies(&self) -> &BTreeMap<&'static str, LatencyHistogram> {
+        &self.latencies
+    }
+
+    pub fn record_error(&mut self, code: &str, error: String) {
         self.errors += 1;
+        *self.errors_by_code.entry(code.to_string()).or_default() += 1;
+        if self.recent_errors.len() == RECENT_ERRORS {
+            self.recent_errors.pop_front();
+        }
+        self.recent_errors.push_back(ErrorRecord {
+            unix_ms: crate::history::now_unix_ms(),
+            code: code.to_string(),
+            message: error.clone(),
+        });
         self.last_error = Some(error);
     }
 
     pub fn record_stats_collection(&mut self) {
+        self.record_stats_at(Instant::now());
+    }
+
+    fn record_stats_at(&mut self, now: Instant) {
         self.stats_collected += 1;
+        if let Some(last) = self.last_stats {
+            let gap = now.saturating_duration_since(last);
+            self.sample_interval = Some(gap);
+            self.max_sample_interval = self.max_sample_interval.max(gap);
+        }
+        self.last_stats = Some(now);
+    }
+
+    /// A stats command line the parser got nothing from (`CollectorMessage::ParseFailed`).
+    pub fn record_parse_failure(&mut self, line: String) {
+        self.parse_failures += 1;
+        self.last_parse_failure = Some(line);
+    }
+
+    /// Time between the last two samples; `None` before the second one.
+    pub fn sample_interval(&self) -> Option<Duration> {
+        self.sample_interval
+    }
+
+    pub fn max_sample_interval(&self) -> Duration {
+        self.max_sample_interval
     }
 
-    pub fn get_health(&self, connected_clients: usize) -> DaemonHealth {
+    /// Running total from the collector channel (`CollectorReceiver::dropped`).
+    pub fn set_dropped_samples(&mut self, dropped: u64) {
+        self.dropped_samples = dropped;
+    }
+
+    /// Time since the last sample arrived; `None` before the first one.
+    pub fn last_stats_age(&self) -> Option<Duration> {
+        self.last_stats.map(|t| t.elapsed())
+    }
+
+    /// Count a client until the returned guard is dropped.
+    pub fn client_connected(health: &Arc<Mutex<HealthTracker>>) -> ClientGuard {
+        if let Ok(mut h) = health.lock() {
+            h.connected_clients += 1;
+        }
+        ClientGuard {
+            health: health.clone(),
+        }
+    }
+
+    pub fn get_health(&self) -> DaemonHealth {
         DaemonHealth {
             uptime_secs: self.start_time.elapsed().as_secs(),
             total_requests: self.total_requests,
             errors: self.errors,
             last_error: self.last_error.clone(),
-            connected_clients,
+            connected_clients: self.connected_clients,
             stats_collected: self.stats_collected,
+            dropped_samples: self.dropped_samples,
+            parse_failures: self.parse_failures,
+            last_parse_failure: self.last_parse_failure.clone(),
+            sample_interval_ms: self.sample_interval.map(|d| d.as_millis() as u64),
+            max_sample_interval_ms: self.max_sample_interval.as_millis() as u64,
+            requests_by_type: self
+                .requests_by_type
+                .iter()
+                .map(|(request, n)| (request.to_string(), *n))
+                .collect(),
+            errors_by_code: self.errors_by_code.clone(),
+            recent_errors: self.recent_errors.iter().cloned().collect(),
+        }
+    }
+}
+
+/// A connected client (`HealthTracker::client_connected`); dropping it
+/// counts the client as gone.
+pub struct ClientGuard {
+    health: Arc<Mutex<HealthTracker>>,
+}
+
+impl Drop for ClientGuard {
+    fn drop(&mut self) {
+        if let Ok(mut h) = self.health.lock() {
+            h.connected_clients = h.connected_clients.saturating_sub(1);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latency_buckets_are_cumulative() {
+        let mut tracker = HealthTracker::new();
+        tracker.record_latency("GetStats", Duration::from_micros(300));
+        tracker.record_latency("GetStats", Duration::from_millis(30));
+        tracker.record_latency("GetMeta", Duration::from_secs(5));
+
+        let stats = &tracker.latencies()["GetStats"];
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.buckets[0], 1);
+        assert_eq!(stats.buckets[LATENCY_BUCKETS.len() - 1], 2);
+        assert!((stats.sum_secs - 0.0303).abs() < 1e-9);
+        // Slower than the last bucket: only in +Inf (count)
+        let meta = &tracker.latencies()["GetMeta"];
+        assert_eq!(meta.buckets.iter().sum::<u64>(), 0);
+        assert_eq!(meta.count, 1);
+    }
+
+    #[test]
+    fn clients_count_until_their_guard_drops() {
+        let health = Arc::new(Mutex::new(HealthTracker::new()));
+        let a = HealthTracker::client_connected(&health);
+        let b = HealthTracker::client_connected(&health);
+        assert_eq!(health.lock().unwrap().get_health().connected_clients, 2);
+        drop(a);
+        assert_eq!(health.lock().unwrap().get_health().connected_clients, 1);
+        drop(b);
+        assert_eq!(health.lock().unwrap().get_health().connected_clients, 0);
+    }
+
+    #[test]
+    fn errors_are_counted_by_code_and_the_newest_kept() {
+        let mut tracker = HealthTracker::new();
+        for i in 0..40 {
+            tracker.record_error(if i % 4 == 0 { "auth_failed" } else { "collector" }, format!("error {i}"));
+        }
+        tracker.record_request_type("GetStats");
+        tracker.record_request_type("GetStats");
+        let health = tracker.get_health();
+        assert_eq!(health.errors, 40);
+        assert_eq!(health.errors_by_code["auth_failed"], 10);
+        assert_eq!(health.errors_by_code["collector"], 30);
+        assert_eq!(health.recent_errors.len(), RECENT_ERRORS);
+        assert_eq!(health.recent_errors[0].message, "error 8");
+        assert_eq!(health.recent_errors[RECENT_ERRORS - 1].message, "error 39");
+        assert_eq!(health.last_error.as_deref(), Some("error 39"));
+        assert_eq!(health.requests_by_type["GetStats"], 2);
+    }
+
+    #[test]
+    fn sample_gaps_and_parse_failures_are_tracked() {
+        let mut tracker = HealthTracker::new();
+        let start = Instant::now();
+        tracker.record_stats_at(start);
+        assert!(tracker.sample_interval().is_none());
+        tracker.record_stats_at(start + Duration::from_millis(4000));
+        tracker.record_stats_at(start + Duration::from_millis(5000));
+        tracker.record_parse_failure("tegrastats: warning".to_string());
+
+        let health = tracker.get_health();
+        assert_eq!(health.stats_collected, 3);
+        assert_eq!(health.sample_interval_ms, Some(1000));
+        assert_eq!(health.max_sample_interval_ms, 4000);
+        assert_eq!(health.parse_failures, 1);
+        assert_eq!(health.last_parse_failure.as_deref(), Some("tegrastats: warning"));
+    }
+}
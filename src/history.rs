@@ -0,0 +1,343 @@
+//! Daemon-side trend history, so clients can backfill their charts on connect.
+//!
+//! Full `TegraStats` samples are kept for the last JETSONSCOPE_HISTORY_MINUTES
+//! (default 10, 5 to 60); older data is averaged into 10 s trend buckets for up
+//! to 24 h. `query` turns either tier into trend points and downsamples them to
+//! the number of points the client can draw; `query_stats` thins the full
+//! samples for tools that need every rail and sensor (GetHistory with `full`).
+//!
+//! `record` also feeds a `stats::Aggregator` for min/max/mean/p95 summaries
+//! (GetSummary).
+
+use crate::parser::TegraStats;
+use crate::stats::{Aggregator, Summary};
+use crate::thermal_governor::valid_temp;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Trend windows up to this long are served from the full samples.
+const FINE_SPAN_MS: u64 = 5 * 60 * 1000;
+/// Cap on kept samples per second of span, in case the collector samples much faster than 1 Hz.
+const MAX_SAMPLES_PER_SEC: u64 = 10;
+/// Bucket width and span of the coarse tier.
+const COARSE_STEP_MS: u64 = 10 * 1000;
+const COARSE_SPAN_MS: u64 = 24 * 60 * 60 * 1000;
+/// Full samples are kept this long unless JETSONSCOPE_HISTORY_MINUTES says otherwise.
+const DEFAULT_SAMPLE_MINUTES: u64 = 10;
+const MIN_SAMPLE_MINUTES: u64 = FINE_SPAN_MS / 60_000;
+const MAX_SAMPLE_MINUTES: u64 = 60;
+
+/// One trend sample: the values the TUI history charts plot.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct HistoryPoint {
+    /// Sample time, ms since the Unix epoch
+    pub unix_ms: u64,
+    /// RAM used (%)
+    pub ram: f64,
+    /// GR3D load (%)
+    pub gpu: f64,
+    /// Mean CPU core load (%)
+    pub cpu: f64,
+    /// Hottest valid sensor (°C)
+    pub temp: f64,
+}
+
+impl HistoryPoint {
+    pub fn from_stats(stats: &TegraStats, unix_ms: u64) -> Self {
+        let ram = stats.ram.as_ref().map_or(0.0, |r| {
+            if r.total_bytes == 0 {
+                0.0
+            } else {
+                r.used_bytes as f64 / r.total_bytes as f64 * 100.0
+            }
+        });
+        let cpu = if stats.cpus.is_empty() {
+            0.0
+        } else {
+            let sum: f64 = stats
+                .cpus
+                .iter()
+                .filter_map(|c| c.load_percent)
+                .map(|v| v as f64)
+                .sum();
+            sum / stats.cpus.len() as f64
+        };
+        // -256C marks an offline sensor
+        let temp = stats
+            .temps
+            .values()
+            .filter(|t| valid_temp(**t))
+            .fold(0.0f64, |acc, t| acc.max(*t as f64));
+        HistoryPoint {
+            unix_ms,
+            ram,
+            gpu: stats.gpu_usage().map_or(0.0, |g| g as f64),
+            cpu,
+            temp,
+        }
+    }
+
+    /// Mean of `points`, stamped with the last one's time.
+    fn average(points: &[HistoryPoint]) -> Option<Self> {
+        let last = points.last()?;
+        let n = points.len() as f64;
+        let mean = |f: fn(&HistoryPoint) -> f64| points.iter().map(f).sum::<f64>() / n;
+        Some(HistoryPoint {
+            unix_ms: last.unix_ms,
+            ram: mean(|p| p.ram),
+            gpu: mean(|p| p.gpu),
+            cpu: mean(|p| p.cpu),
+            temp: mean(|p| p.temp),
+        })
+    }
+}
+
+/// A full sample and when it was taken (for GetHistory with `full`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsSample {
+    /// Sample time, ms since the Unix epoch
+    pub unix_ms: u64,
+    pub stats: TegraStats,
+}
+
+/// Current time in ms since the Unix epoch.
+pub fn now_unix_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone)]
+pub struct HistoryRing {
+    coarse: VecDeque<HistoryPoint>,
+    /// Samples of the coarse bucket still being filled
+    bucket: Vec<HistoryPoint>,
+    samples: VecDeque<StatsSample>,
+    sample_span_ms: u64,
+    /// Min/max/mean/p95 per series (GetSummary)
+    summaries: Aggregator,
+}
+
+impl Default for HistoryRing {
+    fn default() -> Self {
+        Self::with_sample_minutes(DEFAULT_SAMPLE_MINUTES)
+    }
+}
+
+impl HistoryRing {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keeping full samples for `minutes` (5 to 60).
+    pub fn with_sample_minutes(minutes: u64) -> Self {
+        HistoryRing {
+            coarse: VecDeque::new(),
+            bucket: Vec::new(),
+            samples: VecDeque::new(),
+            sample_span_ms: minutes.clamp(MIN_SAMPLE_MINUTES, MAX_SAMPLE_MINUTES) * 60 * 1000,
+            summaries: Aggregator::new(),
+        }
+    }
+
+    /// Full-sample span from JETSONSCOPE_HISTORY_MINUTES.
+    pub fn from_env() -> Self {
+        let minutes = std::env::var("JETSONSCOPE_HISTORY_MINUTES")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_SAMPLE_MINUTES);
+        Self::with_sample_minutes(minutes)
+    }
+
+    /// Add a collected sample: the sample itself, and its trend point to the coarse tier.
+    pub fn record(&mut self, stats: &TegraStats, unix_ms: u64) {
+        self.summaries.push(stats, unix_ms);
+        self.samples.push_back(StatsSample {
+            unix_ms,
+            stats: stats.clone(),
+        });
+        let max_samples = (self.sample_span_ms / 1000 * MAX_SAMPLES_PER_SEC) as usize;
+        while self.samples.len() > max_samples
+            || self
+                .samples
+                .front()
+                .is_some_and(|s| unix_ms.saturating_sub(s.unix_ms) > self.sample_span_ms)
+        {
+            self.samples.pop_front();
+        }
+
+        let point = HistoryPoint::from_stats(stats, unix_ms);
+        let same_bucket = self
+            .bucket
+            .first()
+            .is_some_and(|p| p.unix_ms / COARSE_STEP_MS == unix_ms / COARSE_STEP_MS);
+        if !same_bucket {
+            if let Some(avg) = HistoryPoint::average(&self.bucket) {
+                self.coarse.push_back(avg);
+            }
+            self.bucket.clear();
+        }
+        self.bucket.push(point);
+        while self
+            .coarse
+            .front()
+            .is_some_and(|p| unix_ms.saturating_sub(p.unix_ms) > COARSE_SPAN_MS)
+        {
+            self.coarse.pop_front();
+        }
+    }
+
+    /// Points from the last `since_secs`, oldest first, averaged down to at most `max_points`.
+    pub fn query(&self, since_secs: u64, max_points: usize, now_ms: u64) -> Vec<HistoryPoint> {
+        let window_ms = since_secs.saturating_mul(1000);
+        let points: Vec<HistoryPoint> = if window_ms <= FINE_SPAN_MS {
+            self.samples
+                .iter()
+                .map(|s| HistoryPoint::from_stats(&s.stats, s.unix_ms))
+                .collect()
+        } else {
+            // The open bucket covers the newest seconds
+            self.coarse
+                .iter()
+                .copied()
+                .chain(HistoryPoint::average(&self.bucket))
+                .collect()
+        };
+        let recent: Vec<HistoryPoint> = points
+            .into_iter()
+            .filter(|p| now_ms.saturating_sub(p.unix_ms) <= window_ms)
+            .collect();
+        if max_points == 0 || recent.len() <= max_points {
+            return recent;
+        }
+        // Average into `max_points` equal time slots so gaps stay visible
+        let start = now_ms.saturating_sub(window_ms);
+        let slot_ms = window_ms.div_ceil(max_points as u64).max(1);
+        let slot = |p: &HistoryPoint| {
+            ((p.unix_ms.saturating_sub(start) / slot_ms) as usize).min(max_points - 1)
+        };
+        runs(&recent, slot)
+            .into_iter()
+            .filter_map(HistoryPoint::average)
+            .collect()
+    }
+
+    /// Min/max/mean/p95 of every series over the last `window_secs` (at most 24 h).
+    pub fn summary(&self, window_secs: u64, now_ms: u64) -> Summary {
+        self.summaries.summary(window_secs, now_ms)
+    }
+
+    /// Full samples from the last `since_secs`, oldest first, thinned to at most `max_points`.
+    ///
+    /// Samples cannot be averaged, so each of `max_points` equal time slots
+    /// keeps its newest sample.
+    pub fn query_stats(&self, since_secs: u64, max_points: usize, now_ms: u64) -> Vec<StatsSample> {
+        let window_ms = since_secs.saturating_mul(1000);
+        let recent: Vec<&StatsSample> = self
+            .samples
+            .iter()
+            .filter(|s| now_ms.saturating_sub(s.unix_ms) <= window_ms)
+            .collect();
+        if max_points == 0 || recent.len() <= max_points {
+            return recent.into_iter().cloned().collect();
+        }
+        let start = now_ms.saturating_sub(window_ms);
+        let slot_ms = window_ms.div_ceil(max_points as u64).max(1);
+        let slot = |s: &StatsSample| {
+            ((s.unix_ms.saturating_sub(start) / slot_ms) as usize).min(max_points - 1)
+        };
+        runs(&recent, |s| slot(s))
+            .into_iter()
+            .filter_map(|chunk| chunk.last().map(|s| (*s).clone()))
+            .collect()
+    }
+}
+
+/// Consecutive runs of `items` with the same `key`.
+fn runs<T, K: PartialEq>(items: &[T], key: impl Fn(&T) -> K) -> Vec<&[T]> {
+    let mut out = Vec::new();
+    let mut begin = 0;
+    for i in 1..=items.len() {
+        if i == items.len() || key(&items[i]) != key(&items[begin]) {
+            out.push(&items[begin..i]);
+            begin = i;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(gpu: u64) -> TegraStats {
+        TegraStats::parse(&format!("RAM 2000/8000MB CPU [10%@1190] GR3D_FREQ {gpu}%@918 tj@50C")).unwrap()
+    }
+
+    #[test]
+    fn keeps_fine_and_coarse_tiers() {
+        let mut ring = HistoryRing::with_sample_minutes(5);
+        let start = 1_700_000_000_000;
+        // 20 minutes at 1 Hz, GPU load = minute number
+        for s in 0..1200 {
+            ring.record(&sample(s / 60), start + s * 1000);
+        }
+        let now = start + 1199 * 1000;
+
+        // 61 samples in the last 60 s; the two newest share the last slot
+        let last_minute = ring.query(60, 60, now);
+        assert_eq!(last_minute.len(), 60);
+        assert_eq!(last_minute[0].gpu, 18.0);
+        assert!(last_minute[1..].iter().all(|p| p.gpu == 19.0));
+        // Only 5 minutes are kept per sample
+        assert_eq!(ring.query(300, 1000, now).len(), 301);
+
+        // 15 minutes come from 10 s buckets: 90 of them, two per 20 s slot
+        let quarter = ring.query(900, 45, now);
+        assert!(
+            quarter.len() <= 45 && quarter.len() >= 44,
+            "{}",
+            quarter.len()
+        );
+        assert!(quarter.windows(2).all(|w| w[0].unix_ms < w[1].unix_ms));
+        assert_eq!(quarter.last().unwrap().gpu, 19.0);
+        assert!(quarter[0].gpu >= 4.0 && quarter[0].gpu <= 5.0);
+    }
+
+    #[test]
+    fn keeps_full_samples_for_the_configured_span() {
+        // Below the 5 minutes the trend charts are served from
+        let mut ring = HistoryRing::with_sample_minutes(1);
+        let start = 1_700_000_000_000;
+        // 7 minutes at 1 Hz; the GPU load is the second within the minute
+        for s in 0..420u64 {
+            ring.record(&sample(s % 60), start + s * 1000);
+        }
+        let now = start + 419 * 1000;
+
+        let all = ring.query_stats(3600, 0, now);
+        assert_eq!(all.len(), 301);
+        assert_eq!(all[0].unix_ms, now - 300 * 1000);
+        assert_eq!(ring.query_stats(10, 100, now).len(), 11);
+
+        // Newest sample of each 10 s slot over the last minute
+        let thinned = ring.query_stats(60, 6, now);
+        assert_eq!(thinned.len(), 6);
+        assert!(thinned.windows(2).all(|w| w[0].unix_ms < w[1].unix_ms));
+        assert_eq!(thinned.last().unwrap().unix_ms, now);
+        assert_eq!(thinned.last().unwrap().stats.gpu_usage(), Some(59));
+        // Trend points come from the same samples
+        assert_eq!(ring.query(60, 0, now).len(), 61);
+    }
+
+    #[test]
+    fn point_from_stats_matches_tui_series() {
+        let stats = TegraStats::parse(
+            "RAM 2000/8000MB (lfb 10x4MB) CPU [10%@1190,30%@1190] GR3D_FREQ 40%@918 cpu@45C tj@50.5C gpu@-256C",
+        )
+        .unwrap();
+        let p = HistoryPoint::from_stats(&stats, 5);
+        assert_eq!((p.ram, p.gpu, p.cpu, p.temp), (25.0, 40.0, 20.0, 50.5));
+    }
+}
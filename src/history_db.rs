@@ -0,0 +1,206 @@
+//! Long-term sample history in SQLite (`sqlite` feature).
+//!
+//! Every collected sample goes into one `samples` row: the trend values
+//! (`ram`, `gpu`, `cpu`, `temp`, as in `HistoryPoint`) as columns for quick
+//! SQL, and the full `TegraStats` as JSON in `stats` (readable with
+//! `json_extract(stats, '$.temps.tj')`). Rows older than the retention are
+//! deleted as new ones arrive. The database runs in WAL mode, so `sqlite3`
+//! can read it while the daemon writes.
+
+use crate::history::{HistoryPoint, StatsSample};
+use crate::parser::TegraStats;
+use anyhow::{anyhow, Context, Result};
+use rusqlite::{params, Connection};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+const DEFAULT_RETENTION_HOURS: u64 = 7 * 24;
+/// Old rows are pruned once per this many inserts.
+const PRUNE_EVERY: u64 = 600;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryDbConfig {
+    pub path: PathBuf,
+    pub retention: Duration,
+}
+
+impl HistoryDbConfig {
+    /// JETSONSCOPE_HISTORY_DB enables it; JETSONSCOPE_HISTORY_RETENTION_HOURS (default 168).
+    pub fn from_env() -> Option<Self> {
+        let path = std::env::var("JETSONSCOPE_HISTORY_DB").ok()?;
+        let hours = std::env::var("JETSONSCOPE_HISTORY_RETENTION_HOURS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .filter(|h| *h > 0)
+            .unwrap_or(DEFAULT_RETENTION_HOURS);
+        Some(HistoryDbConfig {
+            path: PathBuf::from(path),
+            retention: Duration::from_secs(hours * 3600),
+        })
+    }
+}
+
+pub struct HistoryDb {
+    conn: Mutex<Connection>,
+    retention_ms: u64,
+    inserts: AtomicU64,
+}
+
+impl HistoryDb {
+    pub fn open(cfg: &HistoryDbConfig) -> Result<Self> {
+        if let Some(dir) = cfg.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+        }
+        let conn = Connection::open(&cfg.path)
+            .with_context(|| format!("opening {}", cfg.path.display()))?;
+        Self::init(conn, cfg.retention)
+    }
+
+    /// A database that lives in memory, for tests.
+    pub fn in_memory(retention: Duration) -> Result<Self> {
+        Self::init(Connection::open_in_memory()?, retention)
+    }
+
+    fn init(conn: Connection, retention: Duration) -> Result<Self> {
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS samples (
+                unix_ms INTEGER PRIMARY KEY,
+                ram REAL NOT NULL,
+                gpu REAL NOT NULL,
+                cpu REAL NOT NULL,
+                temp REAL NOT NULL,
+                stats TEXT NOT NULL
+            );",
+        )?;
+        Ok(HistoryDb {
+            conn: Mutex::new(conn),
+            retention_ms: retention.as_millis() as u64,
+            inserts: AtomicU64::new(0),
+        })
+    }
+
+    pub fn insert(&self, unix_ms: u64, stats: &TegraStats) -> Result<()> {
+        let p = HistoryPoint::from_stats(stats, unix_ms);
+        let json = serde_json::to_string(stats)?;
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|_| anyhow!("history db lock poisoned"))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO samples (unix_ms, ram, gpu, cpu, temp, stats)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![unix_ms as i64, p.ram, p.gpu, p.cpu, p.temp, json],
+        )?;
+        if self.inserts.fetch_add(1, Ordering::Relaxed) % PRUNE_EVERY == 0 {
+            conn.execute(
+                "DELETE FROM samples WHERE unix_ms < ?1",
+                params![unix_ms.saturating_sub(self.retention_ms) as i64],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Samples with `from_ms <= unix_ms <= to_ms`, oldest first.
+    ///
+    /// Above `max_points` (0: no limit) the range is split into equal slots
+    /// and each keeps its newest sample, like `HistoryRing::query_stats`.
+    pub fn query(&self, from_ms: u64, to_ms: u64, max_points: usize) -> Result<Vec<StatsSample>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|_| anyhow!("history db lock poisoned"))?;
+        let count: i64 = conn.query_row(
+            "SELECT count(*) FROM samples WHERE unix_ms BETWEEN ?1 AND ?2",
+            params![from_ms as i64, to_ms as i64],
+            |row| row.get(0),
+        )?;
+        let slot_ms = if max_points == 0 || count as usize <= max_points {
+            1
+        } else {
+            (to_ms.saturating_sub(from_ms) + 1)
+                .div_ceil(max_points as u64)
+                .max(1)
+        };
+        // With max(), SQLite takes the other columns from the row holding the maximum
+        let mut stmt = conn.prepare(
+            "SELECT max(unix_ms), stats FROM samples WHERE unix_ms BETWEEN ?1 AND ?2
+             GROUP BY (unix_ms - ?1) / ?3 ORDER BY 1",
+        )?;
+        let rows = stmt.query_map(
+            params![from_ms as i64, to_ms as i64, slot_ms as i64],
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+        )?;
+        let mut samples = Vec::new();
+        for row in rows {
+            let (unix_ms, json) = row?;
+            samples.push(StatsSample {
+                unix_ms: unix_ms as u64,
+                stats: serde_json::from_str(&json)?,
+            });
+        }
+        Ok(samples)
+    }
+
+    /// Oldest and newest stored sample times.
+    pub fn span(&self) -> Result<Option<(u64, u64)>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|_| anyhow!("history db lock poisoned"))?;
+        let span: (Option<i64>, Option<i64>) = conn.query_row(
+            "SELECT min(unix_ms), max(unix_ms) FROM samples",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(match span {
+            (Some(first), Some(last)) => Some((first as u64, last as u64)),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(gpu: u32) -> TegraStats {
+        TegraStats::parse(&format!(
+            "RAM 2000/8000MB CPU [10%@1190] GR3D_FREQ {}%@918 tj@50C",
+            gpu
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn stores_queries_and_prunes() {
+        let db = HistoryDb::in_memory(Duration::from_secs(3600)).unwrap();
+        let start = 1_700_000_000_000u64;
+        // Two hours at one sample per 10 s; GPU load is the minute within the hour
+        for i in 0..720u64 {
+            let ms = start + i * 10_000;
+            db.insert(ms, &stats(((i / 6) % 60) as u32)).unwrap();
+        }
+        let last = start + 719 * 10_000;
+
+        // Pruned once at insert 600: only rows within an hour of it survive
+        let (first, newest) = db.span().unwrap().unwrap();
+        assert_eq!(newest, last);
+        assert_eq!(first, start + 600 * 10_000 - 3_600_000);
+
+        let minute = db.query(last - 59_000, last, 0).unwrap();
+        assert_eq!(minute.len(), 6);
+        assert!(minute.iter().all(|s| s.stats.gpu_usage() == Some(59)));
+
+        let thinned = db.query(last - 3_599_999, last, 12).unwrap();
+        assert_eq!(thinned.len(), 12);
+        assert!(thinned.windows(2).all(|w| w[0].unix_ms < w[1].unix_ms));
+        assert_eq!(thinned.last().unwrap().unix_ms, last);
+        assert_eq!(thinned[0].stats.temps["tj"], 50.0);
+
+        assert!(db.query(0, start - 1, 10).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,175 @@
+//! Message catalogs and locale selection for user-facing text.
+//!
+//! Each language has a catalog in `locales/<code>.txt`, embedded at build
+//! time: one `key = text` per line, `#` comments, `\n` for line breaks and
+//! `{name}` placeholders filled by `t!`. Code only refers to keys, so a new
+//! translation is a new catalog plus a `Lang` variant; keys missing from a
+//! catalog fall back to English.
+//!
+//! The language comes from JETSONSCOPE_LANG, then LC_ALL, LC_MESSAGES and
+//! LANG (`es_AR.UTF-8` selects Spanish); anything else is English. It can be
+//! switched at runtime with `set_lang`.
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    En,
+    Es,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 2] = [Lang::En, Lang::Es];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::Es => "es",
+        }
+    }
+
+    /// Language part of a locale name: "es", "es_AR.UTF-8", "en-US".
+    pub fn from_code(locale: &str) -> Option<Self> {
+        let code = locale
+            .split(['_', '-', '.', '@'])
+            .next()?
+            .to_ascii_lowercase();
+        Lang::ALL.into_iter().find(|l| l.code() == code)
+    }
+
+    /// From the environment, English when no supported language is set.
+    pub fn detect() -> Self {
+        ["JETSONSCOPE_LANG", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|v| !v.is_empty())
+            .and_then(|v| Lang::from_code(&v))
+            .unwrap_or(Lang::En)
+    }
+
+    /// The language after this one, for cycling in the UI.
+    pub fn next(self) -> Self {
+        let i = Lang::ALL.iter().position(|l| *l == self).unwrap_or(0);
+        Lang::ALL[(i + 1) % Lang::ALL.len()]
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Lang::En => include_str!("../locales/en.txt"),
+            Lang::Es => include_str!("../locales/es.txt"),
+        }
+    }
+}
+
+const UNSET: u8 = u8::MAX;
+static CURRENT: AtomicU8 = AtomicU8::new(UNSET);
+
+static CATALOGS: Lazy<Vec<HashMap<&'static str, String>>> =
+    Lazy::new(|| Lang::ALL.iter().map(|l| parse(l.source())).collect());
+
+fn parse(source: &'static str) -> HashMap<&'static str, String> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, text)| (key.trim(), text.trim().replace("\\n", "\n")))
+        .collect()
+}
+
+/// The active language, detected from the environment on first use.
+pub fn lang() -> Lang {
+    match CURRENT.load(Ordering::Relaxed) {
+        UNSET => {
+            let detected = Lang::detect();
+            set_lang(detected);
+            detected
+        }
+        i => Lang::ALL[i as usize],
+    }
+}
+
+pub fn set_lang(lang: Lang) {
+    let i = Lang::ALL.iter().position(|l| *l == lang).unwrap_or(0);
+    CURRENT.store(i as u8, Ordering::Relaxed);
+}
+
+fn lookup(lang: Lang, key: &'static str) -> &'static str {
+    let catalog = |l: Lang| CATALOGS[Lang::ALL.iter().position(|x| *x == l).unwrap_or(0)].get(key);
+    catalog(lang)
+        .or_else(|| catalog(Lang::En))
+        .map(String::as_str)
+        .unwrap_or(key)
+}
+
+/// Text for `key` in the active language; the key itself if no catalog has it.
+pub fn tr(key: &'static str) -> &'static str {
+    lookup(lang(), key)
+}
+
+/// `tr` with `{name}` placeholders replaced.
+pub fn format(key: &'static str, args: &[(&str, String)]) -> String {
+    let mut text = tr(key).to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{name}}}"), value);
+    }
+    text
+}
+
+/// `t!("key")` is the catalog text; `t!("key", name = value, ...)` fills placeholders.
+#[macro_export]
+macro_rules! t {
+    ($key:literal) => {
+        $crate::i18n::tr($key)
+    };
+    ($key:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::format($key, &[$((stringify!($name), $value.to_string())),+])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = text
+            .split('{')
+            .skip(1)
+            .filter_map(|s| s.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn catalogs_have_the_same_keys_and_placeholders() {
+        let en = &CATALOGS[0];
+        for (lang, catalog) in Lang::ALL.iter().zip(CATALOGS.iter()).skip(1) {
+            for (key, text) in en {
+                let other = catalog
+                    .get(key)
+                    .unwrap_or_else(|| panic!("{} catalog lacks '{}'", lang.code(), key));
+                assert_eq!(placeholders(text), placeholders(other), "{key}");
+            }
+            for key in catalog.keys() {
+                assert!(en.contains_key(key), "'{}' only in {}", key, lang.code());
+            }
+        }
+    }
+
+    #[test]
+    fn parses_locales_and_fills_placeholders() {
+        assert_eq!(Lang::from_code("es_AR.UTF-8"), Some(Lang::Es));
+        assert_eq!(Lang::from_code("en-US"), Some(Lang::En));
+        assert_eq!(Lang::from_code("C"), None);
+        assert_eq!(Lang::Es.next(), Lang::En);
+
+        assert_eq!(lookup(Lang::Es, "lang.name"), "Español");
+        assert_eq!(lookup(Lang::En, "no.such.key"), "no.such.key");
+        let catalog = parse("# comment\na.b = uno\\ndos\nbroken line\n");
+        assert_eq!(catalog["a.b"], "uno\ndos");
+        assert_eq!(catalog.len(), 1);
+    }
+}
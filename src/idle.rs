@@ -0,0 +1,229 @@
+use crate::parser::TegraStats;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Engines that reflect memory/bus clocks rather than workload activity.
+const NON_WORKLOAD_ENGINES: &[&str] = &["EMC", "MC", "AXI", "APE"];
+
+/// Idle detection settings (JETSONSCOPE_IDLE_* environment variables).
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdleConfig {
+    /// Controls to apply while idle, e.g. [("nvpmodel", "MODE_1")]
+    pub profile: Vec<(String, String)>,
+    /// Sustained quiet time before switching
+    pub idle_after: Duration,
+    /// Average CPU load (%) at or below which the board counts as quiet
+    pub cpu_percent: u32,
+    /// GPU / media engine load (%) at or below which the board counts as quiet
+    pub engine_percent: u32,
+}
+
+impl IdleConfig {
+    /// `JETSONSCOPE_IDLE_PROFILE="nvpmodel=MODE_1,cpu_governor=powersave"` enables detection;
+    /// `JETSONSCOPE_IDLE_AFTER` (secs, default 120), `JETSONSCOPE_IDLE_CPU_PCT` and
+    /// `JETSONSCOPE_IDLE_ENGINE_PCT` (default 10) tune it.
+    pub fn from_env() -> Option<Self> {
+        let profile = parse_profile(&std::env::var("JETSONSCOPE_IDLE_PROFILE").ok()?);
+        if profile.is_empty() {
+            return None;
+        }
+        let num = |key: &str, default: u64| {
+            std::env::var(key)
+                .ok()
+                .and_then(|s| s.parse::<u64>().ok())
+                .unwrap_or(default)
+        };
+        Some(IdleConfig {
+            profile,
+            idle_after: Duration::from_secs(num("JETSONSCOPE_IDLE_AFTER", 120)),
+            cpu_percent: num("JETSONSCOPE_IDLE_CPU_PCT", 10) as u32,
+            engine_percent: num("JETSONSCOPE_IDLE_ENGINE_PCT", 10) as u32,
+        })
+    }
+
+    pub fn describe(&self) -> String {
+        self.profile
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// Parse "control=value,control=value"; malformed entries are ignored.
+pub fn parse_profile(spec: &str) -> Vec<(String, String)> {
+    spec.split(',')
+        .filter_map(|item| {
+            let (k, v) = item.split_once('=')?;
+            let (k, v) = (k.trim(), v.trim());
+            (!k.is_empty() && !v.is_empty()).then(|| (k.to_string(), v.to_string()))
+        })
+        .collect()
+}
+
+/// Idle state reported in ControlStatus.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IdleStatus {
+    pub enabled: bool,
+    pub idle: bool,
+    /// Configured profile, "ctrl=value,..."
+    pub profile: Option<String>,
+    /// Seconds the board has been quiet (resets on activity)
+    pub quiet_secs: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleTransition {
+    EnterIdle,
+    ExitIdle,
+}
+
+#[derive(Debug, Clone)]
+pub struct IdleDetector {
+    config: IdleConfig,
+    quiet_since: Option<Instant>,
+    idle: bool,
+}
+
+impl IdleDetector {
+    pub fn new(config: IdleConfig) -> Self {
+        Self {
+            config,
+            quiet_since: None,
+            idle: false,
+        }
+    }
+
+    pub fn config(&self) -> &IdleConfig {
+        &self.config
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.idle
+    }
+
+    pub fn quiet_for(&self, now: Instant) -> Duration {
+        self.quiet_since
+            .map(|t| now.saturating_duration_since(t))
+            .unwrap_or_default()
+    }
+
+    /// Feed one sample; returns a transition when the idle state changes.
+    pub fn update(&mut self, stats: &TegraStats, now: Instant) -> Option<IdleTransition> {
+        if !self.is_quiet(stats) {
+            self.quiet_since = None;
+            if self.idle {
+                self.idle = false;
+                return Some(IdleTransition::ExitIdle);
+            }
+            return None;
+        }
+        let since = *self.quiet_since.get_or_insert(now);
+        if !self.idle && now.saturating_duration_since(since) >= self.config.idle_after {
+            self.idle = true;
+            return Some(IdleTransition::EnterIdle);
+        }
+        None
+    }
+
+    fn is_quiet(&self, stats: &TegraStats) -> bool {
+        let loads: Vec<u32> = stats.cpus.iter().filter_map(|c| c.load_percent).collect();
+        let cpu_avg = if loads.is_empty() {
+            0
+        } else {
+            loads.iter().sum::<u32>() / loads.len() as u32
+        };
+        let engines_quiet = stats
+            .engines
+            .iter()
+            .filter(|(name, _)| !NON_WORKLOAD_ENGINES.contains(&name.as_str()))
+            .filter_map(|(_, e)| e.usage_percent)
+            .all(|pct| pct <= self.config.engine_percent);
+        cpu_avg <= self.config.cpu_percent && engines_quiet
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{CpuCore, EngineStat};
+
+    fn sample(cpu: u32, gpu: u32) -> TegraStats {
+        let mut stats = TegraStats {
+            cpus: vec![
+                CpuCore {
+                    load_percent: Some(cpu),
+                    freq_mhz: None,
+                    cluster: None,
+                };
+                4
+            ],
+            ..Default::default()
+        };
+        stats.engines.insert(
+            "GR3D".into(),
+            EngineStat {
+                usage_percent: Some(gpu),
+                ..Default::default()
+            },
+        );
+        // Memory controller load must not keep the board awake
+        stats.engines.insert(
+            "EMC".into(),
+            EngineStat {
+                usage_percent: Some(40),
+                ..Default::default()
+            },
+        );
+        stats
+    }
+
+    fn config() -> IdleConfig {
+        IdleConfig {
+            profile: parse_profile("nvpmodel=MODE_1"),
+            idle_after: Duration::from_secs(60),
+            cpu_percent: 10,
+            engine_percent: 10,
+        }
+    }
+
+    #[test]
+    fn parses_profile_spec() {
+        assert_eq!(
+            parse_profile("nvpmodel=MODE_1, cpu_governor=powersave,bogus,=x"),
+            vec![
+                ("nvpmodel".to_string(), "MODE_1".to_string()),
+                ("cpu_governor".to_string(), "powersave".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn enters_after_sustained_quiet_and_exits_on_load() {
+        let mut det = IdleDetector::new(config());
+        let t0 = Instant::now();
+        assert_eq!(det.update(&sample(3, 0), t0), None);
+        assert_eq!(
+            det.update(&sample(3, 0), t0 + Duration::from_secs(30)),
+            None
+        );
+        // A busy sample resets the quiet window
+        assert_eq!(
+            det.update(&sample(50, 0), t0 + Duration::from_secs(40)),
+            None
+        );
+        assert_eq!(
+            det.update(&sample(3, 0), t0 + Duration::from_secs(50)),
+            None
+        );
+        assert_eq!(
+            det.update(&sample(3, 0), t0 + Duration::from_secs(110)),
+            Some(IdleTransition::EnterIdle)
+        );
+        assert!(det.is_idle());
+        assert_eq!(
+            det.update(&sample(3, 80), t0 + Duration::from_secs(111)),
+            Some(IdleTransition::ExitIdle)
+        );
+    }
+}
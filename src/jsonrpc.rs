@@ -0,0 +1,202 @@
+//! JSON-RPC 2.0 framing for the daemon socket.
+//!
+//! Methods are the `Request` variant names, either as-is ("GetStats") or in
+//! snake_case ("get_stats"); `params` carries the variant fields by name, e.g.
+//! `{"jsonrpc":"2.0","method":"set_control","params":{"control":"fan","value":"80","token":null},"id":1}`.
+//! The result is the payload of the matching `Response` variant.
+
+use crate::protocol::{Request, Response};
+use serde_json::{json, Value};
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+/// Application errors (`Response::Error`); the daemon's error code goes in `data.code`.
+pub const SERVER_ERROR: i64 = -32000;
+
+/// A decoded call; `id` is `None` for notifications, which get no reply.
+#[derive(Debug)]
+pub struct Call {
+    pub request: Request,
+    pub id: Option<Value>,
+}
+
+/// Decode a JSON-RPC 2.0 message.
+///
+/// Returns `None` when the payload is not JSON-RPC (so the native framing applies),
+/// `Some(Err(reply))` when it is but cannot be served.
+pub fn decode(buf: &[u8]) -> Option<Result<Call, Value>> {
+    let msg: Value = match serde_json::from_slice(buf) {
+        Ok(v) => v,
+        // Only claim unparsable payloads that were clearly meant as JSON-RPC
+        Err(_) if contains(buf, b"\"jsonrpc\"") => {
+            return Some(Err(error(Value::Null, PARSE_ERROR, "Parse error")))
+        }
+        Err(_) => return None,
+    };
+    let obj = msg.as_object()?;
+    if !obj.contains_key("jsonrpc") {
+        return None;
+    }
+    let id = obj.get("id").cloned();
+    let reply_id = id.clone().unwrap_or(Value::Null);
+    if obj.get("jsonrpc").and_then(Value::as_str) != Some("2.0") {
+        return Some(Err(error(
+            reply_id,
+            INVALID_REQUEST,
+            "jsonrpc must be \"2.0\"",
+        )));
+    }
+    let Some(method) = obj.get("method").and_then(Value::as_str) else {
+        return Some(Err(error(reply_id, INVALID_REQUEST, "Missing method")));
+    };
+    let variant = variant_name(method);
+    if !Request::NAMES.contains(&variant.as_str()) {
+        return Some(Err(error(
+            reply_id,
+            METHOD_NOT_FOUND,
+            &format!("Method not found: {}", method),
+        )));
+    }
+    let params = match obj.get("params") {
+        None | Some(Value::Null) => json!({}),
+        Some(params @ Value::Object(_)) => params.clone(),
+        Some(_) => {
+            return Some(Err(error(
+                reply_id,
+                INVALID_PARAMS,
+                "params must be an object",
+            )))
+        }
+    };
+    // Unit variants ("GetStats") when there is nothing to pass, struct variants otherwise
+    let unit = params
+        .as_object()
+        .is_some_and(|p| p.is_empty())
+        .then(|| serde_json::from_value::<Request>(Value::String(variant.clone())).ok())
+        .flatten();
+    let decoded = match unit {
+        Some(request) => Ok(request),
+        None => serde_json::from_value::<Request>(json!({ variant: params })),
+    };
+    Some(match decoded {
+        Ok(request) => Ok(Call { request, id }),
+        Err(e) => Err(error(reply_id, INVALID_PARAMS, &e.to_string())),
+    })
+}
+
+/// Wrap a daemon response as a JSON-RPC result or error.
+pub fn reply(id: Value, resp: Response) -> Value {
+    if let Response::Error(err) = resp {
+        return json!({
+            "jsonrpc": "2.0",
+            "error": {"code": SERVER_ERROR, "message": err.message, "data": {"code": err.code}},
+            "id": id,
+        });
+    }
+    // Externally tagged enum: {"Variant": payload} -> payload
+    let result = match serde_json::to_value(&resp) {
+        Ok(Value::Object(mut map)) if map.len() == 1 => {
+            let key = map.keys().next().cloned().unwrap_or_default();
+            map.remove(&key).unwrap_or(Value::Null)
+        }
+        Ok(other) => other,
+        Err(e) => return error(id, SERVER_ERROR, &e.to_string()),
+    };
+    json!({"jsonrpc": "2.0", "result": result, "id": id})
+}
+
+pub fn error(id: Value, code: i64, message: &str) -> Value {
+    json!({"jsonrpc": "2.0", "error": {"code": code, "message": message}, "id": id})
+}
+
+/// "get_stats" -> "GetStats"; names already in CamelCase pass through.
+fn variant_name(method: &str) -> String {
+    method
+        .split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::ErrorInfo;
+
+    #[test]
+    fn decodes_methods_and_params() {
+        let call = decode(br#"{"jsonrpc":"2.0","method":"get_stats","id":7}"#)
+            .unwrap()
+            .unwrap();
+        assert!(matches!(call.request, Request::GetStats));
+        assert_eq!(call.id, Some(json!(7)));
+
+        let call = decode(
+            br#"{"jsonrpc":"2.0","method":"SetControl","params":{"control":"fan","value":"80","token":null},"id":"a"}"#,
+        )
+        .unwrap()
+        .unwrap();
+        assert!(
+            matches!(call.request, Request::SetControl { ref control, .. } if control == "fan")
+        );
+
+        // Native framing is left alone
+        assert!(decode(br#""GetStats""#).is_none());
+        assert!(decode(b"\xa1").is_none());
+    }
+
+    #[test]
+    fn maps_errors() {
+        let err = decode(br#"{"jsonrpc":"2.0","method":"reboot","id":1}"#)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(err["error"]["code"], METHOD_NOT_FOUND);
+        // Unknown methods are not found, whatever their params
+        let err = decode(br#"{"jsonrpc":"2.0","method":"reboot","params":{"now":true},"id":1}"#)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(err["error"]["code"], METHOD_NOT_FOUND);
+        for name in Request::NAMES {
+            let msg = json!({"jsonrpc": "2.0", "method": name, "params": {"x": 1}, "id": 1});
+            if let Err(err) = decode(msg.to_string().as_bytes()).unwrap() {
+                assert_eq!(err["error"]["code"], INVALID_PARAMS, "{name}");
+                let message = err["error"]["message"].as_str().unwrap();
+                assert!(!message.contains("unknown variant"), "{name}");
+            }
+        }
+        let err = decode(br#"{"jsonrpc":"2.0","method":"stop_session","params":{},"id":1}"#)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(err["error"]["code"], INVALID_PARAMS);
+        // Optional fields may be omitted
+        let call = decode(br#"{"jsonrpc":"2.0","method":"start_session","id":2}"#)
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            call.request,
+            Request::StartSession { label: None }
+        ));
+
+        let out = reply(
+            json!(3),
+            Response::Error(ErrorInfo {
+                code: "auth_failed".into(),
+                message: "Auth failed".into(),
+            }),
+        );
+        assert_eq!(out["error"]["data"]["code"], "auth_failed");
+        let out = reply(json!(4), Response::SessionStarted { id: 9 });
+        assert_eq!(out["result"], json!({"id": 9}));
+    }
+}
@@ -0,0 +1,148 @@
+//! Kubernetes metadata for `/metrics` when the daemon runs as a DaemonSet pod.
+//!
+//! Node, pod and namespace come from downward-API environment variables; pod labels
+//! can come from a downward-API volume (`labels` file) so dashboards can group
+//! Jetson nodes the same way the cluster does.
+
+use std::fs;
+
+/// Extra labels appended to every metric series.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetricLabels {
+    pub labels: Vec<(String, String)>,
+}
+
+impl MetricLabels {
+    /// Reads, in order:
+    /// - `JETSONSCOPE_NODE_NAME` or `NODE_NAME` -> `node`
+    /// - `JETSONSCOPE_POD_NAME` or `POD_NAME` -> `pod`
+    /// - `JETSONSCOPE_POD_NAMESPACE` or `POD_NAMESPACE` -> `namespace`
+    /// - `JETSONSCOPE_PODINFO_LABELS`: downward-API labels file, each label as `label_<key>`
+    /// - `JETSONSCOPE_METRIC_LABELS`: static `key=value,key=value` pairs
+    pub fn from_env() -> Self {
+        let var = |names: &[&str]| {
+            names
+                .iter()
+                .find_map(|n| std::env::var(n).ok())
+                .filter(|v| !v.is_empty())
+        };
+        let mut out = MetricLabels::default();
+        for (label, names) in [
+            ("node", ["JETSONSCOPE_NODE_NAME", "NODE_NAME"]),
+            ("pod", ["JETSONSCOPE_POD_NAME", "POD_NAME"]),
+            ("namespace", ["JETSONSCOPE_POD_NAMESPACE", "POD_NAMESPACE"]),
+        ] {
+            if let Some(value) = var(&names) {
+                out.push(label, &value);
+            }
+        }
+        if let Some(path) = var(&["JETSONSCOPE_PODINFO_LABELS"]) {
+            match fs::read_to_string(&path) {
+                Ok(content) => {
+                    for (key, value) in parse_podinfo(&content) {
+                        out.push(&format!("label_{}", key), &value);
+                    }
+                }
+                Err(e) => eprintln!("Pod labels not loaded from {}: {}", path, e),
+            }
+        }
+        if let Some(pairs) = var(&["JETSONSCOPE_METRIC_LABELS"]) {
+            for pair in pairs.split(',') {
+                if let Some((key, value)) = pair.split_once('=') {
+                    out.push(key.trim(), value.trim());
+                }
+            }
+        }
+        out
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Add a label; the name is reduced to Prometheus' `[a-zA-Z0-9_]` set.
+    pub fn push(&mut self, name: &str, value: &str) {
+        let name: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        if name.is_empty() || self.labels.iter().any(|(n, _)| *n == name) {
+            return;
+        }
+        self.labels.push((name, value.to_string()));
+    }
+
+    /// Add the labels to every sample line of a Prometheus text exposition.
+    pub fn apply(&self, exposition: &str) -> String {
+        if self.is_empty() {
+            return exposition.to_string();
+        }
+        let extra = self
+            .labels
+            .iter()
+            .map(|(n, v)| format!("{}=\"{}\"", n, v.replace('\\', "\\\\").replace('"', "\\\"")))
+            .collect::<Vec<_>>()
+            .join(",");
+        let mut out = String::with_capacity(exposition.len() * 2);
+        for line in exposition.lines() {
+            if line.starts_with('#') || line.trim().is_empty() {
+                out.push_str(line);
+            } else if let Some(brace) = line.find('{') {
+                // name{a="b"} v -> name{a="b",extra} v
+                let close = line.rfind('}').unwrap_or(line.len());
+                let sep = if close == brace + 1 { "" } else { "," };
+                out.push_str(&line[..close]);
+                out.push_str(sep);
+                out.push_str(&extra);
+                out.push_str(&line[close..]);
+            } else if let Some((name, rest)) = line.split_once(' ') {
+                out.push_str(&format!("{}{{{}}} {}", name, extra, rest));
+            } else {
+                out.push_str(line);
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Downward-API `labels`/`annotations` file: one `key="value"` per line.
+pub fn parse_podinfo(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value);
+            Some((key.trim().to_string(), value.replace("\\\"", "\"")))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_labels_to_every_sample() {
+        let mut labels = MetricLabels::default();
+        labels.push("node", "orin-7");
+        for (k, v) in parse_podinfo("app=\"jetsonscope\"\napp.kubernetes.io/part-of=\"edge\"\n") {
+            labels.push(&format!("label_{}", k), &v);
+        }
+        let text = "# HELP up Up\n# TYPE up gauge\nup 1\ntemp{sensor=\"tj\"} 51.5\nempty{} 0\n";
+        assert_eq!(
+            labels.apply(text),
+            concat!(
+                "# HELP up Up\n# TYPE up gauge\n",
+                "up{node=\"orin-7\",label_app=\"jetsonscope\",label_app_kubernetes_io_part_of=\"edge\"} 1\n",
+                "temp{sensor=\"tj\",node=\"orin-7\",label_app=\"jetsonscope\",label_app_kubernetes_io_part_of=\"edge\"} 51.5\n",
+                "empty{node=\"orin-7\",label_app=\"jetsonscope\",label_app_kubernetes_io_part_of=\"edge\"} 0\n",
+            )
+        );
+        assert_eq!(MetricLabels::default().apply(text), text);
+    }
+}
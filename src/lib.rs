@@ -1,10 +1,58 @@
+pub mod alerts;
 pub mod app;
+pub mod auth;
+pub mod burst;
+pub mod client;
+pub mod clock_cap;
 pub mod collector;
+pub mod config;
 pub mod control;
+pub mod dcgm;
+pub mod disk;
+pub mod emulator;
+pub mod energy;
+pub mod export;
+pub mod extremes;
+pub mod fans;
+pub mod graphite;
 pub mod hardware;
 pub mod health;
+pub mod i18n;
+pub mod history;
+#[cfg(feature = "sqlite")]
+pub mod history_db;
+pub mod idle;
+pub mod jsonrpc;
+pub mod k8s;
+pub mod link;
 pub mod metrics_auth;
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+pub mod palette;
+pub mod netdata;
+pub mod network;
 pub mod parser;
+pub mod power_cap;
+pub mod power_supply;
 pub mod processes;
+pub mod profiles;
 pub mod protocol;
+pub mod recorder;
+pub mod residency;
+pub mod scenario;
+pub mod screen_dump;
+pub mod session;
+pub mod smi;
+pub mod stats;
+pub mod statsd;
+pub mod stress;
+pub mod sysfs_stats;
+pub mod systemd;
+pub mod thermal_governor;
+pub mod thermal_guard;
+pub mod theme;
+pub mod thresholds;
+pub mod throttle;
+pub mod trip_points;
 pub mod ui;
+pub mod websocket;
@@ -0,0 +1,81 @@
+//! Connection quality between the TUI and its stats source.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Window for the samples-per-second rate.
+const RATE_WINDOW: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Default)]
+pub struct LinkQuality {
+    /// Last socket round trip; `None` for local sources (command/synthetic)
+    pub rtt: Option<Duration>,
+    /// Polls answered with the sample we already had (daemon behind)
+    pub late: u64,
+    /// Polls that failed or timed out
+    pub dropped: u64,
+    arrivals: VecDeque<Instant>,
+}
+
+impl LinkQuality {
+    pub fn record_poll(&mut self, rtt: Duration, fresh: bool) {
+        self.rtt = Some(rtt);
+        if !fresh {
+            self.late += 1;
+        }
+    }
+
+    pub fn record_failure(&mut self) {
+        self.dropped += 1;
+    }
+
+    pub fn record_sample(&mut self, now: Instant) {
+        self.arrivals.push_back(now);
+        while self
+            .arrivals
+            .front()
+            .is_some_and(|t| now.duration_since(*t) > RATE_WINDOW)
+        {
+            self.arrivals.pop_front();
+        }
+    }
+
+    /// New samples per second over the last RATE_WINDOW.
+    pub fn samples_per_sec(&self, now: Instant) -> f64 {
+        let recent: Vec<&Instant> = self
+            .arrivals
+            .iter()
+            .filter(|t| now.duration_since(**t) <= RATE_WINDOW)
+            .collect();
+        let (Some(first), true) = (recent.first(), recent.len() > 1) else {
+            return 0.0;
+        };
+        // Spread over the span the samples cover, so a fresh start is not underestimated
+        let span = now.duration_since(**first).max(Duration::from_secs(1));
+        (recent.len() - 1) as f64 / span.as_secs_f64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_rate_late_and_dropped() {
+        let start = Instant::now();
+        let mut link = LinkQuality::default();
+        for i in 0..=4 {
+            link.record_sample(start + Duration::from_millis(500 * i));
+        }
+        let rate = link.samples_per_sec(start + Duration::from_secs(2));
+        assert!((rate - 2.0).abs() < 0.01, "{rate}");
+        // Nothing for longer than the window
+        assert_eq!(link.samples_per_sec(start + Duration::from_secs(30)), 0.0);
+
+        link.record_poll(Duration::from_millis(3), true);
+        link.record_poll(Duration::from_millis(4), false);
+        link.record_failure();
+        assert_eq!(link.rtt, Some(Duration::from_millis(4)));
+        assert_eq!((link.late, link.dropped), (1, 1));
+    }
+}
@@ -1,27 +1,187 @@
+#[allow(dead_code)] // daemon-side engine; the TUI only needs AlertStatus for the protocol
+mod alerts;
 mod app;
+#[allow(dead_code)] // daemon-side token checks; the TUI only needs Scope for the protocol
+mod auth;
+#[allow(dead_code)] // daemon-side capture; the TUI only needs BurstCapture
+mod burst;
+mod clock_cap;
 mod collector;
 mod control;
+mod disk;
+mod emulator;
+#[allow(dead_code)] // state-file persistence is daemon-only
+mod energy;
+#[allow(dead_code)] // exporter helpers; the TUI only needs key_metrics for --once --pretty
+mod export;
+mod extremes;
+mod fans;
 mod health;
+#[allow(dead_code)] // daemon-side ring; the TUI only needs HistoryPoint
+mod history;
 mod hardware;
+mod i18n;
+#[allow(dead_code)] // daemon-side detection; the TUI only shows IdleStatus
+mod idle;
+mod link;
+mod network;
 mod processes;
+#[allow(dead_code)] // daemon-side store; the TUI only needs ProfileList
+mod profiles;
+mod palette;
 mod parser;
+mod power_cap;
+mod power_supply;
 mod protocol;
+mod residency;
+mod scenario;
+mod screen_dump;
+#[allow(dead_code)] // daemon-side; the TUI only needs SessionSummary for the protocol
+mod session;
+mod stats;
+#[allow(dead_code)] // only parse_duration, for alert rules
+mod stress;
+mod sysfs_stats;
+mod thermal_governor;
+#[allow(dead_code)] // daemon-side guard; the TUI only shows GuardStatus
+mod thermal_guard;
+mod theme;
+mod thresholds;
+mod throttle;
+mod trip_points;
 mod ui;
 
-use crate::{app::App, ui::ui};
+use crate::{
+    app::{App, AppOptions, ViewMode},
+    export::MetricKind,
+    palette::PaletteCommand,
+    scenario::Scenario,
+    screen_dump::DumpFormat,
+    theme::Theme,
+    ui::ui,
+};
+use clap::{Parser, ValueEnum};
 use crossterm::event::Event::Key;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, KeyCode},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::{
     Terminal,
-    backend::{Backend, CrosstermBackend},
+    backend::{Backend, CrosstermBackend, TestBackend},
 };
-use std::{error::Error, io, time::Duration};
+use std::{error::Error, io, path::PathBuf, time::Duration};
+
+#[derive(Parser)]
+#[command(name = "jscope", version, about = t!("tui.args.about"))]
+struct Cli {
+    #[arg(long, value_name = "PATH", help = t!("tui.args.socket"))]
+    socket: Option<PathBuf>,
+    #[arg(
+        long,
+        value_name = "MS",
+        value_parser = clap::value_parser!(u64).range(100..=60_000),
+        help = t!("tui.args.refresh_ms")
+    )]
+    refresh_ms: Option<u64>,
+    #[arg(long, value_enum, default_value_t = ViewArg::Dashboard, help = t!("tui.args.view"))]
+    view: ViewArg,
+    #[arg(long, help = t!("tui.args.demo"))]
+    demo: bool,
+    #[arg(long, value_enum, help = t!("tui.args.scenario"))]
+    scenario: Option<ScenarioArg>,
+    #[arg(long, help = t!("tui.args.no_animations"))]
+    no_animations: bool,
+    #[arg(long, value_enum, help = t!("tui.args.theme"))]
+    theme: Option<ThemeArg>,
+    #[arg(long, help = t!("tui.args.no_confirm"))]
+    no_confirm: bool,
+    #[arg(long, help = t!("tui.args.once"))]
+    once: bool,
+    #[arg(long, requires = "once", conflicts_with = "pretty", help = t!("tui.args.json"))]
+    json: bool,
+    #[arg(long, requires = "once", help = t!("tui.args.pretty"))]
+    pretty: bool,
+    #[arg(long, default_value_t = 120, help = t!("tui.args.width"))]
+    width: u16,
+    #[arg(long, default_value_t = 40, help = t!("tui.args.height"))]
+    height: u16,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ViewArg {
+    Dashboard,
+    Processes,
+    Gpu,
+    Clocks,
+    Temps,
+    Power,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ScenarioArg {
+    Random,
+    Idle,
+    InferenceBurst,
+    ThermalRunaway,
+    SwapPressure,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ThemeArg {
+    Neon,
+    Plain,
+    HighContrast,
+    ColorBlind,
+}
+
+impl Cli {
+    fn app_options(&self) -> AppOptions {
+        let defaults = AppOptions::default();
+        AppOptions {
+            socket: self.socket.clone(),
+            refresh_ms: self.refresh_ms,
+            view: match self.view {
+                ViewArg::Dashboard => ViewMode::Dashboard,
+                ViewArg::Processes => ViewMode::Processes,
+                ViewArg::Gpu => ViewMode::GpuEngines,
+                ViewArg::Clocks => ViewMode::Clocks,
+                ViewArg::Temps => ViewMode::Temperatures,
+                ViewArg::Power => ViewMode::Power,
+            },
+            demo: self.demo || self.scenario.is_some(),
+            scenario: self.scenario.map(|s| match s {
+                ScenarioArg::Random => Scenario::Random,
+                ScenarioArg::Idle => Scenario::Idle,
+                ScenarioArg::InferenceBurst => Scenario::InferenceBurst,
+                ScenarioArg::ThermalRunaway => Scenario::ThermalRunaway,
+                ScenarioArg::SwapPressure => Scenario::SwapPressure,
+            }),
+            animations: !self.no_animations,
+            theme: match self.theme {
+                Some(ThemeArg::Neon) => Theme::Neon,
+                Some(ThemeArg::Plain) => Theme::Plain,
+                Some(ThemeArg::HighContrast) => Theme::HighContrast,
+                Some(ThemeArg::ColorBlind) => Theme::ColorBlind,
+                None => defaults.theme,
+            },
+            confirm_controls: defaults.confirm_controls && !self.no_confirm,
+        }
+    }
+}
 
 fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+    if cli.once {
+        let format = match (cli.json, cli.pretty) {
+            (true, _) => OnceFormat::Json,
+            (_, true) => OnceFormat::Pretty,
+            _ => OnceFormat::Frame(cli.width, cli.height),
+        };
+        return print_once(cli.app_options(), format);
+    }
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -30,7 +190,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     let mut terminal = Terminal::new(backend)?;
 
     // Create app
-    let mut app = App::new();
+    let mut app = App::new(cli.app_options());
 
     // Run app
     let res = run_app(&mut terminal, &mut app);
@@ -51,25 +211,181 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// What `--once` prints.
+enum OnceFormat {
+    /// The dashboard rendered off-screen as plain text, width x height
+    Frame(u16, u16),
+    /// Stats, control state and source as one line of JSON
+    Json,
+    /// Key metrics, one per line
+    Pretty,
+}
+
+/// Print one snapshot and exit, without touching the terminal (for scripts,
+/// SSH checks, cron/status mails, docs).
+fn print_once(options: AppOptions, format: OnceFormat) -> Result<(), Box<dyn Error>> {
+    let mut app = App::new(options);
+
+    // Give the collector a moment to deliver the first sample (socket or synthetic)
+    for _ in 0..30 {
+        app.on_tick();
+        if app.last_update_tick > 0 {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    // The daemon's history follows the first sample; let it fill the trend panels
+    for _ in 0..5 {
+        std::thread::sleep(Duration::from_millis(50));
+        app.on_tick();
+    }
+
+    match format {
+        OnceFormat::Frame(width, height) => {
+            let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+            terminal.draw(|f| ui(f, &mut app))?;
+            print!("{}", screen_dump::buffer_to_text(terminal.backend().buffer()));
+        }
+        OnceFormat::Json => println!("{}", serde_json::to_string(&app.snapshot())?),
+        OnceFormat::Pretty => {
+            println!("{}", app.source_label);
+            let metrics = export::key_metrics(&app.latest_stats, None);
+            let width = metrics.iter().map(|m| m.name.len()).max().unwrap_or(0);
+            for m in metrics {
+                let value = match m.kind {
+                    MetricKind::Percent => format!("{:.0}%", m.value),
+                    MetricKind::Temperature => format!("{:.1}°C", m.value),
+                    MetricKind::Power => format!("{:.2} W", m.value),
+                };
+                println!("{:<width$}  {}", m.name, value);
+            }
+        }
+    }
+    Ok(())
+}
+
 fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<()> {
     loop {
-        terminal.draw(|f| ui(f, app))?;
+        let frame = terminal.draw(|f| ui(f, app))?;
+        if let Some(format) = app.dump_requested.take() {
+            app.save_dump(format, frame.buffer);
+        }
 
         // Check for new stats
         app.on_tick();
 
-        if event::poll(Duration::from_millis(100))? {
-            if let Key(key) = event::read()? {
+        if event::poll(app.tick_interval())? {
+            let event = event::read()?;
+            if let Event::Mouse(mouse) = event {
+                app.on_mouse(mouse);
+                continue;
+            }
+            if let Key(key) = event {
+                // A pending confirmation takes every key until answered
+                if app.confirm.is_some() {
+                    match key.code {
+                        KeyCode::Char('y') | KeyCode::Char('Y') => app.resolve_confirm(true),
+                        // No is the default: a stray Enter must not change the power mode
+                        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc | KeyCode::Enter => {
+                            app.resolve_confirm(false)
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+                if app.process_detail.is_some() {
+                    if matches!(key.code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q')) {
+                        app.close_process_detail();
+                    }
+                    continue;
+                }
+                // While the palette is open it takes every key
+                if app.palette.is_some() {
+                    let entries = app.palette_entries();
+                    match key.code {
+                        KeyCode::Esc => app.close_palette(),
+                        KeyCode::Enter => app.run_palette_selection(),
+                        _ => {
+                            if let Some(palette) = app.palette.as_mut() {
+                                let matches = palette.filter(&entries).len();
+                                match key.code {
+                                    KeyCode::Up => palette.move_selection(-1, matches),
+                                    KeyCode::Down | KeyCode::Tab => {
+                                        palette.move_selection(1, matches)
+                                    }
+                                    KeyCode::Backspace => palette.pop(),
+                                    KeyCode::Char(c)
+                                        if !key.modifiers.contains(KeyModifiers::CONTROL) =>
+                                    {
+                                        palette.push(c)
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                    }
+                    continue;
+                }
+                if key.code == KeyCode::Char('p') && key.modifiers.contains(KeyModifiers::CONTROL)
+                {
+                    app.open_palette();
+                    continue;
+                }
                 match key.code {
                     KeyCode::Char('q') => return Ok(()),
+                    KeyCode::Up if app.view_mode == ViewMode::Temperatures => {
+                        app.select_temp_sensor(-1)
+                    }
+                    KeyCode::Down if app.view_mode == ViewMode::Temperatures => {
+                        app.select_temp_sensor(1)
+                    }
+                    KeyCode::Up if app.view_mode == ViewMode::Processes => app.select_process(-1),
+                    KeyCode::Down if app.view_mode == ViewMode::Processes => app.select_process(1),
+                    KeyCode::PageUp if app.view_mode == ViewMode::Processes => {
+                        app.select_process(-app.process_page())
+                    }
+                    KeyCode::PageDown if app.view_mode == ViewMode::Processes => {
+                        app.select_process(app.process_page())
+                    }
+                    KeyCode::Home if app.view_mode == ViewMode::Processes => {
+                        app.select_process(-(app.processes.len() as isize))
+                    }
+                    KeyCode::End if app.view_mode == ViewMode::Processes => {
+                        app.select_process(app.processes.len() as isize)
+                    }
+                    KeyCode::Char('k') if app.view_mode == ViewMode::Processes => {
+                        app.kill_selected_process()
+                    }
+                    KeyCode::Char('n') if app.view_mode == ViewMode::Processes => {
+                        app.renice_selected_process()
+                    }
+                    KeyCode::Enter if app.view_mode == ViewMode::Processes => {
+                        app.open_process_detail()
+                    }
+                    KeyCode::Char('u') if app.view_mode == ViewMode::Processes => {
+                        app.toggle_process_users()
+                    }
                     KeyCode::Char('h') => app.toggle_help(),
                     KeyCode::Char('v') => app.cycle_view(),
                     KeyCode::Char('s') => app.toggle_process_sort(),
                     KeyCode::Char('r') => app.request_reconnect(),
                     KeyCode::Char('t') => app.cycle_history_window(),
-                    KeyCode::Char('c') => app.control.toggle_jetson_clocks(),
-                    KeyCode::Char('m') => app.control.cycle_nvpmodel(),
-                    KeyCode::Char('f') => app.control.set_fan(80),
+                    KeyCode::Char('g') => app.cycle_graph_style(),
+                    KeyCode::Char('l') => app.cycle_layout(),
+                    KeyCode::Char('b') => app.start_burst(),
+                    KeyCode::Char('L') => app.cycle_language(),
+                    KeyCode::Char('x') => app.dump_requested = Some(DumpFormat::Ansi),
+                    KeyCode::Char('X') => app.dump_requested = Some(DumpFormat::Html),
+                    KeyCode::Char('+') | KeyCode::Char('=') => app.adjust_refresh(true),
+                    KeyCode::Char('-') => app.adjust_refresh(false),
+                    KeyCode::Char('c') => app.request(PaletteCommand::ToggleJetsonClocks),
+                    KeyCode::Char('m') => match app.control.next_nvpmodel() {
+                        Some(mode) => app.request(PaletteCommand::Nvpmodel(mode)),
+                        // Reports why nvpmodel is unavailable
+                        None => app.control.cycle_nvpmodel(),
+                    },
+                    KeyCode::Char('f') => app.request(PaletteCommand::Fan(80)),
+                    KeyCode::Char('p') => app.next_profile(),
                     _ => {}
                 }
             }
@@ -8,15 +8,30 @@ pub fn authorize_request(req: &Request, env_var: &str) -> bool {
         Ok(t) if !t.is_empty() => t,
         _ => return true,
     };
-    for header in req.headers() {
-        if header.field.equiv("Authorization") {
-            let val = header.value.as_str();
-            if let Some(token) = val.strip_prefix("Bearer ").or_else(|| val.strip_prefix("bearer ")) {
-                if token == expected {
-                    return true;
-                }
-            }
-        }
+    bearer_token(req).is_some_and(|token| token == expected)
+}
+
+/// The token of an `Authorization: Bearer <token>` header.
+pub fn bearer_token(req: &Request) -> Option<String> {
+    req.headers()
+        .iter()
+        .filter(|h| h.field.equiv("Authorization"))
+        .find_map(|h| {
+            let val = h.value.as_str();
+            val.strip_prefix("Bearer ")
+                .or_else(|| val.strip_prefix("bearer "))
+                .map(str::to_string)
+        })
+}
+
+/// Like `authorize_request`, also accepting the token as a `token=` query
+/// parameter, for clients such as browser WebSockets that cannot set headers.
+pub fn authorize_request_or_query(req: &Request, query: &str, env_var: &str) -> bool {
+    if authorize_request(req, env_var) {
+        return true;
+    }
+    match (std::env::var(env_var), crate::websocket::query_param(query, "token")) {
+        (Ok(expected), Some(token)) => token == expected,
+        _ => false,
     }
-    false
 }
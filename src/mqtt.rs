@@ -0,0 +1,345 @@
+use crate::export::{self, KeyMetric, MetricKind};
+use crate::hardware::JetsonHardware;
+use anyhow::{anyhow, Context, Result};
+use serde_json::{json, Map, Value};
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::time::{Duration, Instant};
+
+/// MQTT exporter settings (JETSONSCOPE_MQTT_* environment variables).
+#[derive(Debug, Clone)]
+pub struct MqttConfig {
+    /// Broker "host:port"
+    pub addr: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Topic root; state goes to "<prefix>/<device>/state"
+    pub topic_prefix: String,
+    pub device: String,
+    pub interval: Duration,
+    /// Home Assistant discovery prefix; `None` disables discovery
+    pub discovery_prefix: Option<String>,
+    /// Also publish the full stats and control status JSON
+    pub full_payloads: bool,
+}
+
+impl MqttConfig {
+    /// `JETSONSCOPE_MQTT_ADDR=host:port` enables the exporter; `JETSONSCOPE_MQTT_TOPIC`
+    /// (default "jetsonscope"), `JETSONSCOPE_MQTT_INTERVAL` (secs, default 10),
+    /// `JETSONSCOPE_MQTT_USER`/`JETSONSCOPE_MQTT_PASSWORD` and `JETSONSCOPE_HA_DISCOVERY`
+    /// ("0" disables, otherwise the discovery prefix; default "homeassistant") tune it.
+    /// `JETSONSCOPE_MQTT_FULL=0` leaves out the full stats and control topics.
+    pub fn from_env() -> Option<Self> {
+        let addr = std::env::var("JETSONSCOPE_MQTT_ADDR").ok()?;
+        let var = |key: &str| std::env::var(key).ok().filter(|s| !s.is_empty());
+        let interval = var("JETSONSCOPE_MQTT_INTERVAL")
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(10)
+            .max(1);
+        let discovery_prefix = match var("JETSONSCOPE_HA_DISCOVERY").as_deref() {
+            Some("0") | Some("off") | Some("false") => None,
+            Some("1") | Some("on") | Some("true") | None => Some("homeassistant".to_string()),
+            Some(prefix) => Some(prefix.to_string()),
+        };
+        Some(MqttConfig {
+            addr,
+            username: var("JETSONSCOPE_MQTT_USER"),
+            password: var("JETSONSCOPE_MQTT_PASSWORD"),
+            topic_prefix: var("JETSONSCOPE_MQTT_TOPIC").unwrap_or_else(|| "jetsonscope".into()),
+            device: export::sanitize_key(&export::device_name()),
+            interval: Duration::from_secs(interval),
+            discovery_prefix,
+            full_payloads: !matches!(
+                var("JETSONSCOPE_MQTT_FULL").as_deref(),
+                Some("0") | Some("off") | Some("false")
+            ),
+        })
+    }
+
+    pub fn state_topic(&self) -> String {
+        format!("{}/{}/state", self.topic_prefix, self.device)
+    }
+
+    pub fn availability_topic(&self) -> String {
+        format!("{}/{}/availability", self.topic_prefix, self.device)
+    }
+
+    /// Full `TegraStats` JSON
+    pub fn stats_topic(&self) -> String {
+        format!("{}/{}/stats", self.topic_prefix, self.device)
+    }
+
+    /// `ControlStatus` JSON (retained)
+    pub fn control_topic(&self) -> String {
+        format!("{}/{}/control", self.topic_prefix, self.device)
+    }
+}
+
+/// Minimal MQTT 3.1.1 publisher (QoS 0 only).
+pub struct MqttClient {
+    stream: TcpStream,
+    /// Set to a retained "offline" on a clean disconnect
+    availability_topic: String,
+    keep_alive: Duration,
+    /// Last packet sent; the broker drops clients silent for 1.5x the keep-alive
+    last_sent: Instant,
+}
+
+impl MqttClient {
+    /// Connect with a retained "offline" last will on the availability topic.
+    pub fn connect(cfg: &MqttConfig) -> Result<Self> {
+        let mut stream = TcpStream::connect(&cfg.addr)
+            .with_context(|| format!("connecting to MQTT broker {}", cfg.addr))?;
+        stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+        let keep_alive = (cfg.interval.as_secs() * 3).clamp(30, u16::MAX as u64) as u16;
+        stream.write_all(&encode_connect(cfg, keep_alive))?;
+
+        let mut connack = [0u8; 4];
+        stream
+            .read_exact(&mut connack)
+            .context("waiting for CONNACK")?;
+        if connack[0] != 0x20 || connack[3] != 0 {
+            return Err(anyhow!("broker refused connection (code {})", connack[3]));
+        }
+        Ok(MqttClient {
+            stream,
+            availability_topic: cfg.availability_topic(),
+            keep_alive: Duration::from_secs(keep_alive as u64),
+            last_sent: Instant::now(),
+        })
+    }
+
+    pub fn publish(&mut self, topic: &str, payload: &[u8], retain: bool) -> Result<()> {
+        self.stream
+            .write_all(&encode_publish(topic, payload, retain))
+            .with_context(|| format!("publishing to {}", topic))?;
+        self.last_sent = Instant::now();
+        Ok(())
+    }
+
+    /// PINGREQ once half the keep-alive has passed without a packet; a broker
+    /// that does not answer with PINGRESP is an error.
+    pub fn keep_alive(&mut self) -> Result<()> {
+        if self.last_sent.elapsed() < self.keep_alive / 2 {
+            return Ok(());
+        }
+        self.stream.write_all(&[0xC0, 0x00]).context("sending PINGREQ")?;
+        let mut pingresp = [0u8; 2];
+        self.stream
+            .read_exact(&mut pingresp)
+            .context("waiting for PINGRESP")?;
+        if pingresp != [0xD0, 0x00] {
+            return Err(anyhow!("unexpected reply to PINGREQ: {:02x?}", pingresp));
+        }
+        self.last_sent = Instant::now();
+        Ok(())
+    }
+}
+
+impl Drop for MqttClient {
+    fn drop(&mut self) {
+        // DISCONNECT makes the broker discard the will, so say "offline" first
+        let offline = encode_publish(&self.availability_topic, b"offline", true);
+        let _ = self.stream.write_all(&offline);
+        let _ = self.stream.write_all(&[0xE0, 0x00]);
+    }
+}
+
+/// Flat JSON object published on the state topic, e.g. {"cpu_load": 12.5, "temp_tj": 51.0}.
+pub fn state_payload(metrics: &[KeyMetric]) -> String {
+    let obj: Map<String, Value> = metrics
+        .iter()
+        .map(|m| (m.key.clone(), json!((m.value * 100.0).round() / 100.0)))
+        .collect();
+    Value::Object(obj).to_string()
+}
+
+/// Home Assistant discovery (topic, payload) pairs, one sensor per metric.
+pub fn discovery_messages(
+    cfg: &MqttConfig,
+    metrics: &[KeyMetric],
+    hw: &JetsonHardware,
+) -> Vec<(String, String)> {
+    let Some(prefix) = &cfg.discovery_prefix else {
+        return Vec::new();
+    };
+    let model = if hw.model.is_empty() {
+        "Jetson".to_string()
+    } else {
+        hw.model.clone()
+    };
+    let device = json!({
+        "identifiers": [format!("jetsonscope_{}", cfg.device)],
+        "name": cfg.device,
+        "manufacturer": "NVIDIA",
+        "model": model,
+        "sw_version": hw.l4t_version,
+    });
+    metrics
+        .iter()
+        .map(|m| {
+            let mut payload = json!({
+                "name": m.name,
+                "unique_id": format!("jetsonscope_{}_{}", cfg.device, m.key),
+                "state_topic": cfg.state_topic(),
+                "availability_topic": cfg.availability_topic(),
+                "value_template": format!("{{{{ value_json.{} }}}}", m.key),
+                "unit_of_measurement": m.kind.unit(),
+                "state_class": "measurement",
+                "device": device,
+            });
+            let class = match m.kind {
+                MetricKind::Temperature => Some("temperature"),
+                MetricKind::Power => Some("power"),
+                MetricKind::Percent => None,
+            };
+            if let Some(class) = class {
+                payload["device_class"] = json!(class);
+            }
+            (
+                format!("{}/sensor/{}/{}/config", prefix, cfg.device, m.key),
+                payload.to_string(),
+            )
+        })
+        .collect()
+}
+
+fn encode_connect(cfg: &MqttConfig, keep_alive: u16) -> Vec<u8> {
+    // Clean session + retained will (QoS 0)
+    let mut flags = 0x02 | 0x04 | 0x20;
+    if cfg.username.is_some() {
+        flags |= 0x80;
+    }
+    if cfg.password.is_some() {
+        flags |= 0x40;
+    }
+    let mut body = Vec::new();
+    put_str(&mut body, "MQTT");
+    body.push(4); // protocol level 3.1.1
+    body.push(flags);
+    body.extend_from_slice(&keep_alive.to_be_bytes());
+    put_str(&mut body, &format!("jetsonscope-{}", cfg.device));
+    put_str(&mut body, &cfg.availability_topic());
+    put_str(&mut body, "offline");
+    if let Some(user) = &cfg.username {
+        put_str(&mut body, user);
+    }
+    if let Some(pass) = &cfg.password {
+        put_str(&mut body, pass);
+    }
+    packet(0x10, body)
+}
+
+fn encode_publish(topic: &str, payload: &[u8], retain: bool) -> Vec<u8> {
+    let mut body = Vec::new();
+    put_str(&mut body, topic);
+    body.extend_from_slice(payload);
+    packet(if retain { 0x31 } else { 0x30 }, body)
+}
+
+fn packet(header: u8, body: Vec<u8>) -> Vec<u8> {
+    let mut out = vec![header];
+    // Remaining length: 7 bits per byte, high bit = continuation
+    let mut len = body.len();
+    loop {
+        let mut byte = (len % 128) as u8;
+        len /= 128;
+        if len > 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if len == 0 {
+            break;
+        }
+    }
+    out.extend(body);
+    out
+}
+
+fn put_str(buf: &mut Vec<u8>, s: &str) {
+    buf.extend_from_slice(&(s.len() as u16).to_be_bytes());
+    buf.extend_from_slice(s.as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> MqttConfig {
+        MqttConfig {
+            addr: "localhost:1883".into(),
+            username: None,
+            password: None,
+            topic_prefix: "jetsonscope".into(),
+            device: "orin1".into(),
+            interval: Duration::from_secs(10),
+            discovery_prefix: Some("homeassistant".into()),
+            full_payloads: true,
+        }
+    }
+
+    #[test]
+    fn encodes_publish_with_varint_length() {
+        let pkt = encode_publish("a/b", &[b'x'; 200], true);
+        assert_eq!(pkt[0], 0x31);
+        // 2 + 3 + 200 = 205 -> 0xCD 0x01
+        assert_eq!(&pkt[1..3], &[0xCD, 0x01]);
+        assert_eq!(&pkt[3..8], &[0, 3, b'a', b'/', b'b']);
+        assert_eq!(pkt.len(), 3 + 205);
+    }
+
+    #[test]
+    fn pings_when_idle_and_says_offline_on_disconnect() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut cfg = config();
+        cfg.addr = listener.local_addr().unwrap().to_string();
+        let broker = std::thread::spawn(move || {
+            let (mut peer, _) = listener.accept().unwrap();
+            let mut connect = [0u8; 2];
+            peer.read_exact(&mut connect).unwrap();
+            let mut rest = vec![0u8; connect[1] as usize];
+            peer.read_exact(&mut rest).unwrap();
+            peer.write_all(&[0x20, 0x02, 0x00, 0x00]).unwrap();
+            let mut pingreq = [0u8; 2];
+            peer.read_exact(&mut pingreq).unwrap();
+            peer.write_all(&[0xD0, 0x00]).unwrap();
+            let mut tail = Vec::new();
+            peer.read_to_end(&mut tail).unwrap();
+            (pingreq, tail)
+        });
+
+        let mut client = MqttClient::connect(&cfg).unwrap();
+        // Fresh connection: nothing to do yet
+        client.keep_alive().unwrap();
+        client.last_sent -= client.keep_alive / 2;
+        client.keep_alive().unwrap();
+        drop(client);
+
+        let (pingreq, tail) = broker.join().unwrap();
+        assert_eq!(pingreq, [0xC0, 0x00]);
+        let mut expected = encode_publish("jetsonscope/orin1/availability", b"offline", true);
+        expected.extend_from_slice(&[0xE0, 0x00]);
+        assert_eq!(tail, expected);
+    }
+
+    #[test]
+    fn builds_discovery_for_each_metric() {
+        let metrics = vec![KeyMetric {
+            key: "temp_tj".into(),
+            name: "tj temperature".into(),
+            kind: MetricKind::Temperature,
+            value: 51.25,
+        }];
+        let msgs = discovery_messages(&config(), &metrics, &JetsonHardware::default());
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].0, "homeassistant/sensor/orin1/temp_tj/config");
+        let payload: Value = serde_json::from_str(&msgs[0].1).unwrap();
+        assert_eq!(payload["state_topic"], "jetsonscope/orin1/state");
+        assert_eq!(payload["value_template"], "{{ value_json.temp_tj }}");
+        assert_eq!(payload["device_class"], "temperature");
+        assert_eq!(payload["unique_id"], "jetsonscope_orin1_temp_tj");
+        assert_eq!(state_payload(&metrics), r#"{"temp_tj":51.25}"#);
+        assert_eq!(config().stats_topic(), "jetsonscope/orin1/stats");
+        assert_eq!(config().control_topic(), "jetsonscope/orin1/control");
+    }
+}
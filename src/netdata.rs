@@ -0,0 +1,267 @@
+//! Netdata external plugin protocol (`jscoped --netdata`).
+//!
+//! Netdata runs plugins from `plugins.d/` with the update interval as the first
+//! argument and reads CHART/DIMENSION definitions followed by BEGIN/SET/END blocks
+//! from stdout. Values are integers, so fractional units use the dimension divisor.
+
+use crate::export;
+use crate::parser::TegraStats;
+use crate::thermal_governor::valid_temp;
+use std::collections::BTreeMap;
+
+/// Chart type prefix, i.e. charts are "jetsonscope.<id>".
+const TYPE: &str = "jetsonscope";
+/// Netdata priorities for external plugins start around 100000.
+const PRIORITY: u32 = 140000;
+
+struct Chart {
+    id: &'static str,
+    title: &'static str,
+    units: &'static str,
+    family: &'static str,
+    kind: &'static str,
+    divisor: u32,
+    /// (dimension id, display name, value already scaled by `divisor`)
+    dims: Vec<(String, String, i64)>,
+}
+
+/// Tracks which charts were announced so definitions are only sent when the
+/// dimension set changes (e.g. an engine shows up after boot).
+pub struct NetdataWriter {
+    update_every: u64,
+    defined: BTreeMap<&'static str, Vec<String>>,
+}
+
+impl NetdataWriter {
+    pub fn new(update_every: u64) -> Self {
+        NetdataWriter {
+            update_every: update_every.max(1),
+            defined: BTreeMap::new(),
+        }
+    }
+
+    /// Protocol text for one sample; `since_last_us` is 0 on the first one.
+    pub fn sample(&mut self, stats: &TegraStats, since_last_us: u64) -> String {
+        let mut out = String::new();
+        for (order, chart) in charts(stats).into_iter().enumerate() {
+            if chart.dims.is_empty() {
+                continue;
+            }
+            let ids: Vec<String> = chart.dims.iter().map(|(id, _, _)| id.clone()).collect();
+            if self.defined.get(chart.id) != Some(&ids) {
+                out.push_str(&format!(
+                    "CHART {TYPE}.{id} '' '{title}' '{units}' '{family}' '{TYPE}.{id}' {kind} {prio} {every}\n",
+                    id = chart.id,
+                    title = chart.title,
+                    units = chart.units,
+                    family = chart.family,
+                    kind = chart.kind,
+                    prio = PRIORITY + order as u32,
+                    every = self.update_every,
+                ));
+                for (id, name, _) in &chart.dims {
+                    out.push_str(&format!(
+                        "DIMENSION {} '{}' absolute 1 {}\n",
+                        id, name, chart.divisor
+                    ));
+                }
+                self.defined.insert(chart.id, ids);
+            }
+            if since_last_us > 0 {
+                out.push_str(&format!("BEGIN {TYPE}.{} {}\n", chart.id, since_last_us));
+            } else {
+                out.push_str(&format!("BEGIN {TYPE}.{}\n", chart.id));
+            }
+            for (id, _, value) in &chart.dims {
+                out.push_str(&format!("SET {} = {}\n", id, value));
+            }
+            out.push_str("END\n");
+        }
+        out
+    }
+}
+
+fn charts(stats: &TegraStats) -> Vec<Chart> {
+    let sorted = |keys: Vec<&String>| {
+        let mut keys: Vec<String> = keys.into_iter().cloned().collect();
+        keys.sort();
+        keys
+    };
+
+    let cpu_load = stats
+        .cpus
+        .iter()
+        .enumerate()
+        .filter_map(|(i, c)| Some((format!("cpu{i}"), format!("CPU{i}"), c.load_percent? as i64)))
+        .collect();
+    let cpu_freq = stats
+        .cpus
+        .iter()
+        .enumerate()
+        .filter_map(|(i, c)| Some((format!("cpu{i}"), format!("CPU{i}"), c.freq_mhz? as i64)))
+        .collect();
+
+    let engines = sorted(stats.engines.keys().collect());
+    let engine_load = engines
+        .iter()
+        .filter_map(|name| {
+            let usage = stats.engines[name].usage_percent?;
+            Some((export::sanitize_key(name), name.clone(), usage as i64))
+        })
+        .collect();
+    let engine_freq = engines
+        .iter()
+        .filter_map(|name| {
+            let freq = stats.engines[name].freq_mhz?;
+            Some((export::sanitize_key(name), name.clone(), freq as i64))
+        })
+        .collect();
+
+    let temps = sorted(stats.temps.keys().collect())
+        .into_iter()
+        .filter(|name| valid_temp(stats.temps[name]))
+        .map(|name| {
+            let milli = (stats.temps[&name] * 1000.0).round() as i64;
+            (export::sanitize_key(&name), name, milli)
+        })
+        .collect();
+    let power = sorted(stats.power.keys().collect())
+        .into_iter()
+        .map(|name| {
+            let mw = stats.power[&name].current_mw as i64;
+            (export::sanitize_key(&name), name, mw)
+        })
+        .collect();
+
+    let mib = 1024 * 1024;
+    let mut ram = Vec::new();
+    if let Some(r) = &stats.ram {
+        ram.push((
+            "used".to_string(),
+            "used".to_string(),
+            (r.used_bytes / mib) as i64,
+        ));
+        ram.push((
+            "free".to_string(),
+            "free".to_string(),
+            (r.total_bytes.saturating_sub(r.used_bytes) / mib) as i64,
+        ));
+    }
+    let mut swap = Vec::new();
+    if let Some(s) = &stats.swap {
+        swap.push((
+            "used".to_string(),
+            "used".to_string(),
+            (s.used_bytes / mib) as i64,
+        ));
+        swap.push((
+            "free".to_string(),
+            "free".to_string(),
+            (s.total_bytes.saturating_sub(s.used_bytes) / mib) as i64,
+        ));
+    }
+
+    vec![
+        Chart {
+            id: "cpu_load",
+            title: "CPU load per core",
+            units: "percentage",
+            family: "cpu",
+            kind: "line",
+            divisor: 1,
+            dims: cpu_load,
+        },
+        Chart {
+            id: "cpu_freq",
+            title: "CPU frequency per core",
+            units: "MHz",
+            family: "cpu",
+            kind: "line",
+            divisor: 1,
+            dims: cpu_freq,
+        },
+        Chart {
+            id: "engine_load",
+            title: "Engine utilization (GR3D is the GPU)",
+            units: "percentage",
+            family: "engines",
+            kind: "line",
+            divisor: 1,
+            dims: engine_load,
+        },
+        Chart {
+            id: "engine_freq",
+            title: "Engine clocks",
+            units: "MHz",
+            family: "engines",
+            kind: "line",
+            divisor: 1,
+            dims: engine_freq,
+        },
+        Chart {
+            id: "temperature",
+            title: "Thermal zones",
+            units: "Celsius",
+            family: "thermal",
+            kind: "line",
+            divisor: 1000,
+            dims: temps,
+        },
+        Chart {
+            id: "power",
+            title: "Power rails",
+            units: "Watts",
+            family: "power",
+            kind: "line",
+            divisor: 1000,
+            dims: power,
+        },
+        Chart {
+            id: "ram",
+            title: "RAM (shared with the GPU)",
+            units: "MiB",
+            family: "memory",
+            kind: "stacked",
+            divisor: 1,
+            dims: ram,
+        },
+        Chart {
+            id: "swap",
+            title: "Swap",
+            units: "MiB",
+            family: "memory",
+            kind: "stacked",
+            divisor: 1,
+            dims: swap,
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defines_charts_once_then_sets_values() {
+        let stats = TegraStats::parse(
+            "RAM 2048/7620MB (lfb 10x4MB) CPU [10%@1497,off] GR3D_FREQ 95%@918 tj@51.25C VDD_IN 9000mW/9000mW",
+        )
+        .unwrap();
+        let mut writer = NetdataWriter::new(1);
+        let first = writer.sample(&stats, 0);
+        assert!(first.contains(
+            "CHART jetsonscope.temperature '' 'Thermal zones' 'Celsius' 'thermal' 'jetsonscope.temperature' line"
+        ));
+        assert!(first.contains("DIMENSION tj 'tj' absolute 1 1000\n"));
+        assert!(first.contains("BEGIN jetsonscope.temperature\nSET tj = 51250\nEND\n"));
+        assert!(first.contains("BEGIN jetsonscope.power\nSET vdd_in = 9000\nEND\n"));
+        assert!(first.contains("SET gr3d = 95\n"));
+        assert!(first.contains("SET used = 2048\n"));
+        // No swap in the sample, so no swap chart
+        assert!(!first.contains("jetsonscope.swap"));
+
+        let second = writer.sample(&stats, 1_000_000);
+        assert!(!second.contains("CHART"));
+        assert!(second.contains("BEGIN jetsonscope.cpu_load 1000000\nSET cpu0 = 10\nEND\n"));
+    }
+}
@@ -0,0 +1,161 @@
+//! Per-interface network traffic from `/proc/net/dev`.
+//!
+//! The kernel only exposes byte counters, so rates come from the difference
+//! between two samples; the first sample of an interface reports 0 B/s.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+const PROC_NET_DEV: &str = "/proc/net/dev";
+
+/// Traffic of one network interface (not part of the tegrastats line).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InterfaceStats {
+    pub name: String,
+    /// Counters since boot (or since the driver was loaded)
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+    pub rx_bytes_per_sec: f64,
+    pub tx_bytes_per_sec: f64,
+}
+
+/// Interface name and rx/tx byte counters per line of `/proc/net/dev`, sorted
+/// by name. Loopback is skipped.
+pub fn parse_proc_net_dev(content: &str) -> Vec<(String, u64, u64)> {
+    let mut counters: Vec<(String, u64, u64)> = content
+        .lines()
+        .filter_map(|line| {
+            let (name, fields) = line.split_once(':')?;
+            let name = name.trim();
+            if name == "lo" {
+                return None;
+            }
+            let fields: Vec<u64> = fields
+                .split_whitespace()
+                .map(|f| f.parse().ok())
+                .collect::<Option<_>>()?;
+            // 8 receive columns come first, transmit bytes is the 9th
+            Some((name.to_string(), *fields.first()?, *fields.get(8)?))
+        })
+        .collect();
+    counters.sort_by(|a, b| a.0.cmp(&b.0));
+    counters
+}
+
+/// Keeps the previous counters so each sample carries per-second rates.
+pub struct NetworkSampler {
+    path: PathBuf,
+    prev: HashMap<String, (u64, u64)>,
+    prev_at: Option<Instant>,
+}
+
+impl Default for NetworkSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NetworkSampler {
+    pub fn new() -> Self {
+        Self::with_path(Path::new(PROC_NET_DEV))
+    }
+
+    pub fn with_path(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            prev: HashMap::new(),
+            prev_at: None,
+        }
+    }
+
+    /// Read the counters; empty when the file is missing (non-Linux hosts).
+    pub fn sample(&mut self) -> Vec<InterfaceStats> {
+        let Ok(content) = fs::read_to_string(&self.path) else {
+            return Vec::new();
+        };
+        self.update(&content, Instant::now())
+    }
+
+    fn update(&mut self, content: &str, now: Instant) -> Vec<InterfaceStats> {
+        let secs = self
+            .prev_at
+            .map(|at| now.duration_since(at).as_secs_f64())
+            .filter(|s| *s > 0.0);
+        let counters = parse_proc_net_dev(content);
+        let stats = counters
+            .iter()
+            .map(|(name, rx, tx)| {
+                // A counter that went backwards (driver reload) restarts the rate
+                let rate = |prev: Option<u64>, cur: u64| match (prev, secs) {
+                    (Some(p), Some(s)) if cur >= p => (cur - p) as f64 / s,
+                    _ => 0.0,
+                };
+                let prev = self.prev.get(name);
+                InterfaceStats {
+                    name: name.clone(),
+                    rx_bytes: *rx,
+                    tx_bytes: *tx,
+                    rx_bytes_per_sec: rate(prev.map(|p| p.0), *rx),
+                    tx_bytes_per_sec: rate(prev.map(|p| p.1), *tx),
+                }
+            })
+            .collect();
+        self.prev = counters
+            .into_iter()
+            .map(|(name, rx, tx)| (name, (rx, tx)))
+            .collect();
+        self.prev_at = Some(now);
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const HEADER: &str = "Inter-|   Receive                                                |  Transmit\n \
+face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed\n";
+
+    fn dev(eth0: (u64, u64)) -> String {
+        format!(
+            "{HEADER}    lo: 5000 50 0 0 0 0 0 0 5000 50 0 0 0 0 0 0\n  \
+eth0: {} 900 0 0 0 0 0 12 {} 700 0 0 0 0 0 0\n\
+wlan0: 10 1 0 0 0 0 0 0 20 2 0 0 0 0 0 0\n",
+            eth0.0, eth0.1
+        )
+    }
+
+    #[test]
+    fn parses_counters_without_loopback() {
+        let counters = parse_proc_net_dev(&dev((1_000, 2_000)));
+        assert_eq!(
+            counters,
+            vec![
+                ("eth0".to_string(), 1_000, 2_000),
+                ("wlan0".to_string(), 10, 20),
+            ]
+        );
+    }
+
+    #[test]
+    fn rates_come_from_consecutive_samples() {
+        let mut sampler = NetworkSampler::with_path(Path::new("/nonexistent"));
+        let start = Instant::now();
+        let first = sampler.update(&dev((1_000, 2_000)), start);
+        assert_eq!(first[0].rx_bytes_per_sec, 0.0);
+
+        let next = sampler.update(&dev((3_000, 2_500)), start + Duration::from_secs(2));
+        assert_eq!(next[0].rx_bytes, 3_000);
+        assert_eq!(next[0].rx_bytes_per_sec, 1_000.0);
+        assert_eq!(next[0].tx_bytes_per_sec, 250.0);
+
+        // Counter reset after a driver reload
+        let reset = sampler.update(&dev((100, 100)), start + Duration::from_secs(3));
+        assert_eq!(reset[0].rx_bytes_per_sec, 0.0);
+        assert!(sampler.sample().is_empty());
+    }
+}
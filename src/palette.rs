@@ -0,0 +1,148 @@
+//! Ctrl-P command palette: fuzzy-filtered list of TUI actions.
+
+use crate::app::ViewMode;
+use crate::screen_dump::DumpFormat;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaletteCommand {
+    View(ViewMode),
+    CycleHistoryWindow,
+    CycleGraphStyle,
+    CycleLayout,
+    CycleTheme,
+    RefreshFaster,
+    RefreshSlower,
+    ToggleProcessSort,
+    ToggleJetsonClocks,
+    Nvpmodel(String),
+    CpuGovernor(String),
+    Fan(u8),
+    FanCurve(String),
+    /// Apply a daemon profile by name (`p`)
+    ApplyProfile(String),
+    /// SIGTERM through the daemon (Processes view `k`)
+    SignalProcess { pid: u32, name: String },
+    /// Renice through the daemon (Processes view `n`)
+    ReniceProcess { pid: u32, name: String, nice: i32 },
+    ExportSnapshot,
+    DumpScreen(DumpFormat),
+    StartBurst,
+    CycleLanguage,
+    Reconnect,
+    Help,
+}
+
+#[derive(Debug, Clone)]
+pub struct PaletteEntry {
+    pub label: String,
+    pub command: PaletteCommand,
+}
+
+impl PaletteEntry {
+    pub fn new(label: impl Into<String>, command: PaletteCommand) -> Self {
+        PaletteEntry {
+            label: label.into(),
+            command,
+        }
+    }
+}
+
+/// Open palette state: what was typed and which match is highlighted.
+#[derive(Debug, Clone, Default)]
+pub struct Palette {
+    pub query: String,
+    pub selected: usize,
+}
+
+impl Palette {
+    /// Entries matching the query, best first (original order among ties).
+    pub fn filter<'a>(&self, entries: &'a [PaletteEntry]) -> Vec<&'a PaletteEntry> {
+        let mut scored: Vec<(i32, usize, &PaletteEntry)> = entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, e)| Some((fuzzy_score(&self.query, &e.label)?, i, e)))
+            .collect();
+        scored.sort_by_key(|(score, i, _)| (std::cmp::Reverse(*score), *i));
+        scored.into_iter().map(|(_, _, e)| e).collect()
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.query.push(c);
+        self.selected = 0;
+    }
+
+    pub fn pop(&mut self) {
+        self.query.pop();
+        self.selected = 0;
+    }
+
+    /// Move the highlight by `delta`, wrapping around `len` matches.
+    pub fn move_selection(&mut self, delta: isize, len: usize) {
+        if len == 0 {
+            self.selected = 0;
+            return;
+        }
+        self.selected = (self.selected as isize + delta).rem_euclid(len as isize) as usize;
+    }
+}
+
+/// Case-insensitive subsequence match; `None` when `query` is not a subsequence.
+///
+/// Consecutive matches and matches at word starts score higher, so "nvm"
+/// ranks "nvpmodel: MAXN" above labels that merely contain those letters.
+pub fn fuzzy_score(query: &str, label: &str) -> Option<i32> {
+    let query: Vec<char> = query.to_lowercase().chars().filter(|c| *c != ' ').collect();
+    let label: Vec<char> = label.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut prev_match: Option<usize> = None;
+    for q in query {
+        let idx = (pos..label.len()).find(|&i| label[i] == q)?;
+        score += 1;
+        if prev_match.is_some_and(|p| p + 1 == idx) {
+            score += 5;
+        }
+        if idx == 0 || !label[idx - 1].is_alphanumeric() {
+            score += 3;
+        }
+        prev_match = Some(idx);
+        pos = idx + 1;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_fuzzy_matches() {
+        let entries = vec![
+            PaletteEntry::new("Vista: Procesos", PaletteCommand::View(ViewMode::Processes)),
+            PaletteEntry::new("nvpmodel: MAXN", PaletteCommand::Nvpmodel("MAXN".into())),
+            PaletteEntry::new("Fan 80%", PaletteCommand::Fan(80)),
+            PaletteEntry::new("Exportar snapshot (JSON)", PaletteCommand::ExportSnapshot),
+        ];
+        let mut palette = Palette::default();
+        assert_eq!(palette.filter(&entries).len(), 4);
+
+        for c in "nvm".chars() {
+            palette.push(c);
+        }
+        let hits = palette.filter(&entries);
+        assert_eq!(hits[0].command, PaletteCommand::Nvpmodel("MAXN".into()));
+
+        palette.query = "snap".into();
+        let hits = palette.filter(&entries);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].command, PaletteCommand::ExportSnapshot);
+
+        assert!(fuzzy_score("xyz", "Fan 80%").is_none());
+        assert!(fuzzy_score("fan", "Fan 80%") > fuzzy_score("fn", "Fan 80%"));
+
+        palette.move_selection(-1, 3);
+        assert_eq!(palette.selected, 2);
+        palette.move_selection(1, 3);
+        assert_eq!(palette.selected, 0);
+    }
+}
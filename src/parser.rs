@@ -1,8 +1,17 @@
 use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDateTime, SecondsFormat, TimeZone, Utc};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+
+use crate::disk::DiskStats;
+use crate::hardware::EmcBandwidth;
+use crate::network::InterfaceStats;
+use crate::power_supply::PowerSupply;
+use crate::throttle::ThrottleState;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[derive(Default)]
@@ -10,6 +19,7 @@ pub enum SizeUnit {
     KB,
     #[default]
     MB,
+    GB,
 }
 
 
@@ -18,16 +28,32 @@ impl SizeUnit {
         match raw {
             "k" | "K" => Some(SizeUnit::KB),
             "M" | "m" => Some(SizeUnit::MB),
+            "G" | "g" => Some(SizeUnit::GB),
             _ => None,
         }
     }
 
-    pub fn to_bytes(self, value: u64) -> u64 {
+    fn bytes_per_unit(self) -> u64 {
         match self {
-            SizeUnit::KB => value.saturating_mul(1024),
-            SizeUnit::MB => value.saturating_mul(1024 * 1024),
+            SizeUnit::KB => 1024,
+            SizeUnit::MB => 1024 * 1024,
+            SizeUnit::GB => 1024 * 1024 * 1024,
         }
     }
+
+    pub fn to_bytes(self, value: u64) -> u64 {
+        value.saturating_mul(self.bytes_per_unit())
+    }
+
+    /// Bytes in a fractional value such as the `12.3` of `RAM 12.3/61.4GB`.
+    pub fn fraction_to_bytes(self, value: f64) -> u64 {
+        (value * self.bytes_per_unit() as f64).round() as u64
+    }
+
+    /// `bytes` expressed in this unit.
+    pub fn value_of(self, bytes: u64) -> f64 {
+        bytes as f64 / self.bytes_per_unit() as f64
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -65,6 +91,9 @@ pub struct IramStat {
 pub struct CpuCore {
     pub load_percent: Option<u32>,
     pub freq_mhz: Option<u32>,
+    /// Index into the board's CPU clusters (`hardware::read_cpu_clusters`), when known
+    #[serde(default)]
+    pub cluster: Option<u32>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -88,7 +117,11 @@ pub struct MtsStat {
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TegraStats {
+    /// Local wall-clock time as tegrastats printed it ("%m-%d-%Y %H:%M:%S")
     pub timestamp: Option<String>,
+    /// `timestamp` as Unix seconds; absent from older daemons' replies (see `time`)
+    #[serde(default)]
+    pub timestamp_unix: Option<i64>,
     pub ram: Option<MemoryStat>,
     pub swap: Option<SwapStat>,
     pub iram: Option<IramStat>,
@@ -97,12 +130,39 @@ pub struct TegraStats {
     pub engines: HashMap<String, EngineStat>,
     pub temps: HashMap<String, f32>,
     pub power: HashMap<String, PowerRail>,
+    /// Batteries / PD inputs from sysfs (not part of the tegrastats line)
+    #[serde(default)]
+    pub supplies: Vec<PowerSupply>,
+    /// Per-interface traffic from /proc/net/dev (not part of the tegrastats line)
+    #[serde(default)]
+    pub network: Vec<InterfaceStats>,
+    /// Mount usage and block-device throughput; `None` when unavailable
+    #[serde(default)]
+    pub disk: Option<DiskStats>,
+    /// Why clocks are held back; `None` while not throttling or unknown
+    #[serde(default)]
+    pub throttling: Option<ThrottleState>,
+    /// Memory bandwidth from EMC load and clock; `None` on unknown modules
+    #[serde(default)]
+    pub emc_bandwidth: Option<EmcBandwidth>,
     #[allow(dead_code)]
     pub raw: String,
 }
 
 impl TegraStats {
     pub fn parse(line: &str) -> Result<Self> {
+        Self::parse_with(line, scan_fields)
+    }
+
+    /// `parse` with the previous regex field parsing, kept for comparison
+    /// (`cargo bench --bench parser --features regex-parser`).
+    #[cfg(feature = "regex-parser")]
+    #[allow(dead_code)] // benches
+    pub fn parse_regex(line: &str) -> Result<Self> {
+        Self::parse_with(line, regex_fields)
+    }
+
+    fn parse_with(line: &str, parse_fields: fn(&str) -> Fields) -> Result<Self> {
         let raw = line.trim().to_string();
         let mut stats = TegraStats {
             raw: raw.clone(),
@@ -113,6 +173,7 @@ impl TegraStats {
 
         if let Some(mat) = DATE_RE.find(&payload) {
             stats.timestamp = Some(mat.as_str().trim().to_string());
+            stats.timestamp_unix = local_time(mat.as_str().trim()).map(|t| t.timestamp());
             payload.replace_range(mat.range(), "");
             payload = payload.trim_start().to_string();
         }
@@ -121,14 +182,94 @@ impl TegraStats {
         stats.swap = parse_swap(&payload);
         stats.iram = parse_iram(&payload);
         stats.mts = parse_mts(&payload);
-        stats.cpus = parse_cpus(&payload);
-        stats.engines = parse_engines(&payload);
-        stats.temps = parse_temps(&payload);
-        stats.power = parse_power(&payload);
+        let fields = parse_fields(&payload);
+        stats.cpus = fields.cpus;
+        stats.engines = fields.engines;
+        stats.temps = fields.temps;
+        stats.power = fields.power;
 
         Ok(stats)
     }
 
+    /// Sample time: `timestamp_unix`, else `timestamp` read as local time.
+    pub fn time(&self) -> Option<DateTime<Local>> {
+        match self.timestamp_unix {
+            Some(secs) => Local.timestamp_opt(secs, 0).single(),
+            None => local_time(self.timestamp.as_deref()?),
+        }
+    }
+
+    /// Sample time in UTC, RFC 3339 ("2025-11-30T12:26:01Z"), for exports.
+    pub fn time_rfc3339(&self) -> Option<String> {
+        self.time()
+            .map(|t| t.with_timezone(&Utc).to_rfc3339_opts(SecondsFormat::Secs, true))
+    }
+
+    /// No memory, CPU, engine, temperature or rail figures: the line was not tegrastats output.
+    pub fn is_empty(&self) -> bool {
+        self.ram.is_none()
+            && self.cpus.is_empty()
+            && self.engines.is_empty()
+            && self.temps.is_empty()
+            && self.power.is_empty()
+    }
+
+    /// Parse `line` and account for every token in it: which ones a field was
+    /// read from, which ones nothing recognized, and which sections are absent.
+    pub fn parse_report(line: &str) -> ParseReport {
+        let stats = Self::parse(line).unwrap_or_default();
+        let text = stats.raw.as_str();
+        let mut spans: Vec<Range<usize>> = SECTION_RES
+            .iter()
+            .flat_map(|re| re.find_iter(text).map(|m| m.range()))
+            .chain(scan_fields(text).spans)
+            .collect();
+        spans.sort_by_key(|r| r.start);
+        let mut merged: Vec<Range<usize>> = Vec::new();
+        for span in spans {
+            match merged.last_mut() {
+                Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+                _ => merged.push(span),
+            }
+        }
+
+        let mut consumed = Vec::new();
+        let mut unknown = Vec::new();
+        for token in text.split_whitespace() {
+            let start = token.as_ptr() as usize - text.as_ptr() as usize;
+            let end = start + token.len();
+            if merged.iter().any(|m| m.start <= start && end <= m.end) {
+                consumed.push(token.to_string());
+            } else {
+                unknown.push(token.to_string());
+            }
+        }
+        let missing = Section::EXPECTED
+            .into_iter()
+            .filter(|section| !section.present_in(&stats))
+            .collect();
+        ParseReport {
+            stats,
+            consumed,
+            unknown,
+            missing,
+        }
+    }
+
+    /// Like `parse`, but fails when the line has tokens nothing recognized or
+    /// lacks a section every tegrastats line has (`Section::EXPECTED`).
+    pub fn parse_strict(line: &str) -> std::result::Result<Self, ParseError> {
+        let report = Self::parse_report(line);
+        if report.is_clean() {
+            Ok(report.stats)
+        } else {
+            Err(ParseError {
+                unknown: report.unknown,
+                missing: report.missing,
+            })
+        }
+    }
+
     #[allow(dead_code)]
     pub fn ram_ratio(&self) -> f64 {
         self.ram
@@ -162,50 +303,281 @@ impl TegraStats {
             .get("GR3D")
             .and_then(|e| e.usage_percent.or(e.raw_value))
     }
+
+    /// Compute the change from `prev` to this sample (power, temps, loads, clock transitions).
+    pub fn delta(&self, prev: &TegraStats) -> StatsDelta {
+        let elapsed_secs = match (prev.time(), self.time()) {
+            (Some(a), Some(b)) => Some((b - a).num_milliseconds() as f64 / 1000.0),
+            _ => None,
+        };
+
+        let power_mw = self
+            .power
+            .iter()
+            .filter_map(|(name, rail)| {
+                let old = prev.power.get(name)?;
+                Some((name.clone(), rail.current_mw as i64 - old.current_mw as i64))
+            })
+            .collect();
+
+        let temp_c = self
+            .temps
+            .iter()
+            .filter_map(|(name, val)| {
+                let old = prev.temps.get(name)?;
+                Some((name.clone(), val - old))
+            })
+            .collect();
+
+        let cpu_load = self
+            .cpus
+            .iter()
+            .enumerate()
+            .map(|(idx, core)| {
+                let old = prev.cpus.get(idx)?.load_percent?;
+                Some(core.load_percent? as i32 - old as i32)
+            })
+            .collect();
+
+        let gpu_load = match (self.gpu_usage(), prev.gpu_usage()) {
+            (Some(cur), Some(old)) => Some(cur as i32 - old as i32),
+            _ => None,
+        };
+
+        let mut freq_transitions = Vec::new();
+        for (idx, core) in self.cpus.iter().enumerate() {
+            if let (Some(to), Some(from)) = (core.freq_mhz, prev.cpus.get(idx).and_then(|c| c.freq_mhz)) {
+                if to != from {
+                    freq_transitions.push(FreqTransition {
+                        name: format!("CPU{idx}"),
+                        from_mhz: from,
+                        to_mhz: to,
+                    });
+                }
+            }
+        }
+        let mut engine_names: Vec<&String> = self.engines.keys().collect();
+        engine_names.sort();
+        for name in engine_names {
+            let to = self.engines[name].freq_mhz;
+            let from = prev.engines.get(name).and_then(|e| e.freq_mhz);
+            if let (Some(to), Some(from)) = (to, from) {
+                if to != from {
+                    freq_transitions.push(FreqTransition {
+                        name: name.clone(),
+                        from_mhz: from,
+                        to_mhz: to,
+                    });
+                }
+            }
+        }
+
+        StatsDelta {
+            elapsed_secs,
+            power_mw,
+            temp_c,
+            cpu_load,
+            gpu_load,
+            freq_transitions,
+        }
+    }
+}
+
+/// Sample-to-sample change between two `TegraStats` snapshots.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatsDelta {
+    /// Seconds between samples (only when both carry a timestamp)
+    pub elapsed_secs: Option<f64>,
+    /// Change in current power per rail (mW)
+    pub power_mw: HashMap<String, i64>,
+    /// Change in temperature per sensor (C)
+    pub temp_c: HashMap<String, f32>,
+    /// Change in load per CPU core (percentage points)
+    pub cpu_load: Vec<Option<i32>>,
+    /// Change in GR3D load (percentage points)
+    pub gpu_load: Option<i32>,
+    /// CPU cores and engines whose clock changed
+    pub freq_transitions: Vec<FreqTransition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FreqTransition {
+    pub name: String,
+    pub from_mhz: u32,
+    pub to_mhz: u32,
+}
+
+/// A part of the tegrastats line (`ParseReport::missing`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Section {
+    Ram,
+    Swap,
+    Cpu,
+    Emc,
+    Gpu,
+    Temps,
+    Power,
+}
+
+impl Section {
+    /// Printed by every supported L4T release (timestamps, IRAM and MTS are not)
+    pub const EXPECTED: [Section; 7] = [
+        Section::Ram,
+        Section::Swap,
+        Section::Cpu,
+        Section::Emc,
+        Section::Gpu,
+        Section::Temps,
+        Section::Power,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Section::Ram => "RAM",
+            Section::Swap => "SWAP",
+            Section::Cpu => "CPU",
+            Section::Emc => "EMC_FREQ",
+            Section::Gpu => "GR3D_FREQ",
+            Section::Temps => "temperatures",
+            Section::Power => "power rails",
+        }
+    }
+
+    fn present_in(&self, stats: &TegraStats) -> bool {
+        match self {
+            Section::Ram => stats.ram.is_some(),
+            Section::Swap => stats.swap.is_some(),
+            Section::Cpu => !stats.cpus.is_empty(),
+            Section::Emc => stats.engines.contains_key("EMC"),
+            Section::Gpu => stats.engines.contains_key("GR3D"),
+            Section::Temps => !stats.temps.is_empty(),
+            Section::Power => !stats.power.is_empty(),
+        }
+    }
+}
+
+/// What `TegraStats::parse_report` made of a line.
+#[derive(Debug, Clone)]
+pub struct ParseReport {
+    pub stats: TegraStats,
+    /// Tokens a field was read from, in line order
+    #[allow(dead_code)]
+    pub consumed: Vec<String>,
+    /// Tokens nothing recognized (new or renamed fields), in line order
+    pub unknown: Vec<String>,
+    /// Expected sections the line lacks
+    pub missing: Vec<Section>,
+}
+
+impl ParseReport {
+    pub fn is_clean(&self) -> bool {
+        self.unknown.is_empty() && self.missing.is_empty()
+    }
+}
+
+/// Why `TegraStats::parse_strict` rejected a line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub unknown: Vec<String>,
+    pub missing: Vec<Section>,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if !self.unknown.is_empty() {
+            parts.push(format!("unknown tokens: {}", self.unknown.join(" ")));
+        }
+        if !self.missing.is_empty() {
+            let names: Vec<&str> = self.missing.iter().map(Section::name).collect();
+            parts.push(format!("missing: {}", names.join(", ")));
+        }
+        write!(f, "{}", parts.join("; "))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A tegrastats timestamp in the local zone. When a DST change repeats an
+/// hour the earlier reading wins; times skipped by one have none.
+fn local_time(raw: &str) -> Option<DateTime<Local>> {
+    let naive = NaiveDateTime::parse_from_str(raw, "%m-%d-%Y %H:%M:%S").ok()?;
+    Local.from_local_datetime(&naive).earliest()
 }
 
 static DATE_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"\d{2}-\d{2}-\d{4} \d{2}:\d{2}:\d{2}").unwrap());
-static SWAP_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"SWAP (\d+)/(\d+)(\w)B ?\(cached (\d+)(\w)B\)").unwrap());
-static IRAM_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"IRAM (\d+)/(\d+)(\w)B ?\(lfb (\d+)(\w)B\)").unwrap());
-static RAM_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"RAM (\d+)/(\d+)(\w)B ?\(lfb (\d+)x(\d+)(\w)B\)").unwrap());
+// Sizes may be fractional ("RAM 12.3/61.4GB")
+static SWAP_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"SWAP ([\d.]+)/([\d.]+)(\w)B ?\(cached ([\d.]+)(\w)B\)").unwrap()
+});
+static IRAM_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"IRAM ([\d.]+)/([\d.]+)(\w)B ?\(lfb ([\d.]+)(\w)B\)").unwrap()
+});
+static RAM_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bRAM ([\d.]+)/([\d.]+)(\w)B ?\(lfb (\d+)x([\d.]+)(\w)B\)").unwrap()
+});
 static MTS_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"MTS fg (\d+)% bg (\d+)%").unwrap());
+#[cfg(any(test, feature = "regex-parser"))]
 static VALS_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"\b([A-Z0-9_]+) ([0-9%@]+(?:@\[\d+\]|@\d+)?)\b").unwrap());
+    // `\s|$` as well as `\b`: a bare load ("GR3D_FREQ 75%") ends in a non-word character
+    Lazy::new(|| Regex::new(r"\b([A-Z0-9_]+) ([0-9%@]+(?:@\[\d+\]|@\d+)?)(?:\b|\s|$)").unwrap());
+#[cfg(any(test, feature = "regex-parser"))]
 static ENGINE_OFF_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b([A-Z0-9_]+) off\b").unwrap());
+#[cfg(any(test, feature = "regex-parser"))]
 static BRACKET_FREQ_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"([A-Z0-9_]+) ([0-9]+)%@\[(\d+)\]").unwrap());
+#[cfg(any(test, feature = "regex-parser"))]
 static UTIL_ONLY_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"([A-Z0-9_]+_UTIL) ([0-9]+)%").unwrap());
+#[cfg(any(test, feature = "regex-parser"))]
 static VAL_FREQ_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(\d+)%@(\d+)").unwrap());
+#[cfg(any(test, feature = "regex-parser"))]
 static CPU_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"CPU \[(.*?)\]").unwrap());
+#[cfg(any(test, feature = "regex-parser"))]
 static WATT_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"\b(\w+) ([0-9.]+)(\w?)W?/([0-9.]+)(\w?)W?\b").unwrap());
+#[cfg(any(test, feature = "regex-parser"))]
 static TEMP_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(\w+)@(-?[0-9.]+)C\b").unwrap());
 
+/// Timestamp and memory patterns; their matches are tokens `parse_report` counts as consumed
+static SECTION_RES: [&Lazy<Regex>; 5] = [&DATE_RE, &SWAP_RE, &IRAM_RE, &RAM_RE, &MTS_RE];
+
+/// Field patterns of the regex implementation (`regex-parser` feature)
+#[cfg(any(test, feature = "regex-parser"))]
+static FIELD_RES: [&Lazy<Regex>; 8] = [
+    &VALS_RE,
+    &ENGINE_OFF_RE,
+    &BRACKET_FREQ_RE,
+    &UTIL_ONLY_RE,
+    &CPU_RE,
+    &WATT_RE,
+    &TEMP_RE,
+    &VAL_FREQ_RE,
+];
+
 fn parse_size_unit(raw: &str) -> SizeUnit {
     SizeUnit::from_suffix(raw).unwrap_or(SizeUnit::MB)
 }
 
+/// Bytes in a captured size such as `2461` or `12.3`.
+fn size_bytes(raw: &str, unit: SizeUnit) -> u64 {
+    unit.fraction_to_bytes(raw.parse::<f64>().unwrap_or_default())
+}
+
 fn parse_ram(text: &str) -> Option<MemoryStat> {
     RAM_RE.captures(text).map(|caps| {
         let unit = parse_size_unit(&caps[3]);
-        let used = caps[1].parse::<u64>().unwrap_or_default();
-        let total = caps[2].parse::<u64>().unwrap_or_default();
         let lfb_count = caps[4].parse::<u64>().unwrap_or_default();
-        let lfb_size = caps[5].parse::<u64>().unwrap_or_default();
         let lfb_unit = parse_size_unit(&caps[6]);
         let largest_free_block = Some(LargestFreeBlock::Blocks {
             count: lfb_count,
-            size_bytes: lfb_unit.to_bytes(lfb_size),
+            size_bytes: size_bytes(&caps[5], lfb_unit),
         });
 
         MemoryStat {
-            used_bytes: unit.to_bytes(used),
-            total_bytes: unit.to_bytes(total),
+            used_bytes: size_bytes(&caps[1], unit),
+            total_bytes: size_bytes(&caps[2], unit),
             unit,
             largest_free_block,
         }
@@ -216,14 +588,11 @@ fn parse_swap(text: &str) -> Option<SwapStat> {
     SWAP_RE.captures(text).map(|caps| {
         let unit = parse_size_unit(&caps[3]);
         let cached_unit = parse_size_unit(&caps[5]);
-        let used = caps[1].parse::<u64>().unwrap_or_default();
-        let total = caps[2].parse::<u64>().unwrap_or_default();
-        let cached = caps[4].parse::<u64>().unwrap_or_default();
 
         SwapStat {
-            used_bytes: unit.to_bytes(used),
-            total_bytes: unit.to_bytes(total),
-            cached_bytes: Some(cached_unit.to_bytes(cached)),
+            used_bytes: size_bytes(&caps[1], unit),
+            total_bytes: size_bytes(&caps[2], unit),
+            cached_bytes: Some(size_bytes(&caps[4], cached_unit)),
             unit,
         }
     })
@@ -233,14 +602,11 @@ fn parse_iram(text: &str) -> Option<IramStat> {
     IRAM_RE.captures(text).map(|caps| {
         let unit = parse_size_unit(&caps[3]);
         let lfb_unit = parse_size_unit(&caps[5]);
-        let used = caps[1].parse::<u64>().unwrap_or_default();
-        let total = caps[2].parse::<u64>().unwrap_or_default();
-        let lfb = caps[4].parse::<u64>().unwrap_or_default();
 
         IramStat {
-            used_bytes: unit.to_bytes(used),
-            total_bytes: unit.to_bytes(total),
-            lfb_bytes: Some(lfb_unit.to_bytes(lfb)),
+            used_bytes: size_bytes(&caps[1], unit),
+            total_bytes: size_bytes(&caps[2], unit),
+            lfb_bytes: Some(size_bytes(&caps[4], lfb_unit)),
             unit,
         }
     })
@@ -253,6 +619,7 @@ fn parse_mts(text: &str) -> Option<MtsStat> {
     })
 }
 
+#[cfg(any(test, feature = "regex-parser"))]
 fn parse_val_freq(val: &str) -> EngineStat {
     if let Some((usage_part, freq_part)) = val.split_once('@') {
         let usage = usage_part.trim_end_matches('%').parse::<u32>().ok();
@@ -287,13 +654,39 @@ fn parse_val_freq(val: &str) -> EngineStat {
     }
 }
 
+/// Engine key for a tegrastats token: `_FREQ` dropped, and the DLA spellings
+/// of different L4T releases (`NVDLA0`, `DLA0`, `DLA0_CORE_FREQ`, `NVDLA0_FALCON`)
+/// mapped to `DLA<n>_CORE` / `DLA<n>_FALCON`.
+pub fn normalize_engine_name(token: &str) -> String {
+    let name = token.strip_suffix("_FREQ").unwrap_or(token);
+    let Some(rest) = name.strip_prefix("NV").unwrap_or(name).strip_prefix("DLA") else {
+        return name.to_string();
+    };
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let (unit, part) = rest.split_at(digits);
+    match part {
+        _ if unit.is_empty() => name.to_string(),
+        "" | "_CORE" => format!("DLA{unit}_CORE"),
+        "_FALCON" => format!("DLA{unit}_FALCON"),
+        _ => name.to_string(),
+    }
+}
+
+/// DLA unit of a normalized engine name ("DLA1_FALCON" -> 1).
+pub fn dla_unit(name: &str) -> Option<u32> {
+    let rest = name.strip_prefix("DLA")?;
+    let (unit, part) = rest.split_once('_')?;
+    if !matches!(part, "CORE" | "FALCON") {
+        return None;
+    }
+    unit.parse().ok()
+}
+
+#[cfg(any(test, feature = "regex-parser"))]
 fn parse_engines(text: &str) -> HashMap<String, EngineStat> {
     let mut engines = HashMap::new();
     for caps in BRACKET_FREQ_RE.captures_iter(text) {
-        let mut name = caps[1].to_string();
-        if let Some(stripped) = name.strip_suffix("_FREQ") {
-            name = stripped.to_string();
-        }
+        let name = normalize_engine_name(&caps[1]);
         if matches!(name.as_str(), "RAM" | "SWAP" | "IRAM" | "CPU" | "MTS") {
             continue;
         }
@@ -306,10 +699,7 @@ fn parse_engines(text: &str) -> HashMap<String, EngineStat> {
         });
     }
     for caps in VALS_RE.captures_iter(text) {
-        let mut name = caps[1].to_string();
-        if let Some(stripped) = name.strip_suffix("_FREQ") {
-            name = stripped.to_string();
-        }
+        let name = normalize_engine_name(&caps[1]);
         // Skip fields handled elsewhere
         if matches!(name.as_str(), "RAM" | "SWAP" | "IRAM" | "CPU" | "MTS") {
             continue;
@@ -343,7 +733,7 @@ fn parse_engines(text: &str) -> HashMap<String, EngineStat> {
         }
     }
     for caps in ENGINE_OFF_RE.captures_iter(text) {
-        let name = caps[1].to_string();
+        let name = normalize_engine_name(&caps[1]);
         if matches!(name.as_str(), "RAM" | "SWAP" | "IRAM" | "CPU" | "MTS") {
             continue;
         }
@@ -356,6 +746,7 @@ fn parse_engines(text: &str) -> HashMap<String, EngineStat> {
     engines
 }
 
+#[cfg(any(test, feature = "regex-parser"))]
 fn parse_cpus(text: &str) -> Vec<CpuCore> {
     if let Some(caps) = CPU_RE.captures(text) {
         let content = caps[1].split(',');
@@ -381,6 +772,7 @@ fn parse_cpus(text: &str) -> Vec<CpuCore> {
     }
 }
 
+#[cfg(any(test, feature = "regex-parser"))]
 fn parse_temps(text: &str) -> HashMap<String, f32> {
     TEMP_RE
         .captures_iter(text)
@@ -401,6 +793,7 @@ fn normalize_power(unit: &str, value: f64) -> u32 {
     }
 }
 
+#[cfg(any(test, feature = "regex-parser"))]
 fn parse_power(text: &str) -> HashMap<String, PowerRail> {
     let mut rails = HashMap::new();
     for caps in WATT_RE.captures_iter(text) {
@@ -421,6 +814,213 @@ fn parse_power(text: &str) -> HashMap<String, PowerRail> {
     rails
 }
 
+/// CPU block, engines, temperatures and rails of a line, with the byte ranges
+/// they were read from (`parse_report`).
+#[derive(Debug, Default)]
+struct Fields {
+    cpus: Vec<CpuCore>,
+    engines: HashMap<String, EngineStat>,
+    temps: HashMap<String, f32>,
+    power: HashMap<String, PowerRail>,
+    spans: Vec<Range<usize>>,
+}
+
+#[cfg(any(test, feature = "regex-parser"))]
+fn regex_fields(text: &str) -> Fields {
+    Fields {
+        cpus: parse_cpus(text),
+        engines: parse_engines(text),
+        temps: parse_temps(text),
+        power: parse_power(text),
+        spans: FIELD_RES
+            .iter()
+            .flat_map(|re| re.find_iter(text).map(|m| m.range()))
+            .collect(),
+    }
+}
+
+/// Names of sections read elsewhere, never engines
+const SECTION_NAMES: [&str; 5] = ["RAM", "SWAP", "IRAM", "CPU", "MTS"];
+
+/// One pass over the whitespace tokens: `name@tempC`, `CPU [..]`,
+/// `NAME cur/avg` rails and `NAME value` engines. Unlike the regex
+/// implementation, a bare-number rail (`VDD_IN 4500/4500`) is not also read
+/// as an engine, and a multi-GPC load (`0%@[1300,1300]`) keeps its usage.
+fn scan_fields(text: &str) -> Fields {
+    let tokens: Vec<(usize, &str)> = text
+        .split_whitespace()
+        .map(|token| (token.as_ptr() as usize - text.as_ptr() as usize, token))
+        .collect();
+    let mut fields = Fields::default();
+    // `off` engines and `_UTIL` bases only fill names nothing else reported
+    let mut fallbacks = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let (start, token) = tokens[i];
+        let next = tokens.get(i + 1).map(|(_, t)| *t);
+        let width = if let Some((name, celsius)) = scan_temp(token) {
+            fields.temps.insert(name.to_string(), celsius);
+            1
+        } else if let Some(cpus) = next.filter(|_| token == "CPU").and_then(scan_cpus) {
+            if fields.cpus.is_empty() {
+                fields.cpus = cpus;
+            }
+            2
+        } else if let Some(rail) = next.filter(|_| is_word(token)).and_then(scan_rail) {
+            fields.power.insert(token.to_string(), rail);
+            2
+        } else if let Some((name, stat)) = next.and_then(|value| scan_engine(token, value)) {
+            match stat {
+                Some(stat) => {
+                    if let Some(base) = name.strip_suffix("_UTIL") {
+                        fallbacks.push((
+                            base.to_string(),
+                            EngineStat {
+                                usage_percent: stat.usage_percent,
+                                ..Default::default()
+                            },
+                        ));
+                    }
+                    fields.engines.entry(name).or_insert(stat);
+                }
+                None => fallbacks.push((
+                    name,
+                    EngineStat {
+                        usage_percent: Some(0),
+                        ..Default::default()
+                    },
+                )),
+            }
+            2
+        } else {
+            i += 1;
+            continue;
+        };
+        let (last_start, last) = tokens[i + width - 1];
+        fields.spans.push(start..last_start + last.len());
+        i += width;
+    }
+    for (name, stat) in fallbacks {
+        fields.engines.entry(name).or_insert(stat);
+    }
+    fields
+}
+
+fn is_word(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// `s` split after its leading run of bytes matching `pred`.
+fn split_run(s: &str, pred: impl Fn(u8) -> bool) -> (&str, &str) {
+    s.split_at(s.bytes().position(|b| !pred(b)).unwrap_or(s.len()))
+}
+
+/// `tj@41.468C`, `CV0@-256C`
+fn scan_temp(token: &str) -> Option<(&str, f32)> {
+    let (name, value) = token.strip_suffix('C')?.split_once('@')?;
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    if !is_word(name) || digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+        return None;
+    }
+    Some((name, value.parse().ok()?))
+}
+
+/// `[19%@729,14%,off]`
+fn scan_cpus(block: &str) -> Option<Vec<CpuCore>> {
+    let list = block.strip_prefix('[')?.strip_suffix(']')?;
+    let cores = list
+        .split(',')
+        .map(|core| match core.trim().split_once('@') {
+            Some((load, freq)) => CpuCore {
+                load_percent: load.strip_suffix('%').and_then(|l| l.parse().ok()),
+                freq_mhz: freq.parse().ok(),
+                cluster: None,
+            },
+            None => CpuCore {
+                load_percent: core.trim().strip_suffix('%').and_then(|l| l.parse().ok()),
+                freq_mhz: None,
+                cluster: None,
+            },
+        })
+        .collect();
+    Some(cores)
+}
+
+/// `5704mW/5704mW`, `1.2W/1.1W` or bare mW `14025/14416`
+fn scan_rail(value: &str) -> Option<PowerRail> {
+    let (current, average) = value.split_once('/')?;
+    Some(PowerRail {
+        current_mw: scan_milliwatts(current)?,
+        average_mw: scan_milliwatts(average)?,
+    })
+}
+
+/// A number with an optional unit character and `W` (see `normalize_power`)
+fn scan_milliwatts(s: &str) -> Option<u32> {
+    let (number, rest) = split_run(s, |b| b.is_ascii_digit() || b == b'.');
+    let value = number.parse::<f64>().ok()?;
+    let unit = match rest.chars().next() {
+        Some(c) if c.is_alphanumeric() || c == '_' => &rest[..c.len_utf8()],
+        _ => "",
+    };
+    match &rest[unit.len()..] {
+        "" | "W" => Some(normalize_power(unit, value)),
+        _ => None,
+    }
+}
+
+/// `GR3D_FREQ 59%@1300`, `EMC_FREQ 0%`, `NVDEC 716`, `GR3D_FREQ 0%@[1300,1300]`
+/// (one load, a clock per GPC: the highest is kept). `None` stat for `off`.
+fn scan_engine(name: &str, value: &str) -> Option<(String, Option<EngineStat>)> {
+    if name.is_empty() || !name.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'_') {
+        return None;
+    }
+    let name = normalize_engine_name(name);
+    if SECTION_NAMES.contains(&name.as_str()) {
+        return None;
+    }
+    if value == "off" {
+        return Some((name, None));
+    }
+    let (digits, rest) = split_run(value, |b| b.is_ascii_digit());
+    let number = digits.parse::<u32>().ok()?;
+    let (percent, rest) = match rest.strip_prefix('%') {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+    let stat = if rest.is_empty() && percent {
+        EngineStat {
+            usage_percent: Some(number),
+            freq_mhz: None,
+            raw_value: None,
+        }
+    } else if rest.is_empty() {
+        EngineStat {
+            usage_percent: None,
+            freq_mhz: Some(number),
+            raw_value: Some(number),
+        }
+    } else {
+        let freq = rest.strip_prefix('@')?;
+        let freq_mhz = match freq.strip_prefix('[') {
+            Some(list) => list
+                .strip_suffix(']')?
+                .split(',')
+                .map(|f| f.parse::<u32>().ok())
+                .collect::<Option<Vec<_>>>()?
+                .into_iter()
+                .max()?,
+            None => freq.parse().ok()?,
+        };
+        EngineStat {
+            usage_percent: Some(number),
+            freq_mhz: Some(freq_mhz),
+            raw_value: None,
+        }
+    };
+    Some((name, Some(stat)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -431,6 +1031,9 @@ mod tests {
         let stats = TegraStats::parse(line).unwrap();
 
         assert_eq!(stats.timestamp.as_deref(), Some("01-03-2023 16:10:22"));
+        let local = Local.with_ymd_and_hms(2023, 1, 3, 16, 10, 22).unwrap();
+        assert_eq!(stats.timestamp_unix, Some(local.timestamp()));
+        assert_eq!(stats.time(), Some(local));
         let ram = stats.ram.as_ref().unwrap();
         assert_eq!(ram.total_bytes, SizeUnit::MB.to_bytes(30_536));
         assert_eq!(ram.used_bytes, SizeUnit::MB.to_bytes(2_257));
@@ -440,6 +1043,7 @@ mod tests {
         assert_eq!(stats.cpus[0].freq_mhz, Some(729));
         assert_eq!(stats.cpus[10].load_percent, Some(100));
         assert_eq!(stats.gpu_usage(), Some(75));
+        assert_eq!(stats.engines.get("EMC").and_then(|e| e.usage_percent), Some(0));
         assert!(stats.temps.contains_key("CPU"));
         assert!(stats.temps.contains_key("tj"));
     }
@@ -485,6 +1089,25 @@ mod tests {
         assert_eq!(stats.engines.get("APE").and_then(|e| e.raw_value), Some(200));
     }
 
+    #[test]
+    fn normalizes_dla_engines() {
+        let line = "RAM 3000/30536MB (lfb 4x4MB) CPU [5%@2201,3%@2201] EMC_FREQ 1%@3199 GR3D_FREQ 0%@[1300,1300] NVDLA0 off NVDLA1 1600 DLA0_FALCON off DLA1_FALCON_FREQ 650 VIC off tj@45.1C";
+        let stats = TegraStats::parse(line).unwrap();
+        assert_eq!(stats.engines.get("DLA0_CORE").and_then(|e| e.usage_percent), Some(0));
+        assert_eq!(stats.engines.get("DLA1_CORE").and_then(|e| e.freq_mhz), Some(1600));
+        assert_eq!(stats.engines.get("DLA0_FALCON").and_then(|e| e.usage_percent), Some(0));
+        assert_eq!(stats.engines.get("DLA1_FALCON").and_then(|e| e.freq_mhz), Some(650));
+        assert!(!stats.engines.contains_key("NVDLA0"));
+
+        let core = TegraStats::parse("RAM 1/2MB DLA0_CORE 45%@1395").unwrap();
+        assert_eq!(core.engines.get("DLA0_CORE").and_then(|e| e.usage_percent), Some(45));
+        assert_eq!(normalize_engine_name("DLA_FOO"), "DLA_FOO");
+        assert_eq!(normalize_engine_name("NVENC"), "NVENC");
+        assert_eq!(dla_unit("DLA1_FALCON"), Some(1));
+        assert_eq!(dla_unit("DLA1_OTHER"), None);
+        assert_eq!(dla_unit("GR3D"), None);
+    }
+
     #[test]
     fn parses_extended_engines_from_reference() {
         let line = "RAM 1024/4096MB (lfb 1x1MB) SWAP 0/1024MB (cached 0MB) CPU [10%@1200,20%@1200] EMC_FREQ 25%@1600 MC_FREQ 800 AXI_FREQ 600 GR3D_FREQ 50%@900 NVENC 30%@700 NVDEC 15%@650 NVJPG off NVJPG1 5%@300 VIC 12%@400 OFA 7%@350 ISP 9%@500 NVCSI 3%@250 PCIE 1%@125 NVLINK 2%@400 ISP_UTIL 4% NVCSI_UTIL 6% VDD_IN 5000/5200";
@@ -511,4 +1134,95 @@ mod tests {
         assert_eq!(stats.engines.get("NVCSI_UTIL").and_then(|e| e.usage_percent), Some(6));
         assert_eq!(stats.engines.get("ISP_UTIL").and_then(|e| e.usage_percent), Some(4));
     }
+
+    #[test]
+    fn report_accounts_for_every_token() {
+        let line = "11-30-2025 13:26:01 RAM 2461/7620MB (lfb 3x2MB) SWAP 1243/3810MB (cached 5MB) CPU [19%@729,off] EMC_FREQ 4%@2133 GR3D_FREQ 0%@[305] NVDEC off APE 200 cpu@46.531C VDD_IN 5704mW/5704mW";
+        let report = TegraStats::parse_report(line);
+        assert!(report.is_clean(), "{:?}", report.unknown);
+        assert_eq!(report.consumed.len(), line.split_whitespace().count());
+        assert!(TegraStats::parse_strict(line).is_ok());
+
+        let drifted = "RAM 2461/7620MB (lfb 3x2MB) CPU [19%@729] GR3D_FREQ 0%@[305] NEWENG: 7 OFA n/a tj@40C VDD_IN 5704mW/5704mW";
+        let report = TegraStats::parse_report(drifted);
+        assert_eq!(report.unknown, vec!["NEWENG:", "7", "OFA", "n/a"]);
+        assert_eq!(report.missing, vec![Section::Swap, Section::Emc]);
+        let err = TegraStats::parse_strict(drifted).unwrap_err();
+        assert_eq!(err.to_string(), "unknown tokens: NEWENG: 7 OFA n/a; missing: SWAP, EMC_FREQ");
+    }
+
+    #[test]
+    fn tokenizer_matches_regex_parser() {
+        let lines = [
+            "RAM 2257/30536MB (lfb 5392x4MB) SWAP 0/15268MB (cached 0MB) CPU [10%@729,20%@729,off,40%] EMC_FREQ 0% GR3D_FREQ 75% CV0@-256C CPU@41.375C Tboard@29C tj@41.468C",
+            "RAM 4722/7844MB (lfb 1x512kB) CPU [12%@2035,34%@2034] SWAP 149/1024MB (cached 7MB) EMC_FREQ 2%@1866 GR3D_FREQ 59%@1300 APE 150 MTS fg 3% bg 9% BCPU@-45C VDD_IN 14025/14416 VDD_CPU 2209/2538 NVENC 716 NVDEC 716",
+            "RAM 2461/7620MB (lfb 3x2MB) CPU [19%@729] EMC_FREQ 4%@2133 GR3D_FREQ 0%@[305] NVDEC off VIC off APE 200 cpu@46.531C VDD_IN 5704mW/5704mW VDD_SOC 1.6W/1.5W",
+            "RAM 3000/30536MB (lfb 4x4MB) CPU [5%@2201] EMC_FREQ 1%@3199 NVDLA0 off NVDLA1 1600 DLA1_FALCON_FREQ 650 ISP 9%@500 ISP_UTIL 4% NVCSI_UTIL 6% tj@45.1C",
+            "RAM 2011/3956MB (lfb 4x2MB) SWAP 0/1978MB (cached 0MB) IRAM 0/252kB(lfb 252kB) CPU [15%@1479,8%@1479] EMC_FREQ 3%@1600 GR3D_FREQ 0%@921 PLL@34.5C POM_5V_IN 2338/2338 POM_5V_CPU 443/443",
+        ];
+        fn json(value: impl Serialize) -> serde_json::Value {
+            serde_json::to_value(value).unwrap()
+        }
+        for line in lines {
+            let (scanned, regex) = (scan_fields(line), regex_fields(line));
+            assert_eq!(json(&scanned.cpus), json(&regex.cpus), "{line}");
+            assert_eq!(json(&scanned.temps), json(&regex.temps), "{line}");
+            assert_eq!(json(&scanned.power), json(&regex.power), "{line}");
+            // The regex parser also reads bare-number rails as engines
+            let mut engines = regex.engines;
+            engines.retain(|name, _| !regex.power.contains_key(name));
+            assert_eq!(json(&scanned.engines), json(&engines), "{line}");
+        }
+    }
+
+    #[test]
+    fn tokenizer_keeps_multi_gpc_load() {
+        let stats = scan_fields("GR3D_FREQ 37%@[1300,918] VDD_IN 4500/4500");
+        let gr3d = &stats.engines["GR3D"];
+        assert_eq!(gr3d.usage_percent, Some(37));
+        assert_eq!(gr3d.freq_mhz, Some(1300));
+        assert!(!stats.engines.contains_key("VDD_IN"));
+    }
+
+    #[test]
+    fn delta_reports_changes_and_transitions() {
+        let prev = TegraStats::parse("11-30-2025 13:26:01 CPU [10%@729,20%@729] GR3D_FREQ 10%@305 tj@40C VDD_IN 5000mW/5000mW").unwrap();
+        let cur = TegraStats::parse("11-30-2025 13:26:03 CPU [30%@1190,20%@729] GR3D_FREQ 60%@918 tj@44C VDD_IN 7000mW/5500mW").unwrap();
+        let delta = cur.delta(&prev);
+
+        assert_eq!(delta.elapsed_secs, Some(2.0));
+        assert_eq!(delta.power_mw.get("VDD_IN"), Some(&2000));
+        assert_eq!(delta.temp_c.get("tj"), Some(&4.0));
+        assert_eq!(delta.cpu_load, vec![Some(20), Some(0)]);
+        assert_eq!(delta.gpu_load, Some(50));
+        assert_eq!(
+            delta.freq_transitions,
+            vec![
+                FreqTransition { name: "CPU0".into(), from_mhz: 729, to_mhz: 1190 },
+                FreqTransition { name: "GR3D".into(), from_mhz: 305, to_mhz: 918 },
+            ]
+        );
+    }
+
+    #[test]
+    fn time_is_local_and_exported_as_utc() {
+        let mut stats = TegraStats::parse("11-30-2025 13:26:01 tj@40C").unwrap();
+        let local = Local.with_ymd_and_hms(2025, 11, 30, 13, 26, 1).unwrap();
+        let utc = local.with_timezone(&Utc).format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        assert_eq!(stats.time_rfc3339(), Some(utc));
+        // Replies from daemons that predate `timestamp_unix`
+        stats.timestamp_unix = None;
+        assert_eq!(stats.time(), Some(local));
+        assert!(TegraStats::parse("tj@40C").unwrap().time().is_none());
+    }
+
+    #[test]
+    fn delta_without_timestamps_has_no_elapsed_time() {
+        let prev = TegraStats::parse("tj@40C VDD_IN 5000/5000").unwrap();
+        let cur = TegraStats::parse("tj@41C VDD_IN 5100/5000").unwrap();
+        let delta = cur.delta(&prev);
+        assert!(delta.elapsed_secs.is_none());
+        assert_eq!(delta.power_mw.get("VDD_IN"), Some(&100));
+        assert_eq!(delta.temp_c.get("tj"), Some(&1.0));
+    }
 }
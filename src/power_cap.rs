@@ -0,0 +1,222 @@
+use crate::parser::TegraStats;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Averaging window when JETSONSCOPE_POWER_BUDGET_WINDOW is unset.
+pub const DEFAULT_WINDOW: Duration = Duration::from_secs(10);
+/// Fraction of the cap the input must fall under before clocks are raised again.
+const RELEASE_RATIO: f64 = 0.9;
+/// Consecutive samples under the release threshold needed to step up one level.
+const RELEASE_SAMPLES: u32 = 5;
+
+/// Mean of the samples taken in the last `window` (always at least the newest one).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RollingAverage {
+    window_ms: u64,
+    samples: VecDeque<(u64, u32)>,
+}
+
+impl RollingAverage {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window_ms: window.as_millis() as u64,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Add a sample (mW) taken at `now_ms` and return the average.
+    pub fn push(&mut self, now_ms: u64, mw: u32) -> u32 {
+        self.samples.push_back((now_ms, mw));
+        while self
+            .samples
+            .front()
+            .is_some_and(|(t, _)| now_ms.saturating_sub(*t) >= self.window_ms && self.samples.len() > 1)
+        {
+            self.samples.pop_front();
+        }
+        self.average().unwrap_or(mw)
+    }
+
+    pub fn average(&self) -> Option<u32> {
+        (!self.samples.is_empty()).then(|| {
+            (self.samples.iter().map(|(_, mw)| *mw as u64).sum::<u64>() / self.samples.len() as u64) as u32
+        })
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+}
+
+/// Closed-loop wattage cap: steps clock caps down while the rolling average of
+/// VDD_IN is over the cap and back up once it has stayed comfortably below it.
+///
+/// The window restarts at every step, and the next decision waits until it has
+/// refilled, so each one is based on a full window taken at the current clocks.
+#[derive(Debug, Clone)]
+pub struct PowerCapController {
+    cap_mw: u32,
+    level: usize,
+    max_level: usize,
+    below: u32,
+    average: RollingAverage,
+    avg_mw: Option<u32>,
+    /// No decisions before this time: the window is refilling after a step
+    settle_until_ms: u64,
+}
+
+impl PowerCapController {
+    pub fn new(cap_watts: f32, max_level: usize, window: Duration) -> Self {
+        Self {
+            cap_mw: (cap_watts * 1000.0).round() as u32,
+            level: 0,
+            max_level,
+            below: 0,
+            average: RollingAverage::new(window),
+            avg_mw: None,
+            settle_until_ms: 0,
+        }
+    }
+
+    pub fn cap_watts(&self) -> f32 {
+        self.cap_mw as f32 / 1000.0
+    }
+
+    pub fn set_cap_watts(&mut self, cap_watts: f32) {
+        self.cap_mw = (cap_watts * 1000.0).round() as u32;
+        self.below = 0;
+    }
+
+    #[allow(dead_code)]
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Rolling average the last decision was based on.
+    pub fn average_mw(&self) -> Option<u32> {
+        self.avg_mw
+    }
+
+    /// Feed one input power sample (mW) taken at `now_ms` and return the requested throttle level.
+    pub fn update(&mut self, input_mw: u32, now_ms: u64) -> usize {
+        let avg_mw = self.average.push(now_ms, input_mw);
+        self.avg_mw = Some(avg_mw);
+        if now_ms < self.settle_until_ms {
+            return self.level;
+        }
+        let level = self.level;
+        if avg_mw > self.cap_mw {
+            self.below = 0;
+            self.level = (self.level + 1).min(self.max_level);
+        } else if (avg_mw as f64) < self.cap_mw as f64 * RELEASE_RATIO {
+            self.below += 1;
+            if self.below >= RELEASE_SAMPLES {
+                self.below = 0;
+                self.level = self.level.saturating_sub(1);
+            }
+        } else {
+            self.below = 0;
+        }
+        if self.level != level {
+            self.average.clear();
+            self.settle_until_ms = now_ms + self.average.window_ms;
+        }
+        self.level
+    }
+}
+
+/// Board power budget in mW (JETSONSCOPE_POWER_BUDGET_MW), enforced by `ControlManager::set_power_budget`.
+pub fn budget_from_env() -> Option<u32> {
+    std::env::var("JETSONSCOPE_POWER_BUDGET_MW")
+        .ok()
+        .and_then(|s| s.trim().parse::<u32>().ok())
+        .filter(|mw| *mw > 0)
+}
+
+/// Window the power cap and budget alert average over (JETSONSCOPE_POWER_BUDGET_WINDOW, e.g. "30s").
+pub fn window_from_env() -> Duration {
+    std::env::var("JETSONSCOPE_POWER_BUDGET_WINDOW")
+        .ok()
+        .and_then(|s| crate::stress::parse_duration(&s).ok())
+        .unwrap_or(DEFAULT_WINDOW)
+}
+
+/// Board input power: VDD_IN when reported, otherwise the sum of all rails.
+pub fn input_power_mw(stats: &TegraStats) -> Option<u32> {
+    if stats.power.is_empty() {
+        return None;
+    }
+    Some(
+        stats
+            .power
+            .get("VDD_IN")
+            .map(|r| r.current_mw)
+            .unwrap_or_else(|| stats.power.values().map(|r| r.current_mw).sum()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_down_over_cap_and_recovers_with_hysteresis() {
+        let mut ctl = PowerCapController::new(10.0, 3, Duration::ZERO);
+        assert_eq!(ctl.update(12_000, 0), 1);
+        assert_eq!(ctl.update(11_000, 1000), 2);
+        // Inside the dead band: hold
+        for i in 0..10 {
+            assert_eq!(ctl.update(9_500, 2000 + i * 1000), 2);
+        }
+        // Well below the cap: release one level per RELEASE_SAMPLES samples
+        for i in 0..RELEASE_SAMPLES as u64 - 1 {
+            assert_eq!(ctl.update(6_000, 20_000 + i * 1000), 2);
+        }
+        assert_eq!(ctl.update(6_000, 30_000), 1);
+    }
+
+    #[test]
+    fn averages_over_the_window() {
+        let mut ctl = PowerCapController::new(10.0, 3, Duration::from_secs(10));
+        // A one-sample spike does not trip the cap...
+        assert_eq!(ctl.update(8_000, 0), 0);
+        assert_eq!(ctl.update(8_000, 1000), 0);
+        assert_eq!(ctl.update(13_000, 2000), 0);
+        assert_eq!(ctl.update(8_000, 3000), 0);
+        // ...a sustained excess does
+        assert_eq!(ctl.update(15_000, 4000), 1);
+        assert_eq!(ctl.average_mw(), Some(10_400));
+        // The window restarts at the step, so the earlier samples no longer count
+        assert_eq!(ctl.update(9_000, 5000), 1);
+        assert_eq!(ctl.average_mw(), Some(9_000));
+        let mut avg = RollingAverage::new(Duration::from_secs(2));
+        assert_eq!(avg.push(0, 10_000), 10_000);
+        assert_eq!(avg.push(1000, 20_000), 15_000);
+        assert_eq!(avg.push(2000, 30_000), 25_000);
+    }
+
+    #[test]
+    fn waits_for_the_window_to_refill_after_a_step() {
+        let mut ctl = PowerCapController::new(10.0, 3, Duration::from_secs(10));
+        assert_eq!(ctl.update(15_000, 0), 1);
+        // Over budget right after the step, but the window holds too few samples
+        for secs in 1..10 {
+            assert_eq!(ctl.update(15_000, secs * 1000), 1);
+        }
+        assert_eq!(ctl.update(15_000, 10_000), 2);
+        // Releasing waits for the refill as well
+        for secs in 11..20 {
+            assert_eq!(ctl.update(5_000, secs * 1000), 2);
+        }
+        assert_eq!(ctl.update(5_000, 20_000), 2);
+    }
+
+    #[test]
+    fn level_is_clamped() {
+        let mut ctl = PowerCapController::new(1.0, 2, Duration::ZERO);
+        for i in 0..5 {
+            ctl.update(20_000, i * 1000);
+        }
+        assert_eq!(ctl.level(), 2);
+    }
+}
@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+const SYSFS_POWER_SUPPLY: &str = "/sys/class/power_supply";
+const DEFAULT_LOW_BATTERY_PCT: u8 = 15;
+
+/// A `power_supply` class device (USB-C PD input, fuel gauge, battery).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PowerSupply {
+    pub name: String,
+    /// Kernel type: Battery, USB, Mains, ...
+    pub kind: String,
+    pub online: Option<bool>,
+    /// Charging, Discharging, Full, Not charging, ...
+    pub status: Option<String>,
+    pub voltage_mv: Option<u32>,
+    pub current_ma: Option<i32>,
+    pub power_mw: Option<u32>,
+    /// State of charge in percent
+    pub capacity_percent: Option<u8>,
+}
+
+impl PowerSupply {
+    pub fn is_battery(&self) -> bool {
+        self.kind.eq_ignore_ascii_case("battery")
+    }
+
+    pub fn is_discharging(&self) -> bool {
+        self.status.as_deref() == Some("Discharging")
+    }
+
+    /// Discharge rate in mW; `None` unless the supply is discharging.
+    pub fn discharge_mw(&self) -> Option<u32> {
+        if !self.is_discharging() {
+            return None;
+        }
+        self.power_mw.or_else(|| {
+            let v = self.voltage_mv? as u64;
+            let i = self.current_ma?.unsigned_abs() as u64;
+            Some((v * i / 1000) as u32)
+        })
+    }
+
+    pub fn is_low(&self, threshold_percent: u8) -> bool {
+        self.is_battery()
+            && !matches!(self.status.as_deref(), Some("Charging") | Some("Full"))
+            && self
+                .capacity_percent
+                .is_some_and(|c| c <= threshold_percent)
+    }
+}
+
+/// Low-battery alert threshold (JETSONSCOPE_LOW_BATTERY_PCT, default 15%).
+pub fn low_battery_threshold() -> u8 {
+    std::env::var("JETSONSCOPE_LOW_BATTERY_PCT")
+        .ok()
+        .and_then(|s| s.parse::<u8>().ok())
+        .unwrap_or(DEFAULT_LOW_BATTERY_PCT)
+}
+
+/// Read all supplies from the system sysfs class directory.
+pub fn read_all() -> Vec<PowerSupply> {
+    read_from(Path::new(SYSFS_POWER_SUPPLY))
+}
+
+/// Read all supplies under `base` (one directory per device), sorted by name.
+pub fn read_from(base: &Path) -> Vec<PowerSupply> {
+    let Ok(entries) = fs::read_dir(base) else {
+        return Vec::new();
+    };
+    let mut supplies: Vec<PowerSupply> = entries
+        .flatten()
+        .filter_map(|entry| read_supply(&entry.path()))
+        .collect();
+    supplies.sort_by(|a, b| a.name.cmp(&b.name));
+    supplies
+}
+
+fn read_supply(dir: &Path) -> Option<PowerSupply> {
+    let name = dir.file_name()?.to_string_lossy().to_string();
+    let kind = read_attr(dir, "type")?;
+    Some(PowerSupply {
+        name,
+        kind,
+        online: read_num::<u8>(dir, "online").map(|v| v != 0),
+        status: read_attr(dir, "status"),
+        // sysfs reports micro-units
+        voltage_mv: read_num::<u64>(dir, "voltage_now").map(|uv| (uv / 1000) as u32),
+        current_ma: read_num::<i64>(dir, "current_now").map(|ua| (ua / 1000) as i32),
+        power_mw: read_num::<u64>(dir, "power_now").map(|uw| (uw / 1000) as u32),
+        capacity_percent: read_num::<u8>(dir, "capacity"),
+    })
+}
+
+fn read_attr(dir: &Path, attr: &str) -> Option<String> {
+    fs::read_to_string(dir.join(attr))
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn read_num<T: std::str::FromStr>(dir: &Path, attr: &str) -> Option<T> {
+    read_attr(dir, attr)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_supply(base: &Path, name: &str, attrs: &[(&str, &str)]) {
+        let dir = base.join(name);
+        fs::create_dir_all(&dir).unwrap();
+        for (attr, value) in attrs {
+            fs::write(dir.join(attr), format!("{value}\n")).unwrap();
+        }
+    }
+
+    #[test]
+    fn reads_battery_and_usb_pd() {
+        let base = std::env::temp_dir().join(format!("jscope-psy-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        write_supply(
+            &base,
+            "bq27441-0",
+            &[
+                ("type", "Battery"),
+                ("status", "Discharging"),
+                ("voltage_now", "7400000"),
+                ("current_now", "-1500000"),
+                ("capacity", "12"),
+            ],
+        );
+        write_supply(
+            &base,
+            "ucsi-source-psy",
+            &[("type", "USB"), ("online", "0")],
+        );
+
+        let supplies = read_from(&base);
+        let _ = fs::remove_dir_all(&base);
+
+        assert_eq!(supplies.len(), 2);
+        let bat = &supplies[0];
+        assert!(bat.is_battery());
+        assert_eq!(bat.voltage_mv, Some(7400));
+        assert_eq!(bat.current_ma, Some(-1500));
+        assert_eq!(bat.discharge_mw(), Some(11100));
+        assert!(bat.is_low(15));
+        assert!(!bat.is_low(10));
+        assert_eq!(supplies[1].online, Some(false));
+        assert!(!supplies[1].is_low(15));
+    }
+
+    #[test]
+    fn missing_class_dir_is_empty() {
+        assert!(read_from(Path::new("/nonexistent/power_supply")).is_empty());
+    }
+}
@@ -1,18 +1,533 @@
-use serde::Serialize;
-use sysinfo::{System, Uid};
+use crate::t;
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, Instant};
+use sysinfo::{System, Users};
 
-#[derive(Debug, Clone, Serialize)]
+/// nvmap per-client allocations (debugfs, root only).
+#[allow(dead_code)] // daemon-side
+const NVMAP_CLIENTS: &str = "/sys/kernel/debug/nvmap/iovmm/clients";
+
+/// nvgpu runlist channels with their owner pid (debugfs, root only); the
+/// directory name differs between L4T releases.
+#[allow(dead_code)] // daemon-side
+const NVGPU_SCHED: [&str; 3] = [
+    "/sys/kernel/debug/gpu.0/fifo/sched",
+    "/sys/kernel/debug/17000000.gpu/fifo/sched",
+    "/sys/kernel/debug/17000000.gv11b/fifo/sched",
+];
+/// Weight of the newest sample in the smoothed per-process GPU%.
+const ATTRIBUTION_ALPHA: f32 = 0.3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessInfo {
     pub pid: u32,
     pub name: String,
     pub cpu_usage: f32,
     pub memory_kb: u64,
+    /// Username, or the numeric UID when it has no passwd entry
     pub user: Option<String>,
     pub threads: Option<usize>,
+    /// GPU load (%) from `GpuAttribution`; `None` when it is not available
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpu_usage: Option<f32>,
+    /// Storage reads and writes (`/proc/<pid>/io` read_bytes/write_bytes)
+    /// since the previous scan; 0 on the first scan
+    #[serde(default)]
+    pub read_bytes_per_sec: u64,
+    #[serde(default)]
+    pub write_bytes_per_sec: u64,
+}
+
+impl ProcessInfo {
+    /// Reads plus writes (the I/O sort key).
+    pub fn io_bytes_per_sec(&self) -> u64 {
+        self.read_bytes_per_sec + self.write_bytes_per_sec
+    }
+}
+
+/// Process list order (TUI `s`, `GetProcesses`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessSort {
+    #[default]
+    Cpu,
+    Memory,
+    /// Storage reads + writes per second
+    Io,
+}
+
+impl ProcessSort {
+    /// "cpu", "mem"/"memory" or "io".
+    #[allow(dead_code)] // jscopectl
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "cpu" => Some(ProcessSort::Cpu),
+            "mem" | "memory" => Some(ProcessSort::Memory),
+            "io" => Some(ProcessSort::Io),
+            _ => None,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ProcessSort::Cpu => ProcessSort::Memory,
+            ProcessSort::Memory => ProcessSort::Io,
+            ProcessSort::Io => ProcessSort::Cpu,
+        }
+    }
+}
+
+/// A process holding GPU (nvmap) memory.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GpuProcess {
+    pub pid: u32,
+    pub name: String,
+    pub gpu_memory_kb: u64,
+    /// Estimated share of GR3D load (%), from runlist channels; `None` when not sampled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpu_percent: Option<f32>,
+}
+
+/// Processes with nvmap allocations, largest first; empty without root or off-Jetson.
+#[allow(dead_code)]
+pub fn gpu_processes() -> Vec<GpuProcess> {
+    let Ok(content) = std::fs::read_to_string(NVMAP_CLIENTS) else {
+        return Vec::new();
+    };
+    parse_nvmap_clients(&content)
+        .into_iter()
+        .map(|mut p| {
+            // nvmap truncates names; comm is usually longer
+            if let Ok(comm) = std::fs::read_to_string(format!("/proc/{}/comm", p.pid)) {
+                p.name = comm.trim().to_string();
+            }
+            p
+        })
+        .collect()
+}
+
+/// Parse "CLIENT PROCESS PID SIZE" rows (e.g. "user python3 8126 203160K"), summed per pid.
+#[allow(dead_code)]
+pub fn parse_nvmap_clients(content: &str) -> Vec<GpuProcess> {
+    let mut by_pid: BTreeMap<u32, GpuProcess> = BTreeMap::new();
+    for line in content.lines() {
+        let cols: Vec<&str> = line.split_whitespace().collect();
+        let [_, name, pid, size] = cols.as_slice() else {
+            continue;
+        };
+        let (Ok(pid), Some(Ok(kb))) = (
+            pid.parse::<u32>(),
+            size.strip_suffix('K').map(|s| s.parse::<u64>()),
+        ) else {
+            continue;
+        };
+        by_pid
+            .entry(pid)
+            .or_insert_with(|| GpuProcess {
+                pid,
+                name: name.to_string(),
+                gpu_memory_kb: 0,
+                gpu_percent: None,
+            })
+            .gpu_memory_kb += kb;
+    }
+    let mut procs: Vec<GpuProcess> = by_pid.into_values().collect();
+    procs.sort_by_key(|p| std::cmp::Reverse(p.gpu_memory_kb));
+    procs
+}
+
+/// Channels in the GPU runlist per pid, from nvgpu `fifo/sched`
+/// ("chid tsgid pid timeslice ..." rows; only runlist channels are listed).
+#[allow(dead_code)]
+pub fn parse_nvgpu_sched(content: &str) -> BTreeMap<u32, u32> {
+    let mut channels = BTreeMap::new();
+    for line in content.lines() {
+        let mut cols = line.split_whitespace();
+        let (Some(chid), Some(_tsgid), Some(pid)) = (cols.next(), cols.next(), cols.next()) else {
+            continue;
+        };
+        let (Ok(_), Ok(pid)) = (chid.parse::<u32>(), pid.parse::<u32>()) else {
+            continue; // header
+        };
+        *channels.entry(pid).or_insert(0) += 1;
+    }
+    channels
+}
+
+/// GPU device nodes whose fdinfo may carry engine busy time.
+#[allow(dead_code)] // daemon-side
+const GPU_DEVICE_PREFIXES: [&str; 3] = ["/dev/dri/", "/dev/nvgpu/", "/dev/nvhost-"];
+
+/// Client id and summed `drm-engine-*` busy time (ns) of one fdinfo file.
+///
+/// Several fds may share a client (dup, fork), so callers count each id once.
+#[allow(dead_code)]
+pub fn parse_fdinfo_busy(content: &str) -> Option<(u64, u64)> {
+    let mut client = None;
+    let mut busy = None;
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let number = value.split_whitespace().next().and_then(|v| v.parse::<u64>().ok());
+        if key == "drm-client-id" {
+            client = number;
+        } else if key.starts_with("drm-engine-") && !key.starts_with("drm-engine-capacity") {
+            *busy.get_or_insert(0) += number.unwrap_or(0);
+        }
+    }
+    Some((client.unwrap_or(0), busy?))
+}
+
+/// Engine busy time (ns) per pid from the fdinfo of GPU device fds under `proc_root`.
+#[allow(dead_code)]
+pub fn read_fdinfo_busy(proc_root: &Path) -> BTreeMap<u32, u64> {
+    let mut busy = BTreeMap::new();
+    let Ok(entries) = std::fs::read_dir(proc_root) else {
+        return busy;
+    };
+    for entry in entries.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|n| n.parse::<u32>().ok()) else {
+            continue;
+        };
+        let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        let mut clients = BTreeSet::new();
+        for fd in fds.flatten() {
+            let is_gpu = std::fs::read_link(fd.path()).is_ok_and(|target| {
+                let target = target.to_string_lossy();
+                GPU_DEVICE_PREFIXES.iter().any(|p| target.starts_with(p))
+            });
+            if !is_gpu {
+                continue;
+            }
+            let info = entry.path().join("fdinfo").join(fd.file_name());
+            let Some((client, ns)) = std::fs::read_to_string(info)
+                .ok()
+                .and_then(|c| parse_fdinfo_busy(&c))
+            else {
+                continue;
+            };
+            if clients.insert(client) {
+                *busy.entry(pid).or_insert(0) += ns;
+            }
+        }
+    }
+    busy
+}
+
+/// Splits GR3D load among processes.
+///
+/// With the nvgpu runlist readable, load is split by each process's share of
+/// channels. This is an estimate: a bound channel is counted whether or not it
+/// is submitting work, so idle GPU clients still get a share while others are
+/// busy. Drivers that report engine busy time in fdinfo (`drm-engine-*`) give
+/// measured per-process load instead, used when there is no runlist.
+#[derive(Debug, Default)]
+pub struct GpuAttribution {
+    /// Smoothed GPU% per pid
+    load: BTreeMap<u32, f32>,
+    /// Whether the runlist or fdinfo could be read at all
+    available: bool,
+    /// Busy time per pid at the previous fdinfo sample
+    prev_busy: Option<(Instant, BTreeMap<u32, u64>)>,
+}
+
+#[allow(dead_code)] // daemon-side
+impl GpuAttribution {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read the runlist (or fdinfo) and fold in one sample of GR3D load (%).
+    pub fn sample(&mut self, gr3d_percent: f32) {
+        let content = NVGPU_SCHED
+            .iter()
+            .find_map(|path| std::fs::read_to_string(path).ok());
+        if let Some(content) = content {
+            self.available = true;
+            self.update(&parse_nvgpu_sched(&content), gr3d_percent);
+            return;
+        }
+        let busy = read_fdinfo_busy(Path::new("/proc"));
+        let now = Instant::now();
+        match self.prev_busy.take() {
+            Some((then, prev)) if !busy.is_empty() => {
+                self.available = true;
+                self.update_busy(&prev, &busy, now - then);
+            }
+            _ => {
+                self.available = false;
+                self.load.clear();
+            }
+        }
+        if !busy.is_empty() {
+            self.prev_busy = Some((now, busy));
+        }
+    }
+
+    pub fn update(&mut self, channels: &BTreeMap<u32, u32>, gr3d_percent: f32) {
+        let total: u32 = channels.values().sum();
+        let shares: BTreeMap<u32, f32> = channels
+            .iter()
+            .map(|(pid, count)| (*pid, gr3d_percent * *count as f32 / total as f32))
+            .collect();
+        self.fold(&shares);
+    }
+
+    /// Measured load from fdinfo busy time accumulated over `elapsed`.
+    pub fn update_busy(
+        &mut self,
+        prev: &BTreeMap<u32, u64>,
+        busy: &BTreeMap<u32, u64>,
+        elapsed: Duration,
+    ) {
+        let elapsed_ns = elapsed.as_nanos().max(1) as f32;
+        let shares: BTreeMap<u32, f32> = busy
+            .iter()
+            .map(|(pid, ns)| {
+                let delta = ns.saturating_sub(prev.get(pid).copied().unwrap_or(*ns));
+                (*pid, (delta as f32 * 100.0 / elapsed_ns).min(100.0))
+            })
+            .collect();
+        self.fold(&shares);
+    }
+
+    /// Smooth in one sample; pids missing from it decay towards zero.
+    fn fold(&mut self, shares: &BTreeMap<u32, f32>) {
+        for (pid, load) in self.load.iter_mut() {
+            if !shares.contains_key(pid) {
+                *load *= 1.0 - ATTRIBUTION_ALPHA;
+            }
+        }
+        for (pid, share) in shares {
+            let load = self.load.entry(*pid).or_insert(*share);
+            *load += ATTRIBUTION_ALPHA * (share - *load);
+        }
+        // Forget processes that left once their share has decayed
+        self.load
+            .retain(|pid, load| shares.contains_key(pid) || *load >= 0.05);
+    }
+
+    pub fn percent(&self, pid: u32) -> Option<f32> {
+        if !self.available {
+            return None;
+        }
+        Some(self.load.get(&pid).copied().unwrap_or(0.0))
+    }
+
+    /// Fill `gpu_percent` and add runlist processes without nvmap memory.
+    pub fn annotate(&self, procs: &mut Vec<GpuProcess>) {
+        if !self.available {
+            return;
+        }
+        for (pid, _) in self.load.iter() {
+            if !procs.iter().any(|p| p.pid == *pid) {
+                procs.push(GpuProcess {
+                    pid: *pid,
+                    name: std::fs::read_to_string(format!("/proc/{}/comm", pid))
+                        .map(|c| c.trim().to_string())
+                        .unwrap_or_else(|_| "?".to_string()),
+                    gpu_memory_kb: 0,
+                    gpu_percent: None,
+                });
+            }
+        }
+        for p in procs.iter_mut() {
+            p.gpu_percent = self.percent(p.pid);
+        }
+    }
+
+    /// Fill `gpu_usage` of a CPU process list.
+    pub fn annotate_processes(&self, procs: &mut [ProcessInfo]) {
+        for p in procs.iter_mut() {
+            p.gpu_usage = self.percent(p.pid);
+        }
+    }
+}
+
+/// Signals `SignalProcess` accepts, by name without the "SIG" prefix.
+pub const SIGNALS: [&str; 6] = ["TERM", "KILL", "INT", "HUP", "STOP", "CONT"];
+
+/// "sigterm", "TERM" or "term" -> "TERM"; `None` for signals outside `SIGNALS`.
+pub fn signal_name(signal: &str) -> Option<&'static str> {
+    let upper = signal.trim().to_ascii_uppercase();
+    let name = upper.strip_prefix("SIG").unwrap_or(&upper);
+    SIGNALS.into_iter().find(|s| *s == name)
+}
+
+/// Pids that are never signalled or reniced: the kernel's, init and the caller itself.
+fn check_target(pid: u32) -> Result<()> {
+    if pid <= 1 || pid == std::process::id() {
+        bail!(t!("proc.err.protected_pid", pid = pid));
+    }
+    if !Path::new(&format!("/proc/{pid}")).exists() {
+        bail!(t!("proc.err.no_such_pid", pid = pid));
+    }
+    Ok(())
+}
+
+/// Run a process-control command, turning a failure into its stderr.
+fn run_checked(cmd: &mut Command) -> Result<()> {
+    let output = cmd.output().map_err(|e| anyhow!("{:?}: {e}", cmd.get_program()))?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+/// Memory of one process (`GetProcessDetail`, the TUI's detail popup).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProcessDetail {
+    pub pid: u32,
+    pub name: String,
+    /// Resident set, all mappings
+    pub rss_kb: u64,
+    /// RSS with shared pages split between their users
+    pub pss_kb: u64,
+    /// Resident pages mapped by other processes too (libraries, shm)
+    pub shared_kb: u64,
+    /// Resident pages only this process maps
+    pub private_kb: u64,
+    pub swap_kb: u64,
+    /// nvmap allocations (GPU carveout/IOVMM); `None` without debugfs access
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpu_memory_kb: Option<u64>,
+}
+
+impl ProcessDetail {
+    /// Fill the memory fields from `/proc/<pid>/smaps_rollup` ("Rss:  123 kB" rows).
+    pub fn from_smaps_rollup(pid: u32, name: &str, content: &str) -> Self {
+        let mut detail = ProcessDetail {
+            pid,
+            name: name.to_string(),
+            ..Default::default()
+        };
+        for line in content.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let Some(kb) = value.split_whitespace().next().and_then(|v| v.parse::<u64>().ok()) else {
+                continue;
+            };
+            match key {
+                "Rss" => detail.rss_kb = kb,
+                "Pss" => detail.pss_kb = kb,
+                "Shared_Clean" | "Shared_Dirty" => detail.shared_kb += kb,
+                "Private_Clean" | "Private_Dirty" => detail.private_kb += kb,
+                "Swap" => detail.swap_kb = kb,
+                _ => {}
+            }
+        }
+        detail
+    }
+
+    /// Labelled sizes for display; GPU is `None` without nvmap access.
+    pub fn memory_rows(&self) -> [(String, Option<u64>); 6] {
+        [
+            (t!("proc.mem.rss").to_string(), Some(self.rss_kb)),
+            (t!("proc.mem.pss").to_string(), Some(self.pss_kb)),
+            (t!("proc.mem.shared").to_string(), Some(self.shared_kb)),
+            (t!("proc.mem.private").to_string(), Some(self.private_kb)),
+            (t!("proc.mem.swap").to_string(), Some(self.swap_kb)),
+            (t!("proc.mem.gpu").to_string(), self.gpu_memory_kb),
+        ]
+    }
+}
+
+/// RSS, shared, swap and nvmap memory of `pid`. smaps_rollup needs the
+/// process's owner or root, nvmap needs root (left `None` otherwise).
+pub fn process_detail(pid: u32) -> Result<ProcessDetail> {
+    if !Path::new(&format!("/proc/{pid}")).exists() {
+        bail!(t!("proc.err.no_such_pid", pid = pid));
+    }
+    let name = std::fs::read_to_string(format!("/proc/{pid}/comm")).unwrap_or_default();
+    let smaps = std::fs::read_to_string(format!("/proc/{pid}/smaps_rollup"))
+        .map_err(|e| anyhow!(t!("proc.err.smaps", pid = pid, error = e)))?;
+    let mut detail = ProcessDetail::from_smaps_rollup(pid, name.trim(), &smaps);
+    detail.gpu_memory_kb = std::fs::read_to_string(NVMAP_CLIENTS).ok().map(|clients| {
+        parse_nvmap_clients(&clients)
+            .iter()
+            .find(|p| p.pid == pid)
+            .map_or(0, |p| p.gpu_memory_kb)
+    });
+    Ok(detail)
+}
+
+/// Send `signal` (see `signal_name`) to `pid` with kill(1).
+#[allow(dead_code)] // daemon-side
+pub fn signal_process(pid: u32, signal: &str) -> Result<&'static str> {
+    let name = signal_name(signal).ok_or_else(|| {
+        anyhow!(t!("proc.err.signal", signal = signal, signals = SIGNALS.join(", ")))
+    })?;
+    check_target(pid)?;
+    run_checked(Command::new("kill").args(["-s", name, &pid.to_string()]))?;
+    Ok(name)
+}
+
+/// Set `pid`'s nice value (-20 highest priority to 19 lowest) with renice(1).
+#[allow(dead_code)] // daemon-side
+pub fn renice_process(pid: u32, nice: i32) -> Result<()> {
+    if !(-20..=19).contains(&nice) {
+        bail!(t!("proc.err.nice", nice = nice));
+    }
+    check_target(pid)?;
+    run_checked(Command::new("renice").args(["-n", &nice.to_string(), "-p", &pid.to_string()]))
+}
+
+/// Processes, CPU and memory summed over one user's processes.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UserUsage {
+    pub user: String,
+    pub processes: usize,
+    pub cpu_usage: f32,
+    pub memory_kb: u64,
+    pub read_bytes_per_sec: u64,
+    pub write_bytes_per_sec: u64,
+}
+
+/// Totals per user, sorted like `sort_processes`; processes without a user count as "-".
+pub fn usage_by_user(processes: &[ProcessInfo], sort: ProcessSort) -> Vec<UserUsage> {
+    let mut by_user: BTreeMap<&str, UserUsage> = BTreeMap::new();
+    for p in processes {
+        let user = p.user.as_deref().unwrap_or("-");
+        let usage = by_user.entry(user).or_insert_with(|| UserUsage {
+            user: user.to_string(),
+            processes: 0,
+            cpu_usage: 0.0,
+            memory_kb: 0,
+            read_bytes_per_sec: 0,
+            write_bytes_per_sec: 0,
+        });
+        usage.processes += 1;
+        usage.cpu_usage += p.cpu_usage;
+        usage.memory_kb += p.memory_kb;
+        usage.read_bytes_per_sec += p.read_bytes_per_sec;
+        usage.write_bytes_per_sec += p.write_bytes_per_sec;
+    }
+    let mut users: Vec<UserUsage> = by_user.into_values().collect();
+    match sort {
+        ProcessSort::Cpu => users.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage)),
+        ProcessSort::Memory => users.sort_by_key(|u| std::cmp::Reverse(u.memory_kb)),
+        ProcessSort::Io => users.sort_by_key(|u| {
+            std::cmp::Reverse(u.read_bytes_per_sec + u.write_bytes_per_sec)
+        }),
+    }
+    users
 }
 
 pub struct ProcessMonitor {
     system: System,
+    /// passwd entries, read once; UIDs added later show as numbers
+    users: Users,
+    refreshed: Instant,
+    /// Time between the last two scans (I/O rates); `None` until the second
+    interval: Option<Duration>,
 }
 
 impl Default for ProcessMonitor {
@@ -25,17 +540,32 @@ impl ProcessMonitor {
     pub fn new() -> Self {
         Self {
             system: System::new_all(),
+            users: Users::new_with_refreshed_list(),
+            refreshed: Instant::now(),
+            interval: None,
         }
     }
 
     pub fn refresh(&mut self) {
         self.system.refresh_all(); // full refresh to keep CPU/mem accurate
+        self.interval = Some(self.refreshed.elapsed());
+        self.refreshed = Instant::now();
     }
 
-    pub fn top_processes(&mut self, limit: usize, sort_by_mem: bool) -> Vec<ProcessInfo> {
-        self.refresh();
-        let mut processes: Vec<ProcessInfo> = self
-            .system
+    /// Refresh unless the last scan is younger than `min_age`; back-to-back
+    /// scans give noisy CPU% and I/O rates.
+    #[allow(dead_code)] // daemon-side
+    pub fn refresh_if_older(&mut self, min_age: Duration) {
+        if self.refreshed.elapsed() >= min_age {
+            self.refresh();
+        }
+    }
+
+    /// Every process as of the last `refresh`, unsorted.
+    pub fn processes(&self) -> Vec<ProcessInfo> {
+        let secs = self.interval.map_or(0.0, |d| d.as_secs_f64());
+        let rate = |bytes: u64| if secs > 0.0 { (bytes as f64 / secs) as u64 } else { 0 };
+        self.system
             .processes()
             .iter()
             .map(|(pid, process)| ProcessInfo {
@@ -43,62 +573,223 @@ impl ProcessMonitor {
                 name: process.name().to_string_lossy().to_string(),
                 cpu_usage: process.cpu_usage(),
                 memory_kb: process.memory() / 1024,
-                user: process.user_id().map(|uid: &Uid| uid.to_string()),
+                user: process.user_id().map(|uid| match self.users.get_user_by_id(uid) {
+                    Some(user) => user.name().to_string(),
+                    None => uid.to_string(),
+                }),
                 threads: process.tasks().map(|t| t.len()),
+                gpu_usage: None,
+                read_bytes_per_sec: rate(process.disk_usage().read_bytes),
+                write_bytes_per_sec: rate(process.disk_usage().written_bytes),
             })
-            .collect();
+            .collect()
+    }
 
-        if sort_by_mem {
-            processes.sort_by(|a, b| b.memory_kb.cmp(&a.memory_kb));
-        } else {
-            processes.sort_by(|a, b| b.cpu_usage.partial_cmp(&a.cpu_usage).unwrap());
-        }
-        
+    pub fn top_processes(&mut self, limit: usize, sort: ProcessSort) -> Vec<ProcessInfo> {
+        self.refresh();
+        let mut processes = self.processes();
+        sort_processes(&mut processes, sort);
         processes.truncate(limit);
         processes
     }
 
     #[allow(dead_code)]
     pub fn top_by_cpu(&mut self, limit: usize) -> Vec<ProcessInfo> {
-        self.refresh();
-        let mut processes: Vec<ProcessInfo> = self
-            .system
-            .processes()
-            .iter()
-            .map(|(pid, process)| ProcessInfo {
-                pid: pid.as_u32(),
-                name: process.name().to_string_lossy().to_string(),
-                cpu_usage: process.cpu_usage(),
-                memory_kb: process.memory() / 1024,
-                user: process.user_id().map(|uid: &Uid| uid.to_string()),
-                threads: process.tasks().map(|t| t.len()),
-            })
-            .collect();
-
-        processes.sort_by(|a, b| b.cpu_usage.partial_cmp(&a.cpu_usage).unwrap());
-        processes.truncate(limit);
-        processes
+        self.top_processes(limit, ProcessSort::Cpu)
     }
 
     #[allow(dead_code)]
     pub fn top_by_memory(&mut self, limit: usize) -> Vec<ProcessInfo> {
-        self.refresh();
-        let mut processes: Vec<ProcessInfo> = self
-            .system
-            .processes()
-            .iter()
-            .map(|(pid, process)| ProcessInfo {
-                pid: pid.as_u32(),
-                name: process.name().to_string_lossy().to_string(),
-                cpu_usage: process.cpu_usage(),
-                memory_kb: process.memory() / 1024,
-                user: process.user_id().map(|uid: &Uid| uid.to_string()),
-                threads: process.tasks().map(|t| t.len()),
-            })
-            .collect();
+        self.top_processes(limit, ProcessSort::Memory)
+    }
+}
 
-        processes.sort_by(|a, b| b.memory_kb.cmp(&a.memory_kb));
-        processes.truncate(limit);
-        processes
+/// Highest CPU, memory or I/O first.
+pub fn sort_processes(processes: &mut [ProcessInfo], sort: ProcessSort) {
+    match sort {
+        ProcessSort::Cpu => processes.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage)),
+        ProcessSort::Memory => processes.sort_by_key(|p| std::cmp::Reverse(p.memory_kb)),
+        ProcessSort::Io => processes.sort_by_key(|p| std::cmp::Reverse(p.io_bytes_per_sec())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_signal_names_and_guards_targets() {
+        assert_eq!(signal_name("sigterm"), Some("TERM"));
+        assert_eq!(signal_name(" KILL"), Some("KILL"));
+        assert_eq!(signal_name("SIGSEGV"), None);
+        assert!(signal_process(1, "TERM").is_err());
+        assert!(signal_process(std::process::id(), "TERM").is_err());
+        assert!(signal_process(std::process::id(), "BOGUS").is_err());
+        assert!(renice_process(std::process::id(), 30).is_err());
+        assert!(renice_process(u32::MAX, 10).is_err());
+    }
+
+    #[test]
+    fn sums_usage_per_user() {
+        let proc = |pid, user: Option<&str>, cpu, memory_kb| ProcessInfo {
+            pid,
+            name: format!("p{pid}"),
+            cpu_usage: cpu,
+            memory_kb,
+            user: user.map(str::to_string),
+            threads: None,
+            gpu_usage: None,
+            read_bytes_per_sec: 0,
+            write_bytes_per_sec: 0,
+        };
+        let procs = [
+            proc(10, Some("root"), 1.0, 500),
+            proc(11, Some("nvidia"), 30.0, 2_000),
+            proc(12, Some("nvidia"), 5.5, 1_000),
+            proc(13, None, 50.0, 10),
+        ];
+        let by_mem = usage_by_user(&procs, ProcessSort::Memory);
+        assert_eq!(by_mem[0].user, "nvidia");
+        assert_eq!(by_mem[0].processes, 2);
+        assert_eq!(by_mem[0].memory_kb, 3_000);
+        assert_eq!(by_mem[0].cpu_usage, 35.5);
+        let by_cpu = usage_by_user(&procs, ProcessSort::Cpu);
+        assert_eq!(by_cpu.iter().map(|u| u.user.as_str()).collect::<Vec<_>>(), ["-", "nvidia", "root"]);
+    }
+
+    #[test]
+    fn sorts_by_io_rate() {
+        let proc = |pid, read, write| ProcessInfo {
+            pid,
+            name: format!("p{pid}"),
+            cpu_usage: 0.0,
+            memory_kb: 0,
+            user: None,
+            threads: None,
+            gpu_usage: None,
+            read_bytes_per_sec: read,
+            write_bytes_per_sec: write,
+        };
+        let mut procs = [proc(1, 0, 0), proc(2, 4096, 0), proc(3, 1024, 65_536)];
+        sort_processes(&mut procs, ProcessSort::Io);
+        assert_eq!(procs.map(|p| p.pid), [3, 2, 1]);
+        assert_eq!(ProcessSort::parse("MEM"), Some(ProcessSort::Memory));
+        assert_eq!(ProcessSort::parse("io"), Some(ProcessSort::Io));
+        assert_eq!(ProcessSort::parse("disk"), None);
+        assert_eq!(ProcessSort::Io.next(), ProcessSort::Cpu);
+    }
+
+    #[test]
+    fn parses_smaps_rollup() {
+        let rollup = "\
+55d0c6a2e000-7ffc8a1f9000 ---p 00000000 00:00 0                          [rollup]
+Rss:              204800 kB
+Pss:              150000 kB
+Pss_Anon:         120000 kB
+Shared_Clean:      50000 kB
+Shared_Dirty:       4800 kB
+Private_Clean:     30000 kB
+Private_Dirty:    120000 kB
+Referenced:       200000 kB
+Swap:               2048 kB
+SwapPss:            2048 kB
+";
+        let detail = ProcessDetail::from_smaps_rollup(8126, "python3", rollup);
+        assert_eq!(detail.rss_kb, 204_800);
+        assert_eq!(detail.pss_kb, 150_000);
+        assert_eq!(detail.shared_kb, 54_800);
+        assert_eq!(detail.private_kb, 150_000);
+        assert_eq!(detail.swap_kb, 2_048);
+        assert_eq!(detail.gpu_memory_kb, None);
+        assert!(process_detail(u32::MAX).is_err());
+    }
+
+    #[test]
+    fn parses_nvmap_clients() {
+        let content = "CLIENT                        PROCESS      PID        SIZE
+user                         python3     8126    203160K
+user                   nvargus-daemo     5734     25004K
+user                         python3     8126      1000K
+total                                             229164K
+";
+        let procs = parse_nvmap_clients(content);
+        assert_eq!(procs.len(), 2);
+        assert_eq!(procs[0].pid, 8126);
+        assert_eq!(procs[0].gpu_memory_kb, 204160);
+        assert_eq!(procs[1].name, "nvargus-daemo");
+    }
+
+    #[test]
+    fn splits_gpu_load_by_runlist_channels() {
+        let sched = "chid tsgid pid timeslice timeout interleave graphics_preempt compute_preempt
+  511     0  8126      1024     3000     low          WFI           WFI
+  510     0  8126      1024     3000     low          WFI           WFI
+  509     1  5734      1024     3000     low          WFI           WFI
+";
+        let channels = parse_nvgpu_sched(sched);
+        assert_eq!(channels.len(), 2);
+        assert_eq!(channels[&8126], 2);
+
+        let mut attribution = GpuAttribution {
+            available: true,
+            ..Default::default()
+        };
+        attribution.update(&channels, 90.0);
+        assert_eq!(attribution.percent(8126), Some(60.0));
+        assert_eq!(attribution.percent(5734), Some(30.0));
+
+        // 5734 left: its share decays and is eventually dropped
+        let only = BTreeMap::from([(8126, 1)]);
+        attribution.update(&only, 90.0);
+        assert!((attribution.percent(8126).unwrap() - 69.0).abs() < 0.01);
+        assert!((attribution.percent(5734).unwrap() - 21.0).abs() < 0.01);
+        for _ in 0..30 {
+            attribution.update(&only, 90.0);
+        }
+        assert_eq!(attribution.percent(5734), Some(0.0));
+        assert!(!attribution.load.contains_key(&5734));
+
+        let mut procs = vec![GpuProcess {
+            pid: 8126,
+            name: "python3".into(),
+            gpu_memory_kb: 1024,
+            gpu_percent: None,
+        }];
+        attribution.annotate(&mut procs);
+        assert!(procs[0].gpu_percent.unwrap() > 89.0);
+        assert_eq!(GpuAttribution::new().percent(8126), None);
+    }
+
+    #[test]
+    fn measures_gpu_load_from_fdinfo() {
+        let fdinfo = "pos:\t0\nflags:\t02100002\ndrm-driver:\tnvgpu\ndrm-client-id:\t7\ndrm-engine-gr:\t1500000 ns\ndrm-engine-ce:\t500000 ns\ndrm-engine-capacity-gr:\t2\n";
+        assert_eq!(parse_fdinfo_busy(fdinfo), Some((7, 2_000_000)));
+        assert_eq!(parse_fdinfo_busy("pos:\t0\nflags:\t0100002\n"), None);
+
+        let mut attribution = GpuAttribution {
+            available: true,
+            ..Default::default()
+        };
+        let prev = BTreeMap::from([(100, 1_000_000_000), (200, 0)]);
+        let busy = BTreeMap::from([(100, 1_600_000_000), (200, 100_000_000), (300, 5)]);
+        attribution.update_busy(&prev, &busy, Duration::from_secs(1));
+        assert!((attribution.percent(100).unwrap() - 60.0).abs() < 0.01);
+        assert!((attribution.percent(200).unwrap() - 10.0).abs() < 0.01);
+        // New clients start from zero rather than their lifetime busy time
+        assert_eq!(attribution.percent(300), Some(0.0));
+
+        let mut procs = vec![ProcessInfo {
+            pid: 100,
+            name: "python3".into(),
+            cpu_usage: 10.0,
+            memory_kb: 1024,
+            user: None,
+            threads: None,
+            gpu_usage: None,
+            read_bytes_per_sec: 0,
+            write_bytes_per_sec: 0,
+        }];
+        attribution.annotate_processes(&mut procs);
+        assert!((procs[0].gpu_usage.unwrap() - 60.0).abs() < 0.01);
     }
 }
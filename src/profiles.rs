@@ -0,0 +1,226 @@
+//! Named control profiles kept by the daemon (ListProfiles, ApplyProfile,
+//! SaveProfile; `p` in the TUI).
+//!
+//! A profile is a list of control values applied in order, so `nvpmodel`
+//! should come before the governors and clocks it resets. Profiles are kept in
+//! JETSONSCOPE_PROFILES (default `/var/lib/jetsonscope/profiles.json`); until
+//! the first SaveProfile writes it, the built-in `performance` and `balanced`
+//! profiles are served.
+
+use crate::t;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_PATH: &str = "/var/lib/jetsonscope/profiles.json";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    /// (control, value) in the order they are applied
+    pub controls: Vec<(String, String)>,
+}
+
+impl Profile {
+    /// "ctrl=value,...", the format of JETSONSCOPE_IDLE_PROFILE and `jscopectl profile save`.
+    pub fn describe(&self) -> String {
+        self.controls
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// Profiles the daemon serves (for ListProfiles).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfileList {
+    pub profiles: Vec<Profile>,
+    /// Last profile applied since the daemon started
+    pub active: Option<String>,
+}
+
+/// What `jetson_scope_config preset` used to apply.
+pub fn builtin() -> Vec<Profile> {
+    let profile = |name: &str, controls: &[(&str, &str)]| Profile {
+        name: name.to_string(),
+        controls: controls
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+    };
+    vec![
+        profile(
+            "performance",
+            &[
+                ("jetson_clocks", "on"),
+                ("cpu_governor", "performance"),
+                ("gpu_governor", "performance"),
+            ],
+        ),
+        profile(
+            "balanced",
+            &[
+                ("jetson_clocks", "off"),
+                ("cpu_governor", "ondemand"),
+                ("gpu_governor", "nvhost_podgov"),
+            ],
+        ),
+    ]
+}
+
+#[derive(Debug, Clone)]
+pub struct ProfileStore {
+    path: Option<PathBuf>,
+    list: ProfileList,
+}
+
+impl ProfileStore {
+    /// Profiles from JETSONSCOPE_PROFILES or `DEFAULT_PATH`; the built-in ones if unreadable.
+    pub fn from_env() -> Self {
+        let path = std::env::var("JETSONSCOPE_PROFILES")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from(DEFAULT_PATH));
+        Self::load(&path).unwrap_or_else(|e| {
+            eprintln!("Profiles not loaded ({e:#}), serving the built-in ones");
+            ProfileStore {
+                path: Some(path),
+                list: ProfileList {
+                    profiles: builtin(),
+                    active: None,
+                },
+            }
+        })
+    }
+
+    /// Load saved profiles; a missing file gives the built-in ones.
+    pub fn load(path: &Path) -> Result<Self> {
+        let profiles = if path.exists() {
+            let data = fs::read_to_string(path).with_context(|| format!("reading {:?}", path))?;
+            serde_json::from_str(&data).with_context(|| format!("parsing {:?}", path))?
+        } else {
+            builtin()
+        };
+        Ok(ProfileStore {
+            path: Some(path.to_path_buf()),
+            list: ProfileList {
+                profiles,
+                active: None,
+            },
+        })
+    }
+
+    /// The built-in profiles, never written anywhere (tests, no state directory).
+    pub fn in_memory() -> Self {
+        ProfileStore {
+            path: None,
+            list: ProfileList {
+                profiles: builtin(),
+                active: None,
+            },
+        }
+    }
+
+    pub fn list(&self) -> &ProfileList {
+        &self.list
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        self.list.profiles.iter().find(|p| p.name == name)
+    }
+
+    pub fn set_active(&mut self, name: &str) {
+        self.list.active = Some(name.to_string());
+    }
+
+    /// Add or replace a profile and write the file.
+    pub fn save(&mut self, profile: Profile) -> Result<()> {
+        validate(&profile)?;
+        let mut profiles = self.list.profiles.clone();
+        match profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => profiles.push(profile),
+        }
+        if let Some(path) = &self.path {
+            write(path, &profiles)?;
+        }
+        self.list.profiles = profiles;
+        Ok(())
+    }
+}
+
+/// Names are `[A-Za-z0-9_-]`, up to 64 characters, with at least one control and no
+/// empty entry; jscoped checks each control against its `ControlManager` before saving.
+fn validate(profile: &Profile) -> Result<()> {
+    let name_ok = !profile.name.is_empty()
+        && profile.name.len() <= 64
+        && profile
+            .name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+    if !name_ok {
+        return Err(anyhow!(t!("profile.err.name", name = profile.name)));
+    }
+    if profile.controls.is_empty() {
+        return Err(anyhow!(t!("profile.err.empty")));
+    }
+    if let Some((name, _)) = profile
+        .controls
+        .iter()
+        .find(|(name, value)| name.is_empty() || value.is_empty())
+    {
+        return Err(anyhow!(t!("profile.err.control", control = name)));
+    }
+    Ok(())
+}
+
+fn write(path: &Path, profiles: &[Profile]) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).ok();
+    }
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, serde_json::to_string_pretty(profiles)?)
+        .with_context(|| format!("writing {:?}", tmp))?;
+    fs::rename(&tmp, path).with_context(|| format!("renaming {:?}", tmp))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saves_and_reloads_profiles() {
+        let path = std::env::temp_dir().join(format!("jetsonscope-profiles-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut store = ProfileStore::load(&path).unwrap();
+        assert_eq!(store.list().profiles, builtin());
+
+        let inference = Profile {
+            name: "inference".to_string(),
+            controls: vec![
+                ("nvpmodel".to_string(), "MAXN".to_string()),
+                ("fan".to_string(), "80".to_string()),
+            ],
+        };
+        store.save(inference.clone()).unwrap();
+        assert_eq!(inference.describe(), "nvpmodel=MAXN,fan=80");
+        let bad_name = Profile {
+            name: "demo day".to_string(),
+            ..inference.clone()
+        };
+        assert!(store.save(bad_name).is_err());
+        let bad_control = Profile {
+            name: "demo-day".to_string(),
+            controls: vec![("fan".to_string(), String::new())],
+        };
+        assert!(store.save(bad_control).is_err());
+
+        let reloaded = ProfileStore::load(&path).unwrap();
+        assert_eq!(reloaded.list().profiles.len(), 3);
+        assert_eq!(reloaded.get("inference"), Some(&inference));
+        assert_eq!(reloaded.list().active, None);
+        let _ = fs::remove_file(&path);
+    }
+}
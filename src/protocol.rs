@@ -1,7 +1,28 @@
+use crate::alerts::AlertStatus;
+use crate::auth::Scope;
+use crate::burst::BurstCapture;
+use crate::energy::EnergyReport;
 use crate::hardware::JetsonHardware;
 use crate::health::DaemonHealth;
+use crate::history::{HistoryPoint, StatsSample};
 use crate::parser::TegraStats;
+use crate::processes::{GpuProcess, ProcessDetail, ProcessInfo, ProcessSort};
+use crate::profiles::ProfileList;
+use crate::residency::ResidencyReport;
+use crate::session::SessionSummary;
+use crate::stats::Summary;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::time::Duration;
+
+/// Version of the request/response protocol; bumped whenever requests or
+/// responses are added or change shape. Framed replies carry it (`Reply`).
+#[allow(dead_code)] // daemon-side
+pub const PROTOCOL_VERSION: u32 = 3;
 
 /// Request types for client-daemon communication.
 /// Supports both JSON and CBOR serialization (auto-detected by daemon).
@@ -19,12 +40,161 @@ pub enum Request {
     /// Set a control value
     /// - `control`: control name (e.g., "fan", "nvpmodel", "jetson_clocks")
     /// - `value`: new value (e.g., "80", "MAXN", "on")
-    /// - `token`: optional auth token (set via JETSONSCOPE_AUTH_TOKEN / TEGRA_AUTH_TOKEN env var)
+    /// - `token`: optional auth token; needs admin scope when tokens are configured
+    ///   (JETSONSCOPE_AUTH_TOKEN / TEGRA_AUTH_TOKEN or an admin entry in JETSONSCOPE_AUTH_TOKENS)
+    /// - `dry_run`: validate and reply with `ControlPlan` instead of changing anything
     SetControl {
         control: String,
         value: String,
         token: Option<String>,
+        #[serde(default)]
+        dry_run: bool,
+    },
+    /// Named control profiles and the one last applied
+    ListProfiles,
+    /// Apply a profile's controls in order; needs admin scope like SetControl.
+    /// Every value is checked before the first one is set; controls this
+    /// board does not support are skipped. `dry_run` replies with
+    /// `ProfilePlan` instead.
+    ApplyProfile {
+        name: String,
+        token: Option<String>,
+        #[serde(default)]
+        dry_run: bool,
+    },
+    /// Add or replace a profile ((control, value) pairs, applied in order) and
+    /// persist it; needs admin scope
+    SaveProfile {
+        name: String,
+        controls: Vec<(String, String)>,
+        token: Option<String>,
+    },
+    /// Start a profiling session; samples from now on are aggregated until StopSession
+    StartSession { label: Option<String> },
+    /// Stop a session and return its summary
+    StopSession { id: u64 },
+    /// Time spent at each CPU/GPU frequency step and GPU rail-gated, since boot
+    GetResidency,
+    /// Processes holding GPU memory (needs the daemon to run as root)
+    GetGpuProcesses,
+    /// Top `limit` processes (default 20) by CPU, memory or I/O
+    GetProcesses {
+        #[serde(default)]
+        sort: ProcessSort,
+        #[serde(default)]
+        limit: Option<usize>,
+    },
+    /// RSS, shared, swap and nvmap memory of one process
+    GetProcessDetail { pid: u32 },
+    /// History for the last `since_secs`, at most `max_points` (0: all): trend points
+    /// averaged per time slot, or with `full` the newest full sample of each slot
+    GetHistory {
+        #[serde(alias = "window_secs")]
+        since_secs: u64,
+        max_points: usize,
+        #[serde(default)]
+        full: bool,
+    },
+    /// Min/max/mean/p95 of CPU, GPU, RAM, temperatures and rails over the last `window_secs` (at most 24 h)
+    GetSummary { window_secs: u64 },
+    /// Samples between two times (ms since the Unix epoch) from the SQLite history, thinned to at most `max_points` (0: all)
+    QueryHistory {
+        from_unix_ms: u64,
+        to_unix_ms: u64,
+        max_points: usize,
+    },
+    /// Sample every `interval_ms` (100-250) for `duration_secs` (1-60) into a buffered capture
+    StartBurst { interval_ms: u64, duration_secs: u64 },
+    /// Download a burst capture (partial while it is still running)
+    GetBurst { id: u64 },
+    /// Every alert rule with its state
+    GetAlerts,
+    /// Rail energy integrated since the daemon started, and in total
+    GetEnergy,
+    /// Send a signal ("TERM", "KILL", "INT", "HUP", "STOP", "CONT") to a
+    /// process; needs admin scope like SetControl
+    SignalProcess {
+        pid: u32,
+        signal: String,
+        token: Option<String>,
     },
+    /// Set a process's nice value (-20 to 19); needs admin scope like SetControl
+    ReniceProcess {
+        pid: u32,
+        nice: i32,
+        token: Option<String>,
+    },
+    /// Present a token for the rest of the connection: required before other
+    /// requests over TCP once read-scoped tokens are configured, and used by
+    /// SetControl requests that carry none
+    Authenticate { token: String },
+    /// Daemon version, protocol version and compiled-in features; needs no
+    /// token, so clients can check before relying on newer requests
+    GetVersion,
+}
+
+impl Request {
+    /// Every variant name, as `name` returns them (JSON-RPC method lookup).
+    #[allow(dead_code)] // daemon-side
+    pub const NAMES: [&'static str; 25] = [
+        "GetStats",
+        "GetMeta",
+        "ListControls",
+        "GetHealth",
+        "SetControl",
+        "ListProfiles",
+        "ApplyProfile",
+        "SaveProfile",
+        "StartSession",
+        "StopSession",
+        "GetResidency",
+        "GetGpuProcesses",
+        "GetProcesses",
+        "GetProcessDetail",
+        "GetHistory",
+        "GetSummary",
+        "QueryHistory",
+        "StartBurst",
+        "GetBurst",
+        "GetAlerts",
+        "GetEnergy",
+        "SignalProcess",
+        "ReniceProcess",
+        "Authenticate",
+        "GetVersion",
+    ];
+
+    /// Variant name, e.g. "GetStats" (metric labels, logs).
+    #[allow(dead_code)] // daemon-side
+    pub fn name(&self) -> &'static str {
+        match self {
+            Request::GetStats => "GetStats",
+            Request::GetMeta => "GetMeta",
+            Request::ListControls => "ListControls",
+            Request::GetHealth => "GetHealth",
+            Request::SetControl { .. } => "SetControl",
+            Request::ListProfiles => "ListProfiles",
+            Request::ApplyProfile { .. } => "ApplyProfile",
+            Request::SaveProfile { .. } => "SaveProfile",
+            Request::StartSession { .. } => "StartSession",
+            Request::StopSession { .. } => "StopSession",
+            Request::GetResidency => "GetResidency",
+            Request::GetGpuProcesses => "GetGpuProcesses",
+            Request::GetProcesses { .. } => "GetProcesses",
+            Request::GetProcessDetail { .. } => "GetProcessDetail",
+            Request::GetHistory { .. } => "GetHistory",
+            Request::GetSummary { .. } => "GetSummary",
+            Request::QueryHistory { .. } => "QueryHistory",
+            Request::StartBurst { .. } => "StartBurst",
+            Request::GetBurst { .. } => "GetBurst",
+            Request::GetAlerts => "GetAlerts",
+            Request::GetEnergy => "GetEnergy",
+            Request::SignalProcess { .. } => "SignalProcess",
+            Request::ReniceProcess { .. } => "ReniceProcess",
+            Request::Authenticate { .. } => "Authenticate",
+            Request::GetVersion => "GetVersion",
+        }
+    }
 }
 
 /// Response types from daemon to client.
@@ -45,10 +215,120 @@ pub enum Response {
     Health(DaemonHealth),
     /// Control state after successful SetControl
     ControlState(ControlInfo),
+    /// Commands and sysfs writes a SetControl would run (for `dry_run`)
+    ControlPlan {
+        control: String,
+        value: String,
+        actions: Vec<String>,
+    },
+    /// Named profiles (for ListProfiles)
+    Profiles(ProfileList),
+    /// Control states after ApplyProfile, and the controls skipped as unsupported
+    ProfileApplied {
+        name: String,
+        controls: Vec<ControlInfo>,
+        skipped: Vec<String>,
+    },
+    /// Commands and sysfs writes an ApplyProfile would run (for `dry_run`)
+    ProfilePlan {
+        name: String,
+        actions: Vec<String>,
+        skipped: Vec<String>,
+    },
+    /// Name of the profile stored (for SaveProfile)
+    ProfileSaved { name: String },
+    /// Session id (for StartSession)
+    SessionStarted { id: u64 },
+    /// Session report (for StopSession)
+    SessionSummary(SessionSummary),
+    /// Frequency and GPU runtime-PM residency (for GetResidency)
+    Residency(ResidencyReport),
+    /// GPU memory per process, largest first (for GetGpuProcesses)
+    GpuProcesses(Vec<GpuProcess>),
+    /// Processes in the requested order (for GetProcesses)
+    Processes(Vec<ProcessInfo>),
+    /// Memory breakdown of one process (for GetProcessDetail)
+    ProcessDetail(ProcessDetail),
+    /// Trend points, oldest first (for GetHistory)
+    History(Vec<HistoryPoint>),
+    /// Rolling statistics (for GetSummary)
+    Summary(Summary),
+    /// Full samples, oldest first (for GetHistory with `full` and QueryHistory)
+    StatsHistory(Vec<StatsSample>),
+    /// Capture id (for StartBurst)
+    BurstStarted { id: u64 },
+    /// Burst capture (for GetBurst)
+    Burst(BurstCapture),
+    /// Alert rules in config order (for GetAlerts)
+    Alerts(Vec<AlertStatus>),
+    /// Energy per rail (for GetEnergy)
+    Energy(EnergyReport),
+    /// What was done to the process, e.g. "SIGTERM" or "nice 10" (for
+    /// SignalProcess and ReniceProcess)
+    ProcessUpdated { pid: u32, action: String },
+    /// Name and scope of the accepted token (for Authenticate)
+    Authenticated { name: String, scope: Scope },
+    /// Daemon build and protocol (for GetVersion)
+    Version(VersionInfo),
     /// Error response with structured error info
     Error(ErrorInfo),
 }
 
+/// A response as the daemon frames it: the `Response` variant with the
+/// protocol version next to it, e.g. `{"protocol_version":1,"Meta":{...}}`.
+/// Unframed one-shot replies are a bare `Response`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Reply {
+    /// 0 from daemons that predate versioning
+    #[serde(default)]
+    pub protocol_version: u32,
+    #[serde(flatten)]
+    pub response: Response,
+}
+
+impl Reply {
+    #[allow(dead_code)] // daemon-side
+    pub fn new(response: Response) -> Self {
+        Reply {
+            protocol_version: PROTOCOL_VERSION,
+            response,
+        }
+    }
+}
+
+/// Daemon build information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionInfo {
+    /// Crate version, e.g. "0.1.0"
+    pub crate_version: String,
+    pub protocol_version: u32,
+    /// Optional cargo features compiled in (e.g. "mqtt", "sqlite")
+    pub features: Vec<String>,
+}
+
+impl VersionInfo {
+    /// This build's version.
+    #[allow(dead_code)] // daemon-side
+    pub fn current() -> Self {
+        let features = [
+            ("daemon", cfg!(feature = "daemon")),
+            ("mqtt", cfg!(feature = "mqtt")),
+            ("sqlite", cfg!(feature = "sqlite")),
+            ("emulator", cfg!(feature = "emulator")),
+            ("async", cfg!(feature = "async")),
+        ];
+        VersionInfo {
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            protocol_version: PROTOCOL_VERSION,
+            features: features
+                .into_iter()
+                .filter(|(_, on)| *on)
+                .map(|(name, _)| name.to_string())
+                .collect(),
+        }
+    }
+}
+
 /// Detailed control information including capabilities and current state
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ControlInfo {
@@ -74,6 +354,12 @@ pub struct ControlInfo {
     pub supported: bool,
     /// Unit of measurement (e.g., "%", "MHz")
     pub unit: Option<String>,
+    /// What each option implies, keyed by option (e.g., nvpmodel mode caps)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub option_details: BTreeMap<String, String>,
+    /// Accepted frequencies in Hz, ascending (frequency controls; `options` has them in MHz)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub frequencies_hz: Vec<u64>,
 }
 
 /// Structured error information
@@ -85,3 +371,330 @@ pub struct ErrorInfo {
     /// Human-readable error message
     pub message: String,
 }
+
+impl std::fmt::Display for ErrorInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Error [{}]: {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for ErrorInfo {}
+
+/// Largest payload of a length-prefixed frame.
+///
+/// Frames start with a 4-byte big-endian length, so with this limit their
+/// first byte is always 0; an unframed JSON or CBOR request never starts with
+/// 0, which is how the daemon tells the two apart on a new connection.
+pub const MAX_FRAME_LEN: usize = 0x00FF_FFFF;
+
+/// Write `payload` as one frame.
+pub fn write_frame(w: &mut impl Write, payload: &[u8]) -> io::Result<()> {
+    if payload.len() > MAX_FRAME_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("frame of {} bytes exceeds {}", payload.len(), MAX_FRAME_LEN),
+        ));
+    }
+    w.write_all(&(payload.len() as u32).to_be_bytes())?;
+    w.write_all(payload)?;
+    w.flush()
+}
+
+/// Read one frame; `None` when the peer closed the connection between frames.
+pub fn read_frame(r: &mut impl Read) -> io::Result<Option<Vec<u8>>> {
+    let mut header = [0u8; 4];
+    let mut filled = 0;
+    while filled < header.len() {
+        match r.read(&mut header[filled..]) {
+            Ok(0) if filled == 0 => return Ok(None),
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    let len = u32::from_be_bytes(header) as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame of {} bytes exceeds {}", len, MAX_FRAME_LEN),
+        ));
+    }
+    let mut payload = vec![0u8; len];
+    r.read_exact(&mut payload)?;
+    Ok(Some(payload))
+}
+
+/// `write_frame` on a tokio stream.
+#[cfg(feature = "async")]
+#[allow(dead_code)] // AsyncClient, jscoped
+pub async fn write_frame_async(
+    w: &mut (impl tokio::io::AsyncWrite + Unpin),
+    payload: &[u8],
+) -> io::Result<()> {
+    use tokio::io::AsyncWriteExt;
+    if payload.len() > MAX_FRAME_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("frame of {} bytes exceeds {}", payload.len(), MAX_FRAME_LEN),
+        ));
+    }
+    w.write_all(&(payload.len() as u32).to_be_bytes()).await?;
+    w.write_all(payload).await?;
+    w.flush().await
+}
+
+/// `read_frame` on a tokio stream.
+#[cfg(feature = "async")]
+#[allow(dead_code)] // AsyncClient, jscoped
+pub async fn read_frame_async(r: &mut (impl tokio::io::AsyncRead + Unpin)) -> io::Result<Option<Vec<u8>>> {
+    use tokio::io::AsyncReadExt;
+    let mut header = [0u8; 4];
+    let mut filled = 0;
+    while filled < header.len() {
+        match r.read(&mut header[filled..]).await? {
+            0 if filled == 0 => return Ok(None),
+            0 => return Err(io::ErrorKind::UnexpectedEof.into()),
+            n => filled += n,
+        }
+    }
+    let len = u32::from_be_bytes(header) as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame of {} bytes exceeds {}", len, MAX_FRAME_LEN),
+        ));
+    }
+    let mut payload = vec![0u8; len];
+    r.read_exact(&mut payload).await?;
+    Ok(Some(payload))
+}
+
+/// The daemon's Unix socket, or its TCP listener (JETSONSCOPE_TCP_ADDR).
+enum Transport {
+    Unix(UnixStream),
+    #[allow(dead_code)] // jscopectl --host
+    Tcp(TcpStream),
+}
+
+impl Read for Transport {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Transport::Unix(s) => s.read(buf),
+            Transport::Tcp(s) => s.read(buf),
+        }
+    }
+}
+
+impl Write for Transport {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Transport::Unix(s) => s.write(buf),
+            Transport::Tcp(s) => s.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Transport::Unix(s) => s.flush(),
+            Transport::Tcp(s) => s.flush(),
+        }
+    }
+}
+
+/// Port of `Connection::connect_tcp` when the address has none.
+#[allow(dead_code)] // jscopectl --host
+pub const DEFAULT_TCP_PORT: u16 = 7070;
+
+/// Host and port of "host:port", or of "host" with `DEFAULT_TCP_PORT`; IPv6
+/// hosts may be bracketed ("[::1]:7070").
+#[allow(dead_code)] // jscopectl --host
+pub(crate) fn split_tcp_addr(addr: &str) -> (&str, u16) {
+    let (host, port) = match addr.rsplit_once(':') {
+        Some((host, port)) if !host.ends_with(':') => match port.parse() {
+            Ok(port) => (host, port),
+            Err(_) => (addr, DEFAULT_TCP_PORT),
+        },
+        _ => (addr, DEFAULT_TCP_PORT),
+    };
+    (host.trim_matches(['[', ']']), port)
+}
+
+/// Persistent framed connection to the daemon.
+///
+/// Requests may be pipelined: `send` several, then `recv` the replies, which
+/// come back in order.
+pub struct Connection {
+    stream: Transport,
+    cbor: bool,
+    protocol_version: Option<u32>,
+}
+
+impl Connection {
+    /// Connect to the daemon socket; `cbor` picks the payload encoding.
+    pub fn connect(path: &Path, cbor: bool) -> io::Result<Self> {
+        Ok(Connection {
+            stream: Transport::Unix(UnixStream::connect(path)?),
+            cbor,
+            protocol_version: None,
+        })
+    }
+
+    /// Connect to a daemon's TCP listener: "host:port", or "host" for `DEFAULT_TCP_PORT`.
+    #[allow(dead_code)] // jscopectl --host
+    pub fn connect_tcp(addr: &str, cbor: bool) -> io::Result<Self> {
+        let stream = TcpStream::connect(split_tcp_addr(addr))?;
+        stream.set_nodelay(true)?;
+        Ok(Connection {
+            stream: Transport::Tcp(stream),
+            cbor,
+            protocol_version: None,
+        })
+    }
+
+    /// Fail reads and writes that take longer than `timeout`.
+    pub fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        match &self.stream {
+            Transport::Unix(s) => {
+                s.set_read_timeout(timeout)?;
+                s.set_write_timeout(timeout)
+            }
+            Transport::Tcp(s) => {
+                s.set_read_timeout(timeout)?;
+                s.set_write_timeout(timeout)
+            }
+        }
+    }
+
+    pub fn send(&mut self, req: &Request) -> anyhow::Result<()> {
+        let payload = if self.cbor {
+            serde_cbor::to_vec(req)?
+        } else {
+            serde_json::to_vec(req)?
+        };
+        Ok(write_frame(&mut self.stream, &payload)?)
+    }
+
+    pub fn recv(&mut self) -> anyhow::Result<Response> {
+        let payload = read_frame(&mut self.stream)?
+            .ok_or_else(|| anyhow::anyhow!("daemon closed the connection"))?;
+        let reply: Reply = if self.cbor {
+            serde_cbor::from_slice(&payload)?
+        } else {
+            serde_json::from_slice(&payload)?
+        };
+        self.protocol_version = Some(reply.protocol_version);
+        Ok(reply.response)
+    }
+
+    /// Protocol version of the last reply; `None` before the first one.
+    #[allow(dead_code)] // jscopectl version
+    pub fn protocol_version(&self) -> Option<u32> {
+        self.protocol_version
+    }
+
+    /// `send` then `recv`.
+    pub fn request(&mut self, req: &Request) -> anyhow::Result<Response> {
+        self.send(req)?;
+        self.recv()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn frames_round_trip_and_reject_oversize() {
+        let mut wire = Vec::new();
+        write_frame(&mut wire, b"{\"GetStats\":null}").unwrap();
+        write_frame(&mut wire, b"").unwrap();
+        assert_eq!(wire[0], 0);
+
+        let mut r = Cursor::new(wire);
+        assert_eq!(read_frame(&mut r).unwrap().unwrap(), b"{\"GetStats\":null}");
+        assert_eq!(read_frame(&mut r).unwrap().unwrap(), b"");
+        assert!(read_frame(&mut r).unwrap().is_none());
+
+        assert!(read_frame(&mut Cursor::new(vec![0, 0])).is_err());
+        assert!(read_frame(&mut Cursor::new(vec![1, 0, 0, 0])).is_err());
+        assert!(write_frame(&mut Vec::new(), &vec![0; MAX_FRAME_LEN + 1]).is_err());
+    }
+
+    #[test]
+    fn connection_pipelines_requests() {
+        let (a, mut b) = UnixStream::pair().unwrap();
+        let mut conn = Connection {
+            stream: Transport::Unix(a),
+            cbor: false,
+            protocol_version: None,
+        };
+        conn.send(&Request::GetHealth).unwrap();
+        conn.send(&Request::ListControls).unwrap();
+        for _ in 0..2 {
+            let payload = read_frame(&mut b).unwrap().unwrap();
+            let reply = match serde_json::from_slice(&payload).unwrap() {
+                Request::ListControls => Response::Controls(Vec::new()),
+                _ => Response::SessionStarted { id: 1 },
+            };
+            write_frame(&mut b, &serde_json::to_vec(&Reply::new(reply)).unwrap()).unwrap();
+        }
+        assert!(matches!(conn.recv().unwrap(), Response::SessionStarted { id: 1 }));
+        assert_eq!(conn.protocol_version(), Some(PROTOCOL_VERSION));
+        assert!(matches!(conn.recv().unwrap(), Response::Controls(_)));
+    }
+
+    #[test]
+    fn connection_works_over_tcp() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let server = std::thread::spawn(move || {
+            let (mut s, _) = listener.accept().unwrap();
+            let payload = read_frame(&mut s).unwrap().unwrap();
+            assert!(matches!(serde_cbor::from_slice(&payload).unwrap(), Request::GetHealth));
+            let reply = serde_cbor::to_vec(&Reply::new(Response::SessionStarted { id: 7 })).unwrap();
+            write_frame(&mut s, &reply).unwrap();
+        });
+        let mut conn = Connection::connect_tcp(&addr, true).unwrap();
+        conn.set_timeout(Some(Duration::from_secs(5))).unwrap();
+        assert!(matches!(
+            conn.request(&Request::GetHealth).unwrap(),
+            Response::SessionStarted { id: 7 }
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn replies_carry_the_protocol_version() {
+        let reply = Reply::new(Response::Version(VersionInfo::current()));
+        let json = serde_json::to_value(&reply).unwrap();
+        assert_eq!(json["protocol_version"], PROTOCOL_VERSION);
+        assert_eq!(json["Version"]["crate_version"], env!("CARGO_PKG_VERSION"));
+
+        let cbor = serde_cbor::to_vec(&reply).unwrap();
+        let back: Reply = serde_cbor::from_slice(&cbor).unwrap();
+        assert_eq!(back.protocol_version, PROTOCOL_VERSION);
+        assert!(matches!(back.response, Response::Version(v) if v.protocol_version == PROTOCOL_VERSION));
+
+        // Daemons that predate versioning send the bare response
+        let old: Reply = serde_json::from_str(r#"{"SessionStarted":{"id":3}}"#).unwrap();
+        assert_eq!(old.protocol_version, 0);
+        assert!(matches!(old.response, Response::SessionStarted { id: 3 }));
+    }
+
+    #[test]
+    fn history_requests_from_older_clients_still_parse() {
+        // Version 2 clients named the window `window_secs` and had no `full`
+        let req: Request =
+            serde_json::from_str(r#"{"GetHistory":{"window_secs":300,"max_points":60}}"#).unwrap();
+        assert!(matches!(
+            req,
+            Request::GetHistory {
+                since_secs: 300,
+                max_points: 60,
+                full: false
+            }
+        ));
+    }
+}
@@ -0,0 +1,375 @@
+//! Long-term sample recorder that survives hard power cycles.
+//!
+//! Samples are appended to `journal.jsonl` in the record directory, one JSON
+//! object per line holding the raw tegrastats line, and fsync'ed according to
+//! `FsyncPolicy`. Once the journal passes the segment size it is synced and
+//! renamed to `segment-<first ms>.jsonl`; finished segments are never written
+//! again. On start the journal is scanned, a torn or garbage tail (power cut
+//! mid-write) is truncated, and a gap marker covers the time between the last
+//! good sample and the restart.
+
+use crate::parser::TegraStats;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+const JOURNAL: &str = "journal.jsonl";
+const DEFAULT_SEGMENT_MB: u64 = 16;
+const DEFAULT_FSYNC_SECS: u64 = 5;
+
+/// When appended samples are forced to disk.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FsyncPolicy {
+    /// After every sample
+    Always,
+    /// At most this long after a sample
+    Every(Duration),
+    /// Left to the kernel (only segment rotation syncs)
+    Never,
+}
+
+impl FsyncPolicy {
+    /// "always", "never" or a number of seconds.
+    fn parse(s: &str) -> Option<Self> {
+        match s.trim() {
+            "always" => Some(FsyncPolicy::Always),
+            "never" => Some(FsyncPolicy::Never),
+            secs => Some(FsyncPolicy::Every(Duration::from_secs(secs.parse().ok()?))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecorderConfig {
+    pub dir: PathBuf,
+    pub fsync: FsyncPolicy,
+    pub segment_bytes: u64,
+}
+
+impl RecorderConfig {
+    /// JETSONSCOPE_RECORD_DIR enables recording; JETSONSCOPE_RECORD_FSYNC
+    /// (`always`/`never`/seconds, default 5) and JETSONSCOPE_RECORD_SEGMENT_MB (default 16).
+    pub fn from_env() -> Option<Self> {
+        let dir = std::env::var("JETSONSCOPE_RECORD_DIR").ok()?;
+        let fsync = match std::env::var("JETSONSCOPE_RECORD_FSYNC") {
+            Ok(s) => FsyncPolicy::parse(&s).unwrap_or_else(|| {
+                eprintln!(
+                    "Ignoring JETSONSCOPE_RECORD_FSYNC '{}': expected always, never or seconds",
+                    s
+                );
+                FsyncPolicy::Every(Duration::from_secs(DEFAULT_FSYNC_SECS))
+            }),
+            Err(_) => FsyncPolicy::Every(Duration::from_secs(DEFAULT_FSYNC_SECS)),
+        };
+        let segment_mb = std::env::var("JETSONSCOPE_RECORD_SEGMENT_MB")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .filter(|mb| *mb > 0)
+            .unwrap_or(DEFAULT_SEGMENT_MB);
+        Some(RecorderConfig {
+            dir: PathBuf::from(dir),
+            fsync,
+            segment_bytes: segment_mb * 1024 * 1024,
+        })
+    }
+}
+
+/// One journal line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Record {
+    /// A tegrastats sample; `raw` re-parses with `TegraStats::parse`
+    Sample { unix_ms: u64, raw: String },
+    /// No samples were recorded between these times (daemon down, power loss)
+    Gap { from_ms: u64, to_ms: u64 },
+}
+
+impl Record {
+    pub fn unix_ms(&self) -> u64 {
+        match self {
+            Record::Sample { unix_ms, .. } => *unix_ms,
+            Record::Gap { to_ms, .. } => *to_ms,
+        }
+    }
+}
+
+pub struct Recorder {
+    cfg: RecorderConfig,
+    journal: File,
+    bytes: u64,
+    /// Time of the journal's first record, used to name its segment
+    first_ms: Option<u64>,
+    last_sync: Instant,
+    dirty: bool,
+}
+
+impl Recorder {
+    /// Open (or create) the journal, dropping a torn tail and marking the downtime as a gap.
+    pub fn open(cfg: RecorderConfig, now_ms: u64) -> Result<Self> {
+        fs::create_dir_all(&cfg.dir).with_context(|| format!("creating {:?}", cfg.dir))?;
+        let path = cfg.dir.join(JOURNAL);
+        let scan = scan_journal(&path)?;
+        let journal = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("opening {:?}", path))?;
+        if journal.metadata()?.len() > scan.valid_bytes {
+            journal
+                .set_len(scan.valid_bytes)
+                .with_context(|| format!("truncating {:?}", path))?;
+            journal.sync_all()?;
+        }
+        let last_ms = match scan.last_ms {
+            Some(ms) => Some(ms),
+            None => last_segment_ms(&cfg.dir)?,
+        };
+        let mut recorder = Recorder {
+            cfg,
+            journal,
+            bytes: scan.valid_bytes,
+            first_ms: scan.first_ms,
+            last_sync: Instant::now(),
+            dirty: false,
+        };
+        if let Some(from_ms) = last_ms.filter(|ms| *ms < now_ms) {
+            recorder.write(&Record::Gap {
+                from_ms,
+                to_ms: now_ms,
+            })?;
+            recorder.sync()?;
+        }
+        Ok(recorder)
+    }
+
+    pub fn append(&mut self, unix_ms: u64, stats: &TegraStats) -> Result<()> {
+        self.write(&Record::Sample {
+            unix_ms,
+            raw: stats.raw.clone(),
+        })?;
+        let due = match self.cfg.fsync {
+            FsyncPolicy::Always => true,
+            FsyncPolicy::Every(period) => self.last_sync.elapsed() >= period,
+            FsyncPolicy::Never => false,
+        };
+        if due {
+            self.sync()?;
+        }
+        if self.bytes >= self.cfg.segment_bytes {
+            self.rotate()?;
+        }
+        Ok(())
+    }
+
+    /// Force appended records to disk.
+    pub fn sync(&mut self) -> Result<()> {
+        if self.dirty {
+            self.journal.sync_data().context("syncing journal")?;
+            self.dirty = false;
+        }
+        self.last_sync = Instant::now();
+        Ok(())
+    }
+
+    fn write(&mut self, record: &Record) -> Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        // One write per record keeps a crash from interleaving partial lines
+        self.journal
+            .write_all(&line)
+            .context("appending to journal")?;
+        self.bytes += line.len() as u64;
+        self.first_ms.get_or_insert(record.unix_ms());
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Seal the journal as a segment and start an empty one.
+    fn rotate(&mut self) -> Result<()> {
+        self.sync()?;
+        let journal = self.cfg.dir.join(JOURNAL);
+        let segment = self
+            .cfg
+            .dir
+            .join(format!("segment-{:013}.jsonl", self.first_ms.unwrap_or(0)));
+        fs::rename(&journal, &segment)
+            .with_context(|| format!("renaming {:?} to {:?}", journal, segment))?;
+        self.journal = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&journal)
+            .with_context(|| format!("opening {:?}", journal))?;
+        // Persist the rename and the new journal's directory entry
+        File::open(&self.cfg.dir)
+            .and_then(|d| d.sync_all())
+            .with_context(|| format!("syncing {:?}", self.cfg.dir))?;
+        self.bytes = 0;
+        self.first_ms = None;
+        Ok(())
+    }
+}
+
+/// Valid prefix of a journal and the times of its first and last records.
+#[derive(Debug, Default, PartialEq)]
+struct JournalScan {
+    valid_bytes: u64,
+    first_ms: Option<u64>,
+    last_ms: Option<u64>,
+}
+
+/// Records up to the first line that is incomplete or does not parse.
+fn scan_journal(path: &Path) -> Result<JournalScan> {
+    let mut scan = JournalScan::default();
+    let file = match File::open(path) {
+        Ok(f) => f,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(scan),
+        Err(e) => return Err(e).with_context(|| format!("opening {:?}", path)),
+    };
+    let mut reader = BufReader::new(file);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        let n = reader.read_until(b'\n', &mut line)?;
+        if n == 0 || line.last() != Some(&b'\n') {
+            break;
+        }
+        let Ok(record) = serde_json::from_slice::<Record>(&line) else {
+            break;
+        };
+        scan.first_ms.get_or_insert(record.unix_ms());
+        scan.last_ms = Some(record.unix_ms());
+        scan.valid_bytes += n as u64;
+    }
+    Ok(scan)
+}
+
+fn segments(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut out: Vec<PathBuf> = fs::read_dir(dir)
+        .with_context(|| format!("reading {:?}", dir))?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("segment-") && n.ends_with(".jsonl"))
+        })
+        .collect();
+    // Zero-padded start times sort chronologically
+    out.sort();
+    Ok(out)
+}
+
+fn last_segment_ms(dir: &Path) -> Result<Option<u64>> {
+    match segments(dir)?.last() {
+        Some(path) => Ok(scan_journal(path)?.last_ms),
+        None => Ok(None),
+    }
+}
+
+/// Every readable record in `dir`, oldest first (segments, then the journal).
+pub fn read_records(dir: &Path) -> Result<Vec<Record>> {
+    let mut files = segments(dir)?;
+    files.push(dir.join(JOURNAL));
+    let mut records = Vec::new();
+    for path in files {
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        records.extend(
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str::<Record>(line).ok()),
+        );
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(raw: &str) -> TegraStats {
+        TegraStats {
+            raw: raw.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn recovers_torn_journal_with_gap_marker() {
+        let dir = std::env::temp_dir().join(format!("jscope-rec-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let cfg = RecorderConfig {
+            dir: dir.clone(),
+            fsync: FsyncPolicy::Always,
+            segment_bytes: 1024 * 1024,
+        };
+
+        let mut rec = Recorder::open(cfg.clone(), 1_000).unwrap();
+        rec.append(1_000, &sample("RAM 1/2MB")).unwrap();
+        rec.append(2_000, &sample("RAM 1/2MB")).unwrap();
+        drop(rec);
+        // Power cut mid-write: half a line plus zeroed blocks
+        let mut f = OpenOptions::new()
+            .append(true)
+            .open(dir.join(JOURNAL))
+            .unwrap();
+        f.write_all(b"{\"kind\":\"sample\",\"unix_ms\":3000,\"ra\0\0\0\0")
+            .unwrap();
+        drop(f);
+
+        let mut rec = Recorder::open(cfg, 60_000).unwrap();
+        rec.append(60_000, &sample("RAM 1/2MB")).unwrap();
+        let records = read_records(&dir).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(records.len(), 4);
+        assert_eq!(
+            records[2],
+            Record::Gap {
+                from_ms: 2_000,
+                to_ms: 60_000
+            }
+        );
+        assert_eq!(records[3].unix_ms(), 60_000);
+    }
+
+    #[test]
+    fn rotates_segments_and_reads_them_in_order() {
+        let dir = std::env::temp_dir().join(format!("jscope-rec-rot-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let cfg = RecorderConfig {
+            dir: dir.clone(),
+            fsync: FsyncPolicy::Never,
+            segment_bytes: 200,
+        };
+        let mut rec = Recorder::open(cfg.clone(), 0).unwrap();
+        for i in 0..10 {
+            rec.append(1_000 + i, &sample(&"x".repeat(40))).unwrap();
+        }
+        drop(rec);
+        let segs = segments(&dir).unwrap();
+        assert!(segs.len() >= 3, "{:?}", segs);
+        assert!(segs[0].ends_with("segment-0000000001000.jsonl"));
+
+        // Empty journal after a rotation: the gap starts at the last segment's end
+        let rec = Recorder::open(cfg, 5_000).unwrap();
+        drop(rec);
+        let records = read_records(&dir).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        let times: Vec<u64> = records.iter().map(Record::unix_ms).collect();
+        assert!(times.windows(2).all(|w| w[0] <= w[1]), "{:?}", times);
+        assert_eq!(records.len(), 11);
+        assert!(matches!(records[10], Record::Gap { from_ms: 1_009, .. }));
+
+        assert_eq!(FsyncPolicy::parse("always"), Some(FsyncPolicy::Always));
+        assert_eq!(
+            FsyncPolicy::parse("10"),
+            Some(FsyncPolicy::Every(Duration::from_secs(10)))
+        );
+        assert_eq!(FsyncPolicy::parse("soon"), None);
+    }
+}
@@ -0,0 +1,331 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+const SYSFS_CPUFREQ: &str = "/sys/devices/system/cpu/cpufreq";
+
+/// Time spent at one frequency step.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResidencyLevel {
+    pub freq_mhz: u64,
+    pub time_ms: u64,
+    /// Share of the domain's total time at this step
+    pub percent: f64,
+}
+
+/// Frequency residency of one clock domain (a cpufreq policy or the GPU devfreq).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FreqResidency {
+    /// "CPU0-3" (cores sharing a policy) or "GPU"
+    pub domain: String,
+    /// Current step, when the kernel marks it (devfreq only)
+    pub current_mhz: Option<u64>,
+    /// Ascending by frequency
+    pub levels: Vec<ResidencyLevel>,
+}
+
+impl FreqResidency {
+    fn new(domain: String, current_mhz: Option<u64>, mut levels: Vec<ResidencyLevel>) -> Self {
+        levels.sort_by_key(|l| l.freq_mhz);
+        let mut res = FreqResidency {
+            domain,
+            current_mhz,
+            levels,
+        };
+        res.update_percent();
+        res
+    }
+
+    pub fn total_ms(&self) -> u64 {
+        self.levels.iter().map(|l| l.time_ms).sum()
+    }
+
+    /// Residency accumulated since `earlier` (the kernel counters are cumulative since boot).
+    pub fn since(&self, earlier: &FreqResidency) -> FreqResidency {
+        let levels = self
+            .levels
+            .iter()
+            .map(|l| {
+                let before = earlier
+                    .levels
+                    .iter()
+                    .find(|e| e.freq_mhz == l.freq_mhz)
+                    .map(|e| e.time_ms)
+                    .unwrap_or(0);
+                ResidencyLevel {
+                    freq_mhz: l.freq_mhz,
+                    time_ms: l.time_ms.saturating_sub(before),
+                    percent: 0.0,
+                }
+            })
+            .collect();
+        FreqResidency::new(self.domain.clone(), self.current_mhz, levels)
+    }
+
+    /// Steps sorted by time spent, busiest first.
+    pub fn busiest(&self) -> Vec<&ResidencyLevel> {
+        let mut levels: Vec<&ResidencyLevel> = self.levels.iter().collect();
+        levels.sort_by_key(|l| std::cmp::Reverse(l.time_ms));
+        levels
+    }
+
+    fn update_percent(&mut self) {
+        let total = self.total_ms();
+        for level in &mut self.levels {
+            level.percent = if total == 0 {
+                0.0
+            } else {
+                level.time_ms as f64 * 100.0 / total as f64
+            };
+        }
+    }
+}
+
+/// GPU runtime-PM residency: time powered vs rail-gated/suspended.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GpuRuntimePm {
+    pub active_ms: u64,
+    pub suspended_ms: u64,
+    /// Share of time the GPU was rail-gated/suspended
+    pub suspended_percent: f64,
+}
+
+impl GpuRuntimePm {
+    pub fn new(active_ms: u64, suspended_ms: u64) -> Self {
+        let total = active_ms + suspended_ms;
+        GpuRuntimePm {
+            active_ms,
+            suspended_ms,
+            suspended_percent: if total == 0 {
+                0.0
+            } else {
+                suspended_ms as f64 * 100.0 / total as f64
+            },
+        }
+    }
+
+    pub fn since(&self, earlier: &GpuRuntimePm) -> GpuRuntimePm {
+        GpuRuntimePm::new(
+            self.active_ms.saturating_sub(earlier.active_ms),
+            self.suspended_ms.saturating_sub(earlier.suspended_ms),
+        )
+    }
+}
+
+/// Everything GetResidency returns.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResidencyReport {
+    pub domains: Vec<FreqResidency>,
+    pub gpu_runtime: Option<GpuRuntimePm>,
+}
+
+impl ResidencyReport {
+    pub fn read() -> Self {
+        ResidencyReport {
+            domains: read_all(),
+            gpu_runtime: crate::control::gpu_power_control_path()
+                .and_then(|control| read_runtime_pm(control.parent()?)),
+        }
+    }
+
+    /// Residency accumulated since `earlier`; domains missing from it are reported as-is.
+    pub fn since(&self, earlier: &ResidencyReport) -> ResidencyReport {
+        ResidencyReport {
+            domains: self
+                .domains
+                .iter()
+                .map(
+                    |now| match earlier.domains.iter().find(|b| b.domain == now.domain) {
+                        Some(base) => now.since(base),
+                        None => now.clone(),
+                    },
+                )
+                .collect(),
+            gpu_runtime: match (&self.gpu_runtime, &earlier.gpu_runtime) {
+                (Some(now), Some(base)) => Some(now.since(base)),
+                (now, _) => now.clone(),
+            },
+        }
+    }
+}
+
+/// Read `runtime_active_time`/`runtime_suspended_time` (ms) from a device `power/` directory.
+pub fn read_runtime_pm(power_dir: &Path) -> Option<GpuRuntimePm> {
+    let read = |name: &str| -> Option<u64> {
+        fs::read_to_string(power_dir.join(name))
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+    };
+    Some(GpuRuntimePm::new(
+        read("runtime_active_time")?,
+        read("runtime_suspended_time")?,
+    ))
+}
+
+/// CPU policies plus the GPU, whichever expose statistics.
+pub fn read_all() -> Vec<FreqResidency> {
+    let mut all = read_cpufreq(Path::new(SYSFS_CPUFREQ));
+    if let Some(dir) = crate::control::gpu_devfreq_path() {
+        if let Ok(content) = fs::read_to_string(dir.join("trans_stat")) {
+            all.extend(parse_trans_stat("GPU", &content));
+        }
+    }
+    all
+}
+
+/// Read `policy*/stats/time_in_state` under a cpufreq directory.
+pub fn read_cpufreq(base: &Path) -> Vec<FreqResidency> {
+    let Ok(entries) = fs::read_dir(base) else {
+        return Vec::new();
+    };
+    let mut policies: Vec<_> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("policy"))
+        })
+        .collect();
+    policies.sort();
+    policies
+        .iter()
+        .filter_map(|dir| {
+            let content = fs::read_to_string(dir.join("stats/time_in_state")).ok()?;
+            let name = dir.file_name()?.to_string_lossy().to_string();
+            let domain = fs::read_to_string(dir.join("related_cpus"))
+                .ok()
+                .and_then(|cpus| cpu_range(&cpus))
+                .unwrap_or(name);
+            Some(parse_time_in_state(&domain, &content))
+        })
+        .filter(|r| !r.levels.is_empty())
+        .collect()
+}
+
+/// cpufreq `time_in_state`: "<kHz> <time in 10ms units>" per line.
+pub fn parse_time_in_state(domain: &str, content: &str) -> FreqResidency {
+    let levels = content
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let khz: u64 = parts.next()?.parse().ok()?;
+            let ticks: u64 = parts.next()?.parse().ok()?;
+            Some(ResidencyLevel {
+                freq_mhz: khz / 1000,
+                time_ms: ticks * 10,
+                percent: 0.0,
+            })
+        })
+        .collect();
+    FreqResidency::new(domain.to_string(), None, levels)
+}
+
+/// devfreq `trans_stat`: a transition matrix whose rows are "[*]<Hz>: counts... <time ms>".
+pub fn parse_trans_stat(domain: &str, content: &str) -> Option<FreqResidency> {
+    let mut current = None;
+    let mut levels = Vec::new();
+    for line in content.lines() {
+        let Some((head, rest)) = line.split_once(':') else {
+            continue;
+        };
+        let head = head.trim();
+        let (is_current, freq) = match head.strip_prefix('*') {
+            Some(f) => (true, f.trim()),
+            None => (false, head),
+        };
+        let Ok(hz) = freq.parse::<u64>() else {
+            continue;
+        };
+        let Some(time_ms) = rest.split_whitespace().last().and_then(|t| t.parse().ok()) else {
+            continue;
+        };
+        if is_current {
+            current = Some(hz / 1_000_000);
+        }
+        levels.push(ResidencyLevel {
+            freq_mhz: hz / 1_000_000,
+            time_ms,
+            percent: 0.0,
+        });
+    }
+    (!levels.is_empty()).then(|| FreqResidency::new(domain.to_string(), current, levels))
+}
+
+/// "0 1 2 3" -> "CPU0-3"
+fn cpu_range(cpus: &str) -> Option<String> {
+    let ids: Vec<u32> = cpus
+        .split_whitespace()
+        .filter_map(|s| s.parse().ok())
+        .collect();
+    match (ids.first(), ids.last()) {
+        (Some(a), Some(b)) if a == b => Some(format!("CPU{}", a)),
+        (Some(a), Some(b)) => Some(format!("CPU{}-{}", a, b)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cpufreq_policies() {
+        let dir = std::env::temp_dir().join(format!("jscope-residency-{}", std::process::id()));
+        let policy = dir.join("policy0");
+        fs::create_dir_all(policy.join("stats")).unwrap();
+        fs::write(policy.join("related_cpus"), "0 1 2 3\n").unwrap();
+        fs::write(
+            policy.join("stats/time_in_state"),
+            "1497600 300\n729600 100\n",
+        )
+        .unwrap();
+
+        let res = read_cpufreq(&dir);
+        fs::remove_dir_all(&dir).ok();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].domain, "CPU0-3");
+        assert_eq!(res[0].levels[0].freq_mhz, 729);
+        assert_eq!(res[0].levels[1].time_ms, 3000);
+        assert_eq!(res[0].levels[1].percent, 75.0);
+    }
+
+    #[test]
+    fn runtime_pm_suspended_share() {
+        let dir = std::env::temp_dir().join(format!("jscope-runtime-pm-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("runtime_active_time"), "1000\n").unwrap();
+        fs::write(dir.join("runtime_suspended_time"), "3000\n").unwrap();
+        let pm = read_runtime_pm(&dir).unwrap();
+        fs::remove_dir_all(&dir).ok();
+        assert_eq!(pm.suspended_percent, 75.0);
+        let later = GpuRuntimePm::new(2000, 3000);
+        assert_eq!(later.since(&pm).suspended_percent, 0.0);
+    }
+
+    #[test]
+    fn parses_devfreq_trans_stat_and_deltas() {
+        let before = "     From  :   To
+           : 306000000 918000000   time(ms)
+* 306000000:         0         4      1000
+  918000000:         3         0      1000
+Total transition : 7
+";
+        let after = "     From  :   To
+           : 306000000 918000000   time(ms)
+  306000000:         0         5      1500
+* 918000000:         4         0      2500
+Total transition : 9
+";
+        let a = parse_trans_stat("GPU", before).unwrap();
+        let b = parse_trans_stat("GPU", after).unwrap();
+        assert_eq!(a.current_mhz, Some(306));
+        assert_eq!(b.current_mhz, Some(918));
+        let d = b.since(&a);
+        assert_eq!(d.total_ms(), 2000);
+        assert_eq!(d.busiest()[0].freq_mhz, 918);
+        assert_eq!(d.busiest()[0].percent, 75.0);
+    }
+}
@@ -0,0 +1,347 @@
+//! Scripted synthetic data for demos, screenshots and UI work.
+//!
+//! `jscope --scenario` or JETSONSCOPE_DEMO_SCENARIO picks a profile. Each one
+//! drives load from a sample counter, derives rail power from load and clocks,
+//! and moves temperatures toward what that power would heat the board to, so
+//! GPU, power and temperature curves rise and fall together. `random` is the
+//! old uniform noise.
+
+use crate::collector::synthesize_stats;
+use crate::parser::{PowerRail, TegraStats};
+use crate::throttle::{CappedClock, HotSensor, ThrottleState};
+use rand::Rng;
+use std::collections::HashMap;
+
+/// Synthetic data profile (`jscope --scenario`, JETSONSCOPE_DEMO_SCENARIO).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Scenario {
+    /// Independent uniform noise on every value
+    #[default]
+    Random,
+    /// Near-idle board: low load, cool, occasional GPU blips
+    Idle,
+    /// Camera pipeline: GPU/DLA bursts every 20 samples, heat following power
+    InferenceBurst,
+    /// Sustained load without cooling: temperatures climb until the clocks throttle
+    ThermalRunaway,
+    /// RAM fills up, then swap grows with heavy eMMC writes
+    SwapPressure,
+}
+
+impl Scenario {
+    pub const ALL: [Scenario; 5] = [
+        Scenario::Random,
+        Scenario::Idle,
+        Scenario::InferenceBurst,
+        Scenario::ThermalRunaway,
+        Scenario::SwapPressure,
+    ];
+
+    /// JETSONSCOPE_DEMO_SCENARIO, else random.
+    pub fn from_env() -> Self {
+        std::env::var("JETSONSCOPE_DEMO_SCENARIO")
+            .ok()
+            .and_then(|v| Self::parse(&v))
+            .unwrap_or_default()
+    }
+
+    /// "idle", "inference-burst", ... (case-insensitive; `_` works for `-`).
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().to_ascii_lowercase().replace('_', "-");
+        Self::ALL.into_iter().find(|sc| sc.name() == s)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scenario::Random => "random",
+            Scenario::Idle => "idle",
+            Scenario::InferenceBurst => "inference-burst",
+            Scenario::ThermalRunaway => "thermal-runaway",
+            Scenario::SwapPressure => "swap-pressure",
+        }
+    }
+}
+
+const AMBIENT_C: f32 = 30.0;
+/// Share of the gap to the steady-state temperature closed per sample
+const THERMAL_LAG: f32 = 0.08;
+/// Thermal-runaway clocks drop at this junction temperature and recover below the second
+const THROTTLE_C: f32 = 97.0;
+const UNTHROTTLE_C: f32 = 92.0;
+const INFERENCE_PERIOD: u64 = 20;
+const INFERENCE_BURST: u64 = 8;
+
+/// Loads and clocks of one sample, before power and heat are derived.
+#[derive(Debug, Clone, Copy, Default)]
+struct Load {
+    cpu: f32,
+    gpu: f32,
+    dla: f32,
+    emc: f32,
+    cpu_mhz: u32,
+    gpu_mhz: u32,
+    /// Share of RAM and swap in use
+    ram: f64,
+    swap: f64,
+    disk_write_bytes_per_sec: f64,
+    /// Steady-state degrees per watt; higher means worse cooling
+    c_per_watt: f32,
+}
+
+/// Produces one sample per call for a `Scenario`.
+pub struct ScenarioGenerator {
+    scenario: Scenario,
+    tick: u64,
+    temp_c: f32,
+    ram: f64,
+    swap: f64,
+    throttled: bool,
+    rail_avg: HashMap<String, f64>,
+}
+
+impl ScenarioGenerator {
+    pub fn new(scenario: Scenario) -> Self {
+        Self {
+            scenario,
+            tick: 0,
+            temp_c: AMBIENT_C + 8.0,
+            ram: 0.6,
+            swap: 0.0,
+            throttled: false,
+            rail_avg: HashMap::new(),
+        }
+    }
+
+    pub fn next_stats(&mut self) -> TegraStats {
+        // The random sample supplies the layout (cores, engines, rails, mounts)
+        let mut stats = synthesize_stats();
+        if self.scenario == Scenario::Random {
+            return stats;
+        }
+        let load = self.load();
+        self.tick += 1;
+        self.apply(&mut stats, &load);
+        if self.throttled {
+            // Clocks stay capped until the board cools below UNTHROTTLE_C
+            let cpu_c = stats.temps["CPU"];
+            stats.throttling = Some(ThrottleState {
+                hot_sensors: (cpu_c >= THROTTLE_C)
+                    .then(|| HotSensor {
+                        sensor: "CPU".to_string(),
+                        temp_c: cpu_c,
+                        passive_c: THROTTLE_C,
+                    })
+                    .into_iter()
+                    .collect(),
+                capped_clocks: vec![CappedClock {
+                    domain: format!("CPU0-{}", stats.cpus.len().saturating_sub(1)),
+                    ceiling_mhz: load.cpu_mhz as u64,
+                    baseline_mhz: 1497,
+                }],
+                throttle_events: 0,
+            });
+        }
+        stats
+    }
+
+    fn load(&mut self) -> Load {
+        let mut rng = rand::thread_rng();
+        let mut jitter = |spread: f32| rng.gen_range(-spread..=spread);
+        let t = self.tick;
+        match self.scenario {
+            Scenario::Random | Scenario::Idle => Load {
+                cpu: 3.0 + jitter(2.0),
+                gpu: if t % 30 == 0 { 12.0 } else { 0.0 },
+                dla: 0.0,
+                emc: 4.0 + jitter(1.0),
+                cpu_mhz: 729,
+                gpu_mhz: 306,
+                ram: 0.22,
+                swap: 0.0,
+                disk_write_bytes_per_sec: 20_000.0,
+                c_per_watt: 2.0,
+            },
+            Scenario::InferenceBurst => {
+                let busy = t % INFERENCE_PERIOD < INFERENCE_BURST;
+                Load {
+                    cpu: if busy { 35.0 + jitter(8.0) } else { 5.0 + jitter(2.0) },
+                    gpu: if busy { 95.0 + jitter(4.0) } else { 2.0 },
+                    dla: if busy { 68.0 + jitter(6.0) } else { 0.0 },
+                    emc: if busy { 58.0 + jitter(5.0) } else { 6.0 },
+                    cpu_mhz: if busy { 1497 } else { 729 },
+                    gpu_mhz: if busy { 1300 } else { 306 },
+                    ram: 0.55,
+                    swap: 0.0,
+                    disk_write_bytes_per_sec: 150_000.0,
+                    c_per_watt: 3.0,
+                }
+            }
+            Scenario::ThermalRunaway => {
+                if self.temp_c >= THROTTLE_C {
+                    self.throttled = true;
+                } else if self.temp_c < UNTHROTTLE_C {
+                    self.throttled = false;
+                }
+                Load {
+                    cpu: 88.0 + jitter(5.0),
+                    gpu: 98.0 + jitter(2.0),
+                    dla: 40.0 + jitter(5.0),
+                    emc: 72.0 + jitter(4.0),
+                    cpu_mhz: if self.throttled { 1036 } else { 1497 },
+                    gpu_mhz: if self.throttled { 624 } else { 1300 },
+                    ram: 0.7,
+                    swap: 0.0,
+                    disk_write_bytes_per_sec: 50_000.0,
+                    // A stopped fan: nowhere near enough cooling for this load
+                    c_per_watt: 6.5,
+                }
+            }
+            Scenario::SwapPressure => {
+                // RAM fills first, then the kernel starts swapping
+                if self.ram < 0.97 {
+                    self.ram = (self.ram + 0.01).min(0.97);
+                } else {
+                    self.swap = (self.swap + 0.015).min(0.95);
+                }
+                let swapping = self.ram >= 0.97 && self.swap < 0.95;
+                Load {
+                    // kswapd spins while pages move out
+                    cpu: if swapping { 45.0 + jitter(15.0) } else { 15.0 + jitter(5.0) },
+                    gpu: 10.0 + jitter(5.0),
+                    dla: 0.0,
+                    emc: 25.0 + jitter(5.0),
+                    cpu_mhz: 1190,
+                    gpu_mhz: 306,
+                    ram: self.ram,
+                    swap: self.swap,
+                    disk_write_bytes_per_sec: if swapping { 28_000_000.0 } else { 200_000.0 },
+                    c_per_watt: 3.0,
+                }
+            }
+        }
+    }
+
+    fn apply(&mut self, stats: &mut TegraStats, load: &Load) {
+        let mut rng = rand::thread_rng();
+        let cpu_clock = load.cpu_mhz as f32 / 1497.0;
+        let gpu_clock = load.gpu_mhz as f32 / 1300.0;
+
+        for core in stats.cpus.iter_mut() {
+            let core_load = (load.cpu + rng.gen_range(-6.0..=6.0)).clamp(0.0, 100.0);
+            core.load_percent = Some(core_load.round() as u32);
+            core.freq_mhz = Some(load.cpu_mhz);
+        }
+        let mut set_engine = |name: &str, usage: f32, mhz: Option<u32>| {
+            if let Some(engine) = stats.engines.get_mut(name) {
+                engine.usage_percent = Some(usage.clamp(0.0, 100.0).round() as u32);
+                if mhz.is_some() {
+                    engine.freq_mhz = mhz;
+                }
+            }
+        };
+        set_engine("GR3D", load.gpu, Some(load.gpu_mhz));
+        set_engine("EMC", load.emc, None);
+        set_engine("DLA0_CORE", load.dla, None);
+
+        // Rails follow load scaled by clock, the board follows the rails
+        let cpu_mw = 400.0 + load.cpu * 32.0 * cpu_clock;
+        let gpu_mw = 150.0 + load.gpu * 75.0 * gpu_clock;
+        let in_mw = 3_500.0 + cpu_mw + gpu_mw + load.dla * 18.0 + load.emc * 12.0;
+        for (rail, mw) in [("VDD_IN", in_mw), ("VDD_CPU", cpu_mw)] {
+            let avg = self.rail_avg.entry(rail.to_string()).or_insert(mw as f64);
+            *avg = *avg * 0.9 + mw as f64 * 0.1;
+            stats.power.insert(
+                rail.to_string(),
+                PowerRail {
+                    current_mw: mw as u32,
+                    average_mw: *avg as u32,
+                },
+            );
+        }
+        let target = AMBIENT_C + in_mw / 1000.0 * load.c_per_watt;
+        self.temp_c += (target - self.temp_c) * THERMAL_LAG;
+        let gpu_heat = load.gpu / 100.0 * 2.0;
+        for (sensor, offset) in [
+            ("CPU", 0.0),
+            ("GPU", gpu_heat),
+            ("soc0", -2.0),
+            ("soc1", -1.5),
+            ("tj", gpu_heat.max(0.0) + 1.0),
+        ] {
+            let temp = self.temp_c + offset + rng.gen_range(-0.3..=0.3);
+            stats.temps.insert(sensor.to_string(), temp);
+        }
+
+        if let Some(ram) = stats.ram.as_mut() {
+            ram.used_bytes = (ram.total_bytes as f64 * load.ram) as u64;
+        }
+        if let Some(swap) = stats.swap.as_mut() {
+            swap.used_bytes = (swap.total_bytes as f64 * load.swap) as u64;
+            swap.cached_bytes = Some(swap.used_bytes / 8);
+        }
+        if let Some(dev) = stats.disk.as_mut().and_then(|d| d.devices.first_mut()) {
+            dev.write_bytes_per_sec = load.disk_write_bytes_per_sec;
+            dev.read_bytes_per_sec = load.disk_write_bytes_per_sec / 4.0;
+        }
+        stats.raw = format!("synthetic {}", self.scenario.name());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(scenario: Scenario, samples: usize) -> Vec<TegraStats> {
+        let mut generator = ScenarioGenerator::new(scenario);
+        (0..samples).map(|_| generator.next_stats()).collect()
+    }
+
+    fn input_mw(stats: &TegraStats) -> u32 {
+        stats.power["VDD_IN"].current_mw
+    }
+
+    #[test]
+    fn parses_names_and_env_spellings() {
+        assert_eq!(Scenario::parse("Inference_Burst"), Some(Scenario::InferenceBurst));
+        assert_eq!(Scenario::parse(" swap-pressure "), Some(Scenario::SwapPressure));
+        assert_eq!(Scenario::parse("meltdown"), None);
+        for scenario in Scenario::ALL {
+            assert_eq!(Scenario::parse(scenario.name()), Some(scenario));
+        }
+    }
+
+    #[test]
+    fn inference_bursts_raise_gpu_power_and_heat_together() {
+        let samples = run(Scenario::InferenceBurst, 40);
+        let (burst, quiet) = (&samples[20], &samples[35]);
+        assert!(burst.gpu_usage().unwrap() > 85);
+        assert!(quiet.gpu_usage().unwrap() < 10);
+        assert!(input_mw(burst) > input_mw(quiet) + 5_000);
+        // Heat lags power: warmest at the end of a burst, cooler after the pause
+        assert!(samples[27].temps["tj"] > samples[39].temps["tj"]);
+    }
+
+    #[test]
+    fn thermal_runaway_climbs_until_the_clocks_throttle() {
+        let samples = run(Scenario::ThermalRunaway, 120);
+        assert!(samples[30].temps["CPU"] > samples[2].temps["CPU"] + 20.0);
+        let throttled = samples
+            .iter()
+            .position(|s| s.engines["GR3D"].freq_mhz == Some(624))
+            .expect("never throttled");
+        assert!(samples[throttled - 1].temps["CPU"] >= THROTTLE_C - 1.0);
+        assert!(input_mw(&samples[throttled]) < input_mw(&samples[throttled - 1]));
+        assert!(samples[throttled].throttling.is_some());
+        assert!(samples[throttled - 1].throttling.is_none());
+    }
+
+    #[test]
+    fn swap_grows_once_ram_is_full() {
+        let samples = run(Scenario::SwapPressure, 80);
+        let swap_used = |s: &TegraStats| s.swap.as_ref().unwrap().used_bytes;
+        assert_eq!(swap_used(&samples[10]), 0);
+        assert!(swap_used(&samples[79]) > swap_used(&samples[50]));
+        let ram = samples[79].ram.as_ref().unwrap();
+        assert!(ram.used_bytes as f64 / ram.total_bytes as f64 > 0.95);
+    }
+}
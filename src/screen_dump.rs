@@ -0,0 +1,291 @@
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::style::{Color, Modifier};
+use std::fmt::Write;
+
+/// File formats for the TUI's screen dump key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DumpFormat {
+    /// Text with SGR escape codes (`cat` it in a terminal)
+    Ansi,
+    /// Standalone page with a colored `<pre>`
+    Html,
+}
+
+impl DumpFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            DumpFormat::Ansi => "ans",
+            DumpFormat::Html => "html",
+        }
+    }
+
+    pub fn render(self, buffer: &Buffer) -> String {
+        match self {
+            DumpFormat::Ansi => buffer_to_ansi(buffer),
+            DumpFormat::Html => buffer_to_html(buffer),
+        }
+    }
+}
+
+/// Convert a rendered frame buffer into plain text (one line per row, trailing spaces trimmed).
+pub fn buffer_to_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut out = String::new();
+    for y in area.top()..area.bottom() {
+        let mut line = String::new();
+        for x in area.left()..area.right() {
+            line.push_str(buffer[(x, y)].symbol());
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// Convert a frame buffer into text with ANSI colors, resetting at the end of each row.
+pub fn buffer_to_ansi(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut out = String::new();
+    for y in area.top()..area.bottom() {
+        let mut current: Option<(Color, Color, Modifier)> = None;
+        for x in area.left()..area.right() {
+            let cell = &buffer[(x, y)];
+            let style = (cell.fg, cell.bg, cell.modifier);
+            if current != Some(style) {
+                out.push_str(&sgr(cell));
+                current = Some(style);
+            }
+            out.push_str(cell.symbol());
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// SGR sequence selecting a cell's colors and modifiers from a clean state.
+fn sgr(cell: &Cell) -> String {
+    let mut codes = vec!["0".to_string()];
+    for (modifier, code) in [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::REVERSED, "7"),
+        (Modifier::CROSSED_OUT, "9"),
+    ] {
+        if cell.modifier.contains(modifier) {
+            codes.push(code.to_string());
+        }
+    }
+    codes.extend(ansi_color(cell.fg, false));
+    codes.extend(ansi_color(cell.bg, true));
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+fn ansi_color(color: Color, background: bool) -> Option<String> {
+    let base = if background { 40 } else { 30 };
+    let code = match color {
+        Color::Reset => return None,
+        Color::Black => base,
+        Color::Red => base + 1,
+        Color::Green => base + 2,
+        Color::Yellow => base + 3,
+        Color::Blue => base + 4,
+        Color::Magenta => base + 5,
+        Color::Cyan => base + 6,
+        Color::Gray => base + 7,
+        Color::DarkGray => base + 60,
+        Color::LightRed => base + 61,
+        Color::LightGreen => base + 62,
+        Color::LightYellow => base + 63,
+        Color::LightBlue => base + 64,
+        Color::LightMagenta => base + 65,
+        Color::LightCyan => base + 66,
+        Color::White => base + 67,
+        Color::Indexed(i) => return Some(format!("{};5;{}", base + 8, i)),
+        Color::Rgb(r, g, b) => return Some(format!("{};2;{};{};{}", base + 8, r, g, b)),
+    };
+    Some(code.to_string())
+}
+
+/// Convert a frame buffer into a standalone HTML page (dark background, colored spans).
+pub fn buffer_to_html(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>JetsonScope</title></head>\n\
+         <body style=\"background:#000;color:#c0c0c0\">\n<pre style=\"font-family:monospace;line-height:1.1\">",
+    );
+    for y in area.top()..area.bottom() {
+        let mut x = area.left();
+        while x < area.right() {
+            let cell = &buffer[(x, y)];
+            let style = (cell.fg, cell.bg, cell.modifier);
+            // Group runs of identical style into one span
+            let mut text = String::new();
+            while x < area.right() {
+                let next = &buffer[(x, y)];
+                if (next.fg, next.bg, next.modifier) != style {
+                    break;
+                }
+                text.push_str(next.symbol());
+                x += 1;
+            }
+            let css = html_style(cell);
+            if css.is_empty() {
+                out.push_str(&html_escape(&text));
+            } else {
+                let _ = write!(out, "<span style=\"{}\">{}</span>", css, html_escape(&text));
+            }
+        }
+        out.push('\n');
+    }
+    out.push_str("</pre>\n</body></html>\n");
+    out
+}
+
+fn html_style(cell: &Cell) -> String {
+    let (mut fg, mut bg) = (cell.fg, cell.bg);
+    if cell.modifier.contains(Modifier::REVERSED) {
+        // Reset swaps to the page defaults
+        fg = if cell.bg == Color::Reset {
+            Color::Black
+        } else {
+            cell.bg
+        };
+        bg = if cell.fg == Color::Reset {
+            Color::Gray
+        } else {
+            cell.fg
+        };
+    }
+    let mut css = Vec::new();
+    if let Some(hex) = html_color(fg) {
+        css.push(format!("color:{}", hex));
+    }
+    if let Some(hex) = html_color(bg) {
+        css.push(format!("background:{}", hex));
+    }
+    if cell.modifier.contains(Modifier::BOLD) {
+        css.push("font-weight:bold".to_string());
+    }
+    if cell.modifier.contains(Modifier::DIM) {
+        css.push("opacity:0.6".to_string());
+    }
+    if cell.modifier.contains(Modifier::ITALIC) {
+        css.push("font-style:italic".to_string());
+    }
+    if cell.modifier.contains(Modifier::UNDERLINED) {
+        css.push("text-decoration:underline".to_string());
+    }
+    css.join(";")
+}
+
+/// xterm's default palette for the 16 named colors.
+fn html_color(color: Color) -> Option<String> {
+    let hex = match color {
+        Color::Reset => return None,
+        Color::Black => "#000000",
+        Color::Red => "#cd0000",
+        Color::Green => "#00cd00",
+        Color::Yellow => "#cdcd00",
+        Color::Blue => "#0000ee",
+        Color::Magenta => "#cd00cd",
+        Color::Cyan => "#00cdcd",
+        Color::Gray => "#e5e5e5",
+        Color::DarkGray => "#7f7f7f",
+        Color::LightRed => "#ff0000",
+        Color::LightGreen => "#00ff00",
+        Color::LightYellow => "#ffff00",
+        Color::LightBlue => "#5c5cff",
+        Color::LightMagenta => "#ff00ff",
+        Color::LightCyan => "#00ffff",
+        Color::White => "#ffffff",
+        Color::Rgb(r, g, b) => return Some(format!("#{:02x}{:02x}{:02x}", r, g, b)),
+        Color::Indexed(i) => return Some(indexed_hex(i)),
+    };
+    Some(hex.to_string())
+}
+
+/// 256-color palette: 16 named, 6x6x6 cube, then a 24-step gray ramp.
+fn indexed_hex(i: u8) -> String {
+    const NAMED: [Color; 16] = [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::Gray,
+        Color::DarkGray,
+        Color::LightRed,
+        Color::LightGreen,
+        Color::LightYellow,
+        Color::LightBlue,
+        Color::LightMagenta,
+        Color::LightCyan,
+        Color::White,
+    ];
+    match i {
+        0..=15 => html_color(NAMED[i as usize]).unwrap_or_default(),
+        16..=231 => {
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let n = i - 16;
+            format!(
+                "#{:02x}{:02x}{:02x}",
+                level(n / 36),
+                level((n / 6) % 6),
+                level(n % 6)
+            )
+        }
+        _ => {
+            let v = 8 + (i - 232) * 10;
+            format!("#{:02x}{:02x}{:02x}", v, v, v)
+        }
+    }
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn text_dump_trims_rows() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 2));
+        buffer.set_string(0, 0, "CPU", ratatui::style::Style::default());
+        buffer.set_string(1, 1, "ok", ratatui::style::Style::default());
+        assert_eq!(buffer_to_text(&buffer), "CPU\n ok\n");
+    }
+
+    #[test]
+    fn colored_dumps_keep_styles() {
+        use ratatui::style::Style;
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 5, 1));
+        buffer.set_string(0, 0, "<a>", Style::default().fg(Color::Red));
+        buffer.set_string(
+            3,
+            0,
+            "ok",
+            Style::default()
+                .bg(Color::Rgb(1, 2, 3))
+                .add_modifier(Modifier::BOLD),
+        );
+
+        assert_eq!(
+            buffer_to_ansi(&buffer),
+            "\x1b[0;31m<a>\x1b[0;1;48;2;1;2;3mok\x1b[0m\n"
+        );
+        let html = buffer_to_html(&buffer);
+        assert!(html.contains("<span style=\"color:#cd0000\">&lt;a&gt;</span>"));
+        assert!(html.contains("<span style=\"background:#010203;font-weight:bold\">ok</span>"));
+        assert_eq!(indexed_hex(196), "#ff0000");
+        assert_eq!(indexed_hex(244), "#808080");
+    }
+}
@@ -0,0 +1,248 @@
+use crate::parser::TegraStats;
+use crate::thermal_governor::valid_temp;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Instant;
+
+/// Upper bound on concurrently open sessions (abandoned ones are evicted oldest-first).
+const MAX_ACTIVE_SESSIONS: usize = 16;
+/// Load (%) above which a frequency drop counts as a throttle event.
+const THROTTLE_LOAD_PERCENT: u32 = 80;
+
+/// Average and peak of one series over a session.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SeriesSummary {
+    pub avg: f64,
+    pub max: f64,
+}
+
+/// Report returned by StopSession.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub id: u64,
+    pub label: Option<String>,
+    /// Local wall-clock start, "%Y-%m-%d %H:%M:%S"
+    pub started_at: String,
+    pub duration_secs: f64,
+    pub samples: u64,
+    /// Average CPU load across cores
+    pub cpu: SeriesSummary,
+    pub gpu: SeriesSummary,
+    pub temps: BTreeMap<String, SeriesSummary>,
+    /// Energy consumed per rail during the session (Wh)
+    pub energy_wh: BTreeMap<String, f64>,
+    /// Clock-cap step-downs plus CPU/GPU frequency drops under heavy load
+    pub throttle_events: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Accumulator {
+    sum: f64,
+    max: f64,
+    count: u64,
+}
+
+impl Accumulator {
+    fn push(&mut self, v: f64) {
+        self.max = if self.count == 0 { v } else { self.max.max(v) };
+        self.sum += v;
+        self.count += 1;
+    }
+
+    fn summary(&self) -> SeriesSummary {
+        SeriesSummary {
+            avg: if self.count == 0 {
+                0.0
+            } else {
+                self.sum / self.count as f64
+            },
+            max: self.max,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Session {
+    label: Option<String>,
+    started: Instant,
+    started_at: String,
+    energy_start: BTreeMap<String, f64>,
+    samples: u64,
+    cpu: Accumulator,
+    gpu: Accumulator,
+    temps: BTreeMap<String, Accumulator>,
+    throttle_events: u64,
+    prev: Option<TegraStats>,
+    cap_level: usize,
+}
+
+/// Open profiling sessions, fed from the daemon's sample stream.
+#[derive(Debug, Default)]
+pub struct SessionManager {
+    next_id: u64,
+    active: HashMap<u64, Session>,
+}
+
+impl SessionManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open a session; `energy_wh` is the current per-rail energy counter snapshot.
+    pub fn start(
+        &mut self,
+        label: Option<String>,
+        energy_wh: BTreeMap<String, f64>,
+        cap_level: usize,
+    ) -> u64 {
+        if self.active.len() >= MAX_ACTIVE_SESSIONS {
+            if let Some(oldest) = self
+                .active
+                .iter()
+                .min_by_key(|(_, s)| s.started)
+                .map(|(id, _)| *id)
+            {
+                self.active.remove(&oldest);
+            }
+        }
+        self.next_id += 1;
+        self.active.insert(
+            self.next_id,
+            Session {
+                label,
+                started: Instant::now(),
+                started_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                energy_start: energy_wh,
+                samples: 0,
+                cpu: Accumulator::default(),
+                gpu: Accumulator::default(),
+                temps: BTreeMap::new(),
+                throttle_events: 0,
+                prev: None,
+                cap_level,
+            },
+        );
+        self.next_id
+    }
+
+    pub fn active_count(&self) -> usize {
+        self.active.len()
+    }
+
+    /// Add a sample to every open session; `cap_level` is the current clock-cap level.
+    pub fn record(&mut self, stats: &TegraStats, cap_level: usize) {
+        for session in self.active.values_mut() {
+            session.record(stats, cap_level);
+        }
+    }
+
+    /// Close a session and build its summary; `None` for unknown ids.
+    pub fn stop(&mut self, id: u64, energy_wh: &BTreeMap<String, f64>) -> Option<SessionSummary> {
+        let s = self.active.remove(&id)?;
+        let energy = energy_wh
+            .iter()
+            .map(|(rail, wh)| {
+                let start = s.energy_start.get(rail).copied().unwrap_or(0.0);
+                (rail.clone(), (wh - start).max(0.0))
+            })
+            .collect();
+        Some(SessionSummary {
+            id,
+            label: s.label,
+            started_at: s.started_at,
+            duration_secs: s.started.elapsed().as_secs_f64(),
+            samples: s.samples,
+            cpu: s.cpu.summary(),
+            gpu: s.gpu.summary(),
+            temps: s
+                .temps
+                .iter()
+                .map(|(k, acc)| (k.clone(), acc.summary()))
+                .collect(),
+            energy_wh: energy,
+            throttle_events: s.throttle_events,
+        })
+    }
+}
+
+impl Session {
+    fn record(&mut self, stats: &TegraStats, cap_level: usize) {
+        self.samples += 1;
+        let loads: Vec<u32> = stats.cpus.iter().filter_map(|c| c.load_percent).collect();
+        let cpu_avg = if loads.is_empty() {
+            None
+        } else {
+            Some(loads.iter().sum::<u32>() as f64 / loads.len() as f64)
+        };
+        if let Some(avg) = cpu_avg {
+            self.cpu.push(avg);
+        }
+        if let Some(gpu) = stats.gpu_usage() {
+            self.gpu.push(gpu as f64);
+        }
+        for (name, temp) in &stats.temps {
+            // -256C marks a disabled zone
+            if valid_temp(*temp) {
+                self.temps.entry(name.clone()).or_default().push(*temp as f64);
+            }
+        }
+
+        if cap_level > self.cap_level {
+            self.throttle_events += 1;
+        }
+        self.cap_level = cap_level;
+
+        if let Some(prev) = &self.prev {
+            let busy_cpu = cpu_avg.is_some_and(|l| l >= THROTTLE_LOAD_PERCENT as f64);
+            let busy_gpu = stats
+                .gpu_usage()
+                .is_some_and(|g| g >= THROTTLE_LOAD_PERCENT);
+            let drops = stats
+                .delta(prev)
+                .freq_transitions
+                .iter()
+                .filter(|t| t.to_mhz < t.from_mhz)
+                .filter(|t| {
+                    (t.name.starts_with("CPU") && busy_cpu) || (t.name == "GR3D" && busy_gpu)
+                })
+                .count();
+            self.throttle_events += drops as u64;
+        }
+        self.prev = Some(stats.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_window_and_energy() {
+        let mut mgr = SessionManager::new();
+        let mut energy = BTreeMap::from([("VDD_IN".to_string(), 1.0)]);
+        let id = mgr.start(Some("bench".into()), energy.clone(), 0);
+
+        let a = TegraStats::parse(
+            "11-30-2025 13:26:01 CPU [90%@1497,90%@1497] GR3D_FREQ 95%@918 tj@60C VDD_IN 9000mW/9000mW",
+        )
+        .unwrap();
+        let b = TegraStats::parse(
+            "11-30-2025 13:26:02 CPU [90%@1190,90%@1497] GR3D_FREQ 85%@624 tj@70C VDD_IN 9000mW/9000mW",
+        )
+        .unwrap();
+        mgr.record(&a, 0);
+        mgr.record(&b, 1);
+        energy.insert("VDD_IN".into(), 1.5);
+
+        let summary = mgr.stop(id, &energy).unwrap();
+        assert_eq!(summary.label.as_deref(), Some("bench"));
+        assert_eq!(summary.samples, 2);
+        assert_eq!(summary.cpu.max, 90.0);
+        assert_eq!(summary.gpu.avg, 90.0);
+        assert_eq!(summary.temps["tj"].max, 70.0);
+        assert_eq!(summary.energy_wh["VDD_IN"], 0.5);
+        // cap step-down + CPU0 drop + GR3D drop
+        assert_eq!(summary.throttle_events, 3);
+        assert!(mgr.stop(id, &energy).is_none());
+    }
+}
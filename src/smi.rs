@@ -0,0 +1,192 @@
+//! nvidia-smi style summary (`jscopectl smi`).
+
+use crate::export;
+use crate::hardware::JetsonHardware;
+use crate::parser::TegraStats;
+use crate::processes::GpuProcess;
+use crate::protocol::ControlInfo;
+
+/// Inner width of the boxes (79 columns with borders, like nvidia-smi).
+const WIDTH: usize = 77;
+
+/// Render the summary table; `timestamp` goes on the first line.
+pub fn render(
+    timestamp: &str,
+    stats: Option<&TegraStats>,
+    hw: &JetsonHardware,
+    controls: &[ControlInfo],
+    procs: &[GpuProcess],
+) -> String {
+    let control = |name: &str| {
+        controls
+            .iter()
+            .find(|c| c.name == name && c.supported)
+            .map(|c| c.value.clone())
+    };
+    let stats = stats.cloned().unwrap_or_default();
+    let na = || "N/A".to_string();
+
+    let name = if hw.model.is_empty() {
+        "Jetson GPU".to_string()
+    } else {
+        hw.model.trim_start_matches("NVIDIA ").to_string()
+    };
+    let temp = export::gpu_temp_c(&stats)
+        .map(|t| format!("{:.0}C", t))
+        .unwrap_or_else(na);
+    let watts = |mw: u32| format!("{:.1}W", mw as f64 / 1000.0);
+    let gpu_power = export::gpu_power_mw(&stats)
+        .map(watts)
+        .unwrap_or_else(na);
+    let total_power = crate::power_cap::input_power_mw(&stats)
+        .map(watts)
+        .unwrap_or_else(na);
+    let memory = stats
+        .ram
+        .as_ref()
+        .map(|r| {
+            format!(
+                "{:>6}MiB / {:>6}MiB",
+                r.used_bytes / (1024 * 1024),
+                r.total_bytes / (1024 * 1024)
+            )
+        })
+        .unwrap_or_else(na);
+    let gr3d = stats.engines.get("GR3D");
+    let util = gr3d
+        .and_then(|e| e.usage_percent)
+        .map(|u| format!("{}%", u))
+        .unwrap_or_else(na);
+    let clock = gr3d
+        .and_then(|e| e.freq_mhz)
+        .map(|f| format!("{}MHz", f))
+        .unwrap_or_else(na);
+    let fan = control("fan").unwrap_or_else(na);
+    let perf = control("nvpmodel").unwrap_or_else(na);
+
+    let border = format!("+{}+", "-".repeat(WIDTH));
+    let cells = |a: String, b: String, c: String| {
+        format!("|{}|{}|{}|", fit(&a, 31), fit(&b, 22), fit(&c, 22))
+    };
+    let sep = |ch: &str, edge: char| {
+        format!(
+            "{edge}{}+{}+{}{edge}",
+            ch.repeat(31),
+            ch.repeat(22),
+            ch.repeat(22)
+        )
+    };
+    let mut out = Vec::new();
+    out.push(timestamp.to_string());
+    out.push(border.clone());
+    out.push(format!(
+        "| {} |",
+        fit(
+            &format!(
+                "JetsonScope SMI        L4T: {:<12} JetPack: {}",
+                or_na(&hw.l4t_version),
+                or_na(&hw.jetpack_version)
+            ),
+            WIDTH - 2
+        )
+    ));
+    out.push(sep("-", '|'));
+    out.push(cells(
+        " GPU  Name".into(),
+        " Temp  Pwr:GPU/Total".into(),
+        " Memory-Usage".into(),
+    ));
+    out.push(cells(
+        " Fan  Perf".into(),
+        " Clock".into(),
+        " GPU-Util".into(),
+    ));
+    out.push(sep("=", '|'));
+    out.push(cells(
+        format!("   0  {}", fit(&name, 24)),
+        format!(" {:>4}  {} / {}", temp, gpu_power, total_power),
+        format!(" {}", memory),
+    ));
+    out.push(cells(
+        format!(" {:>4} {}", fan, perf),
+        format!(" {}", clock),
+        format!(" {:>8}", util),
+    ));
+    out.push(sep("-", '+'));
+    out.push(String::new());
+    out.push(border.clone());
+    out.push(format!("| {} |", fit("Processes:", WIDTH - 2)));
+    out.push(format!(
+        "| {:>6}   {} {:>12} |",
+        "PID",
+        fit("Process name", 53),
+        "GPU Memory"
+    ));
+    out.push(format!("|{}|", "=".repeat(WIDTH)));
+    if procs.is_empty() {
+        out.push(format!(
+            "| {} |",
+            fit(
+                "No running processes found (per-process GPU memory needs root)",
+                WIDTH - 2
+            )
+        ));
+    }
+    for p in procs {
+        out.push(format!(
+            "| {:>6}   {} {:>12} |",
+            p.pid,
+            fit(&p.name, 53),
+            format!("{}MiB", p.gpu_memory_kb / 1024)
+        ));
+    }
+    out.push(border);
+    out.join("\n")
+}
+
+fn or_na(s: &str) -> &str {
+    if s.is_empty() {
+        "N/A"
+    } else {
+        s
+    }
+}
+
+/// Pad or truncate to exactly `width` characters.
+fn fit(s: &str, width: usize) -> String {
+    let truncated: String = s.chars().take(width).collect();
+    format!("{:<width$}", truncated, width = width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_fixed_width_table() {
+        let stats = TegraStats::parse(
+            "RAM 2048/7620MB (lfb 10x4MB) CPU [10%@1497] GR3D_FREQ 95%@918 gpu@51.2C VDD_GPU_SOC 3200mW/3000mW VDD_IN 9000mW/9000mW",
+        )
+        .unwrap();
+        let hw = JetsonHardware {
+            model: "NVIDIA Jetson AGX Orin Developer Kit".into(),
+            ..Default::default()
+        };
+        let procs = vec![GpuProcess {
+            pid: 8126,
+            name: "python3".into(),
+            gpu_memory_kb: 524288,
+            gpu_percent: None,
+        }];
+        let out = render("now", Some(&stats), &hw, &[], &procs);
+        for line in out.lines().skip(1).filter(|l| !l.is_empty()) {
+            assert_eq!(line.chars().count(), WIDTH + 2, "{line}");
+        }
+        assert!(out.contains(" 51C  3.2W / 9.0W"));
+        assert!(out.contains("918MHz"));
+        assert!(out.contains("95%"));
+        assert!(out.contains("  2048MiB /   7620MiB"));
+        assert!(out.contains("python3"));
+        assert!(out.contains("512MiB"));
+    }
+}
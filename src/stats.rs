@@ -0,0 +1,307 @@
+//! Rolling min/max/mean/p95 of CPU, GPU, RAM, temperatures and rail power.
+//!
+//! `Aggregator` keeps every sample of the last ten minutes and folds older
+//! ones into one-minute buckets for up to 24 h, like `history`, so a summary
+//! over a long window reads a few thousand entries at most. Within the last
+//! ten minutes every statistic is exact; further back, windows start on a
+//! bucket boundary and p95 is taken from the one-minute means.
+
+use crate::history::HistoryPoint;
+use crate::parser::TegraStats;
+use crate::thermal_governor::valid_temp;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+
+/// Span kept per sample.
+const FINE_SPAN_MS: u64 = 10 * 60 * 1000;
+/// Cap on fine samples, in case the collector samples much faster than 1 Hz.
+const FINE_MAX_SAMPLES: usize = 6000;
+/// Bucket width of the coarse tier.
+const COARSE_STEP_MS: u64 = 60 * 1000;
+/// Longest window a summary covers.
+pub const MAX_WINDOW_SECS: u64 = 24 * 60 * 60;
+
+/// One series over a window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SeriesSummary {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub p95: f64,
+    pub samples: u64,
+}
+
+/// Every series over the last `window_secs` (for GetSummary and the TUI trend panels).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Summary {
+    pub window_secs: u64,
+    pub samples: u64,
+    /// Mean CPU core load (%)
+    pub cpu: Option<SeriesSummary>,
+    /// GR3D load (%)
+    pub gpu: Option<SeriesSummary>,
+    /// RAM used (%)
+    pub ram: Option<SeriesSummary>,
+    /// Hottest valid sensor (°C)
+    pub hottest: Option<SeriesSummary>,
+    /// Every valid sensor (°C)
+    pub temps: BTreeMap<String, SeriesSummary>,
+    /// Current draw per rail (mW)
+    pub power: BTreeMap<String, SeriesSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Series {
+    Cpu,
+    Gpu,
+    Ram,
+    Hottest,
+    Temp(String),
+    Power(String),
+}
+
+/// Min/max/sum of the samples folded into a bucket.
+#[derive(Debug, Clone, Copy)]
+struct Acc {
+    min: f64,
+    max: f64,
+    sum: f64,
+    count: u64,
+}
+
+impl Acc {
+    fn one(value: f64) -> Self {
+        Acc {
+            min: value,
+            max: value,
+            sum: value,
+            count: 1,
+        }
+    }
+
+    fn merge(&mut self, other: &Acc) {
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.sum += other.sum;
+        self.count += other.count;
+    }
+
+    fn mean(&self) -> f64 {
+        self.sum / self.count as f64
+    }
+}
+
+/// A series over a window: its totals and (mean, weight) points for p95.
+type Folded = (Acc, Vec<(f64, u64)>);
+
+#[derive(Debug, Clone)]
+struct Bucket {
+    /// Newest sample in the bucket, ms since the Unix epoch
+    unix_ms: u64,
+    samples: u64,
+    /// Index into `Aggregator::series`, and the values seen
+    values: Vec<(usize, Acc)>,
+}
+
+impl Bucket {
+    fn merge(&mut self, other: &Bucket) {
+        self.unix_ms = self.unix_ms.max(other.unix_ms);
+        self.samples += other.samples;
+        for (id, acc) in &other.values {
+            match self.values.iter_mut().find(|(i, _)| i == id) {
+                Some((_, mine)) => mine.merge(acc),
+                None => self.values.push((*id, *acc)),
+            }
+        }
+    }
+}
+
+/// Rolling summaries over any window up to 24 h.
+#[derive(Debug, Clone, Default)]
+pub struct Aggregator {
+    series: Vec<Series>,
+    fine: VecDeque<Bucket>,
+    coarse: VecDeque<Bucket>,
+}
+
+impl Aggregator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a sample taken at `unix_ms`.
+    pub fn push(&mut self, stats: &TegraStats, unix_ms: u64) {
+        let point = HistoryPoint::from_stats(stats, unix_ms);
+        let mut values = vec![
+            (self.id(Series::Cpu), Acc::one(point.cpu)),
+            (self.id(Series::Ram), Acc::one(point.ram)),
+            (self.id(Series::Hottest), Acc::one(point.temp)),
+        ];
+        if let Some(gpu) = stats.gpu_usage() {
+            values.push((self.id(Series::Gpu), Acc::one(gpu as f64)));
+        }
+        // -256C marks an offline sensor
+        for (name, temp) in stats.temps.iter().filter(|(_, t)| valid_temp(**t)) {
+            values.push((self.id(Series::Temp(name.clone())), Acc::one(*temp as f64)));
+        }
+        for (name, rail) in &stats.power {
+            values.push((self.id(Series::Power(name.clone())), Acc::one(rail.current_mw as f64)));
+        }
+        self.fine.push_back(Bucket {
+            unix_ms,
+            samples: 1,
+            values,
+        });
+
+        while self.fine.len() > FINE_MAX_SAMPLES
+            || self
+                .fine
+                .front()
+                .is_some_and(|b| unix_ms.saturating_sub(b.unix_ms) > FINE_SPAN_MS)
+        {
+            let Some(old) = self.fine.pop_front() else {
+                break;
+            };
+            match self.coarse.back_mut() {
+                Some(last) if last.unix_ms / COARSE_STEP_MS == old.unix_ms / COARSE_STEP_MS => {
+                    last.merge(&old)
+                }
+                _ => self.coarse.push_back(old),
+            }
+        }
+        let span_ms = MAX_WINDOW_SECS * 1000;
+        while self
+            .coarse
+            .front()
+            .is_some_and(|b| unix_ms.saturating_sub(b.unix_ms) > span_ms)
+        {
+            self.coarse.pop_front();
+        }
+    }
+
+    /// Summary of the samples from the last `window_secs` (at most 24 h) before `now_ms`.
+    pub fn summary(&self, window_secs: u64, now_ms: u64) -> Summary {
+        let window_secs = window_secs.min(MAX_WINDOW_SECS);
+        let window_ms = window_secs * 1000;
+        let buckets: Vec<&Bucket> = self
+            .coarse
+            .iter()
+            .chain(self.fine.iter())
+            .filter(|b| now_ms.saturating_sub(b.unix_ms) <= window_ms)
+            .collect();
+
+        let mut totals: Vec<Option<Folded>> = vec![None; self.series.len()];
+        for bucket in &buckets {
+            for (id, acc) in &bucket.values {
+                match &mut totals[*id] {
+                    Some((total, points)) => {
+                        total.merge(acc);
+                        points.push((acc.mean(), acc.count));
+                    }
+                    slot @ None => *slot = Some((*acc, vec![(acc.mean(), acc.count)])),
+                }
+            }
+        }
+
+        let mut summary = Summary {
+            window_secs,
+            samples: buckets.iter().map(|b| b.samples).sum(),
+            ..Default::default()
+        };
+        for (series, total) in self.series.iter().zip(totals) {
+            let Some((acc, mut points)) = total else {
+                continue;
+            };
+            let stat = SeriesSummary {
+                min: acc.min,
+                max: acc.max,
+                mean: acc.mean(),
+                p95: percentile(&mut points, 0.95),
+                samples: acc.count,
+            };
+            match series {
+                Series::Cpu => summary.cpu = Some(stat),
+                Series::Gpu => summary.gpu = Some(stat),
+                Series::Ram => summary.ram = Some(stat),
+                Series::Hottest => summary.hottest = Some(stat),
+                Series::Temp(name) => {
+                    summary.temps.insert(name.clone(), stat);
+                }
+                Series::Power(name) => {
+                    summary.power.insert(name.clone(), stat);
+                }
+            }
+        }
+        summary
+    }
+
+    fn id(&mut self, series: Series) -> usize {
+        match self.series.iter().position(|s| *s == series) {
+            Some(id) => id,
+            None => {
+                self.series.push(series);
+                self.series.len() - 1
+            }
+        }
+    }
+}
+
+/// Smallest value with at least `q` of the total weight at or below it.
+fn percentile(points: &mut [(f64, u64)], q: f64) -> f64 {
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let total: u64 = points.iter().map(|(_, w)| w).sum();
+    let target = (total as f64 * q).ceil() as u64;
+    let mut seen = 0;
+    for (value, weight) in points.iter() {
+        seen += weight;
+        if seen >= target {
+            return *value;
+        }
+    }
+    points.last().map_or(0.0, |(v, _)| *v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(gpu: u32, tj: f32, vdd_in: u32) -> TegraStats {
+        TegraStats::parse(&format!(
+            "RAM 2000/8000MB (lfb 10x4MB) CPU [10%@1190,30%@1190] GR3D_FREQ {gpu}%@918 tj@{tj}C gpu@-256C VDD_IN {vdd_in}mW/{vdd_in}mW"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn summarizes_recent_and_folded_samples() {
+        let mut agg = Aggregator::new();
+        let start = 1_700_000_000_000;
+        // 30 minutes at 1 Hz; GPU load is the second within the minute
+        for s in 0..1800u64 {
+            agg.push(&sample((s % 60) as u32, 40.0 + (s / 60) as f32, 5000), start + s * 1000);
+        }
+        let now = start + 1799 * 1000;
+
+        let minute = agg.summary(60, now);
+        assert_eq!(minute.samples, 61);
+        let gpu = minute.gpu.unwrap();
+        assert_eq!((gpu.min, gpu.max, gpu.samples), (0.0, 59.0, 61));
+        assert_eq!(gpu.p95, 57.0);
+        assert_eq!(minute.cpu.unwrap().mean, 20.0);
+        assert_eq!(minute.ram.unwrap().max, 25.0);
+        assert_eq!(minute.temps["tj"].max, 69.0);
+        assert!(!minute.temps.contains_key("gpu"));
+        assert_eq!(minute.power["VDD_IN"].mean, 5000.0);
+
+        // The first 20 minutes are one-minute buckets now; extremes stay exact
+        let all = agg.summary(3600, now);
+        assert_eq!(all.samples, 1800);
+        let tj = all.hottest.unwrap();
+        assert_eq!((tj.min, tj.max, tj.mean), (40.0, 69.0, 54.5));
+        assert_eq!(all.gpu.unwrap().max, 59.0);
+        assert!((all.gpu.unwrap().mean - 29.5).abs() < 1e-9);
+        assert_eq!(agg.summary(10 * MAX_WINDOW_SECS, now).window_secs, MAX_WINDOW_SECS);
+
+        assert_eq!(Aggregator::new().summary(60, now), Summary { window_secs: 60, ..Default::default() });
+    }
+}
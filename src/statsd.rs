@@ -0,0 +1,120 @@
+use crate::export::{self, KeyMetric};
+use std::time::Duration;
+
+/// Keep datagrams under a typical Ethernet MTU minus IP/UDP headers.
+const MAX_DATAGRAM: usize = 1432;
+
+/// StatsD exporter settings (JETSONSCOPE_STATSD_* environment variables).
+#[derive(Debug, Clone)]
+pub struct StatsdConfig {
+    /// StatsD "host:port" (UDP, usually 8125)
+    pub addr: String,
+    /// Metric name root; names are "<prefix>.<device>.<metric>"
+    pub prefix: String,
+    pub device: String,
+    pub interval: Duration,
+}
+
+impl StatsdConfig {
+    /// `JETSONSCOPE_STATSD_ADDR=host:8125` enables the exporter; `JETSONSCOPE_STATSD_PREFIX`
+    /// (default "jetsonscope") and `JETSONSCOPE_STATSD_INTERVAL` (secs, default 10) tune it.
+    pub fn from_env() -> Option<Self> {
+        let addr = std::env::var("JETSONSCOPE_STATSD_ADDR").ok()?;
+        let prefix = std::env::var("JETSONSCOPE_STATSD_PREFIX")
+            .ok()
+            .map(|p| p.trim_matches('.').to_string())
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| "jetsonscope".to_string());
+        let interval = std::env::var("JETSONSCOPE_STATSD_INTERVAL")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(10)
+            .max(1);
+        Some(StatsdConfig {
+            addr,
+            prefix,
+            device: export::sanitize_key(&export::device_name()),
+            interval: Duration::from_secs(interval),
+        })
+    }
+
+    /// Gauge lines "<name>:<value>|g" packed into datagrams of at most MAX_DATAGRAM bytes.
+    pub fn datagrams(&self, metrics: &[KeyMetric]) -> Vec<String> {
+        let mut out: Vec<String> = Vec::new();
+        let mut current = String::new();
+        for m in metrics {
+            let name = format!("{}.{}.{}", self.prefix, self.device, m.key);
+            // A leading sign means "adjust by" in StatsD, so a negative gauge (a cold
+            // sensor) is set to 0 first; both lines stay in one datagram
+            let line = if m.value < 0.0 {
+                format!("{name}:0|g\n{name}:{}|g", m.value)
+            } else {
+                format!("{name}:{}|g", m.value)
+            };
+            if !current.is_empty() && current.len() + 1 + line.len() > MAX_DATAGRAM {
+                out.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push('\n');
+            }
+            current.push_str(&line);
+        }
+        if !current.is_empty() {
+            out.push(current);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::MetricKind;
+
+    #[test]
+    fn packs_gauges_into_datagrams() {
+        let cfg = StatsdConfig {
+            addr: "localhost:8125".into(),
+            prefix: "edge".into(),
+            device: "orin_1".into(),
+            interval: Duration::from_secs(10),
+        };
+        let metric = |key: &str, value: f64| KeyMetric {
+            key: key.into(),
+            name: key.into(),
+            kind: MetricKind::Percent,
+            value,
+        };
+        let grams = cfg.datagrams(&[metric("cpu_load", 12.5), metric("gpu_load", 40.0)]);
+        assert_eq!(
+            grams,
+            vec!["edge.orin_1.cpu_load:12.5|g\nedge.orin_1.gpu_load:40|g"]
+        );
+
+        let many: Vec<KeyMetric> = (0..100).map(|i| metric(&format!("m{i}"), 1.0)).collect();
+        let grams = cfg.datagrams(&many);
+        assert!(grams.len() > 1);
+        assert!(grams.iter().all(|g| g.len() <= MAX_DATAGRAM));
+        assert_eq!(grams.iter().map(|g| g.lines().count()).sum::<usize>(), 100);
+    }
+
+    #[test]
+    fn negative_gauges_are_reset_first() {
+        let cfg = StatsdConfig {
+            addr: "localhost:8125".into(),
+            prefix: "edge".into(),
+            device: "orin_1".into(),
+            interval: Duration::from_secs(10),
+        };
+        let grams = cfg.datagrams(&[KeyMetric {
+            key: "temp_board".into(),
+            name: "temp_board".into(),
+            kind: MetricKind::Temperature,
+            value: -5.0,
+        }]);
+        assert_eq!(
+            grams,
+            vec!["edge.orin_1.temp_board:0|g\nedge.orin_1.temp_board:-5|g"]
+        );
+    }
+}
@@ -0,0 +1,347 @@
+//! Load generators and pass/fail evaluation for `jscopectl stress`.
+//!
+//! CPU load comes from spin threads that run a duty cycle per 100 ms period;
+//! GPU load is an external command (the "GPU hook", e.g. a CUDA burn binary),
+//! since the tool itself links no GPU runtime. The daemon records the run as a
+//! profiling session, whose summary is judged here.
+
+use crate::session::SessionSummary;
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+const DUTY_PERIOD: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StressConfig {
+    /// Busy share of each spin thread (0-100; 0 disables CPU load)
+    pub cpu_percent: u8,
+    pub threads: usize,
+    /// Shell command producing GPU load, when `--gpu` is given
+    pub gpu_cmd: Option<String>,
+    pub duration: Duration,
+    /// Fail the run when any sensor peaks above this (°C)
+    pub max_temp: Option<f64>,
+}
+
+impl StressConfig {
+    /// `--cpu 100% --threads N --gpu [--gpu-cmd CMD] --duration 10m --max-temp 85`.
+    ///
+    /// Without `--gpu-cmd`, `--gpu` runs JETSONSCOPE_STRESS_GPU_CMD.
+    pub fn parse_args(args: &[String]) -> Result<Self> {
+        let mut cfg = StressConfig {
+            cpu_percent: 100,
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+            gpu_cmd: None,
+            duration: Duration::from_secs(600),
+            max_temp: None,
+        };
+        let mut gpu = false;
+        let mut it = args.iter();
+        while let Some(arg) = it.next() {
+            let mut value = |flag: &str| {
+                it.next()
+                    .cloned()
+                    .with_context(|| format!("{flag} needs a value"))
+            };
+            match arg.as_str() {
+                "--cpu" => {
+                    let v = value("--cpu")?;
+                    cfg.cpu_percent = v
+                        .trim_end_matches('%')
+                        .parse()
+                        .ok()
+                        .filter(|p| *p <= 100)
+                        .with_context(|| format!("invalid --cpu '{v}': expected 0-100%"))?;
+                }
+                "--threads" => {
+                    let v = value("--threads")?;
+                    cfg.threads = v
+                        .parse()
+                        .ok()
+                        .filter(|n| *n > 0)
+                        .with_context(|| format!("invalid --threads '{v}'"))?;
+                }
+                "--gpu" => gpu = true,
+                "--gpu-cmd" => {
+                    gpu = true;
+                    cfg.gpu_cmd = Some(value("--gpu-cmd")?);
+                }
+                "--duration" => cfg.duration = parse_duration(&value("--duration")?)?,
+                "--max-temp" => {
+                    let v = value("--max-temp")?;
+                    cfg.max_temp = Some(
+                        v.trim_end_matches('C')
+                            .parse()
+                            .with_context(|| format!("invalid --max-temp '{v}'"))?,
+                    );
+                }
+                other => bail!("unknown stress option '{other}'"),
+            }
+        }
+        if gpu && cfg.gpu_cmd.is_none() {
+            cfg.gpu_cmd = std::env::var("JETSONSCOPE_STRESS_GPU_CMD").ok();
+            if cfg.gpu_cmd.is_none() {
+                bail!("--gpu needs a workload: pass --gpu-cmd or set JETSONSCOPE_STRESS_GPU_CMD");
+            }
+        }
+        if cfg.cpu_percent == 0 && cfg.gpu_cmd.is_none() {
+            bail!("nothing to run: --cpu 0% without --gpu");
+        }
+        Ok(cfg)
+    }
+
+    pub fn label(&self) -> String {
+        let mut label = format!("stress cpu {}%x{}", self.cpu_percent, self.threads);
+        if self.gpu_cmd.is_some() {
+            label.push_str(" +gpu");
+        }
+        label
+    }
+}
+
+/// "90", "90s", "10m" or "1h".
+pub fn parse_duration(s: &str) -> Result<Duration> {
+    let s = s.trim();
+    let (num, unit) = match s.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => s.split_at(i),
+        None => (s, "s"),
+    };
+    let n: u64 = num
+        .parse()
+        .with_context(|| format!("invalid duration '{s}'"))?;
+    let secs = match unit {
+        "s" => n,
+        "m" => n * 60,
+        "h" => n * 3600,
+        _ => bail!("invalid duration '{s}': use s, m or h"),
+    };
+    if secs == 0 {
+        bail!("duration must be positive");
+    }
+    Ok(Duration::from_secs(secs))
+}
+
+/// Running load generators; dropped generators keep running, call `stop`.
+pub struct Workload {
+    stop: Arc<AtomicBool>,
+    threads: Vec<JoinHandle<()>>,
+    gpu: Option<Child>,
+    gpu_exit: Option<String>,
+}
+
+impl Workload {
+    pub fn start(cfg: &StressConfig) -> Result<Self> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let threads = if cfg.cpu_percent == 0 {
+            Vec::new()
+        } else {
+            (0..cfg.threads)
+                .map(|_| {
+                    let stop = stop.clone();
+                    let percent = cfg.cpu_percent;
+                    thread::spawn(move || spin(percent, &stop))
+                })
+                .collect()
+        };
+        let gpu = match &cfg.gpu_cmd {
+            Some(cmd) => Some(
+                Command::new("sh")
+                    .arg("-c")
+                    .arg(cmd)
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .spawn()
+                    .with_context(|| format!("starting GPU workload '{cmd}'"))?,
+            ),
+            None => None,
+        };
+        Ok(Workload {
+            stop,
+            threads,
+            gpu,
+            gpu_exit: None,
+        })
+    }
+
+    /// Why the GPU workload ended, if it already did.
+    pub fn gpu_exit(&mut self) -> Option<&str> {
+        if self.gpu_exit.is_none() {
+            if let Some(Ok(Some(status))) = self.gpu.as_mut().map(Child::try_wait) {
+                self.gpu_exit = Some(format!("GPU workload exited early ({status})"));
+            }
+        }
+        self.gpu_exit.as_deref()
+    }
+
+    /// Stop every generator; returns the GPU workload's early exit, if any.
+    pub fn stop(mut self) -> Option<String> {
+        self.gpu_exit();
+        self.stop.store(true, Ordering::Relaxed);
+        for t in self.threads.drain(..) {
+            let _ = t.join();
+        }
+        if let Some(mut child) = self.gpu.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        self.gpu_exit
+    }
+}
+
+fn spin(percent: u8, stop: &AtomicBool) {
+    let busy = DUTY_PERIOD * percent as u32 / 100;
+    while !stop.load(Ordering::Relaxed) {
+        let start = Instant::now();
+        while start.elapsed() < busy {
+            std::hint::spin_loop();
+        }
+        if let Some(idle) = DUTY_PERIOD.checked_sub(start.elapsed()) {
+            thread::sleep(idle);
+        }
+    }
+}
+
+/// Outcome of a stress run.
+#[derive(Debug, Clone, Serialize)]
+pub struct StressReport {
+    pub label: String,
+    pub duration_secs: f64,
+    /// Hottest sensor and its peak (°C)
+    pub peak_temp: Option<(String, f64)>,
+    pub throttle_events: u64,
+    pub cpu_avg: f64,
+    pub gpu_avg: f64,
+    /// Energy over the run (Wh): the VDD_IN total rail, or all rails summed without one
+    pub energy_wh: f64,
+    pub stable: bool,
+    /// Reasons the run is not stable
+    pub failures: Vec<String>,
+}
+
+/// Judge a run from its session summary; `missed_polls` counts progress polls the daemon failed.
+pub fn evaluate(
+    cfg: &StressConfig,
+    summary: &SessionSummary,
+    gpu_exit: Option<String>,
+    missed_polls: u32,
+) -> StressReport {
+    let peak_temp = summary
+        .temps
+        .iter()
+        .map(|(name, s)| (name.clone(), s.max))
+        .max_by(|a, b| a.1.total_cmp(&b.1));
+    let mut failures = Vec::new();
+    if summary.samples == 0 {
+        failures.push("daemon recorded no samples".to_string());
+    }
+    if missed_polls > 0 {
+        failures.push(format!("daemon did not answer {missed_polls} polls"));
+    }
+    failures.extend(gpu_exit);
+    if let (Some(limit), Some((name, peak))) = (cfg.max_temp, &peak_temp) {
+        if *peak > limit {
+            failures.push(format!("{name} reached {peak:.1}C (limit {limit:.1}C)"));
+        }
+    }
+    StressReport {
+        label: cfg.label(),
+        duration_secs: summary.duration_secs,
+        peak_temp,
+        throttle_events: summary.throttle_events,
+        cpu_avg: summary.cpu.avg,
+        gpu_avg: summary.gpu.avg,
+        energy_wh: summary
+            .energy_wh
+            .get("VDD_IN")
+            .copied()
+            .unwrap_or_else(|| summary.energy_wh.values().sum()),
+        stable: failures.is_empty(),
+        failures,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::SeriesSummary;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parses_flags_and_durations() {
+        let cfg = StressConfig::parse_args(&args(
+            "--cpu 75% --threads 2 --gpu-cmd burn --duration 10m --max-temp 85C",
+        ))
+        .unwrap();
+        assert_eq!(cfg.cpu_percent, 75);
+        assert_eq!(cfg.threads, 2);
+        assert_eq!(cfg.gpu_cmd.as_deref(), Some("burn"));
+        assert_eq!(cfg.duration, Duration::from_secs(600));
+        assert_eq!(cfg.max_temp, Some(85.0));
+        assert_eq!(cfg.label(), "stress cpu 75%x2 +gpu");
+
+        assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("1h").unwrap(), Duration::from_secs(3600));
+        assert!(parse_duration("10d").is_err());
+        assert!(StressConfig::parse_args(&args("--cpu 150%")).is_err());
+        assert!(StressConfig::parse_args(&args("--cpu 0")).is_err());
+        assert!(StressConfig::parse_args(&args("--fast")).is_err());
+    }
+
+    #[test]
+    fn flags_hot_or_unstable_runs() {
+        let cfg = StressConfig::parse_args(&args("--threads 1 --max-temp 80")).unwrap();
+        let mut summary = SessionSummary {
+            samples: 60,
+            throttle_events: 2,
+            gpu: SeriesSummary { avg: 0.0, max: 0.0 },
+            ..Default::default()
+        };
+        summary.temps.insert(
+            "cpu".into(),
+            SeriesSummary {
+                avg: 60.0,
+                max: 72.0,
+            },
+        );
+        summary.temps.insert(
+            "tj".into(),
+            SeriesSummary {
+                avg: 70.0,
+                max: 79.5,
+            },
+        );
+        summary.energy_wh.insert("VDD_IN".into(), 0.25);
+
+        let report = evaluate(&cfg, &summary, None, 0);
+        assert!(report.stable, "{:?}", report.failures);
+        assert_eq!(report.peak_temp, Some(("tj".to_string(), 79.5)));
+        assert_eq!(report.throttle_events, 2);
+
+        summary.temps.insert(
+            "gpu".into(),
+            SeriesSummary {
+                avg: 70.0,
+                max: 84.0,
+            },
+        );
+        let report = evaluate(&cfg, &summary, Some("GPU workload exited early".into()), 1);
+        assert!(!report.stable);
+        assert_eq!(report.failures.len(), 3);
+    }
+
+    #[test]
+    fn workload_stops_spin_threads() {
+        let cfg = StressConfig::parse_args(&args("--cpu 10% --threads 2")).unwrap();
+        let workload = Workload::start(&cfg).unwrap();
+        thread::sleep(Duration::from_millis(150));
+        assert_eq!(workload.stop(), None);
+    }
+}
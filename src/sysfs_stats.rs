@@ -0,0 +1,429 @@
+//! Stats read straight from sysfs and procfs, without launching tegrastats.
+//!
+//! CPU load comes from `/proc/stat` deltas, clocks from cpufreq and devfreq,
+//! temperatures from the thermal zones and power from the INA3221 monitors
+//! (hwmon, or the iio driver on older L4T). Each sample is rendered as a
+//! tegrastats line and parsed like one, so history, recordings and exports
+//! cannot tell the two sources apart.
+
+use crate::hardware::devfreq_domain;
+use chrono::Local;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Busy and total jiffies of one CPU from `/proc/stat`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CpuTimes {
+    busy: u64,
+    total: u64,
+}
+
+pub struct SysfsSampler {
+    sys: PathBuf,
+    proc_root: PathBuf,
+    prev_cpu: HashMap<usize, CpuTimes>,
+    /// Running sum and count of each rail's readings, for the average column
+    rail_avg: HashMap<String, (u64, u64)>,
+}
+
+impl Default for SysfsSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SysfsSampler {
+    pub fn new() -> Self {
+        Self::with_roots(Path::new("/sys"), Path::new("/proc"))
+    }
+
+    /// Sampler over another tree laid out like `/sys` and `/proc`.
+    pub fn with_roots(sys: &Path, proc_root: &Path) -> Self {
+        SysfsSampler {
+            sys: sys.to_path_buf(),
+            proc_root: proc_root.to_path_buf(),
+            prev_cpu: HashMap::new(),
+            rail_avg: HashMap::new(),
+        }
+    }
+
+    /// Whether there is anything to read: cpufreq or a thermal zone.
+    pub fn available(&self) -> bool {
+        self.sys.join("devices/system/cpu/cpu0/cpufreq").exists()
+            || self.sys.join("class/thermal/thermal_zone0").exists()
+    }
+
+    /// One tegrastats-formatted line; CPU loads are since the previous call (since boot on the first).
+    pub fn sample_line(&mut self) -> String {
+        let mut fields = vec![Local::now().format("%m-%d-%Y %H:%M:%S").to_string()];
+        fields.extend(self.memory());
+        fields.push(self.cpus());
+        fields.extend(self.engines());
+        fields.extend(self.temps());
+        fields.extend(self.rails());
+        fields.join(" ")
+    }
+
+    fn memory(&self) -> Vec<String> {
+        let Some(info) = read(&self.proc_root.join("meminfo")) else {
+            return Vec::new();
+        };
+        let kb = |key: &str| {
+            info.lines()
+                .find_map(|l| l.strip_prefix(key)?.strip_prefix(':'))
+                .and_then(|v| v.split_whitespace().next()?.parse::<u64>().ok())
+                .unwrap_or(0)
+        };
+        let mb = |kb: u64| kb / 1024;
+        let (count, size) = self.largest_free_block();
+        let total = kb("MemTotal");
+        let mut out = vec![format!(
+            "RAM {}/{}MB (lfb {}x{})",
+            mb(total.saturating_sub(kb("MemAvailable"))),
+            mb(total),
+            count,
+            size
+        )];
+        let swap = kb("SwapTotal");
+        if swap > 0 {
+            out.push(format!(
+                "SWAP {}/{}MB (cached {}MB)",
+                mb(swap.saturating_sub(kb("SwapFree"))),
+                mb(swap),
+                mb(kb("SwapCached"))
+            ));
+        }
+        out
+    }
+
+    /// Free blocks of the highest order with any, from `/proc/buddyinfo` (4 kB pages).
+    fn largest_free_block(&self) -> (u64, String) {
+        let mut per_order: Vec<u64> = Vec::new();
+        for line in read(&self.proc_root.join("buddyinfo"))
+            .unwrap_or_default()
+            .lines()
+        {
+            // "Node 0, zone   Normal   12  8  3 ..."
+            let counts = line
+                .split_whitespace()
+                .skip(4)
+                .filter_map(|c| c.parse::<u64>().ok());
+            for (order, count) in counts.enumerate() {
+                if per_order.len() <= order {
+                    per_order.resize(order + 1, 0);
+                }
+                per_order[order] += count;
+            }
+        }
+        match per_order.iter().rposition(|c| *c > 0) {
+            Some(order) => {
+                let kb = 4u64 << order;
+                let size = if kb >= 1024 {
+                    format!("{}MB", kb / 1024)
+                } else {
+                    format!("{}kB", kb)
+                };
+                (per_order[order], size)
+            }
+            None => (0, "4MB".to_string()),
+        }
+    }
+
+    fn cpus(&mut self) -> String {
+        let times = read(&self.proc_root.join("stat"))
+            .map(|s| parse_proc_stat(&s))
+            .unwrap_or_default();
+        let cpu_dir = self.sys.join("devices/system/cpu");
+        let mut cores: Vec<usize> = fs::read_dir(&cpu_dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|e| e.file_name().to_str()?.strip_prefix("cpu")?.parse().ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        if cores.is_empty() {
+            cores = times.keys().copied().collect();
+        }
+        cores.sort_unstable();
+
+        let mut out = Vec::with_capacity(cores.len());
+        for n in cores {
+            let dir = cpu_dir.join(format!("cpu{n}"));
+            let online = read(&dir.join("online")).map_or(true,|s| s.trim() != "0");
+            let Some(now) = times.get(&n).copied().filter(|_| online) else {
+                self.prev_cpu.remove(&n);
+                out.push("off".to_string());
+                continue;
+            };
+            let prev = self
+                .prev_cpu
+                .insert(n, now)
+                .unwrap_or(CpuTimes { busy: 0, total: 0 });
+            let total = now.total.saturating_sub(prev.total);
+            let load = (now.busy.saturating_sub(prev.busy) * 100)
+                .checked_div(total)
+                .unwrap_or(0);
+            match read_u64(&dir.join("cpufreq/scaling_cur_freq")) {
+                Some(khz) => out.push(format!("{}%@{}", load, khz / 1000)),
+                None => out.push(format!("{}%", load)),
+            }
+        }
+        format!("CPU [{}]", out.join(","))
+    }
+
+    /// devfreq clocks; the GPU also gets its load (`device/load`, per mille).
+    fn engines(&self) -> Vec<String> {
+        let mut out = Vec::new();
+        for dir in sorted_entries(&self.sys.join("class/devfreq")) {
+            let Some(device) = dir.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let Some(mhz) = read_u64(&dir.join("cur_freq")).map(|hz| hz / 1_000_000) else {
+                continue;
+            };
+            let domain = sanitize(&devfreq_domain(device));
+            if domain == "GPU" {
+                let load = read_u64(&dir.join("device/load")).unwrap_or(0) / 10;
+                out.push(format!("GR3D_FREQ {}%@{}", load.min(100), mhz));
+            } else {
+                out.push(format!("{}_FREQ {}", domain, mhz));
+            }
+        }
+        out
+    }
+
+    /// "cpu-thermal" zones become `cpu@45.5C`, as tegrastats names them.
+    fn temps(&self) -> Vec<String> {
+        sorted_entries(&self.sys.join("class/thermal"))
+            .into_iter()
+            .filter(|dir| {
+                dir.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with("thermal_zone"))
+            })
+            .filter_map(|dir| {
+                let kind = read(&dir.join("type"))?;
+                let kind = kind.trim();
+                let name = kind
+                    .strip_suffix("-thermal")
+                    .or_else(|| kind.strip_suffix("-therm"))
+                    .unwrap_or(kind);
+                let milli: i64 = read(&dir.join("temp"))?.trim().parse().ok()?;
+                Some(format!("{}@{}C", sanitize(name), milli as f64 / 1000.0))
+            })
+            .collect()
+    }
+
+    fn rails(&mut self) -> Vec<String> {
+        let mut readings = read_ina3221_hwmon(&self.sys.join("class/hwmon"));
+        readings.extend(read_ina3221_iio(&self.sys.join("bus/i2c/drivers/ina3221x")));
+        readings
+            .into_iter()
+            .map(|(name, mw)| {
+                let (sum, count) = self.rail_avg.entry(name.clone()).or_insert((0, 0));
+                *sum += mw;
+                *count += 1;
+                format!("{} {}mW/{}mW", name, mw, *sum / *count)
+            })
+            .collect()
+    }
+}
+
+/// Per-CPU busy/total jiffies from `/proc/stat` (the aggregate `cpu` line is skipped).
+fn parse_proc_stat(content: &str) -> HashMap<usize, CpuTimes> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let n: usize = parts.next()?.strip_prefix("cpu")?.parse().ok()?;
+            let values: Vec<u64> = parts.filter_map(|v| v.parse().ok()).collect();
+            // user nice system idle iowait irq softirq steal ...
+            let idle = values.get(3)? + values.get(4).unwrap_or(&0);
+            let total: u64 = values.iter().take(8).sum();
+            Some((
+                n,
+                CpuTimes {
+                    busy: total.saturating_sub(idle),
+                    total,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// (label, mW) of every channel of `ina3221` hwmon devices under `base`.
+fn read_ina3221_hwmon(base: &Path) -> Vec<(String, u64)> {
+    let mut out = Vec::new();
+    for dir in sorted_entries(base) {
+        if read(&dir.join("name")).as_deref().map(str::trim) != Some("ina3221") {
+            continue;
+        }
+        for ch in 1..=3 {
+            let Some(label) = read(&dir.join(format!("in{ch}_label"))) else {
+                continue;
+            };
+            let (Some(mv), Some(ma)) = (
+                read_u64(&dir.join(format!("in{ch}_input"))),
+                read_u64(&dir.join(format!("curr{ch}_input"))),
+            ) else {
+                continue;
+            };
+            out.push((sanitize(label.trim()), mv * ma / 1000));
+        }
+    }
+    out
+}
+
+/// (rail, mW) from the iio `ina3221x` driver (`rail_name_N`, `in_power N_input`).
+fn read_ina3221_iio(base: &Path) -> Vec<(String, u64)> {
+    let mut out = Vec::new();
+    for device in sorted_entries(base) {
+        for dir in sorted_entries(&device) {
+            let is_iio = dir
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("iio:device"));
+            if !is_iio {
+                continue;
+            }
+            for ch in 0..3 {
+                let (Some(name), Some(mw)) = (
+                    read(&dir.join(format!("rail_name_{ch}"))),
+                    read_u64(&dir.join(format!("in_power{ch}_input"))),
+                ) else {
+                    continue;
+                };
+                out.push((sanitize(name.trim()), mw));
+            }
+        }
+    }
+    out
+}
+
+/// Names end up as `\w+` tokens in the line.
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn sorted_entries(dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+    paths.sort();
+    paths
+}
+
+fn read(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok()
+}
+
+fn read_u64(path: &Path) -> Option<u64> {
+    read(path)?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::TegraStats;
+
+    fn write(base: &Path, rel: &str, content: &str) {
+        let path = base.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn renders_a_line_the_parser_understands() {
+        let root = std::env::temp_dir().join(format!("jscope-sysfs-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let (sys, proc_root) = (root.join("sys"), root.join("proc"));
+        write(
+            &proc_root,
+            "meminfo",
+            "MemTotal: 8000000 kB\nMemFree: 100 kB\nMemAvailable: 6000000 kB\nSwapTotal: 4096000 kB\nSwapFree: 4096000 kB\nSwapCached: 0 kB\n",
+        );
+        write(
+            &proc_root,
+            "buddyinfo",
+            "Node 0, zone Normal 5 4 3 2 1 0 0 0 0 0 7\n",
+        );
+        write(
+            &proc_root,
+            "stat",
+            "cpu 0 0 0 0\ncpu0 100 0 100 800 0 0 0 0\ncpu1 0 0 0 1000 0 0 0 0\n",
+        );
+        write(
+            &sys,
+            "devices/system/cpu/cpu0/cpufreq/scaling_cur_freq",
+            "1190400\n",
+        );
+        write(&sys, "devices/system/cpu/cpu1/online", "0\n");
+        write(&sys, "class/devfreq/17000000.gpu/cur_freq", "918000000\n");
+        write(&sys, "class/devfreq/17000000.gpu/device/load", "456\n");
+        write(&sys, "class/devfreq/15340000.vic/cur_freq", "115200000\n");
+        write(&sys, "class/thermal/thermal_zone0/type", "cpu-thermal\n");
+        write(&sys, "class/thermal/thermal_zone0/temp", "45500\n");
+        write(&sys, "class/thermal/thermal_zone1/type", "tj-thermal\n");
+        write(&sys, "class/thermal/thermal_zone1/temp", "47250\n");
+        write(&sys, "class/hwmon/hwmon1/name", "ina3221\n");
+        write(&sys, "class/hwmon/hwmon1/in1_label", "VDD_IN\n");
+        write(&sys, "class/hwmon/hwmon1/in1_input", "5000\n");
+        write(&sys, "class/hwmon/hwmon1/curr1_input", "1200\n");
+
+        let mut sampler = SysfsSampler::with_roots(&sys, &proc_root);
+        assert!(sampler.available());
+        sampler.sample_line();
+        write(
+            &proc_root,
+            "stat",
+            "cpu 0 0 0 0\ncpu0 150 0 150 900 0 0 0 0\ncpu1 0 0 0 1000 0 0 0 0\n",
+        );
+        write(&sys, "class/hwmon/hwmon1/curr1_input", "1600\n");
+        let stats = TegraStats::parse(&sampler.sample_line()).unwrap();
+        let _ = fs::remove_dir_all(&root);
+
+        assert!(stats.timestamp.is_some());
+        let ram = stats.ram.as_ref().unwrap();
+        assert_eq!(ram.total_bytes, 7812 * 1024 * 1024);
+        assert_eq!(ram.used_bytes, 1953 * 1024 * 1024);
+        assert_eq!(stats.swap.as_ref().unwrap().used_bytes, 0);
+        assert_eq!(stats.cpus.len(), 2);
+        assert_eq!(stats.cpus[0].load_percent, Some(50));
+        assert_eq!(stats.cpus[0].freq_mhz, Some(1190));
+        assert_eq!(stats.cpus[1].load_percent, None);
+        assert_eq!(stats.gpu_usage(), Some(45));
+        assert_eq!(stats.engines["GR3D"].freq_mhz, Some(918));
+        assert_eq!(stats.engines["VIC"].freq_mhz, Some(115));
+        assert_eq!(stats.temps["cpu"], 45.5);
+        assert_eq!(stats.temps["tj"], 47.25);
+        assert_eq!(stats.power["VDD_IN"].current_mw, 8000);
+        assert_eq!(stats.power["VDD_IN"].average_mw, 7000);
+    }
+
+    #[test]
+    fn reads_iio_rails_and_proc_stat() {
+        let root = std::env::temp_dir().join(format!("jscope-ina-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let dev = "1-0040/iio:device0";
+        write(&root, &format!("{dev}/rail_name_0"), "VDD_SYS_GPU\n");
+        write(&root, &format!("{dev}/in_power0_input"), "1520\n");
+        write(&root, &format!("{dev}/rail_name_1"), "VDD_SYS_SOC\n");
+        let rails = read_ina3221_iio(&root);
+        let _ = fs::remove_dir_all(&root);
+        assert_eq!(rails, vec![("VDD_SYS_GPU".to_string(), 1520)]);
+
+        let times = parse_proc_stat("cpu 1 2 3 4\ncpu3 10 0 10 70 10 0 0 0 5 5\nintr 1 2\n");
+        assert_eq!(times.len(), 1);
+        assert_eq!(
+            times[&3],
+            CpuTimes {
+                busy: 20,
+                total: 100
+            }
+        );
+    }
+}
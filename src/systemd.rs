@@ -0,0 +1,139 @@
+//! systemd integration for jscoped: `sd_notify` readiness and watchdog
+//! messages, socket activation, and the unit files behind `--install-service`.
+//!
+//! Everything is a no-op outside systemd: without `NOTIFY_SOCKET` nothing is
+//! sent, and without `LISTEN_FDS` the daemon binds its socket itself.
+
+use anyhow::{Context, Result};
+use std::os::fd::{FromRawFd, IntoRawFd};
+use std::os::unix::net::{UnixDatagram, UnixListener};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// First descriptor passed by socket activation (`SD_LISTEN_FDS_START`).
+const LISTEN_FDS_START: i32 = 3;
+
+pub const SERVICE_UNIT: &str = include_str!("../install/jscoped.service");
+pub const SOCKET_UNIT: &str = include_str!("../install/jscoped.socket");
+pub const DEFAULT_UNIT_DIR: &str = "/etc/systemd/system";
+
+/// Send a state string ("READY=1", "WATCHDOG=1", ...) to `NOTIFY_SOCKET`.
+///
+/// Returns false when not started by systemd with notify support.
+pub fn notify(state: &str) -> std::io::Result<bool> {
+    let Some(path) = std::env::var_os("NOTIFY_SOCKET") else {
+        return Ok(false);
+    };
+    let sock = UnixDatagram::unbound()?;
+    let path = path.to_string_lossy();
+    match path.strip_prefix('@') {
+        // Abstract namespace socket
+        Some(name) => {
+            use std::os::linux::net::SocketAddrExt;
+            let addr = std::os::unix::net::SocketAddr::from_abstract_name(name)?;
+            sock.send_to_addr(state.as_bytes(), &addr)?;
+        }
+        None => {
+            sock.send_to(state.as_bytes(), path.as_ref())?;
+        }
+    }
+    Ok(true)
+}
+
+/// How often to send "WATCHDOG=1": half of `WATCHDOG_USEC`, if the watchdog
+/// is enabled for this process.
+pub fn watchdog_interval() -> Option<Duration> {
+    if let Ok(pid) = std::env::var("WATCHDOG_PID") {
+        if pid.trim().parse::<u32>().ok()? != std::process::id() {
+            return None;
+        }
+    }
+    let usec: u64 = std::env::var("WATCHDOG_USEC").ok()?.trim().parse().ok()?;
+    (usec > 0).then(|| Duration::from_micros(usec / 2))
+}
+
+/// The listening socket passed by a `.socket` unit, if this process got one.
+///
+/// Clears `LISTEN_PID`/`LISTEN_FDS` so children don't inherit them; call
+/// before starting threads.
+pub fn take_listener() -> Option<UnixListener> {
+    let pid = std::env::var("LISTEN_PID").ok()?;
+    let fds: i32 = std::env::var("LISTEN_FDS").ok()?.trim().parse().ok()?;
+    std::env::remove_var("LISTEN_PID");
+    std::env::remove_var("LISTEN_FDS");
+    std::env::remove_var("LISTEN_FDNAMES");
+    if pid.trim().parse::<u32>().ok()? != std::process::id() || fds < 1 {
+        return None;
+    }
+    // SAFETY: systemd hands this descriptor to us open and unowned
+    let listener = unsafe { UnixListener::from_raw_fd(LISTEN_FDS_START) };
+    if listener.local_addr().is_err() {
+        // Not a Unix socket (e.g. ListenStream= set to a TCP port): leave the fd alone
+        let _ = listener.into_raw_fd();
+        return None;
+    }
+    // The inherited fd lacks close-on-exec; a std dup has it, so the stats
+    // command doesn't inherit the listening socket
+    listener.try_clone().ok()
+}
+
+/// A shipped unit with `ExecStart` pointing at `exe` and the socket path
+/// replaced by `socket`.
+pub fn render_unit(template: &str, exe: &Path, socket: &str) -> String {
+    let mut out = String::with_capacity(template.len());
+    for line in template.lines() {
+        if line.starts_with("ExecStart=") {
+            out.push_str(&format!("ExecStart={}", exe.display()));
+        } else if line.starts_with("ListenStream=") {
+            out.push_str(&format!("ListenStream={socket}"));
+        } else if line.starts_with("Environment=\"JETSONSCOPE_SOCKET_PATH=") {
+            out.push_str(&format!("Environment=\"JETSONSCOPE_SOCKET_PATH={socket}\""));
+        } else {
+            out.push_str(line);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Write jscoped.service and jscoped.socket into `dir`; returns their paths.
+pub fn install_units(dir: &Path, exe: &Path, socket: &str) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    for (name, template) in [
+        ("jscoped.service", SERVICE_UNIT),
+        ("jscoped.socket", SOCKET_UNIT),
+    ] {
+        let path = dir.join(name);
+        std::fs::write(&path, render_unit(template, exe, socket))
+            .with_context(|| format!("writing {}", path.display()))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_units_and_notifies() {
+        let service = render_unit(SERVICE_UNIT, Path::new("/opt/bin/jscoped"), "/run/js.sock");
+        assert!(service.contains("\nExecStart=/opt/bin/jscoped\n"));
+        assert!(service.contains("Environment=\"JETSONSCOPE_SOCKET_PATH=/run/js.sock\""));
+        assert!(service.contains("Type=notify"));
+        let socket = render_unit(SOCKET_UNIT, Path::new("/opt/bin/jscoped"), "/run/js.sock");
+        assert!(socket.contains("\nListenStream=/run/js.sock\n"));
+
+        let path = std::env::temp_dir().join(format!("jscope-notify-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let server = UnixDatagram::bind(&path).unwrap();
+        std::env::set_var("NOTIFY_SOCKET", &path);
+        assert!(notify("READY=1").unwrap());
+        std::env::remove_var("NOTIFY_SOCKET");
+        let mut buf = [0u8; 64];
+        let n = server.recv(&mut buf).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(&buf[..n], b"READY=1");
+        assert!(!notify("READY=1").unwrap());
+    }
+}
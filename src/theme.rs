@@ -0,0 +1,184 @@
+//! TUI color themes.
+//!
+//! `jscope --theme` picks one, `JETSONSCOPE_THEME=neon|plain|high-contrast|color-blind`
+//! sets the default. Only `neon` animates (and only without `--no-animations`);
+//! the others draw fixed colors, which also keeps redraws small over SSH.
+
+use crate::thresholds::Level;
+use ratatui::style::Color;
+
+/// Panel colors (`jscope --theme`, JETSONSCOPE_THEME).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Theme {
+    /// Rainbow borders and pulsing gauges
+    #[default]
+    Neon,
+    /// Fixed terminal colors, for low-color terminals and screenshots
+    Plain,
+    /// Bright colors only, for dim screens and projectors
+    HighContrast,
+    /// Okabe-Ito colors, told apart with red-green color blindness
+    ColorBlind,
+}
+
+/// Gauges with their own color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GaugeKind {
+    Ram,
+    Swap,
+    Gpu,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 4] = [Theme::Neon, Theme::Plain, Theme::HighContrast, Theme::ColorBlind];
+
+    /// JETSONSCOPE_THEME, else neon.
+    pub fn from_env() -> Self {
+        std::env::var("JETSONSCOPE_THEME")
+            .ok()
+            .and_then(|v| Self::parse(&v))
+            .unwrap_or_default()
+    }
+
+    /// "plain", "high-contrast", ... (case-insensitive; `_` works for `-`).
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().to_ascii_lowercase().replace('_', "-");
+        Self::ALL.into_iter().find(|t| t.name() == s)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Theme::Neon => "neon",
+            Theme::Plain => "plain",
+            Theme::HighContrast => "high-contrast",
+            Theme::ColorBlind => "color-blind",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Theme::Neon => Theme::Plain,
+            Theme::Plain => Theme::HighContrast,
+            Theme::HighContrast => Theme::ColorBlind,
+            Theme::ColorBlind => Theme::Neon,
+        }
+    }
+
+    /// Border/title color; `tick` is `None` when animations are off.
+    pub fn accent(&self, tick: Option<u64>, offset: u64) -> Color {
+        match self {
+            Theme::Neon => rainbow(tick.unwrap_or(0), offset),
+            Theme::Plain => Color::Cyan,
+            Theme::HighContrast => Color::White,
+            Theme::ColorBlind => Color::Rgb(86, 180, 233),
+        }
+    }
+
+    /// Gauge fill; neon pulses with `tick`.
+    pub fn gauge(&self, kind: GaugeKind, tick: Option<u64>) -> Color {
+        match self {
+            Theme::Neon => {
+                let base = match kind {
+                    GaugeKind::Ram => (0, 255, 0),
+                    GaugeKind::Swap => (255, 255, 0),
+                    GaugeKind::Gpu => (255, 0, 255),
+                };
+                match tick {
+                    Some(tick) => neon_pulse(tick, base),
+                    None => Color::Rgb(base.0, base.1, base.2),
+                }
+            }
+            Theme::Plain => match kind {
+                GaugeKind::Ram => Color::Green,
+                GaugeKind::Swap => Color::Yellow,
+                GaugeKind::Gpu => Color::Magenta,
+            },
+            Theme::HighContrast => match kind {
+                GaugeKind::Ram => Color::LightGreen,
+                GaugeKind::Swap => Color::LightYellow,
+                GaugeKind::Gpu => Color::LightMagenta,
+            },
+            Theme::ColorBlind => match kind {
+                GaugeKind::Ram => Color::Rgb(0, 158, 115),
+                GaugeKind::Swap => Color::Rgb(240, 228, 66),
+                GaugeKind::Gpu => Color::Rgb(204, 121, 167),
+            },
+        }
+    }
+
+    /// Color for a value's warning/critical level.
+    pub fn level(&self, level: Level) -> Color {
+        match (self, level) {
+            (Theme::Neon | Theme::Plain, Level::Normal) => Color::Green,
+            (Theme::Neon | Theme::Plain, Level::Warning) => Color::Yellow,
+            (Theme::Neon | Theme::Plain, Level::Critical) => Color::Red,
+            (Theme::HighContrast, Level::Normal) => Color::LightGreen,
+            (Theme::HighContrast, Level::Warning) => Color::LightYellow,
+            (Theme::HighContrast, Level::Critical) => Color::LightRed,
+            (Theme::ColorBlind, Level::Normal) => Color::Rgb(0, 114, 178),
+            (Theme::ColorBlind, Level::Warning) => Color::Rgb(230, 159, 0),
+            (Theme::ColorBlind, Level::Critical) => Color::Rgb(213, 94, 0),
+        }
+    }
+}
+
+/// Rainbow color cycling with the tick.
+fn rainbow(tick: u64, offset: u64) -> Color {
+    let f = 0.1;
+    let i = (tick + offset) as f64;
+    let r = (f * i + 0.0).sin() * 127.0 + 128.0;
+    let g = (f * i + 2.0).sin() * 127.0 + 128.0;
+    let b = (f * i + 4.0).sin() * 127.0 + 128.0;
+    Color::Rgb(r as u8, g as u8, b as u8)
+}
+
+/// `base_color` dimmed between 50% and 100% with the tick.
+fn neon_pulse(tick: u64, base_color: (u8, u8, u8)) -> Color {
+    let (r, g, b) = base_color;
+    let pulse = (tick as f64 * 0.1).sin().abs(); // 0.0 to 1.0
+    let factor = 0.5 + (pulse * 0.5); // 0.5 to 1.0
+
+    Color::Rgb(
+        (r as f64 * factor) as u8,
+        (g as f64 * factor) as u8,
+        (b as f64 * factor) as u8,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_names_and_env_spellings() {
+        assert_eq!(Theme::parse("plain"), Some(Theme::Plain));
+        assert_eq!(Theme::parse("High_Contrast"), Some(Theme::HighContrast));
+        assert_eq!(Theme::parse(" color-blind "), Some(Theme::ColorBlind));
+        assert_eq!(Theme::parse("rainbow"), None);
+        for theme in Theme::ALL {
+            assert_eq!(Theme::parse(theme.name()), Some(theme));
+        }
+    }
+
+    #[test]
+    fn only_animated_neon_follows_the_tick() {
+        assert_ne!(Theme::Neon.accent(Some(3), 0), Theme::Neon.accent(Some(20), 0));
+        assert_eq!(Theme::Neon.accent(None, 0), Theme::Neon.accent(Some(0), 0));
+        for theme in [Theme::Plain, Theme::HighContrast, Theme::ColorBlind] {
+            assert_eq!(theme.accent(Some(3), 0), theme.accent(Some(20), 5));
+            assert_eq!(theme.gauge(GaugeKind::Gpu, Some(3)), theme.gauge(GaugeKind::Gpu, None));
+        }
+    }
+
+    #[test]
+    fn level_colors_are_distinct_per_theme() {
+        for theme in Theme::ALL {
+            let colors = [Level::Normal, Level::Warning, Level::Critical].map(|l| theme.level(l));
+            assert_ne!(colors[0], colors[1]);
+            assert_ne!(colors[1], colors[2]);
+            assert_ne!(colors[0], colors[2]);
+        }
+        // No red/green pair in the color-blind theme
+        assert_ne!(Theme::ColorBlind.level(Level::Normal), Color::Green);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::parser::TegraStats;
+
+/// Degrees below the target the sensor must reach before clocks are raised again.
+const RELEASE_BAND_C: f32 = 3.0;
+/// Consecutive cool samples needed to step up one level.
+const RELEASE_SAMPLES: u32 = 3;
+/// Overshoot beyond which the governor steps down two levels at once.
+const FAST_STEP_C: f32 = 5.0;
+/// tegrastats reports disabled zones as -256C; ignore anything this cold.
+const MIN_VALID_C: f32 = -40.0;
+
+/// Whether `temp` is a real reading rather than a disabled zone.
+pub fn valid_temp(temp: f32) -> bool {
+    temp > MIN_VALID_C
+}
+
+/// Software thermal governor: lowers clock caps just enough to hold a sensor
+/// under a target, releasing them again once it has cooled below the band.
+#[derive(Debug, Clone)]
+pub struct ThermalGovernor {
+    target_c: f32,
+    sensor: Option<String>,
+    level: usize,
+    max_level: usize,
+    cool: u32,
+}
+
+impl ThermalGovernor {
+    pub fn new(target_c: f32, sensor: Option<String>, max_level: usize) -> Self {
+        Self {
+            target_c,
+            sensor,
+            level: 0,
+            max_level,
+            cool: 0,
+        }
+    }
+
+    pub fn target_c(&self) -> f32 {
+        self.target_c
+    }
+
+    pub fn set_target_c(&mut self, target_c: f32) {
+        self.target_c = target_c;
+        self.cool = 0;
+    }
+
+    /// Temperature the governor regulates (see `read_temp`).
+    pub fn read_temp(&self, stats: &TegraStats) -> Option<f32> {
+        read_temp(stats, self.sensor.as_deref())
+    }
+
+    /// Feed one temperature sample and return the requested throttle level.
+    pub fn update(&mut self, temp_c: f32) -> usize {
+        if temp_c > self.target_c {
+            self.cool = 0;
+            let step = if temp_c > self.target_c + FAST_STEP_C {
+                2
+            } else {
+                1
+            };
+            self.level = (self.level + step).min(self.max_level);
+        } else if temp_c < self.target_c - RELEASE_BAND_C {
+            self.cool += 1;
+            if self.cool >= RELEASE_SAMPLES {
+                self.cool = 0;
+                self.level = self.level.saturating_sub(1);
+            }
+        } else {
+            self.cool = 0;
+        }
+        self.level
+    }
+}
+
+/// `sensor` if given, else `tj`, else the hottest zone; disabled zones are skipped.
+pub fn read_temp(stats: &TegraStats, sensor: Option<&str>) -> Option<f32> {
+    let valid = |t: &f32| valid_temp(*t);
+    if let Some(name) = sensor {
+        return stats
+            .temps
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, t)| *t)
+            .filter(valid);
+    }
+    stats
+        .temps
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("tj"))
+        .map(|(_, t)| *t)
+        .filter(valid)
+        .or_else(|| {
+            stats
+                .temps
+                .values()
+                .copied()
+                .filter(valid)
+                .fold(None, |acc: Option<f32>, t| {
+                    Some(acc.map_or(t, |a| a.max(t)))
+                })
+        })
+}
+
+/// Optional sensor override for the thermal governor (JETSONSCOPE_THERMAL_SENSOR).
+pub fn sensor_from_env() -> Option<String> {
+    std::env::var("JETSONSCOPE_THERMAL_SENSOR")
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn holds_target_with_hysteresis() {
+        let mut gov = ThermalGovernor::new(85.0, None, 4);
+        assert_eq!(gov.update(86.0), 1);
+        assert_eq!(gov.update(91.5), 3);
+        // Between target-band and target: hold
+        assert_eq!(gov.update(84.0), 3);
+        assert_eq!(gov.update(81.0), 3);
+        assert_eq!(gov.update(81.0), 3);
+        assert_eq!(gov.update(81.0), 2);
+    }
+
+    #[test]
+    fn picks_tj_then_hottest_valid_zone() {
+        let mut stats = TegraStats::default();
+        stats.temps.insert("GPU".into(), -256.0);
+        stats.temps.insert("CPU".into(), 61.0);
+        stats.temps.insert("SOC0".into(), 64.5);
+        let gov = ThermalGovernor::new(85.0, None, 4);
+        assert_eq!(gov.read_temp(&stats), Some(64.5));
+        stats.temps.insert("tj".into(), 66.0);
+        assert_eq!(gov.read_temp(&stats), Some(66.0));
+        let gov = ThermalGovernor::new(85.0, Some("cpu".into()), 4);
+        assert_eq!(gov.read_temp(&stats), Some(61.0));
+    }
+}
@@ -0,0 +1,174 @@
+use crate::thermal_governor;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Thermal guard settings (JETSONSCOPE_GUARD_* environment variables).
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuardConfig {
+    /// Temperature that counts as critical
+    pub critical_c: f32,
+    /// Temperature at or below which the board counts as recovered
+    pub recover_c: f32,
+    /// How long the sensor must stay past either threshold before acting
+    pub hold: Duration,
+    /// Sensor to watch; `tj`, else the hottest zone, when unset
+    pub sensor: Option<String>,
+}
+
+impl GuardConfig {
+    /// `JETSONSCOPE_GUARD_CRITICAL_C=95` enables the guard; `JETSONSCOPE_GUARD_AFTER`
+    /// (secs, default 10), `JETSONSCOPE_GUARD_RECOVER_C` (default 10 below critical)
+    /// and `JETSONSCOPE_GUARD_SENSOR` tune it.
+    pub fn from_env() -> Option<Self> {
+        let num = |key: &str| {
+            std::env::var(key)
+                .ok()
+                .and_then(|s| s.trim().parse::<f32>().ok())
+                .filter(|c| c.is_finite())
+        };
+        let critical_c = num("JETSONSCOPE_GUARD_CRITICAL_C")?;
+        Some(GuardConfig {
+            critical_c,
+            recover_c: num("JETSONSCOPE_GUARD_RECOVER_C")
+                .filter(|c| *c < critical_c)
+                .unwrap_or(critical_c - 10.0),
+            hold: Duration::from_secs(
+                std::env::var("JETSONSCOPE_GUARD_AFTER")
+                    .ok()
+                    .and_then(|s| s.trim().parse::<u64>().ok())
+                    .unwrap_or(10),
+            ),
+            sensor: std::env::var("JETSONSCOPE_GUARD_SENSOR")
+                .ok()
+                .filter(|s| !s.trim().is_empty()),
+        })
+    }
+}
+
+/// Thermal guard state reported in ControlStatus.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GuardStatus {
+    pub enabled: bool,
+    pub tripped: bool,
+    pub critical_c: Option<f32>,
+    pub recover_c: Option<f32>,
+    /// Last temperature of the watched sensor
+    pub temp_c: Option<f32>,
+    /// Controls changed by the current trip, "ctrl=value"
+    pub actions: Vec<String>,
+    /// Trips since the daemon started
+    pub trips: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuardTransition {
+    Trip,
+    Recover,
+}
+
+/// What the guard did, for the daemon's audit log.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GuardEvent {
+    /// Over critical for the hold time; (control, value) applied
+    Tripped {
+        temp_c: f32,
+        actions: Vec<(String, String)>,
+    },
+    /// Back under the recovery temperature; (control, value) restored
+    Recovered {
+        temp_c: f32,
+        restored: Vec<(String, String)>,
+    },
+    Failed {
+        control: String,
+        error: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct ThermalGuard {
+    config: GuardConfig,
+    /// Since when the sensor has been past the threshold for the next transition
+    past_since: Option<Instant>,
+    tripped: bool,
+}
+
+impl ThermalGuard {
+    pub fn new(config: GuardConfig) -> Self {
+        Self {
+            config,
+            past_since: None,
+            tripped: false,
+        }
+    }
+
+    pub fn is_tripped(&self) -> bool {
+        self.tripped
+    }
+
+    /// Back to armed, e.g. once the trip's actions were undone on shutdown.
+    pub fn reset(&mut self) {
+        self.tripped = false;
+        self.past_since = None;
+    }
+
+    pub fn read_temp(&self, stats: &crate::parser::TegraStats) -> Option<f32> {
+        thermal_governor::read_temp(stats, self.config.sensor.as_deref())
+    }
+
+    /// Feed one temperature sample; returns a transition once the sensor has stayed
+    /// above critical (or, when tripped, at or below recovery) for the hold time.
+    pub fn update(&mut self, temp_c: Option<f32>, now: Instant) -> Option<GuardTransition> {
+        let past = match temp_c {
+            Some(t) if self.tripped => t <= self.config.recover_c,
+            Some(t) => t > self.config.critical_c,
+            None => false,
+        };
+        if !past {
+            self.past_since = None;
+            return None;
+        }
+        let since = *self.past_since.get_or_insert(now);
+        if now.saturating_duration_since(since) < self.config.hold {
+            return None;
+        }
+        self.past_since = None;
+        self.tripped = !self.tripped;
+        Some(if self.tripped {
+            GuardTransition::Trip
+        } else {
+            GuardTransition::Recover
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trips_after_hold_and_recovers_below_band() {
+        let mut guard = ThermalGuard::new(GuardConfig {
+            critical_c: 95.0,
+            recover_c: 85.0,
+            hold: Duration::from_secs(10),
+            sensor: None,
+        });
+        let t0 = Instant::now();
+        let at = |secs| t0 + Duration::from_secs(secs);
+        assert_eq!(guard.update(Some(96.0), at(0)), None);
+        // A spike that drops back resets the window
+        assert_eq!(guard.update(Some(94.0), at(5)), None);
+        assert_eq!(guard.update(Some(97.0), at(6)), None);
+        assert_eq!(guard.update(Some(97.0), at(15)), None);
+        assert_eq!(guard.update(Some(97.0), at(16)), Some(GuardTransition::Trip));
+        assert!(guard.is_tripped());
+        // Under critical but above recovery: stay tripped
+        assert_eq!(guard.update(Some(90.0), at(30)), None);
+        assert_eq!(guard.update(Some(84.0), at(40)), None);
+        assert_eq!(guard.update(None, at(45)), None);
+        assert_eq!(guard.update(Some(84.0), at(46)), None);
+        assert_eq!(guard.update(Some(80.0), at(56)), Some(GuardTransition::Recover));
+        assert!(!guard.is_tripped());
+    }
+}
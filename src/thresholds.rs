@@ -0,0 +1,186 @@
+//! Warning/critical cutoffs for TUI colors.
+//!
+//! `JETSONSCOPE_THRESHOLDS` overrides the defaults with comma-separated
+//! `metric=warn:crit` pairs, e.g. `temp=70:90,temp.tj=85:100,cpu=60:90`.
+//! Metrics: `temp` (°C, any sensor), `temp.<sensor>`, `cpu` (per-core load %;
+//! reaching a cutoff counts), `engine` (engine load %), `engine.<name>` (e.g.
+//! `engine.GR3D`), `process` (per-process CPU %). Sensor and engine names are
+//! case-insensitive.
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Normal,
+    Warning,
+    Critical,
+}
+
+/// Values above `warn` are warnings, above `crit` critical; with `inclusive`,
+/// reaching a cutoff is enough.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Band {
+    pub warn: f64,
+    pub crit: f64,
+    pub inclusive: bool,
+}
+
+impl Band {
+    pub const fn new(warn: f64, crit: f64) -> Self {
+        Band {
+            warn,
+            crit,
+            inclusive: false,
+        }
+    }
+
+    /// Cutoffs that count as reached at their own value (CPU load: 50% is a warning).
+    pub const fn inclusive(self) -> Self {
+        Band {
+            inclusive: true,
+            ..self
+        }
+    }
+
+    pub fn level(&self, value: f64) -> Level {
+        let past = |cutoff: f64| if self.inclusive { value >= cutoff } else { value > cutoff };
+        if past(self.crit) {
+            Level::Critical
+        } else if past(self.warn) {
+            Level::Warning
+        } else {
+            Level::Normal
+        }
+    }
+
+    /// "60:80" -> Band; `crit` must not be below `warn`.
+    fn parse(s: &str) -> Option<Self> {
+        let (warn, crit) = s.split_once(':')?;
+        let band = Band::new(warn.trim().parse().ok()?, crit.trim().parse().ok()?);
+        (band.crit >= band.warn).then_some(band)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Thresholds {
+    pub temp: Band,
+    pub cpu: Band,
+    pub engine: Band,
+    pub process: Band,
+    /// Per-sensor overrides, keyed by lowercase name
+    temp_sensors: HashMap<String, Band>,
+    /// Per-engine overrides, keyed by lowercase name
+    engines: HashMap<String, Band>,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Thresholds {
+            temp: Band::new(60.0, 80.0),
+            cpu: Band::new(50.0, 80.0).inclusive(),
+            engine: Band::new(50.0, 75.0),
+            process: Band::new(25.0, 50.0),
+            temp_sensors: HashMap::new(),
+            engines: HashMap::new(),
+        }
+    }
+}
+
+impl Thresholds {
+    pub fn from_env() -> Self {
+        match std::env::var("JETSONSCOPE_THRESHOLDS") {
+            Ok(spec) => Self::parse(&spec),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Defaults plus overrides from `spec`; bad entries are reported and skipped.
+    pub fn parse(spec: &str) -> Self {
+        let mut out = Self::default();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let parsed = entry
+                .split_once('=')
+                .and_then(|(metric, band)| Some((metric.trim(), Band::parse(band)?)));
+            let Some((metric, band)) = parsed else {
+                eprintln!("Ignoring threshold '{}': expected metric=warn:crit", entry);
+                continue;
+            };
+            let (kind, name) = match metric.split_once('.') {
+                Some((kind, name)) => (kind, Some(name.to_lowercase())),
+                None => (metric, None),
+            };
+            match (kind, name) {
+                ("temp", None) => out.temp = band,
+                ("temp", Some(sensor)) => {
+                    out.temp_sensors.insert(sensor, band);
+                }
+                ("cpu", None) => out.cpu = band.inclusive(),
+                ("engine", None) => out.engine = band,
+                ("engine", Some(engine)) => {
+                    out.engines.insert(engine, band);
+                }
+                ("process", None) => out.process = band,
+                _ => eprintln!("Ignoring threshold '{}': unknown metric", entry),
+            }
+        }
+        out
+    }
+
+    pub fn temp_level(&self, sensor: &str, celsius: f64) -> Level {
+        self.temp_band(sensor).level(celsius)
+    }
+
+    /// The sensor's own band, else the `temp` one.
+    pub fn temp_band(&self, sensor: &str) -> Band {
+        *self
+            .temp_sensors
+            .get(&sensor.to_lowercase())
+            .unwrap_or(&self.temp)
+    }
+
+    pub fn engine_level(&self, engine: &str, percent: f64) -> Level {
+        self.engines
+            .get(&engine.to_lowercase())
+            .unwrap_or(&self.engine)
+            .level(percent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_overrides_per_metric_and_sensor() {
+        let t = Thresholds::parse(
+            "temp=70:90, temp.TJ=85:100,cpu=60:90,engine.gr3d=40:60,bogus,fan=1:2",
+        );
+        assert_eq!(t.temp_level("CPU", 75.0), Level::Warning);
+        assert_eq!(t.temp_level("tj", 95.0), Level::Warning);
+        assert_eq!(t.temp_level("tj", 101.0), Level::Critical);
+        assert_eq!(t.cpu.level(59.0), Level::Normal);
+        assert_eq!(t.engine_level("GR3D", 50.0), Level::Warning);
+        assert_eq!(t.engine_level("NVENC", 50.0), Level::Normal);
+        assert_eq!(t.process, Thresholds::default().process);
+        // crit below warn is rejected
+        assert_eq!(Thresholds::parse("temp=90:70").temp, Band::new(60.0, 80.0));
+    }
+
+    #[test]
+    fn defaults_match_previous_colors() {
+        let t = Thresholds::default();
+        assert_eq!(t.temp_level("tj", 60.0), Level::Normal);
+        assert_eq!(t.temp_level("tj", 80.5), Level::Critical);
+        assert_eq!(t.temp_level("tj", 80.0), Level::Warning);
+        assert_eq!(t.cpu.level(45.0), Level::Normal);
+        // Cores were cyan below 50% and yellow below 80%
+        assert_eq!(t.cpu.level(49.0), Level::Normal);
+        assert_eq!(t.cpu.level(50.0), Level::Warning);
+        assert_eq!(t.cpu.level(65.0), Level::Warning);
+        assert_eq!(t.cpu.level(79.0), Level::Warning);
+        assert_eq!(t.cpu.level(80.0), Level::Critical);
+        assert_eq!(t.cpu.level(95.0), Level::Critical);
+        assert_eq!(t.engine_level("GR3D", 76.0), Level::Critical);
+        assert_eq!(t.process.level(30.0), Level::Warning);
+    }
+}
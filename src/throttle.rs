@@ -0,0 +1,245 @@
+//! Thermal throttling detection: sensors past their passive trip point,
+//! clock ceilings lowered since the collector started, and the kernel's
+//! per-CPU throttle counters.
+//!
+//! The baseline ceiling of each clock is the highest `scaling_max_freq`
+//! (CPU cluster) or devfreq `max_freq` (GPU) seen so far, so the nvpmodel
+//! mode the board booted in is not reported as throttling, but a later cap
+//! (thermal, power cap, or a switch to a lower mode) is.
+
+use crate::hardware::{self, CpuCluster};
+use crate::parser::TegraStats;
+use crate::trip_points::{self, TripPoints};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Why clocks are being held back; present in a snapshot only while throttling.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ThrottleState {
+    /// Sensors at or above their passive trip point
+    pub hot_sensors: Vec<HotSensor>,
+    /// Clocks whose ceiling is below the highest seen since start
+    pub capped_clocks: Vec<CappedClock>,
+    /// Increase of the CPUs' `thermal_throttle` counters since the previous sample
+    pub throttle_events: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HotSensor {
+    pub sensor: String,
+    pub temp_c: f32,
+    pub passive_c: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CappedClock {
+    /// "CPU0-3", "GPU"
+    pub domain: String,
+    pub ceiling_mhz: u64,
+    pub baseline_mhz: u64,
+}
+
+impl ThrottleState {
+    /// One line for the TUI banner, e.g. "CPU 97.0°C≥95.5°C · CPU4-7 ≤1190/1728MHz".
+    pub fn summary(&self) -> String {
+        let mut parts: Vec<String> = self
+            .hot_sensors
+            .iter()
+            .map(|h| format!("{} {:.1}°C≥{:.1}°C", h.sensor, h.temp_c, h.passive_c))
+            .collect();
+        parts.extend(
+            self.capped_clocks
+                .iter()
+                .map(|c| format!("{} ≤{}/{}MHz", c.domain, c.ceiling_mhz, c.baseline_mhz)),
+        );
+        if self.throttle_events > 0 {
+            parts.push(format!("{} throttle events", self.throttle_events));
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Per-collector state: trip points, clock baselines and last counter total.
+pub struct ThrottleMonitor {
+    trips: BTreeMap<String, TripPoints>,
+    cpu_base: PathBuf,
+    clusters: Vec<CpuCluster>,
+    gpu_devfreq: Option<PathBuf>,
+    baselines: BTreeMap<String, u64>,
+    last_events: Option<u64>,
+}
+
+impl ThrottleMonitor {
+    pub fn new() -> Self {
+        Self::with_paths(
+            trip_points::read_all(),
+            Path::new(hardware::CPU_SYSFS),
+            crate::control::gpu_devfreq_path(),
+        )
+    }
+
+    pub fn with_paths(
+        trips: BTreeMap<String, TripPoints>,
+        cpu_base: &Path,
+        gpu_devfreq: Option<PathBuf>,
+    ) -> Self {
+        ThrottleMonitor {
+            trips,
+            cpu_base: cpu_base.to_path_buf(),
+            clusters: hardware::read_cpu_clusters(cpu_base),
+            gpu_devfreq,
+            baselines: BTreeMap::new(),
+            last_events: None,
+        }
+    }
+
+    /// Throttling seen in this sample; `None` when nothing is held back.
+    pub fn sample(&mut self, stats: &TegraStats) -> Option<ThrottleState> {
+        let mut state = ThrottleState::default();
+        for (sensor, temp) in &stats.temps {
+            let Some(passive) = trip_points::lookup(&self.trips, sensor).and_then(|t| t.passive) else {
+                continue;
+            };
+            if *temp >= passive {
+                state.hot_sensors.push(HotSensor {
+                    sensor: sensor.clone(),
+                    temp_c: *temp,
+                    passive_c: passive,
+                });
+            }
+        }
+        state.hot_sensors.sort_by(|a, b| a.sensor.cmp(&b.sensor));
+
+        for (domain, ceiling_khz) in self.ceilings() {
+            let baseline = self.baselines.entry(domain.clone()).or_insert(ceiling_khz);
+            *baseline = (*baseline).max(ceiling_khz);
+            if ceiling_khz < *baseline {
+                state.capped_clocks.push(CappedClock {
+                    domain,
+                    ceiling_mhz: ceiling_khz / 1000,
+                    baseline_mhz: *baseline / 1000,
+                });
+            }
+        }
+
+        if let Some(total) = self.throttle_count() {
+            state.throttle_events = self.last_events.map_or(0, |last| total.saturating_sub(last));
+            self.last_events = Some(total);
+        }
+
+        (state != ThrottleState::default()).then_some(state)
+    }
+
+    /// Current ceiling in kHz per CPU cluster and for the GPU.
+    fn ceilings(&self) -> Vec<(String, u64)> {
+        let mut ceilings = Vec::new();
+        for cluster in &self.clusters {
+            let (Some(first), Some(last)) = (cluster.cpus.first(), cluster.cpus.last()) else {
+                continue;
+            };
+            let path = self.cpu_base.join(format!("cpu{first}/cpufreq/scaling_max_freq"));
+            if let Some(khz) = read_u64(&path) {
+                let domain = if first == last {
+                    format!("CPU{first}")
+                } else {
+                    format!("CPU{first}-{last}")
+                };
+                ceilings.push((domain, khz));
+            }
+        }
+        if let Some(hz) = self.gpu_devfreq.as_ref().and_then(|d| read_u64(&d.join("max_freq"))) {
+            ceilings.push(("GPU".to_string(), hz / 1000));
+        }
+        ceilings
+    }
+
+    /// Sum of `thermal_throttle/core_throttle_count` over all CPUs, where the kernel has it.
+    fn throttle_count(&self) -> Option<u64> {
+        let counts: Vec<u64> = self
+            .clusters
+            .iter()
+            .flat_map(|c| &c.cpus)
+            .filter_map(|cpu| {
+                read_u64(
+                    &self
+                        .cpu_base
+                        .join(format!("cpu{cpu}/thermal_throttle/core_throttle_count")),
+                )
+            })
+            .collect();
+        (!counts.is_empty()).then(|| counts.iter().sum())
+    }
+}
+
+impl Default for ThrottleMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn read_u64(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_hot_sensors_lowered_ceilings_and_counters() {
+        let base = std::env::temp_dir().join(format!("jscope-throttle-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        let write = |rel: &str, value: &str| {
+            let path = base.join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, format!("{value}\n")).unwrap();
+        };
+        for cpu in 0..4 {
+            write(&format!("cpu/cpu{cpu}/cpufreq/related_cpus"), if cpu < 2 { "0-1" } else { "2-3" });
+            write(&format!("cpu/cpu{cpu}/cpufreq/scaling_max_freq"), "1728000");
+            write(&format!("cpu/cpu{cpu}/thermal_throttle/core_throttle_count"), "5");
+        }
+        write("gpu/max_freq", "1300500000");
+
+        let trips = BTreeMap::from([(
+            "cpu".to_string(),
+            TripPoints {
+                passive: Some(95.5),
+                critical: Some(105.0),
+            },
+        )]);
+        let mut monitor = ThrottleMonitor::with_paths(trips, &base.join("cpu"), Some(base.join("gpu")));
+        let mut stats = TegraStats::default();
+        stats.temps.insert("CPU".to_string(), 60.0);
+        stats.temps.insert("GPU".to_string(), 99.0);
+        assert_eq!(monitor.sample(&stats), None);
+
+        stats.temps.insert("CPU".to_string(), 97.0);
+        write("cpu/cpu2/cpufreq/scaling_max_freq", "1190400");
+        write("gpu/max_freq", "624750000");
+        write("cpu/cpu3/thermal_throttle/core_throttle_count", "8");
+        let state = monitor.sample(&stats).unwrap();
+        let _ = fs::remove_dir_all(&base);
+        assert_eq!(
+            state.hot_sensors,
+            vec![HotSensor {
+                sensor: "CPU".to_string(),
+                temp_c: 97.0,
+                passive_c: 95.5,
+            }]
+        );
+        let capped: Vec<(&str, u64, u64)> = state
+            .capped_clocks
+            .iter()
+            .map(|c| (c.domain.as_str(), c.ceiling_mhz, c.baseline_mhz))
+            .collect();
+        assert_eq!(capped, vec![("CPU2-3", 1190, 1728), ("GPU", 624, 1300)]);
+        assert_eq!(state.throttle_events, 3);
+        assert_eq!(
+            state.summary(),
+            "CPU 97.0°C≥95.5°C · CPU2-3 ≤1190/1728MHz · GPU ≤624/1300MHz · 3 throttle events"
+        );
+    }
+}
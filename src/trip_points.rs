@@ -0,0 +1,186 @@
+//! Thermal zone trip points from sysfs, for "how much headroom is left" bars.
+//!
+//! Zones are matched to tegrastats sensors by their `type` with the
+//! `-therm`/`_therm` suffix dropped, case-insensitive (`CPU-therm` -> `cpu`).
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+const SYSFS_THERMAL: &str = "/sys/class/thermal";
+
+/// Passive (throttling starts) and critical (shutdown) trip points in °C.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TripPoints {
+    pub passive: Option<f32>,
+    pub critical: Option<f32>,
+}
+
+impl TripPoints {
+    /// Degrees left before the next trip point, and whether that is the critical one.
+    pub fn headroom(&self, temp_c: f32) -> Option<(f32, bool)> {
+        match (self.passive, self.critical) {
+            (Some(passive), _) if temp_c < passive => Some((passive - temp_c, false)),
+            (_, Some(critical)) => Some((critical - temp_c, true)),
+            (Some(passive), None) => Some((passive - temp_c, false)),
+            (None, None) => None,
+        }
+    }
+
+    /// `width`-cell bar from 0°C to the critical (else passive) trip,
+    /// with `┃` marking the passive trip while it is still ahead.
+    pub fn bar(&self, temp_c: f32, width: usize) -> Option<String> {
+        let top = self.critical.or(self.passive)?;
+        if top <= 0.0 || width == 0 {
+            return None;
+        }
+        let cell = |t: f32| ((t / top) * width as f32).round().clamp(0.0, width as f32) as usize;
+        let filled = cell(temp_c);
+        let marker = self
+            .passive
+            .filter(|_| self.critical.is_some())
+            .map(|p| cell(p).min(width - 1));
+        Some(
+            (0..width)
+                .map(|i| match i {
+                    i if i < filled => '█',
+                    i if Some(i) == marker => '┃',
+                    _ => '·',
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Trip points for every thermal zone on this system, keyed by sensor name.
+pub fn read_all() -> BTreeMap<String, TripPoints> {
+    read_from(Path::new(SYSFS_THERMAL))
+}
+
+/// Read `thermal_zone*` directories under `base`.
+pub fn read_from(base: &Path) -> BTreeMap<String, TripPoints> {
+    let mut zones = BTreeMap::new();
+    let Ok(entries) = fs::read_dir(base) else {
+        return zones;
+    };
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        let is_zone = dir
+            .file_name()
+            .is_some_and(|n| n.to_string_lossy().starts_with("thermal_zone"));
+        if !is_zone {
+            continue;
+        }
+        let Some(kind) = read_attr(&dir, "type") else {
+            continue;
+        };
+        let trips = read_zone(&dir);
+        if trips != TripPoints::default() {
+            zones.insert(sensor_key(&kind), trips);
+        }
+    }
+    zones
+}
+
+/// Sensor lookup with the same normalisation as zone names.
+pub fn lookup<'a>(zones: &'a BTreeMap<String, TripPoints>, sensor: &str) -> Option<&'a TripPoints> {
+    zones.get(&sensor_key(sensor))
+}
+
+fn sensor_key(name: &str) -> String {
+    let lower = name.trim().to_lowercase();
+    lower
+        .strip_suffix("-therm")
+        .or_else(|| lower.strip_suffix("_therm"))
+        .unwrap_or(&lower)
+        .to_string()
+}
+
+/// Lowest passive and critical trip of a zone (zones may list several of each).
+fn read_zone(dir: &Path) -> TripPoints {
+    let mut trips = TripPoints::default();
+    for idx in 0.. {
+        let Some(kind) = read_attr(dir, &format!("trip_point_{idx}_type")) else {
+            break;
+        };
+        // millidegrees; disabled trips are reported as huge or negative values
+        let Some(temp) = read_attr(dir, &format!("trip_point_{idx}_temp"))
+            .and_then(|t| t.parse::<i64>().ok())
+            .map(|m| m as f32 / 1000.0)
+            .filter(|t| *t > 0.0 && *t < 200.0)
+        else {
+            continue;
+        };
+        let slot = match kind.as_str() {
+            "passive" => &mut trips.passive,
+            "critical" => &mut trips.critical,
+            _ => continue,
+        };
+        *slot = Some(slot.map_or(temp, |t: f32| t.min(temp)));
+    }
+    trips
+}
+
+fn read_attr(dir: &Path, attr: &str) -> Option<String> {
+    fs::read_to_string(dir.join(attr))
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_zones_and_draws_headroom() {
+        let base = std::env::temp_dir().join(format!("jscope-trips-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        let zone = |n: u32, attrs: &[(&str, &str)]| {
+            let dir = base.join(format!("thermal_zone{n}"));
+            fs::create_dir_all(&dir).unwrap();
+            for (attr, value) in attrs {
+                fs::write(dir.join(attr), format!("{value}\n")).unwrap();
+            }
+        };
+        zone(
+            0,
+            &[
+                ("type", "CPU-therm"),
+                ("trip_point_0_type", "critical"),
+                ("trip_point_0_temp", "105000"),
+                ("trip_point_1_type", "passive"),
+                ("trip_point_1_temp", "99000"),
+                ("trip_point_2_type", "passive"),
+                ("trip_point_2_temp", "95500"),
+                ("trip_point_3_type", "hot"),
+                ("trip_point_3_temp", "100000"),
+            ],
+        );
+        zone(
+            1,
+            &[
+                ("type", "tj-therm"),
+                ("trip_point_0_type", "critical"),
+                ("trip_point_0_temp", "-1"),
+            ],
+        );
+
+        let zones = read_from(&base);
+        let _ = fs::remove_dir_all(&base);
+        let cpu = lookup(&zones, "CPU").unwrap();
+        assert_eq!(cpu.passive, Some(95.5));
+        assert_eq!(cpu.critical, Some(105.0));
+        assert!(lookup(&zones, "tj").is_none());
+
+        assert_eq!(cpu.headroom(45.5), Some((50.0, false)));
+        assert_eq!(cpu.headroom(100.0), Some((5.0, true)));
+        let trips = TripPoints {
+            passive: Some(50.0),
+            critical: Some(100.0),
+        };
+        assert_eq!(trips.bar(30.0, 10).unwrap(), "███··┃····");
+        assert_eq!(trips.bar(70.0, 10).unwrap(), "███████···");
+        assert!(TripPoints::default().bar(30.0, 10).is_none());
+    }
+}
@@ -1,34 +1,44 @@
-use crate::app::App;
-use crate::processes::ProcessMonitor;
+use crate::app::{App, BurstState, ClickAreas, ConnectionStatus, GraphStyle, ViewMode};
+use crate::processes::{self, ProcessSort};
+use crate::t;
+use crate::theme::GaugeKind;
+use crate::thermal_governor::valid_temp;
+use crate::thresholds::Level;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Gauge, Paragraph, Row, Sparkline, Table},
+    symbols::Marker,
+    widgets::{
+        Axis, Block, Borders, Chart, Clear, Dataset, Gauge, GraphType, LineGauge, Paragraph, Row,
+        Table,
+    },
 };
 
-// Helper to generate a rainbow color based on a tick
-fn get_rainbow_color(tick: u64, offset: u64) -> Color {
-    let f = 0.1;
-    let i = (tick + offset) as f64;
-    let r = (f * i + 0.0).sin() * 127.0 + 128.0;
-    let g = (f * i + 2.0).sin() * 127.0 + 128.0;
-    let b = (f * i + 4.0).sin() * 127.0 + 128.0;
-    Color::Rgb(r as u8, g as u8, b as u8)
-}
-
-// Helper for a pulsing neon color
-fn get_neon_pulse(tick: u64, base_color: (u8, u8, u8)) -> Color {
-    let (r, g, b) = base_color;
-    let pulse = (tick as f64 * 0.1).sin().abs(); // 0.0 to 1.0
-    let factor = 0.5 + (pulse * 0.5); // 0.5 to 1.0
-
-    Color::Rgb(
-        (r as f64 * factor) as u8,
-        (g as f64 * factor) as u8,
-        (b as f64 * factor) as u8,
-    )
+// Border/title color for the app's theme; still when animations are off
+fn accent_color(app: &App, offset: u64) -> Color {
+    app.theme.accent(app.animations.then_some(app.tick_count), offset)
+}
+
+// Gauge color: pulsing with neon and animations on, the theme's fixed color otherwise
+fn gauge_color(app: &App, kind: GaugeKind) -> Color {
+    app.theme.gauge(kind, app.animations.then_some(app.tick_count))
+}
+
+// Trend arrow for a sample-to-sample change, ignoring changes inside the dead band
+fn trend_arrow(change: f64, dead_band: f64) -> &'static str {
+    if change > dead_band {
+        "↑"
+    } else if change < -dead_band {
+        "↓"
+    } else {
+        "→"
+    }
+}
+
+fn level_color(app: &App, level: Level) -> Color {
+    app.theme.level(level)
 }
 
 fn bytes_to_mb(bytes: u64) -> u64 {
@@ -39,33 +49,326 @@ fn unit_label(unit: crate::parser::SizeUnit) -> &'static str {
     match unit {
         crate::parser::SizeUnit::KB => "KB",
         crate::parser::SizeUnit::MB => "MB",
+        crate::parser::SizeUnit::GB => "GB",
+    }
+}
+
+/// `bytes` in the unit tegrastats printed the size in ("7620" MB, "61.4" GB).
+fn in_unit(bytes: u64, unit: crate::parser::SizeUnit) -> String {
+    match unit {
+        crate::parser::SizeUnit::GB => format!("{:.1}", unit.value_of(bytes)),
+        _ => format!("{}", unit.value_of(bytes) as u64),
     }
 }
 
-pub fn ui(f: &mut Frame, app: &App) {
+/// Draw the current view; records the clickable areas in `app.click_areas`.
+pub fn ui(f: &mut Frame, app: &mut App) {
+    app.click_areas = ClickAreas::default();
     // Switch views based on view_mode
     match app.view_mode {
+        crate::app::ViewMode::Dashboard if is_compact(f, app) => render_dashboard_compact(f, app),
         crate::app::ViewMode::Dashboard => render_dashboard(f, app),
         crate::app::ViewMode::Processes => render_processes_view(f, app),
         crate::app::ViewMode::GpuEngines => render_gpu_engines_view(f, app),
         crate::app::ViewMode::Clocks => render_clocks_view(f, app),
+        crate::app::ViewMode::Temperatures => render_temperatures_view(f, app),
+        crate::app::ViewMode::Power => render_power_view(f, app),
+    }
+
+    render_footer(f, app);
+    if !is_compact(f, app) {
+        render_tabs(f, app);
     }
 
     // Always render help overlay if shown
     if app.show_help {
         render_help(f);
     }
+    if let Some(detail) = &app.process_detail {
+        render_process_detail(f, detail);
+    }
+    if let Some(palette) = &app.palette {
+        render_palette(f, app, palette);
+    }
+    if let Some(pending) = &app.confirm {
+        render_confirm(f, &pending.question, &pending.detail);
+    }
+}
+
+/// View tabs in the top margin row, clickable; the current view is highlighted.
+fn render_tabs(f: &mut Frame, app: &mut App) {
+    let area = f.area();
+    if area.height < 2 || area.width < 4 {
+        return;
+    }
+    let mut x = area.x + 1;
+    let mut spans = Vec::new();
+    for mode in ViewMode::ALL {
+        let label = format!(" {} ", mode.tab_label());
+        let width = label.chars().count() as u16;
+        if x + width > area.right() - 1 {
+            break;
+        }
+        let style = if mode == app.view_mode {
+            Style::default()
+                .fg(accent_color(app, 0))
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        spans.push(Span::styled(label, style));
+        app.click_areas.tabs.push((mode, Rect::new(x, area.y, width, 1)));
+        x += width;
+    }
+    let tabs_area = Rect::new(area.x + 1, area.y, x - area.x - 1, 1);
+    f.render_widget(Paragraph::new(Line::from(spans)), tabs_area);
+}
+
+/// Condensed layout for small terminals (e.g. 80x24 serial consoles).
+fn is_compact(f: &Frame, app: &App) -> bool {
+    let area = f.area();
+    app.layout_mode.is_compact(area.width, area.height)
+}
+
+/// One-row gauge for the compact layout.
+fn line_gauge(label: String, ratio: f64, color: Color) -> LineGauge<'static> {
+    LineGauge::default()
+        .label(label)
+        .ratio(ratio.clamp(0.0, 1.0))
+        .filled_style(Style::default().fg(color))
+        .unfilled_style(Style::default().fg(Color::DarkGray))
+}
+
+/// Dashboard without borders or trends: one-row gauges, a core grid and
+/// short temperature/power tables, leaving the last row for the footer.
+fn render_dashboard_compact(f: &mut Frame, app: &App) {
+    let area = f.area();
+    let body = Rect {
+        height: area.height.saturating_sub(1),
+        ..area
+    };
+    let stats = &app.latest_stats;
+    let core_cols = (body.width / 18).max(1) as usize;
+    let core_rows = stats.cpus.len().div_ceil(core_cols) as u16;
+    let sensors = stats.temps.values().filter(|t| valid_temp(**t)).count();
+    let table_rows = sensors.max(stats.power.len()) as u16 + 1;
+    let disk_rows = stats.disk.is_some() as u16;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),         // Header
+            Constraint::Length(1),         // RAM/SWAP/GPU
+            Constraint::Length(1),         // Engines
+            Constraint::Length(disk_rows), // Disk
+            Constraint::Length(core_rows), // CPU cores
+            Constraint::Length(table_rows), // Temps/Power
+            Constraint::Min(0),            // Trends, when rows are left
+        ])
+        .split(body);
+
+    let mut header = vec![
+        Span::styled(
+            "JetsonScope ",
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            app.connection_status.label(),
+            Style::default().fg(connection_color(&app.connection_status)),
+        ),
+        Span::raw(" "),
+    ];
+    if app.connection_status.is_demo() {
+        header.push(Span::styled("DEMO ", Style::default().fg(Color::Yellow)));
+    }
+    header.push(link_span(app));
+    if let Some(banner) = throttle_span(app) {
+        header.push(Span::raw(" "));
+        header.push(banner);
+    }
+    f.render_widget(Paragraph::new(Line::from(header)), chunks[0]);
+
+    let mem_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(40),
+            Constraint::Percentage(30),
+            Constraint::Percentage(30),
+        ])
+        .split(chunks[1]);
+    let usage = |used: u64, total: u64| {
+        let ratio = if total == 0 { 0.0 } else { used as f64 / total as f64 };
+        (bytes_to_mb(used), bytes_to_mb(total), ratio)
+    };
+    let (used, total, ratio) = stats
+        .ram
+        .as_ref()
+        .map(|r| usage(r.used_bytes, r.total_bytes))
+        .unwrap_or((0, 0, 0.0));
+    f.render_widget(
+        line_gauge(format!("RAM {}/{}M", used, total), ratio, Color::Green),
+        mem_chunks[0],
+    );
+    let (used, total, ratio) = stats
+        .swap
+        .as_ref()
+        .map(|s| usage(s.used_bytes, s.total_bytes))
+        .unwrap_or((0, 0, 0.0));
+    f.render_widget(
+        line_gauge(format!(" SWP {}/{}M", used, total), ratio, Color::Yellow),
+        mem_chunks[1],
+    );
+    let gpu = stats.gpu_usage().unwrap_or(0);
+    f.render_widget(
+        line_gauge(format!(" GPU {:>3}%", gpu), gpu as f64 / 100.0, Color::Magenta),
+        mem_chunks[2],
+    );
+
+    // Engines other than GR3D (already the GPU gauge) on one line
+    let mut engines: Vec<(&String, &crate::parser::EngineStat)> = stats
+        .engines
+        .iter()
+        .filter(|(name, _)| name.as_str() != "GR3D")
+        .collect();
+    engines.sort_by(|a, b| a.0.cmp(b.0));
+    let mut engine_spans = Vec::new();
+    for (name, stat) in engines {
+        let value = stat
+            .usage_percent
+            .map(|v| format!("{v}%"))
+            .or_else(|| stat.freq_mhz.or(stat.raw_value).map(|v| format!("{v}M")))
+            .unwrap_or_else(|| "-".to_string());
+        let color = level_color(
+            app,
+            app.thresholds
+                .engine_level(name, stat.usage_percent.unwrap_or(0) as f64),
+        );
+        engine_spans.push(Span::styled(format!("{} ", name), Style::default().fg(Color::Magenta)));
+        engine_spans.push(Span::styled(format!("{}  ", value), Style::default().fg(color)));
+    }
+    f.render_widget(Paragraph::new(Line::from(engine_spans)), chunks[2]);
+    f.render_widget(Paragraph::new(Line::from(disk_spans(app))), chunks[3]);
+
+    let row_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Length(1); core_rows as usize])
+        .split(chunks[4]);
+    for (row_idx, row_area) in row_chunks.iter().enumerate() {
+        let col_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, core_cols as u32); core_cols])
+            .split(*row_area);
+        for (col_idx, cell) in col_chunks.iter().enumerate() {
+            let i = row_idx * core_cols + col_idx;
+            let Some(core) = stats.cpus.get(i) else {
+                break;
+            };
+            let load = core.load_percent.unwrap_or(0);
+            let color = level_color(app, app.thresholds.cpu.level(load as f64));
+            f.render_widget(
+                line_gauge(format!("C{:<2}{:>3}% ", i, load), load as f64 / 100.0, color),
+                *cell,
+            );
+        }
+    }
+
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[5]);
+    let header_style = Style::default()
+        .fg(Color::White)
+        .add_modifier(Modifier::BOLD);
+
+    let mut temps: Vec<(&String, &f32)> = stats.temps.iter().filter(|(_, t)| valid_temp(**t)).collect();
+    temps.sort_by(|a, b| a.0.cmp(b.0));
+    let temp_rows: Vec<Row> = temps
+        .iter()
+        .map(|(name, temp)| {
+            let color = level_color(app, app.thresholds.temp_level(name, **temp as f64));
+            let max = app
+                .extremes
+                .temps
+                .get(*name)
+                .map(|s| format!("{:.0}C", s.max))
+                .unwrap_or_else(|| "-".to_string());
+            Row::new(vec![
+                Span::styled((*name).to_string(), Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{:.1}C", temp), Style::default().fg(color)),
+                Span::styled(max, Style::default().fg(Color::Gray)),
+            ])
+        })
+        .collect();
+    let temp_table = Table::new(
+        temp_rows,
+        [Constraint::Min(6), Constraint::Length(7), Constraint::Length(5)],
+    )
+    .header(Row::new(vec!["Temp", t!("ui.col.current"), "Max"]).style(header_style));
+    f.render_widget(temp_table, bottom[0]);
+
+    let mut rails: Vec<(&String, &crate::parser::PowerRail)> = stats.power.iter().collect();
+    rails.sort_by(|a, b| a.0.cmp(b.0));
+    let power_rows: Vec<Row> = rails
+        .iter()
+        .map(|(name, rail)| {
+            Row::new(vec![
+                Span::styled((*name).to_string(), Style::default().fg(Color::Magenta)),
+                Span::styled(format!("{}mW", rail.current_mw), Style::default().fg(Color::White)),
+                Span::styled(format!("{}mW", rail.average_mw), Style::default().fg(Color::Gray)),
+            ])
+        })
+        .collect();
+    let power_table = Table::new(
+        power_rows,
+        [Constraint::Min(6), Constraint::Length(8), Constraint::Length(8)],
+    )
+    .header(Row::new(vec!["Rail", t!("ui.col.current"), t!("ui.col.avg")]).style(header_style));
+    f.render_widget(power_table, bottom[1]);
+
+    if chunks[6].height >= 3 {
+        let trend_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, 4); 4])
+            .split(chunks[6]);
+        for (series, area) in trend_series(app).into_iter().zip(trend_chunks.iter()) {
+            let last = series.data.last().map(|(_, v)| *v).unwrap_or_default();
+            let summary = series
+                .summary
+                .map(|s| format!(" ⌀{:.0} ▲{:.0}", s.mean, s.max))
+                .unwrap_or_default();
+            let block = Block::default().title(Span::styled(
+                format!("{} {:.0}{summary}", series.name, last),
+                header_style,
+            ));
+            render_trend(f, *area, app, block, &series);
+        }
+    }
 }
 
+/// Mount usage colors on the Disk row; a full eMMC stops the board.
+const DISK_WARNING_PERCENT: f64 = 80.0;
+const DISK_CRITICAL_PERCENT: f64 = 90.0;
+
+/// Height of the dashboard's trend row; tall screens get charts with axes.
+const TREND_ROWS: u16 = 3;
+const TREND_ROWS_TALL: u16 = 10;
+const TALL_HEIGHT: u16 = 40;
+
 fn render_dashboard(f: &mut Frame, app: &App) {
+    let trend_rows = if f.area().height >= TALL_HEIGHT {
+        TREND_ROWS_TALL
+    } else {
+        TREND_ROWS
+    };
+    let disk_rows = if app.latest_stats.disk.is_some() { 3 } else { 0 };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
         .constraints(
             [
                 Constraint::Length(3),  // Header
-                Constraint::Length(3),  // Source/Trends/Conn
+                Constraint::Length(trend_rows), // Source/Trends/Conn
                 Constraint::Length(4),  // RAM/SWAP/IRAM
+                Constraint::Length(disk_rows), // Disk
                 Constraint::Length(12), // CPU/GPU/Engines
                 Constraint::Min(0),     // Temps/Power
             ]
@@ -74,11 +377,11 @@ fn render_dashboard(f: &mut Frame, app: &App) {
         .split(f.area());
 
     // Animated Border Color
-    let border_color = get_rainbow_color(app.tick_count, 0);
+    let border_color = accent_color(app, 0);
 
     // Header
-    let title_color = get_rainbow_color(app.tick_count, 10);
-    let header_text = Line::from(vec![
+    let title_color = accent_color(app, 10);
+    let mut header_spans = vec![
         Span::styled(
             "JetsonScope ",
             Style::default()
@@ -92,24 +395,31 @@ fn render_dashboard(f: &mut Frame, app: &App) {
                 app.latest_stats
                     .timestamp
                     .clone()
-                    .unwrap_or_else(|| "awaiting data".to_string())
+                    .unwrap_or_else(|| t!("ui.awaiting_data").to_string())
             ),
             Style::default().fg(Color::Gray),
         ),
-    ]);
+        Span::raw("  "),
+        link_span(app),
+    ];
+    if let Some(banner) = throttle_span(app) {
+        header_spans.push(Span::raw("  "));
+        header_spans.push(banner);
+    }
+    let header_text = Line::from(header_spans);
 
     let header = Paragraph::new(header_text)
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(border_color))
-                .title(if app.connection_status.contains("demo") || app.connection_status.contains("sintético") {
-                    "⚠ MODO DEMO (Datos Sintéticos) ⚠"
+                .title(if app.connection_status.is_demo() {
+                    t!("ui.title.demo")
                 } else {
-                    "System Status"
+                    t!("ui.title.system_status")
                 }),
         )
-        .style(Style::default().fg(if app.connection_status.contains("demo") || app.connection_status.contains("sintético") {
+        .style(Style::default().fg(if app.connection_status.is_demo() {
             Color::Yellow
         } else {
             Color::Cyan
@@ -137,8 +447,8 @@ fn render_dashboard(f: &mut Frame, app: &App) {
         .ram
         .as_ref()
         .map(|ram| {
-            let used = bytes_to_mb(ram.used_bytes);
-            let total = bytes_to_mb(ram.total_bytes);
+            let used = in_unit(ram.used_bytes, ram.unit);
+            let total = in_unit(ram.total_bytes, ram.unit);
             let ratio = if ram.total_bytes == 0 {
                 0.0
             } else {
@@ -146,14 +456,15 @@ fn render_dashboard(f: &mut Frame, app: &App) {
             };
             (used, total, ratio, unit_label(ram.unit))
         })
-        .unwrap_or((0, 0, 0.0, "MB"));
+        .unwrap_or(("0".into(), "0".into(), 0.0, "MB"));
 
     // Neon Green for RAM
-    let ram_color = get_neon_pulse(app.tick_count, (0, 255, 0));
+    let ram_color = gauge_color(app, GaugeKind::Ram);
     let ram_gauge = Gauge::default()
         .block(
             Block::default()
                 .title("RAM")
+                .title_bottom(app.extremes.ram.annotation("MB"))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(border_color)),
         )
@@ -167,8 +478,8 @@ fn render_dashboard(f: &mut Frame, app: &App) {
         .swap
         .as_ref()
         .map(|swap| {
-            let used = bytes_to_mb(swap.used_bytes);
-            let total = bytes_to_mb(swap.total_bytes);
+            let used = in_unit(swap.used_bytes, swap.unit);
+            let total = in_unit(swap.total_bytes, swap.unit);
             let ratio = if swap.total_bytes == 0 {
                 0.0
             } else {
@@ -176,10 +487,10 @@ fn render_dashboard(f: &mut Frame, app: &App) {
             };
             (used, total, ratio, unit_label(swap.unit))
         })
-        .unwrap_or((0, 0, 0.0, "MB"));
+        .unwrap_or(("0".into(), "0".into(), 0.0, "MB"));
 
     // Neon Yellow for SWAP
-    let swap_color = get_neon_pulse(app.tick_count, (255, 255, 0));
+    let swap_color = gauge_color(app, GaugeKind::Swap);
     let swap_gauge = Gauge::default()
         .block(
             Block::default()
@@ -200,8 +511,8 @@ fn render_dashboard(f: &mut Frame, app: &App) {
         .map(|iram| {
             format!(
                 "IRAM: {}/{} {}\nLFB: {} MB",
-                bytes_to_mb(iram.used_bytes),
-                bytes_to_mb(iram.total_bytes),
+                in_unit(iram.used_bytes, iram.unit),
+                in_unit(iram.total_bytes, iram.unit),
                 unit_label(iram.unit),
                 bytes_to_mb(iram.lfb_bytes.unwrap_or_default())
             )
@@ -241,7 +552,7 @@ fn render_dashboard(f: &mut Frame, app: &App) {
     // Lightweight clocks/engines summary (EMC/GR3D/NVENC/NVDEC)
     let mut engine_summary = Vec::new();
     for name in ["EMC", "GR3D", "MC", "AXI", "NVENC", "NVDEC"].iter() {
-        if let Some(stat) = app.latest_stats.engines.get(&name.to_string()) {
+        if let Some(stat) = app.latest_stats.engines.get(*name) {
             let usage = stat
                 .usage_percent
                 .map(|v| format!("{v}%"))
@@ -279,11 +590,22 @@ fn render_dashboard(f: &mut Frame, app: &App) {
     );
     f.render_widget(mem_info, mem_chunks[2]);
 
+    // Disk
+    if app.latest_stats.disk.is_some() {
+        let disk = Paragraph::new(Line::from(disk_spans(app))).block(
+            Block::default()
+                .title(t!("ui.title.disk"))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(border_color)),
+        );
+        f.render_widget(disk, chunks[3]);
+    }
+
     // CPU & GPU
     let cpu_gpu_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(70), Constraint::Percentage(30)].as_ref())
-        .split(chunks[3]);
+        .split(chunks[4]);
 
     let cpu_block = Block::default()
         .title("CPU")
@@ -296,38 +618,12 @@ fn render_dashboard(f: &mut Frame, app: &App) {
             vertical: 1,
             horizontal: 1,
         });
-
-        let core_constraints: Vec<Constraint> = (0..app.latest_stats.cpus.len())
-            .map(|_| Constraint::Length(1))
-            .collect();
-
-        let core_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(core_constraints)
-            .split(inner_area);
-
-        for (i, core) in app.latest_stats.cpus.iter().enumerate() {
-            if i < core_chunks.len() {
-                let load = core.load_percent.unwrap_or(0);
-                let freq = core.freq_mhz.unwrap_or(0);
-                let label = format!("Core {}: {}% @ {}MHz", i, load, freq);
-                let ratio = load as f64 / 100.0;
-
-                // Color based on load (Green -> Yellow -> Red) but neon
-                let core_color = if load < 50 {
-                    Color::Rgb(0, 255, 255) // Cyan
-                } else if load < 80 {
-                    Color::Rgb(255, 255, 0) // Yellow
-                } else {
-                    Color::Rgb(255, 0, 255) // Magenta/Red
-                };
-
-                let gauge = Gauge::default()
-                    .gauge_style(Style::default().fg(core_color))
-                    .ratio(ratio)
-                    .label(label);
-                f.render_widget(gauge, core_chunks[i]);
-            }
+        let clusters = cores_by_cluster(&app.latest_stats.cpus);
+        if clusters.len() > 1 {
+            render_cpu_clusters(f, app, inner_area, &clusters);
+        } else {
+            let cores: Vec<usize> = (0..app.latest_stats.cpus.len()).collect();
+            render_core_gauges(f, app, inner_area, &cores);
         }
     }
 
@@ -340,11 +636,12 @@ fn render_dashboard(f: &mut Frame, app: &App) {
     let gpu_load = app.latest_stats.gpu_usage().unwrap_or(0);
     let gpu_ratio = gpu_load as f64 / 100.0;
     // Neon Magenta for GPU
-    let gpu_color = get_neon_pulse(app.tick_count, (255, 0, 255));
+    let gpu_color = gauge_color(app, GaugeKind::Gpu);
     let gpu_gauge = Gauge::default()
         .block(
             Block::default()
                 .title("GPU")
+                .title_bottom(app.extremes.gpu.annotation("%"))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(border_color)),
         )
@@ -392,7 +689,7 @@ fn render_dashboard(f: &mut Frame, app: &App) {
             .border_style(Style::default().fg(border_color)),
     )
     .header(
-        Row::new(vec!["Name", "Usage", "Freq"]).style(
+        Row::new(vec![t!("ui.col.name"), t!("ui.col.usage"), t!("ui.col.freq")]).style(
             Style::default()
                 .fg(Color::White)
                 .add_modifier(Modifier::BOLD),
@@ -404,7 +701,21 @@ fn render_dashboard(f: &mut Frame, app: &App) {
     let bottom_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
-        .split(chunks[4]);
+        .split(chunks[5]);
+    // Network below Power, sized to the interfaces it lists
+    let power_area = if app.latest_stats.network.is_empty() {
+        bottom_chunks[1]
+    } else {
+        let rows = app.latest_stats.network.len().min(4) as u16 + 3;
+        let right = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(rows)])
+            .split(bottom_chunks[1]);
+        render_network(f, right[1], app, border_color);
+        right[0]
+    };
+
+    let delta = app.last_delta();
 
     // Temps Table
     let mut temps: Vec<(&String, &f32)> = app.latest_stats.temps.iter().collect();
@@ -412,31 +723,62 @@ fn render_dashboard(f: &mut Frame, app: &App) {
     let temp_rows: Vec<Row> = temps
         .iter()
         .map(|(k, v)| {
-            let color = if **v > 80.0 {
-                Color::Red
-            } else if **v > 60.0 {
-                Color::Yellow
-            } else {
-                Color::Green
+            let color = level_color(app, app.thresholds.temp_level(k, **v as f64));
+            let arrow = delta
+                .as_ref()
+                .and_then(|d| d.temp_c.get(*k))
+                .map(|c| trend_arrow(*c as f64, 0.5))
+                .unwrap_or(" ");
+            let ext = app.extremes.temps.get(*k);
+            let stat = |f: fn(&crate::extremes::RunningStat) -> String| {
+                ext.map(f).unwrap_or_else(|| "-".to_string())
             };
             Row::new(vec![
                 Span::styled((*k).to_string(), Style::default().fg(Color::Cyan)),
-                Span::styled(format!("{:.1}C", v), Style::default().fg(color)),
+                Span::styled(format!("{:.1}C {}", v, arrow), Style::default().fg(color)),
+                Span::styled(stat(|s| format!("{:.1}C", s.min)), Style::default().fg(Color::Gray)),
+                Span::styled(
+                    stat(|s| {
+                        let at = s.max_at.map(|t| t.format("%H:%M:%S").to_string());
+                        format!("{:.1}C {}", s.max, at.unwrap_or_default())
+                    }),
+                    Style::default().fg(Color::Gray),
+                ),
+                Span::styled(
+                    stat(|s| format!("{:.1}C", s.avg().unwrap_or_default())),
+                    Style::default().fg(Color::Gray),
+                ),
+                trip_span(app, k, **v),
             ])
         })
         .collect();
     let temp_table = Table::new(
         temp_rows,
-        [Constraint::Percentage(50), Constraint::Percentage(50)],
+        [
+            Constraint::Min(5),
+            Constraint::Length(8),
+            Constraint::Length(6),
+            Constraint::Length(15),
+            Constraint::Length(6),
+            Constraint::Length(18),
+        ],
     )
     .block(
         Block::default()
-            .title("Temperatures")
+            .title(t!("ui.title.temperatures"))
             .borders(Borders::ALL)
             .border_style(Style::default().fg(border_color)),
     )
     .header(
-        Row::new(vec!["Sensor", "Temp"]).style(
+        Row::new(vec![
+            "Sensor",
+            "Temp",
+            "Min",
+            t!("ui.col.max_at"),
+            t!("ui.col.avg"),
+            "Trip (pas┃crit)",
+        ])
+        .style(
             Style::default()
                 .fg(Color::White)
                 .add_modifier(Modifier::BOLD),
@@ -451,10 +793,15 @@ fn render_dashboard(f: &mut Frame, app: &App) {
     let power_rows: Vec<Row> = power_entries
         .iter()
         .map(|(k, rail)| {
+            let arrow = delta
+                .as_ref()
+                .and_then(|d| d.power_mw.get(*k))
+                .map(|c| trend_arrow(*c as f64, 50.0))
+                .unwrap_or(" ");
             Row::new(vec![
                 Span::styled((*k).to_string(), Style::default().fg(Color::Magenta)),
                 Span::styled(
-                    format!("{}mW", rail.current_mw),
+                    format!("{}mW {}", rail.current_mw, arrow),
                     Style::default().fg(Color::White),
                 ),
                 Span::styled(
@@ -463,6 +810,30 @@ fn render_dashboard(f: &mut Frame, app: &App) {
                 ),
             ])
         })
+        .chain(app.latest_stats.supplies.iter().map(|supply| {
+            let low = supply.is_low(crate::power_supply::low_battery_threshold());
+            let draw = supply
+                .discharge_mw()
+                .or(supply.power_mw)
+                .map(|mw| format!("{}mW", mw))
+                .or_else(|| supply.voltage_mv.map(|mv| format!("{:.2}V", mv as f64 / 1000.0)))
+                .unwrap_or_else(|| "-".to_string());
+            let soc = match (supply.capacity_percent, supply.online) {
+                (Some(pct), _) if low => format!("{}% {}", pct, t!("ui.supply.low")),
+                (Some(pct), _) => format!("{}%", pct),
+                (None, Some(true)) => "online".to_string(),
+                (None, Some(false)) => "offline".to_string(),
+                (None, None) => "-".to_string(),
+            };
+            Row::new(vec![
+                Span::styled(supply.name.clone(), Style::default().fg(Color::Yellow)),
+                Span::styled(draw, Style::default().fg(Color::White)),
+                Span::styled(
+                    soc,
+                    Style::default().fg(if low { Color::Red } else { Color::Gray }),
+                ),
+            ])
+        }))
         .collect();
     let power_table = Table::new(
         power_rows,
@@ -474,24 +845,243 @@ fn render_dashboard(f: &mut Frame, app: &App) {
     )
     .block(
         Block::default()
-            .title("Power")
+            .title(power_title(app))
             .borders(Borders::ALL)
             .border_style(Style::default().fg(border_color)),
     )
     .header(
-        Row::new(vec!["Rail", "Current", "Avg"]).style(
+        Row::new(vec!["Rail", t!("ui.col.current"), t!("ui.col.avg")]).style(
             Style::default()
                 .fg(Color::White)
                 .add_modifier(Modifier::BOLD),
         ),
     );
-    f.render_widget(power_table, bottom_chunks[1]);
+    f.render_widget(power_table, power_area);
 
     if app.show_help {
         render_help(f);
     }
 }
 
+/// Core indexes per CPU cluster, in cluster order; cores without a cluster come last.
+fn cores_by_cluster(cpus: &[crate::parser::CpuCore]) -> Vec<(Option<u32>, Vec<usize>)> {
+    let mut groups: Vec<(Option<u32>, Vec<usize>)> = Vec::new();
+    for (i, core) in cpus.iter().enumerate() {
+        match groups.iter_mut().find(|(cluster, _)| *cluster == core.cluster) {
+            Some((_, cores)) => cores.push(i),
+            None => groups.push((core.cluster, vec![i])),
+        }
+    }
+    groups.sort_by_key(|(cluster, _)| cluster.unwrap_or(u32::MAX));
+    groups
+}
+
+/// One column per cluster: a header with its highest core clock, then its cores.
+fn render_cpu_clusters(f: &mut Frame, app: &App, area: Rect, clusters: &[(Option<u32>, Vec<usize>)]) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, clusters.len() as u32); clusters.len()])
+        .split(area);
+    for ((cluster, cores), column) in clusters.iter().zip(columns.iter()) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(*column);
+        let max_mhz = cores
+            .iter()
+            .filter_map(|&i| app.latest_stats.cpus[i].freq_mhz)
+            .max();
+        let name = match cluster {
+            Some(n) => format!("Cluster {n}"),
+            None => "Other".to_string(),
+        };
+        let header = match max_mhz {
+            Some(mhz) => format!("{name} · max {mhz}MHz"),
+            None => name,
+        };
+        f.render_widget(
+            Paragraph::new(header).style(Style::default().add_modifier(Modifier::BOLD)),
+            rows[0],
+        );
+        render_core_gauges(f, app, rows[1], cores);
+    }
+}
+
+/// A one-line load gauge per core, top to bottom.
+fn render_core_gauges(f: &mut Frame, app: &App, area: Rect, cores: &[usize]) {
+    let core_constraints: Vec<Constraint> = cores.iter().map(|_| Constraint::Length(1)).collect();
+    let core_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(core_constraints)
+        .split(area);
+
+    for (&i, chunk) in cores.iter().zip(core_chunks.iter()) {
+        let core = &app.latest_stats.cpus[i];
+        let load = core.load_percent.unwrap_or(0);
+        let freq = core.freq_mhz.unwrap_or(0);
+        let label = format!("Core {}: {}% @ {}MHz", i, load, freq);
+        let ratio = load as f64 / 100.0;
+
+        // Color based on load (Green -> Yellow -> Red) but neon
+        let core_color = match app.thresholds.cpu.level(load as f64) {
+            Level::Normal => Color::Rgb(0, 255, 255), // Cyan
+            Level::Warning => Color::Rgb(255, 255, 0), // Yellow
+            Level::Critical => Color::Rgb(255, 0, 255), // Magenta/Red
+        };
+
+        let gauge = Gauge::default()
+            .gauge_style(Style::default().fg(core_color))
+            .ratio(ratio)
+            .label(label);
+        f.render_widget(gauge, *chunk);
+    }
+}
+
+/// One line of mount usage ("/ 82% 47.1/58.0 GB") then device throughput.
+fn disk_spans(app: &App) -> Vec<Span<'static>> {
+    let Some(disk) = &app.latest_stats.disk else {
+        return Vec::new();
+    };
+    let mut spans = Vec::new();
+    for mount in disk.mounts.iter() {
+        let pct = mount.used_percent();
+        let level = match pct {
+            p if p >= DISK_CRITICAL_PERCENT => Level::Critical,
+            p if p >= DISK_WARNING_PERCENT => Level::Warning,
+            _ => Level::Normal,
+        };
+        spans.push(Span::styled(
+            format!("{} ", mount.mount_point),
+            Style::default().fg(Color::Cyan),
+        ));
+        spans.push(Span::styled(
+            format!("{:.0}% ", pct),
+            Style::default().fg(level_color(app, level)),
+        ));
+        spans.push(Span::styled(
+            format!(
+                "{}/{}  ",
+                format_bytes(mount.used_bytes()),
+                format_bytes(mount.total_bytes)
+            ),
+            Style::default().fg(Color::Gray),
+        ));
+    }
+    for dev in disk.devices.iter() {
+        spans.push(Span::styled(
+            format!("{} ", dev.name),
+            Style::default().fg(Color::Magenta),
+        ));
+        spans.push(Span::styled(
+            format!(
+                "R {} W {}  ",
+                format_byte_rate(dev.read_bytes_per_sec),
+                format_byte_rate(dev.write_bytes_per_sec)
+            ),
+            Style::default().fg(Color::White),
+        ));
+    }
+    spans
+}
+
+/// Network panel: per-interface rates and totals since boot.
+fn render_network(f: &mut Frame, area: Rect, app: &App, border_color: Color) {
+    let rows: Vec<Row> = app
+        .latest_stats
+        .network
+        .iter()
+        .map(|iface| {
+            Row::new(vec![
+                Span::styled(iface.name.clone(), Style::default().fg(Color::Cyan)),
+                Span::styled(
+                    format_byte_rate(iface.rx_bytes_per_sec),
+                    Style::default().fg(Color::Green),
+                ),
+                Span::styled(
+                    format_byte_rate(iface.tx_bytes_per_sec),
+                    Style::default().fg(Color::Magenta),
+                ),
+                Span::styled(
+                    format!(
+                        "{}/{}",
+                        format_bytes(iface.rx_bytes),
+                        format_bytes(iface.tx_bytes)
+                    ),
+                    Style::default().fg(Color::Gray),
+                ),
+            ])
+        })
+        .collect();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(25),
+            Constraint::Percentage(22),
+            Constraint::Percentage(22),
+            Constraint::Percentage(31),
+        ],
+    )
+    .block(
+        Block::default()
+            .title(t!("ui.title.network"))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color)),
+    )
+    .header(
+        Row::new(vec![
+            t!("ui.col.interface"),
+            t!("ui.col.rx"),
+            t!("ui.col.tx"),
+            t!("ui.col.total"),
+        ])
+        .style(
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+    );
+    f.render_widget(table, area);
+}
+
+/// Bar from 0°C to the sensor's critical trip plus degrees left to the next
+/// trip ("pas"ive throttling, then "crit"ical); "-" without sysfs trip points.
+fn trip_span(app: &App, sensor: &str, temp: f32) -> Span<'static> {
+    let Some(trips) = crate::trip_points::lookup(&app.trip_points, sensor) else {
+        return Span::styled("-", Style::default().fg(Color::DarkGray));
+    };
+    let (Some(bar), Some((left, critical))) = (trips.bar(temp, 8), trips.headroom(temp)) else {
+        return Span::styled("-", Style::default().fg(Color::DarkGray));
+    };
+    let color = match (critical, left) {
+        (true, _) => Color::Red,
+        (false, l) if l < 10.0 => Color::Yellow,
+        _ => Color::Green,
+    };
+    let label = if critical { "crit" } else { "pas" };
+    Span::styled(
+        format!("{} {:.0}C {}", bar, left, label),
+        Style::default().fg(color),
+    )
+}
+
+/// Power title plus energy since start and, when a tariff is configured, cost/CO2 estimates.
+fn power_title(app: &App) -> String {
+    let wh = app.energy.input_wh();
+    if wh <= 0.0 {
+        return t!("ui.title.power").to_string();
+    }
+    let mut title = format!("{} | {:.3} Wh", t!("ui.title.power"), wh);
+    if let Some(tariff) = &app.tariff {
+        if let Some(cost) = tariff.cost(wh) {
+            title.push_str(&format!(" | {:.4} {}", cost, tariff.currency));
+        }
+        if let Some(grams) = tariff.co2_grams(wh) {
+            title.push_str(&format!(" | {:.1} gCO2", grams));
+        }
+    }
+    title
+}
+
 fn render_trends(f: &mut Frame, area: ratatui::layout::Rect, app: &App, border_color: Color) {
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
@@ -505,13 +1095,11 @@ fn render_trends(f: &mut Frame, area: ratatui::layout::Rect, app: &App, border_c
         )
         .split(area);
 
-    let source = Paragraph::new(format!(
-        "Source: {} | {}",
-        app.source_label, app.connection_status
-    ))
+    let status = app.connection_status.label();
+    let source = Paragraph::new(t!("ui.source", source = app.source_label, status = status))
     .block(
         Block::default()
-            .title(format!("Fuente/Conexión [{}]", app.connection_status))
+            .title(t!("ui.title.source", status = status))
             .borders(Borders::ALL)
             .border_style(Style::default().fg(connection_color(&app.connection_status))),
     )
@@ -521,9 +1109,9 @@ fn render_trends(f: &mut Frame, area: ratatui::layout::Rect, app: &App, border_c
     // Controls status (read-only for now)
     let ctrl = app.control.status();
     let ctrl_lines = vec![
-        Line::from(format!(
-            "available: {}",
-            if ctrl.available { "yes" } else { "no" }
+        Line::from(t!(
+            "ui.ctrl.available",
+            value = if ctrl.available { t!("common.yes") } else { t!("common.no") }
         )),
         Line::from(format!(
             "jetson_clocks: {}",
@@ -536,23 +1124,30 @@ fn render_trends(f: &mut Frame, area: ratatui::layout::Rect, app: &App, border_c
             ctrl.nvpmodel.clone().unwrap_or_else(|| "n/a".to_string())
         )),
         Line::from(format!(
-            "fan: {}",
-            ctrl.fan.clone().unwrap_or_else(|| "n/a".to_string())
+            "fan: {}{}",
+            ctrl.fan.clone().unwrap_or_else(|| "n/a".to_string()),
+            ctrl.fan_curve.as_ref().map(|c| format!(" ({})", t!("ui.clocks.fan_curve", curve = c))).unwrap_or_default(),
         )),
-        Line::from(format!(
-            "modes: {}",
-            if ctrl.nvpmodel_modes.is_empty() {
+        Line::from(t!(
+            "ui.ctrl.modes",
+            modes = if ctrl.nvpmodel_modes.is_empty() {
                 "n/a".to_string()
             } else {
                 ctrl.nvpmodel_modes.join(", ")
             }
         )),
         Line::from(ctrl.note.clone()),
-        Line::from(ctrl.last_error.clone().unwrap_or_else(|| "OK".to_string())),
+        match &app.control_pending {
+            Some(pending) => Line::from(Span::styled(
+                t!("ui.ctrl.pending", action = pending.label),
+                Style::default().fg(Color::Yellow),
+            )),
+            None => Line::from(ctrl.last_error.clone().unwrap_or_else(|| "OK".to_string())),
+        },
     ];
     let ctrl_widget = Paragraph::new(ctrl_lines).block(
         Block::default()
-            .title("Controles (c/m/f)")
+            .title(t!("ui.title.controls_keys"))
             .borders(Borders::ALL)
             .border_style(Style::default().fg(border_color)),
     );
@@ -561,108 +1156,352 @@ fn render_trends(f: &mut Frame, area: ratatui::layout::Rect, app: &App, border_c
     let trend_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Percentage(33),
-                Constraint::Percentage(33),
-                Constraint::Percentage(34),
-            ])
-            .split(chunks[2]);
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
+        ])
+        .split(chunks[2]);
 
-    // Filter history by time window
-    use std::time::Instant;
-    let now = Instant::now();
-    let window_secs = app.history_window.duration_secs();
     let window_label = app.history_window.label();
-    
-    let filter_by_window = |data: &std::collections::VecDeque<(Instant, f64)>| -> Vec<u64> {
-        data.iter()
-            .filter(|(timestamp, _)| now.duration_since(*timestamp).as_secs() <= window_secs)
-            .map(|(_, value)| *value as u64)
-            .collect()
+    for (series, area) in trend_series(app).into_iter().zip(trend_chunks.iter()) {
+        let mut block = Block::default()
+            .title(format!("{} [{}]", series.name, window_label))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color));
+        if let Some(stat) = series.summary {
+            let unit = series.unit;
+            block = block.title_bottom(format!(
+                "⌀{:.0}{unit} ▲{:.0}{unit} p95 {:.0}{unit}",
+                stat.mean, stat.max, stat.p95
+            ));
+        }
+        render_trend(f, *area, app, block, &series);
+    }
+}
+
+/// One history panel: (age in seconds, value) pairs inside the window, oldest first.
+struct TrendSeries {
+    name: String,
+    data: Vec<(f64, f64)>,
+    color: Color,
+    ceiling: f64,
+    /// Suffix for the y-axis labels
+    unit: &'static str,
+    /// Mean/max/p95 over the window, from samples since the TUI started
+    summary: Option<crate::stats::SeriesSummary>,
+}
+
+/// RAM, GPU, CPU average and hottest sensor over the selected history window.
+fn trend_series(app: &App) -> [TrendSeries; 4] {
+    let filter_by_window = |data| window_ages(app, data);
+
+    let temp_data = filter_by_window(&app.history.temp);
+    let temp_ceiling = temp_data.iter().fold(100.0f64, |acc, (_, t)| acc.max(*t));
+    let summary = app
+        .summaries
+        .summary(app.history_window.duration_secs(), crate::history::now_unix_ms());
+    let series = |name: &str, data, color, ceiling, unit, summary| TrendSeries {
+        name: name.to_string(),
+        data,
+        color,
+        ceiling,
+        unit,
+        summary,
     };
-    
-    let ram_data = filter_by_window(&app.history.ram);
-    let gpu_data = filter_by_window(&app.history.gpu);
-    let cpu_data = filter_by_window(&app.history.cpu);
+    [
+        series("RAM", filter_by_window(&app.history.ram), Color::Green, 100.0, "%", summary.ram),
+        series("GPU", filter_by_window(&app.history.gpu), Color::Magenta, 100.0, "%", summary.gpu),
+        series(t!("ui.trend.cpu_avg"), filter_by_window(&app.history.cpu), Color::Cyan, 100.0, "%", summary.cpu),
+        series(t!("ui.trend.temp_max"), temp_data, Color::Red, temp_ceiling, "°", summary.hottest),
+    ]
+}
 
-    let sparkline_ram = Sparkline::default()
-        .block(
-            Block::default()
-                .title(format!("RAM [{}]", window_label))
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(border_color)),
-        )
-        .data(&ram_data)
-        .style(Style::default().fg(Color::Green));
+/// (age in seconds, value) pairs of `data` inside the selected history window.
+fn window_ages(app: &App, data: &std::collections::VecDeque<(std::time::Instant, f64)>) -> Vec<(f64, f64)> {
+    let now = std::time::Instant::now();
+    let window_secs = app.history_window.duration_secs() as f64;
+    data.iter()
+        .map(|(timestamp, value)| (now.duration_since(*timestamp).as_secs_f64(), *value))
+        .filter(|(age, _)| *age <= window_secs)
+        .collect()
+}
 
-    let sparkline_gpu = Sparkline::default()
-        .block(
-            Block::default()
-                .title(format!("GPU [{}]", window_label))
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(border_color)),
-        )
-        .data(&gpu_data)
-        .style(Style::default().fg(Color::Magenta));
+/// How long ago `secs` was, for time axis ticks: "-5m", "-2m30s", "-12h".
+fn ago_label(secs: u64) -> String {
+    if secs == 0 {
+        return t!("ui.trend.now").to_string();
+    }
+    let (h, m, s) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    let mut out = String::from("-");
+    for (value, unit) in [(h, "h"), (m, "m"), (s, "s")] {
+        if value > 0 {
+            out.push_str(&format!("{value}{unit}"));
+        }
+    }
+    out
+}
 
-    let sparkline_cpu = Sparkline::default()
-        .block(
-            Block::default()
-                .title(format!("CPU avg [{}]", window_label))
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(border_color)),
-        )
-        .data(&cpu_data)
-        .style(Style::default().fg(Color::Cyan));
+fn render_trend(f: &mut Frame, area: Rect, app: &App, block: Block, series: &TrendSeries) {
+    // x runs from -window (left) to now (right)
+    let window_secs = app.history_window.duration_secs();
+    let points: Vec<(f64, f64)> = series
+        .data
+        .iter()
+        .map(|(age, value)| (-age, *value))
+        .collect();
+    let (marker, graph_type) = match app.graph_style {
+        GraphStyle::Braille => (Marker::Braille, GraphType::Line),
+        GraphStyle::Bars => (Marker::Bar, GraphType::Bar),
+    };
+    let dataset = Dataset::default()
+        .marker(marker)
+        .graph_type(graph_type)
+        .style(Style::default().fg(series.color))
+        .data(&points);
+    let mut x_axis = Axis::default().bounds([-(window_secs as f64), 0.0]);
+    let mut y_axis = Axis::default().bounds([0.0, series.ceiling]);
+    // Labels take a row and a few columns; one-line panels keep the whole area for the plot
+    if area.height >= 6 {
+        let axis_style = Style::default().fg(Color::DarkGray);
+        x_axis = x_axis
+            .labels([window_secs, window_secs / 2, 0].map(ago_label))
+            .style(axis_style);
+        y_axis = y_axis
+            .labels([0.0, series.ceiling / 2.0, series.ceiling].map(|v| format!("{v:.0}{}", series.unit)))
+            .style(axis_style);
+    }
+    let chart = Chart::new(vec![dataset])
+        .block(block)
+        .x_axis(x_axis)
+        .y_axis(y_axis);
+    f.render_widget(chart, area);
+}
 
-    f.render_widget(sparkline_ram, trend_chunks[0]);
-    f.render_widget(sparkline_gpu, trend_chunks[1]);
-    f.render_widget(sparkline_cpu, trend_chunks[2]);
+/// "RTT 1.2ms · 1.0/s · late 0 · dropped 0"; red while no samples arrive.
+fn link_span(app: &App) -> Span<'static> {
+    let link = &app.link;
+    let rtt = link
+        .rtt
+        .map(|d| format!("{:.1}ms", d.as_secs_f64() * 1000.0))
+        .unwrap_or_else(|| "-".to_string());
+    let rate = link.samples_per_sec(std::time::Instant::now());
+    let text = t!(
+        "ui.link",
+        rtt = rtt,
+        rate = format!("{:.1}", rate),
+        late = link.late,
+        dropped = link.dropped
+    );
+    let color = if rate == 0.0 {
+        Color::Red
+    } else if link.late > 0 || link.dropped > 0 {
+        Color::Yellow
+    } else {
+        Color::Green
+    };
+    Span::styled(text, Style::default().fg(color))
 }
 
-#[allow(dead_code)]
-fn history_to_u64(data: &[f64]) -> Vec<u64> {
-    data.iter()
-        .map(|v| if *v < 0.0 { 0 } else { v.round() as u64 })
-        .collect()
+/// Red banner while the snapshot reports throttling (hot sensors, capped clocks).
+fn throttle_span(app: &App) -> Option<Span<'static>> {
+    let state = app.latest_stats.throttling.as_ref()?;
+    Some(Span::styled(
+        format!(" {} ", t!("ui.throttling", reasons = state.summary())),
+        Style::default()
+            .fg(Color::White)
+            .bg(Color::Red)
+            .add_modifier(Modifier::BOLD),
+    ))
 }
 
-fn connection_color(status: &str) -> Color {
-    if status.contains("conectado") && !status.contains("offline") {
-        Color::Green
-    } else if status.contains("reintentando") || status.contains("timeout") {
-        Color::Yellow
-    } else if status.contains("offline") || status.contains("error") {
-        Color::Red
-    } else if status.contains("demo") || status.contains("sintético") {
-        Color::Gray
+fn connection_color(status: &ConnectionStatus) -> Color {
+    match status {
+        ConnectionStatus::Connected | ConnectionStatus::ConnectedSocket => Color::Green,
+        ConnectionStatus::Retrying(_) | ConnectionStatus::NoData => Color::Yellow,
+        ConnectionStatus::Offline | ConnectionStatus::Error(_) => Color::Red,
+        ConnectionStatus::Demo => Color::Gray,
+        ConnectionStatus::Connecting | ConnectionStatus::Reconnecting => Color::Cyan,
+    }
+}
+
+/// One-line status in the bottom margin left free by every view.
+fn render_footer(f: &mut Frame, app: &App) {
+    let area = f.area();
+    if area.height < 2 || area.width < 4 {
+        return;
+    }
+    let refresh = app.refresh_interval();
+    let rate = if refresh.as_millis() < 1000 {
+        format!("{}ms", refresh.as_millis())
     } else {
-        Color::Cyan // conectando
+        format!("{:.0}s", refresh.as_secs_f64())
+    };
+    let mut spans = vec![
+        Span::styled(
+            format!(
+                " {} ",
+                t!("ui.footer.refresh", rate = rate, tick = app.tick_interval().as_millis())
+            ),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::styled(
+            if is_compact(f, app) {
+                "+/- ^P h ".to_string()
+            } else {
+                format!("{} ", t!("ui.footer.keys"))
+            },
+            Style::default().fg(Color::DarkGray),
+        ),
+    ];
+    if let Some(burst) = app.burst {
+        let text = match burst {
+            BurstState::Requested { .. } => t!("ui.burst.starting").to_string(),
+            BurstState::Running { id, until } => t!(
+                "ui.burst.running",
+                id = id,
+                ms = app.burst_config.interval_ms,
+                secs = until
+                    .saturating_duration_since(std::time::Instant::now())
+                    .as_secs_f64()
+                    .ceil()
+            ),
+            BurstState::Fetching { id, .. } => t!("ui.burst.fetching", id = id),
+        } + " ";
+        spans.push(Span::styled(text, Style::default().fg(Color::Magenta)));
     }
+    // Action results stay visible for a few seconds
+    if let Some((notice, at)) = &app.notice {
+        if at.elapsed() < std::time::Duration::from_secs(5) {
+            spans.push(Span::styled(notice.clone(), Style::default().fg(Color::Yellow)));
+        }
+    }
+    let footer = Paragraph::new(Line::from(spans));
+    let footer_area = Rect {
+        x: area.x + 1,
+        y: area.bottom() - 1,
+        width: area.width - 2,
+        height: 1,
+    };
+    f.render_widget(footer, footer_area);
 }
 
-fn render_help(f: &mut Frame) {
-    let area = centered_rect(70, 60, f.area());
-    let help_text = vec![
-        Line::from("Teclas:"),
-        Line::from("  q: salir"),
-        Line::from("  h: toggle ayuda"),
-        Line::from("  v: ciclo de vista (Dashboard/Procesos/GPU/Clocks)"),
-        Line::from("  s: ordenar procesos (CPU/Mem)"),
-        Line::from("  r: reconectar al socket"),
+fn render_confirm(f: &mut Frame, question: &str, detail: &str) {
+    let area = centered_rect(70, 30, f.area());
+    let lines = vec![
+        Line::from(Span::styled(
+            format!("{question} {}", t!("ui.confirm.default_no")),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
         Line::from(""),
-        Line::from("Controles (requieren daemon):"),
-        Line::from("  c: toggle jetson_clocks"),
-        Line::from("  m: cambiar nvpmodel"),
-        Line::from("  f: fan 80% (demo)"),
+        Line::from(Span::styled(
+            detail.to_string(),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
         Line::from(""),
-        Line::from("Conexión:"),
-        Line::from("  Socket: /tmp/jetsonscope.sock (legacy: /tmp/tegrastats.sock)"),
-        Line::from("  Fallback: modo sintético si socket no disponible"),
-        Line::from("  Estados: conectado (verde), reintentando (amarillo),"),
-        Line::from("           offline (rojo), demo (gris)"),
+        Line::from(Span::styled(
+            t!("ui.confirm.keys"),
+            Style::default().fg(Color::Gray),
+        )),
     ];
     let block = Block::default()
-        .title("Ayuda")
+        .title(t!("ui.confirm.title"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red));
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(ratatui::widgets::Wrap { trim: true }),
+        area,
+    );
+}
+
+/// Memory breakdown of one process, sizes in MiB with a bar against RSS.
+fn render_process_detail(f: &mut Frame, detail: &processes::ProcessDetail) {
+    let area = centered_rect(60, 50, f.area());
+    let bar_width = area.width.saturating_sub(34) as u64;
+    let mut lines = vec![Line::from("")];
+    for (label, kb) in detail.memory_rows() {
+        let row = match kb {
+            Some(kb) => {
+                let filled = (kb * bar_width).checked_div(detail.rss_kb).unwrap_or(0).min(bar_width);
+                vec![
+                    Span::styled(format!(" {label:<12}"), Style::default().fg(Color::White)),
+                    Span::styled(
+                        format!("{:>9.1} MiB ", kb as f64 / 1024.0),
+                        Style::default().fg(Color::Magenta),
+                    ),
+                    Span::styled("█".repeat(filled as usize), Style::default().fg(Color::Cyan)),
+                ]
+            }
+            None => vec![
+                Span::styled(format!(" {label:<12}"), Style::default().fg(Color::White)),
+                Span::styled(t!("proc.mem.needs_root"), Style::default().fg(Color::DarkGray)),
+            ],
+        };
+        lines.push(Line::from(row));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        t!("ui.proc.detail_keys"),
+        Style::default().fg(Color::Gray),
+    )));
+    let block = Block::default()
+        .title(t!("ui.proc.detail_title", name = detail.name, pid = detail.pid))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn render_palette(f: &mut Frame, app: &App, palette: &crate::palette::Palette) {
+    let area = centered_rect(60, 60, f.area());
+    let entries = app.palette_entries();
+    let matches = palette.filter(&entries);
+    let visible = area.height.saturating_sub(4) as usize;
+    // Keep the highlighted row in view
+    let first = palette.selected.saturating_sub(visible.saturating_sub(1));
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(Color::Cyan)),
+            Span::styled(palette.query.clone(), Style::default().fg(Color::White)),
+            Span::styled("█", Style::default().fg(Color::Gray)),
+        ]),
+        Line::from(""),
+    ];
+    for (i, entry) in matches.iter().enumerate().skip(first).take(visible) {
+        let style = if i == palette.selected {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        lines.push(Line::from(Span::styled(format!(" {} ", entry.label), style)));
+    }
+    if matches.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!(" {}", t!("ui.palette.no_matches")),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    let block = Block::default()
+        .title(t!("ui.palette.title"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn render_help(f: &mut Frame) {
+    let area = centered_rect(70, 60, f.area());
+    let help_text: Vec<Line> = t!("ui.help.text").lines().map(Line::from).collect();
+    let block = Block::default()
+        .title(t!("ui.help.title"))
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Cyan));
     let para = Paragraph::new(help_text).block(block).style(Style::default().fg(Color::White));
@@ -698,48 +1537,63 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     vertical[1]
 }
 
-fn render_processes_view(f: &mut Frame, app: &App) {
+fn render_processes_view(f: &mut Frame, app: &mut App) {
+    let compact = is_compact(f, app);
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .margin(1)
+        .margin(if compact { 0 } else { 1 })
         .constraints([
-            Constraint::Length(3),  // Header
-            Constraint::Min(0),     // Process table
+            Constraint::Length(if compact { 1 } else { 3 }), // Header
+            Constraint::Min(0),                              // Process table
+            Constraint::Length(if compact { 1 } else { 0 }), // Footer row
         ])
         .split(f.area());
 
     // Header
-    let border_color = get_rainbow_color(app.tick_count, 0);
-    let header = Paragraph::new("Vista de Procesos - Top CPU/Memoria")
-        .block(
+    let border_color = accent_color(app, 0);
+    let header = if compact {
+        Paragraph::new(t!("ui.proc.header_compact"))
+    } else {
+        Paragraph::new(t!("ui.proc.header")).block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(border_color))
-                .title("Procesos"),
+                .title(t!("ui.proc.title")),
         )
-        .style(Style::default().fg(Color::Cyan));
+    }
+    .style(Style::default().fg(Color::Cyan));
     f.render_widget(header, chunks[0]);
 
-    // Process table
-    let mut monitor = ProcessMonitor::new();
-    let top_processes = monitor.top_processes(15, app.process_sort_by_mem);
-    
-    let rows: Vec<Row> = top_processes
+    if app.process_by_user {
+        app.selected_process = None;
+        render_user_table(f, app, chunks[1], compact);
+        return;
+    }
+
+    // Process table: every process, scrolled to keep the selection in view
+    let rows: Vec<Row> = app
+        .processes
         .iter()
         .map(|p| {
-            let cpu_color = if p.cpu_usage > 50.0 {
-                Color::Red
-            } else if p.cpu_usage > 25.0 {
-                Color::Yellow
-            } else {
-                Color::Green
-            };
+            let gpu_usage = app.gpu_load_by_pid.get(&p.pid).copied();
+            let cpu_color = level_color(app, app.thresholds.process.level(p.cpu_usage as f64));
             
             Row::new(vec![
                 Span::styled(p.pid.to_string(), Style::default().fg(Color::Cyan)),
                 Span::styled(p.name.clone(), Style::default().fg(Color::White)),
                 Span::styled(format!("{:.1}%", p.cpu_usage), Style::default().fg(cpu_color)),
+                match gpu_usage {
+                    Some(gpu) => Span::styled(
+                        format!("{:.1}%", gpu),
+                        Style::default().fg(level_color(app, app.thresholds.engine_level("GR3D", gpu as f64))),
+                    ),
+                    None => Span::styled("-", Style::default().fg(Color::DarkGray)),
+                },
                 Span::styled(format!("{} MB", p.memory_kb / 1024), Style::default().fg(Color::Magenta)),
+                Span::styled(
+                    format_io_rate(p.read_bytes_per_sec, p.write_bytes_per_sec),
+                    Style::default().fg(Color::Yellow),
+                ),
                 Span::styled(p.user.clone().unwrap_or_else(|| "-".to_string()), Style::default().fg(Color::Gray)),
                 Span::styled(
                     p.threads
@@ -751,51 +1605,210 @@ fn render_processes_view(f: &mut Frame, app: &App) {
         })
         .collect();
 
-    let table = Table::new(
-        rows,
-        [
-            Constraint::Length(8),
-            Constraint::Percentage(32),
-            Constraint::Length(10),
-            Constraint::Length(14),
-            Constraint::Length(10),
-            Constraint::Length(8),
-        ],
-    )
-    .block(
-        Block::default()
-            .title("Top Procesos")
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(border_color)),
-    )
-    .header(
-        Row::new(vec![
-            "PID",
-            "Nombre",
-            if app.process_sort_by_mem { "CPU (▲)" } else { "CPU" },
-            if app.process_sort_by_mem { "Memoria (▼)" } else { "Memoria" },
-            "UID",
-            "Threads",
-        ])
-        .style(Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
-    );
-    
-    f.render_widget(table, chunks[1]);
+    let header_row = Row::new(vec![
+        "PID".to_string(),
+        t!("ui.col.name").to_string(),
+        sort_header(app, "CPU", ProcessSort::Cpu),
+        "GPU".to_string(),
+        sort_header(app, t!("ui.col.memory"), ProcessSort::Memory),
+        sort_header(app, t!("ui.col.io"), ProcessSort::Io),
+        t!("ui.col.user").to_string(),
+        t!("ui.col.threads").to_string(),
+    ])
+    .style(Style::default().fg(Color::White).add_modifier(Modifier::BOLD));
+
+    let table = if compact {
+        // User and threads do not fit; zero-width columns drop them
+        Table::new(
+            rows,
+            [
+                Constraint::Length(7),
+                Constraint::Min(10),
+                Constraint::Length(8),
+                Constraint::Length(6),
+                Constraint::Length(9),
+                Constraint::Length(11),
+                Constraint::Length(0),
+                Constraint::Length(0),
+            ],
+        )
+        .column_spacing(1)
+    } else {
+        Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Percentage(28),
+                Constraint::Length(10),
+                Constraint::Length(7),
+                Constraint::Length(14),
+                Constraint::Length(13),
+                Constraint::Length(10),
+                Constraint::Length(8),
+            ],
+        )
+        .block(
+            Block::default()
+                .title(t!("ui.proc.top"))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(border_color)),
+        )
+    }
+    .header(header_row)
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    // Rows start below the border (full layout) and the header row
+    let inner = if compact {
+        chunks[1]
+    } else {
+        chunks[1].inner(ratatui::layout::Margin::new(1, 1))
+    };
+    app.click_areas.process_rows = Some(Rect {
+        y: inner.y + 1,
+        height: inner.height.saturating_sub(1),
+        ..inner
+    });
+    let count = app.processes.len();
+    if let Some(selected) = app.process_table.selected() {
+        app.process_table.select((count > 0).then(|| selected.min(count - 1)));
+    }
+    app.selected_process = app
+        .process_table
+        .selected()
+        .and_then(|i| app.processes.get(i))
+        .map(|p| (p.pid, p.name.clone()));
+    f.render_stateful_widget(table, chunks[1], &mut app.process_table);
+}
+
+/// One row per user: process count, summed CPU and memory.
+fn render_user_table(f: &mut Frame, app: &App, area: Rect, compact: bool) {
+    let users = processes::usage_by_user(&app.processes, app.process_sort);
+    let rows: Vec<Row> = users
+        .iter()
+        .map(|u| {
+            Row::new(vec![
+                Span::styled(u.user.clone(), Style::default().fg(Color::Cyan)),
+                Span::styled(u.processes.to_string(), Style::default().fg(Color::White)),
+                Span::styled(
+                    format!("{:.1}%", u.cpu_usage),
+                    Style::default().fg(level_color(app, app.thresholds.process.level(u.cpu_usage as f64))),
+                ),
+                Span::styled(format_memory_kb(u.memory_kb), Style::default().fg(Color::Magenta)),
+                Span::styled(
+                    format_io_rate(u.read_bytes_per_sec, u.write_bytes_per_sec),
+                    Style::default().fg(Color::Yellow),
+                ),
+            ])
+        })
+        .collect();
+    let header_row = Row::new(vec![
+        t!("ui.col.user").to_string(),
+        t!("ui.col.processes").to_string(),
+        sort_header(app, "CPU", ProcessSort::Cpu),
+        sort_header(app, t!("ui.col.memory"), ProcessSort::Memory),
+        sort_header(app, t!("ui.col.io"), ProcessSort::Io),
+    ])
+    .style(Style::default().fg(Color::White).add_modifier(Modifier::BOLD));
+    let widths = [
+        Constraint::Min(12),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(12),
+        Constraint::Length(13),
+    ];
+    let table = Table::new(rows, widths).header(header_row);
+    let table = if compact {
+        table.column_spacing(1)
+    } else {
+        table.block(
+            Block::default()
+                .title(t!("ui.proc.by_user"))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(accent_color(app, 0))),
+        )
+    };
+    f.render_widget(table, area);
+}
+
+/// Column title, marked when the table is sorted by it.
+fn sort_header(app: &App, label: &str, sort: ProcessSort) -> String {
+    if app.process_sort == sort {
+        format!("{label} (▼)")
+    } else {
+        label.to_string()
+    }
+}
+
+/// Read/write rates as "1.2M/40K" (bytes per second); "-" when idle.
+fn format_io_rate(read: u64, write: u64) -> String {
+    fn short(bytes: u64) -> String {
+        match bytes {
+            0..1024 => format!("{bytes}"),
+            1024..1_048_576 => format!("{}K", bytes / 1024),
+            _ => format!("{:.1}M", bytes as f64 / 1_048_576.0),
+        }
+    }
+    if read == 0 && write == 0 {
+        return "-".to_string();
+    }
+    format!("{}/{}", short(read), short(write))
+}
+
+/// "512 B/s", "1.2 MB/s".
+fn format_byte_rate(bytes_per_sec: f64) -> String {
+    format!("{}/s", format_bytes(bytes_per_sec as u64))
+}
+
+/// "512 B", "3.4 KB", "1.2 MB", "7.9 GB" (binary multiples).
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// "812 MB" below 1 GB, "3.1 GB" above.
+fn format_memory_kb(kb: u64) -> String {
+    if kb >= 1024 * 1024 {
+        format!("{:.1} GB", kb as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{} MB", kb / 1024)
+    }
 }
 
 fn render_gpu_engines_view(f: &mut Frame, app: &App) {
+    if is_compact(f, app) {
+        render_gpu_engines_compact(f, app);
+        return;
+    }
+    // DLAs get their own section below the grid
+    let (dla, mut engines): (Vec<_>, Vec<_>) = app
+        .latest_stats
+        .engines
+        .iter()
+        .partition(|(name, _)| crate::parser::dla_unit(name).is_some());
+    engines.sort_by(|a, b| a.0.cmp(b.0));
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
         .constraints([
             Constraint::Length(3),  // Header
             Constraint::Min(0),     // Engines grid
+            Constraint::Length(if dla.is_empty() { 0 } else { 5 }), // DLA section
         ])
         .split(f.area());
 
     // Header
-    let border_color = get_rainbow_color(app.tick_count, 0);
-    let header = Paragraph::new("Vista de GPU Engines - Frecuencias y Uso")
+    let border_color = accent_color(app, 0);
+    let header = Paragraph::new(t!("ui.gpu.header"))
         .block(
             Block::default()
                 .borders(Borders::ALL)
@@ -805,14 +1818,9 @@ fn render_gpu_engines_view(f: &mut Frame, app: &App) {
         .style(Style::default().fg(Color::Cyan));
     f.render_widget(header, chunks[0]);
 
-    // Engines grid
-    let mut engines: Vec<(&String, &crate::parser::EngineStat)> =
-        app.latest_stats.engines.iter().collect();
-    engines.sort_by(|a, b| a.0.cmp(b.0));
-
     // Create grid layout
     let num_engines = engines.len();
-    let rows = (num_engines + 1) / 2; // 2 columns
+    let rows = num_engines.div_ceil(2); // 2 columns
     let mut constraints = vec![];
     for _ in 0..rows {
         constraints.push(Constraint::Length(5));
@@ -843,13 +1851,7 @@ fn render_gpu_engines_view(f: &mut Frame, app: &App) {
             .or_else(|| stat.raw_value.map(|v| v.to_string()))
             .unwrap_or_else(|| "-".to_string());
         
-        let color = if usage > 75 {
-            Color::Red
-        } else if usage > 50 {
-            Color::Yellow
-        } else {
-            Color::Green
-        };
+        let color = level_color(app, app.thresholds.engine_level(name, usage as f64));
         
         let gauge = Gauge::default()
             .block(
@@ -864,6 +1866,104 @@ fn render_gpu_engines_view(f: &mut Frame, app: &App) {
         
         f.render_widget(gauge, area);
     }
+
+    if !dla.is_empty() {
+        render_dla_section(f, app, chunks[2], &dla);
+    }
+}
+
+/// One gauge per DLA unit: core load, with the core and falcon clocks in the title.
+fn render_dla_section(f: &mut Frame, app: &App, area: Rect, dla: &[(&String, &crate::parser::EngineStat)]) {
+    let border_color = accent_color(app, 0);
+    let mut units: std::collections::BTreeMap<u32, [Option<&crate::parser::EngineStat>; 2]> =
+        std::collections::BTreeMap::new();
+    for (name, stat) in dla {
+        if let Some(unit) = crate::parser::dla_unit(name) {
+            let slot = if name.ends_with("_FALCON") { 1 } else { 0 };
+            units.entry(unit).or_default()[slot] = Some(stat);
+        }
+    }
+    let block = Block::default()
+        .title(t!("ui.gpu.dla_title"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let clock = |stat: Option<&crate::parser::EngineStat>| match stat.and_then(|s| s.freq_mhz.or(s.raw_value)) {
+        Some(mhz) => format!("{mhz} MHz"),
+        None => t!("ui.gpu.dla_off").to_string(),
+    };
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, units.len() as u32); units.len()])
+        .split(inner);
+    for ((unit, [core, falcon]), area) in units.iter().zip(columns.iter()) {
+        let usage = core.and_then(|s| s.usage_percent);
+        let color = level_color(app, app.thresholds.engine_level("DLA", usage.unwrap_or(0) as f64));
+        let gauge = Gauge::default()
+            .block(Block::default().title(t!(
+                "ui.gpu.dla_unit",
+                unit = unit,
+                core = clock(*core),
+                falcon = clock(*falcon)
+            )))
+            .gauge_style(Style::default().fg(color))
+            .ratio(usage.unwrap_or(0).min(100) as f64 / 100.0)
+            .label(usage.map(|u| format!("{u}%")).unwrap_or_else(|| "-".to_string()));
+        f.render_widget(gauge, *area);
+    }
+}
+
+/// One gauge row per engine, two columns when wide enough.
+fn render_gpu_engines_compact(f: &mut Frame, app: &App) {
+    let area = f.area();
+    let body = Rect {
+        height: area.height.saturating_sub(1),
+        ..area
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(body);
+    f.render_widget(
+        Paragraph::new(t!("ui.gpu.header_compact")).style(Style::default().fg(Color::Cyan)),
+        chunks[0],
+    );
+
+    let mut engines: Vec<(&String, &crate::parser::EngineStat)> =
+        app.latest_stats.engines.iter().collect();
+    engines.sort_by(|a, b| a.0.cmp(b.0));
+    let cols = if chunks[1].width >= 60 { 2 } else { 1 };
+    let rows = engines.len().div_ceil(cols);
+    let row_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Length(1); rows])
+        .split(chunks[1]);
+    for (i, (name, stat)) in engines.iter().enumerate() {
+        let Some(row) = row_chunks.get(i / cols) else {
+            break;
+        };
+        let col_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, cols as u32); cols])
+            .split(*row);
+        let usage = stat.usage_percent.unwrap_or(0);
+        let freq = stat
+            .freq_mhz
+            .or(stat.raw_value)
+            .map(|f| format!("{}M", f))
+            .unwrap_or_else(|| "-".to_string());
+        let color = level_color(app, app.thresholds.engine_level(name, usage as f64));
+        f.render_widget(
+            line_gauge(
+                format!("{:<6}{:>3}% {:>6} ", name, usage, freq),
+                usage as f64 / 100.0,
+                color,
+            ),
+            col_chunks[i % cols],
+        );
+    }
 }
 
 fn render_clocks_view(f: &mut Frame, app: &App) {
@@ -872,14 +1972,14 @@ fn render_clocks_view(f: &mut Frame, app: &App) {
         .margin(1)
         .constraints([
             Constraint::Length(3),  // Header
-            Constraint::Length(6),  // CPU clusters
+            Constraint::Length(cpu_row_height(app.history.cores.len())), // CPU clusters
             Constraint::Length(6),  // EMC/MC/AXI
             Constraint::Length(6),  // GPU/GR3D
             Constraint::Min(0),     // Controls/governors
         ])
         .split(f.area());
 
-    let border_color = get_rainbow_color(app.tick_count, 0);
+    let border_color = accent_color(app, 0);
     let header = Paragraph::new("Clocks & Governors")
         .block(
             Block::default()
@@ -895,8 +1995,8 @@ fn render_clocks_view(f: &mut Frame, app: &App) {
     let cpu_freqs: Vec<_> = app.latest_stats.cpus.iter().map(|c| c.freq_mhz.unwrap_or(0)).collect();
     let cpu_lines = vec![
         Line::from(format!("Cores: {}", app.latest_stats.cpus.len())),
-        Line::from(format!("Avg load: {:.1}%", if cpu_loads.is_empty() { 0.0 } else { cpu_loads.iter().sum::<u32>() as f64 / cpu_loads.len() as f64 })),
-        Line::from(format!("Max freq: {} MHz", cpu_freqs.iter().max().cloned().unwrap_or(0))),
+        Line::from(t!("ui.clocks.avg_load", load = format!("{:.1}", if cpu_loads.is_empty() { 0.0 } else { cpu_loads.iter().sum::<u32>() as f64 / cpu_loads.len() as f64 }))),
+        Line::from(t!("ui.clocks.max_freq", mhz = cpu_freqs.iter().max().cloned().unwrap_or(0))),
         Line::from(format!(
             "Governor: {}",
             app.control
@@ -913,7 +2013,12 @@ fn render_clocks_view(f: &mut Frame, app: &App) {
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(border_color)),
         );
-    f.render_widget(cpu_block, chunks[1]);
+    let cpu_row = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(30), Constraint::Min(0)])
+        .split(chunks[1]);
+    f.render_widget(cpu_block, cpu_row[0]);
+    render_core_history(f, cpu_row[1], app, border_color);
 
     // EMC/MC/AXI
     let mut emc_lines = Vec::new();
@@ -924,12 +2029,19 @@ fn render_clocks_view(f: &mut Frame, app: &App) {
             emc_lines.push(Line::from(format!("{name}: {usage}{freq}")));
         }
     }
+    if let Some(bw) = app.latest_stats.emc_bandwidth {
+        emc_lines.push(Line::from(t!(
+            "ui.clocks.emc_bandwidth",
+            gbps = format!("{:.1}", bw.gbps),
+            peak = format!("{:.1}", bw.peak_gbps)
+        )));
+    }
     if emc_lines.is_empty() {
-        emc_lines.push(Line::from("No EMC/MC/AXI data"));
+        emc_lines.push(Line::from(t!("ui.clocks.no_emc")));
     }
     let emc_block = Paragraph::new(emc_lines).block(
         Block::default()
-            .title("Memory/Bus Clocks")
+            .title(t!("ui.clocks.memory_title"))
             .borders(Borders::ALL)
             .border_style(Style::default().fg(border_color)),
     );
@@ -945,7 +2057,7 @@ fn render_clocks_view(f: &mut Frame, app: &App) {
         }
     }
     if eng_lines.is_empty() {
-        eng_lines.push(Line::from("No engine data"));
+        eng_lines.push(Line::from(t!("ui.clocks.no_engines")));
     }
     let eng_block = Paragraph::new(eng_lines).block(
         Block::default()
@@ -957,17 +2069,468 @@ fn render_clocks_view(f: &mut Frame, app: &App) {
 
     // Controls/governors summary
     let ctrl = app.control.status();
-    let ctrl_lines = vec![
+    let mut ctrl_lines = vec![
         Line::from(format!("jetson_clocks: {}", ctrl.jetson_clocks.map(|v| if v { "on" } else { "off" }).unwrap_or("n/a"))),
         Line::from(format!("nvpmodel: {}", ctrl.nvpmodel.clone().unwrap_or_else(|| "n/a".to_string()))),
-        Line::from(format!("fan: {}", ctrl.fan.clone().unwrap_or_else(|| "n/a".to_string()))),
-        Line::from(format!("supports: fan={} nvpmodel={} jetson_clocks={}", ctrl.supports_fan, ctrl.supports_nvpmodel, ctrl.supports_jetson_clocks)),
+        Line::from(format!(
+            "fan: {}{}",
+            ctrl.fan.clone().unwrap_or_else(|| "n/a".to_string()),
+            ctrl.fan_curve.as_ref().map(|c| format!(" ({})", t!("ui.clocks.fan_curve", curve = c))).unwrap_or_default(),
+        )),
+        Line::from(t!("ui.clocks.supports", fan = ctrl.supports_fan, nvpmodel = ctrl.supports_nvpmodel, jetson_clocks = ctrl.supports_jetson_clocks)),
+        Line::from(format!(
+            "power_cap: {}{}",
+            ctrl.clock_cap
+                .power_cap_watts
+                .map(|w| format!("{:.1}W", w))
+                .unwrap_or_else(|| "off".to_string()),
+            ctrl.clock_cap
+                .power_budget_mw
+                .map(|mw| format!(" (budget {:.1}W)", mw as f32 / 1000.0))
+                .unwrap_or_default()
+        )),
+        Line::from(format!(
+            "thermal: {}",
+            match (ctrl.clock_cap.thermal_target_c, ctrl.clock_cap.thermal_temp_c) {
+                (Some(target), Some(temp)) => format!("{:.1}C / {:.0}C", temp, target),
+                (Some(target), None) => t!("ui.clocks.target", target = format!("{:.0}", target)),
+                (None, _) => "off".to_string(),
+            }
+        )),
+        Line::from(format!(
+            "clock cap: {}",
+            match &ctrl.clock_cap.applied {
+                Some(applied) if ctrl.clock_cap.level > 0 => {
+                    t!("ui.clocks.cap_level", level = ctrl.clock_cap.level, max = ctrl.clock_cap.max_level, applied = applied)
+                }
+                _ => t!("ui.clocks.cap_none").to_string(),
+            }
+        )),
+        Line::from(format!(
+            "idle: {}",
+            if !ctrl.idle.enabled {
+                "off".to_string()
+            } else if ctrl.idle.idle {
+                t!("ui.clocks.idle_saving", profile = ctrl.idle.profile.clone().unwrap_or_default())
+            } else {
+                t!("ui.clocks.idle_active", secs = ctrl.idle.quiet_secs)
+            }
+        )),
     ];
+    // One line per hwmon fan, after the legacy "fan:" line
+    for (i, fan) in ctrl.fans.iter().enumerate() {
+        ctrl_lines.insert(
+            3 + i,
+            Line::from(format!(
+                "fan{} {}: {}{}{}",
+                fan.index,
+                fan.name,
+                fan.percent().map(|p| format!("{}%", p)).unwrap_or_else(|| "n/a".to_string()),
+                fan.rpm.map(|r| format!(" {} RPM", r)).unwrap_or_default(),
+                fan.curve.as_ref().map(|c| format!(" ({})", t!("ui.clocks.fan_curve", curve = c))).unwrap_or_default(),
+            )),
+        );
+    }
     let ctrl_block = Paragraph::new(ctrl_lines).block(
         Block::default()
-            .title("Controls")
+            .title(t!("ui.clocks.controls"))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color)),
+    );
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[4]);
+    f.render_widget(ctrl_block, bottom[0]);
+
+    // Frequency residency since start: busiest steps per domain
+    let mut res_lines = Vec::new();
+    for domain in &app.residency.domains {
+        let top: Vec<String> = domain
+            .busiest()
+            .iter()
+            .filter(|l| l.time_ms > 0)
+            .take(3)
+            .map(|l| format!("{}MHz {:.0}%", l.freq_mhz, l.percent))
+            .collect();
+        let summary = if top.is_empty() { "-".to_string() } else { top.join(" | ") };
+        res_lines.push(Line::from(format!("{}: {}", domain.domain, summary)));
+    }
+    if let Some(pm) = &app.residency.gpu_runtime {
+        res_lines.push(Line::from(format!("GPU rail-gated: {:.0}%", pm.suspended_percent)));
+    }
+    if res_lines.is_empty() {
+        res_lines.push(Line::from(t!("ui.clocks.no_residency")));
+    }
+    let res_block = Paragraph::new(res_lines).block(
+        Block::default()
+            .title(t!("ui.clocks.residency"))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color)),
+    );
+    f.render_widget(res_block, bottom[1]);
+}
+
+/// Per-core load charts per row of the Clocks view's grid.
+const CORE_GRID_COLUMNS: usize = 4;
+/// Title line plus two plot rows per core.
+const CORE_CELL_HEIGHT: u16 = 3;
+
+/// Height of the Clocks view's CPU row: the summary, or the core grid when taller.
+fn cpu_row_height(cores: usize) -> u16 {
+    let rows = cores.div_ceil(CORE_GRID_COLUMNS) as u16;
+    (rows * CORE_CELL_HEIGHT + 2).max(6)
+}
+
+/// One small load chart per core, so load moving between clusters shows over time.
+fn render_core_history(f: &mut Frame, area: Rect, app: &App, border_color: Color) {
+    let block = Block::default()
+        .title(t!("ui.clocks.core_history", window = app.history_window.label()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let cores = &app.history.cores;
+    if cores.is_empty() {
+        f.render_widget(Paragraph::new(t!("ui.clocks.no_core_history")), inner);
+        return;
+    }
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![
+            Constraint::Length(CORE_CELL_HEIGHT);
+            cores.len().div_ceil(CORE_GRID_COLUMNS)
+        ])
+        .split(inner);
+    for (r, (row, chunk)) in rows.iter().zip(cores.chunks(CORE_GRID_COLUMNS)).enumerate() {
+        let cells = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, CORE_GRID_COLUMNS as u32); CORE_GRID_COLUMNS])
+            .split(*row);
+        for (c, (cell, data)) in cells.iter().zip(chunk).enumerate() {
+            let idx = r * CORE_GRID_COLUMNS + c;
+            let data = window_ages(app, data);
+            let online = app
+                .latest_stats
+                .cpus
+                .get(idx)
+                .is_some_and(|c| c.load_percent.is_some());
+            let (label, color) = match data.last() {
+                Some((_, load)) if online => (
+                    format!("CPU{idx} {load:.0}%"),
+                    level_color(app, app.thresholds.cpu.level(*load)),
+                ),
+                _ => (format!("CPU{idx} off"), Color::DarkGray),
+            };
+            let series = TrendSeries {
+                name: label.clone(),
+                data,
+                color,
+                ceiling: 100.0,
+                unit: "%",
+                summary: None,
+            };
+            let block = Block::default().title(Span::styled(label, Style::default().fg(color)));
+            render_trend(f, *cell, app, block, &series);
+        }
+    }
+}
+
+fn render_temperatures_view(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Sensors + chart
+        ])
+        .split(f.area());
+
+    let border_color = accent_color(app, 0);
+    let header = Paragraph::new(t!("ui.temps.header"))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(border_color))
+                .title(t!("ui.temps.title")),
+        )
+        .style(Style::default().fg(Color::Cyan));
+    f.render_widget(header, chunks[0]);
+
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(24), Constraint::Min(0)])
+        .split(chunks[1]);
+
+    let selected = app.selected_temp_sensor();
+    let sensor_lines: Vec<Line> = app
+        .history
+        .temps
+        .iter()
+        .map(|(name, series)| {
+            let temp = series.back().map(|(_, t)| *t).unwrap_or_default();
+            let marker = if Some(name.as_str()) == selected { "▶ " } else { "  " };
+            let mut style = Style::default().fg(level_color(app, app.thresholds.temp_level(name, temp)));
+            if Some(name.as_str()) == selected {
+                style = style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+            }
+            Line::from(Span::styled(format!("{marker}{name:<12}{temp:>6.1}°C"), style))
+        })
+        .collect();
+    let sensors = Paragraph::new(sensor_lines).block(
+        Block::default()
+            .title(t!("ui.temps.sensors"))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color)),
+    );
+    f.render_widget(sensors, body[0]);
+
+    let Some(sensor) = selected else {
+        let empty = Paragraph::new(t!("ui.temps.none")).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(border_color)),
+        );
+        f.render_widget(empty, body[1]);
+        return;
+    };
+
+    // x runs from -window (left) to now (right)
+    let window_secs = app.history_window.duration_secs();
+    let now = std::time::Instant::now();
+    let points: Vec<(f64, f64)> = app.history.temps[sensor]
+        .iter()
+        .map(|(at, t)| (-now.duration_since(*at).as_secs_f64(), *t))
+        .filter(|(x, _)| -x <= window_secs as f64)
+        .collect();
+
+    // Guide lines: sysfs trip points when the board has them, else the color thresholds
+    let guides: Vec<(String, f64, Color)> =
+        match crate::trip_points::lookup(&app.trip_points, sensor) {
+            Some(trips) => [
+                (t!("ui.temps.passive"), trips.passive, Color::Yellow),
+                (t!("ui.temps.critical"), trips.critical, Color::Red),
+            ]
+            .into_iter()
+            .filter_map(|(label, c, color)| c.map(|c| (label.to_string(), c as f64, color)))
+            .collect(),
+            None => {
+                let band = app.thresholds.temp_band(sensor);
+                vec![
+                    (t!("ui.temps.warn").to_string(), band.warn, Color::Yellow),
+                    (t!("ui.temps.crit").to_string(), band.crit, Color::Red),
+                ]
+            }
+        };
+    let ceiling = points
+        .iter()
+        .map(|(_, t)| *t)
+        .chain(guides.iter().map(|(_, c, _)| *c))
+        .fold(100.0f64, |acc, t| acc.max(t + 5.0));
+    let guide_points: Vec<[(f64, f64); 2]> = guides
+        .iter()
+        .map(|(_, c, _)| [(-(window_secs as f64), *c), (0.0, *c)])
+        .collect();
+
+    let (marker, graph_type) = match app.graph_style {
+        GraphStyle::Braille => (Marker::Braille, GraphType::Line),
+        GraphStyle::Bars => (Marker::Bar, GraphType::Bar),
+    };
+    let mut datasets = vec![Dataset::default()
+        .name(sensor.to_string())
+        .marker(marker)
+        .graph_type(graph_type)
+        .style(Style::default().fg(Color::Cyan))
+        .data(&points)];
+    for ((label, celsius, color), line) in guides.iter().zip(guide_points.iter()) {
+        datasets.push(
+            Dataset::default()
+                .name(format!("{label} {celsius:.0}°C"))
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(*color))
+                .data(line),
+        );
+    }
+
+    let axis_style = Style::default().fg(Color::DarkGray);
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(format!("{sensor} [{}]", app.history_window.label()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(border_color)),
+        )
+        .x_axis(
+            Axis::default()
+                .bounds([-(window_secs as f64), 0.0])
+                .labels([window_secs, window_secs / 2, 0].map(ago_label))
+                .style(axis_style),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([0.0, ceiling])
+                .labels([0.0, ceiling / 2.0, ceiling].map(|v| format!("{v:.0}°C")))
+                .style(axis_style),
+        )
+        .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)));
+    f.render_widget(chart, body[1]);
+}
+
+/// Rail colors in the Power view, cycled in name order.
+const RAIL_COLORS: [Color; 6] = [
+    Color::Cyan,
+    Color::Magenta,
+    Color::Yellow,
+    Color::Green,
+    Color::Red,
+    Color::Blue,
+];
+
+fn render_power_view(f: &mut Frame, app: &App) {
+    let rails = app.history.rails.len() as u16;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3),         // Header
+            Constraint::Min(8),            // Rail power chart
+            Constraint::Length(rails + 3), // Energy table
+        ])
+        .split(f.area());
+
+    let border_color = accent_color(app, 0);
+    let header = Paragraph::new(t!("ui.power.header"))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(border_color))
+                .title(t!("ui.power.title")),
+        )
+        .style(Style::default().fg(Color::Cyan));
+    f.render_widget(header, chunks[0]);
+
+    // x runs from -window (left) to now (right)
+    let window_secs = app.history_window.duration_secs();
+    let now = std::time::Instant::now();
+    let series: Vec<(&String, Vec<(f64, f64)>)> = app
+        .history
+        .rails
+        .iter()
+        .map(|(name, points)| {
+            let data = points
+                .iter()
+                .map(|(at, w)| (-now.duration_since(*at).as_secs_f64(), *w))
+                .filter(|(x, _)| -x <= window_secs as f64)
+                .collect();
+            (name, data)
+        })
+        .collect();
+    let ceiling = series
+        .iter()
+        .flat_map(|(_, data)| data.iter().map(|(_, w)| *w))
+        .fold(1.0f64, f64::max)
+        * 1.1;
+    let marker = match app.graph_style {
+        GraphStyle::Braille => Marker::Braille,
+        GraphStyle::Bars => Marker::Bar,
+    };
+    let datasets: Vec<Dataset> = series
+        .iter()
+        .zip(RAIL_COLORS.iter().cycle())
+        .map(|((name, data), color)| {
+            Dataset::default()
+                .name(name.to_string())
+                .marker(marker)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(*color))
+                .data(data)
+        })
+        .collect();
+    let axis_style = Style::default().fg(Color::DarkGray);
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(format!("{} [{}]", t!("ui.power.chart"), app.history_window.label()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(border_color)),
+        )
+        .x_axis(
+            Axis::default()
+                .bounds([-(window_secs as f64), 0.0])
+                .labels([window_secs, window_secs / 2, 0].map(ago_label))
+                .style(axis_style),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([0.0, ceiling])
+                .labels([0.0, ceiling / 2.0, ceiling].map(|w| format!("{w:.1}W")))
+                .style(axis_style),
+        )
+        .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)));
+    f.render_widget(chart, chunks[1]);
+
+    // Energy per rail: since the TUI started, since the daemon started, daemon total
+    let header_style = Style::default()
+        .fg(Color::White)
+        .add_modifier(Modifier::BOLD);
+    let daemon = app.daemon_energy.as_ref();
+    let rows: Vec<Row> = app
+        .latest_stats
+        .power
+        .iter()
+        .collect::<std::collections::BTreeMap<_, _>>()
+        .into_iter()
+        .map(|(name, rail)| {
+            let daemon_mwh = daemon
+                .and_then(|d| d.since_start_wh.get(name))
+                .map(|wh| format!("{:.1}", wh * 1000.0))
+                .unwrap_or_else(|| "-".into());
+            let total_wh = daemon
+                .and_then(|d| d.total_wh.get(name))
+                .map(|wh| format!("{wh:.3}"))
+                .unwrap_or_else(|| "-".into());
+            Row::new(vec![
+                Span::styled(name.clone(), Style::default().fg(Color::Magenta)),
+                Span::raw(format!("{:.2}", rail.current_mw as f64 / 1000.0)),
+                Span::styled(
+                    format!("{:.2}", rail.average_mw as f64 / 1000.0),
+                    Style::default().fg(Color::Gray),
+                ),
+                Span::raw(format!("{:.1}", app.energy.rail_wh(name) * 1000.0)),
+                Span::raw(daemon_mwh),
+                Span::styled(total_wh, Style::default().fg(Color::Gray)),
+            ])
+        })
+        .collect();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(12),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(12),
+            Constraint::Length(14),
+            Constraint::Length(12),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "Rail".to_string(),
+            t!("ui.power.col.now").to_string(),
+            t!("ui.power.col.avg").to_string(),
+            t!("ui.power.col.tui").to_string(),
+            t!("ui.power.col.daemon").to_string(),
+            t!("ui.power.col.total").to_string(),
+        ])
+        .style(header_style),
+    )
+    .block(
+        Block::default()
+            .title(power_title(app))
             .borders(Borders::ALL)
             .border_style(Style::default().fg(border_color)),
     );
-    f.render_widget(ctrl_block, chunks[4]);
+    f.render_widget(table, chunks[2]);
 }
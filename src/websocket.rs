@@ -0,0 +1,117 @@
+//! Server side of the WebSocket protocol (RFC 6455), just enough to push
+//! JSON frames to browsers from the daemon's HTTP endpoint.
+//!
+//! tiny_http does the upgrade; this module computes the handshake answer and
+//! encodes outgoing frames. Server frames are never masked, so no client
+//! frame decoding is needed to stream.
+
+use base64::Engine;
+use std::io::{self, Write};
+use std::time::Duration;
+
+const GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+const OP_TEXT: u8 = 0x1;
+const OP_CLOSE: u8 = 0x8;
+
+const DEFAULT_INTERVAL_MS: u64 = 1000;
+const MIN_INTERVAL_MS: u64 = 100;
+const MAX_INTERVAL_MS: u64 = 60_000;
+
+/// `Sec-WebSocket-Accept` value for a client's `Sec-WebSocket-Key`.
+pub fn accept_key(key: &str) -> String {
+    let sha = sha1_smol::Sha1::from(format!("{}{GUID}", key.trim()));
+    base64::engine::general_purpose::STANDARD.encode(sha.digest().bytes())
+}
+
+fn frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(payload.len() + 10);
+    out.push(0x80 | opcode);
+    match payload.len() {
+        n if n < 126 => out.push(n as u8),
+        n if n <= u16::MAX as usize => {
+            out.push(126);
+            out.extend_from_slice(&(n as u16).to_be_bytes());
+        }
+        n => {
+            out.push(127);
+            out.extend_from_slice(&(n as u64).to_be_bytes());
+        }
+    }
+    out.extend_from_slice(payload);
+    out
+}
+
+/// One unfragmented text frame.
+pub fn text_frame(text: &str) -> Vec<u8> {
+    frame(OP_TEXT, text.as_bytes())
+}
+
+pub fn write_text(stream: &mut dyn Write, text: &str) -> io::Result<()> {
+    stream.write_all(&text_frame(text))?;
+    stream.flush()
+}
+
+/// Close frame with status 1000 (normal closure).
+pub fn write_close(stream: &mut dyn Write) -> io::Result<()> {
+    stream.write_all(&frame(OP_CLOSE, &1000u16.to_be_bytes()))?;
+    stream.flush()
+}
+
+/// Time between frames: `interval_ms` from the query string, else
+/// JETSONSCOPE_WS_INTERVAL_MS, else 1 s; clamped to 100 ms - 60 s.
+pub fn stream_interval(query: &str) -> Duration {
+    let from_query = query_param(query, "interval_ms").and_then(|v| v.parse::<u64>().ok());
+    let ms = from_query
+        .or_else(|| {
+            std::env::var("JETSONSCOPE_WS_INTERVAL_MS")
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+        })
+        .unwrap_or(DEFAULT_INTERVAL_MS)
+        .clamp(MIN_INTERVAL_MS, MAX_INTERVAL_MS);
+    Duration::from_millis(ms)
+}
+
+/// Value of `name` in a `a=1&b=2` query string.
+pub fn query_param(query: &str, name: &str) -> Option<String> {
+    serde_urlencoded::from_str::<Vec<(String, String)>>(query)
+        .ok()?
+        .into_iter()
+        .find(|(k, _)| k == name)
+        .map(|(_, v)| v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_the_handshake_and_frames_text() {
+        // Example from RFC 6455, section 1.3
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+
+        assert_eq!(text_frame("Hello"), b"\x81\x05Hello");
+        let medium = text_frame(&"x".repeat(300));
+        assert_eq!(&medium[..4], &[0x81, 126, 0x01, 0x2c]);
+        assert_eq!(medium.len(), 304);
+        let large = text_frame(&"x".repeat(70_000));
+        assert_eq!(&large[..2], &[0x81, 127]);
+        assert_eq!(u64::from_be_bytes(large[2..10].try_into().unwrap()), 70_000);
+
+        assert_eq!(
+            stream_interval("interval_ms=250"),
+            Duration::from_millis(250)
+        );
+        assert_eq!(
+            stream_interval("token=x&interval_ms=5"),
+            Duration::from_millis(100)
+        );
+        assert_eq!(
+            query_param("a=1&token=s%20t", "token").as_deref(),
+            Some("s t")
+        );
+    }
+}
@@ -1,12 +1,13 @@
 use jetsonscope::control::ControlManager;
-use jetsonscope::hardware::JetsonHardware;
+use jetsonscope::hardware::{parse_nvpmodel_conf, JetsonHardware};
 
 // Helpers to create ControlManager with mocked hardware detection.
 fn mock_hw(is_jetson: bool) -> JetsonHardware {
-    let mut hw = JetsonHardware::default();
-    hw.is_jetson = is_jetson;
-    hw.nvpmodel_modes = vec!["MODE_0".into(), "MODE_1".into()];
-    hw
+    JetsonHardware {
+        is_jetson,
+        nvpmodel_modes: vec!["MODE_0".into(), "MODE_1".into()],
+        ..Default::default()
+    }
 }
 
 #[test]
@@ -30,6 +31,8 @@ fn fan_set_valid_range_ok() {
 
 #[test]
 fn nvpmodel_invalid_mode_errors() {
+    // Other tests in this binary only match text shared by every catalog
+    jetsonscope::i18n::set_lang(jetsonscope::i18n::Lang::Es);
     let hw = mock_hw(true);
     let mut ctrl = ControlManager::mock(hw);
     ctrl.set_nvpmodel_mode(Some("INVALID".into()));
@@ -52,6 +55,158 @@ fn nvpmodel_valid_mode_ok() {
     assert_eq!(info.value, "MODE_1");
 }
 
+#[test]
+fn nvpmodel_options_carry_mode_caps() {
+    let mut hw = mock_hw(true);
+    hw.nvpmodel_caps = parse_nvpmodel_conf(
+        "< POWER_MODEL ID=0 NAME=MODE_0 >\nGPU MAX_FREQ -1\n\
+         < POWER_MODEL ID=1 NAME=MODE_1 >\nGPU MAX_FREQ 624750000\n",
+    );
+    let ctrl = ControlManager::mock(hw);
+    let info = ctrl.control_info("nvpmodel");
+    assert_eq!(info.option_details["MODE_0"], "sin limites");
+    assert_eq!(info.option_details["MODE_1"], "GPU<=624MHz");
+}
+
+#[test]
+fn frequency_controls_offer_devfreq_steps() {
+    use jetsonscope::hardware::FrequencyCaps;
+
+    let mut hw = mock_hw(true);
+    hw.frequencies = vec![FrequencyCaps::from_steps(
+        "GPU",
+        "17000000.gpu",
+        vec![306_000_000, 624_750_000, 918_000_000],
+    )
+    .unwrap()];
+    let mut ctrl = ControlManager::mock(hw);
+    let info = ctrl.control_info("gpu_max_freq");
+    assert_eq!(info.options, vec!["306", "624", "918"]);
+    assert_eq!((info.min, info.max, info.step), (Some(306), Some(918), None));
+    assert_eq!(info.frequencies_hz[1], 624_750_000);
+    assert_eq!(info.value, "918");
+
+    assert!(ctrl.apply_control("gpu_max_freq", "700").is_err());
+    assert_eq!(ctrl.apply_control("gpu_max_freq", "624").unwrap().value, "624");
+    assert_eq!(ctrl.status().max_freqs["GPU"], 624_750_000);
+    assert_eq!(ctrl.apply_control("gpu_max_freq", "max").unwrap().value, "918");
+    assert!(ctrl.apply_control("emc_max_freq", "max").is_err());
+}
+
+#[test]
+fn fans_get_their_own_controls_and_curves() {
+    use jetsonscope::fans::FanInfo;
+    use jetsonscope::parser::TegraStats;
+
+    let mut hw = mock_hw(true);
+    hw.fans = (0..2)
+        .map(|index| FanInfo {
+            index,
+            name: "pwm-fan".into(),
+            ..Default::default()
+        })
+        .collect();
+    let mut ctrl = ControlManager::mock(hw);
+    assert_eq!(ctrl.control_info("fan0").value, "0%");
+    assert!(ctrl.apply_control("fan1", "101").is_err());
+    assert_eq!(ctrl.apply_control("fan1", "60").unwrap().value, "60%");
+    assert_eq!(ctrl.control_info("fan0").value, "0%");
+    assert!(ctrl.apply_control("fan2", "50").is_err());
+
+    assert!(ctrl.apply_control("fan0_curve", "80:10,40:90").is_err());
+    ctrl.apply_control("fan0_curve", "40:20,80:100").unwrap();
+    let mut stats = TegraStats::default();
+    stats.temps.insert("tj".into(), 60.0);
+    stats.temps.insert("gpu".into(), -256.0);
+    ctrl.on_stats(&stats);
+    assert_eq!(ctrl.control_info("fan0").value, "60%");
+    assert_eq!(ctrl.control_info("fan0_curve").value, "40:20,80:100");
+    assert_eq!(ctrl.control_info("fan1").value, "60%");
+
+    // A fixed speed takes the fan off its curve
+    ctrl.apply_control("fan0", "30").unwrap();
+    assert_eq!(ctrl.control_info("fan0_curve").value, "off");
+    ctrl.on_stats(&stats);
+    assert_eq!(ctrl.control_info("fan0").value, "30%");
+}
+
+#[test]
+fn thermal_guard_maxes_every_fan_and_caps_through_the_arbiter() {
+    use jetsonscope::fans::FanInfo;
+    use jetsonscope::parser::TegraStats;
+    use jetsonscope::thermal_guard::GuardConfig;
+    use std::time::Duration;
+
+    let mut hw = mock_hw(true);
+    hw.fans = (0..2)
+        .map(|index| FanInfo {
+            index,
+            name: "pwm-fan".into(),
+            ..Default::default()
+        })
+        .collect();
+    let mut ctrl = ControlManager::mock(hw);
+    ctrl.configure_thermal_guard(GuardConfig {
+        critical_c: 95.0,
+        recover_c: 85.0,
+        hold: Duration::ZERO,
+        sensor: None,
+    });
+    ctrl.apply_control("fan0_curve", "40:20,100:60").unwrap();
+    ctrl.apply_control("fan1", "60").unwrap();
+
+    let mut hot = TegraStats::default();
+    hot.temps.insert("tj".into(), 97.0);
+    ctrl.on_stats(&hot);
+    ctrl.on_stats(&hot);
+    assert!(ctrl.status().guard.tripped);
+    assert_eq!(ctrl.status().clock_cap.level, 3);
+    // The curve would want ~58% here; the guard keeps both fans maxed
+    assert_eq!(ctrl.control_info("fan0").value, "100%");
+    assert_eq!(ctrl.control_info("fan1").value, "100%");
+
+    let mut cool = TegraStats::default();
+    cool.temps.insert("tj".into(), 80.0);
+    ctrl.on_stats(&cool);
+    assert!(!ctrl.status().guard.tripped);
+    assert_eq!(ctrl.status().clock_cap.level, 0);
+    assert_eq!(ctrl.control_info("fan0_curve").value, "40:20,100:60");
+    assert_eq!(ctrl.control_info("fan1").value, "60%");
+
+    // Shutting down while tripped undoes the trip as well
+    ctrl.on_stats(&hot);
+    assert!(ctrl.status().guard.tripped);
+    ctrl.release_automation();
+    assert!(!ctrl.status().guard.tripped);
+    assert_eq!(ctrl.status().clock_cap.level, 0);
+    assert_eq!(ctrl.control_info("fan1").value, "60%");
+}
+
+#[test]
+fn fan_curve_drives_the_fan_setpoint() {
+    use jetsonscope::parser::TegraStats;
+
+    let mut ctrl = ControlManager::mock(mock_hw(true));
+    assert!(ctrl.apply_control("fan_curve", "loud").is_err());
+    ctrl.apply_control("fan_curve", "aggressive").unwrap();
+    let mut stats = TegraStats::default();
+    stats.temps.insert("cpu".into(), 42.5);
+    stats.temps.insert("tj".into(), 57.5);
+    ctrl.on_stats(&stats);
+    assert_eq!(ctrl.control_info("fan").value, "85%");
+    assert_eq!(ctrl.control_info("fan_curve").value, "aggressive");
+
+    ctrl.apply_control("fan_curve", "40:20,80:100").unwrap();
+    ctrl.on_stats(&stats);
+    assert_eq!(ctrl.control_info("fan").value, "55%");
+
+    // The `f` hotkey and "set fan" take the fan off its curve
+    ctrl.set_fan(80);
+    assert_eq!(ctrl.control_info("fan_curve").value, "off");
+    ctrl.on_stats(&stats);
+    assert_eq!(ctrl.control_info("fan").value, "80%");
+}
+
 #[test]
 fn jetson_clocks_toggle_on_non_jetson_is_noop() {
     let hw = mock_hw(false);
@@ -63,3 +218,233 @@ fn jetson_clocks_toggle_on_non_jetson_is_noop() {
     // Value may remain default or toggle; accept on/off/unknown.
     assert!(matches!(info.value.as_str(), "on" | "off" | "unknown"));
 }
+
+#[test]
+fn power_cap_throttles_over_budget_and_disables() {
+    use jetsonscope::parser::{PowerRail, TegraStats};
+
+    let mut ctrl = ControlManager::mock(mock_hw(true));
+    assert_eq!(ctrl.control_info("power_cap_watts").value, "off");
+    assert!(ctrl.apply_control("power_cap_watts", "abc").is_err());
+    ctrl.apply_control("power_cap_watts", "10").unwrap();
+    assert_eq!(ctrl.control_info("power_cap_watts").value, "10.0");
+
+    let mut stats = TegraStats::default();
+    stats.power.insert(
+        "VDD_IN".into(),
+        PowerRail {
+            current_mw: 14_000,
+            average_mw: 14_000,
+        },
+    );
+    ctrl.on_stats(&stats);
+    // The next step waits until the averaging window has refilled
+    ctrl.on_stats(&stats);
+    assert_eq!(ctrl.status().clock_cap.level, 1);
+    assert!(ctrl.status().clock_cap.applied.as_deref().unwrap().contains("GPU<="));
+
+    ctrl.apply_control("power_cap_watts", "off").unwrap();
+    assert_eq!(ctrl.status().clock_cap.level, 0);
+    assert_eq!(ctrl.status().clock_cap.power_cap_watts, None);
+}
+
+#[test]
+fn power_budget_outlives_power_cap_changes() {
+    use jetsonscope::parser::{PowerRail, TegraStats};
+
+    let mut ctrl = ControlManager::mock(mock_hw(true));
+    ctrl.set_power_budget(12_000).unwrap();
+    let mut stats = TegraStats::default();
+    stats.power.insert(
+        "VDD_IN".into(),
+        PowerRail {
+            current_mw: 14_000,
+            average_mw: 14_000,
+        },
+    );
+    ctrl.on_stats(&stats);
+    assert_eq!(ctrl.status().clock_cap.level, 1);
+    assert_eq!(ctrl.status().clock_cap.power_budget_mw, Some(12_000));
+
+    // Setting or clearing power_cap_watts does not lift the budget's cap
+    ctrl.apply_control("power_cap_watts", "50").unwrap();
+    ctrl.apply_control("power_cap_watts", "off").unwrap();
+    assert_eq!(ctrl.status().clock_cap.level, 1);
+    assert_eq!(ctrl.control_info("power_cap_watts").value, "off");
+}
+
+#[test]
+fn release_automation_lifts_every_clock_cap() {
+    use jetsonscope::parser::{PowerRail, TegraStats};
+
+    let mut ctrl = ControlManager::mock(mock_hw(true));
+    let top = ctrl.status().cpu_max_freq_khz;
+    ctrl.set_power_budget(12_000).unwrap();
+    ctrl.apply_control("thermal_target_c", "85").unwrap();
+    let mut stats = TegraStats::default();
+    stats.temps.insert("tj".into(), 95.0);
+    stats.power.insert(
+        "VDD_IN".into(),
+        PowerRail {
+            current_mw: 14_000,
+            average_mw: 14_000,
+        },
+    );
+    ctrl.on_stats(&stats);
+    assert!(ctrl.status().clock_cap.level > 0);
+    assert!(ctrl.status().cpu_max_freq_khz < top);
+
+    // The board must not stay throttled once the daemon is gone
+    ctrl.release_automation();
+    assert_eq!(ctrl.status().clock_cap.level, 0);
+    assert_eq!(ctrl.status().cpu_max_freq_khz, top);
+}
+
+#[test]
+fn idle_profile_applies_and_restores() {
+    use jetsonscope::idle::{parse_profile, IdleConfig};
+    use jetsonscope::parser::TegraStats;
+    use std::time::Duration;
+
+    let mut ctrl = ControlManager::mock(mock_hw(true));
+    ctrl.configure_idle(IdleConfig {
+        profile: parse_profile("nvpmodel=MODE_1,fan=20"),
+        idle_after: Duration::ZERO,
+        cpu_percent: 10,
+        engine_percent: 10,
+    });
+    ctrl.set_fan(60);
+
+    ctrl.on_stats(&TegraStats::default());
+    assert!(ctrl.status().idle.idle);
+    assert_eq!(ctrl.control_info("nvpmodel").value, "MODE_1");
+    assert_eq!(ctrl.control_info("fan").value, "20%");
+    assert_eq!(ctrl.control_info("idle_profile").value, "idle");
+
+    let mut busy = TegraStats::default();
+    busy.cpus.push(jetsonscope::parser::CpuCore {
+        load_percent: Some(90),
+        freq_mhz: None,
+        cluster: None,
+    });
+    ctrl.on_stats(&busy);
+    assert!(!ctrl.status().idle.idle);
+    assert_eq!(ctrl.control_info("nvpmodel").value, "MODE_0");
+    assert_eq!(ctrl.control_info("fan").value, "60%");
+
+    // Shutting down while idle puts the profile back as well
+    ctrl.on_stats(&TegraStats::default());
+    assert_eq!(ctrl.control_info("fan").value, "20%");
+    ctrl.release_automation();
+    assert_eq!(ctrl.control_info("nvpmodel").value, "MODE_0");
+    assert_eq!(ctrl.control_info("fan").value, "60%");
+}
+
+#[test]
+fn thermal_target_shares_clock_caps_with_power_cap() {
+    use jetsonscope::parser::{PowerRail, TegraStats};
+
+    let mut ctrl = ControlManager::mock(mock_hw(true));
+    assert!(ctrl.apply_control("thermal_target_c", "200").is_err());
+    ctrl.apply_control("thermal_target_c", "85").unwrap();
+    ctrl.apply_control("power_cap_watts", "10").unwrap();
+
+    let mut stats = TegraStats::default();
+    stats.temps.insert("tj".into(), 88.0);
+    stats.power.insert(
+        "VDD_IN".into(),
+        PowerRail {
+            current_mw: 12_000,
+            average_mw: 12_000,
+        },
+    );
+    ctrl.on_stats(&stats);
+    ctrl.on_stats(&stats);
+    assert_eq!(ctrl.status().clock_cap.thermal_temp_c, Some(88.0));
+    assert_eq!(ctrl.status().clock_cap.level, 2);
+
+    // Dropping the power cap keeps the thermal request in force
+    ctrl.apply_control("power_cap_watts", "off").unwrap();
+    assert_eq!(ctrl.status().clock_cap.level, 2);
+    ctrl.apply_control("thermal_target_c", "off").unwrap();
+    assert_eq!(ctrl.status().clock_cap.level, 0);
+}
+
+#[test]
+fn clock_caps_keep_the_user_cpu_ceiling() {
+    use jetsonscope::parser::TegraStats;
+
+    let mut ctrl = ControlManager::mock(mock_hw(true));
+    ctrl.apply_control("cpu_max_freq", "1497").unwrap();
+    ctrl.apply_control("thermal_target_c", "85").unwrap();
+    let mut stats = TegraStats::default();
+    stats.temps.insert("tj".into(), 88.0);
+    ctrl.on_stats(&stats);
+    ctrl.on_stats(&stats);
+    assert_eq!(ctrl.status().clock_cap.level, 2);
+    assert_eq!(ctrl.status().cpu_max_freq_khz, Some(1_190_400));
+
+    // A floor above the cap step holds the cap there
+    ctrl.apply_control("cpu_min_freq", "1497").unwrap();
+    assert_eq!(ctrl.status().cpu_max_freq_khz, Some(1_497_600));
+    ctrl.apply_control("cpu_min_freq", "min").unwrap();
+    assert_eq!(ctrl.status().cpu_max_freq_khz, Some(1_190_400));
+
+    // Lifting the cap goes back to the user's ceiling, not the top step
+    ctrl.apply_control("thermal_target_c", "off").unwrap();
+    assert_eq!(ctrl.status().cpu_max_freq_khz, Some(1_497_600));
+    assert_eq!(ctrl.control_info("cpu_max_freq").value, "1497");
+}
+
+#[test]
+fn action_on_a_clone_is_adopted() {
+    let mut ctrl = ControlManager::mock(mock_hw(true));
+    ctrl.set_legacy_fan_curve("balanced").unwrap();
+    // What the TUI's control worker does
+    let mut worker = ctrl.clone();
+    let handle = std::thread::spawn(move || {
+        worker.toggle_jetson_clocks();
+        worker.set_nvpmodel_mode(Some("MODE_1".into()));
+        worker.set_fan(60);
+        worker
+    });
+    ctrl.adopt_action_result(handle.join().unwrap());
+    let status = ctrl.status();
+    assert_eq!(status.jetson_clocks, Some(true));
+    assert_eq!(status.nvpmodel.as_deref(), Some("MODE_1"));
+    assert_eq!(status.fan.as_deref(), Some("60%"));
+    assert!(status.fan_curve.is_none());
+}
+
+#[test]
+fn power_mode_changes_wait_for_confirmation() {
+    use jetsonscope::app::{App, AppOptions, ViewMode};
+    use jetsonscope::palette::PaletteCommand;
+
+    let mut app = App::new(AppOptions {
+        demo: true,
+        confirm_controls: true,
+        ..AppOptions::default()
+    });
+    app.request(PaletteCommand::Nvpmodel("MAXN".into()));
+    let pending = app.confirm.as_ref().expect("nvpmodel asks first");
+    assert!(pending.question.contains("MAXN"), "{}", pending.question);
+    app.resolve_confirm(false);
+    assert!(app.confirm.is_none());
+    assert!(app.control_pending.is_none());
+    assert!(app.notice.is_some());
+
+    // Fan changes ask too, from the 'f' key and the palette alike
+    app.request(PaletteCommand::Fan(80));
+    let pending = app.confirm.as_ref().expect("fan asks first");
+    assert!(pending.detail.contains("jetson_fan --set 80"), "{}", pending.detail);
+    app.resolve_confirm(false);
+    app.request(PaletteCommand::FanCurve("quiet".into()));
+    assert!(app.confirm.as_ref().is_some_and(|p| p.detail.contains("quiet")));
+    app.resolve_confirm(false);
+
+    // Views and other harmless actions run at once
+    app.request(PaletteCommand::View(ViewMode::Clocks));
+    assert!(app.confirm.is_none());
+    assert_eq!(app.view_mode, ViewMode::Clocks);
+}
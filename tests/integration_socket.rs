@@ -1,9 +1,9 @@
 use std::io::{Read, Write};
 use std::os::unix::net::UnixStream;
 use std::path::PathBuf;
-use jetsonscope::protocol::{Request, Response};
+use jetsonscope::protocol::{Connection, Request, Response};
 
-fn connect() -> Option<UnixStream> {
+fn socket_path() -> Option<PathBuf> {
     let socket_path = std::env::var("JETSONSCOPE_SOCKET_PATH")
         .or_else(|_| std::env::var("TEGRA_SOCKET_PATH"))
         .unwrap_or_else(|_| "/tmp/jetsonscope.sock".to_string());
@@ -17,6 +17,11 @@ fn connect() -> Option<UnixStream> {
             return None;
         }
     }
+    Some(path)
+}
+
+fn connect() -> Option<UnixStream> {
+    let path = socket_path()?;
     match UnixStream::connect(&path) {
         Ok(s) => Some(s),
         Err(e) => {
@@ -48,7 +53,7 @@ fn test_socket_stats_request() {
         Response::Stats { source, data } => {
             assert!(!source.is_empty(), "Source should not be empty");
             if let Some(stats) = data {
-                assert!(stats.cpus.len() > 0 || stats.ram.is_some());
+                assert!(!stats.cpus.is_empty() || stats.ram.is_some());
             }
         }
         _ => panic!("Expected Stats response"),
@@ -139,3 +144,72 @@ fn test_reconnect_after_close() {
     stream2.read_to_string(&mut buf2).expect("Failed to read response");
     let _resp2: Response = serde_json::from_str(&buf2).expect("Failed to parse response");
 }
+
+#[test]
+fn test_framed_requests_share_a_connection() {
+    let mut conn = match socket_path().map(|p| Connection::connect(&p, false)) {
+        Some(Ok(c)) => c,
+        _ => return,
+    };
+    conn.send(&Request::GetStats).expect("Failed to send");
+    conn.send(&Request::GetMeta).expect("Failed to send");
+    assert!(matches!(conn.recv().expect("Failed to read"), Response::Stats { .. }));
+    assert!(matches!(conn.recv().expect("Failed to read"), Response::Meta(_)));
+    assert!(matches!(
+        conn.request(&Request::ListControls).expect("Failed to read"),
+        Response::Controls(_)
+    ));
+}
+
+#[test]
+fn test_stats_history_is_oldest_first() {
+    let mut conn = match socket_path().map(|p| Connection::connect(&p, false)) {
+        Some(Ok(c)) => c,
+        _ => return,
+    };
+    let req = Request::GetHistory {
+        since_secs: 600,
+        max_points: 5,
+        full: true,
+    };
+    match conn.request(&req).expect("Failed to read") {
+        Response::StatsHistory(samples) => {
+            assert!(samples.len() <= 5);
+            assert!(samples.windows(2).all(|w| w[0].unix_ms < w[1].unix_ms));
+        }
+        _ => panic!("Expected StatsHistory response"),
+    }
+}
+
+#[test]
+fn test_version_matches_reply_protocol() {
+    let mut conn = match socket_path().map(|p| Connection::connect(&p, false)) {
+        Some(Ok(c)) => c,
+        _ => return,
+    };
+    match conn.request(&Request::GetVersion).expect("Failed to read") {
+        Response::Version(info) => {
+            assert!(!info.crate_version.is_empty());
+            assert_eq!(conn.protocol_version(), Some(info.protocol_version));
+        }
+        _ => panic!("Expected Version response"),
+    }
+}
+
+#[test]
+fn test_process_actions_need_a_token() {
+    let mut conn = match socket_path().map(|p| Connection::connect(&p, false)) {
+        Some(Ok(c)) => c,
+        _ => return,
+    };
+    // Even with no token configured on the daemon
+    let req = Request::SignalProcess {
+        pid: u32::MAX,
+        signal: "TERM".to_string(),
+        token: None,
+    };
+    match conn.request(&req).expect("Failed to read") {
+        Response::Error(err) => assert_eq!(err.code, "auth_failed"),
+        other => panic!("Expected auth_failed, got {other:?}"),
+    }
+}
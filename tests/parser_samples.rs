@@ -24,7 +24,7 @@ fn parses_power_and_engines_xavier() {
     let ram = stats.ram.as_ref().unwrap();
     assert_eq!(ram.total_bytes, SizeUnit::MB.to_bytes(7_771));
     assert!(stats.power.contains_key("VDD_IN"));
-    assert!(stats.engines.get("GR3D").is_some());
+    assert!(stats.engines.contains_key("GR3D"));
 }
 
 #[test]
@@ -44,3 +44,38 @@ fn parses_negative_temps_and_missing_values() {
     assert!(stats.temps.contains_key("CPU"));
     assert_eq!(stats.temps.get("GPU"), Some(&-256.0));
 }
+
+#[test]
+fn reference_samples_parse_strictly() {
+    // New JetPack releases that rename or add fields fail here first
+    for line in [
+        "RAM 4181/7771MB (lfb 8x4MB) SWAP 0/3885MB (cached 0MB) CPU [10%@1190,0%@1190,1%@1190,0%@1190,5%@1190,1%@1190] EMC_FREQ 15%@1600 GR3D_FREQ 0% PLL@42.906C Tdiode@43.25C Tboard@36C GPU@41.75C BCPU@42.5C MCPU@47.5C thermal@42.425C VDD_SYS_GPU 47mW/0mW VDD_SYS_SOC 813mW/207mW VDD_4V0_WIFI 495mW/0mW VDD_IN 3539mW/1422mW VDD_SYS_CPU 125mW/104mW",
+        "11-30-2025 13:26:01 RAM 2461/7620MB (lfb 3x2MB) SWAP 1243/3810MB (cached 5MB) CPU [19%@729,14%@729,22%@729,8%@729,15%@729,17%@729] EMC_FREQ 4%@2133 GR3D_FREQ 0%@[305] NVDEC off NVJPG off NVJPG1 off VIC off OFA off APE 200 cpu@46.531C soc2@47.312C soc0@46.593C gpu@48.218C tj@48.843C soc1@48.843C VDD_IN 5704mW/5704mW VDD_CPU_GPU_CV 831mW/831mW VDD_SOC 1624mW/1624mW",
+    ] {
+        if let Err(err) = TegraStats::parse_strict(line) {
+            panic!("{err}\n{line}");
+        }
+    }
+}
+
+#[test]
+fn parses_gb_and_fractional_sizes() {
+    let line = "RAM 12.3/61.4GB (lfb 2x4MB) SWAP 0.5/30.7GB (cached 0.1GB) CPU [3%@1728,5%@1728] EMC_FREQ 0%@3199 GR3D_FREQ 0%@[1300] tj@40.5C VDD_IN 9000mW/9000mW";
+    let stats = parse(line);
+    let ram = stats.ram.as_ref().unwrap();
+    assert_eq!(ram.unit, SizeUnit::GB);
+    assert_eq!(ram.used_bytes, SizeUnit::GB.fraction_to_bytes(12.3));
+    assert_eq!(ram.total_bytes, SizeUnit::GB.fraction_to_bytes(61.4));
+    assert!((stats.ram_ratio() - 12.3 / 61.4).abs() < 1e-9);
+    let swap = stats.swap.as_ref().unwrap();
+    assert_eq!(swap.total_bytes, SizeUnit::GB.fraction_to_bytes(30.7));
+    assert_eq!(swap.cached_bytes, Some(SizeUnit::GB.fraction_to_bytes(0.1)));
+    assert!(TegraStats::parse_strict(line).is_ok());
+
+    let nano = parse("RAM 2011/3956MB (lfb 4x2MB) SWAP 0/1978MB (cached 0MB) IRAM 10.5/252kB(lfb 241.5kB) CPU [15%@1479] GR3D_FREQ 0%@921");
+    let iram = nano.iram.as_ref().unwrap();
+    assert_eq!(iram.used_bytes, 10_752);
+    assert_eq!(iram.total_bytes, SizeUnit::KB.to_bytes(252));
+    assert_eq!(iram.lfb_bytes, Some(247_296));
+    assert_eq!(nano.ram.as_ref().unwrap().used_bytes, SizeUnit::MB.to_bytes(2_011));
+}